@@ -1,7 +1,8 @@
 //! Flatline CLI entry point.
 //!
-//! Provides `start`, `check`, and `update` subcommands for running the supervisor
-//! daemon, performing a single diagnostic check, or applying updates.
+//! Provides `start`, `check`, `update`, and `service` subcommands for running
+//! the supervisor daemon, performing a single diagnostic check, applying
+//! updates, or installing the supervisor as a systemd/launchd service.
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
@@ -15,10 +16,13 @@ use tracing::{debug, info, warn};
 use flatline::config::{flatline_paths, load_flatline_config};
 use flatline::db::StateDb;
 use flatline::reporter::Reporter;
-use flatline::stats::StatsEngine;
+use flatline::services::{self, Service};
+use flatline::stats::{open_usage_ledger, StatsEngine};
 use flatline::updater::{self, Updater};
 use flatline::watcher::Watcher;
 use flatline::{diagnosis, fixer, patterns};
+use wintermute::agent::budget::BudgetTimezone;
+use wintermute::service::ServicePaths;
 
 /// Flatline — supervisor process for the Wintermute AI agent.
 #[derive(Parser)]
@@ -42,6 +46,23 @@ enum Command {
         #[arg(long)]
         check: bool,
     },
+    /// Install, remove or inspect the systemd/launchd service.
+    Service {
+        /// Service operation.
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
+}
+
+/// Service subcommands.
+#[derive(Subcommand)]
+enum ServiceAction {
+    /// Generate the service file, then enable and start it.
+    Install,
+    /// Stop the service and remove its service file.
+    Uninstall,
+    /// Show whether the Wintermute and Flatline services are installed and running.
+    Status,
 }
 
 #[tokio::main]
@@ -52,6 +73,7 @@ async fn main() -> anyhow::Result<()> {
         Command::Start => handle_start().await,
         Command::Check => handle_check().await,
         Command::Update { check } => handle_update(check).await,
+        Command::Service { action } => handle_service(action).await,
     }
 }
 
@@ -76,7 +98,10 @@ async fn handle_start() -> anyhow::Result<()> {
 
     // Set up production logging (JSON file + stderr).
     let logs_dir = fl_paths.root.join("logs");
-    let _logging_guard = wintermute::logging::init_production(&logs_dir)?;
+    let _logging_guard = wintermute::logging::init_production(
+        &logs_dir,
+        &wintermute::config::TelemetryConfig::default(),
+    )?;
 
     // Load configs.
     let config = load_flatline_config(&flatline_config_path)
@@ -109,7 +134,7 @@ async fn handle_start() -> anyhow::Result<()> {
     let mut watcher = Watcher::new(log_dir, wm_paths.health_json.clone());
 
     // Create StatsEngine.
-    let stats = StatsEngine::new(Arc::clone(&db));
+    let stats = build_stats(&db, &wm_paths.memory_db, Some(&wm_config.budget)).await;
 
     // Create Telegram Reporter.
     let bot_token = credentials
@@ -582,6 +607,62 @@ async fn handle_update(check_only: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Install, remove or inspect the Flatline service.
+async fn handle_service(action: ServiceAction) -> anyhow::Result<()> {
+    wintermute::logging::init_cli();
+
+    let manager = wintermute::service::resolve_manager()?;
+    match action {
+        ServiceAction::Install => {
+            let paths = ServicePaths::for_current_exe()?;
+            let path = services::install_service(manager, Service::Flatline, &paths).await?;
+            info!(
+                path = %path.display(),
+                binary = %paths.binary.display(),
+                "flatline service installed and started"
+            );
+        }
+        ServiceAction::Uninstall => {
+            if services::uninstall_service(manager, Service::Flatline).await? {
+                info!("flatline service stopped and removed");
+            } else {
+                info!("flatline service is not installed");
+            }
+        }
+        ServiceAction::Status => {
+            for service in [Service::Agent, Service::Flatline] {
+                let path = wintermute::service::service_file(manager, service)?;
+                info!(
+                    service = service.name(),
+                    manager = ?manager,
+                    path = %path.display(),
+                    installed = path.exists(),
+                    running = services::is_running(manager, service).await,
+                    "service status"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build the stats engine, reading token usage from Wintermute's usage
+/// ledger and counting budget days in Wintermute's budget timezone.
+async fn build_stats(
+    db: &Arc<StateDb>,
+    memory_db: &std::path::Path,
+    budget: Option<&wintermute::config::BudgetConfig>,
+) -> StatsEngine {
+    let mut stats = StatsEngine::new(Arc::clone(db));
+    if let Some(ledger) = open_usage_ledger(memory_db).await {
+        stats = stats.with_usage_ledger(ledger);
+    }
+    if let Some(timezone) = budget.and_then(|b| BudgetTimezone::parse(&b.timezone)) {
+        stats = stats.with_budget_timezone(timezone);
+    }
+    stats
+}
+
 /// Run a single diagnostic check and exit.
 async fn handle_check() -> anyhow::Result<()> {
     wintermute::logging::init_cli();
@@ -600,7 +681,13 @@ async fn handle_check() -> anyhow::Result<()> {
 
     // Open state database (for stats queries).
     let db = Arc::new(StateDb::open(&fl_paths.state_db).await?);
-    let stats = StatsEngine::new(Arc::clone(&db));
+    let wm_config = wintermute::config::load_default_config().ok();
+    let stats = build_stats(
+        &db,
+        &wm_paths.memory_db,
+        wm_config.as_ref().map(|c| &c.budget),
+    )
+    .await;
 
     // Step 1: Read health.
     let health = match watcher.read_health() {
@@ -317,7 +317,7 @@ async fn check_budget_exhaustion(
     let threshold = config.thresholds.budget_burn_rate_alert;
 
     let limit = report.budget_today.limit;
-    let used = report.budget_today.used;
+    let used = stats.tokens_used_today(report).await;
 
     if limit == 0 {
         return None;
@@ -326,7 +326,7 @@ async fn check_budget_exhaustion(
     #[allow(clippy::cast_precision_loss)]
     let usage_fraction = used as f64 / limit as f64;
 
-    let day_fraction = crate::stats::day_fraction_elapsed();
+    let day_fraction = crate::stats::day_fraction_elapsed(stats.budget_timezone());
 
     // Fire if >80% budget used AND less than 25% of day has passed.
     if usage_fraction > threshold && day_fraction < 0.25 {
@@ -2,6 +2,8 @@
 //!
 //! Provides cross-platform service stop/start/install for Wintermute
 //! and Flatline services, used by the `flatline update` CLI command.
+//! Detection and service file locations come from [`wintermute::service`],
+//! which also backs `flatline service install`.
 //! All `std::process::Command` invocations use hardcoded arguments only.
 
 use std::path::{Path, PathBuf};
@@ -9,75 +11,12 @@ use std::path::{Path, PathBuf};
 use anyhow::Context;
 use tracing::{debug, info, warn};
 
-// -- Constants: service file names --
-
-/// macOS launchd plist for the Wintermute agent.
-const LAUNCHD_AGENT_PLIST: &str = "com.wintermute.agent.plist";
-
-/// macOS launchd plist for the Flatline supervisor.
-const LAUNCHD_FLATLINE_PLIST: &str = "com.wintermute.flatline.plist";
-
-/// Linux systemd unit for the Wintermute agent.
-const SYSTEMD_AGENT_UNIT: &str = "wintermute.service";
-
-/// Linux systemd unit for the Flatline supervisor.
-const SYSTEMD_FLATLINE_UNIT: &str = "flatline.service";
-
-/// Detected service manager on the current platform.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ServiceManager {
-    /// macOS launchd (`~/Library/LaunchAgents/`).
-    Launchd,
-    /// Linux systemd user units (`~/.config/systemd/user/`).
-    Systemd,
-}
-
-/// Resolve the macOS LaunchAgents directory (`~/Library/LaunchAgents/`).
-///
-/// # Errors
-///
-/// Returns an error if the home directory cannot be determined.
-fn launchd_agents_dir() -> anyhow::Result<PathBuf> {
-    let home = directories::BaseDirs::new()
-        .ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
-    Ok(home.home_dir().join("Library/LaunchAgents"))
-}
-
-/// Resolve the Linux systemd user units directory (`~/.config/systemd/user/`).
-///
-/// # Errors
-///
-/// Returns an error if the home directory cannot be determined.
-fn systemd_user_dir() -> anyhow::Result<PathBuf> {
-    let home = directories::BaseDirs::new()
-        .ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
-    Ok(home.home_dir().join(".config/systemd/user"))
-}
-
-/// Detect which service manager is active based on installed service files.
-///
-/// Checks for the presence of Wintermute service files in the platform's
-/// standard service directory. Returns `None` if no service files are
-/// installed (user runs processes manually).
-pub fn detect() -> Option<ServiceManager> {
-    if cfg!(target_os = "macos") {
-        if let Ok(dir) = launchd_agents_dir() {
-            if dir.join(LAUNCHD_AGENT_PLIST).exists() {
-                return Some(ServiceManager::Launchd);
-            }
-        }
-    }
-
-    if cfg!(target_os = "linux") {
-        if let Ok(dir) = systemd_user_dir() {
-            if dir.join(SYSTEMD_AGENT_UNIT).exists() {
-                return Some(ServiceManager::Systemd);
-            }
-        }
-    }
-
-    None
-}
+pub use wintermute::service::{detect, Service, ServiceManager};
+use wintermute::service::{
+    launchd_agents_dir, remove_service_file, service_file, systemd_user_dir, write_service_file,
+    ServicePaths, LAUNCHD_AGENT_PLIST, LAUNCHD_FLATLINE_PLIST, SYSTEMD_AGENT_UNIT,
+    SYSTEMD_FLATLINE_UNIT,
+};
 
 /// Stop both Wintermute and Flatline services.
 ///
@@ -179,6 +118,95 @@ pub async fn start_services(manager: ServiceManager) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Generate the service file for `service`, then load and start it.
+///
+/// Reinstalling replaces the file and restarts the service.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or the service manager
+/// rejects it.
+pub async fn install_service(
+    manager: ServiceManager,
+    service: Service,
+    paths: &ServicePaths,
+) -> anyhow::Result<PathBuf> {
+    let existing = service_file(manager, service)?;
+    if manager == ServiceManager::Launchd && existing.exists() {
+        // launchd keeps the old definition until the job is unloaded.
+        launchctl_unload(&existing).await;
+    }
+
+    let path = write_service_file(manager, service, paths)?;
+    match manager {
+        ServiceManager::Launchd => launchctl_load(&path).await?,
+        ServiceManager::Systemd => {
+            systemctl_daemon_reload().await?;
+            systemctl_enable(service.file_name(manager)).await?;
+        }
+    }
+    Ok(path)
+}
+
+/// Stop `service` and remove its service file.
+///
+/// Returns `false` when no service file was installed.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be removed or systemd cannot reload.
+pub async fn uninstall_service(manager: ServiceManager, service: Service) -> anyhow::Result<bool> {
+    let path = service_file(manager, service)?;
+    match manager {
+        ServiceManager::Launchd => {
+            if path.exists() {
+                launchctl_unload(&path).await;
+            }
+        }
+        ServiceManager::Systemd => systemctl_action("stop", service.file_name(manager)).await,
+    }
+
+    let removed = remove_service_file(manager, service)?;
+    if removed && manager == ServiceManager::Systemd {
+        systemctl_daemon_reload().await?;
+    }
+    Ok(removed)
+}
+
+/// Whether the service manager reports `service` as running.
+///
+/// Any failure to query the service manager counts as not running.
+pub async fn is_running(manager: ServiceManager, service: Service) -> bool {
+    match manager {
+        ServiceManager::Launchd => {
+            let label = service.label();
+            let output = tokio::task::spawn_blocking(move || {
+                std::process::Command::new("launchctl")
+                    .args(["list", label])
+                    .stderr(std::process::Stdio::null())
+                    .output()
+            })
+            .await;
+            // `launchctl list <label>` includes a "PID" entry while the job runs.
+            matches!(output, Ok(Ok(out))
+                if out.status.success()
+                    && String::from_utf8_lossy(&out.stdout).contains("\"PID\""))
+        }
+        ServiceManager::Systemd => {
+            let unit = service.file_name(manager);
+            let status = tokio::task::spawn_blocking(move || {
+                std::process::Command::new("systemctl")
+                    .args(["--user", "is-active", "--quiet", unit])
+                    .stdout(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .status()
+            })
+            .await;
+            matches!(status, Ok(Ok(status)) if status.success())
+        }
+    }
+}
+
 // -- Private helpers --
 
 /// Copy a single file into a destination directory.
@@ -329,6 +357,37 @@ async fn systemctl_start(unit: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Run `systemctl --user enable --now <unit>`.
+///
+/// # Errors
+///
+/// Returns an error if the command fails.
+async fn systemctl_enable(unit: &str) -> anyhow::Result<()> {
+    let unit_owned = unit.to_owned();
+    info!(unit = %unit, "enabling systemd service");
+
+    let status = tokio::task::spawn_blocking(move || {
+        std::process::Command::new("systemctl")
+            .args(["--user", "enable", "--now", &unit_owned])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .status()
+    })
+    .await
+    .context("systemctl enable task panicked")?
+    .context("failed to run systemctl enable")?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "systemctl --user enable --now {} failed with exit code {:?}",
+            unit,
+            status.code()
+        );
+    }
+
+    Ok(())
+}
+
 /// Run `systemctl --user daemon-reload`.
 ///
 /// # Errors
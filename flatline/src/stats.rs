@@ -2,12 +2,19 @@
 //!
 //! Aggregates `LogEvent` data into hourly buckets stored in the state database,
 //! and provides query methods for failure rates and budget burn analysis.
+//! When Wintermute's memory database is attached, today's token usage is read
+//! from its usage ledger instead of the in-memory counter in `health.json`.
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::Context;
 use chrono::Timelike;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use tracing::debug;
+use wintermute::agent::budget::BudgetTimezone;
 use wintermute::heartbeat::health::HealthReport;
 
 use crate::db::StateDb;
@@ -16,12 +23,57 @@ use crate::watcher::LogEvent;
 /// Aggregates tool execution events and queries derived statistics.
 pub struct StatsEngine {
     db: Arc<StateDb>,
+    usage_ledger: Option<SqlitePool>,
+    budget_timezone: BudgetTimezone,
 }
 
 impl StatsEngine {
     /// Create a new stats engine backed by the given state database.
     pub fn new(db: Arc<StateDb>) -> Self {
-        Self { db }
+        Self {
+            db,
+            usage_ledger: None,
+            budget_timezone: BudgetTimezone::Utc,
+        }
+    }
+
+    /// Count budget days in `timezone` (Wintermute's `[budget] timezone`)
+    /// instead of UTC.
+    #[must_use]
+    pub fn with_budget_timezone(mut self, timezone: BudgetTimezone) -> Self {
+        self.budget_timezone = timezone;
+        self
+    }
+
+    /// Timezone in which the budget day starts.
+    pub fn budget_timezone(&self) -> BudgetTimezone {
+        self.budget_timezone
+    }
+
+    /// Read token usage from Wintermute's usage ledger in this pool.
+    #[must_use]
+    pub fn with_usage_ledger(mut self, pool: SqlitePool) -> Self {
+        self.usage_ledger = Some(pool);
+        self
+    }
+
+    /// Tokens used since the start of the budget day.
+    ///
+    /// Sums the usage ledger when one is attached. Falls back to the
+    /// counter reported in `health.json` when there is no ledger or the
+    /// query fails (e.g. the migration has not been applied yet).
+    pub async fn tokens_used_today(&self, health: &HealthReport) -> u64 {
+        let Some(pool) = self.usage_ledger.as_ref() else {
+            return health.budget_today.used;
+        };
+        let day_start = self.budget_timezone.today_start(chrono::Utc::now());
+        match wintermute::memory::usage::tokens_since(pool, day_start).await {
+            Ok(used) => used,
+            Err(e) => {
+                debug!(error = %e, "usage ledger unavailable, using health.json budget");
+                health.budget_today.used
+            }
+        }
     }
 
     /// Ingest a batch of log events, aggregating tool_call events into hourly buckets.
@@ -137,12 +189,12 @@ impl StatsEngine {
     /// Calculate the budget burn rate as a ratio.
     ///
     /// Compares the fraction of daily budget already used against the fraction
-    /// of the day elapsed (UTC). A value > 1.0 means the budget is being
+    /// of the budget day elapsed. A value > 1.0 means the budget is being
     /// consumed faster than uniform daily pace.
     ///
     /// Returns 0.0 if the budget limit is zero.
     pub async fn budget_burn_rate(&self, health: &HealthReport) -> f64 {
-        let used = health.budget_today.used;
+        let used = self.tokens_used_today(health).await;
         let limit = health.budget_today.limit;
 
         if limit == 0 {
@@ -152,7 +204,7 @@ impl StatsEngine {
         #[allow(clippy::cast_precision_loss)]
         let budget_fraction = used as f64 / limit as f64;
 
-        let day_fraction = day_fraction_elapsed();
+        let day_fraction = day_fraction_elapsed(self.budget_timezone);
         if day_fraction <= 0.0 {
             return budget_fraction;
         }
@@ -161,15 +213,38 @@ impl StatsEngine {
     }
 }
 
-/// Fraction of the current UTC day that has elapsed (0.0 to 1.0).
+/// Open Wintermute's memory database read-only for usage ledger queries.
+///
+/// Returns `None` if the file does not exist or cannot be opened; callers
+/// then fall back to the budget reported in `health.json`.
+pub async fn open_usage_ledger(memory_db: &Path) -> Option<SqlitePool> {
+    if !memory_db.exists() {
+        return None;
+    }
+    let options = SqliteConnectOptions::new()
+        .filename(memory_db)
+        .read_only(true);
+    match SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+    {
+        Ok(pool) => Some(pool),
+        Err(e) => {
+            debug!(error = %e, path = %memory_db.display(), "cannot open usage ledger");
+            None
+        }
+    }
+}
+
+/// Fraction of the current budget day in `timezone` that has elapsed (0.0 to 1.0).
 ///
-/// Returns 0.0 at the very start of the day (midnight UTC).
-pub fn day_fraction_elapsed() -> f64 {
+/// Returns 0.0 at the very start of the day (midnight).
+pub fn day_fraction_elapsed(timezone: BudgetTimezone) -> f64 {
     let now = chrono::Utc::now();
-    let seconds_into_day = i64::from(now.hour())
-        .saturating_mul(3600)
-        .saturating_add(i64::from(now.minute()).saturating_mul(60))
-        .saturating_add(i64::from(now.second()));
+    let seconds_into_day = now
+        .signed_duration_since(timezone.today_start(now))
+        .num_seconds();
 
     const SECONDS_PER_DAY: i64 = 86400;
 
@@ -178,7 +253,7 @@ pub fn day_fraction_elapsed() -> f64 {
     }
 
     #[allow(clippy::cast_precision_loss)]
-    let fraction = seconds_into_day as f64 / SECONDS_PER_DAY as f64;
+    let fraction = seconds_into_day.min(SECONDS_PER_DAY) as f64 / SECONDS_PER_DAY as f64;
     fraction
 }
 
@@ -1,5 +1,6 @@
 //! Tests for the 8 known failure pattern detectors.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use flatline::config::FlatlineConfig;
@@ -35,6 +36,7 @@ fn make_health_report() -> HealthReport {
             limit: 5_000_000,
         },
         last_error: None,
+        components: BTreeMap::new(),
     }
 }
 
@@ -1,5 +1,6 @@
 //! Tests for the rolling statistics engine.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use flatline::db::StateDb;
@@ -188,6 +189,50 @@ async fn budget_burn_rate_positive() {
     assert!(rate > 0.0);
 }
 
+async fn ledger_pool(migrate: bool) -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("pool should connect");
+    if migrate {
+        sqlx::raw_sql(include_str!("../../migrations/016_usage_ledger.sql"))
+            .execute(&pool)
+            .await
+            .expect("016 should apply");
+    }
+    pool
+}
+
+#[tokio::test]
+async fn tokens_used_today_reads_usage_ledger() {
+    let (engine, _db, _dir) = setup().await;
+    let pool = ledger_pool(true).await;
+    sqlx::query(
+        "INSERT INTO usage_ledger (model, input_tokens, output_tokens, latency_ms, success, created_at) \
+         VALUES ('a/b', 700, 300, 10, 1, datetime('now')), \
+                ('a/b', 5000, 0, 10, 1, datetime('now', '-2 days'))",
+    )
+    .execute(&pool)
+    .await
+    .expect("insert should succeed");
+    let engine = engine.with_usage_ledger(pool);
+
+    let health = make_health_report(1, 100_000);
+    assert_eq!(engine.tokens_used_today(&health).await, 1000);
+}
+
+#[tokio::test]
+async fn tokens_used_today_falls_back_to_health() {
+    let (engine, _db, _dir) = setup().await;
+    let health = make_health_report(42, 100_000);
+    assert_eq!(engine.tokens_used_today(&health).await, 42);
+
+    // A database without the ledger table behaves like no ledger.
+    let engine = engine.with_usage_ledger(ledger_pool(false).await);
+    assert_eq!(engine.tokens_used_today(&health).await, 42);
+}
+
 fn make_health_report(used: u64, limit: u64) -> HealthReport {
     HealthReport {
         status: "running".to_owned(),
@@ -201,5 +246,6 @@ fn make_health_report(used: u64, limit: u64) -> HealthReport {
         dynamic_tools_count: 0,
         budget_today: BudgetReport { used, limit },
         last_error: None,
+        components: BTreeMap::new(),
     }
 }
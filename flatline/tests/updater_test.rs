@@ -53,6 +53,7 @@ fn make_health(active_sessions: usize) -> wintermute::heartbeat::health::HealthR
             limit: 5_000_000,
         },
         last_error: None,
+        components: std::collections::BTreeMap::new(),
     }
 }
 
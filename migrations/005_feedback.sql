@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS turn_feedback (
+    turn_id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    memory_ids TEXT NOT NULL DEFAULT '[]',
+    tools_used TEXT NOT NULL DEFAULT '[]',
+    rating INTEGER CHECK(rating IN (-1, 1)),
+    comment TEXT,
+    processed BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    rated_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_feedback_session ON turn_feedback(session_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_feedback_pending ON turn_feedback(processed, rating);
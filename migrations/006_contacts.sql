@@ -0,0 +1,11 @@
+ALTER TABLE contacts ADD COLUMN telegram_handle TEXT;
+
+CREATE TABLE IF NOT EXISTS contact_aliases (
+    alias TEXT PRIMARY KEY COLLATE NOCASE,
+    contact_id INTEGER NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_contacts_phone ON contacts(phone);
+CREATE INDEX IF NOT EXISTS idx_contacts_telegram ON contacts(telegram_handle);
+CREATE INDEX IF NOT EXISTS idx_contact_aliases_contact ON contact_aliases(contact_id);
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS outbound_queue (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    brief_id TEXT,
+    session_id TEXT NOT NULL,
+    channel TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    message_text TEXT NOT NULL,
+    deliver_at TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued'
+        CHECK(status IN ('queued', 'sent', 'failed', 'cancelled')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    sent_at TEXT
+);
+
+ALTER TABLE outbound_log ADD COLUMN delivery TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_outbound_queue_due ON outbound_queue(status, deliver_at);
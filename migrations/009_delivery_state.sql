@@ -0,0 +1,7 @@
+ALTER TABLE outbound_log ADD COLUMN message_id TEXT;
+ALTER TABLE outbound_log ADD COLUMN delivery_state TEXT
+    CHECK(delivery_state IN ('sent', 'delivered', 'read', 'failed'));
+ALTER TABLE outbound_log ADD COLUMN state_updated_at TEXT;
+ALTER TABLE outbound_log ADD COLUMN unread_alerted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_outbound_message_id ON outbound_log(message_id);
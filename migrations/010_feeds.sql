@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS feeds (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL UNIQUE,
+    title TEXT,
+    priority TEXT NOT NULL DEFAULT 'normal'
+        CHECK(priority IN ('normal', 'high')),
+    last_polled_at TEXT,
+    last_error TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS feed_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    feed_id INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    guid TEXT NOT NULL,
+    title TEXT NOT NULL,
+    link TEXT,
+    summary TEXT,
+    published_at TEXT,
+    seen_at TEXT NOT NULL DEFAULT (datetime('now')),
+    digested_at TEXT,
+    UNIQUE(feed_id, guid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_feed_entries_pending ON feed_entries(digested_at, feed_id);
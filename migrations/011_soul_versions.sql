@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS soul_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    content TEXT NOT NULL,
+    status TEXT NOT NULL
+        CHECK(status IN ('active', 'pending', 'rejected', 'superseded')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    decided_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_soul_versions_status ON soul_versions(status);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS memory_audit (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    memory_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK(action IN ('remember', 'forget')),
+    content TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_audit_memory ON memory_audit(memory_id);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS tool_network_grants (
+    tool_name TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    approved_by TEXT NOT NULL,
+    granted_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (tool_name, domain)
+);
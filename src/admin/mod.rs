@@ -0,0 +1,316 @@
+//! Localhost HTTP admin API.
+//!
+//! An optional REST API for dashboards and scripts, enabled with
+//! `[admin_api]` in `config.toml`. It binds to a loopback address only and
+//! every request needs `Authorization: Bearer <token>`, where the token is
+//! read from `.env` (`WINTERMUTE_ADMIN_TOKEN` by default).
+//!
+//! Endpoints:
+//! - `GET /api/health` — the latest `health.json` report
+//! - `GET /api/sessions` — active session IDs
+//! - `GET /api/budget` — daily token usage
+//! - `GET /api/memory/search?q=...&limit=N` — memory search
+//! - `GET /api/tasks` — scheduled tasks
+//! - `POST /api/tasks/{name}/run` — run a scheduled task now (via the heartbeat)
+//!
+//! Task output is redacted like tool output.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{info, warn};
+
+use crate::agent::budget::DailyBudget;
+use crate::agent::SessionRouter;
+use crate::config::AgentConfig;
+use crate::executor::redactor::Redactor;
+use crate::heartbeat::health::HealthReport;
+use crate::heartbeat::TaskTrigger;
+use crate::memory::MemoryEngine;
+
+/// Default number of memory search results.
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+/// Maximum number of memory search results.
+const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Admin API errors.
+#[derive(Debug, thiserror::Error)]
+pub enum AdminError {
+    /// The bind address is not a valid socket address.
+    #[error("invalid admin API bind address '{0}'")]
+    InvalidBind(String),
+
+    /// The bind address is not a loopback address.
+    #[error("admin API must bind to a loopback address, got '{0}'")]
+    NotLoopback(String),
+
+    /// The server could not bind or failed while serving.
+    #[error("admin API server error: {0}")]
+    Server(String),
+}
+
+/// Shared state for admin API handlers.
+pub struct AdminState {
+    /// Bearer token required on every request.
+    pub token: String,
+    /// Memory engine for search.
+    pub memory: Arc<MemoryEngine>,
+    /// Shared daily budget.
+    pub daily_budget: Arc<DailyBudget>,
+    /// Session router for the active session list.
+    pub session_router: Arc<SessionRouter>,
+    /// Agent configuration, for the scheduled task list.
+    pub agent_config: Arc<AgentConfig>,
+    /// Path of `health.json`.
+    pub health_path: PathBuf,
+    /// Channel into the heartbeat for manual task runs; `None` when the
+    /// heartbeat is disabled.
+    pub task_tx: Option<mpsc::Sender<TaskTrigger>>,
+    /// Redactor applied to task output.
+    pub redactor: Redactor,
+}
+
+impl std::fmt::Debug for AdminState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminState")
+            .field("health_path", &self.health_path)
+            .field("has_task_tx", &self.task_tx.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parse a bind address and require it to be loopback.
+///
+/// # Errors
+///
+/// Returns [`AdminError::InvalidBind`] or [`AdminError::NotLoopback`].
+pub fn check_bind(bind: &str) -> Result<SocketAddr, AdminError> {
+    let addr: SocketAddr = bind
+        .parse()
+        .map_err(|_| AdminError::InvalidBind(bind.to_owned()))?;
+    if !addr.ip().is_loopback() {
+        return Err(AdminError::NotLoopback(bind.to_owned()));
+    }
+    Ok(addr)
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header value.
+pub fn bearer_token(header_value: &str) -> Option<&str> {
+    header_value
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+/// Compare tokens in time independent of where they differ.
+pub fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0_u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Clamp a requested memory search limit.
+pub fn search_limit(requested: Option<usize>) -> usize {
+    requested
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT)
+}
+
+/// Build the admin API router.
+pub fn router(state: Arc<AdminState>) -> Router {
+    Router::new()
+        .route("/api/health", get(health))
+        .route("/api/sessions", get(sessions))
+        .route("/api/budget", get(budget))
+        .route("/api/memory/search", get(memory_search))
+        .route("/api/tasks", get(tasks))
+        .route("/api/tasks/{name}/run", post(run_task))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            require_token,
+        ))
+        .with_state(state)
+}
+
+/// Serve the admin API until shutdown is signalled.
+///
+/// # Errors
+///
+/// Returns [`AdminError`] if the address is invalid or not loopback, or the
+/// server cannot bind.
+pub async fn serve(
+    bind: &str,
+    state: Arc<AdminState>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> Result<(), AdminError> {
+    let addr = check_bind(bind)?;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| AdminError::Server(format!("bind {addr}: {e}")))?;
+    info!(%addr, "admin API listening");
+
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async move {
+            while shutdown_rx.changed().await.is_ok() {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+        })
+        .await
+        .map_err(|e| AdminError::Server(e.to_string()))
+}
+
+/// Reject requests without the configured bearer token.
+async fn require_token(
+    State(state): State<Arc<AdminState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(bearer_token)
+        .is_some_and(|t| token_matches(t, &state.token));
+    if !authorized {
+        warn!(path = %request.uri().path(), "admin API request rejected: bad token");
+        return error_response(StatusCode::UNAUTHORIZED, "missing or invalid bearer token");
+    }
+    next.run(request).await
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({"error": message}))).into_response()
+}
+
+async fn health(State(state): State<Arc<AdminState>>) -> Response {
+    let contents = match tokio::fs::read_to_string(&state.health_path).await {
+        Ok(contents) => contents,
+        Err(_) => {
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "no health report yet");
+        }
+    };
+    match serde_json::from_str::<HealthReport>(&contents) {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("unreadable health report: {e}"),
+        ),
+    }
+}
+
+async fn sessions(State(state): State<Arc<AdminState>>) -> Response {
+    let ids = state.session_router.session_ids().await;
+    Json(json!({"count": ids.len(), "sessions": ids})).into_response()
+}
+
+async fn budget(State(state): State<Arc<AdminState>>) -> Response {
+    let used = state.daily_budget.used();
+    let limit = state.daily_budget.limit();
+    Json(json!({
+        "used": used,
+        "limit": limit,
+        "remaining": limit.saturating_sub(used),
+    }))
+    .into_response()
+}
+
+/// Query parameters for memory search.
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    q: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+async fn memory_search(
+    State(state): State<Arc<AdminState>>,
+    Query(params): Query<SearchParams>,
+) -> Response {
+    if params.q.trim().is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "q must not be empty");
+    }
+    match state
+        .memory
+        .search(&params.q, search_limit(params.limit))
+        .await
+    {
+        Ok(results) => Json(json!({"count": results.len(), "results": results})).into_response(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+async fn tasks(State(state): State<Arc<AdminState>>) -> Response {
+    let tasks: Vec<serde_json::Value> = state
+        .agent_config
+        .scheduled_tasks
+        .iter()
+        .map(|t| {
+            json!({
+                "name": t.name,
+                "cron": t.cron,
+                "enabled": t.enabled,
+                "builtin": t.builtin,
+                "tool": t.tool,
+            })
+        })
+        .collect();
+    Json(json!({"tasks": tasks})).into_response()
+}
+
+async fn run_task(State(state): State<Arc<AdminState>>, Path(name): Path<String>) -> Response {
+    let Some(task_tx) = &state.task_tx else {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "heartbeat is disabled");
+    };
+    if !state
+        .agent_config
+        .scheduled_tasks
+        .iter()
+        .any(|t| t.name == name)
+    {
+        return error_response(StatusCode::NOT_FOUND, "unknown task");
+    }
+
+    let (reply, reply_rx) = oneshot::channel();
+    let trigger = TaskTrigger {
+        name: name.clone(),
+        reply,
+    };
+    if task_tx.send(trigger).await.is_err() {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "heartbeat is not running");
+    }
+    info!(task = %name, "scheduled task triggered via admin API");
+
+    match reply_rx.await {
+        Ok(Ok(outcome)) => Json(json!({
+            "name": outcome.name,
+            "success": outcome.success,
+            "output": state.redactor.redact(&outcome.output),
+            "duration_ms": u64::try_from(outcome.duration.as_millis()).unwrap_or(u64::MAX),
+        }))
+        .into_response(),
+        Ok(Err(e)) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &state.redactor.redact(&e),
+        ),
+        Err(_) => error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "heartbeat stopped before the task ran",
+        ),
+    }
+}
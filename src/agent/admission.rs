@@ -0,0 +1,148 @@
+//! Health-aware admission of user messages.
+//!
+//! Before a user message reaches a session, [`super::SessionRouter`] checks
+//! the live call outcomes of the agent's model and the latest heartbeat
+//! health report. When either is down the user gets a specific status
+//! message straight away instead of a generic error from deep inside the
+//! agent loop:
+//!
+//! - Model provider failing: the message is not routed. Once
+//!   [`PROVIDER_RETRY_AFTER_SECS`] pass without a new failure, messages are
+//!   let through again so a recovered provider is noticed.
+//! - Executor down: the message is held, and the user either sends it
+//!   anyway (with a note that commands will fail) or drops it.
+
+use chrono::{DateTime, Utc};
+
+use crate::heartbeat::health::{provider_health, ComponentState, HealthReport, COMPONENT_EXECUTOR};
+use crate::providers::health::ProviderOutcome;
+use crate::telegram::ui::escape_html;
+
+/// Seconds after the last provider failure during which messages are refused.
+pub const PROVIDER_RETRY_AFTER_SECS: i64 = 60;
+
+/// Health reports older than this many seconds are ignored.
+pub const MAX_REPORT_AGE_SECS: i64 = 600;
+
+/// Maximum characters of an error shown to the user.
+const MAX_ERROR_CHARS: usize = 200;
+
+/// Prepended to a held message the user chose to send while the executor is down.
+pub const EXECUTOR_DOWN_NOTE: &str = "[System: the command sandbox is unavailable, so \
+    execute_command and script tools will fail. Answer without running commands.]";
+
+/// Whether a user message may be routed to its session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    /// Route the message.
+    Admit,
+    /// The agent's model provider is failing; the message is refused.
+    ProviderDown {
+        /// Model spec of the failing provider.
+        spec: String,
+        /// Most recent error.
+        error: Option<String>,
+        /// Seconds until messages are let through again.
+        retry_in_secs: i64,
+    },
+    /// The executor is down; the message is held until the user decides.
+    ExecutorDown {
+        /// Executor kind as reported by the heartbeat (`Docker`, `Direct`).
+        executor: String,
+        /// Most recent error.
+        error: Option<String>,
+    },
+}
+
+/// Decide whether to admit a message.
+///
+/// `spec` and `outcome` describe the agent's default model; `report` is the
+/// latest heartbeat health report, if any.
+pub fn check(
+    report: Option<&HealthReport>,
+    spec: &str,
+    outcome: &ProviderOutcome,
+    now: DateTime<Utc>,
+) -> Admission {
+    if provider_health(outcome).status == ComponentState::Unavailable {
+        let since_failure = outcome
+            .last_failure_at
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| now.signed_duration_since(ts).num_seconds());
+        if let Some(elapsed) = since_failure.filter(|s| *s < PROVIDER_RETRY_AFTER_SECS) {
+            return Admission::ProviderDown {
+                spec: spec.to_owned(),
+                error: outcome.last_error.clone(),
+                retry_in_secs: PROVIDER_RETRY_AFTER_SECS.saturating_sub(elapsed.max(0)),
+            };
+        }
+    }
+
+    let Some(report) = report.filter(|r| is_fresh(r, now)) else {
+        return Admission::Admit;
+    };
+    match report.component(COMPONENT_EXECUTOR) {
+        Some(executor) if executor.status != ComponentState::Healthy => Admission::ExecutorDown {
+            executor: report.executor.clone(),
+            error: executor.last_error.clone(),
+        },
+        _ => Admission::Admit,
+    }
+}
+
+/// Whether the report was written recently enough to act on.
+fn is_fresh(report: &HealthReport, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&report.last_heartbeat)
+        .is_ok_and(|ts| now.signed_duration_since(ts).num_seconds() < MAX_REPORT_AGE_SECS)
+}
+
+/// Status message for a failing model provider (Telegram HTML).
+///
+/// `error` should already be redacted.
+pub fn format_provider_down(spec: &str, error: Option<&str>, retry_in_secs: i64) -> String {
+    let mut text = format!(
+        "\u{26A0}\u{FE0F} My model (<code>{}</code>) is failing right now, so I can't answer.",
+        escape_html(spec)
+    );
+    push_error(&mut text, error);
+    text.push_str(&format!(
+        "\nSend your message again in about {} seconds.",
+        retry_in_secs.max(1)
+    ));
+    text
+}
+
+/// Status message for a down executor (Telegram HTML).
+///
+/// `held` is how many of the user's messages are waiting, counting the one
+/// just received. `error` should already be redacted.
+pub fn format_executor_down(executor: &str, error: Option<&str>, held: usize) -> String {
+    let mut text = if executor.eq_ignore_ascii_case("docker") {
+        "\u{26A0}\u{FE0F} Docker is down, so I can't run commands right now.".to_owned()
+    } else {
+        format!(
+            "\u{26A0}\u{FE0F} The {} executor is down, so I can't run commands right now.",
+            escape_html(&executor.to_lowercase())
+        )
+    };
+    push_error(&mut text, error);
+    if held > 1 {
+        text.push_str(&format!("\n{held} messages are waiting."));
+    }
+    text.push_str("\nI can still answer without running commands.");
+    if executor.eq_ignore_ascii_case("docker") {
+        text.push_str(
+            " Restarting Wintermute while Docker is unavailable falls back to direct mode \
+             (no sandbox).",
+        );
+    }
+    text
+}
+
+fn push_error(text: &mut String, error: Option<&str>) {
+    if let Some(error) = error.filter(|e| !e.is_empty()) {
+        let short: String = error.chars().take(MAX_ERROR_CHARS).collect();
+        text.push_str(&format!("\nLast error: {}", escape_html(&short)));
+    }
+}
@@ -21,6 +21,9 @@ const BASE62_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmn
 /// Default approval expiry in minutes.
 const APPROVAL_EXPIRY_MINUTES: i64 = 5;
 
+/// How long a decision is remembered so repeated taps are recognised.
+const RESOLVED_RETENTION_MINUTES: i64 = 30;
+
 /// A pending approval request waiting for user action.
 #[derive(Debug, Clone)]
 pub struct PendingApproval {
@@ -65,6 +68,23 @@ pub enum ApprovalResult {
     NotFound,
     /// The resolving user does not match the request owner.
     WrongUser,
+    /// The approval was already decided (for example a double tap); the
+    /// original decision stands.
+    AlreadyResolved {
+        /// Whether it was approved.
+        approved: bool,
+        /// Tool name.
+        tool_name: String,
+    },
+}
+
+/// A recent decision, keyed by approval ID.
+#[derive(Debug, Clone)]
+struct ResolvedApproval {
+    approved: bool,
+    tool_name: String,
+    user_id: i64,
+    resolved_at: DateTime<Utc>,
 }
 
 /// Manages pending tool-call approval requests.
@@ -73,6 +93,8 @@ pub enum ApprovalResult {
 #[derive(Debug)]
 pub struct ApprovalManager {
     pending: Mutex<HashMap<String, PendingApproval>>,
+    /// Recent decisions; always locked after `pending`.
+    resolved: Mutex<HashMap<String, ResolvedApproval>>,
 }
 
 impl ApprovalManager {
@@ -80,6 +102,7 @@ impl ApprovalManager {
     pub fn new() -> Self {
         Self {
             pending: Mutex::new(HashMap::new()),
+            resolved: Mutex::new(HashMap::new()),
         }
     }
 
@@ -116,6 +139,9 @@ impl ApprovalManager {
     /// Resolve an approval request by ID.
     ///
     /// The entry is removed on resolution regardless of outcome (single-use).
+    /// The approval ID doubles as an idempotency key: resolving an already
+    /// decided approval again returns [`ApprovalResult::AlreadyResolved`]
+    /// with the original decision.
     pub fn resolve(&self, approval_id: &str, approved: bool, user_id: i64) -> ApprovalResult {
         let mut map = match self.pending.lock() {
             Ok(m) => m,
@@ -124,7 +150,7 @@ impl ApprovalManager {
 
         let entry = match map.remove(approval_id) {
             Some(e) => e,
-            None => return ApprovalResult::NotFound,
+            None => return self.previous_decision(approval_id, user_id),
         };
 
         if entry.user_id != user_id {
@@ -133,10 +159,27 @@ impl ApprovalManager {
             return ApprovalResult::WrongUser;
         }
 
-        if Utc::now() > entry.expires_at {
+        let now = Utc::now();
+        if now > entry.expires_at {
             return ApprovalResult::Expired;
         }
 
+        if let Ok(mut resolved) = self.resolved.lock() {
+            let cutoff = now
+                .checked_sub_signed(Duration::minutes(RESOLVED_RETENTION_MINUTES))
+                .unwrap_or(now);
+            resolved.retain(|_, v| v.resolved_at > cutoff);
+            resolved.insert(
+                approval_id.to_owned(),
+                ResolvedApproval {
+                    approved,
+                    tool_name: entry.tool_name.clone(),
+                    user_id,
+                    resolved_at: now,
+                },
+            );
+        }
+
         if approved {
             ApprovalResult::Approved {
                 session_id: entry.session_id,
@@ -151,12 +194,33 @@ impl ApprovalManager {
         }
     }
 
-    /// Remove all expired entries.
+    /// Outcome for an ID that is no longer pending.
+    fn previous_decision(&self, approval_id: &str, user_id: i64) -> ApprovalResult {
+        let Ok(resolved) = self.resolved.lock() else {
+            return ApprovalResult::NotFound;
+        };
+        match resolved.get(approval_id) {
+            Some(previous) if previous.user_id != user_id => ApprovalResult::WrongUser,
+            Some(previous) => ApprovalResult::AlreadyResolved {
+                approved: previous.approved,
+                tool_name: previous.tool_name.clone(),
+            },
+            None => ApprovalResult::NotFound,
+        }
+    }
+
+    /// Remove all expired entries and forget old decisions.
     pub fn gc_expired(&self) {
+        let now = Utc::now();
         if let Ok(mut map) = self.pending.lock() {
-            let now = Utc::now();
             map.retain(|_, v| v.expires_at > now);
         }
+        let cutoff = now
+            .checked_sub_signed(Duration::minutes(RESOLVED_RETENTION_MINUTES))
+            .unwrap_or(now);
+        if let Ok(mut resolved) = self.resolved.lock() {
+            resolved.retain(|_, v| v.resolved_at > cutoff);
+        }
     }
 
     /// Access the underlying pending map (for testing expiry manipulation).
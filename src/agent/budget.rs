@@ -1,14 +1,18 @@
 //! Atomic budget tracking for token usage and tool call limits.
 //!
 //! Provides per-session and per-day budget enforcement using lock-free atomics.
-//! The [`DailyBudget`] automatically resets when the calendar day changes.
+//! The [`DailyBudget`] automatically resets when the calendar day changes in
+//! its [`BudgetTimezone`], optionally carrying part of the unused budget over.
 
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
 
-use crate::config::BudgetConfig;
+use crate::config::{BudgetConfig, DegradationConfig};
+
+/// Model role used while [`Degradation::cheap_model`] is in effect.
+pub const BUDGET_MODEL_ROLE: &str = "budget";
 
 /// Warning thresholds as percentage of session budget.
 const WARNING_THRESHOLDS: [u8; 3] = [70, 85, 95];
@@ -56,6 +60,79 @@ impl BudgetScope {
     }
 }
 
+/// Degradation steps in effect at the current budget usage.
+///
+/// See [`DegradationConfig`] for the thresholds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Degradation {
+    /// Turns run on the `budget` model role.
+    pub cheap_model: bool,
+    /// Observer extraction and proactive checks are skipped.
+    pub pause_background: bool,
+    /// Context is cut to the configured fraction.
+    pub shorten_context: bool,
+}
+
+impl Degradation {
+    const CHEAP_MODEL: u8 = 1;
+    const PAUSE_BACKGROUND: u8 = 2;
+    const SHORTEN_CONTEXT: u8 = 4;
+
+    /// Steps that apply at `percent` budget usage.
+    pub fn at(config: &DegradationConfig, percent: u8) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        Self {
+            cheap_model: percent >= config.cheap_model_at,
+            pause_background: percent >= config.pause_background_at,
+            shorten_context: percent >= config.shorten_context_at,
+        }
+    }
+
+    /// Whether any step is in effect.
+    pub fn is_active(self) -> bool {
+        self.cheap_model || self.pause_background || self.shorten_context
+    }
+
+    /// Short user-facing descriptions of the active steps.
+    pub fn describe(self) -> Vec<&'static str> {
+        let mut steps = Vec::new();
+        if self.cheap_model {
+            steps.push("switching to the cheaper model");
+        }
+        if self.pause_background {
+            steps.push("pausing background learning and proactive checks");
+        }
+        if self.shorten_context {
+            steps.push("keeping less conversation history in context");
+        }
+        steps
+    }
+
+    fn bits(self) -> u8 {
+        let mut bits = 0;
+        if self.cheap_model {
+            bits |= Self::CHEAP_MODEL;
+        }
+        if self.pause_background {
+            bits |= Self::PAUSE_BACKGROUND;
+        }
+        if self.shorten_context {
+            bits |= Self::SHORTEN_CONTEXT;
+        }
+        bits
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self {
+            cheap_model: bits & Self::CHEAP_MODEL != 0,
+            pause_background: bits & Self::PAUSE_BACKGROUND != 0,
+            shorten_context: bits & Self::SHORTEN_CONTEXT != 0,
+        }
+    }
+}
+
 /// Errors produced when budget limits are exceeded.
 #[derive(Debug, thiserror::Error)]
 pub enum BudgetError {
@@ -99,27 +176,113 @@ impl BudgetError {
     }
 }
 
+/// Timezone in which the daily budget rolls over (`[budget] timezone`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetTimezone {
+    /// Midnight UTC.
+    Utc,
+    /// Midnight in the host's local timezone.
+    Local,
+    /// Midnight at a fixed UTC offset.
+    Fixed(FixedOffset),
+}
+
+impl BudgetTimezone {
+    /// Parse `utc`, `local`, or an offset such as `+08:00` or `-05:30`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("utc") {
+            return Some(Self::Utc);
+        }
+        if value.eq_ignore_ascii_case("local") {
+            return Some(Self::Local);
+        }
+        value.parse::<FixedOffset>().ok().map(Self::Fixed)
+    }
+
+    /// Calendar date of `now` in this timezone.
+    pub fn date(self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::Utc => now.date_naive(),
+            Self::Local => now.with_timezone(&Local).date_naive(),
+            Self::Fixed(offset) => now.with_timezone(&offset).date_naive(),
+        }
+    }
+
+    /// The instant `date` begins in this timezone.
+    ///
+    /// If local midnight does not exist (a DST gap), midnight UTC is used.
+    pub fn day_start(self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        let start = match self {
+            Self::Utc => Some(midnight.and_utc()),
+            Self::Local => Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+            Self::Fixed(offset) => offset
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc)),
+        };
+        start.unwrap_or_else(|| midnight.and_utc())
+    }
+
+    /// Start of the budget day containing `now`.
+    pub fn today_start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.day_start(self.date(now))
+    }
+}
+
 /// Daily token budget shared across all sessions.
 ///
-/// Automatically resets the counter when the ordinal day of the year changes.
+/// Resets the counter when the calendar day changes in its [`BudgetTimezone`].
+/// On a rollover from the previous day, `carry_over_percent` of that day's
+/// unused base limit is added to the new day's limit. Carry-over is computed
+/// against the base limit only, so it never compounds across days.
 #[derive(Debug)]
 pub struct DailyBudget {
     tokens: AtomicU64,
-    /// Ordinal day of the year for reset detection.
+    /// Current budget day, as days since 0001-01-01 in the budget timezone.
     ///
     /// Exposed for testing only — production code should not touch this.
     pub reset_day: AtomicU32,
-    limit: u64,
+    limit: AtomicU64,
+    carried_over: AtomicU64,
+    carry_over_percent: AtomicU8,
+    timezone: BudgetTimezone,
 }
 
 impl DailyBudget {
-    /// Create a new daily budget with the given token limit.
+    /// Create a new daily budget with the given token limit, rolling over
+    /// at midnight UTC without carry-over.
     pub fn new(limit: u64) -> Self {
-        let today = current_ordinal_day();
+        Self::with_rollover(limit, BudgetTimezone::Utc, 0)
+    }
+
+    /// Create a daily budget from the `[budget]` config.
+    ///
+    /// An unparseable timezone (rejected when the config is loaded) falls
+    /// back to UTC.
+    pub fn from_config(config: &BudgetConfig) -> Self {
+        let timezone = BudgetTimezone::parse(&config.timezone).unwrap_or(BudgetTimezone::Utc);
+        Self::with_rollover(
+            config.max_tokens_per_day,
+            timezone,
+            config.carry_over_percent,
+        )
+    }
+
+    /// Create a daily budget that rolls over at midnight in `timezone` and
+    /// carries `carry_over_percent` (0–100) of the unused limit forward.
+    pub fn with_rollover(limit: u64, timezone: BudgetTimezone, carry_over_percent: u8) -> Self {
         Self {
             tokens: AtomicU64::new(0),
-            reset_day: AtomicU32::new(today),
-            limit,
+            reset_day: AtomicU32::new(day_number(timezone.date(Utc::now()))),
+            limit: AtomicU64::new(limit),
+            carried_over: AtomicU64::new(0),
+            carry_over_percent: AtomicU8::new(carry_over_percent.min(100)),
+            timezone,
         }
     }
 
@@ -134,11 +297,9 @@ impl DailyBudget {
         self.maybe_reset();
         let used = self.tokens.load(Ordering::Relaxed);
         let new_total = used.saturating_add(amount);
-        if new_total > self.limit {
-            return Err(BudgetError::DailyLimitExceeded {
-                used,
-                limit: self.limit,
-            });
+        let limit = self.limit();
+        if new_total > limit {
+            return Err(BudgetError::DailyLimitExceeded { used, limit });
         }
         Ok(())
     }
@@ -154,14 +315,62 @@ impl DailyBudget {
         self.tokens.load(Ordering::Relaxed)
     }
 
-    /// Configured daily token limit.
+    /// Today's token limit: the configured limit plus any carry-over.
     pub fn limit(&self) -> u64 {
-        self.limit
+        self.maybe_reset();
+        self.base_limit()
+            .saturating_add(self.carried_over.load(Ordering::Relaxed))
+    }
+
+    /// Configured daily token limit, without carry-over.
+    pub fn base_limit(&self) -> u64 {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// Tokens carried over from yesterday into today's limit.
+    pub fn carried_over(&self) -> u64 {
+        self.maybe_reset();
+        self.carried_over.load(Ordering::Relaxed)
+    }
+
+    /// Timezone in which the budget rolls over.
+    pub fn timezone(&self) -> BudgetTimezone {
+        self.timezone
+    }
+
+    /// Change the share of unused budget carried into the next day.
+    ///
+    /// Today's carry-over is kept.
+    pub fn set_carry_over_percent(&self, percent: u8) {
+        self.carry_over_percent
+            .store(percent.min(100), Ordering::Relaxed);
+    }
+
+    /// Set today's carry-over from yesterday's usage, e.g. read from the
+    /// usage ledger after a restart.
+    pub fn restore_carry_over(&self, yesterday_used: u64) {
+        self.carried_over
+            .store(self.carry_over_for(yesterday_used), Ordering::Relaxed);
+    }
+
+    /// Daily usage as a percentage (0–100), clamped.
+    pub fn percent(&self) -> u8 {
+        percent_of(self.used(), self.limit())
+    }
+
+    /// Change the daily token limit, e.g. after a config reload.
+    ///
+    /// Usage so far today is kept.
+    pub fn set_limit(&self, limit: u64) {
+        self.limit.store(limit, Ordering::Relaxed);
     }
 
     /// Reset the counter if the calendar day has changed.
+    ///
+    /// Unused budget is only carried over from the immediately preceding
+    /// day; after an idle gap the new day starts at the base limit.
     fn maybe_reset(&self) {
-        let today = current_ordinal_day();
+        let today = day_number(self.timezone.date(Utc::now()));
         let stored = self.reset_day.load(Ordering::Relaxed);
         if stored != today {
             // Day changed — reset. Compare-exchange avoids double-reset races.
@@ -170,10 +379,23 @@ impl DailyBudget {
                 .compare_exchange(stored, today, Ordering::Relaxed, Ordering::Relaxed)
                 .is_ok()
             {
-                self.tokens.store(0, Ordering::Relaxed);
+                let used = self.tokens.swap(0, Ordering::Relaxed);
+                let carry = if stored.checked_add(1) == Some(today) {
+                    self.carry_over_for(used)
+                } else {
+                    0
+                };
+                self.carried_over.store(carry, Ordering::Relaxed);
             }
         }
     }
+
+    /// Tokens carried into the next day after using `used` of the base limit.
+    fn carry_over_for(&self, used: u64) -> u64 {
+        let unused = self.base_limit().saturating_sub(used);
+        let percent = u64::from(self.carry_over_percent.load(Ordering::Relaxed));
+        unused.saturating_mul(percent) / 100
+    }
 }
 
 /// Per-session budget tracker.
@@ -189,6 +411,8 @@ pub struct SessionBudget {
     config: BudgetConfig,
     /// Whether the session is paused due to budget exhaustion.
     paused: AtomicBool,
+    /// Degradation steps the user has already been told about.
+    announced: AtomicU8,
 }
 
 impl SessionBudget {
@@ -199,9 +423,22 @@ impl SessionBudget {
             daily,
             config,
             paused: AtomicBool::new(false),
+            announced: AtomicU8::new(0),
         }
     }
 
+    /// Budget limits currently applied to this session.
+    pub fn config(&self) -> &BudgetConfig {
+        &self.config
+    }
+
+    /// Replace the session limits, e.g. after a config reload.
+    ///
+    /// Usage so far is kept and checked against the new limits.
+    pub fn set_config(&mut self, config: BudgetConfig) {
+        self.config = config;
+    }
+
     /// Check whether `estimated_tokens` can be consumed without exceeding limits.
     ///
     /// # Errors
@@ -257,9 +494,11 @@ impl SessionBudget {
         self.config.max_tokens_per_session
     }
 
-    /// Maximum tokens allowed per day.
+    /// Maximum tokens allowed today, including carry-over.
     pub fn daily_limit(&self) -> u64 {
-        self.config.max_tokens_per_day
+        self.config
+            .max_tokens_per_day
+            .saturating_add(self.daily.carried_over())
     }
 
     /// Session usage as a percentage (0–100), clamped.
@@ -269,7 +508,7 @@ impl SessionBudget {
 
     /// Daily usage as a percentage (0–100), clamped.
     pub fn daily_percent(&self) -> u8 {
-        percent_of(self.daily_used(), self.config.max_tokens_per_day)
+        percent_of(self.daily_used(), self.daily_limit())
     }
 
     /// Reset the session token counter for a new budget window.
@@ -291,6 +530,24 @@ impl SessionBudget {
         true
     }
 
+    /// Degradation steps in effect, based on the higher of session and
+    /// daily usage.
+    pub fn degradation(&self) -> Degradation {
+        let percent = self.session_percent().max(self.daily_percent());
+        Degradation::at(&self.config.degradation, percent)
+    }
+
+    /// Current degradation plus the steps not yet announced to the user.
+    ///
+    /// Each step is reported once; a step that lifts (after a budget renewal
+    /// or a new day) is reported again if it returns.
+    pub fn announce_degradation(&self) -> (Degradation, Degradation) {
+        let current = self.degradation();
+        let previous = self.announced.swap(current.bits(), Ordering::Relaxed);
+        let new = Degradation::from_bits(current.bits() & !previous);
+        (current, new)
+    }
+
     /// Whether the session is paused due to budget exhaustion.
     pub fn is_paused(&self) -> bool {
         self.paused.load(Ordering::Relaxed)
@@ -371,8 +628,7 @@ fn warning_status(threshold: u8) -> BudgetStatus {
     }
 }
 
-/// Returns the current ordinal day of the year (1-366).
-fn current_ordinal_day() -> u32 {
-    use chrono::Datelike;
-    Utc::now().ordinal()
+/// Day number of `date` (days since 0001-01-01), for rollover detection.
+fn day_number(date: NaiveDate) -> u32 {
+    u32::try_from(date.num_days_from_ce()).unwrap_or(0)
 }
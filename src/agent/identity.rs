@@ -134,7 +134,8 @@ HOST → browser (CDP or sidecar) + egress-proxy (Squid) → sandbox → service
         "- {} custom tools (agent-created)",
         snap.dynamic_tool_count
     );
-    doc.push_str("- Core tools: execute_command, web_fetch (+ save_to for file downloads), web_request, browser, memory_search, memory_save, send_message, manage_brief, read_messages, create_tool, escalate, docker_manage\n");
+    doc.push_str("- Core tools: execute_command, web_fetch (+ save_to for file downloads), web_search, web_request, browser, memory_search, memory_save, send_message, manage_brief, read_messages, rss_subscribe, rss_list, create_tool, escalate, docker_manage, email (when configured; sends need approval)\n");
+    doc.push_str("- MCP tools: mcp_{server}_{tool} from configured MCP servers; non-read-only ones need approval\n");
 
     // Dynamic tool stats
     if !snap.dynamic_tool_summaries.is_empty() {
@@ -289,22 +290,22 @@ through tools and config, not by recompiling.
     match snap.soul_modification_mode {
         crate::config::SoulModificationMode::Notify => {
             doc.push_str(
-                "Mode: **notify** — you may modify your soul and send the diff to the user.\n\
+                "Mode: **notify** — you may modify your soul; the system sends the diff to the user.\n\
                  1. Make the change to agent.toml\n\
-                 2. Show the before/after diff to the user\n\
-                 3. Git commit: \"evolve: {what changed}\"\n\
-                 4. The change takes effect on the next conversation\n\n",
+                 2. Git commit: \"evolve: {what changed}\"\n\
+                 3. Within a minute the change is recorded as a new soul version\n\
+                 4. The user can roll it back with /soul revert\n\n",
             );
         }
         crate::config::SoulModificationMode::Approve => {
             doc.push_str(
-                "Mode: **approve** — you must show the before/after and wait for user approval.\n\
+                "Mode: **approve** — soul edits only take effect once the user approves the diff.\n\
                  1. Tell the user what you want to change and why\n\
-                 2. Show the before/after\n\
-                 3. Wait for explicit approval\n\
-                 4. Apply via execute_command editing agent.toml\n\
-                 5. Git commit: \"evolve: {what changed}\"\n\
-                 6. The change takes effect on the next conversation\n\n",
+                 2. Apply via execute_command editing agent.toml\n\
+                 3. Git commit: \"evolve: {what changed}\"\n\
+                 4. The system sends the diff to the user with Apply/Reject buttons\n\
+                 5. Wait for explicit approval; until then you keep your current soul\n\
+                 6. A rejected edit is rolled back in agent.toml\n\n",
             );
         }
     }
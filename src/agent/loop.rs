@@ -3,31 +3,40 @@
 //! Each user session runs as an independent Tokio task. The session receives
 //! [`SessionEvent`]s via an mpsc channel and drives the LLM reasoning loop
 //! for each user message.
+//!
+//! Each turn is traced as an `agent.turn` span with child spans for context
+//! assembly, provider calls, tool execution and outbound sends, so latency
+//! can be broken down when spans are exported (see `[telemetry]`).
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use url::Url;
 
 use crate::observer::ObserverEvent;
 
 use crate::agent::approval::ApprovalResult;
-use crate::agent::budget::{BudgetStatus, SessionBudget};
+use crate::agent::budget::{BudgetStatus, SessionBudget, BUDGET_MODEL_ROLE};
 use crate::agent::context::{
     apply_compaction, assemble_system_prompt, build_compaction_plan, build_compaction_request,
     estimate_messages_tokens, should_compact, trim_messages, trim_messages_to_fraction,
     COMPACTION_KEEP_LAST,
 };
 use crate::agent::policy::{check_policy, PolicyContext, PolicyDecision};
-use crate::agent::TelegramOutbound;
-use crate::config::{AgentConfig, Config};
+use crate::agent::{Keyboard, TelegramOutbound};
+use crate::config::{AgentConfig, BudgetConfig, Config};
+use crate::memory::feedback::TurnRecord;
 use crate::memory::{ConversationEntry, Memory, MemoryEngine, MemoryStatus, TrustSource};
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{
     extract_text, CompletionRequest, ContentPart, Message, MessageContent, Role, StopReason,
+    UsageStats,
 };
+use crate::telegram::ui::escape_html;
 use crate::tools::ToolRouter;
 
 use super::approval::ApprovalManager;
@@ -53,6 +62,8 @@ pub enum SessionEvent {
         /// Message text content.
         text: String,
     },
+    /// Budget limits changed by a config reload.
+    BudgetReloaded(BudgetConfig),
     /// Graceful shutdown signal.
     Shutdown,
 }
@@ -121,7 +132,7 @@ const OBSERVER_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
 /// session. It maintains the conversation history and dispatches to the
 /// agent reasoning loop on each user message. Includes idle detection for
 /// the observer pipeline.
-pub async fn run_session(cfg: SessionConfig, mut event_rx: mpsc::Receiver<SessionEvent>) {
+pub async fn run_session(mut cfg: SessionConfig, mut event_rx: mpsc::Receiver<SessionEvent>) {
     info!(session_id = %cfg.session_id, user_id = cfg.user_id, "session started");
 
     let mut conversation: Vec<Message> = Vec::new();
@@ -172,15 +183,22 @@ pub async fn run_session(cfg: SessionConfig, mut event_rx: mpsc::Receiver<Sessio
                     // Only send the last MAX_OBSERVER_TAIL messages to avoid
                     // cloning the entire conversation (observer truncates anyway).
                     if let Some(ref observer_tx) = cfg.observer_tx {
-                        let start = conversation.len().saturating_sub(20);
-                        let event = ObserverEvent {
-                            session_id: cfg.session_id.clone(),
-                            user_id: cfg.user_id,
-                            messages: conversation[start..].to_vec(),
-                            tools_modified: tools_modified.clone(),
-                        };
-                        if let Err(e) = observer_tx.try_send(event) {
-                            debug!(error = %e, "failed to send observer event (non-blocking)");
+                        if cfg.budget.degradation().pause_background {
+                            debug!(
+                                session_id = %cfg.session_id,
+                                "budget running low, skipping observer extraction"
+                            );
+                        } else {
+                            let start = conversation.len().saturating_sub(20);
+                            let event = ObserverEvent {
+                                session_id: cfg.session_id.clone(),
+                                user_id: cfg.user_id,
+                                messages: conversation[start..].to_vec(),
+                                tools_modified: tools_modified.clone(),
+                            };
+                            if let Err(e) = observer_tx.try_send(event) {
+                                debug!(error = %e, "failed to send observer event (non-blocking)");
+                            }
                         }
                     }
                     last_turn_had_activity = false;
@@ -247,6 +265,10 @@ pub async fn run_session(cfg: SessionConfig, mut event_rx: mpsc::Receiver<Sessio
 
                 checkpoint_session(&cfg).await;
             }
+            SessionEvent::BudgetReloaded(budget) => {
+                debug!(session_id = %cfg.session_id, "applying reloaded budget limits");
+                cfg.budget.set_config(budget);
+            }
             SessionEvent::ApprovalResolved(result) => {
                 debug!(session_id = %cfg.session_id, "received approval resolution");
                 handle_approval_resolved(
@@ -354,6 +376,15 @@ const OVERFLOW_TRIM_FRACTION: f64 = 0.5;
 /// executing each tool call and feeding results back. `bootstrap_memories`
 /// are merged into the first turn's context and drained so that subsequent
 /// turns rely solely on query-driven memory search.
+#[tracing::instrument(
+    name = "agent.turn",
+    skip_all,
+    fields(
+        session_id = %cfg.session_id,
+        llm_calls = tracing::field::Empty,
+        tool_calls = tracing::field::Empty,
+    )
+)]
 async fn run_agent_turn(
     cfg: &SessionConfig,
     conversation: &mut Vec<Message>,
@@ -363,6 +394,10 @@ async fn run_agent_turn(
     tools_modified: &mut Vec<String>,
 ) {
     let mut tool_call_count: u32 = 0;
+    let mut llm_call_count: u32 = 0;
+    // Memories and tools this turn drew on, linked to any user feedback.
+    let mut turn_memory_ids: Vec<i64> = Vec::new();
+    let mut turn_tools: Vec<String> = Vec::new();
 
     // Context compaction: compress older messages if budget usage is high.
     // Only fires once per session to avoid repeated LLM summarization calls.
@@ -394,8 +429,14 @@ async fn run_agent_turn(
                     stop_sequences: vec![],
                 };
 
-                match provider.complete(request).await {
+                let span = llm_span(provider.model_id(), "compaction");
+                let scope = UsageScope::current_with_role("compaction");
+                match usage::scoped(scope, provider.complete(request))
+                    .instrument(span.clone())
+                    .await
+                {
                     Ok(response) => {
+                        record_llm_usage(&span, &response.usage, &cfg.budget);
                         let summary = extract_text(&response.content);
                         if !summary.is_empty() {
                             cfg.budget.record_usage(
@@ -420,66 +461,107 @@ async fn run_agent_turn(
     }
 
     loop {
-        // Step 1: Search for relevant memories
-        let last_query = last_user_text(conversation);
-        let mut memories = match cfg.memory.search(&last_query, 5).await {
-            Ok(mems) => mems,
-            Err(e) => {
-                warn!(error = %e, "memory search failed, proceeding without memories");
-                Vec::new()
-            }
-        };
+        let (tools, system_prompt) = async {
+            // Step 1: Search for relevant memories
+            let last_query = last_user_text(conversation);
+            let mut memories = match cfg.memory.search(&last_query, 5).await {
+                Ok(mems) => mems,
+                Err(e) => {
+                    warn!(error = %e, "memory search failed, proceeding without memories");
+                    Vec::new()
+                }
+            };
 
-        // Merge bootstrap memories (consumed on first turn only).
-        merge_bootstrap_memories(&mut memories, bootstrap_memories);
+            // Merge bootstrap memories (consumed on first turn only).
+            merge_bootstrap_memories(&mut memories, bootstrap_memories);
+            for id in memories.iter().filter_map(|m| m.id) {
+                if !turn_memory_ids.contains(&id) {
+                    turn_memory_ids.push(id);
+                }
+            }
 
-        // Step 2: Assemble system prompt
-        let pending_approvals = cfg.approval_manager.pending_count(&cfg.session_id);
-        let current_time = chrono::Utc::now()
-            .format("%Y-%m-%d %H:%M:%S UTC")
-            .to_string();
+            // Step 2: Assemble system prompt
+            let pending_approvals = cfg.approval_manager.pending_count(&cfg.session_id);
+            let current_time = chrono::Utc::now()
+                .format("%Y-%m-%d %H:%M:%S UTC")
+                .to_string();
 
-        let tools = cfg.tool_router.tool_definitions(
-            cfg.config.budget.max_dynamic_tools_per_turn,
-            Some(&last_query),
-        );
-        let core_tool_count = crate::tools::core::core_tool_definitions().len();
+            let tools = cfg.tool_router.tool_definitions(
+                cfg.budget.config().max_dynamic_tools_per_turn,
+                Some(&last_query),
+            );
+            let core_tool_count = crate::tools::core::core_tool_definitions().len();
 
-        // Load active briefs for context injection
-        let active_briefs = match crate::messaging::brief::active_briefs_for_session(
-            cfg.memory.pool(),
-            &cfg.session_id,
-        )
-        .await
-        {
-            Ok(briefs) => {
-                if briefs.is_empty() {
+            // Load active briefs for context injection
+            let active_briefs = match crate::messaging::brief::active_briefs_for_session(
+                cfg.memory.pool(),
+                &cfg.session_id,
+            )
+            .await
+            {
+                Ok(briefs) => {
+                    if briefs.is_empty() {
+                        None
+                    } else {
+                        Some(crate::messaging::brief::active_briefs_summary(&briefs))
+                    }
+                }
+                Err(e) => {
+                    warn!(error = %e, "failed to load active briefs");
                     None
-                } else {
-                    Some(crate::messaging::brief::active_briefs_summary(&briefs))
                 }
-            }
-            Err(e) => {
-                warn!(error = %e, "failed to load active briefs");
-                None
-            }
-        };
+            };
 
-        let system_prompt = assemble_system_prompt(
-            &cfg.agent_config.personality.soul,
-            cfg.identity_document.as_deref(),
-            cfg.agents_md_content.as_deref(),
-            cfg.user_md_content.as_deref(),
-            cfg.policy_context.executor_kind,
-            tools.len().saturating_sub(core_tool_count),
-            &memories,
-            pending_approvals,
-            &current_time,
-            active_briefs.as_deref(),
-        );
+            // An unapproved soul edit in agent.toml never reaches the prompt.
+            let soul = match crate::agent::soul::active_soul(cfg.memory.pool()).await {
+                Ok(Some(version)) => version.content,
+                Ok(None) => cfg.agent_config.personality.soul.clone(),
+                Err(e) => {
+                    warn!(error = %e, "failed to load active soul, using agent.toml");
+                    cfg.agent_config.personality.soul.clone()
+                }
+            };
 
-        // Step 3: Resolve provider
-        let provider = match cfg.router.resolve(None, None) {
+            let system_prompt = assemble_system_prompt(
+                &soul,
+                cfg.identity_document.as_deref(),
+                cfg.agents_md_content.as_deref(),
+                cfg.user_md_content.as_deref(),
+                cfg.policy_context.executor_kind,
+                tools.len().saturating_sub(core_tool_count),
+                &memories,
+                pending_approvals,
+                &current_time,
+                active_briefs.as_deref(),
+            );
+            (tools, system_prompt)
+        }
+        .instrument(info_span!("agent.context"))
+        .await;
+
+        // Step 3: Resolve provider, degrading as the budget runs low. The
+        // cheap-model step only applies when the budget role names a model
+        // other than the default.
+        let (mut degradation, mut newly_degraded) = cfg.budget.announce_degradation();
+        if !cfg.router.has_distinct_role(BUDGET_MODEL_ROLE) {
+            degradation.cheap_model = false;
+            newly_degraded.cheap_model = false;
+        }
+        if newly_degraded.is_active() {
+            let percent = cfg.budget.session_percent().max(cfg.budget.daily_percent());
+            info!(percent, ?degradation, "budget degradation applied");
+            send_text(
+                cfg,
+                &format!(
+                    "\u{26A0}\u{FE0F} Budget at {percent}%: {}. \
+                     Thresholds are under [budget.degradation] in config.toml.",
+                    newly_degraded.describe().join(", ")
+                ),
+            )
+            .await;
+        }
+        let role = degradation.cheap_model.then_some(BUDGET_MODEL_ROLE);
+        let provider = match cfg.router.resolve(role, None) {
             Ok(p) => p,
             Err(e) => {
                 error!(error = %e, "failed to resolve LLM provider");
@@ -489,7 +571,15 @@ async fn run_agent_turn(
         };
 
         // Step 4–5: Trim, budget check, LLM call — with overflow retry
-        let mut trimmed = trim_messages(conversation, cfg.config.budget.max_tokens_per_session);
+        let mut trimmed = if degradation.shorten_context {
+            trim_messages_to_fraction(
+                conversation,
+                cfg.budget.session_limit(),
+                cfg.budget.config().degradation.shortened_context_fraction,
+            )
+        } else {
+            trim_messages(conversation, cfg.budget.session_limit())
+        };
         let mut overflow_retries: u32 = 0;
 
         let response = loop {
@@ -531,8 +621,14 @@ async fn run_agent_turn(
                 stop_sequences: vec![],
             };
 
-            match provider.complete(request).await {
-                Ok(r) => break r,
+            llm_call_count = llm_call_count.saturating_add(1);
+            tracing::Span::current().record("llm_calls", llm_call_count);
+            let span = llm_span(provider.model_id(), "chat");
+            match provider.complete(request).instrument(span.clone()).await {
+                Ok(r) => {
+                    record_llm_usage(&span, &r.usage, &cfg.budget);
+                    break r;
+                }
                 Err(e) if e.is_context_overflow() && overflow_retries < MAX_OVERFLOW_RETRIES => {
                     overflow_retries = overflow_retries.saturating_add(1);
                     let fraction =
@@ -543,7 +639,7 @@ async fn run_agent_turn(
                     );
                     trimmed = trim_messages_to_fraction(
                         conversation,
-                        cfg.config.budget.max_tokens_per_session,
+                        cfg.budget.session_limit(),
                         fraction,
                     );
                 }
@@ -563,19 +659,36 @@ async fn run_agent_turn(
 
         // Step 7: Process response content parts
         let mut tool_results: Vec<(String, crate::tools::ToolResult)> = Vec::new();
+        let mut tool_durations: HashMap<&str, Duration> = HashMap::new();
         let mut assistant_content: Vec<ContentPart> = Vec::new();
 
-        for part in &response.content {
+        // The last text of the final response carries the feedback buttons.
+        let feedback_index =
+            if response.stop_reason != StopReason::ToolUse && cfg.agent_config.learning.feedback {
+                response
+                    .content
+                    .iter()
+                    .rposition(|p| matches!(p, ContentPart::Text { .. }))
+            } else {
+                None
+            };
+
+        for (index, part) in response.content.iter().enumerate() {
             match part {
                 ContentPart::Text { text } => {
                     assistant_content.push(part.clone());
-                    send_text(cfg, text).await;
+                    if feedback_index == Some(index) {
+                        send_text_with_feedback(cfg, text, &turn_memory_ids, &turn_tools).await;
+                    } else {
+                        send_text(cfg, text).await;
+                    }
                 }
                 ContentPart::ToolUse { id, name, input } => {
                     assistant_content.push(part.clone());
 
                     // Check per-turn tool call limit
                     tool_call_count = tool_call_count.saturating_add(1);
+                    tracing::Span::current().record("tool_calls", tool_call_count);
                     if let Err(e) = cfg.budget.check_tool_calls(tool_call_count) {
                         tool_results.push((
                             id.clone(),
@@ -586,16 +699,24 @@ async fn run_agent_turn(
 
                     // Policy gate
                     let trusted_domain = trusted_domain_for_tool(&cfg.memory, name, input).await;
-                    let decision = check_policy(name, input, &cfg.policy_context, &|domain| {
+                    let decision = match check_policy(name, input, &cfg.policy_context, &|domain| {
                         trusted_domain.as_deref() == Some(domain)
-                    });
+                    }) {
+                        // MCP tools run on the host; writes always need approval.
+                        PolicyDecision::Allow if cfg.tool_router.requires_approval(name) => {
+                            PolicyDecision::RequireApproval
+                        }
+                        decision => decision,
+                    };
 
                     let result = match decision {
                         PolicyDecision::Allow => {
-                            let r = cfg
-                                .tool_router
-                                .execute_for_user(name, input, Some(cfg.user_id))
-                                .await;
+                            if !turn_tools.contains(name) {
+                                turn_tools.push(name.clone());
+                            }
+                            let started = Instant::now();
+                            let r = execute_tool(cfg, name, input).await;
+                            tool_durations.insert(id.as_str(), started.elapsed());
                             // Track tools created/modified for observer reflection.
                             if name == "create_tool" && !r.is_error {
                                 if let Some(tool_name) = input.get("name").and_then(|v| v.as_str())
@@ -615,13 +736,24 @@ async fn run_agent_turn(
                                 cfg.user_id,
                             );
 
+                            let mut prompt = format!("Tool <b>{name}</b> needs approval");
+                            if let Some(preview) =
+                                cfg.tool_router.approval_preview(name, input).await
+                            {
+                                prompt.push_str(&format!(
+                                    "\n\n<b>Preview:</b>\n<pre>{}</pre>",
+                                    escape_html(&preview)
+                                ));
+                            }
+
                             let _ = cfg
                                 .telegram_tx
                                 .send(TelegramOutbound {
                                     user_id: cfg.user_id,
-                                    text: Some(format!("Tool <b>{name}</b> needs approval")),
+                                    text: Some(prompt),
                                     file_path: None,
                                     approval_keyboard: Some((approval_id, name.clone())),
+                                    keyboard: None,
                                 })
                                 .await;
 
@@ -668,6 +800,42 @@ async fn run_agent_turn(
             }
         }
 
+        // Step 9b: Persist tool calls and their results for transcripts.
+        // The call row also records the outcome and, if the tool ran, its duration.
+        for (id, result) in &tool_results {
+            let duration_ms = tool_durations
+                .get(id.as_str())
+                .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
+            let call = response.content.iter().find_map(|p| match p {
+                ContentPart::ToolUse {
+                    id: use_id,
+                    name,
+                    input,
+                } if use_id == id => Some(serde_json::json!({
+                    "name": name,
+                    "input": input,
+                    "is_error": result.is_error,
+                    "duration_ms": duration_ms,
+                })),
+                _ => None,
+            });
+            let entries = call
+                .map(|c| ("tool_call", c.to_string()))
+                .into_iter()
+                .chain(std::iter::once(("tool_result", result.content.clone())));
+            for (role, content) in entries {
+                let entry = ConversationEntry {
+                    session_id: cfg.session_id.clone(),
+                    role: role.to_owned(),
+                    content: clip_for_log(&content, crate::memory::MAX_CONTENT_SIZE),
+                    tokens_used: None,
+                };
+                if let Err(e) = cfg.memory.save_conversation(entry).await {
+                    warn!(error = %e, role, "failed to save tool conversation entry");
+                }
+            }
+        }
+
         // Step 10: If there were tool calls, add tool results to conversation
         if !tool_results.is_empty() {
             let result_parts: Vec<ContentPart> = tool_results
@@ -693,6 +861,53 @@ async fn run_agent_turn(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Tracing helpers
+// ---------------------------------------------------------------------------
+
+/// Span for a single provider call; usage fields are filled in on success.
+fn llm_span(model: &str, purpose: &'static str) -> tracing::Span {
+    info_span!(
+        "llm.complete",
+        gen_ai.request.model = model,
+        purpose,
+        gen_ai.usage.input_tokens = tracing::field::Empty,
+        gen_ai.usage.output_tokens = tracing::field::Empty,
+        budget.session_used = tracing::field::Empty,
+        budget.daily_used = tracing::field::Empty,
+    )
+}
+
+/// Record token usage and the resulting budget consumption on an LLM span.
+///
+/// Budgets are token-denominated, so token counts are the cost attributes.
+fn record_llm_usage(span: &tracing::Span, usage: &UsageStats, budget: &SessionBudget) {
+    span.record("gen_ai.usage.input_tokens", usage.input_tokens);
+    span.record("gen_ai.usage.output_tokens", usage.output_tokens);
+    span.record("budget.session_used", budget.session_used());
+    span.record("budget.daily_used", budget.daily_used());
+}
+
+/// Execute a tool for the session owner inside a `tool.execute` span.
+async fn execute_tool(
+    cfg: &SessionConfig,
+    name: &str,
+    input: &serde_json::Value,
+) -> crate::tools::ToolResult {
+    let span = info_span!(
+        "tool.execute",
+        tool.name = name,
+        tool.is_error = tracing::field::Empty
+    );
+    let result = cfg
+        .tool_router
+        .execute_for_user(name, input, Some(cfg.user_id))
+        .instrument(span.clone())
+        .await;
+    span.record("tool.is_error", result.is_error);
+    result
+}
+
 // ---------------------------------------------------------------------------
 // Approval handling
 // ---------------------------------------------------------------------------
@@ -729,10 +944,10 @@ async fn handle_approval_resolved(
                 }
             }
 
-            let tool_result = cfg
-                .tool_router
-                .execute_for_user(&tool_name, &input, Some(cfg.user_id))
-                .await;
+            let tool_result = execute_tool(cfg, &tool_name, &input).await;
+            if tool_name == "create_tool" && !tool_result.is_error {
+                grant_created_tool_network(cfg, &input).await;
+            }
             send_text(cfg, &format!("Approved tool <b>{tool_name}</b> executed.")).await;
 
             // Add the tool result to conversation and trigger another turn
@@ -783,6 +998,9 @@ async fn handle_approval_resolved(
         ApprovalResult::WrongUser => {
             warn!(session_id = %cfg.session_id, "wrong user attempted approval resolution");
         }
+        ApprovalResult::AlreadyResolved { tool_name, .. } => {
+            debug!(session_id = %cfg.session_id, tool = %tool_name, "approval already resolved");
+        }
     }
 }
 
@@ -850,8 +1068,53 @@ async fn send_text(cfg: &SessionConfig, text: &str) {
         text: Some(text.to_owned()),
         file_path: None,
         approval_keyboard: None,
+        keyboard: None,
     };
-    if let Err(e) = cfg.telegram_tx.send(msg).await {
+    if let Err(e) = cfg
+        .telegram_tx
+        .send(msg)
+        .instrument(info_span!("outbound.send", channel = "telegram"))
+        .await
+    {
+        error!(error = %e, "failed to send outbound telegram message");
+    }
+}
+
+/// Record the turn for feedback and send its final text with 👍/👎 buttons.
+///
+/// Falls back to a plain message if the turn cannot be recorded.
+async fn send_text_with_feedback(
+    cfg: &SessionConfig,
+    text: &str,
+    memory_ids: &[i64],
+    tools_used: &[String],
+) {
+    let turn = TurnRecord {
+        turn_id: uuid::Uuid::new_v4().simple().to_string(),
+        session_id: cfg.session_id.clone(),
+        memory_ids: memory_ids.to_vec(),
+        tools_used: tools_used.to_vec(),
+    };
+    let turn_id = turn.turn_id.clone();
+    if let Err(e) = cfg.memory.record_turn(turn).await {
+        warn!(error = %e, "failed to record turn for feedback");
+        send_text(cfg, text).await;
+        return;
+    }
+
+    let msg = TelegramOutbound {
+        user_id: cfg.user_id,
+        text: Some(text.to_owned()),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard: Some(Keyboard::Feedback(turn_id)),
+    };
+    if let Err(e) = cfg
+        .telegram_tx
+        .send(msg)
+        .instrument(info_span!("outbound.send", channel = "telegram"))
+        .await
+    {
         error!(error = %e, "failed to send outbound telegram message");
     }
 }
@@ -866,6 +1129,43 @@ fn last_user_text(conversation: &[Message]) -> String {
         .unwrap_or_default()
 }
 
+/// Clip content to fit the conversation log limit, marking the cut.
+fn clip_for_log(content: &str, max_bytes: usize) -> String {
+    const NOTICE: &str = "\n...[truncated]";
+    if content.len() <= max_bytes {
+        return content.to_owned();
+    }
+    let mut end = max_bytes.saturating_sub(NOTICE.len());
+    while end > 0 && !content.is_char_boundary(end) {
+        end = end.saturating_sub(1);
+    }
+    format!("{}{NOTICE}", &content[..end])
+}
+
+/// Record the network grant of a dynamic tool the user approved creating.
+async fn grant_created_tool_network(cfg: &SessionConfig, input: &serde_json::Value) {
+    let Some(name) = input.get("name").and_then(|v| v.as_str()) else {
+        return;
+    };
+    let mut manifest = match crate::tools::core::parse_manifest(input) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            warn!(error = %e, tool = name, "approved tool has an invalid manifest");
+            return;
+        }
+    };
+    if manifest.validate().is_err() || manifest.needs_network.is_empty() {
+        return;
+    }
+    if let Err(e) = cfg
+        .memory
+        .grant_tool_network(name, &manifest.needs_network, TrustSource::User)
+        .await
+    {
+        warn!(error = %e, tool = name, "failed to persist tool network grant");
+    }
+}
+
 /// Resolve trusted domain from trust ledger for domain-sensitive tools.
 async fn trusted_domain_for_tool(
     memory: &MemoryEngine,
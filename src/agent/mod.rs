@@ -7,25 +7,32 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, Mutex};
 use tracing::{info, warn};
 
+pub mod admission;
 pub mod approval;
 pub mod budget;
 pub mod context;
 pub mod identity;
 pub mod r#loop;
 pub mod policy;
+pub mod reload;
 pub mod session_manager;
+pub mod soul;
 
 pub use r#loop::SessionEvent;
 
-use crate::config::{AgentConfig, Config, RuntimePaths};
+use crate::config::{AgentConfig, BudgetConfig, Config, RuntimePaths};
+use crate::heartbeat::health::HealthReport;
 use crate::memory::MemoryEngine;
 use crate::observer::ObserverEvent;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::tools::ToolRouter;
 
+use self::admission::Admission;
 use self::approval::{ApprovalManager, ApprovalResult};
 use self::budget::{DailyBudget, SessionBudget};
 use self::policy::PolicyContext;
@@ -33,7 +40,7 @@ use self::r#loop::SessionConfig;
 use self::session_manager::SessionManager;
 
 /// Outbound message from agent to Telegram.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TelegramOutbound {
     /// Target Telegram user ID.
     pub user_id: i64,
@@ -43,6 +50,25 @@ pub struct TelegramOutbound {
     pub file_path: Option<String>,
     /// Optional approval keyboard (approval_id, description).
     pub approval_keyboard: Option<(String, String)>,
+    /// Optional inline keyboard other than approval.
+    pub keyboard: Option<Keyboard>,
+}
+
+/// Inline keyboard attached to an outbound message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Keyboard {
+    /// 👍/👎 feedback buttons for the given turn id.
+    Feedback(String),
+    /// Memory contradiction review buttons for the pair (older_id, newer_id).
+    Review(i64, i64),
+    /// Approve/reject buttons for a proposed skill memory id.
+    Skill(i64),
+    /// Approve/reject buttons for a proposed soul version id.
+    Soul(i64),
+    /// Re-enable/fix buttons for a dynamic tool disabled for failing.
+    ToolDisabled(String),
+    /// Send/drop buttons for a message held while the executor is down.
+    HeldMessage,
 }
 
 /// Session channel buffer size.
@@ -77,12 +103,18 @@ pub struct SessionRouter {
     config: Arc<Config>,
     /// Agent-owned configuration.
     agent_config: Arc<AgentConfig>,
+    /// Budget limits for new sessions; replaced on config reload.
+    budget_config: std::sync::RwLock<BudgetConfig>,
     /// Optional channel for observer idle events.
     observer_tx: Option<mpsc::Sender<ObserverEvent>>,
     /// Resolved runtime paths.
     paths: RuntimePaths,
     /// Session persistence manager.
     session_manager: Arc<SessionManager>,
+    /// Latest heartbeat health report, used for admission.
+    health: std::sync::RwLock<Option<HealthReport>>,
+    /// Messages held while the executor is down, oldest first, keyed by user ID.
+    held: Mutex<HashMap<i64, Vec<String>>>,
 }
 
 impl std::fmt::Debug for SessionRouter {
@@ -119,25 +151,128 @@ impl SessionRouter {
             approval_manager,
             policy_context,
             telegram_tx,
+            budget_config: std::sync::RwLock::new(config.budget.clone()),
             config,
             agent_config,
             observer_tx,
             paths,
             session_manager,
+            health: std::sync::RwLock::new(None),
+            held: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Route a user message to the appropriate session, creating one if needed.
+    /// Store the latest heartbeat health report for admission checks.
+    pub fn record_health(&self, report: &HealthReport) {
+        if let Ok(mut health) = self.health.write() {
+            *health = Some(report.clone());
+        }
+    }
+
+    /// Check whether a user message may be routed right now.
     ///
-    /// Session key format: `user_{user_id}`.
+    /// See [`admission`] for the rules.
+    pub fn admission(&self) -> Admission {
+        let spec = self.router.resolve_spec(None, None);
+        let outcome = self.router.provider_outcome(&spec);
+        let health = self.health.read().ok();
+        admission::check(
+            health.as_ref().and_then(|h| h.as_ref()),
+            &spec,
+            &outcome,
+            chrono::Utc::now(),
+        )
+    }
+
+    /// Route a user message to the appropriate session, creating one if needed.
     ///
-    /// If the channel for an existing session is full or closed, the dead session
-    /// is replaced with a fresh one.
+    /// When the model provider or the executor is down (see [`admission`]),
+    /// the user gets a status message instead; with the executor down the
+    /// message is held until [`Self::release_held`].
     ///
     /// # Errors
     ///
     /// Returns an error if the event cannot be sent after creating a new session.
     pub async fn route_message(&self, user_id: i64, text: String) -> anyhow::Result<()> {
+        match self.admission() {
+            Admission::Admit => self.deliver(user_id, text).await,
+            Admission::ProviderDown {
+                spec,
+                error,
+                retry_in_secs,
+            } => {
+                info!(spec = %spec, "refusing message, model provider is failing");
+                let error = error.map(|e| self.tool_router.redactor().redact(&e));
+                let notice =
+                    admission::format_provider_down(&spec, error.as_deref(), retry_in_secs);
+                self.notify(user_id, notice, None).await;
+                Ok(())
+            }
+            Admission::ExecutorDown { executor, error } => {
+                info!(executor = %executor, "holding message, executor is down");
+                let held = {
+                    let mut held = self.held.lock().await;
+                    let queue = held.entry(user_id).or_default();
+                    queue.push(text);
+                    queue.len()
+                };
+                let error = error.map(|e| self.tool_router.redactor().redact(&e));
+                let notice = admission::format_executor_down(&executor, error.as_deref(), held);
+                self.notify(user_id, notice, Some(Keyboard::HeldMessage))
+                    .await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Send or drop the messages held for `user_id` while the executor was down.
+    ///
+    /// Sent messages go to the session as one turn, in the order received,
+    /// with a note that commands will fail unless the executor has recovered
+    /// meanwhile. Returns how many messages were released (0 if none).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages cannot be delivered to a session.
+    pub async fn release_held(&self, user_id: i64, send: bool) -> anyhow::Result<usize> {
+        let Some(texts) = self.held.lock().await.remove(&user_id) else {
+            return Ok(0);
+        };
+        let count = texts.len();
+        if send && count > 0 {
+            let text = texts.join("\n\n");
+            let text = match self.admission() {
+                Admission::ExecutorDown { .. } => {
+                    format!("{}\n\n{text}", admission::EXECUTOR_DOWN_NOTE)
+                }
+                _ => text,
+            };
+            self.deliver(user_id, text).await?;
+        }
+        Ok(count)
+    }
+
+    /// Send a status message to the user, logging delivery failures.
+    async fn notify(&self, user_id: i64, text: String, keyboard: Option<Keyboard>) {
+        let msg = TelegramOutbound {
+            user_id,
+            text: Some(text),
+            file_path: None,
+            approval_keyboard: None,
+            keyboard,
+        };
+        if let Err(e) = self.telegram_tx.send(msg).await {
+            warn!(error = %e, "failed to send admission notice");
+        }
+    }
+
+    /// Deliver a message to the user's session, creating one if needed.
+    ///
+    /// Session key format: `user_{user_id}`.
+    ///
+    /// If the channel for an existing session is full or closed, the dead session
+    /// is replaced with a fresh one.
+    async fn deliver(&self, user_id: i64, text: String) -> anyhow::Result<()> {
         let session_key = format!("user_{user_id}");
 
         let mut sessions = self.sessions.lock().await;
@@ -149,10 +284,12 @@ impl SessionRouter {
                 Err(mpsc::error::TrySendError::Closed(_)) => {
                     info!(session = %session_key, "session channel closed, creating new session");
                     sessions.remove(&session_key);
+                    self.tool_router.end_session(&session_key);
                 }
                 Err(mpsc::error::TrySendError::Full(_)) => {
                     warn!(session = %session_key, "session channel full, replacing session");
                     sessions.remove(&session_key);
+                    self.tool_router.end_session(&session_key);
                 }
             }
         }
@@ -171,10 +308,11 @@ impl SessionRouter {
         }
 
         let spawn_key = session_key.clone();
-        tokio::spawn(async move {
+        let scope = UsageScope::session("agent", &session_key);
+        tokio::spawn(usage::scoped(scope, async move {
             r#loop::run_session(session_cfg, rx).await;
             info!(session = %spawn_key, "session task ended");
-        });
+        }));
 
         tx.send(SessionEvent::UserMessage(text))
             .await
@@ -193,7 +331,10 @@ impl SessionRouter {
         let session_id = match &result {
             ApprovalResult::Approved { session_id, .. } => session_id.clone(),
             ApprovalResult::Denied { session_id, .. } => session_id.clone(),
-            ApprovalResult::Expired | ApprovalResult::NotFound | ApprovalResult::WrongUser => {
+            ApprovalResult::Expired
+            | ApprovalResult::NotFound
+            | ApprovalResult::WrongUser
+            | ApprovalResult::AlreadyResolved { .. } => {
                 return Ok(());
             }
         };
@@ -258,6 +399,7 @@ impl SessionRouter {
                 warn!(error = %e, session = %session_key, "failed to mark session completed");
             }
             let _ = tx.send(SessionEvent::Shutdown).await;
+            self.tool_router.end_session(&session_key);
             true
         } else {
             false
@@ -271,6 +413,7 @@ impl SessionRouter {
             if let Err(e) = tx.send(SessionEvent::Shutdown).await {
                 warn!(session = %key, error = %e, "failed to send shutdown to session");
             }
+            self.tool_router.end_session(&key);
         }
         info!("all sessions shut down");
     }
@@ -280,10 +423,49 @@ impl SessionRouter {
         self.sessions.lock().await.len()
     }
 
+    /// Returns the IDs of active sessions, sorted.
+    pub async fn session_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.sessions.lock().await.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Budget limits currently applied to sessions.
+    pub fn budget_config(&self) -> BudgetConfig {
+        self.budget_config
+            .read()
+            .map(|b| b.clone())
+            .unwrap_or_else(|_| self.config.budget.clone())
+    }
+
+    /// Apply reloaded budget limits to the daily budget, new sessions, and
+    /// every active session.
+    pub async fn apply_budget(&self, budget: BudgetConfig) {
+        self.daily_budget.set_limit(budget.max_tokens_per_day);
+        self.daily_budget
+            .set_carry_over_percent(budget.carry_over_percent);
+        if let Ok(mut current) = self.budget_config.write() {
+            *current = budget.clone();
+        }
+        // Clone the senders so a full session channel cannot stall routing.
+        let senders: Vec<(String, mpsc::Sender<SessionEvent>)> = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .map(|(key, tx)| (key.clone(), tx.clone()))
+            .collect();
+        for (key, tx) in senders {
+            if let Err(e) = tx.send(SessionEvent::BudgetReloaded(budget.clone())).await {
+                warn!(session = %key, error = %e, "failed to send reloaded budget to session");
+            }
+        }
+    }
+
     /// Build a [`SessionConfig`] for a new session.
     fn build_session_config(&self, session_id: String, user_id: i64) -> SessionConfig {
         let session_budget =
-            SessionBudget::new(Arc::clone(&self.daily_budget), self.config.budget.clone());
+            SessionBudget::new(Arc::clone(&self.daily_budget), self.budget_config());
 
         let identity_document = identity::load_identity(&self.paths.identity_md);
 
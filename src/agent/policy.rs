@@ -105,17 +105,50 @@ pub fn check_policy(
 ) -> PolicyDecision {
     match tool_name {
         "execute_command" => check_execute_command(input, ctx),
-        "web_fetch" => PolicyDecision::Allow,
+        "web_fetch" | "web_search" => PolicyDecision::Allow,
         "web_request" => check_domain_policy(input, ctx, is_domain_trusted),
         "browser" => check_browser_policy(input, ctx, is_domain_trusted),
         "docker_manage" => check_docker_manage(input),
-        "memory_search" | "memory_save" | "send_message" | "create_tool" | "manage_brief"
-        | "read_messages" => PolicyDecision::Allow,
+        "send_message" => check_send_message(input),
+        "email" => check_email(input),
+        "create_tool" => check_create_tool(input),
+        "memory_search" | "memory_save" | "manage_brief" | "read_messages" | "rss_subscribe"
+        | "rss_list" => PolicyDecision::Allow,
         // Dynamic tools execute inside the sandbox via the executor, so they are allowed.
         _ => PolicyDecision::Allow,
     }
 }
 
+/// Check create_tool: a tool whose manifest asks for network access needs approval.
+fn check_create_tool(input: &serde_json::Value) -> PolicyDecision {
+    let needs_network = input
+        .pointer("/manifest/needs_network")
+        .and_then(|v| v.as_array())
+        .is_some_and(|domains| !domains.is_empty());
+    if needs_network {
+        PolicyDecision::RequireApproval
+    } else {
+        PolicyDecision::Allow
+    }
+}
+
+/// Check send_message: templated sends need the user to approve the filled text.
+fn check_send_message(input: &serde_json::Value) -> PolicyDecision {
+    if input.get("template").is_some_and(|v| !v.is_null()) {
+        PolicyDecision::RequireApproval
+    } else {
+        PolicyDecision::Allow
+    }
+}
+
+/// Check email: reading is allowed, every send needs approval.
+fn check_email(input: &serde_json::Value) -> PolicyDecision {
+    match input.get("action").and_then(|v| v.as_str()) {
+        Some("list" | "read") => PolicyDecision::Allow,
+        _ => PolicyDecision::RequireApproval,
+    }
+}
+
 /// Check execute_command: allow if Docker, restrict dangerous commands if Direct.
 fn check_execute_command(input: &serde_json::Value, ctx: &PolicyContext) -> PolicyDecision {
     match ctx.executor_kind {
@@ -0,0 +1,300 @@
+//! In-place config reload.
+//!
+//! Triggered by `SIGHUP` or the owner's `/reload` command. Re-reads
+//! `config.toml` and `agent.toml` and hot-applies the settings that can change
+//! without a restart:
+//!
+//! - `[budget]` — daily limit, new sessions, and every active session
+//! - `[models].roles` / `[models].skills` — overrides pointing at already
+//!   loaded providers
+//! - `channels.telegram.allowed_users`
+//! - `heartbeat.interval_secs`
+//!
+//! Every other change is listed as requiring a restart. A file that fails to
+//! parse leaves the running config untouched.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use tracing::info;
+
+use crate::config::{
+    load_agent_config, load_config, AgentConfig, Config, MessagingConfig, RuntimePaths,
+};
+use crate::providers::router::ModelRouter;
+use crate::telegram::ui::escape_html;
+
+use super::SessionRouter;
+
+/// Outcome of a config reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Settings applied to the running process.
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart.
+    pub restart_required: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Whether the files matched the running config.
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// Reloads config files and applies hot-reloadable settings.
+pub struct ConfigReloader {
+    paths: RuntimePaths,
+    /// Config the process started with; restart-only settings are compared
+    /// against it, so they keep being reported until the next restart.
+    startup_config: Arc<Config>,
+    /// Agent config the process started with.
+    startup_agent_config: Arc<AgentConfig>,
+    session_router: Arc<SessionRouter>,
+    model_router: Arc<ModelRouter>,
+    allowed_users: RwLock<Vec<i64>>,
+    heartbeat_interval_secs: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for ConfigReloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigReloader")
+            .field("config_toml", &self.paths.config_toml)
+            .field("agent_toml", &self.paths.agent_toml)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConfigReloader {
+    /// Create a reloader seeded with the running configuration.
+    pub fn new(
+        paths: RuntimePaths,
+        config: Arc<Config>,
+        agent_config: Arc<AgentConfig>,
+        session_router: Arc<SessionRouter>,
+        model_router: Arc<ModelRouter>,
+    ) -> Self {
+        let allowed_users = RwLock::new(config.channels.telegram.allowed_users.clone());
+        let heartbeat_interval_secs =
+            Arc::new(AtomicU64::new(agent_config.heartbeat.interval_secs));
+        Self {
+            paths,
+            startup_config: config,
+            startup_agent_config: agent_config,
+            session_router,
+            model_router,
+            allowed_users,
+            heartbeat_interval_secs,
+        }
+    }
+
+    /// Whether a Telegram user may talk to the agent.
+    pub fn is_allowed(&self, user_id: i64) -> bool {
+        self.allowed_users
+            .read()
+            .is_ok_and(|users| users.contains(&user_id))
+    }
+
+    /// Currently allowed Telegram users.
+    pub fn allowed_users(&self) -> Vec<i64> {
+        self.allowed_users
+            .read()
+            .map(|users| users.clone())
+            .unwrap_or_default()
+    }
+
+    /// The owner: the first allowed user.
+    pub fn owner(&self) -> Option<i64> {
+        self.allowed_users
+            .read()
+            .ok()
+            .and_then(|users| users.first().copied())
+    }
+
+    /// Live heartbeat interval in seconds, shared with the heartbeat loop.
+    pub fn heartbeat_interval(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.heartbeat_interval_secs)
+    }
+
+    /// Re-read both config files and apply what can change in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read or parsed; nothing is
+    /// applied in that case.
+    pub async fn reload(&self) -> anyhow::Result<ReloadReport> {
+        let config = load_config(&self.paths.config_toml)?;
+        let agent_config = load_agent_config(&self.paths.agent_toml)?;
+
+        let mut report = ReloadReport {
+            restart_required: restart_required_changes(
+                &self.startup_config,
+                &self.startup_agent_config,
+                &config,
+                &agent_config,
+            ),
+            ..ReloadReport::default()
+        };
+
+        if config.budget != self.session_router.budget_config() {
+            self.session_router
+                .apply_budget(config.budget.clone())
+                .await;
+            report.applied.push("budget".to_owned());
+        }
+
+        if self
+            .model_router
+            .overrides_differ(&config.models.roles, &config.models.skills)
+        {
+            let missing = self
+                .model_router
+                .set_overrides(config.models.roles.clone(), config.models.skills.clone());
+            report
+                .applied
+                .push("models.roles, models.skills".to_owned());
+            report.restart_required.extend(
+                missing
+                    .into_iter()
+                    .map(|spec| format!("models: load provider {spec}")),
+            );
+        }
+
+        let new_users = &config.channels.telegram.allowed_users;
+        if *new_users != self.allowed_users() {
+            if let Ok(mut users) = self.allowed_users.write() {
+                users.clone_from(new_users);
+            }
+            report
+                .applied
+                .push("channels.telegram.allowed_users".to_owned());
+        }
+
+        // Zero would stall the heartbeat timer; treat it as one second.
+        let new_interval = agent_config.heartbeat.interval_secs.max(1);
+        let old_interval = self
+            .heartbeat_interval_secs
+            .swap(new_interval, Ordering::Relaxed);
+        if old_interval != new_interval {
+            report.applied.push(format!(
+                "heartbeat.interval_secs ({old_interval}s → {new_interval}s)"
+            ));
+        }
+
+        info!(
+            applied = ?report.applied,
+            restart_required = ?report.restart_required,
+            "config reloaded"
+        );
+        Ok(report)
+    }
+}
+
+/// List changed settings that cannot be applied without a restart.
+///
+/// Hot-reloadable settings are ignored.
+pub fn restart_required_changes(
+    old: &Config,
+    old_agent: &AgentConfig,
+    new: &Config,
+    new_agent: &AgentConfig,
+) -> Vec<String> {
+    let checks: [(&str, bool); 23] = [
+        ("models.default", old.models.default != new.models.default),
+        (
+            "models.fixtures",
+            old.models.fixtures != new.models.fixtures,
+        ),
+        (
+            "channels.telegram.bot_token_env",
+            old.channels.telegram.bot_token_env != new.channels.telegram.bot_token_env,
+        ),
+        (
+            "budget.timezone",
+            old.budget.timezone != new.budget.timezone,
+        ),
+        ("sandbox", old.sandbox != new.sandbox),
+        ("egress", old.egress != new.egress),
+        ("privacy", old.privacy != new.privacy),
+        ("browser", old.browser != new.browser),
+        ("whatsapp", old.whatsapp != new.whatsapp),
+        ("search", old.search != new.search),
+        ("email", old.email != new.email),
+        ("mcp_servers", old.mcp_servers != new.mcp_servers),
+        ("admin_api", old.admin_api != new.admin_api),
+        ("telemetry", old.telemetry != new.telemetry),
+        (
+            "personality",
+            old_agent.personality != new_agent.personality,
+        ),
+        (
+            "heartbeat.enabled",
+            old_agent.heartbeat.enabled != new_agent.heartbeat.enabled,
+        ),
+        (
+            "heartbeat.proactive",
+            old_agent.heartbeat.proactive != new_agent.heartbeat.proactive
+                || old_agent.heartbeat.proactive_interval_mins
+                    != new_agent.heartbeat.proactive_interval_mins
+                || old_agent.heartbeat.proactive_budget != new_agent.heartbeat.proactive_budget,
+        ),
+        (
+            "heartbeat.feed_poll_mins",
+            old_agent.heartbeat.feed_poll_mins != new_agent.heartbeat.feed_poll_mins,
+        ),
+        ("learning", old_agent.learning != new_agent.learning),
+        ("sessions", old_agent.sessions != new_agent.sessions),
+        (
+            "messaging",
+            messaging_changed(&old_agent.messaging, &new_agent.messaging),
+        ),
+        (
+            "scheduled_tasks",
+            old_agent.scheduled_tasks != new_agent.scheduled_tasks,
+        ),
+        ("services", old_agent.services != new_agent.services),
+    ];
+    checks
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// Format a reload report as Telegram HTML.
+pub fn format_report(report: &ReloadReport) -> String {
+    if report.is_empty() {
+        return "<b>Config reloaded</b>\nNo changes.".to_owned();
+    }
+    let mut lines = vec!["<b>Config reloaded</b>".to_owned()];
+    if !report.applied.is_empty() {
+        lines.push(String::new());
+        lines.push("Applied:".to_owned());
+        lines.extend(
+            report
+                .applied
+                .iter()
+                .map(|s| format!("• {}", escape_html(s))),
+        );
+    }
+    if !report.restart_required.is_empty() {
+        lines.push(String::new());
+        lines.push("Needs restart:".to_owned());
+        lines.extend(
+            report
+                .restart_required
+                .iter()
+                .map(|s| format!("• {}", escape_html(s))),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Field-wise comparison: `Debug` output of the quiet-hours map is unordered.
+fn messaging_changed(a: &MessagingConfig, b: &MessagingConfig) -> bool {
+    a.update_frequency != b.update_frequency
+        || a.default_commitment != b.default_commitment
+        || a.quiet_hours != b.quiet_hours
+        || a.contact_quiet_hours != b.contact_quiet_hours
+        || a.unread_follow_up_days != b.unread_follow_up_days
+}
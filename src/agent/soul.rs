@@ -0,0 +1,460 @@
+//! Soul versioning: every change to `[personality].soul` is recorded.
+//!
+//! The heartbeat compares the soul in `agent.toml` with the active version in
+//! the `soul_versions` table. In `notify` mode a change is activated at once
+//! and its diff is sent to the owner; in `approve` mode it is stored as
+//! pending and only activated once the owner approves the diff. Sessions use
+//! the active version, so an unapproved edit never reaches the system prompt.
+//! Rejecting or reverting writes the active soul back to `agent.toml`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::agent::{Keyboard, TelegramOutbound};
+use crate::config::SoulModificationMode;
+use crate::memory::writer::WriteOp;
+use crate::memory::MemoryEngine;
+use crate::telegram::ui::escape_html;
+
+/// Maximum lines per side diffed line-by-line; larger souls are shown whole.
+const MAX_DIFF_LINES: usize = 300;
+
+/// Maximum characters of diff sent in one Telegram message.
+const MAX_DIFF_CHARS: usize = 3000;
+
+/// Lifecycle state of a soul version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoulStatus {
+    /// The version sessions currently use. At most one at a time.
+    Active,
+    /// Proposed by the agent, waiting for owner approval.
+    Pending,
+    /// Declined by the owner.
+    Rejected,
+    /// Was active, replaced by a later version.
+    Superseded,
+}
+
+impl SoulStatus {
+    /// Database representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Pending => "pending",
+            Self::Rejected => "rejected",
+            Self::Superseded => "superseded",
+        }
+    }
+
+    /// Parse the database representation.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(Self::Active),
+            "pending" => Some(Self::Pending),
+            "rejected" => Some(Self::Rejected),
+            "superseded" => Some(Self::Superseded),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded version of the soul.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoulVersion {
+    /// Version number (row id).
+    pub id: i64,
+    /// Full soul text.
+    pub content: String,
+    /// Current lifecycle state.
+    pub status: SoulStatus,
+    /// Creation time (SQLite `datetime('now')`, UTC).
+    pub created_at: String,
+}
+
+/// Outcome of comparing `agent.toml` with the active version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SoulChange {
+    /// No version existed; the file's soul was recorded as version 1.
+    Seeded(SoulVersion),
+    /// The change was activated immediately (`notify` mode).
+    Activated {
+        /// Version that was active before.
+        previous: SoulVersion,
+        /// Newly active version.
+        current: SoulVersion,
+    },
+    /// The change awaits owner approval (`approve` mode).
+    Proposed {
+        /// Version still in use.
+        active: SoulVersion,
+        /// Proposed version.
+        pending: SoulVersion,
+    },
+}
+
+type SoulRow = (i64, String, String, String);
+
+const SOUL_COLUMNS: &str = "id, content, status, created_at";
+
+fn from_row(row: SoulRow) -> SoulVersion {
+    let (id, content, status, created_at) = row;
+    SoulVersion {
+        id,
+        content,
+        status: SoulStatus::parse(&status).unwrap_or(SoulStatus::Superseded),
+        created_at,
+    }
+}
+
+/// Load the active soul version, if any has been recorded.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn active_soul(db: &SqlitePool) -> Result<Option<SoulVersion>> {
+    let row: Option<SoulRow> = sqlx::query_as(&format!(
+        "SELECT {SOUL_COLUMNS} FROM soul_versions WHERE status = 'active' \
+         ORDER BY id DESC LIMIT 1"
+    ))
+    .fetch_optional(db)
+    .await
+    .context("failed to load active soul")?;
+    Ok(row.map(from_row))
+}
+
+/// Load a soul version by id.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn get_version(db: &SqlitePool, id: i64) -> Result<Option<SoulVersion>> {
+    let row: Option<SoulRow> = sqlx::query_as(&format!(
+        "SELECT {SOUL_COLUMNS} FROM soul_versions WHERE id = ?1"
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .context("failed to load soul version")?;
+    Ok(row.map(from_row))
+}
+
+/// List the most recent soul versions, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn history(db: &SqlitePool, limit: usize) -> Result<Vec<SoulVersion>> {
+    let rows: Vec<SoulRow> = sqlx::query_as(&format!(
+        "SELECT {SOUL_COLUMNS} FROM soul_versions ORDER BY id DESC LIMIT ?1"
+    ))
+    .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+    .fetch_all(db)
+    .await
+    .context("failed to load soul history")?;
+    Ok(rows.into_iter().map(from_row).collect())
+}
+
+/// Record the soul found in `agent.toml` if it differs from the active one.
+///
+/// Returns `None` when nothing changed, or when the same text is already
+/// pending or was rejected (so an unchanged file is not re-proposed every
+/// tick). A new proposal replaces any older pending one.
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn sync_soul(
+    memory: &MemoryEngine,
+    file_soul: &str,
+    mode: SoulModificationMode,
+) -> Result<Option<SoulChange>> {
+    let db = memory.pool();
+    let Some(active) = active_soul(db).await? else {
+        let id = add_version(memory, file_soul, true, false).await?;
+        let seeded = get_version(db, id)
+            .await?
+            .context("seeded soul version vanished")?;
+        return Ok(Some(SoulChange::Seeded(seeded)));
+    };
+    if active.content == file_soul {
+        return Ok(None);
+    }
+
+    let latest: Option<(String,)> = sqlx::query_as(
+        "SELECT status FROM soul_versions WHERE content = ?1 ORDER BY id DESC LIMIT 1",
+    )
+    .bind(file_soul)
+    .fetch_optional(db)
+    .await
+    .context("failed to look up soul version")?;
+    if matches!(
+        latest.as_ref().map(|(s,)| s.as_str()),
+        Some("pending" | "rejected")
+    ) {
+        return Ok(None);
+    }
+
+    match mode {
+        SoulModificationMode::Notify => {
+            let id = add_version(memory, file_soul, true, false).await?;
+            let current = get_version(db, id)
+                .await?
+                .context("activated soul version vanished")?;
+            Ok(Some(SoulChange::Activated {
+                previous: active,
+                current,
+            }))
+        }
+        SoulModificationMode::Approve => {
+            let id = add_version(memory, file_soul, false, true).await?;
+            let pending = get_version(db, id)
+                .await?
+                .context("proposed soul version vanished")?;
+            Ok(Some(SoulChange::Proposed { active, pending }))
+        }
+    }
+}
+
+/// Approve a pending version, making it active.
+///
+/// Returns `false` if the version does not exist or is no longer pending.
+///
+/// # Errors
+///
+/// Returns an error if the write fails.
+pub async fn approve(memory: &MemoryEngine, id: i64) -> Result<bool> {
+    let approved = memory
+        .write_and_wait(|reply| WriteOp::ApproveSoulVersion { id, reply })
+        .await
+        .context("failed to approve soul version")?;
+    if approved > 0 {
+        info!(version = id, "soul change approved");
+    }
+    Ok(approved > 0)
+}
+
+/// Reject a pending version and restore the active soul in `agent.toml`.
+///
+/// Returns `false` if the version does not exist or is no longer pending.
+///
+/// # Errors
+///
+/// Returns an error if a query fails or `agent.toml` cannot be rewritten.
+pub async fn reject(memory: &MemoryEngine, id: i64, agent_toml: &Path) -> Result<bool> {
+    let rejected = memory
+        .write_and_wait(|reply| WriteOp::RejectSoulVersion { id, reply })
+        .await
+        .context("failed to reject soul version")?;
+    if rejected == 0 {
+        return Ok(false);
+    }
+    if let Some(active) = active_soul(memory.pool()).await? {
+        write_soul(agent_toml, &active.content)?;
+    }
+    info!(version = id, "soul change rejected");
+    Ok(true)
+}
+
+/// Make the content of an earlier version active again.
+///
+/// The revert is recorded as a new version so history stays linear, and
+/// `agent.toml` is rewritten to match. Returns the previous active version
+/// and the new one, or `None` if `id` does not exist.
+///
+/// # Errors
+///
+/// Returns an error if a query fails or `agent.toml` cannot be rewritten.
+pub async fn revert(
+    memory: &MemoryEngine,
+    id: i64,
+    agent_toml: &Path,
+) -> Result<Option<(Option<SoulVersion>, SoulVersion)>> {
+    let db = memory.pool();
+    let Some(target) = get_version(db, id).await? else {
+        return Ok(None);
+    };
+    let previous = active_soul(db).await?;
+    let new_id = add_version(memory, &target.content, true, false).await?;
+    write_soul(agent_toml, &target.content)?;
+    let current = get_version(db, new_id)
+        .await?
+        .context("reverted soul version vanished")?;
+    info!(from = id, version = new_id, "soul reverted");
+    Ok(Some((previous, current)))
+}
+
+/// Record a version through the writer; see [`WriteOp::AddSoulVersion`].
+async fn add_version(
+    memory: &MemoryEngine,
+    content: &str,
+    activate: bool,
+    replace_pending: bool,
+) -> Result<i64> {
+    memory
+        .write_and_wait(|reply| WriteOp::AddSoulVersion {
+            content: content.to_owned(),
+            activate,
+            replace_pending,
+            reply,
+        })
+        .await
+        .context("failed to insert soul version")
+}
+
+/// Replace `[personality].soul` in `agent.toml`, keeping the rest of the
+/// file (comments, ordering) intact.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, parsed, or written.
+pub fn write_soul(agent_toml: &Path, soul: &str) -> Result<()> {
+    let contents = std::fs::read_to_string(agent_toml)
+        .with_context(|| format!("failed to read {}", agent_toml.display()))?;
+    let mut doc: toml_edit::DocumentMut = contents
+        .parse()
+        .with_context(|| format!("failed to parse {}", agent_toml.display()))?;
+    let personality = doc
+        .entry("personality")
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+        .context("[personality] is not a table")?;
+    personality.insert("soul", toml_edit::value(soul));
+    std::fs::write(agent_toml, doc.to_string())
+        .with_context(|| format!("failed to write {}", agent_toml.display()))?;
+    Ok(())
+}
+
+/// Line diff between two souls: ` ` unchanged, `-` removed, `+` added.
+pub fn soul_diff(old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let mut out = Vec::with_capacity(a.len().saturating_add(b.len()));
+
+    if a.len() > MAX_DIFF_LINES || b.len() > MAX_DIFF_LINES {
+        out.extend(a.iter().map(|l| format!("- {l}")));
+        out.extend(b.iter().map(|l| format!("+ {l}")));
+        return out.join("\n");
+    }
+
+    // lcs[i][j] = length of the longest common subsequence of a[i..] and b[j..].
+    let mut lcs = vec![vec![0usize; b.len().saturating_add(1)]; a.len().saturating_add(1)];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i.saturating_add(1)][j.saturating_add(1)].saturating_add(1)
+            } else {
+                lcs[i.saturating_add(1)][j].max(lcs[i][j.saturating_add(1)])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(format!("  {}", a[i]));
+            i = i.saturating_add(1);
+            j = j.saturating_add(1);
+        } else if lcs[i.saturating_add(1)][j] >= lcs[i][j.saturating_add(1)] {
+            out.push(format!("- {}", a[i]));
+            i = i.saturating_add(1);
+        } else {
+            out.push(format!("+ {}", b[j]));
+            j = j.saturating_add(1);
+        }
+    }
+    out.extend(a[i..].iter().map(|l| format!("- {l}")));
+    out.extend(b[j..].iter().map(|l| format!("+ {l}")));
+    out.join("\n")
+}
+
+/// Render a diff as an HTML `<pre>` block, truncated for Telegram.
+pub fn format_diff_html(old: &str, new: &str) -> String {
+    let diff = soul_diff(old, new);
+    let mut shown: String = diff.chars().take(MAX_DIFF_CHARS).collect();
+    if diff.chars().count() > MAX_DIFF_CHARS {
+        shown.push_str("\n…");
+    }
+    format!("<pre>{}</pre>", escape_html(&shown))
+}
+
+/// Build callback data for the approve/reject buttons: `sv+:{id}` / `sv-:{id}`.
+pub fn soul_callback_data(approve: bool, id: i64) -> String {
+    if approve {
+        format!("sv+:{id}")
+    } else {
+        format!("sv-:{id}")
+    }
+}
+
+/// Parse callback data produced by [`soul_callback_data`].
+pub fn parse_soul_callback(data: &str) -> Option<(bool, i64)> {
+    if let Some(id) = data.strip_prefix("sv+:") {
+        return id.parse().ok().map(|id| (true, id));
+    }
+    data.strip_prefix("sv-:")
+        .and_then(|id| id.parse().ok())
+        .map(|id| (false, id))
+}
+
+/// Check `agent.toml` for a soul edit and notify the owner.
+///
+/// Called on every heartbeat tick. Seeding the first version is silent.
+///
+/// # Errors
+///
+/// Returns an error if `agent.toml` cannot be read or a query fails.
+pub async fn check_soul(
+    memory: &MemoryEngine,
+    agent_toml: &Path,
+    mode: SoulModificationMode,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    notify_user_id: i64,
+) -> Result<()> {
+    let agent_config = crate::config::load_agent_config(agent_toml)?;
+    let change = sync_soul(memory, &agent_config.personality.soul, mode).await?;
+
+    let (text, keyboard) = match change {
+        None | Some(SoulChange::Seeded(_)) => return Ok(()),
+        Some(SoulChange::Activated { previous, current }) => {
+            info!(version = current.id, "soul change activated");
+            (
+                format!(
+                    "<b>Soul updated</b> (v{} → v{})\n{}\nUndo with /soul revert {}",
+                    previous.id,
+                    current.id,
+                    format_diff_html(&previous.content, &current.content),
+                    previous.id
+                ),
+                None,
+            )
+        }
+        Some(SoulChange::Proposed { active, pending }) => {
+            info!(version = pending.id, "soul change awaiting approval");
+            (
+                format!(
+                    "<b>Soul change proposed</b> (v{} → v{})\n{}",
+                    active.id,
+                    pending.id,
+                    format_diff_html(&active.content, &pending.content)
+                ),
+                Some(Keyboard::Soul(pending.id)),
+            )
+        }
+    };
+
+    let msg = TelegramOutbound {
+        user_id: notify_user_id,
+        text: Some(text),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard,
+    };
+    if let Err(e) = telegram_tx.send(msg).await {
+        warn!(error = %e, "failed to send soul change notification");
+    }
+    Ok(())
+}
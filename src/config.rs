@@ -21,7 +21,7 @@ pub enum SoulModificationMode {
 }
 
 /// Top-level human-owned configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct Config {
     /// Model routing configuration.
     pub models: ModelsConfig,
@@ -52,10 +52,34 @@ pub struct Config {
     /// WhatsApp sidecar configuration.
     #[serde(default)]
     pub whatsapp: WhatsAppConfig,
+
+    /// Web search backend configuration.
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    /// Email account used by the `email` tool.
+    #[serde(default)]
+    pub email: EmailConfig,
+
+    /// MCP servers whose tools are exposed to the agent.
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerConfig>,
+
+    /// Localhost HTTP admin API.
+    #[serde(default)]
+    pub admin_api: AdminApiConfig,
+
+    /// Trace export for agent-loop spans.
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+
+    /// Automatic disabling of failing dynamic tools.
+    #[serde(default)]
+    pub tool_health: ToolHealthConfig,
 }
 
 /// Top-level agent-owned configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct AgentConfig {
     /// Agent personality settings.
     #[serde(default)]
@@ -87,7 +111,7 @@ pub struct AgentConfig {
 }
 
 /// Docker service definition persisted by the agent.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ServiceConfig {
     /// Service name (e.g. "ollama").
     pub name: String,
@@ -105,7 +129,7 @@ pub struct ServiceConfig {
 }
 
 /// Outbound messaging configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct MessagingConfig {
     /// How often to update the user during outbound tasks.
     #[serde(default = "default_update_frequency")]
@@ -114,6 +138,21 @@ pub struct MessagingConfig {
     /// Default commitment level for new briefs.
     #[serde(default = "default_commitment")]
     pub default_commitment: String,
+
+    /// Global quiet hours in local time (e.g. "22:00-08:00"). Outbound
+    /// messages composed inside the window are queued until it ends.
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+
+    /// Per-contact quiet hours keyed by contact name or alias. Overrides the
+    /// global window; "off" disables quiet hours for that contact.
+    #[serde(default)]
+    pub contact_quiet_hours: HashMap<String, String>,
+
+    /// Days an outbound message may stay unread before the user is asked
+    /// whether to follow up (0 disables).
+    #[serde(default = "default_unread_follow_up_days")]
+    pub unread_follow_up_days: u32,
 }
 
 impl Default for MessagingConfig {
@@ -121,12 +160,15 @@ impl Default for MessagingConfig {
         Self {
             update_frequency: default_update_frequency(),
             default_commitment: default_commitment(),
+            quiet_hours: None,
+            contact_quiet_hours: HashMap::new(),
+            unread_follow_up_days: default_unread_follow_up_days(),
         }
     }
 }
 
 /// Model routing: default model, per-role and per-skill overrides.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct ModelsConfig {
     /// Default model identifier (e.g. "anthropic/claude-sonnet-4-5-20250929").
     pub default: String,
@@ -138,17 +180,47 @@ pub struct ModelsConfig {
     /// Per-skill model overrides.
     #[serde(default)]
     pub skills: std::collections::HashMap<String, String>,
+
+    /// Record or replay provider completions (`[models.fixtures]`).
+    #[serde(default)]
+    pub fixtures: FixtureConfig,
+}
+
+/// Whether provider completions are recorded to or replayed from fixtures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureMode {
+    /// Call providers normally.
+    #[default]
+    Off,
+    /// Call providers and append each redacted completion to a fixture file.
+    Record,
+    /// Answer from fixture files instead of calling providers.
+    Replay,
+}
+
+/// Provider record/replay settings, see [`crate::providers::replay`].
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct FixtureConfig {
+    /// Record, replay, or off (default).
+    #[serde(default)]
+    pub mode: FixtureMode,
+
+    /// Directory with one JSONL fixture file per model spec. Required
+    /// unless `mode` is off.
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
 }
 
 /// Channel configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct ChannelsConfig {
     /// Telegram bot settings.
     pub telegram: TelegramConfig,
 }
 
 /// Telegram-specific configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct TelegramConfig {
     /// Environment variable name holding the bot token.
     pub bot_token_env: String,
@@ -158,7 +230,7 @@ pub struct TelegramConfig {
 }
 
 /// Personality and identity settings for the agent.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct PersonalityConfig {
     /// Human-readable agent name.
     #[serde(default = "default_personality_name")]
@@ -184,7 +256,7 @@ impl Default for PersonalityConfig {
 }
 
 /// Sandbox resource limits.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct SandboxConfig {
     /// Docker image for the sandbox container.
     #[serde(default = "default_sandbox_image")]
@@ -215,7 +287,7 @@ impl Default for SandboxConfig {
 }
 
 /// Budget limits for token usage and tool calls.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct BudgetConfig {
     /// Maximum tokens per agent session.
     #[serde(default = "default_session_tokens")]
@@ -232,6 +304,20 @@ pub struct BudgetConfig {
     /// Maximum dynamic tools included per LLM call.
     #[serde(default = "default_dynamic_tools_per_turn")]
     pub max_dynamic_tools_per_turn: u32,
+
+    /// What to give up as the budget runs low, before hard-stopping.
+    #[serde(default)]
+    pub degradation: DegradationConfig,
+
+    /// Timezone in which the daily budget resets: `local` (default), `utc`,
+    /// or a fixed offset such as `+08:00`.
+    #[serde(default = "default_budget_timezone")]
+    pub timezone: String,
+
+    /// Percentage (0–100) of yesterday's unused daily budget added to
+    /// today's limit (default 0).
+    #[serde(default)]
+    pub carry_over_percent: u8,
 }
 
 impl Default for BudgetConfig {
@@ -241,12 +327,54 @@ impl Default for BudgetConfig {
             max_tokens_per_day: default_daily_tokens(),
             max_tool_calls_per_turn: default_tool_calls_per_turn(),
             max_dynamic_tools_per_turn: default_dynamic_tools_per_turn(),
+            degradation: DegradationConfig::default(),
+            timezone: default_budget_timezone(),
+            carry_over_percent: 0,
+        }
+    }
+}
+
+/// Budget degradation ladder (`[budget.degradation]`).
+///
+/// Thresholds are percentages of the session or daily budget, whichever is
+/// higher. A threshold above 100 disables that step.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DegradationConfig {
+    /// Whether the ladder is applied at all.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Switch turns to the `budget` model role (`[models.roles] budget`).
+    #[serde(default = "default_cheap_model_at")]
+    pub cheap_model_at: u8,
+
+    /// Stop observer extraction and proactive heartbeat checks.
+    #[serde(default = "default_pause_background_at")]
+    pub pause_background_at: u8,
+
+    /// Shorten the conversation context sent with each turn.
+    #[serde(default = "default_shorten_context_at")]
+    pub shorten_context_at: u8,
+
+    /// Share of the normal context kept once shortened (0.0–1.0].
+    #[serde(default = "default_shortened_context_fraction")]
+    pub shortened_context_fraction: f64,
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cheap_model_at: default_cheap_model_at(),
+            pause_background_at: default_pause_background_at(),
+            shorten_context_at: default_shorten_context_at(),
+            shortened_context_fraction: default_shortened_context_fraction(),
         }
     }
 }
 
 /// Egress (outbound network) policy configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct EgressConfig {
     /// Domains pre-approved for outbound HTTP requests.
     #[serde(default)]
@@ -270,7 +398,7 @@ pub struct EgressConfig {
 }
 
 /// Privacy boundary policy configuration.
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, PartialEq)]
 pub struct PrivacyConfig {
     /// Domains that always require explicit user approval.
     #[serde(default)]
@@ -288,6 +416,10 @@ pub struct PrivacyConfig {
     #[serde(default)]
     pub private_terms: Vec<String>,
 
+    /// Extra regex patterns the output redactor treats as secrets.
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+
     /// Whether briefs require user confirmation before activating.
     #[serde(default = "default_true")]
     pub require_brief_confirmation: bool,
@@ -306,7 +438,7 @@ impl Default for EgressConfig {
 }
 
 /// Browser automation sidecar configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct BrowserConfig {
     /// Chrome DevTools Protocol port for attached mode.
     #[serde(default = "default_cdp_port")]
@@ -323,6 +455,22 @@ pub struct BrowserConfig {
     /// Docker image for the Playwright sidecar.
     #[serde(default = "default_browser_image")]
     pub image: String,
+
+    /// User agent for new browser sessions; Chromium's own when unset.
+    #[serde(default)]
+    pub user_agent: Option<String>,
+
+    /// Hide common automation markers (`navigator.webdriver`) from pages.
+    #[serde(default)]
+    pub stealth: bool,
+
+    /// Maximum browser sessions (per-site cookie jars) kept open at once.
+    #[serde(default = "default_browser_max_sessions")]
+    pub max_sessions: usize,
+
+    /// Minutes an unused browser session stays open (default 30).
+    #[serde(default = "default_browser_session_idle_mins")]
+    pub session_idle_mins: u64,
 }
 
 impl Default for BrowserConfig {
@@ -332,12 +480,16 @@ impl Default for BrowserConfig {
             auto_submit: false,
             standalone_fallback: default_standalone_fallback(),
             image: default_browser_image(),
+            user_agent: None,
+            stealth: false,
+            max_sessions: default_browser_max_sessions(),
+            session_idle_mins: default_browser_session_idle_mins(),
         }
     }
 }
 
 /// WhatsApp sidecar configuration (human-owned).
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct WhatsAppConfig {
     /// Enable WhatsApp integration.
     #[serde(default)]
@@ -356,8 +508,188 @@ impl Default for WhatsAppConfig {
     }
 }
 
+/// Backend used by the `web_search` tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchBackendKind {
+    /// DuckDuckGo HTML results page (no key required).
+    #[default]
+    DuckDuckGo,
+    /// Self-hosted SearXNG instance (JSON API).
+    Searxng,
+    /// Brave Search API (key in `.env` as `BRAVE_API_KEY`).
+    Brave,
+}
+
+/// Web search configuration.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SearchConfig {
+    /// Which search backend to query.
+    #[serde(default)]
+    pub backend: SearchBackendKind,
+    /// Base URL of the SearXNG instance, required for the `searxng` backend.
+    #[serde(default)]
+    pub searxng_url: Option<String>,
+    /// Default number of results returned per query.
+    #[serde(default = "default_search_max_results")]
+    pub max_results: usize,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            backend: SearchBackendKind::default(),
+            searxng_url: None,
+            max_results: default_search_max_results(),
+        }
+    }
+}
+
+/// Email account configuration (human-owned).
+///
+/// The password lives in `.env` as `EMAIL_PASSWORD`; `EMAIL_USERNAME` is
+/// only needed when the login differs from `address`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct EmailConfig {
+    /// Enable the `email` tool.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Account address, used as the sender and default login.
+    #[serde(default)]
+    pub address: String,
+    /// IMAP server hostname (implicit TLS).
+    #[serde(default)]
+    pub imap_host: String,
+    /// IMAP server port.
+    #[serde(default = "default_imap_port")]
+    pub imap_port: u16,
+    /// SMTP server hostname.
+    #[serde(default)]
+    pub smtp_host: String,
+    /// SMTP server port: 465 for implicit TLS, anything else uses STARTTLS.
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: String::new(),
+            imap_host: String::new(),
+            imap_port: default_imap_port(),
+            smtp_host: String::new(),
+            smtp_port: default_smtp_port(),
+        }
+    }
+}
+
+/// Localhost HTTP admin API configuration (human-owned).
+///
+/// The bearer token lives in `.env` under `token_env`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AdminApiConfig {
+    /// Serve the admin API.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listen address; must be loopback.
+    #[serde(default = "default_admin_bind")]
+    pub bind: String,
+    /// `.env` key holding the bearer token.
+    #[serde(default = "default_admin_token_env")]
+    pub token_env: String,
+}
+
+impl Default for AdminApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: default_admin_bind(),
+            token_env: default_admin_token_env(),
+        }
+    }
+}
+
+/// Trace export configuration (human-owned).
+///
+/// Agent-loop spans are always emitted to the log; setting `otlp_endpoint`
+/// additionally exports them over OTLP/HTTP.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TelemetryConfig {
+    /// OTLP/HTTP traces endpoint (e.g. `http://localhost:4318/v1/traces`).
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` resource attribute on exported spans.
+    #[serde(default = "default_telemetry_service_name")]
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: default_telemetry_service_name(),
+        }
+    }
+}
+
+/// Automatic disabling of failing dynamic tools (`[tool_health]`).
+///
+/// A tool whose success rate over its last `window` invocations drops
+/// below `min_success_rate` is disabled until the owner re-enables it or
+/// the agent replaces it with `create_tool`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ToolHealthConfig {
+    /// Whether failing tools are disabled automatically.
+    #[serde(default = "default_true")]
+    pub auto_disable: bool,
+    /// Success rate (0.0-1.0) below which a tool is disabled.
+    #[serde(default = "default_tool_min_success_rate")]
+    pub min_success_rate: f64,
+    /// Number of recent invocations the success rate is computed over.
+    #[serde(default = "default_tool_health_window")]
+    pub window: u32,
+}
+
+impl Default for ToolHealthConfig {
+    fn default() -> Self {
+        Self {
+            auto_disable: true,
+            min_success_rate: default_tool_min_success_rate(),
+            window: default_tool_health_window(),
+        }
+    }
+}
+
+/// An MCP server spawned on the host (`[[mcp_servers]]`).
+///
+/// Every tool requires approval unless listed in `auto_approve`; the
+/// server's own read-only annotations are ignored.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct McpServerConfig {
+    /// Server name, used in tool names (`mcp_{name}_{tool}`); `[a-z0-9_-]`.
+    pub name: String,
+    /// Executable to run.
+    pub command: String,
+    /// Command-line arguments.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// `.env` keys passed to the server as environment variables.
+    #[serde(default)]
+    pub credentials: Vec<String>,
+    /// Tool names (as known to the server) that run without approval.
+    #[serde(default)]
+    pub auto_approve: Vec<String>,
+    /// Start this server.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Per-request timeout in seconds.
+    #[serde(default = "default_mcp_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
 /// Heartbeat scheduler settings.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct HeartbeatConfig {
     /// Enables or disables heartbeat processing.
     #[serde(default = "default_heartbeat_enabled")]
@@ -378,6 +710,10 @@ pub struct HeartbeatConfig {
     /// Token budget per proactive check (default 5000).
     #[serde(default = "default_proactive_budget")]
     pub proactive_budget: u64,
+
+    /// Minutes between polls of each RSS/Atom feed subscription (default 30).
+    #[serde(default = "default_feed_poll_mins")]
+    pub feed_poll_mins: u32,
 }
 
 impl Default for HeartbeatConfig {
@@ -388,6 +724,7 @@ impl Default for HeartbeatConfig {
             proactive: false,
             proactive_interval_mins: default_proactive_interval_mins(),
             proactive_budget: default_proactive_budget(),
+            feed_poll_mins: default_feed_poll_mins(),
         }
     }
 }
@@ -406,7 +743,7 @@ pub enum PromotionMode {
 }
 
 /// Learning and promotion settings.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct LearningConfig {
     /// Enables or disables observer-driven learning.
     #[serde(default = "default_learning_enabled")]
@@ -420,9 +757,23 @@ pub struct LearningConfig {
     #[serde(default = "default_auto_promote_threshold")]
     pub auto_promote_threshold: u32,
 
+    /// Minimum extraction confidence required for auto-promotion.
+    ///
+    /// Lower-confidence extractions stay pending for manual review.
+    #[serde(default = "default_auto_promote_min_confidence")]
+    pub auto_promote_min_confidence: f64,
+
     /// Enable post-session reflection on tool changes.
     #[serde(default = "default_true")]
     pub reflection: bool,
+
+    /// Offer 👍/👎 buttons on answers and adjust memory confidence from them.
+    #[serde(default = "default_true")]
+    pub feedback: bool,
+
+    /// Propose repeated successful tool sequences as skills for approval.
+    #[serde(default = "default_true")]
+    pub skills: bool,
 }
 
 impl Default for LearningConfig {
@@ -431,13 +782,16 @@ impl Default for LearningConfig {
             enabled: default_learning_enabled(),
             promotion_mode: PromotionMode::default(),
             auto_promote_threshold: default_auto_promote_threshold(),
+            auto_promote_min_confidence: default_auto_promote_min_confidence(),
             reflection: true,
+            feedback: true,
+            skills: true,
         }
     }
 }
 
 /// Session persistence and timeout configuration.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct SessionsConfig {
     /// Idle timeout for Telegram sessions in seconds (default 300 = 5 min).
     #[serde(default = "default_session_idle_timeout")]
@@ -463,7 +817,7 @@ impl Default for SessionsConfig {
 }
 
 /// Agent-owned scheduled task configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct ScheduledTaskConfig {
     /// Task name used for identification and logging.
     pub name: String,
@@ -490,6 +844,10 @@ pub struct ScheduledTaskConfig {
     /// Whether this task is active.
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Names of tasks that must complete successfully before this one runs.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
 }
 
 /// Resolved runtime paths under `~/.wintermute`.
@@ -549,12 +907,27 @@ fn default_session_tokens() -> u64 {
 fn default_daily_tokens() -> u64 {
     10_000_000
 }
+fn default_budget_timezone() -> String {
+    "local".to_owned()
+}
 fn default_tool_calls_per_turn() -> u32 {
     20
 }
 fn default_dynamic_tools_per_turn() -> u32 {
     20
 }
+fn default_cheap_model_at() -> u8 {
+    80
+}
+fn default_pause_background_at() -> u8 {
+    85
+}
+fn default_shorten_context_at() -> u8 {
+    90
+}
+fn default_shortened_context_fraction() -> f64 {
+    0.5
+}
 fn default_fetch_rate() -> u32 {
     30
 }
@@ -579,9 +952,34 @@ fn default_learning_enabled() -> bool {
 fn default_auto_promote_threshold() -> u32 {
     3
 }
+fn default_auto_promote_min_confidence() -> f64 {
+    0.8
+}
+fn default_admin_bind() -> String {
+    "127.0.0.1:8787".to_owned()
+}
+
+fn default_admin_token_env() -> String {
+    "WINTERMUTE_ADMIN_TOKEN".to_owned()
+}
+
+fn default_telemetry_service_name() -> String {
+    "wintermute".to_owned()
+}
+
+fn default_mcp_timeout_secs() -> u64 {
+    60
+}
+
 fn default_true() -> bool {
     true
 }
+fn default_tool_min_success_rate() -> f64 {
+    0.5
+}
+fn default_tool_health_window() -> u32 {
+    10
+}
 fn default_session_idle_timeout() -> u64 {
     300
 }
@@ -594,12 +992,18 @@ fn default_proactive_interval_mins() -> u32 {
 fn default_proactive_budget() -> u64 {
     5000
 }
+fn default_feed_poll_mins() -> u32 {
+    30
+}
 fn default_update_frequency() -> String {
     "milestone".to_owned()
 }
 fn default_commitment() -> String {
     "negotiate_only".to_owned()
 }
+fn default_unread_follow_up_days() -> u32 {
+    3
+}
 fn default_cdp_port() -> u16 {
     9222
 }
@@ -609,9 +1013,24 @@ fn default_standalone_fallback() -> bool {
 fn default_browser_image() -> String {
     crate::executor::playwright::BROWSER_IMAGE.to_owned()
 }
+fn default_browser_max_sessions() -> usize {
+    4
+}
+fn default_browser_session_idle_mins() -> u64 {
+    30
+}
 fn default_whatsapp_image() -> String {
     "ghcr.io/pycckuu/wintermute-whatsapp:latest".to_owned()
 }
+fn default_search_max_results() -> usize {
+    8
+}
+fn default_imap_port() -> u16 {
+    993
+}
+fn default_smtp_port() -> u16 {
+    465
+}
 
 /// Load the human-owned config from a TOML file.
 ///
@@ -623,6 +1042,55 @@ pub fn load_config(path: &Path) -> anyhow::Result<Config> {
         .map_err(|e| anyhow::anyhow!("failed to read config at {}: {e}", path.display()))?;
     let config: Config = toml::from_str(&contents)
         .map_err(|e| anyhow::anyhow!("failed to parse config at {}: {e}", path.display()))?;
+    for pattern in &config.privacy.redact_patterns {
+        regex::Regex::new(pattern).map_err(|e| {
+            anyhow::anyhow!(
+                "invalid privacy.redact_patterns entry {pattern:?} in {}: {e}",
+                path.display()
+            )
+        })?;
+    }
+    let degradation = &config.budget.degradation;
+    let budget_model = config
+        .models
+        .roles
+        .get(crate::agent::budget::BUDGET_MODEL_ROLE)
+        .filter(|spec| **spec != config.models.default);
+    if degradation.enabled && degradation.cheap_model_at <= 100 && budget_model.is_none() {
+        tracing::warn!(
+            path = %path.display(),
+            "budget.degradation.cheap_model_at is set but [models.roles] budget names no \
+             model other than the default; the cheap-model step will be skipped"
+        );
+    }
+    let fraction = config.budget.degradation.shortened_context_fraction;
+    if !(fraction > 0.0 && fraction <= 1.0) {
+        anyhow::bail!(
+            "invalid budget.degradation.shortened_context_fraction {fraction} in {}: \
+             must be in (0.0, 1.0]",
+            path.display()
+        );
+    }
+    if crate::agent::budget::BudgetTimezone::parse(&config.budget.timezone).is_none() {
+        anyhow::bail!(
+            "invalid budget.timezone {:?} in {}: use \"local\", \"utc\" or an offset like \"+08:00\"",
+            config.budget.timezone,
+            path.display()
+        );
+    }
+    if config.budget.carry_over_percent > 100 {
+        anyhow::bail!(
+            "invalid budget.carry_over_percent {} in {}: must be at most 100",
+            config.budget.carry_over_percent,
+            path.display()
+        );
+    }
+    if config.models.fixtures.mode != FixtureMode::Off && config.models.fixtures.dir.is_none() {
+        anyhow::bail!(
+            "models.fixtures.dir is required when models.fixtures.mode is not \"off\" in {}",
+            path.display()
+        );
+    }
     Ok(config)
 }
 
@@ -636,9 +1104,100 @@ pub fn load_agent_config(path: &Path) -> anyhow::Result<AgentConfig> {
         .map_err(|e| anyhow::anyhow!("failed to read agent config at {}: {e}", path.display()))?;
     let config: AgentConfig = toml::from_str(&contents)
         .map_err(|e| anyhow::anyhow!("failed to parse agent config at {}: {e}", path.display()))?;
+    validate_scheduled_tasks(&config.scheduled_tasks)
+        .map_err(|e| anyhow::anyhow!("invalid agent config at {}: {e}", path.display()))?;
     Ok(config)
 }
 
+/// Validate the `depends_on` graph of scheduled tasks.
+///
+/// Rejects duplicate task names, dependencies on unknown tasks, enabled
+/// tasks that depend on disabled ones (they could never run), and
+/// dependency cycles (including self-dependencies).
+///
+/// # Errors
+///
+/// Returns an error describing the first problem found.
+pub fn validate_scheduled_tasks(tasks: &[ScheduledTaskConfig]) -> anyhow::Result<()> {
+    let mut index: HashMap<&str, usize> = HashMap::with_capacity(tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        if index.insert(task.name.as_str(), i).is_some() {
+            return Err(anyhow::anyhow!(
+                "duplicate scheduled task name '{}'",
+                task.name
+            ));
+        }
+    }
+
+    for task in tasks {
+        for dep in &task.depends_on {
+            let Some(dep_task) = index.get(dep.as_str()).and_then(|&i| tasks.get(i)) else {
+                return Err(anyhow::anyhow!(
+                    "scheduled task '{}' depends on unknown task '{dep}'",
+                    task.name
+                ));
+            };
+            if task.enabled && !dep_task.enabled {
+                return Err(anyhow::anyhow!(
+                    "scheduled task '{}' depends on disabled task '{dep}'",
+                    task.name
+                ));
+            }
+        }
+    }
+
+    // Iterative DFS with three-colour marking: 0 = unvisited, 1 = on stack, 2 = done.
+    let mut colour = vec![0_u8; tasks.len()];
+    for root in 0..tasks.len() {
+        if colour.get(root).copied() != Some(0) {
+            continue;
+        }
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        if let Some(c) = colour.get_mut(root) {
+            *c = 1;
+        }
+        while let Some((node, next_edge)) = stack.pop() {
+            let deps = tasks
+                .get(node)
+                .map(|t| t.depends_on.as_slice())
+                .unwrap_or(&[]);
+            let Some(dep) = deps.get(next_edge) else {
+                if let Some(c) = colour.get_mut(node) {
+                    *c = 2;
+                }
+                continue;
+            };
+            stack.push((node, next_edge.saturating_add(1)));
+            let Some(&child) = index.get(dep.as_str()) else {
+                continue;
+            };
+            match colour.get(child).copied() {
+                Some(0) => {
+                    if let Some(c) = colour.get_mut(child) {
+                        *c = 1;
+                    }
+                    stack.push((child, 0));
+                }
+                Some(1) => {
+                    let cycle: Vec<&str> = stack
+                        .iter()
+                        .skip_while(|(n, _)| *n != child)
+                        .filter_map(|(n, _)| tasks.get(*n).map(|t| t.name.as_str()))
+                        .chain(std::iter::once(dep.as_str()))
+                        .collect();
+                    return Err(anyhow::anyhow!(
+                        "scheduled task dependency cycle: {}",
+                        cycle.join(" -> ")
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Resolve the default config directory (`~/.wintermute/`).
 ///
 /// # Errors
@@ -0,0 +1,390 @@
+//! Installation checks behind `wintermute doctor`.
+//!
+//! Each check produces a [`Check`] with a pass, warn or fail status. The
+//! CLI collects them into a [`Report`] and prints it, colored when stdout
+//! is a terminal. Checks never modify state: the memory database is opened
+//! read-only and providers are probed with a one-token completion.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::ConnectOptions;
+use teloxide::requests::Requester;
+use teloxide::Bot;
+
+use crate::config::{all_model_specs, ModelsConfig};
+use crate::credentials::{is_token_expired, AnthropicAuth};
+use crate::executor::docker::DockerExecutor;
+use crate::providers::router::ModelRouter;
+use crate::providers::{CompletionRequest, LlmProvider, Message, MessageContent, Role};
+
+/// How long a single network probe may take.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Size above which the WAL file suggests checkpoints are not keeping up.
+pub const WAL_WARN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Role whose model only serves embeddings and cannot be probed with a completion.
+const EMBEDDING_ROLE: &str = "embedding";
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Everything is in order.
+    Pass,
+    /// Works, but something deserves attention.
+    Warn,
+    /// Broken; Wintermute will not run correctly.
+    Fail,
+}
+
+impl CheckStatus {
+    /// Short label printed in front of the check.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+        }
+    }
+
+    /// ANSI color code for the label.
+    fn color(self) -> &'static str {
+        match self {
+            Self::Pass => "\x1b[32m",
+            Self::Warn => "\x1b[33m",
+            Self::Fail => "\x1b[31m",
+        }
+    }
+}
+
+/// Result of one check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// What was checked.
+    pub name: String,
+    /// Outcome.
+    pub status: CheckStatus,
+    /// Human-readable explanation.
+    pub detail: String,
+}
+
+impl Check {
+    /// A passing check.
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    /// A check that passed with a warning.
+    pub fn warn(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, detail)
+    }
+
+    /// A failing check.
+    pub fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// All checks from one doctor run, in the order they ran.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    checks: Vec<Check>,
+}
+
+impl Report {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a check.
+    pub fn push(&mut self, check: Check) {
+        self.checks.push(check);
+    }
+
+    /// Append several checks.
+    pub fn extend(&mut self, checks: impl IntoIterator<Item = Check>) {
+        self.checks.extend(checks);
+    }
+
+    /// Checks recorded so far.
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    /// Number of checks with the given status.
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Whether any check failed.
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+
+    /// Render the report, one line per check plus a summary line.
+    ///
+    /// With `color`, status labels are wrapped in ANSI color codes.
+    pub fn render(&self, color: bool) -> String {
+        let width = self
+            .checks
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for check in &self.checks {
+            let label = if color {
+                format!("{}{}\x1b[0m", check.status.color(), check.status.label())
+            } else {
+                check.status.label().to_owned()
+            };
+            let _ = writeln!(out, "[{label}] {:<width$}  {}", check.name, check.detail);
+        }
+        let _ = writeln!(
+            out,
+            "\n{} passed, {} warnings, {} failed",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        );
+        out
+    }
+}
+
+/// Probe every configured model spec.
+///
+/// Specs that could not be instantiated (usually missing credentials) fail
+/// without a network call; embedding-only specs are reported but not probed.
+pub async fn check_providers(models: &ModelsConfig, router: &ModelRouter) -> Vec<Check> {
+    let embedding_only = models
+        .roles
+        .get(EMBEDDING_ROLE)
+        .filter(|spec| {
+            **spec != models.default
+                && !models
+                    .roles
+                    .iter()
+                    .any(|(role, s)| role != EMBEDDING_ROLE && s == *spec)
+                && !models.skills.values().any(|s| s == *spec)
+        })
+        .cloned();
+
+    let mut checks = Vec::new();
+    for spec in all_model_specs(models) {
+        let name = format!("provider {spec}");
+        let Some(provider) = router.provider(&spec) else {
+            checks.push(Check::fail(
+                name,
+                "not loaded; check the spec and its credentials in .env",
+            ));
+            continue;
+        };
+        if embedding_only.as_deref() == Some(spec.as_str()) {
+            checks.push(Check::pass(name, "loaded (embedding model, not probed)"));
+            continue;
+        }
+        checks.push(probe_provider(name, provider.as_ref()).await);
+    }
+    checks
+}
+
+/// Send a one-token completion and report whether the provider answered.
+pub async fn probe_provider(name: String, provider: &dyn LlmProvider) -> Check {
+    let request = CompletionRequest {
+        messages: vec![Message {
+            role: Role::User,
+            content: MessageContent::Text("ping".to_owned()),
+        }],
+        system: None,
+        tools: Vec::new(),
+        max_tokens: Some(1),
+        stop_sequences: Vec::new(),
+    };
+    let started = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, provider.complete(request)).await {
+        Ok(Ok(_)) => Check::pass(
+            name,
+            format!("responded in {} ms", started.elapsed().as_millis()),
+        ),
+        Ok(Err(e)) => Check::fail(name, e.to_string()),
+        Err(_) => Check::fail(
+            name,
+            format!("no response within {}s", PROBE_TIMEOUT.as_secs()),
+        ),
+    }
+}
+
+/// Check the Anthropic OAuth token without refreshing it.
+///
+/// Returns `None` for API keys or when no Anthropic auth is configured; the
+/// provider probe covers those. An expired or soon-expiring token is a
+/// warning, since `wintermute start` refreshes it.
+pub fn check_anthropic_auth(auth: Option<&AnthropicAuth>) -> Option<Check> {
+    const NAME: &str = "anthropic oauth";
+    let auth = auth?;
+    let AnthropicAuth::OAuth {
+        refresh_token,
+        expires_at,
+        ..
+    } = auth
+    else {
+        return None;
+    };
+    if is_token_expired(auth) {
+        let hint = if refresh_token.is_some() {
+            "token expired or expiring; `wintermute start` refreshes it"
+        } else {
+            "token expired or expiring and no refresh token is set; log in again"
+        };
+        return Some(Check::warn(NAME, hint));
+    }
+    let detail = expires_at
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|at| format!("token valid until {}", at.format("%Y-%m-%d %H:%M UTC")))
+        .unwrap_or_else(|| "token set, expiry unknown".to_owned());
+    Some(Check::pass(NAME, detail))
+}
+
+/// Check that the Docker daemon is reachable.
+pub async fn check_docker() -> Check {
+    if DockerExecutor::docker_available().await {
+        Check::pass("docker", "daemon reachable; sandboxed executor available")
+    } else {
+        Check::warn(
+            "docker",
+            "daemon unreachable; only the direct executor (maintenance mode) is available",
+        )
+    }
+}
+
+/// Check the memory database: integrity, journal mode, WAL size and migrations.
+///
+/// The database is opened read-only. A missing file is a warning, since
+/// `wintermute init` or `start` creates it.
+pub async fn check_memory_db(path: &Path, expected_migrations: &[&str]) -> Vec<Check> {
+    if !path.exists() {
+        return vec![Check::warn(
+            "memory.db",
+            format!("{} does not exist; run `wintermute init`", path.display()),
+        )];
+    }
+
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    let mut conn = match options.connect().await {
+        Ok(conn) => conn,
+        Err(e) => return vec![Check::fail("memory.db", format!("cannot open: {e}"))],
+    };
+
+    let mut checks = Vec::new();
+    checks.push(
+        match sqlx::query_as::<_, (String,)>("PRAGMA quick_check")
+            .fetch_one(&mut conn)
+            .await
+        {
+            Ok((result,)) if result == "ok" => Check::pass("memory.db integrity", "quick_check ok"),
+            Ok((result,)) => Check::fail("memory.db integrity", result),
+            Err(e) => Check::fail("memory.db integrity", e.to_string()),
+        },
+    );
+    checks.push(
+        match sqlx::query_as::<_, (String,)>("PRAGMA journal_mode")
+            .fetch_one(&mut conn)
+            .await
+        {
+            Ok((mode,)) => journal_check(&mode, wal_size(path)),
+            Err(e) => Check::fail("memory.db journal", e.to_string()),
+        },
+    );
+    checks.push(
+        match sqlx::query_as::<_, (String,)>("SELECT name FROM migrations")
+            .fetch_all(&mut conn)
+            .await
+        {
+            Ok(rows) => {
+                let applied: Vec<String> = rows.into_iter().map(|(name,)| name).collect();
+                migration_check(&applied, expected_migrations)
+            }
+            Err(e) => Check::fail("memory.db schema", format!("cannot read migrations: {e}")),
+        },
+    );
+    checks
+}
+
+/// Judge the journal mode and the current WAL file size.
+pub fn journal_check(mode: &str, wal_bytes: Option<u64>) -> Check {
+    const NAME: &str = "memory.db journal";
+    if !mode.eq_ignore_ascii_case("wal") {
+        return Check::warn(
+            NAME,
+            format!("journal mode is {mode}; `wintermute start` switches it to wal"),
+        );
+    }
+    match wal_bytes {
+        Some(bytes) if bytes > WAL_WARN_BYTES => Check::warn(
+            NAME,
+            format!(
+                "wal file is {} MiB; checkpoints may be blocked by a long-lived reader",
+                bytes / (1024 * 1024)
+            ),
+        ),
+        Some(bytes) => Check::pass(NAME, format!("wal mode, wal file {} KiB", bytes / 1024)),
+        None => Check::pass(NAME, "wal mode, no pending wal file"),
+    }
+}
+
+/// Compare applied migrations against the ones this binary ships.
+pub fn migration_check(applied: &[String], expected: &[&str]) -> Check {
+    const NAME: &str = "memory.db schema";
+    let missing: Vec<&str> = expected
+        .iter()
+        .copied()
+        .filter(|name| !applied.iter().any(|a| a == name))
+        .collect();
+    if missing.is_empty() {
+        Check::pass(NAME, format!("{} migrations applied", applied.len()))
+    } else {
+        Check::warn(
+            NAME,
+            format!(
+                "missing {}; they are applied on the next `wintermute start`",
+                missing.join(", ")
+            ),
+        )
+    }
+}
+
+/// Size of the `-wal` file next to the database, if there is one.
+fn wal_size(db_path: &Path) -> Option<u64> {
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    std::fs::metadata(Path::new(&wal)).ok().map(|m| m.len())
+}
+
+/// Check a Telegram bot token with `getMe`.
+pub async fn check_telegram(token: &str) -> Check {
+    const NAME: &str = "telegram token";
+    let bot = Bot::new(token);
+    match tokio::time::timeout(PROBE_TIMEOUT, bot.get_me()).await {
+        Ok(Ok(me)) => Check::pass(
+            NAME,
+            format!("valid, bot @{}", me.user.username.as_deref().unwrap_or("?")),
+        ),
+        Ok(Err(e)) => Check::fail(NAME, e.to_string()),
+        Err(_) => Check::fail(
+            NAME,
+            format!("no response within {}s", PROBE_TIMEOUT.as_secs()),
+        ),
+    }
+}
@@ -13,12 +13,15 @@ use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
 use bollard::models::HostConfig;
 use bollard::Docker;
 use tokio_stream::StreamExt;
+use tracing::info;
 
 use crate::config::{Config, RuntimePaths, SandboxConfig};
 
-use super::egress::EgressProxy;
+use super::egress::{self, EgressProxy};
 use super::redactor::Redactor;
-use super::{ExecOptions, ExecResult, Executor, ExecutorError, ExecutorKind, HealthStatus};
+use super::{
+    ExecOptions, ExecRestrictions, ExecResult, Executor, ExecutorError, ExecutorKind, HealthStatus,
+};
 
 const SANDBOX_CONTAINER_NAME: &str = "wintermute-sandbox";
 const RESET_REQUIREMENTS_COMMAND: &str =
@@ -69,11 +72,25 @@ impl DockerExecutor {
 
         let workspace_dir = paths.workspace_dir.clone();
         let scripts_dir = paths.scripts_dir.clone();
-        std::fs::create_dir_all(&workspace_dir)
-            .map_err(|e| ExecutorError::Infrastructure(e.to_string()))?;
+        prepare_workspace_dir(&workspace_dir)?;
         std::fs::create_dir_all(&scripts_dir)
             .map_err(|e| ExecutorError::Infrastructure(e.to_string()))?;
 
+        // Older versions put the sandbox on a network with a direct route
+        // out; rebuild both containers on the internal network instead.
+        let migrate = egress::network_needs_isolation(&docker).await?;
+        if migrate {
+            info!("sandbox network is not internal, recreating sandbox and egress proxy");
+            let remove_opts = RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            };
+            let _ = docker
+                .remove_container(SANDBOX_CONTAINER_NAME, Some(remove_opts))
+                .await;
+            EgressProxy::teardown(&docker).await?;
+        }
+
         // Start egress proxy before sandbox so the network is ready.
         let egress_proxy =
             Some(EgressProxy::ensure(&docker, &config.egress.allowed_domains).await?);
@@ -87,6 +104,16 @@ impl DockerExecutor {
             egress_proxy,
         };
         instance.ensure_container(config).await?;
+        if migrate {
+            let reinstall = ExecOptions {
+                timeout: Duration::from_secs(600),
+                working_dir: Some(PathBuf::from("/workspace")),
+                restrictions: None,
+            };
+            let _ = instance
+                .execute(RESET_REQUIREMENTS_COMMAND, reinstall)
+                .await?;
+        }
         Ok(instance)
     }
 
@@ -138,6 +165,7 @@ impl DockerExecutor {
         let reset_opts = ExecOptions {
             timeout: Duration::from_secs(600),
             working_dir: Some(PathBuf::from("/workspace")),
+            restrictions: None,
         };
         let _ = self.execute(RESET_REQUIREMENTS_COMMAND, reset_opts).await?;
         Ok(())
@@ -189,7 +217,6 @@ impl DockerExecutor {
             &self.scripts_dir,
             &config.sandbox,
             self.egress_proxy.as_ref().map(|p| p.network_name()),
-            self.egress_proxy.as_ref().map(|p| p.proxy_address()),
         )?;
 
         let options = Some(CreateContainerOptions {
@@ -259,7 +286,16 @@ impl Executor for DockerExecutor {
             .and_then(|value| value.to_str().map(ToOwned::to_owned))
             .unwrap_or_else(|| "/workspace".to_owned());
 
-        let create_exec = CreateExecOptions {
+        let proxy_url = match &self.egress_proxy {
+            Some(proxy) => {
+                proxy
+                    .proxy_url(&self.docker, opts.restrictions.as_ref())
+                    .await?
+            }
+            None => None,
+        };
+
+        let mut create_exec = CreateExecOptions {
             attach_stdout: Some(true),
             attach_stderr: Some(true),
             cmd: Some(vec!["bash".to_owned(), "-lc".to_owned(), wrapped_command]),
@@ -267,6 +303,12 @@ impl Executor for DockerExecutor {
             working_dir: Some(working_dir),
             ..Default::default()
         };
+        if let Some(url) = proxy_url.as_deref() {
+            create_exec.env = Some(proxy_env(url));
+        }
+        if let Some(restrictions) = &opts.restrictions {
+            apply_restrictions(&mut create_exec, restrictions);
+        }
 
         let created = self
             .docker
@@ -355,16 +397,16 @@ impl Executor for DockerExecutor {
 
 /// Build a container configuration for the sandbox.
 ///
-/// When `network_name` and `proxy_address` are provided, the sandbox joins
-/// the egress proxy network with HTTP(S) proxy environment variables set.
-/// When absent, the sandbox runs with `network: none` (test/fallback mode).
+/// When `network_name` is provided, the sandbox joins the internal egress
+/// proxy network; proxy variables are set per exec (see [`proxy_env`]), so
+/// the container itself carries no proxy credential. When absent, the
+/// sandbox runs with `network: none` (test/fallback mode).
 #[doc(hidden)]
 pub fn build_container_config(
     workspace_dir: &Path,
     scripts_dir: &Path,
     sandbox: &SandboxConfig,
     network_name: Option<&str>,
-    proxy_address: Option<&str>,
 ) -> Result<ContainerConfig<String>, ExecutorError> {
     let memory_limit = i64::from(sandbox.memory_mb)
         .saturating_mul(1024)
@@ -396,30 +438,67 @@ pub fn build_container_config(
         ..Default::default()
     };
 
-    let env = match proxy_address {
-        Some(addr) => {
-            let proxy_url = format!("http://{addr}");
-            vec![
-                format!("HTTP_PROXY={proxy_url}"),
-                format!("HTTPS_PROXY={proxy_url}"),
-                format!("http_proxy={proxy_url}"),
-                format!("https_proxy={proxy_url}"),
-            ]
-        }
-        None => Vec::new(),
-    };
-
     Ok(ContainerConfig {
         image: Some(sandbox.image.clone()),
         cmd: Some(vec!["sleep".to_owned(), "infinity".to_owned()]),
         user: None,
         working_dir: Some("/workspace".to_owned()),
-        env: Some(env),
+        env: Some(Vec::new()),
         host_config: Some(host_config),
         ..Default::default()
     })
 }
 
+/// HTTP(S) proxy variables pointing an exec at the egress proxy.
+///
+/// `proxy_url` carries the exec's credential; without these variables an
+/// exec has no credential and the proxy refuses it.
+#[doc(hidden)]
+pub fn proxy_env(proxy_url: &str) -> Vec<String> {
+    vec![
+        format!("HTTP_PROXY={proxy_url}"),
+        format!("HTTPS_PROXY={proxy_url}"),
+        format!("http_proxy={proxy_url}"),
+        format!("https_proxy={proxy_url}"),
+    ]
+}
+
+/// User that cannot write to the bind-mounted workspace.
+const READ_ONLY_USER: &str = "nobody";
+
+/// Narrow a sandbox exec to a dynamic tool's manifest.
+///
+/// Network is scoped by the proxy credential chosen in
+/// [`EgressProxy::proxy_url`]. Without workspace writes the command runs as
+/// an unprivileged user; [`prepare_workspace_dir`] keeps `/workspace` closed
+/// to it.
+#[doc(hidden)]
+pub fn apply_restrictions(exec: &mut CreateExecOptions<String>, restrictions: &ExecRestrictions) {
+    if !restrictions.workspace_write {
+        exec.user = Some(READ_ONLY_USER.to_owned());
+    }
+}
+
+/// Create the workspace directory and strip its group and other write bits.
+///
+/// The sandbox's `nobody` user is neither the owner nor in the owning
+/// group, so this is what keeps read-only tools from writing to it.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or its mode set.
+#[doc(hidden)]
+pub fn prepare_workspace_dir(path: &Path) -> Result<(), ExecutorError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::create_dir_all(path).map_err(|e| ExecutorError::Infrastructure(e.to_string()))?;
+    let mut perms = std::fs::metadata(path)
+        .map_err(|e| ExecutorError::Infrastructure(e.to_string()))?
+        .permissions();
+    perms.set_mode(perms.mode() & !0o022);
+    std::fs::set_permissions(path, perms).map_err(|e| ExecutorError::Infrastructure(e.to_string()))
+}
+
 /// Shell-escape a string for use in `bash -c`.
 #[doc(hidden)]
 pub fn shell_escape(raw: &str) -> String {
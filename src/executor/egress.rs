@@ -1,28 +1,46 @@
 //! Egress proxy management for Docker sandbox network control.
 //!
 //! Manages a Squid forward-proxy container that enforces the domain
-//! allowlist from `config.toml [egress].allowed_domains`. The sandbox
-//! container routes all HTTP(S) traffic through this proxy.
+//! allowlist from `config.toml [egress].allowed_domains`. The sandbox sits
+//! on an internal network whose only way out is this proxy, and every
+//! request must carry a per-exec credential: sandbox commands get the whole
+//! allowlist, dynamic tools only the domains in their manifest, and tools
+//! without network get no credential at all.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 use base64::Engine;
 use bollard::container::{
     Config as ContainerConfig, CreateContainerOptions, InspectContainerOptions,
     RemoveContainerOptions, StartContainerOptions,
 };
+use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
 use bollard::models::HostConfig;
-use bollard::network::CreateNetworkOptions;
+use bollard::network::{ConnectNetworkOptions, CreateNetworkOptions};
 use bollard::Docker;
+use tokio::sync::Mutex;
+use tokio_stream::StreamExt;
 use tracing::info;
 
-use super::ExecutorError;
+use super::{ExecRestrictions, ExecutorError};
 
 const SQUID_IMAGE: &str = "ubuntu/squid:latest";
 const SQUID_CONTAINER_NAME: &str = "wintermute-egress";
 pub(crate) const NETWORK_NAME: &str = "wintermute-net";
+/// Network that gives the proxy (and only the proxy) a route out.
+const OUTBOUND_NETWORK_NAME: &str = "wintermute-egress-out";
 const PROXY_PORT: u16 = 3128;
 
+/// Squid include file holding the credential-scoped access rules.
+const GRANTS_PATH: &str = "/etc/squid/grants.conf";
+
+/// Proxy user for the sandbox's own commands.
+const SANDBOX_PROXY_USER: &str = "sandbox";
+
+/// Proxy user for dynamic tool commands.
+const TOOL_PROXY_USER: &str = "tool";
+
 /// Package registries that are always allowed regardless of user config.
 const ALWAYS_ALLOWED_DOMAINS: &[&str] = &[
     // Python
@@ -40,18 +58,39 @@ const ALWAYS_ALLOWED_DOMAINS: &[&str] = &[
     "security.ubuntu.com",
 ];
 
+/// Proxy access granted to one dynamic tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolGrant {
+    /// Secret the tool's exec presents as its proxy password.
+    pub token: String,
+    /// Domains the tool may reach (further bounded by the allowlist).
+    pub domains: Vec<String>,
+}
+
 /// Egress proxy backed by a Squid container on a shared Docker network.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct EgressProxy {
     proxy_address: String,
     network_name: String,
+    sandbox_token: String,
+    tool_grants: Arc<Mutex<BTreeMap<String, ToolGrant>>>,
+}
+
+impl std::fmt::Debug for EgressProxy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EgressProxy")
+            .field("proxy_address", &self.proxy_address)
+            .field("network_name", &self.network_name)
+            .finish_non_exhaustive()
+    }
 }
 
 impl EgressProxy {
     /// Ensure the egress proxy is running with the given domain allowlist.
     ///
-    /// Creates the Docker network and Squid container if they don't exist,
-    /// and starts them if they're stopped.
+    /// Creates the Docker networks and Squid container if they don't exist,
+    /// recreates the container if it is stopped or its config drifted, and
+    /// installs a fresh sandbox credential.
     ///
     /// # Errors
     ///
@@ -60,17 +99,24 @@ impl EgressProxy {
         docker: &Docker,
         allowed_domains: &[String],
     ) -> Result<Self, ExecutorError> {
-        ensure_network(docker).await?;
-        let squid_config = generate_squid_config(allowed_domains);
-        ensure_squid_container(docker, &squid_config).await?;
-
-        Ok(Self {
+        ensure_networks(docker).await?;
+        let proxy = Self {
             proxy_address: format!("{SQUID_CONTAINER_NAME}:{PROXY_PORT}"),
             network_name: NETWORK_NAME.to_owned(),
-        })
+            sandbox_token: new_token(),
+            tool_grants: Arc::new(Mutex::new(BTreeMap::new())),
+        };
+
+        let squid_config = generate_squid_config(allowed_domains);
+        let grants = generate_grants_config(&proxy.sandbox_token, &BTreeMap::new());
+        let fresh = ensure_squid_container(docker, &squid_config, &grants).await?;
+        if !fresh {
+            push_grants(docker, &grants).await?;
+        }
+        Ok(proxy)
     }
 
-    /// Returns the proxy address for HTTP_PROXY / HTTPS_PROXY env vars.
+    /// Returns the proxy address (host:port) inside the sandbox network.
     pub fn proxy_address(&self) -> &str {
         &self.proxy_address
     }
@@ -80,6 +126,59 @@ impl EgressProxy {
         &self.network_name
     }
 
+    /// Proxy URL, with credentials, for one sandbox exec.
+    ///
+    /// Unrestricted commands get the sandbox credential. A dynamic tool gets
+    /// a credential scoped to its declared domains, installed in the proxy
+    /// on first use; a tool without network gets `None` and so has no way
+    /// through the proxy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a new tool grant cannot be installed.
+    pub async fn proxy_url(
+        &self,
+        docker: &Docker,
+        restrictions: Option<&ExecRestrictions>,
+    ) -> Result<Option<String>, ExecutorError> {
+        let Some(restrictions) = restrictions else {
+            return Ok(Some(
+                self.credentialed_url(SANDBOX_PROXY_USER, &self.sandbox_token),
+            ));
+        };
+        if restrictions.domains.is_empty() {
+            return Ok(None);
+        }
+
+        let mut grants = self.tool_grants.lock().await;
+        if let Some(grant) = grants.get(&restrictions.tool) {
+            if grant.domains == restrictions.domains {
+                return Ok(Some(self.credentialed_url(TOOL_PROXY_USER, &grant.token)));
+            }
+        }
+        let token = new_token();
+        let previous = grants.insert(
+            restrictions.tool.clone(),
+            ToolGrant {
+                token: token.clone(),
+                domains: restrictions.domains.clone(),
+            },
+        );
+        let config = generate_grants_config(&self.sandbox_token, &grants);
+        if let Err(e) = push_grants(docker, &config).await {
+            match previous {
+                Some(grant) => grants.insert(restrictions.tool.clone(), grant),
+                None => grants.remove(&restrictions.tool),
+            };
+            return Err(e);
+        }
+        Ok(Some(self.credentialed_url(TOOL_PROXY_USER, &token)))
+    }
+
+    fn credentialed_url(&self, user: &str, token: &str) -> String {
+        format!("http://{user}:{token}@{}", self.proxy_address)
+    }
+
     /// Tear down the egress proxy container and network.
     ///
     /// # Errors
@@ -95,8 +194,9 @@ impl EgressProxy {
             .remove_container(SQUID_CONTAINER_NAME, Some(remove_opts))
             .await;
 
-        // Remove network (ignore errors — may have other containers attached).
+        // Remove networks (ignore errors — may have other containers attached).
         let _ = docker.remove_network(NETWORK_NAME).await;
+        let _ = docker.remove_network(OUTBOUND_NETWORK_NAME).await;
 
         Ok(())
     }
@@ -127,13 +227,12 @@ pub fn generate_squid_config(allowed_domains: &[String]) -> String {
     }
 
     // Access rules
-    config.push_str("\n# Allow CONNECT (HTTPS) to allowed domains\n");
+    config.push_str("\n# Allow CONNECT (HTTPS) only to port 443\n");
     config.push_str("acl SSL_ports port 443\n");
-    config.push_str("acl CONNECT method CONNECT\n");
-    config.push_str("http_access allow CONNECT wintermute_allowed SSL_ports\n\n");
+    config.push_str("acl CONNECT method CONNECT\n\n");
 
-    config.push_str("# Allow HTTP to allowed domains\n");
-    config.push_str("http_access allow wintermute_allowed\n\n");
+    config.push_str("# Credential-scoped allow rules, rewritten at runtime\n");
+    config.push_str(&format!("include {GRANTS_PATH}\n\n"));
 
     config.push_str("# Deny everything else\n");
     config.push_str("http_access deny all\n\n");
@@ -150,38 +249,120 @@ pub fn generate_squid_config(allowed_domains: &[String]) -> String {
     config
 }
 
-/// Ensure the `wintermute-net` Docker bridge network exists.
-pub(crate) async fn ensure_network(docker: &Docker) -> Result<(), ExecutorError> {
-    let inspect = docker.inspect_network::<&str>(NETWORK_NAME, None).await;
-    if inspect.is_ok() {
-        return Ok(());
+/// Generate the Squid include file that ties each credential to its domains.
+///
+/// Requests without one of these credentials match no allow rule and fall
+/// through to `http_access deny all`.
+#[doc(hidden)]
+pub fn generate_grants_config(sandbox_token: &str, tools: &BTreeMap<String, ToolGrant>) -> String {
+    let mut config = String::new();
+    config.push_str("# Wintermute egress grants — rewritten at runtime\n\n");
+
+    config.push_str("# Sandbox commands: the whole allowlist\n");
+    config.push_str(&format!(
+        "acl wintermute_sandbox req_header Proxy-Authorization {}\n",
+        credential_pattern(SANDBOX_PROXY_USER, sandbox_token)
+    ));
+    config.push_str("http_access allow CONNECT wintermute_sandbox wintermute_allowed SSL_ports\n");
+    config.push_str("http_access allow wintermute_sandbox wintermute_allowed\n");
+
+    for (index, (name, grant)) in tools.iter().enumerate() {
+        let acl = format!("wintermute_tool_{index}");
+        config.push_str(&format!("\n# Tool {name}: its declared domains only\n"));
+        config.push_str(&format!(
+            "acl {acl} req_header Proxy-Authorization {}\n",
+            credential_pattern(TOOL_PROXY_USER, &grant.token)
+        ));
+        let domains: Vec<String> = grant.domains.iter().map(|d| format!(".{d}")).collect();
+        config.push_str(&format!(
+            "acl {acl}_domains dstdomain {}\n",
+            domains.join(" ")
+        ));
+        config.push_str(&format!(
+            "http_access allow CONNECT {acl} {acl}_domains wintermute_allowed SSL_ports\n"
+        ));
+        config.push_str(&format!(
+            "http_access allow {acl} {acl}_domains wintermute_allowed\n"
+        ));
     }
 
-    let options = CreateNetworkOptions {
-        name: NETWORK_NAME,
-        driver: "bridge",
-        ..Default::default()
-    };
+    config
+}
 
-    docker
-        .create_network(options)
-        .await
-        .map_err(|e| ExecutorError::Infrastructure(format!("failed to create network: {e}")))?;
+/// Regex matching the `Proxy-Authorization` header for one credential.
+///
+/// The space after `Basic` is matched with `.` because Squid splits ACL
+/// values on whitespace.
+fn credential_pattern(user: &str, token: &str) -> String {
+    let encoded = base64_encode(&format!("{user}:{token}"));
+    format!("^Basic.{}$", encoded.replace('+', "\\+"))
+}
+
+/// Random secret for a proxy credential.
+fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Whether the sandbox network exists without the internal flag.
+///
+/// Networks created by older versions let the sandbox reach the internet
+/// directly; they have to be torn down and recreated.
+///
+/// # Errors
+///
+/// Returns an error if the network cannot be inspected.
+pub(crate) async fn network_needs_isolation(docker: &Docker) -> Result<bool, ExecutorError> {
+    match docker.inspect_network::<&str>(NETWORK_NAME, None).await {
+        Ok(network) => Ok(network.internal != Some(true)),
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 404, ..
+        }) => Ok(false),
+        Err(e) => Err(ExecutorError::Infrastructure(format!(
+            "failed to inspect network: {e}"
+        ))),
+    }
+}
+
+/// Ensure the internal `wintermute-net` network and the proxy's outbound
+/// network exist.
+async fn ensure_networks(docker: &Docker) -> Result<(), ExecutorError> {
+    for (name, internal) in [(NETWORK_NAME, true), (OUTBOUND_NETWORK_NAME, false)] {
+        if docker.inspect_network::<&str>(name, None).await.is_ok() {
+            continue;
+        }
+
+        let options = CreateNetworkOptions {
+            name,
+            driver: "bridge",
+            internal,
+            ..Default::default()
+        };
+
+        docker.create_network(options).await.map_err(|e| {
+            ExecutorError::Infrastructure(format!("failed to create network {name}: {e}"))
+        })?;
+    }
 
     Ok(())
 }
 
 /// Ensure the Squid proxy container is running with the given config.
 ///
-/// If the container exists but its config has drifted (domain allowlist changed),
-/// it is recreated with the new config before starting.
-async fn ensure_squid_container(docker: &Docker, squid_config: &str) -> Result<(), ExecutorError> {
+/// A running container whose config matches is kept, and `false` is
+/// returned so the caller pushes its grants. Otherwise (missing, stopped,
+/// or drifted) the container is recreated with `grants` baked in and
+/// `true` is returned.
+async fn ensure_squid_container(
+    docker: &Docker,
+    squid_config: &str,
+    grants: &str,
+) -> Result<bool, ExecutorError> {
     let desired_b64 = base64_encode(squid_config);
     let inspect = docker
         .inspect_container(SQUID_CONTAINER_NAME, None::<InspectContainerOptions>)
         .await;
 
-    let needs_start = match inspect {
+    match inspect {
         Ok(state) => {
             let running = state.state.and_then(|s| s.running).unwrap_or(false);
 
@@ -196,51 +377,108 @@ async fn ensure_squid_container(docker: &Docker, squid_config: &str) -> Result<(
                         .map(|e| e.trim_start_matches("SQUID_CONFIG_B64="))
                 });
 
-            if current_b64 != Some(desired_b64.as_str()) {
-                // Config changed: recreate the container with the new allowlist.
-                info!("egress proxy config drifted, recreating container");
-                let remove_opts = RemoveContainerOptions {
-                    force: true,
-                    ..Default::default()
-                };
-                let _ = docker
-                    .remove_container(SQUID_CONTAINER_NAME, Some(remove_opts))
-                    .await;
-                create_squid_container(docker, squid_config).await?;
-                true
-            } else {
-                !running
+            if running && current_b64 == Some(desired_b64.as_str()) {
+                return Ok(false);
             }
+            // Config changed or container stopped: recreate it so the
+            // allowlist and grants are current.
+            info!(running, "egress proxy stale, recreating container");
+            let remove_opts = RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            };
+            let _ = docker
+                .remove_container(SQUID_CONTAINER_NAME, Some(remove_opts))
+                .await;
         }
         Err(bollard::errors::Error::DockerResponseServerError {
             status_code: 404, ..
-        }) => {
-            create_squid_container(docker, squid_config).await?;
-            true
-        }
+        }) => {}
         Err(e) => {
             return Err(ExecutorError::Infrastructure(format!(
                 "failed to inspect egress proxy: {e}"
             )));
         }
+    }
+
+    create_squid_container(docker, squid_config, grants).await?;
+    docker
+        .start_container(SQUID_CONTAINER_NAME, None::<StartContainerOptions<String>>)
+        .await
+        .map_err(|e| ExecutorError::Infrastructure(format!("failed to start egress proxy: {e}")))?;
+
+    Ok(true)
+}
+
+/// Replace the grants file in the running proxy and reload it.
+async fn push_grants(docker: &Docker, grants: &str) -> Result<(), ExecutorError> {
+    let exec = CreateExecOptions {
+        attach_stdout: Some(true),
+        attach_stderr: Some(true),
+        cmd: Some(vec![
+            "bash".to_owned(),
+            "-c".to_owned(),
+            format!(
+                "echo \"$SQUID_GRANTS_B64\" | base64 -d > {GRANTS_PATH} && squid -k reconfigure"
+            ),
+        ]),
+        env: Some(vec![format!("SQUID_GRANTS_B64={}", base64_encode(grants))]),
+        ..Default::default()
     };
+    let created = docker
+        .create_exec(SQUID_CONTAINER_NAME, exec)
+        .await
+        .map_err(|e| {
+            ExecutorError::Infrastructure(format!("failed to update egress grants: {e}"))
+        })?;
 
-    if needs_start {
-        docker
-            .start_container(SQUID_CONTAINER_NAME, None::<StartContainerOptions<String>>)
-            .await
-            .map_err(|e| {
-                ExecutorError::Infrastructure(format!("failed to start egress proxy: {e}"))
-            })?;
+    let mut output = String::new();
+    let started = docker
+        .start_exec(
+            &created.id,
+            Some(StartExecOptions {
+                detach: false,
+                tty: false,
+                output_capacity: None,
+            }),
+        )
+        .await
+        .map_err(|e| {
+            ExecutorError::Infrastructure(format!("failed to update egress grants: {e}"))
+        })?;
+    if let StartExecResults::Attached {
+        output: mut stream, ..
+    } = started
+    {
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| ExecutorError::Infrastructure(e.to_string()))?;
+            output.push_str(&chunk.to_string());
+        }
     }
 
+    let inspect = docker
+        .inspect_exec(&created.id)
+        .await
+        .map_err(|e| ExecutorError::Infrastructure(e.to_string()))?;
+    if inspect.exit_code != Some(0) {
+        return Err(ExecutorError::Infrastructure(format!(
+            "failed to reload egress grants: {}",
+            output.trim()
+        )));
+    }
     Ok(())
 }
 
 /// Create the Squid proxy container.
 ///
-/// Pulls the image first if it is not available locally.
-async fn create_squid_container(docker: &Docker, squid_config: &str) -> Result<(), ExecutorError> {
+/// Pulls the image first if it is not available locally. The container's
+/// own network is the outbound one; it is then attached to the internal
+/// sandbox network so it can serve as the sandbox's only gateway.
+async fn create_squid_container(
+    docker: &Docker,
+    squid_config: &str,
+    grants: &str,
+) -> Result<(), ExecutorError> {
     super::ensure_image(docker, SQUID_IMAGE, None).await?;
 
     let mut labels = HashMap::new();
@@ -249,9 +487,10 @@ async fn create_squid_container(docker: &Docker, squid_config: &str) -> Result<(
     // Pass config via environment variable; entrypoint writes it to disk.
     // Alternative: use a tmpfs mount and write the config file.
     let config_b64 = base64_encode(squid_config);
+    let grants_b64 = base64_encode(grants);
 
     let host_config = HostConfig {
-        network_mode: Some(NETWORK_NAME.to_owned()),
+        network_mode: Some(OUTBOUND_NETWORK_NAME.to_owned()),
         restart_policy: Some(bollard::models::RestartPolicy {
             name: Some(bollard::models::RestartPolicyNameEnum::UNLESS_STOPPED),
             maximum_retry_count: None,
@@ -262,12 +501,20 @@ async fn create_squid_container(docker: &Docker, squid_config: &str) -> Result<(
     let container_config = ContainerConfig {
         image: Some(SQUID_IMAGE.to_owned()),
         labels: Some(labels),
-        env: Some(vec![format!("SQUID_CONFIG_B64={config_b64}")]),
+        env: Some(vec![
+            format!("SQUID_CONFIG_B64={config_b64}"),
+            format!("SQUID_GRANTS_B64={grants_b64}"),
+        ]),
+        // Grants are only seeded on first start; later updates are pushed
+        // into the file and must survive a container restart.
         cmd: Some(vec![
             "bash".to_owned(),
             "-c".to_owned(),
-            "echo \"$SQUID_CONFIG_B64\" | base64 -d > /etc/squid/squid.conf && squid -NYC"
-                .to_owned(),
+            format!(
+                "echo \"$SQUID_CONFIG_B64\" | base64 -d > /etc/squid/squid.conf && \
+                 {{ [ -f {GRANTS_PATH} ] || echo \"$SQUID_GRANTS_B64\" | base64 -d > {GRANTS_PATH}; }} && \
+                 squid -NYC"
+            ),
         ]),
         host_config: Some(host_config),
         ..Default::default()
@@ -285,6 +532,21 @@ async fn create_squid_container(docker: &Docker, squid_config: &str) -> Result<(
             ExecutorError::Infrastructure(format!("failed to create egress proxy container: {e}"))
         })?;
 
+    docker
+        .connect_network(
+            NETWORK_NAME,
+            ConnectNetworkOptions {
+                container: SQUID_CONTAINER_NAME,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| {
+            ExecutorError::Infrastructure(format!(
+                "failed to attach egress proxy to {NETWORK_NAME}: {e}"
+            ))
+        })?;
+
     Ok(())
 }
 
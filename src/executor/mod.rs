@@ -32,6 +32,8 @@ pub struct ExecOptions {
     pub timeout: Duration,
     /// Optional working directory inside executor context.
     pub working_dir: Option<PathBuf>,
+    /// Per-command restrictions; `None` runs with the sandbox defaults.
+    pub restrictions: Option<ExecRestrictions>,
 }
 
 impl Default for ExecOptions {
@@ -39,10 +41,22 @@ impl Default for ExecOptions {
         Self {
             timeout: Duration::from_secs(120),
             working_dir: None,
+            restrictions: None,
         }
     }
 }
 
+/// Restrictions a dynamic tool's manifest places on a single command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecRestrictions {
+    /// Tool the command runs for; its proxy credential is scoped to it.
+    pub tool: String,
+    /// Domains the command may reach through the egress proxy (empty: none).
+    pub domains: Vec<String>,
+    /// Whether the command may write to the workspace.
+    pub workspace_write: bool,
+}
+
 /// Command execution result.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExecResult {
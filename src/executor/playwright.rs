@@ -49,44 +49,69 @@ const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs
 /// into the Dockerfile at runtime to avoid heredoc issues with Docker's
 /// classic builder (which ends `RUN` at the first newline).
 const BRIDGE_SCRIPT: &str = r#"import json
+import os
+import time
 from flask import Flask, request, jsonify
 from playwright.sync_api import sync_playwright
 
 app = Flask(__name__)
 pw = None
 browser = None
-page = None
+sessions = {}
 
 MAX_EXTRACT_BYTES = 50 * 1024
+SESSION_DIR = os.environ.get("SESSION_DIR", "/home/pwuser/sessions")
+SCREENSHOT_DIR = "/workspace/screenshots"
+STEALTH_SCRIPT = "Object.defineProperty(navigator, 'webdriver', {get: () => undefined});"
 
-def get_browser():
-    global pw, browser, page
+def state_path(name):
+    return os.path.join(SESSION_DIR, name + ".json")
+
+def get_browser(stealth):
+    global pw, browser
     if browser is None:
         pw = sync_playwright().start()
-        browser = pw.chromium.launch(headless=True, args=[
+        args = [
             "--no-sandbox",
             "--disable-gpu",
             "--host-resolver-rules=MAP * ~NOTFOUND, EXCLUDE *.com, EXCLUDE *.org, EXCLUDE *.net, EXCLUDE *.io, EXCLUDE *.dev, EXCLUDE *.app, EXCLUDE *.co",
-        ])
-    if page is None:
-        ctx = browser.new_context(viewport={"width": 1280, "height": 720})
-        page = ctx.new_page()
-    return page
-
-def close_browser():
-    global pw, browser, page
-    if page is not None:
-        try: page.close()
-        except Exception: pass
-        page = None
-    if browser is not None:
-        try: browser.close()
-        except Exception: pass
-        browser = None
-    if pw is not None:
-        try: pw.stop()
+        ]
+        if stealth:
+            args.append("--disable-blink-features=AutomationControlled")
+        browser = pw.chromium.launch(headless=True, args=args)
+    return browser
+
+def get_page(name, user_agent, stealth):
+    entry = sessions.get(name)
+    if entry is None:
+        opts = {"viewport": {"width": 1280, "height": 720}}
+        if user_agent:
+            opts["user_agent"] = user_agent
+        if os.path.exists(state_path(name)):
+            opts["storage_state"] = state_path(name)
+        ctx = get_browser(stealth).new_context(**opts)
+        if stealth:
+            ctx.add_init_script(STEALTH_SCRIPT)
+        entry = {"ctx": ctx, "page": ctx.new_page()}
+        sessions[name] = entry
+    return entry["page"]
+
+def save_session(name):
+    entry = sessions.get(name)
+    if entry is None:
+        return
+    try:
+        os.makedirs(SESSION_DIR, exist_ok=True)
+        entry["ctx"].storage_state(path=state_path(name))
+    except Exception:
+        pass
+
+def close_session(name):
+    save_session(name)
+    entry = sessions.pop(name, None)
+    if entry is not None:
+        try: entry["ctx"].close()
         except Exception: pass
-        pw = None
 
 @app.route("/health")
 def health():
@@ -98,7 +123,13 @@ def execute():
         data = request.get_json(force=True)
         action = data.get("action", "")
         timeout_ms = data.get("timeout_ms", 30000)
-        p = get_browser()
+        name = data.get("session") or "default"
+        for evicted in data.get("close_sessions") or []:
+            close_session(evicted)
+        if action == "close_session":
+            close_session(name)
+            return jsonify({"success": True, "result": f"closed session {name}"})
+        p = get_page(name, data.get("user_agent"), bool(data.get("stealth")))
         p.set_default_timeout(timeout_ms)
 
         if action == "navigate":
@@ -109,7 +140,7 @@ def execute():
                 p.wait_for_load_state("networkidle")
             elif wait:
                 p.wait_for_selector(wait, timeout=timeout_ms)
-            result = json.dumps({"title": p.title(), "url": p.url})
+            result = json.dumps({"title": p.title(), "url": p.url, "session": name})
 
         elif action == "click":
             sel = data.get("selector", "")
@@ -123,9 +154,10 @@ def execute():
             result = f"typed into {sel}"
 
         elif action == "screenshot":
-            path = "/workspace/screenshot.png"
+            os.makedirs(SCREENSHOT_DIR, exist_ok=True)
+            path = f"{SCREENSHOT_DIR}/{name}-{int(time.time() * 1000)}.png"
             p.screenshot(path=path)
-            result = json.dumps({"path": path})
+            result = json.dumps({"path": path, "session": name})
 
         elif action == "extract":
             sel = data.get("selector")
@@ -165,6 +197,7 @@ def execute():
         else:
             return jsonify({"success": False, "error": f"unknown action: {action}"})
 
+        save_session(name)
         return jsonify({"success": True, "result": result})
     except Exception as e:
         return jsonify({"success": False, "error": f"{type(e).__name__}: {e}"})
@@ -1,4 +1,10 @@
 //! Secret redaction chokepoint for tool and executor outputs.
+//!
+//! Besides flat strings, the [`Redactor`] handles:
+//! - secrets as they appear once JSON- or HTML-escaped,
+//! - multi-line matches, replaced line by line so code blocks keep their shape,
+//! - JSON documents, walked key by key ([`Redactor::redact_json`]),
+//! - custom patterns from `[privacy] redact_patterns`.
 
 use regex::Regex;
 
@@ -14,30 +20,108 @@ pub struct Redactor {
 
 impl Redactor {
     /// Create a redactor from known secret values.
+    ///
+    /// Each secret also matches its JSON-escaped and HTML-escaped forms.
     pub fn new(exact_secrets: Vec<String>) -> Self {
         let patterns = default_credential_patterns();
+        let mut expanded: Vec<String> = Vec::with_capacity(exact_secrets.len());
+        for secret in exact_secrets.into_iter().filter(|s| !s.is_empty()) {
+            for variant in escaped_variants(&secret) {
+                if !expanded.contains(&variant) {
+                    expanded.push(variant);
+                }
+            }
+            if !expanded.contains(&secret) {
+                expanded.push(secret);
+            }
+        }
+        // Longest first, so a secret is never half-replaced by a shorter one.
+        expanded.sort_by_key(|s| std::cmp::Reverse(s.len()));
         Self {
-            exact_secrets,
+            exact_secrets: expanded,
             patterns,
         }
     }
 
+    /// Add custom regex patterns (for example from `[privacy] redact_patterns`).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first pattern that fails to compile.
+    pub fn with_patterns(mut self, patterns: &[String]) -> Result<Self, regex::Error> {
+        for pattern in patterns {
+            self.patterns.push(Regex::new(pattern)?);
+        }
+        Ok(self)
+    }
+
     /// Redact exact known secrets and known secret patterns.
+    ///
+    /// Matches spanning several lines are replaced line by line, keeping
+    /// line breaks and indentation intact.
     pub fn redact(&self, text: &str) -> String {
         let mut sanitized = text.to_owned();
         for secret in &self.exact_secrets {
-            if !secret.is_empty() {
-                sanitized = sanitized.replace(secret, REDACTION_MARKER);
+            if sanitized.contains(secret.as_str()) {
+                sanitized = sanitized.replace(secret.as_str(), &mask(secret));
             }
         }
         for pattern in &self.patterns {
             sanitized = pattern
-                .replace_all(&sanitized, REDACTION_MARKER)
-                .to_string();
+                .replace_all(&sanitized, |caps: &regex::Captures<'_>| {
+                    mask(caps.get(0).map_or("", |m| m.as_str()))
+                })
+                .into_owned();
         }
         sanitized
     }
 
+    /// Redact every string (keys included) inside a JSON value.
+    pub fn redact_json(&self, value: &serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+        match value {
+            Value::String(s) => Value::String(self.redact(s)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.redact_json(v)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (self.redact(k), self.redact_json(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Redact tool output, walking it as JSON when it parses as an object
+    /// or array.
+    ///
+    /// Walking catches secrets that only match once unescaped (for example
+    /// a value containing quotes or `\u` escapes). Output is re-serialised
+    /// only when the walk replaced something, so clean JSON keeps its key
+    /// order and formatting; multi-line JSON is then pretty-printed,
+    /// single-line JSON compact.
+    pub fn redact_output(&self, text: &str) -> String {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+                let redacted = self.redact_json(&value);
+                if redacted == value {
+                    return self.redact(text);
+                }
+                let rendered = if text.trim().contains('\n') {
+                    serde_json::to_string_pretty(&redacted)
+                } else {
+                    serde_json::to_string(&redacted)
+                };
+                if let Ok(rendered) = rendered {
+                    return self.redact(&rendered);
+                }
+            }
+        }
+        self.redact(text)
+    }
+
     /// Redact a [`RawExecResult`](super::docker::RawExecResult) into an [`ExecResult`](super::ExecResult).
     pub fn redact_result(&self, raw: super::docker::RawExecResult) -> super::ExecResult {
         super::ExecResult {
@@ -50,6 +134,50 @@ impl Redactor {
     }
 }
 
+/// Replacement for a matched secret: one marker per non-blank line,
+/// keeping line breaks and leading indentation.
+fn mask(matched: &str) -> String {
+    if !matched.contains('\n') {
+        return REDACTION_MARKER.to_owned();
+    }
+    matched
+        .split('\n')
+        .map(|line| {
+            let body = line.trim_start();
+            if body.trim().is_empty() {
+                line.to_owned()
+            } else {
+                let indent = &line[..line.len().saturating_sub(body.len())];
+                format!("{indent}{REDACTION_MARKER}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Forms a secret takes once embedded in JSON strings or Telegram HTML.
+fn escaped_variants(secret: &str) -> Vec<String> {
+    let mut variants = Vec::new();
+    if let Ok(quoted) = serde_json::to_string(secret) {
+        let inner = quoted
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .unwrap_or(&quoted);
+        if inner != secret {
+            variants.push(inner.to_owned());
+        }
+    }
+    let html = secret
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;");
+    if html != secret {
+        variants.push(html);
+    }
+    variants
+}
+
 /// Returns the default credential detection regex patterns.
 ///
 /// Shared by the redactor and the Telegram input guard.
@@ -0,0 +1,92 @@
+//! Daily budget reset notice for the morning digest.
+//!
+//! The `feed_digest` builtin opens with a short summary of the previous
+//! budget day: tokens used against the daily limit, call count, the model
+//! that used the most, and today's limit including carry-over. Usage comes
+//! from the provider usage ledger, so the summary survives restarts. Days
+//! are counted in the budget timezone (`[budget] timezone`).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqlitePool;
+
+use crate::agent::budget::DailyBudget;
+use crate::memory::usage::{self, UsageGroup, UsageTotals};
+use crate::telegram::ui::escape_html;
+
+/// Build the reset notice for the budget day before `now`.
+///
+/// Returns `None` when nothing was used that day.
+///
+/// # Errors
+///
+/// Returns an error if the usage ledger cannot be read.
+pub async fn reset_notice(
+    pool: &SqlitePool,
+    budget: &DailyBudget,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Option<String>> {
+    let timezone = budget.timezone();
+    let today = timezone.date(now);
+    let Some(yesterday) = today.pred_opt() else {
+        return Ok(None);
+    };
+    let since = timezone.day_start(yesterday);
+    let until = timezone.day_start(today);
+
+    let totals = usage::usage_between(pool, since, until).await?;
+    if totals.calls == 0 {
+        return Ok(None);
+    }
+    let top_model = usage::usage_by_between(pool, since, until, UsageGroup::Model)
+        .await?
+        .into_iter()
+        .next();
+    Ok(Some(format_reset_notice(
+        yesterday,
+        &totals,
+        top_model.as_ref(),
+        budget.base_limit(),
+        budget.carried_over(),
+    )))
+}
+
+/// Render the reset notice (Telegram HTML).
+pub fn format_reset_notice(
+    day: NaiveDate,
+    totals: &UsageTotals,
+    top_model: Option<&UsageTotals>,
+    base_limit: u64,
+    carried_over: u64,
+) -> String {
+    let used = totals.total_tokens();
+    let mut out = format!(
+        "<b>Budget reset</b> — {}\nYesterday: {used} of {base_limit} tokens ({}%) in {} calls",
+        day.format("%Y-%m-%d"),
+        percent(used, base_limit),
+        totals.calls
+    );
+    if totals.failures > 0 {
+        out.push_str(&format!(", {} failed", totals.failures));
+    }
+    if let Some(model) = top_model {
+        out.push_str(&format!(
+            "\nTop model: <code>{}</code> ({} tokens)",
+            escape_html(&model.key),
+            model.total_tokens()
+        ));
+    }
+    let today = base_limit.saturating_add(carried_over);
+    if carried_over > 0 {
+        out.push_str(&format!(
+            "\nToday: {today} tokens ({carried_over} carried over)"
+        ));
+    } else {
+        out.push_str(&format!("\nToday: {today} tokens"));
+    }
+    out
+}
+
+/// `used` as a whole percentage of `limit`, unclamped.
+fn percent(used: u64, limit: u64) -> u64 {
+    used.saturating_mul(100).checked_div(limit).unwrap_or(0)
+}
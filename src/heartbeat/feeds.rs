@@ -0,0 +1,222 @@
+//! RSS/Atom feed polling and delivery.
+//!
+//! Each heartbeat tick polls subscriptions not fetched within
+//! `heartbeat.feed_poll_mins` and stores new items. Items from high-priority
+//! feeds are sent to Telegram straight away; the rest are collected by the
+//! `feed_digest` builtin scheduled task into one daily message.
+
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::agent::TelegramOutbound;
+use crate::memory::MemoryEngine;
+use crate::telegram::ui::escape_html;
+use crate::tools::rss::{self, FeedEntry, FeedPriority};
+
+/// Maximum items listed in one digest; the rest wait for the next one.
+const MAX_DIGEST_ITEMS: i64 = 40;
+
+/// Telegram's message length limit, in characters.
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// Longest rendered digest line (item or feed heading); longer ones fall
+/// back to a truncated plain title so one item can never fill a message.
+const MAX_LINE_CHARS: usize = 1000;
+
+/// Title length kept when a digest line falls back to plain text.
+const FALLBACK_TITLE_CHARS: usize = 120;
+
+/// Maximum high-priority alerts sent per tick.
+const MAX_ALERTS_PER_TICK: i64 = 10;
+
+/// Outcome of one polling pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeedPollReport {
+    /// Feeds fetched successfully.
+    pub polled: usize,
+    /// Feeds whose fetch failed.
+    pub failed: usize,
+    /// New items stored.
+    pub new_entries: usize,
+}
+
+/// Poll every feed not fetched within `interval_mins` and store new items.
+///
+/// A failing feed records its error and does not stop the pass.
+///
+/// # Errors
+///
+/// Returns an error if the subscription list cannot be read or items
+/// cannot be stored.
+pub async fn poll_due_feeds(
+    memory: &MemoryEngine,
+    interval_mins: u32,
+) -> anyhow::Result<FeedPollReport> {
+    let mut report = FeedPollReport::default();
+    for feed in rss::feeds_due(memory.pool(), interval_mins).await? {
+        match rss::fetch_feed(&feed.url).await {
+            Ok(parsed) => {
+                let added = rss::record_entries(memory, feed.id, &parsed.entries, false).await?;
+                rss::mark_polled(memory, feed.id, parsed.title.as_deref(), None).await?;
+                debug!(url = %feed.url, added, "feed polled");
+                report.polled = report.polled.saturating_add(1);
+                report.new_entries = report.new_entries.saturating_add(added);
+            }
+            Err(e) => {
+                warn!(url = %feed.url, error = %e, "feed poll failed");
+                rss::mark_polled(memory, feed.id, None, Some(&e.to_string())).await?;
+                report.failed = report.failed.saturating_add(1);
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Send new items of high-priority feeds, one message each.
+///
+/// Returns the number of items sent. Items are marked delivered even if
+/// the send fails, so a closed channel cannot cause repeats.
+///
+/// # Errors
+///
+/// Returns an error if pending items cannot be read or marked.
+pub async fn notify_high_priority(
+    memory: &MemoryEngine,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+) -> anyhow::Result<usize> {
+    let entries =
+        rss::pending_entries(memory.pool(), FeedPriority::High, MAX_ALERTS_PER_TICK).await?;
+    for entry in &entries {
+        send(telegram_tx, user_id, format_alert(entry)).await;
+    }
+    let ids: Vec<i64> = entries.iter().map(|e| e.id).collect();
+    rss::mark_digested(memory, &ids).await?;
+    Ok(entries.len())
+}
+
+/// Execute the `feed_digest` builtin: send undelivered normal-priority
+/// items as one message grouped by feed.
+///
+/// The message is kept within Telegram's length limit; only the items that
+/// fit are marked digested, the rest wait for the next digest. `notice`
+/// (the budget reset notice) opens the message; it is sent on its own when
+/// there are no new items.
+///
+/// # Errors
+///
+/// Returns an error if pending items cannot be read or marked.
+pub async fn execute_feed_digest(
+    memory: &MemoryEngine,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+    notice: Option<String>,
+) -> anyhow::Result<String> {
+    let entries =
+        rss::pending_entries(memory.pool(), FeedPriority::Normal, MAX_DIGEST_ITEMS).await?;
+    if entries.is_empty() {
+        if let Some(notice) = notice {
+            send(telegram_tx, user_id, notice).await;
+            return Ok("feed digest: no new items, budget notice sent".to_owned());
+        }
+        return Ok("feed digest: no new items".to_owned());
+    }
+
+    let prefix = notice.map(|n| format!("{n}\n\n")).unwrap_or_default();
+    let budget = MAX_MESSAGE_CHARS.saturating_sub(prefix.chars().count());
+    let (digest, included) = format_digest(&entries, budget);
+    send(telegram_tx, user_id, format!("{prefix}{digest}")).await;
+    let ids: Vec<i64> = entries.iter().take(included).map(|e| e.id).collect();
+    rss::mark_digested(memory, &ids).await?;
+    Ok(format!("feed digest: sent {included} item(s)"))
+}
+
+/// Render the digest message (Telegram HTML) in at most `max_chars`
+/// characters. Entries are expected grouped by feed, as returned by
+/// [`rss::pending_entries`].
+///
+/// Returns the message and how many leading entries it lists. At least one
+/// entry is always listed so a digest can never stall.
+pub fn format_digest(entries: &[FeedEntry], max_chars: usize) -> (String, usize) {
+    // Reserve the header at its widest count so the final one always fits.
+    let header_chars = header(entries.len()).chars().count();
+    let mut body = String::new();
+    let mut used = header_chars;
+    let mut included = 0usize;
+    let mut current_feed: Option<i64> = None;
+    for entry in entries {
+        let mut chunk = String::new();
+        if current_feed != Some(entry.feed_id) {
+            let heading = bounded(
+                format!("<b>{}</b>", escape_html(&entry.feed_title)),
+                &entry.feed_title,
+            );
+            chunk.push_str(&format!("\n\n{heading}"));
+        }
+        chunk.push_str(&format!("\n• {}", bounded(entry_line(entry), &entry.title)));
+        let chunk_chars = chunk.chars().count();
+        if included > 0 && used.saturating_add(chunk_chars) > max_chars {
+            break;
+        }
+        current_feed = Some(entry.feed_id);
+        body.push_str(&chunk);
+        used = used.saturating_add(chunk_chars);
+        included = included.saturating_add(1);
+    }
+    (format!("{}{body}", header(included)), included)
+}
+
+/// Digest header line for `count` items.
+fn header(count: usize) -> String {
+    format!("<b>Feed digest</b> — {count} new item(s)")
+}
+
+/// Keep `html` when it is short enough, else fall back to a truncated,
+/// escaped `plain` title.
+fn bounded(html: String, plain: &str) -> String {
+    if html.chars().count() <= MAX_LINE_CHARS {
+        return html;
+    }
+    let mut title: String = plain.chars().take(FALLBACK_TITLE_CHARS).collect();
+    title.push('…');
+    escape_html(&title)
+}
+
+/// Render a high-priority item alert (Telegram HTML).
+pub fn format_alert(entry: &FeedEntry) -> String {
+    let mut out = format!(
+        "<b>{}</b>\n{}",
+        escape_html(&entry.feed_title),
+        entry_line(entry)
+    );
+    if let Some(summary) = &entry.summary {
+        out.push_str(&format!("\n{}", escape_html(summary)));
+    }
+    out
+}
+
+/// Item title, linked when the item has a link.
+fn entry_line(entry: &FeedEntry) -> String {
+    match &entry.link {
+        Some(link) => format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(link).replace('"', "&quot;"),
+            escape_html(&entry.title)
+        ),
+        None => escape_html(&entry.title),
+    }
+}
+
+/// Send a plain text message, logging failures.
+async fn send(telegram_tx: &mpsc::Sender<TelegramOutbound>, user_id: i64, text: String) {
+    let msg = TelegramOutbound {
+        user_id,
+        text: Some(text),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard: None,
+    };
+    if let Err(e) = telegram_tx.send(msg).await {
+        warn!(error = %e, "failed to send feed message");
+    }
+}
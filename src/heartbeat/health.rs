@@ -1,8 +1,12 @@
 //! Health self-checks and `health.json` file writing.
 //!
 //! Gathers health data from all system components and writes an atomic
-//! health report to disk each heartbeat tick.
+//! health report to disk each heartbeat tick. Besides the summary fields,
+//! the report lists each component (memory, executor, every provider,
+//! WhatsApp, browser, observer) with its own state and last error, so
+//! Flatline can act on the component that failed.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::Instant;
 
@@ -10,8 +14,25 @@ use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
+use crate::executor::HealthStatus;
+use crate::providers::health::ProviderOutcome;
+
 use super::HeartbeatDeps;
 
+/// Component name of the memory engine.
+pub const COMPONENT_MEMORY: &str = "memory";
+/// Component name of the executor.
+pub const COMPONENT_EXECUTOR: &str = "executor";
+/// Component name of the WhatsApp sidecar.
+pub const COMPONENT_WHATSAPP: &str = "whatsapp";
+/// Component name of the browser bridge.
+pub const COMPONENT_BROWSER: &str = "browser";
+/// Component name of the observer pipeline.
+pub const COMPONENT_OBSERVER: &str = "observer";
+
+/// Consecutive failures after which a provider is reported unavailable.
+const PROVIDER_UNAVAILABLE_AFTER: u32 = 3;
+
 /// Health report written to `~/.wintermute/health.json` each heartbeat tick.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthReport {
@@ -37,6 +58,146 @@ pub struct HealthReport {
     pub budget_today: BudgetReport,
     /// Last error message, if any.
     pub last_error: Option<String>,
+    /// Per-component status, keyed by component name. Providers are keyed
+    /// `provider:{model spec}`.
+    #[serde(default)]
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl HealthReport {
+    /// Look up one component.
+    pub fn component(&self, name: &str) -> Option<&ComponentHealth> {
+        self.components.get(name)
+    }
+
+    /// Names of components in the given state, sorted.
+    pub fn components_in(&self, state: ComponentState) -> Vec<&str> {
+        self.components
+            .iter()
+            .filter(|(_, c)| c.status == state)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// State of one component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentState {
+    /// Working normally.
+    Healthy,
+    /// Working, but with recent errors or under pressure.
+    Degraded,
+    /// Not working.
+    Unavailable,
+}
+
+/// Health of one component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    /// Current state.
+    pub status: ComponentState,
+    /// Most recent error, if any.
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Numeric details (e.g. queue depth).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, u64>,
+}
+
+impl ComponentHealth {
+    /// A healthy component.
+    pub fn healthy() -> Self {
+        Self {
+            status: ComponentState::Healthy,
+            last_error: None,
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// A degraded component with the reason.
+    pub fn degraded(error: impl Into<String>) -> Self {
+        Self {
+            status: ComponentState::Degraded,
+            last_error: Some(error.into()),
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// An unavailable component with the reason.
+    pub fn unavailable(error: impl Into<String>) -> Self {
+        Self {
+            status: ComponentState::Unavailable,
+            last_error: Some(error.into()),
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// Attach a numeric detail.
+    #[must_use]
+    pub fn with_metric(mut self, key: &str, value: u64) -> Self {
+        self.metrics.insert(key.to_owned(), value);
+        self
+    }
+}
+
+/// Component key for a provider's model spec.
+pub fn provider_component(spec: &str) -> String {
+    format!("provider:{spec}")
+}
+
+/// Provider health from its recent call outcomes.
+///
+/// A provider is degraded after a failed call and unavailable after
+/// several in a row; one success makes it healthy again.
+pub fn provider_health(outcome: &ProviderOutcome) -> ComponentHealth {
+    let failures = outcome.consecutive_failures;
+    let error = outcome.last_error.clone().unwrap_or_default();
+    let health = if failures == 0 {
+        ComponentHealth::healthy()
+    } else if failures < PROVIDER_UNAVAILABLE_AFTER {
+        ComponentHealth::degraded(error)
+    } else {
+        ComponentHealth::unavailable(error)
+    };
+    health.with_metric("consecutive_failures", u64::from(failures))
+}
+
+/// Observer health from its queue: degraded at three quarters full,
+/// unavailable once the observer task has stopped.
+pub fn observer_health(depth: usize, capacity: usize, closed: bool) -> ComponentHealth {
+    let health = if closed {
+        ComponentHealth::unavailable("observer task stopped")
+    } else if depth.saturating_mul(4) >= capacity.saturating_mul(3) && depth > 0 {
+        ComponentHealth::degraded(format!("observer queue {depth}/{capacity}"))
+    } else {
+        ComponentHealth::healthy()
+    };
+    health
+        .with_metric("queue_depth", u64::try_from(depth).unwrap_or(u64::MAX))
+        .with_metric(
+            "queue_capacity",
+            u64::try_from(capacity).unwrap_or(u64::MAX),
+        )
+}
+
+/// Overall status: `unhealthy` when the executor is down, `degraded` when
+/// there is an error or any component is not healthy, else `running`.
+pub fn overall_status(
+    container_healthy: bool,
+    last_error: Option<&str>,
+    components: &BTreeMap<String, ComponentHealth>,
+) -> String {
+    let all_healthy = components
+        .values()
+        .all(|c| c.status == ComponentState::Healthy);
+    if !container_healthy {
+        "unhealthy".to_owned()
+    } else if last_error.is_some() || !all_healthy {
+        "degraded".to_owned()
+    } else {
+        "running".to_owned()
+    }
 }
 
 /// Budget usage snapshot.
@@ -53,26 +214,97 @@ pub async fn check_health(deps: &HeartbeatDeps, start_time: Instant) -> HealthRe
     let uptime_secs = start_time.elapsed().as_secs();
     let last_heartbeat = chrono::Utc::now().to_rfc3339();
 
+    let mut components = BTreeMap::new();
+
     // Executor health.
-    let (container_healthy, executor_kind, last_error) = match deps.executor.health_check().await {
-        Ok(h) => (h.is_healthy(), format!("{:?}", deps.executor.kind()), None),
-        Err(e) => (
-            false,
-            format!("{:?}", deps.executor.kind()),
-            Some(e.to_string()),
-        ),
+    let executor_kind = format!("{:?}", deps.executor.kind());
+    let (container_healthy, last_error) = match deps.executor.health_check().await {
+        Ok(h) => {
+            let component = match &h {
+                HealthStatus::Healthy { .. } => ComponentHealth::healthy(),
+                HealthStatus::Degraded { details, .. } => ComponentHealth::degraded(details),
+                HealthStatus::Unavailable { details, .. } => ComponentHealth::unavailable(details),
+            };
+            components.insert(COMPONENT_EXECUTOR.to_owned(), component);
+            (h.is_healthy(), None)
+        }
+        Err(e) => {
+            components.insert(
+                COMPONENT_EXECUTOR.to_owned(),
+                ComponentHealth::unavailable(e.to_string()),
+            );
+            (false, Some(e.to_string()))
+        }
     };
 
     // Memory database size.
     #[allow(clippy::cast_precision_loss)]
     let memory_db_size_mb = match deps.memory.db_size_bytes().await {
-        Ok(bytes) => (bytes as f64) / (1024.0 * 1024.0),
+        Ok(bytes) => {
+            components.insert(
+                COMPONENT_MEMORY.to_owned(),
+                ComponentHealth::healthy().with_metric("db_size_bytes", bytes),
+            );
+            (bytes as f64) / (1024.0 * 1024.0)
+        }
         Err(e) => {
             warn!(error = %e, "failed to get memory db size");
+            components.insert(
+                COMPONENT_MEMORY.to_owned(),
+                ComponentHealth::unavailable(e.to_string()),
+            );
             0.0
         }
     };
 
+    // Providers, from their recent call outcomes.
+    for (spec, outcome) in deps.router.provider_outcomes() {
+        components.insert(provider_component(&spec), provider_health(&outcome));
+    }
+
+    // WhatsApp sidecar, when enabled.
+    if let Some(client) = deps.tool_router.whatsapp_client() {
+        let component = match client.status().await {
+            Ok(status) if status.connected => ComponentHealth::healthy(),
+            Ok(status) if status.logged_out => {
+                ComponentHealth::unavailable("logged out; pair again with a QR code")
+            }
+            Ok(_) => ComponentHealth::degraded("not connected to WhatsApp"),
+            Err(e) => ComponentHealth::unavailable(e.to_string()),
+        };
+        components.insert(COMPONENT_WHATSAPP.to_owned(), component);
+    }
+
+    // Browser bridge, when one was started.
+    if let Some(bridge) = deps.tool_router.browser_bridge() {
+        let component = match bridge.health().await {
+            Ok(()) => ComponentHealth::healthy(),
+            Err(e) => ComponentHealth::unavailable(e),
+        };
+        components.insert(COMPONENT_BROWSER.to_owned(), component);
+    }
+
+    // Observer queue, when learning is enabled. Events still in the channel
+    // count toward the depth of the coalescing queue behind it.
+    if let Some(tx) = &deps.observer_tx {
+        let in_channel = tx.max_capacity().saturating_sub(tx.capacity());
+        let component = match &deps.observer_queue {
+            Some(queue) => {
+                let stats = queue.stats();
+                observer_health(
+                    stats.depth.saturating_add(in_channel),
+                    stats.capacity,
+                    tx.is_closed(),
+                )
+                .with_metric("coalesced_total", stats.coalesced)
+                .with_metric("dropped_total", stats.dropped)
+                .with_metric("batches_total", stats.batches)
+            }
+            None => observer_health(in_channel, tx.max_capacity(), tx.is_closed()),
+        };
+        components.insert(COMPONENT_OBSERVER.to_owned(), component);
+    }
+
     // Count scripts (JSON files in scripts dir).
     let scripts_count = count_json_files(&deps.paths.scripts_dir).await;
     let dynamic_tools_count = scripts_count;
@@ -82,15 +314,9 @@ pub async fn check_health(deps: &HeartbeatDeps, start_time: Instant) -> HealthRe
 
     // Budget.
     let budget_used = deps.daily_budget.used();
-    let budget_limit = deps.config.budget.max_tokens_per_day;
+    let budget_limit = deps.daily_budget.limit();
 
-    let status = if container_healthy && last_error.is_none() {
-        "running".to_owned()
-    } else if container_healthy {
-        "degraded".to_owned()
-    } else {
-        "unhealthy".to_owned()
-    };
+    let status = overall_status(container_healthy, last_error.as_deref(), &components);
 
     HealthReport {
         status,
@@ -107,6 +333,7 @@ pub async fn check_health(deps: &HeartbeatDeps, start_time: Instant) -> HealthRe
             limit: budget_limit,
         },
         last_error,
+        components,
     }
 }
 
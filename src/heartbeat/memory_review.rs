@@ -0,0 +1,248 @@
+//! Weekly memory review: surface contradicting active memories.
+//!
+//! Called as the `memory_review` builtin scheduled task. Pairs of active
+//! memories of the same kind that talk about the same thing but disagree are
+//! sent to the user one message per pair, each with keep/replace/archive
+//! buttons. Resolutions are applied through the memory writer actor.
+
+use std::collections::HashSet;
+
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::agent::{Keyboard, TelegramOutbound};
+use crate::memory::{Memory, MemoryEngine, MemoryError, MemoryStatus};
+use crate::observer::staging::{normalize_for_compare, word_overlap};
+use crate::telegram::ui::escape_html;
+
+/// Maximum active memories scanned per review.
+const MAX_SCANNED_MEMORIES: usize = 500;
+
+/// Maximum contradiction pairs sent per review.
+const MAX_PAIRS_PER_REVIEW: usize = 5;
+
+/// Lower word-overlap bound: below this the memories are about different things.
+const MIN_OVERLAP: f64 = 0.4;
+
+/// Upper word-overlap bound: above this the memories say the same thing.
+const MAX_OVERLAP: f64 = 0.9;
+
+/// Metadata key on the older memory of a reviewed pair, holding an object
+/// whose keys are the ids of the newer memories it was kept alongside.
+const REVIEWED_KEY: &str = "contradiction_reviewed_with";
+
+/// Two active memories that appear to conflict.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContradictionPair {
+    /// The older memory.
+    pub older: Memory,
+    /// The newer memory.
+    pub newer: Memory,
+    /// Word overlap between the two (0.0–1.0).
+    pub overlap: f64,
+}
+
+/// A user's decision on a contradiction pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReviewAction {
+    /// Both memories are correct; stop flagging this pair.
+    KeepBoth,
+    /// Keep the older memory and archive the newer one.
+    KeepOlder,
+    /// Keep the newer memory and archive the older one (replace).
+    KeepNewer,
+    /// Archive both memories.
+    ArchiveBoth,
+}
+
+impl ReviewAction {
+    /// Single-character code used in callback data.
+    pub fn code(&self) -> char {
+        match self {
+            Self::KeepBoth => 'k',
+            Self::KeepOlder => 'o',
+            Self::KeepNewer => 'n',
+            Self::ArchiveBoth => 'x',
+        }
+    }
+
+    /// Parse a callback code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "k" => Some(Self::KeepBoth),
+            "o" => Some(Self::KeepOlder),
+            "n" => Some(Self::KeepNewer),
+            "x" => Some(Self::ArchiveBoth),
+            _ => None,
+        }
+    }
+}
+
+/// Build callback data for a review button: `mr:{code}:{older}:{newer}`.
+pub fn review_callback_data(action: ReviewAction, older_id: i64, newer_id: i64) -> String {
+    format!("mr:{}:{older_id}:{newer_id}", action.code())
+}
+
+/// Parse callback data produced by [`review_callback_data`].
+pub fn parse_review_callback(data: &str) -> Option<(ReviewAction, i64, i64)> {
+    let rest = data.strip_prefix("mr:")?;
+    let mut parts = rest.splitn(3, ':');
+    let action = ReviewAction::from_code(parts.next()?)?;
+    let older = parts.next()?.parse().ok()?;
+    let newer = parts.next()?.parse().ok()?;
+    Some((action, older, newer))
+}
+
+/// Find pairs of active memories that look contradictory.
+///
+/// Two memories conflict when they share a kind, were not already kept
+/// together by the user, and overlap enough to be about the same entity
+/// without saying the same thing. Each memory appears in at most one pair.
+pub fn find_contradictions(memories: &[Memory]) -> Vec<ContradictionPair> {
+    let candidates: Vec<&Memory> = memories
+        .iter()
+        .filter(|m| m.id.is_some() && m.status == MemoryStatus::Active)
+        .collect();
+
+    let mut used: HashSet<i64> = HashSet::new();
+    let mut pairs = Vec::new();
+
+    for (i, a) in candidates.iter().enumerate() {
+        for b in candidates.iter().skip(i.saturating_add(1)) {
+            let (Some(a_id), Some(b_id)) = (a.id, b.id) else {
+                continue;
+            };
+            if a.kind != b.kind || used.contains(&a_id) || used.contains(&b_id) {
+                continue;
+            }
+            let (older, newer) = if a_id < b_id { (a, b) } else { (b, a) };
+            if is_reviewed_pair(older, a_id.max(b_id)) {
+                continue;
+            }
+            let a_norm = normalize_for_compare(&a.content);
+            let b_norm = normalize_for_compare(&b.content);
+            if a_norm == b_norm {
+                continue;
+            }
+            let overlap = word_overlap(&a_norm, &b_norm);
+            if overlap > MIN_OVERLAP && overlap < MAX_OVERLAP {
+                used.insert(a_id);
+                used.insert(b_id);
+                pairs.push(ContradictionPair {
+                    older: (*older).clone(),
+                    newer: (*newer).clone(),
+                    overlap,
+                });
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Apply a review decision through the memory writer.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+pub async fn apply_review(
+    memory: &MemoryEngine,
+    action: ReviewAction,
+    older_id: i64,
+    newer_id: i64,
+) -> Result<(), MemoryError> {
+    match action {
+        ReviewAction::KeepBoth => {
+            // json_patch merges nested objects, so earlier pairs are kept.
+            let patch = serde_json::json!({ REVIEWED_KEY: { newer_id.to_string(): true } });
+            memory.merge_metadata(older_id, patch).await?;
+        }
+        ReviewAction::KeepOlder => {
+            memory
+                .update_memory_status(newer_id, MemoryStatus::Archived)
+                .await?;
+        }
+        ReviewAction::KeepNewer => {
+            memory
+                .update_memory_status(older_id, MemoryStatus::Archived)
+                .await?;
+        }
+        ReviewAction::ArchiveBoth => {
+            memory
+                .update_memory_status(older_id, MemoryStatus::Archived)
+                .await?;
+            memory
+                .update_memory_status(newer_id, MemoryStatus::Archived)
+                .await?;
+        }
+    }
+    info!(
+        older_id,
+        newer_id,
+        action = %action.code(),
+        "memory review resolution applied"
+    );
+    Ok(())
+}
+
+/// Execute the weekly memory review and send one message per conflict.
+///
+/// # Errors
+///
+/// Returns an error if active memories cannot be loaded.
+pub async fn execute_memory_review(
+    memory: &MemoryEngine,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+) -> anyhow::Result<String> {
+    let active = memory
+        .search_by_status(MemoryStatus::Active, MAX_SCANNED_MEMORIES)
+        .await?;
+    let pairs = find_contradictions(&active);
+
+    if pairs.is_empty() {
+        return Ok(format!(
+            "memory review: no contradictions among {} active memories",
+            active.len()
+        ));
+    }
+
+    let total = pairs.len();
+    for pair in pairs.iter().take(MAX_PAIRS_PER_REVIEW) {
+        let (Some(older_id), Some(newer_id)) = (pair.older.id, pair.newer.id) else {
+            continue;
+        };
+        let text = format!(
+            "<b>Memory review — possible contradiction</b>\n\
+             <b>Older:</b> {}\n<b>Newer:</b> {}",
+            escape_html(&pair.older.content),
+            escape_html(&pair.newer.content),
+        );
+        let msg = TelegramOutbound {
+            user_id,
+            text: Some(text),
+            file_path: None,
+            approval_keyboard: None,
+            keyboard: Some(Keyboard::Review(older_id, newer_id)),
+        };
+        if let Err(e) = telegram_tx.send(msg).await {
+            warn!(error = %e, "failed to send memory review message");
+        }
+    }
+
+    Ok(format!(
+        "memory review: sent {} of {total} contradiction(s) for review",
+        total.min(MAX_PAIRS_PER_REVIEW)
+    ))
+}
+
+/// Whether the user already kept `older` together with memory `newer_id`.
+fn is_reviewed_pair(older: &Memory, newer_id: i64) -> bool {
+    older
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(REVIEWED_KEY))
+        .and_then(|v| v.get(newer_id.to_string()))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
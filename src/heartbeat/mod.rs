@@ -1,28 +1,37 @@
 //! Heartbeat: scheduled tasks, health monitoring, and backup automation.
 //!
 //! Runs as a background Tokio task, ticking at a configurable interval.
-//! Each tick evaluates cron schedules, dispatches due tasks, performs
-//! health checks, and writes a health report to disk.
+//! Each tick evaluates cron schedules, dispatches due tasks, releases
+//! outbound messages held by quiet hours, flags unread outbound messages,
+//! polls RSS/Atom subscriptions, records soul edits, performs health checks, and writes a
+//! health report to disk.
 
 pub mod backup;
+pub mod budget_notice;
 pub mod digest;
+pub mod feeds;
 pub mod health;
+pub mod memory_review;
+pub mod outbound_delivery;
 pub mod proactive;
 pub mod scheduler;
 pub mod tool_review;
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use tokio::sync::{mpsc, watch};
-use tracing::{error, info, warn};
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{debug, error, info, warn};
 
-use crate::agent::budget::DailyBudget;
+use crate::agent::budget::{DailyBudget, Degradation};
 use crate::agent::identity::{self, IdentitySnapshot};
 use crate::agent::{SessionRouter, TelegramOutbound};
 use crate::config::{AgentConfig, Config, RuntimePaths};
 use crate::executor::Executor;
 use crate::memory::{MemoryEngine, MemoryStatus};
+use crate::observer::queue::ObserverQueue;
+use crate::observer::ObserverEvent;
 use crate::providers::router::ModelRouter;
 use crate::tools::browser::BrowserMode;
 use crate::tools::ToolRouter;
@@ -53,20 +62,41 @@ pub struct HeartbeatDeps {
     pub session_router: Arc<SessionRouter>,
     /// Detected browser mode for SID generation.
     pub browser_mode: BrowserMode,
+    /// Observer channel, for queue depth in the health report.
+    pub observer_tx: Option<mpsc::Sender<ObserverEvent>>,
+    /// Observer queue, for coalescing and batching metrics in the health report.
+    pub observer_queue: Option<Arc<ObserverQueue>>,
+    /// Tick interval in seconds; changed in place by a config reload.
+    pub interval_secs: Arc<AtomicU64>,
+}
+
+/// Request to run a scheduled task now, outside its cron schedule.
+///
+/// Sent by the admin API; the heartbeat runs the task between ticks so it
+/// shares the scheduler state with cron runs.
+#[derive(Debug)]
+pub struct TaskTrigger {
+    /// Name of the scheduled task.
+    pub name: String,
+    /// Receives the outcome, or an error if the task is unknown.
+    pub reply: oneshot::Sender<Result<scheduler::TaskOutcome, String>>,
 }
 
 /// Run the heartbeat background loop.
 ///
-/// Ticks every `interval_secs` from [`crate::config::HeartbeatConfig`]. Each tick evaluates
+/// Ticks every [`HeartbeatDeps::interval_secs`] seconds, re-read after each
+/// tick so a config reload takes effect without a restart. Each tick evaluates
 /// cron schedules for due tasks, runs health checks, and writes `health.json`.
+/// Between ticks it runs tasks requested through `trigger_rx`.
 ///
 /// Exits when the shutdown signal is received or the watch channel closes.
 pub async fn run_heartbeat(
     deps: HeartbeatDeps,
     start_time: Instant,
     mut shutdown_rx: watch::Receiver<bool>,
+    mut trigger_rx: mpsc::Receiver<TaskTrigger>,
 ) {
-    let interval_secs = deps.agent_config.heartbeat.interval_secs;
+    let mut interval_secs = deps.interval_secs.load(Ordering::Relaxed);
     info!(interval_secs, "heartbeat started");
 
     let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
@@ -100,6 +130,19 @@ pub async fn run_heartbeat(
                     )
                     .await;
                 }
+
+                let reloaded_secs = deps.interval_secs.load(Ordering::Relaxed);
+                if reloaded_secs != interval_secs && reloaded_secs > 0 {
+                    info!(from = interval_secs, to = reloaded_secs, "heartbeat interval changed");
+                    interval_secs = reloaded_secs;
+                    interval = tokio::time::interval(Duration::from_secs(interval_secs));
+                    // Wait a full period instead of firing immediately.
+                    interval.reset();
+                }
+            }
+            Some(trigger) = trigger_rx.recv() => {
+                let result = run_triggered_task(&deps, &mut scheduler_state, &trigger.name).await;
+                let _ = trigger.reply.send(result);
             }
             result = shutdown_rx.changed() => {
                 if result.is_err() || *shutdown_rx.borrow() {
@@ -113,6 +156,24 @@ pub async fn run_heartbeat(
     info!("heartbeat stopped");
 }
 
+/// Run a scheduled task by name on request. Dependencies are not checked:
+/// a manual run is an explicit decision.
+async fn run_triggered_task(
+    deps: &HeartbeatDeps,
+    scheduler_state: &mut scheduler::SchedulerState,
+    name: &str,
+) -> Result<scheduler::TaskOutcome, String> {
+    let task = deps
+        .agent_config
+        .scheduled_tasks
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| format!("unknown task '{name}'"))?;
+    scheduler::execute_task(task, deps, scheduler_state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Run a proactive check if enough time has elapsed since the last one.
 async fn maybe_run_proactive_check(
     deps: &HeartbeatDeps,
@@ -127,6 +188,14 @@ async fn maybe_run_proactive_check(
         return;
     }
 
+    // Thresholds come from the session router so reloads apply here too.
+    let budget = deps.session_router.budget_config();
+    let degradation = Degradation::at(&budget.degradation, deps.daily_budget.percent());
+    if degradation.pause_background {
+        debug!("daily budget running low, skipping proactive check");
+        return;
+    }
+
     let now_str = chrono::Utc::now()
         .format("%Y-%m-%d %H:%M:%S UTC")
         .to_string();
@@ -164,6 +233,7 @@ async fn maybe_run_proactive_check(
                 text: Some(redacted),
                 file_path: None,
                 approval_keyboard: None,
+                keyboard: None,
             };
             if let Err(e) = deps.telegram_tx.send(msg).await {
                 warn!(error = %e, "failed to send proactive action");
@@ -190,6 +260,14 @@ async fn run_tick(
     let due = scheduler::due_tasks(&deps.agent_config.scheduled_tasks, scheduler_state, now);
 
     for task_config in due {
+        // Dependencies due this tick were dispatched first, so their outcome
+        // is already reflected in the scheduler state.
+        let unmet = scheduler::unmet_dependencies(task_config, scheduler_state);
+        if !unmet.is_empty() {
+            scheduler::report_skipped(task_config, &unmet, deps, scheduler_state).await;
+            continue;
+        }
+
         match scheduler::execute_task(task_config, deps, scheduler_state).await {
             Ok(outcome) => {
                 info!(
@@ -209,9 +287,72 @@ async fn run_tick(
         }
     }
 
-    // 2. Health check and report.
+    // 2. Release outbound messages held back by quiet hours.
+    if let Some(client) = deps.tool_router.whatsapp_client() {
+        match outbound_delivery::deliver_due_messages(deps.memory.pool(), client, now).await {
+            Ok(report) if report != outbound_delivery::DeliveryReport::default() => {
+                info!(
+                    sent = report.sent,
+                    failed = report.failed,
+                    cancelled = report.cancelled,
+                    "queued outbound delivery pass"
+                );
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "queued outbound delivery failed"),
+        }
+    }
+
+    // 3. Flag outbound messages that stay unread.
+    match outbound_delivery::notify_unread(
+        deps.memory.pool(),
+        &deps.telegram_tx,
+        deps.notify_user_id,
+        deps.agent_config.messaging.unread_follow_up_days,
+    )
+    .await
+    {
+        Ok(0) => {}
+        Ok(count) => info!(count, "unread outbound messages flagged"),
+        Err(e) => warn!(error = %e, "unread outbound check failed"),
+    }
+
+    // 4. Poll feed subscriptions; high-priority items go out immediately.
+    match feeds::poll_due_feeds(&deps.memory, deps.agent_config.heartbeat.feed_poll_mins).await {
+        Ok(report) if report != feeds::FeedPollReport::default() => {
+            info!(
+                polled = report.polled,
+                failed = report.failed,
+                new_entries = report.new_entries,
+                "feed poll pass"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "feed poll failed"),
+    }
+    match feeds::notify_high_priority(&deps.memory, &deps.telegram_tx, deps.notify_user_id).await {
+        Ok(0) => {}
+        Ok(count) => info!(count, "high-priority feed items sent"),
+        Err(e) => warn!(error = %e, "high-priority feed notification failed"),
+    }
+
+    // 5. Record soul edits; send the diff for approval or as a notice.
+    if let Err(e) = crate::agent::soul::check_soul(
+        &deps.memory,
+        &deps.paths.agent_toml,
+        deps.agent_config.personality.soul_modification,
+        &deps.telegram_tx,
+        deps.notify_user_id,
+    )
+    .await
+    {
+        warn!(error = %e, "soul version check failed");
+    }
+
+    // 6. Health check and report.
     let health_path = deps.paths.root.join("health.json");
     let report = health::check_health(deps, start_time).await;
+    deps.session_router.record_health(&report);
 
     if let Err(e) = health::write_health_file(&report, &health_path).await {
         warn!(error = %e, "failed to write health.json");
@@ -282,8 +423,8 @@ async fn regenerate_sid(deps: &HeartbeatDeps, start_time: Instant) {
         active_memory_count: active_count,
         pending_memory_count: pending_count,
         has_vector_search: deps.memory.has_embedder(),
-        session_budget_limit: deps.config.budget.max_tokens_per_session,
-        daily_budget_limit: deps.config.budget.max_tokens_per_day,
+        session_budget_limit: deps.session_router.budget_config().max_tokens_per_session,
+        daily_budget_limit: deps.daily_budget.limit(),
         uptime: start_time.elapsed(),
         agent_name: deps.agent_config.personality.name.clone(),
         browser_mode: deps.browser_mode,
@@ -0,0 +1,231 @@
+//! Release outbound messages held back by quiet hours.
+//!
+//! Each heartbeat tick picks up a small batch of queued messages whose
+//! delivery time has passed and sends them with the same human-like pacing
+//! as a live reply: typing indicator, a length-based delay, then the text.
+//! Messages whose brief has since been completed or cancelled are dropped.
+//!
+//! The same pass flags sent messages that stay unread for too long, asking
+//! the user whether to follow up.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::agent::TelegramOutbound;
+
+use crate::messaging::audit;
+use crate::messaging::brief::{self, BriefStatus};
+use crate::messaging::delivery::{self, DeliveryState};
+use crate::messaging::outbound_composer::human_like_delay_ms;
+use crate::messaging::outbound_queue::{self, QueuedMessage, DELIVERED_FROM_QUEUE};
+use crate::messaging::MessagingError;
+use crate::telegram::ui::escape_html;
+use crate::whatsapp::client::WhatsAppClient;
+
+/// Maximum queued messages released per heartbeat tick.
+const DELIVERY_BATCH: i64 = 5;
+
+/// Characters of message text quoted in unread alerts.
+const UNREAD_EXCERPT_CHARS: usize = 120;
+
+/// Outcome of one delivery pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeliveryReport {
+    /// Messages sent.
+    pub sent: usize,
+    /// Messages whose delivery attempt failed.
+    pub failed: usize,
+    /// Messages dropped because their brief is closed.
+    pub cancelled: usize,
+}
+
+/// Deliver queued WhatsApp messages that are due at `now`.
+///
+/// If the WhatsApp sidecar is not connected, due messages stay queued
+/// without consuming a delivery attempt.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] if the queue cannot be read or updated.
+pub async fn deliver_due_messages(
+    db: &SqlitePool,
+    client: &WhatsAppClient,
+    now: DateTime<Utc>,
+) -> Result<DeliveryReport, MessagingError> {
+    let mut report = DeliveryReport::default();
+    let due = outbound_queue::due_messages(db, now, DELIVERY_BATCH).await?;
+
+    let mut deliverable = Vec::with_capacity(due.len());
+    for message in due {
+        if let Some(reason) = closed_brief_reason(db, &message).await {
+            outbound_queue::cancel(db, message.id, &reason).await?;
+            info!(id = message.id, reason = %reason, "queued outbound message cancelled");
+            report.cancelled = report.cancelled.saturating_add(1);
+        } else {
+            deliverable.push(message);
+        }
+    }
+
+    if deliverable.is_empty() {
+        return Ok(report);
+    }
+
+    if !client.health_check().await.unwrap_or(false) {
+        debug!(
+            pending = deliverable.len(),
+            "WhatsApp not connected, holding queued messages"
+        );
+        return Ok(report);
+    }
+
+    for message in deliverable {
+        if message.channel != "whatsapp" {
+            outbound_queue::cancel(db, message.id, "unsupported channel").await?;
+            report.cancelled = report.cancelled.saturating_add(1);
+            continue;
+        }
+
+        if let Err(e) = client.send_typing(&message.recipient).await {
+            debug!(error = %e, "typing indicator failed (non-critical)");
+        }
+        let delay_ms = human_like_delay_ms(0, message.message_text.len());
+        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+
+        match client
+            .send_text(&message.recipient, &message.message_text)
+            .await
+        {
+            Ok(message_id) => {
+                outbound_queue::mark_sent(db, message.id).await?;
+                log_delivery(db, &message, DeliveryState::Sent, message_id.as_deref()).await;
+                info!(
+                    id = message.id,
+                    jid = %message.recipient,
+                    delay_ms,
+                    "queued WhatsApp message delivered"
+                );
+                report.sent = report.sent.saturating_add(1);
+            }
+            Err(e) => {
+                let gave_up =
+                    outbound_queue::record_failure(db, message.id, &e.to_string()).await?;
+                warn!(id = message.id, error = %e, gave_up, "queued WhatsApp delivery failed");
+                if gave_up {
+                    log_delivery(db, &message, DeliveryState::Failed, None).await;
+                }
+                report.failed = report.failed.saturating_add(1);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Tell the user about sent messages unread for `days` days.
+///
+/// Each message is flagged once. Returns the number of alerts sent.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] if the audit log cannot be queried
+/// or updated.
+pub async fn notify_unread(
+    db: &SqlitePool,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+    days: u32,
+) -> Result<usize, MessagingError> {
+    if days == 0 {
+        return Ok(0);
+    }
+
+    let mut alerted: usize = 0;
+    for entry in delivery::stale_unread(db, days).await? {
+        let who = entry.contact_name.as_deref().unwrap_or(&entry.recipient);
+        let excerpt: String = entry
+            .message_text
+            .chars()
+            .take(UNREAD_EXCERPT_CHARS)
+            .collect();
+        let brief = entry.brief_id.as_deref().map_or_else(String::new, |id| {
+            format!(" (brief <code>{}</code>)", escape_html(id))
+        });
+        let text = format!(
+            "\u{23F0} {} hasn't read your message from {} days ago{brief}:\n<i>{}</i>\n\
+             Want me to follow up, or close the brief?",
+            escape_html(who),
+            days,
+            escape_html(&excerpt),
+        );
+
+        let outbound = TelegramOutbound {
+            user_id,
+            text: Some(text),
+            file_path: None,
+            approval_keyboard: None,
+            keyboard: None,
+        };
+        if telegram_tx.send(outbound).await.is_err() {
+            warn!("telegram channel closed, unread alerts not sent");
+            break;
+        }
+        delivery::mark_unread_alerted(db, entry.id).await?;
+        alerted = alerted.saturating_add(1);
+    }
+    Ok(alerted)
+}
+
+/// Return why a queued message should be dropped, if its brief is closed.
+async fn closed_brief_reason(db: &SqlitePool, message: &QueuedMessage) -> Option<String> {
+    let brief_id = message.brief_id.as_deref()?;
+    match brief::load_brief(db, brief_id).await {
+        Ok(b) if matches!(b.status, BriefStatus::Completed | BriefStatus::Cancelled) => {
+            Some(format!("brief {brief_id} is {}", b.status.as_str()))
+        }
+        Ok(_) => None,
+        Err(MessagingError::BriefNotFound(_)) => Some(format!("brief {brief_id} not found")),
+        Err(e) => {
+            warn!(error = %e, brief_id, "failed to check brief for queued message");
+            None
+        }
+    }
+}
+
+/// Record a released (or abandoned) message in the audit trail.
+async fn log_delivery(
+    db: &SqlitePool,
+    message: &QueuedMessage,
+    state: DeliveryState,
+    message_id: Option<&str>,
+) {
+    let log_id = match audit::log_outbound(
+        db,
+        message.brief_id.as_deref(),
+        &message.session_id,
+        &message.channel,
+        &message.recipient,
+        &message.message_text,
+        "outbound",
+        None,
+        false,
+        Some(DELIVERED_FROM_QUEUE),
+    )
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            warn!(error = %e, "failed to log queued delivery to audit trail");
+            return;
+        }
+    };
+
+    let result = match state {
+        DeliveryState::Failed => delivery::mark_failed(db, log_id).await,
+        _ => delivery::mark_sent(db, log_id, message_id).await,
+    };
+    if let Err(e) = result {
+        warn!(error = %e, "failed to record delivery state");
+    }
+}
@@ -10,6 +10,7 @@ use tracing::{debug, info};
 
 use crate::agent::budget::DailyBudget;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{extract_text, CompletionRequest, Message, MessageContent, Role};
 
 /// System prompt for proactive behavior checks.
@@ -61,10 +62,12 @@ pub async fn run_proactive_check(
         stop_sequences: vec![],
     };
 
-    let response = provider
-        .complete(request)
-        .await
-        .context("proactive check LLM call failed")?;
+    let response = usage::scoped(
+        UsageScope::current_with_role("proactive"),
+        provider.complete(request),
+    )
+    .await
+    .context("proactive check LLM call failed")?;
 
     // Record actual token usage.
     let total = u64::from(response.usage.input_tokens)
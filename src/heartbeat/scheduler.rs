@@ -3,8 +3,12 @@
 //! Evaluates cron expressions from `agent.toml` scheduled tasks and dispatches
 //! due tasks. Builtin tasks (like "backup") are handled internally. Dynamic
 //! tool tasks execute via [`crate::tools::ToolRouter`].
+//!
+//! Tasks may declare `depends_on`. Due tasks are dispatched in dependency
+//! order, and a task only runs once every dependency has succeeded since the
+//! task's own last run. Unsatisfied tasks are skipped and retried next tick.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 
@@ -25,6 +29,8 @@ pub struct SchedulerState {
     last_run: HashMap<String, DateTime<Utc>>,
     /// Cached parsed cron schedules, keyed by cron expression string.
     schedules: HashMap<String, cron::Schedule>,
+    /// Tasks whose dependency skip has already been reported this cron window.
+    reported_skips: HashSet<String>,
 }
 
 impl SchedulerState {
@@ -33,12 +39,22 @@ impl SchedulerState {
         Self {
             last_run: HashMap::new(),
             schedules: HashMap::new(),
+            reported_skips: HashSet::new(),
         }
     }
 
     /// Record that a task was executed at the given time.
     pub fn record_run(&mut self, name: &str, at: DateTime<Utc>) {
         self.last_run.insert(name.to_owned(), at);
+        self.reported_skips.remove(name);
+    }
+
+    /// Mark a dependency skip as reported.
+    ///
+    /// Returns `true` the first time for a task, and `false` until the task
+    /// runs again, so a blocked task is reported once rather than every tick.
+    pub fn mark_skip_reported(&mut self, name: &str) -> bool {
+        self.reported_skips.insert(name.to_owned())
     }
 
     /// Get the last run time for a task.
@@ -90,6 +106,9 @@ pub struct TaskOutcome {
 /// 1. It is enabled.
 /// 2. Its cron expression matches a time between the last run and now.
 /// 3. It has not been run within the current cron interval.
+///
+/// The result is ordered so that every task comes after any due task it
+/// depends on; otherwise config order is preserved.
 pub fn due_tasks<'a>(
     tasks: &'a [ScheduledTaskConfig],
     state: &mut SchedulerState,
@@ -103,7 +122,7 @@ pub fn due_tasks<'a>(
         }
     }
 
-    tasks
+    let due: Vec<&ScheduledTaskConfig> = tasks
         .iter()
         .filter(|task| {
             if !task.enabled {
@@ -124,9 +143,96 @@ pub fn due_tasks<'a>(
             // Check if there's a cron trigger between the last run and now.
             schedule.after(&after).take(1).any(|next| next <= now)
         })
+        .collect();
+
+    dependency_order(due)
+}
+
+/// Order tasks so that dependencies come before their dependents.
+///
+/// Uses Kahn's algorithm restricted to the given set, always picking the
+/// earliest remaining task in config order. Cycles are rejected at config
+/// load; any that slip through are appended in config order.
+fn dependency_order(tasks: Vec<&ScheduledTaskConfig>) -> Vec<&ScheduledTaskConfig> {
+    let names: HashSet<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+    let mut placed: HashSet<&str> = HashSet::with_capacity(tasks.len());
+    let mut remaining = tasks;
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|task| {
+            task.depends_on
+                .iter()
+                .all(|dep| !names.contains(dep.as_str()) || placed.contains(dep.as_str()))
+        });
+        let Some(pos) = ready else {
+            warn!("scheduled task dependency cycle detected at dispatch");
+            ordered.append(&mut remaining);
+            break;
+        };
+        let task = remaining.remove(pos);
+        placed.insert(task.name.as_str());
+        ordered.push(task);
+    }
+
+    ordered
+}
+
+/// List the dependencies of `task` that have not succeeded since its last run.
+///
+/// A dependency is satisfied when it has a recorded successful run at or
+/// after the dependent's own last run (or any successful run, if the
+/// dependent has never run).
+pub fn unmet_dependencies(task: &ScheduledTaskConfig, state: &SchedulerState) -> Vec<String> {
+    let since = state
+        .last_run_for(&task.name)
+        .copied()
+        .unwrap_or(DateTime::<Utc>::UNIX_EPOCH);
+
+    task.depends_on
+        .iter()
+        .filter(|dep| state.last_run_for(dep).is_none_or(|at| *at < since))
+        .cloned()
         .collect()
 }
 
+/// Report a task skipped because its dependencies have not succeeded.
+///
+/// Logs every skip, and notifies the user (if the task has `notify` set)
+/// once per blocked cron window.
+pub async fn report_skipped(
+    task: &ScheduledTaskConfig,
+    unmet: &[String],
+    deps: &HeartbeatDeps,
+    state: &mut SchedulerState,
+) {
+    warn!(
+        task = %task.name,
+        unmet = %unmet.join(", "),
+        "scheduled task skipped: dependencies not satisfied"
+    );
+
+    if !state.mark_skip_reported(&task.name) || !task.notify {
+        return;
+    }
+
+    let text = format!(
+        "<b>Scheduled task:</b> {}\n<b>Status:</b> skipped\n<b>Waiting on:</b> {}",
+        escape_html(&task.name),
+        escape_html(&unmet.join(", "))
+    );
+    let msg = TelegramOutbound {
+        user_id: deps.notify_user_id,
+        text: Some(text),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard: None,
+    };
+    if let Err(e) = deps.telegram_tx.send(msg).await {
+        warn!(error = %e, "failed to send task skip notification");
+    }
+}
+
 /// Execute a scheduled task.
 ///
 /// Dispatches to the appropriate handler based on task configuration:
@@ -199,6 +305,7 @@ pub async fn execute_task(
             text: Some(text),
             file_path: None,
             approval_keyboard: None,
+            keyboard: None,
         };
         if let Err(e) = deps.telegram_tx.send(msg).await {
             warn!(error = %e, "failed to send task notification");
@@ -246,6 +353,33 @@ async fn execute_builtin(name: &str, deps: &HeartbeatDeps) -> anyhow::Result<Str
             )
             .await
         }
+        "memory_review" => {
+            super::memory_review::execute_memory_review(
+                &deps.memory,
+                &deps.telegram_tx,
+                deps.notify_user_id,
+            )
+            .await
+        }
+        "feed_digest" => {
+            let notice = super::budget_notice::reset_notice(
+                deps.memory.pool(),
+                &deps.daily_budget,
+                Utc::now(),
+            )
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "failed to build budget reset notice");
+                None
+            });
+            super::feeds::execute_feed_digest(
+                &deps.memory,
+                &deps.telegram_tx,
+                deps.notify_user_id,
+                notice,
+            )
+            .await
+        }
         other => Err(anyhow::anyhow!("unknown builtin task: {other}")),
     }
 }
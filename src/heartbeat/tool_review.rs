@@ -133,6 +133,7 @@ pub async fn execute_tool_review(
         text: Some(report.clone()),
         file_path: None,
         approval_keyboard: None,
+        keyboard: None,
     };
     telegram_tx.send(msg).await?;
 
@@ -11,6 +11,7 @@
 pub mod config;
 pub mod credentials;
 pub mod executor;
+pub mod log_query;
 pub mod logging;
 pub mod memory;
 pub mod providers;
@@ -22,5 +23,9 @@ pub mod whatsapp;
 
 pub mod tools;
 
+pub mod admin;
+pub mod doctor;
 pub mod heartbeat;
 pub mod observer;
+pub mod service;
+pub mod tui;
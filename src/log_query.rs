@@ -0,0 +1,290 @@
+//! Structured queries over the production JSONL logs (`wintermute logs`).
+//!
+//! [`crate::logging::init_production`] writes one JSON object per line to
+//! `data/logs/wintermute.log.YYYY-MM-DD`, rotating daily. This module reads
+//! those files oldest first, parses each line and applies a [`LogFilter`]
+//! on the structured fields instead of the raw text. Files whose date is
+//! before `since` are skipped without being opened. Lines that are not
+//! valid log records are ignored.
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde_json::Value;
+
+/// File name prefix used by the daily rolling appender.
+pub const LOG_FILE_PREFIX: &str = "wintermute.log";
+
+/// Lines longer than this are skipped rather than parsed.
+const MAX_LINE_LEN: usize = 1_048_576;
+
+/// One parsed log line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// When the event was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Level as written by `tracing` (`ERROR`, `WARN`, `INFO`, ...).
+    pub level: String,
+    /// Module path of the event.
+    pub target: String,
+    /// Event message, empty if the event had none.
+    pub message: String,
+    /// The full JSON object as written.
+    pub raw: Value,
+}
+
+impl LogRecord {
+    /// Parse one JSONL line. Returns `None` for anything that is not a log record.
+    pub fn parse(line: &str) -> Option<Self> {
+        let raw: Value = serde_json::from_str(line).ok()?;
+        let timestamp = raw
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())?
+            .with_timezone(&Utc);
+        let level = raw.get("level").and_then(Value::as_str)?.to_owned();
+        let target = raw
+            .get("target")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let message = raw
+            .pointer("/fields/message")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        Some(Self {
+            timestamp,
+            level,
+            target,
+            message,
+            raw,
+        })
+    }
+
+    /// The session this event belongs to, from its fields or enclosing spans.
+    pub fn session_id(&self) -> Option<&str> {
+        let from_fields = self.raw.pointer("/fields/session_id");
+        let from_span = self.raw.pointer("/span/session_id");
+        let from_spans = self
+            .raw
+            .get("spans")
+            .and_then(Value::as_array)
+            .and_then(|spans| spans.iter().rev().find_map(|s| s.get("session_id")));
+        from_fields
+            .or(from_span)
+            .or(from_spans)
+            .and_then(Value::as_str)
+    }
+
+    /// Event fields other than the message, in `key=value` form.
+    pub fn field_pairs(&self) -> Vec<String> {
+        let Some(fields) = self.raw.get("fields").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+        fields
+            .iter()
+            .filter(|(key, _)| key.as_str() != "message")
+            .map(|(key, value)| match value {
+                Value::String(s) => format!("{key}={s}"),
+                other => format!("{key}={other}"),
+            })
+            .collect()
+    }
+
+    /// Single-line, human-readable rendering.
+    pub fn pretty(&self) -> String {
+        let mut line = format!(
+            "{} {:<5} {}: {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            self.level,
+            self.target,
+            self.message
+        );
+        for pair in self.field_pairs() {
+            line.push(' ');
+            line.push_str(&pair);
+        }
+        if let Some(session) = self
+            .session_id()
+            .filter(|_| self.raw.pointer("/fields/session_id").is_none())
+        {
+            line.push_str(" session_id=");
+            line.push_str(session);
+        }
+        line
+    }
+}
+
+/// Which records to keep. Empty criteria match everything.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Only records at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+    /// Only records at least this severe.
+    pub min_level: Option<Severity>,
+    /// Only records belonging to this session.
+    pub session: Option<String>,
+    /// Only records whose message or fields match this pattern.
+    pub grep: Option<Regex>,
+}
+
+impl LogFilter {
+    /// Whether `record` passes every criterion.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        if self.since.is_some_and(|since| record.timestamp < since) {
+            return false;
+        }
+        if let Some(min) = self.min_level {
+            match Severity::parse(&record.level) {
+                Some(level) if level >= min => {}
+                _ => return false,
+            }
+        }
+        if let Some(ref session) = self.session {
+            if record.session_id() != Some(session.as_str()) {
+                return false;
+            }
+        }
+        if let Some(ref grep) = self.grep {
+            let matched = grep.is_match(&record.message)
+                || record.field_pairs().iter().any(|pair| grep.is_match(pair));
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Log level ordered by severity, least severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// `TRACE`.
+    Trace,
+    /// `DEBUG`.
+    Debug,
+    /// `INFO`.
+    Info,
+    /// `WARN`.
+    Warn,
+    /// `ERROR`.
+    Error,
+}
+
+impl Severity {
+    /// Parse a level name, case-insensitively (`warning` is accepted for `warn`).
+    pub fn parse(level: &str) -> Option<Self> {
+        match level.to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Parse `--since`: a relative age (`90s`, `15m`, `2h`, `3d`, `1w`) before
+/// `now`, or an RFC 3339 timestamp.
+///
+/// # Errors
+///
+/// Returns an error when the value is neither.
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow::anyhow!("missing unit in '{value}' (use s, m, h, d or w)"))?;
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .with_context(|| format!("invalid duration '{value}'"))?;
+    let age = match unit {
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow::anyhow!("invalid duration '{value}' (use s, m, h, d or w)"))?;
+    now.checked_sub_signed(age)
+        .ok_or_else(|| anyhow::anyhow!("duration '{value}' is out of range"))
+}
+
+/// Log files in `dir`, oldest first, skipping files dated before `since`.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+pub fn log_files(dir: &Path, since: Option<DateTime<Utc>>) -> anyhow::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let since_date = since.map(|s| s.date_naive());
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(suffix) = name.strip_prefix(LOG_FILE_PREFIX) else {
+            continue;
+        };
+        let date = suffix
+            .strip_prefix('.')
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        if let (Some(date), Some(since_date)) = (date, since_date) {
+            if date < since_date {
+                continue;
+            }
+        }
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Stream every matching record in `dir` to `on_match`, oldest first.
+///
+/// Returns the number of matching records.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a log file cannot be read.
+pub fn query(
+    dir: &Path,
+    filter: &LogFilter,
+    mut on_match: impl FnMut(&LogRecord),
+) -> anyhow::Result<usize> {
+    let mut matched: usize = 0;
+    for path in log_files(dir, filter.since)? {
+        let file =
+            fs::File::open(&path).with_context(|| format!("failed to open {}", path.display()))?;
+        for line in BufReader::new(file).lines() {
+            let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+            if line.len() > MAX_LINE_LEN {
+                continue;
+            }
+            let Some(record) = LogRecord::parse(&line) else {
+                continue;
+            };
+            if filter.matches(&record) {
+                matched = matched.saturating_add(1);
+                on_match(&record);
+            }
+        }
+    }
+    Ok(matched)
+}
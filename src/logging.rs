@@ -1,36 +1,67 @@
 //! Structured logging setup using `tracing-subscriber` and `tracing-appender`.
 //!
-//! Two modes:
+//! Three modes:
 //! - **Production** ([`init_production`]): JSON file layer (daily rotation) + console layer
+//! - **TUI** ([`init_tui`]): JSON file layer + in-memory [`LogTail`] (the
+//!   terminal belongs to the UI, so nothing goes to stderr)
 //! - **CLI** ([`init_cli`]): console-only for one-shot subcommands
+//!
+//! In production mode, spans are additionally exported over OTLP/HTTP when
+//! `[telemetry] otlp_endpoint` is set.
 
+use std::collections::VecDeque;
+use std::io::Write;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
+use crate::config::TelemetryConfig;
+
 /// Holds the non-blocking writer guard for file logging.
 ///
 /// The [`WorkerGuard`] must be kept alive for the duration of the process.
-/// Dropping it flushes pending log entries and closes the file.
+/// Dropping it flushes pending log entries and closes the file, and flushes
+/// any spans still queued for OTLP export.
 pub struct LoggingGuard {
     _guard: WorkerGuard,
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for LoggingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            // Logging may already be torn down; nothing useful to do on error.
+            let _ = provider.shutdown();
+        }
+    }
 }
 
 /// Initialise logging for the `start` subcommand (production mode).
 ///
 /// Writes JSON logs to `{logs_dir}/wintermute.log.YYYY-MM-DD` with daily
 /// rotation. Also emits human-readable output to stderr controlled by the
-/// `RUST_LOG` environment variable (default: `info`).
+/// `RUST_LOG` environment variable (default: `info`). When
+/// `telemetry.otlp_endpoint` is set, spans are batched to that endpoint.
 ///
 /// Returns a [`LoggingGuard`] that must be kept alive for log flushing.
 ///
 /// # Errors
 ///
-/// Returns an error if the logs directory cannot be created.
-pub fn init_production(logs_dir: &Path) -> anyhow::Result<LoggingGuard> {
+/// Returns an error if the logs directory cannot be created or the OTLP
+/// exporter cannot be built.
+pub fn init_production(
+    logs_dir: &Path,
+    telemetry: &TelemetryConfig,
+) -> anyhow::Result<LoggingGuard> {
     std::fs::create_dir_all(logs_dir).map_err(|e| {
         anyhow::anyhow!(
             "failed to create logs directory {}: {e}",
@@ -49,13 +80,162 @@ pub fn init_production(logs_dir: &Path) -> anyhow::Result<LoggingGuard> {
 
     let console_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
 
+    let tracer_provider = match telemetry.otlp_endpoint.as_deref() {
+        Some(endpoint) => Some(build_tracer_provider(endpoint, &telemetry.service_name)?),
+        None => None,
+    };
+    let otel_layer = tracer_provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("wintermute")));
+
     tracing_subscriber::registry()
         .with(env_filter)
         .with(json_layer)
         .with(console_layer)
+        .with(otel_layer)
         .init();
 
-    Ok(LoggingGuard { _guard: guard })
+    Ok(LoggingGuard {
+        _guard: guard,
+        tracer_provider,
+    })
+}
+
+/// Build a batching tracer provider exporting to an OTLP/HTTP endpoint.
+fn build_tracer_provider(endpoint: &str, service_name: &str) -> anyhow::Result<SdkTracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| anyhow::anyhow!("failed to build OTLP exporter for {endpoint}: {e}"))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_owned())
+                .build(),
+        )
+        .build())
+}
+
+/// Initialise logging for the `tui` subcommand.
+///
+/// Same JSON file output as [`init_production`], but console output goes to
+/// `tail` instead of stderr so it can be shown inside the terminal UI.
+///
+/// # Errors
+///
+/// Returns an error if the logs directory cannot be created.
+pub fn init_tui(logs_dir: &Path, tail: LogTail) -> anyhow::Result<LoggingGuard> {
+    std::fs::create_dir_all(logs_dir).map_err(|e| {
+        anyhow::anyhow!(
+            "failed to create logs directory {}: {e}",
+            logs_dir.display()
+        )
+    })?;
+
+    let file_appender = tracing_appender::rolling::daily(logs_dir, "wintermute.log");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let json_layer = tracing_subscriber::fmt::layer()
+        .json()
+        .with_writer(non_blocking);
+    let tail_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_target(false)
+        .with_writer(tail);
+
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(json_layer)
+        .with(tail_layer)
+        .init();
+
+    Ok(LoggingGuard {
+        _guard: guard,
+        tracer_provider: None,
+    })
+}
+
+/// Bounded in-memory buffer of the most recent formatted log lines.
+///
+/// Cloning shares the same buffer. Implements [`MakeWriter`] so it can be
+/// plugged into a `tracing_subscriber` fmt layer.
+#[derive(Debug, Clone)]
+pub struct LogTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl LogTail {
+    /// Create a buffer holding at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Append one line, evicting the oldest when full.
+    pub fn push(&self, line: String) {
+        if let Ok(mut lines) = self.lines.lock() {
+            while lines.len() >= self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+
+    /// Snapshot of the buffered lines, oldest first.
+    pub fn lines(&self) -> Vec<String> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Writer handed out by [`LogTail`] for a single log event.
+#[derive(Debug)]
+pub struct LogTailWriter {
+    tail: LogTail,
+    buf: Vec<u8>,
+}
+
+impl Write for LogTailWriter {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let text = String::from_utf8_lossy(&self.buf).into_owned();
+        self.buf.clear();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            self.tail.push(line.to_owned());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LogTailWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogTail {
+    type Writer = LogTailWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogTailWriter {
+            tail: self.clone(),
+            buf: Vec::new(),
+        }
+    }
 }
 
 /// Initialise minimal logging for non-`start` subcommands (CLI mode).
@@ -35,7 +35,9 @@ use wintermute::executor::redactor::Redactor;
 use wintermute::executor::Executor;
 use wintermute::logging;
 use wintermute::memory::{MemoryEngine, TrustSource};
+use wintermute::observer::queue::ObserverQueue;
 use wintermute::providers::router::ModelRouter;
+use wintermute::service::{self, Service};
 use wintermute::telegram;
 use wintermute::tools::browser::{detect_browser, BrowserBridge, BrowserMode};
 use wintermute::tools::browser_bridge::PlaywrightBridge;
@@ -46,6 +48,38 @@ const BOOTSTRAP_MIGRATION: &str = "001_schema.sql";
 const MEMORY_MIGRATION: &str = "002_memory.sql";
 const SESSIONS_MIGRATION: &str = "003_sessions.sql";
 const BRIEFS_MIGRATION: &str = "004_briefs.sql";
+const FEEDBACK_MIGRATION: &str = "005_feedback.sql";
+const CONTACTS_MIGRATION: &str = "006_contacts.sql";
+const OUTBOUND_QUEUE_MIGRATION: &str = "007_outbound_queue.sql";
+const TEMPLATES_MIGRATION: &str = "008_templates.sql";
+const DELIVERY_STATE_MIGRATION: &str = "009_delivery_state.sql";
+const FEEDS_MIGRATION: &str = "010_feeds.sql";
+const SOUL_VERSIONS_MIGRATION: &str = "011_soul_versions.sql";
+const TELEGRAM_OUTBOX_MIGRATION: &str = "012_telegram_outbox.sql";
+const TELEGRAM_UPDATES_MIGRATION: &str = "013_telegram_updates.sql";
+const MEMORY_AUDIT_MIGRATION: &str = "014_memory_audit.sql";
+const TOOL_GRANTS_MIGRATION: &str = "015_tool_network_grants.sql";
+const USAGE_LEDGER_MIGRATION: &str = "016_usage_ledger.sql";
+
+/// Every migration this binary applies, checked by `wintermute doctor`.
+const ALL_MIGRATIONS: &[&str] = &[
+    BOOTSTRAP_MIGRATION,
+    MEMORY_MIGRATION,
+    SESSIONS_MIGRATION,
+    BRIEFS_MIGRATION,
+    FEEDBACK_MIGRATION,
+    CONTACTS_MIGRATION,
+    OUTBOUND_QUEUE_MIGRATION,
+    TEMPLATES_MIGRATION,
+    DELIVERY_STATE_MIGRATION,
+    FEEDS_MIGRATION,
+    SOUL_VERSIONS_MIGRATION,
+    TELEGRAM_OUTBOX_MIGRATION,
+    TELEGRAM_UPDATES_MIGRATION,
+    MEMORY_AUDIT_MIGRATION,
+    TOOL_GRANTS_MIGRATION,
+    USAGE_LEDGER_MIGRATION,
+];
 
 /// Wintermute — a self-coding AI agent.
 #[derive(Parser)]
@@ -63,8 +97,12 @@ enum Command {
     Init,
     /// Start the agent (connects to Telegram, begins listening)
     Start,
+    /// Chat with the agent in a local terminal UI (no Telegram needed)
+    Tui,
     /// Show health status, sandbox info, and memory stats
     Status,
+    /// Check config, credentials, providers, Docker, memory.db and Telegram
+    Doctor,
     /// Recreate the sandbox container and reinstall dependencies
     Reset,
     /// Trigger an immediate backup of scripts and memory
@@ -73,6 +111,30 @@ enum Command {
         #[command(subcommand)]
         action: Option<BackupAction>,
     },
+    /// Search the production logs, including rotated files
+    Logs {
+        /// Only entries newer than this age (30m, 2h, 7d) or RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Minimum level: trace, debug, info, warn or error
+        #[arg(long)]
+        level: Option<String>,
+        /// Only entries for this session ID
+        #[arg(long)]
+        session: Option<String>,
+        /// Regex matched against the message and fields
+        #[arg(long)]
+        grep: Option<String>,
+        /// Print matching entries as raw JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Install, remove or inspect the systemd/launchd service
+    Service {
+        /// Subcommand for service operations
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
 }
 
 /// Backup subcommands.
@@ -87,17 +149,42 @@ enum BackupAction {
     },
 }
 
+/// Service subcommands.
+#[derive(Subcommand)]
+enum ServiceAction {
+    /// Generate the service file, then enable and start it
+    Install,
+    /// Stop the service and remove its service file
+    Uninstall,
+    /// Show whether the service is installed and running
+    Status,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     // Start subcommand gets production logging (JSON + file rotation).
     // All other subcommands get simple CLI logging (stderr only).
+    let log_tail = logging::LogTail::new(TUI_LOG_LINES);
     let _logging_guard = match &cli.command {
         Command::Start => {
             let paths = runtime_paths()?;
             let logs_dir = paths.data_dir.join("logs");
-            Some(logging::init_production(&logs_dir)?)
+            // A broken config.toml is reported by handle_start; tracing
+            // export just falls back to off here.
+            let telemetry = load_default_config()
+                .map(|c| c.telemetry)
+                .unwrap_or_default();
+            Some(logging::init_production(&logs_dir, &telemetry)?)
+        }
+        // The terminal belongs to the UI; logs go to the file and the log pane.
+        Command::Tui => {
+            let paths = runtime_paths()?;
+            Some(logging::init_tui(
+                &paths.data_dir.join("logs"),
+                log_tail.clone(),
+            )?)
         }
         _ => {
             logging::init_cli();
@@ -108,7 +195,9 @@ async fn main() -> anyhow::Result<()> {
     match cli.command {
         Command::Init => handle_init().await?,
         Command::Start => handle_start().await?,
+        Command::Tui => handle_tui(log_tail).await?,
         Command::Status => handle_status().await?,
+        Command::Doctor => handle_doctor().await?,
         Command::Reset => handle_reset().await?,
         Command::Backup { action } => match action {
             None => handle_backup(None).await?,
@@ -117,6 +206,14 @@ async fn main() -> anyhow::Result<()> {
                 handle_backup(Some(BackupRequest::Restore { index })).await?
             }
         },
+        Command::Logs {
+            since,
+            level,
+            session,
+            grep,
+            json,
+        } => handle_logs(since, level, session, grep, json)?,
+        Command::Service { action } => handle_service(action).await?,
     }
 
     Ok(())
@@ -146,6 +243,54 @@ async fn handle_init() -> anyhow::Result<()> {
 /// Outbound channel buffer size.
 const OUTBOUND_CHANNEL_CAPACITY: usize = 256;
 
+/// Log lines kept for the TUI log pane.
+const TUI_LOG_LINES: usize = 500;
+
+/// Reload config on SIGHUP and report the result to the owner.
+#[cfg(unix)]
+fn spawn_sighup_reload(
+    reloader: Arc<wintermute::agent::reload::ConfigReloader>,
+    telegram_tx: mpsc::Sender<TelegramOutbound>,
+) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            warn!(error = %e, "failed to install SIGHUP handler; use /reload instead");
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            info!("SIGHUP received, reloading config");
+            let text = match reloader.reload().await {
+                Ok(report) => wintermute::agent::reload::format_report(&report),
+                Err(e) => {
+                    warn!(error = %e, "config reload failed");
+                    format!(
+                        "Config reload failed, keeping the running config: {}",
+                        wintermute::telegram::ui::escape_html(&e.to_string())
+                    )
+                }
+            };
+            let Some(owner) = reloader.owner() else {
+                continue;
+            };
+            let msg = TelegramOutbound {
+                user_id: owner,
+                text: Some(text),
+                file_path: None,
+                approval_keyboard: None,
+                keyboard: None,
+            };
+            if let Err(e) = telegram_tx.send(msg).await {
+                warn!(error = %e, "failed to send reload report");
+            }
+        }
+    });
+}
+
 async fn handle_start() -> anyhow::Result<()> {
     let paths = runtime_paths()?;
 
@@ -153,15 +298,449 @@ async fn handle_start() -> anyhow::Result<()> {
     std::fs::write(&paths.pid_file, std::process::id().to_string())
         .with_context(|| format!("failed to write PID file {}", paths.pid_file.display()))?;
 
-    let config = load_default_config()
-        .with_context(|| format!("failed to load {}", paths.config_toml.display()))?;
-    let agent_config = load_default_agent_config()
-        .with_context(|| format!("failed to load {}", paths.agent_toml.display()))?;
-    let credentials = load_default_credentials()
-        .with_context(|| format!("failed to load {}", paths.env_file.display()))?;
-    let token_key = &config.channels.telegram.bot_token_env;
-    let telegram_token = credentials.require(token_key)?;
+    let config = load_default_config()
+        .with_context(|| format!("failed to load {}", paths.config_toml.display()))?;
+    let agent_config = load_default_agent_config()
+        .with_context(|| format!("failed to load {}", paths.agent_toml.display()))?;
+    let credentials = load_default_credentials()
+        .with_context(|| format!("failed to load {}", paths.env_file.display()))?;
+    let token_key = &config.channels.telegram.bot_token_env;
+    let telegram_token = credentials.require(token_key)?;
+
+    let AgentStack {
+        config: config_arc,
+        agent_config: agent_config_arc,
+        credentials,
+        all_secrets,
+        executor,
+        memory,
+        router: router_arc,
+        daily_budget,
+        approval_manager,
+        registry,
+        tool_router,
+        session_router,
+        observer_tx,
+        observer_queue,
+        browser_mode,
+        telegram_tx,
+        telegram_rx,
+    } = build_agent_stack(&paths, config, agent_config, credentials).await?;
+
+    // Phase 4: WhatsApp event listener for autonomous inbound message routing.
+    if config_arc.whatsapp.enabled {
+        let wa_base_url = format!(
+            "http://127.0.0.1:{}",
+            wintermute::whatsapp::client::DEFAULT_BRIDGE_PORT
+        );
+        let (wa_event_tx, mut wa_event_rx) =
+            mpsc::channel::<wintermute::whatsapp::events::WhatsAppEvent>(128);
+        wintermute::whatsapp::events::spawn_event_listener(wa_base_url, wa_event_tx);
+
+        let wa_session_router = Arc::clone(&session_router);
+        let wa_memory_pool = memory.pool().clone();
+        let wa_telegram_tx = telegram_tx.clone();
+        let wa_media_client = wintermute::whatsapp::client::WhatsAppClient::default_url();
+        let wa_inbox_dir = paths.workspace_dir.join("inbox");
+        let wa_notify_user_id = config_arc
+            .channels
+            .telegram
+            .allowed_users
+            .first()
+            .copied()
+            .unwrap_or(0);
+
+        let wa_supervisor_client =
+            Arc::new(wintermute::whatsapp::client::WhatsAppClient::default_url());
+        let wa_connection_tx = wintermute::whatsapp::reconnect::spawn_supervisor(
+            Arc::clone(&wa_supervisor_client),
+            paths.data_dir.clone(),
+            telegram_tx.clone(),
+            wa_notify_user_id,
+        );
+        // Reconnect or re-pair right away if the session is not up at startup.
+        let startup_signal = match wa_supervisor_client.status().await {
+            Ok(status) if status.connected => None,
+            Ok(status) => Some(
+                wintermute::whatsapp::reconnect::ConnectionSignal::Disconnected {
+                    reason: Some("not connected at startup".to_owned()),
+                    logged_out: status.logged_out,
+                },
+            ),
+            Err(e) => Some(
+                wintermute::whatsapp::reconnect::ConnectionSignal::Disconnected {
+                    reason: Some(format!("sidecar unreachable at startup: {e}")),
+                    logged_out: false,
+                },
+            ),
+        };
+        if let Some(signal) = startup_signal {
+            let _ = wa_connection_tx.send(signal).await;
+        }
+
+        tokio::spawn(async move {
+            while let Some(event) = wa_event_rx.recv().await {
+                match event {
+                    wintermute::whatsapp::events::WhatsAppEvent::Message {
+                        jid,
+                        text,
+                        from_me,
+                        message_id,
+                        media,
+                    } => {
+                        // Skip messages sent by the agent itself
+                        if from_me {
+                            continue;
+                        }
+
+                        match wintermute::whatsapp::router::route_incoming(&wa_memory_pool, &jid)
+                            .await
+                        {
+                            Ok(wintermute::whatsapp::router::RouteResult::Routed {
+                                brief_id,
+                                session_id,
+                            }) => {
+                                // Media is only fetched for contacts with an
+                                // active brief; it replaces the text with a
+                                // description, as Telegram media does.
+                                let text = match (&media, &message_id) {
+                                    (Some(media), Some(message_id)) => {
+                                        match wintermute::whatsapp::media::save_incoming(
+                                            &wa_media_client,
+                                            message_id,
+                                            media,
+                                            &text,
+                                            &wa_inbox_dir,
+                                        )
+                                        .await
+                                        {
+                                            Ok(desc) => desc.text,
+                                            Err(e) => {
+                                                warn!(error = %e, %jid, "failed to save WhatsApp media");
+                                                format!("[Media could not be downloaded]\n{text}")
+                                            }
+                                        }
+                                    }
+                                    _ => text,
+                                };
+
+                                // Audit-log the inbound message for compliance.
+                                if let Err(e) = wintermute::messaging::audit::log_outbound(
+                                    &wa_memory_pool,
+                                    Some(&brief_id),
+                                    &session_id,
+                                    "whatsapp",
+                                    &jid,
+                                    &text,
+                                    "inbound",
+                                    None,
+                                    false,
+                                    None,
+                                )
+                                .await
+                                {
+                                    warn!(error = %e, "failed to audit-log inbound WhatsApp message");
+                                }
+
+                                if let Err(e) = wa_session_router
+                                    .route_inbound(
+                                        brief_id.clone(),
+                                        session_id.clone(),
+                                        jid.clone(),
+                                        text,
+                                    )
+                                    .await
+                                {
+                                    warn!(
+                                        error = %e,
+                                        %brief_id,
+                                        %session_id,
+                                        "failed to route inbound WhatsApp message"
+                                    );
+                                }
+                            }
+                            Ok(wintermute::whatsapp::router::RouteResult::Unhandled {
+                                jid: unhandled_jid,
+                            }) => {
+                                info!(jid = %unhandled_jid, "unhandled WhatsApp message (no active brief)");
+                                // Forward to user via Telegram for awareness
+                                let preview = if text.len() > 200 {
+                                    // Use char-boundary-safe truncation to avoid
+                                    // panicking on multi-byte UTF-8 (e.g. emoji).
+                                    let end = text.floor_char_boundary(200);
+                                    format!("{}...", &text[..end])
+                                } else {
+                                    text
+                                };
+                                let preview = if media.is_some() {
+                                    format!("[media] {preview}")
+                                } else {
+                                    preview
+                                };
+                                let notify = wintermute::agent::TelegramOutbound {
+                                    user_id: wa_notify_user_id,
+                                    text: Some(format!(
+                                        "[WhatsApp] Unhandled message from {unhandled_jid}: {preview}"
+                                    )),
+                                    file_path: None,
+                                    approval_keyboard: None,
+                                    keyboard: None,
+                                };
+                                let _ = wa_telegram_tx.send(notify).await;
+                            }
+                            Err(e) => {
+                                warn!(error = %e, %jid, "WhatsApp message routing failed");
+                            }
+                        }
+                    }
+                    wintermute::whatsapp::events::WhatsAppEvent::Receipt {
+                        message_id,
+                        status,
+                        ..
+                    } => {
+                        let Some(state) =
+                            wintermute::messaging::delivery::DeliveryState::from_receipt(&status)
+                        else {
+                            continue;
+                        };
+                        if let Err(e) = wintermute::messaging::delivery::record_receipt(
+                            &wa_memory_pool,
+                            &message_id,
+                            state,
+                        )
+                        .await
+                        {
+                            warn!(error = %e, "failed to record WhatsApp receipt");
+                        }
+                    }
+                    wintermute::whatsapp::events::WhatsAppEvent::Connected => {
+                        info!("WhatsApp connected");
+                        let _ = wa_connection_tx
+                            .send(wintermute::whatsapp::reconnect::ConnectionSignal::Connected)
+                            .await;
+                    }
+                    wintermute::whatsapp::events::WhatsAppEvent::Disconnected {
+                        reason,
+                        logged_out,
+                    } => {
+                        warn!(reason = ?reason, logged_out, "WhatsApp disconnected");
+                        let _ = wa_connection_tx
+                            .send(
+                                wintermute::whatsapp::reconnect::ConnectionSignal::Disconnected {
+                                    reason,
+                                    logged_out,
+                                },
+                            )
+                            .await;
+                    }
+                }
+            }
+        });
+        info!("WhatsApp event listener spawned");
+    }
+
+    // In-place config reload via SIGHUP or the owner's /reload command.
+    let reloader = Arc::new(wintermute::agent::reload::ConfigReloader::new(
+        paths.clone(),
+        Arc::clone(&config_arc),
+        Arc::clone(&agent_config_arc),
+        Arc::clone(&session_router),
+        Arc::clone(&router_arc),
+    ));
+    #[cfg(unix)]
+    spawn_sighup_reload(Arc::clone(&reloader), telegram_tx.clone());
+
+    // Phase 3: Heartbeat background task with graceful shutdown via Ctrl+C.
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("ctrl-c received, signalling heartbeat shutdown");
+            let _ = shutdown_tx.send(true);
+        }
+    });
+    let admin_shutdown_rx = shutdown_rx.clone();
+    let (task_tx, task_rx) = mpsc::channel::<wintermute::heartbeat::TaskTrigger>(8);
+    if agent_config_arc.heartbeat.enabled {
+        let notify_user_id = match config_arc.channels.telegram.allowed_users.first() {
+            Some(&id) => id,
+            None => {
+                warn!("no allowed_users configured; heartbeat notifications disabled");
+                0
+            }
+        };
+        let heartbeat_deps = wintermute::heartbeat::HeartbeatDeps {
+            config: Arc::clone(&config_arc),
+            agent_config: Arc::clone(&agent_config_arc),
+            memory: Arc::clone(&memory),
+            executor: Arc::clone(&executor),
+            tool_router: Arc::clone(&tool_router),
+            router: Arc::clone(&router_arc),
+            daily_budget: Arc::clone(&daily_budget),
+            telegram_tx: telegram_tx.clone(),
+            notify_user_id,
+            paths: paths.clone(),
+            session_router: Arc::clone(&session_router),
+            browser_mode,
+            observer_tx,
+            observer_queue,
+            interval_secs: reloader.heartbeat_interval(),
+        };
+        tokio::spawn(wintermute::heartbeat::run_heartbeat(
+            heartbeat_deps,
+            Instant::now(),
+            shutdown_rx,
+            task_rx,
+        ));
+        info!("heartbeat spawned");
+    } else {
+        info!("heartbeat disabled via heartbeat.enabled = false");
+    }
+
+    // Localhost admin API for dashboards and scripts.
+    if config_arc.admin_api.enabled {
+        let bind = config_arc.admin_api.bind.clone();
+        wintermute::admin::check_bind(&bind).context("invalid [admin_api] configuration")?;
+        let admin_state = Arc::new(wintermute::admin::AdminState {
+            token: credentials.require(&config_arc.admin_api.token_env)?,
+            memory: Arc::clone(&memory),
+            daily_budget: Arc::clone(&daily_budget),
+            session_router: Arc::clone(&session_router),
+            agent_config: Arc::clone(&agent_config_arc),
+            health_path: paths.health_json.clone(),
+            task_tx: agent_config_arc.heartbeat.enabled.then_some(task_tx),
+            redactor: tool_router.redactor().clone(),
+        });
+        tokio::spawn(async move {
+            if let Err(e) = wintermute::admin::serve(&bind, admin_state, admin_shutdown_rx).await {
+                warn!(error = %e, "admin API stopped");
+            }
+        });
+    }
+
+    info!(
+        default_model = %config_arc.models.default,
+        "starting telegram bot"
+    );
+
+    let pid_file = paths.pid_file.clone();
+    telegram::run_telegram(
+        &telegram_token,
+        reloader,
+        session_router,
+        approval_manager,
+        telegram_rx,
+        all_secrets,
+        tool_router.redactor().clone(),
+        executor,
+        memory,
+        registry,
+        paths,
+    )
+    .await?;
+
+    // A clean stop frees the runtime for `tui`.
+    if let Err(e) = std::fs::remove_file(&pid_file) {
+        warn!(error = %e, "failed to remove PID file");
+    }
+
+    Ok(())
+}
+
+/// Run the agent behind a local terminal UI instead of Telegram.
+///
+/// Shares the session, tool, and memory wiring with `start`, but skips the
+/// Telegram bot, heartbeat, WhatsApp listener, admin API, and PID file. It
+/// refuses to run while a `start` process is alive, since both would own the
+/// same sandbox container and database writer.
+async fn handle_tui(log_tail: logging::LogTail) -> anyhow::Result<()> {
+    let paths = runtime_paths()?;
+    if let Some(pid) = running_daemon_pid(&paths.pid_file) {
+        return Err(anyhow::anyhow!(
+            "wintermute is already running (pid {pid}); stop it before starting the TUI, \
+             or remove {} if that process is gone",
+            paths.pid_file.display()
+        ));
+    }
+    let config = load_default_config()
+        .with_context(|| format!("failed to load {}", paths.config_toml.display()))?;
+    let agent_config = load_default_agent_config()
+        .with_context(|| format!("failed to load {}", paths.agent_toml.display()))?;
+    let credentials = load_default_credentials()
+        .with_context(|| format!("failed to load {}", paths.env_file.display()))?;
+
+    // Act as the owner so sessions and approvals match Telegram's.
+    let user_id = config
+        .channels
+        .telegram
+        .allowed_users
+        .first()
+        .copied()
+        .with_context(|| {
+            format!(
+                "no allowed user configured; add your Telegram user id to \
+                 channels.telegram.allowed_users in {}",
+                paths.config_toml.display()
+            )
+        })?;
+
+    let stack = build_agent_stack(&paths, config, agent_config, credentials).await?;
+
+    wintermute::tui::run_tui(wintermute::tui::TuiDeps {
+        session_router: stack.session_router,
+        approval_manager: stack.approval_manager,
+        daily_budget: stack.daily_budget,
+        outbound_rx: stack.telegram_rx,
+        log_tail,
+        user_id,
+    })
+    .await
+}
+
+/// Return the PID recorded by a live `start` process, if any.
+///
+/// `start` removes its PID file on a clean stop. Where `/proc` exists the
+/// PID is also checked there, so a file left by a crash does not count; a
+/// missing or unreadable file, or one naming this process, never does.
+fn running_daemon_pid(pid_file: &Path) -> Option<u32> {
+    let pid: u32 = fs::read_to_string(pid_file).ok()?.trim().parse().ok()?;
+    if pid == std::process::id() {
+        return None;
+    }
+    let proc_root = Path::new("/proc");
+    if proc_root.is_dir() && !proc_root.join(pid.to_string()).exists() {
+        return None;
+    }
+    Some(pid)
+}
+
+/// Components shared by the Telegram bot (`start`) and the local TUI (`tui`).
+struct AgentStack {
+    config: Arc<wintermute::config::Config>,
+    agent_config: Arc<wintermute::config::AgentConfig>,
+    credentials: Credentials,
+    all_secrets: Vec<String>,
+    executor: Arc<dyn Executor>,
+    memory: Arc<MemoryEngine>,
+    router: Arc<ModelRouter>,
+    daily_budget: Arc<DailyBudget>,
+    approval_manager: Arc<ApprovalManager>,
+    registry: Arc<DynamicToolRegistry>,
+    tool_router: Arc<ToolRouter>,
+    session_router: Arc<SessionRouter>,
+    observer_tx: Option<mpsc::Sender<wintermute::observer::ObserverEvent>>,
+    observer_queue: Option<Arc<ObserverQueue>>,
+    browser_mode: BrowserMode,
+    telegram_tx: mpsc::Sender<TelegramOutbound>,
+    telegram_rx: mpsc::Receiver<TelegramOutbound>,
+}
 
+/// Build the executor, memory engine, tool router, and session router.
+async fn build_agent_stack(
+    paths: &RuntimePaths,
+    config: wintermute::config::Config,
+    agent_config: wintermute::config::AgentConfig,
+    credentials: Credentials,
+) -> anyhow::Result<AgentStack> {
     // Resolve auth once so the router and redactor use the same token.
     // If an OAuth token is expired and a refresh token is available, attempt
     // to refresh before building the provider.
@@ -178,9 +757,11 @@ async fn handle_start() -> anyhow::Result<()> {
     }
 
     // Set up executor: Docker preferred, Direct as fallback
-    let redactor = Redactor::new(all_secrets.clone());
+    let redactor = Redactor::new(all_secrets.clone())
+        .with_patterns(&config.privacy.redact_patterns)
+        .context("invalid [privacy] redact_patterns")?;
     let executor: Arc<dyn Executor> = if DockerExecutor::docker_available().await {
-        let docker = DockerExecutor::new(&config, &paths, redactor.clone()).await?;
+        let docker = DockerExecutor::new(&config, paths, redactor.clone()).await?;
         let health = docker.health_check().await?;
         if !health.is_healthy() {
             return Err(anyhow::anyhow!("docker executor unhealthy: {health:?}"));
@@ -222,12 +803,85 @@ async fn handle_start() -> anyhow::Result<()> {
         include_str!("../migrations/004_briefs.sql"),
     )
     .await?;
+    apply_migration(
+        &pool,
+        FEEDBACK_MIGRATION,
+        include_str!("../migrations/005_feedback.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        CONTACTS_MIGRATION,
+        include_str!("../migrations/006_contacts.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        OUTBOUND_QUEUE_MIGRATION,
+        include_str!("../migrations/007_outbound_queue.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        TEMPLATES_MIGRATION,
+        include_str!("../migrations/008_templates.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        DELIVERY_STATE_MIGRATION,
+        include_str!("../migrations/009_delivery_state.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        FEEDS_MIGRATION,
+        include_str!("../migrations/010_feeds.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        SOUL_VERSIONS_MIGRATION,
+        include_str!("../migrations/011_soul_versions.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        TELEGRAM_OUTBOX_MIGRATION,
+        include_str!("../migrations/012_telegram_outbox.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        TELEGRAM_UPDATES_MIGRATION,
+        include_str!("../migrations/013_telegram_updates.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        MEMORY_AUDIT_MIGRATION,
+        include_str!("../migrations/014_memory_audit.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        TOOL_GRANTS_MIGRATION,
+        include_str!("../migrations/015_tool_network_grants.sql"),
+    )
+    .await?;
+    apply_migration(
+        &pool,
+        USAGE_LEDGER_MIGRATION,
+        include_str!("../migrations/016_usage_ledger.sql"),
+    )
+    .await?;
 
     let memory = Arc::new(
         MemoryEngine::new(pool, None)
             .await
             .context("failed to initialise memory engine")?,
     );
+    router.record_usage_to(Arc::clone(&memory));
 
     // Seed trust ledger with pre-approved domains from config.
     for domain in &config.egress.allowed_domains {
@@ -238,11 +892,13 @@ async fn handle_start() -> anyhow::Result<()> {
     }
 
     // Phase 2 wiring
-    let daily_budget = Arc::new(DailyBudget::new(config.budget.max_tokens_per_day));
+    let daily_budget = Arc::new(DailyBudget::from_config(&config.budget));
+    restore_daily_budget(&daily_budget, memory.pool()).await;
     let approval_manager = Arc::new(ApprovalManager::new());
 
     let registry = DynamicToolRegistry::new(paths.scripts_dir.clone())
         .context("failed to create tool registry")?;
+    registry.set_health_policy(config.tool_health.clone());
 
     let (telegram_tx, telegram_rx) = mpsc::channel::<TelegramOutbound>(OUTBOUND_CHANNEL_CAPACITY);
 
@@ -281,7 +937,8 @@ async fn handle_start() -> anyhow::Result<()> {
                         Ok(sidecar) => {
                             info!("browser bridge: Playwright sidecar ready");
                             let bridge: Option<Arc<dyn BrowserBridge>> = Some(Arc::new(
-                                PlaywrightBridge::new(sidecar.base_url().to_owned()),
+                                PlaywrightBridge::new(sidecar.base_url().to_owned())
+                                    .with_config(&config.browser),
                             ));
                             (BrowserMode::Standalone { port: 9223 }, bridge)
                         }
@@ -322,7 +979,9 @@ async fn handle_start() -> anyhow::Result<()> {
                     if wa_client.health_check().await.unwrap_or(false) {
                         info!("WhatsApp sidecar connected");
                     } else {
-                        warn!("WhatsApp sidecar started but not yet connected — scan QR");
+                        warn!(
+                            "WhatsApp sidecar started but not yet connected — pairing via Telegram"
+                        );
                     }
                 }
                 Err(e) => {
@@ -350,16 +1009,21 @@ async fn handle_start() -> anyhow::Result<()> {
         let outbound_redactor = wintermute::messaging::outbound_redactor::OutboundRedactor::new(
             config_arc.privacy.private_terms.clone(),
         );
+        let quiet_hours = wintermute::messaging::quiet_hours::QuietHoursPolicy::from_config(
+            &agent_config_arc.messaging,
+        )
+        .context("invalid [messaging] quiet hours in agent.toml")?;
         Some(Arc::new(
             wintermute::messaging::outbound_composer::OutboundComposer::new(
                 Arc::clone(&router_arc),
                 Arc::clone(&daily_budget),
                 outbound_redactor,
-            ),
+            )
+            .with_quiet_hours(quiet_hours),
         ))
     };
 
-    let tool_router = Arc::new(ToolRouter::new(
+    let mut tool_router = ToolRouter::new(
         Arc::clone(&executor),
         redactor,
         Arc::clone(&memory),
@@ -376,10 +1040,37 @@ async fn handle_start() -> anyhow::Result<()> {
         Some(Arc::clone(&daily_budget)),
         whatsapp_client_arc,
         outbound_composer_arc,
-    ));
+    )
+    .with_web_search(
+        wintermute::tools::web_search::WebSearch::from_config(&config_arc.search, &credentials)
+            .context("invalid [search] configuration")?,
+    )
+    .with_owner(config_arc.channels.telegram.allowed_users.first().copied());
+    if let Some(account) =
+        wintermute::tools::email::EmailAccount::from_config(&config_arc.email, &credentials)
+            .context("invalid [email] configuration")?
+    {
+        info!(address = account.address(), "email tool enabled");
+        tool_router = tool_router.with_email(account);
+    }
+    if !config_arc.mcp_servers.is_empty() {
+        let mcp =
+            wintermute::tools::mcp::McpManager::start(&config_arc.mcp_servers, &credentials).await;
+        info!(
+            servers = mcp.server_count(),
+            tools = mcp.tool_count(),
+            "MCP servers started"
+        );
+        tool_router = tool_router.with_mcp(Arc::new(mcp));
+    }
+    let tool_router = Arc::new(tool_router);
 
     // Phase 3: Observer channel + background task
-    let observer_tx = if agent_config_arc.learning.enabled {
+    let observer_queue = agent_config_arc
+        .learning
+        .enabled
+        .then(|| Arc::new(ObserverQueue::default()));
+    let observer_tx = if let Some(queue) = &observer_queue {
         let (tx, rx) = mpsc::channel::<wintermute::observer::ObserverEvent>(64);
         let observer_deps = wintermute::observer::ObserverDeps {
             memory: Arc::clone(&memory),
@@ -388,224 +1079,89 @@ async fn handle_start() -> anyhow::Result<()> {
             redactor: observer_redactor,
             learning_config: agent_config_arc.learning.clone(),
             telegram_tx: telegram_tx.clone(),
+            queue: Arc::clone(queue),
         };
-        tokio::spawn(wintermute::observer::run_observer(observer_deps, rx));
-        info!("observer pipeline spawned");
-        Some(tx)
-    } else {
-        info!("observer disabled via learning.enabled = false");
-        None
-    };
-
-    // Session persistence: create manager and recover crashed sessions.
-    let session_manager = Arc::new(SessionManager::new(memory.pool().clone()));
-    if agent_config_arc.sessions.crash_recovery {
-        if let Err(e) = session_manager.mark_crashed_sessions().await {
-            warn!(error = %e, "failed to mark crashed sessions");
-        }
-        match session_manager.recover_sessions().await {
-            Ok(recovered) if !recovered.is_empty() => {
-                info!(count = recovered.len(), "recovered sessions from crash");
-                // Session re-spawn will come in a later phase; for now just log.
-            }
-            Ok(_) => {}
-            Err(e) => {
-                warn!(error = %e, "failed to recover sessions");
-            }
-        }
-    }
-
-    let session_router = Arc::new(SessionRouter::new(
-        Arc::clone(&router_arc),
-        Arc::clone(&tool_router),
-        Arc::clone(&memory),
-        Arc::clone(&daily_budget),
-        Arc::clone(&approval_manager),
-        policy_context,
-        telegram_tx.clone(),
-        Arc::clone(&config_arc),
-        Arc::clone(&agent_config_arc),
-        observer_tx,
-        paths.clone(),
-        Arc::clone(&session_manager),
-    ));
-
-    // Phase 4: WhatsApp event listener for autonomous inbound message routing.
-    if config_arc.whatsapp.enabled {
-        let wa_base_url = format!(
-            "http://127.0.0.1:{}",
-            wintermute::whatsapp::client::DEFAULT_BRIDGE_PORT
-        );
-        let (wa_event_tx, mut wa_event_rx) =
-            mpsc::channel::<wintermute::whatsapp::events::WhatsAppEvent>(128);
-        wintermute::whatsapp::events::spawn_event_listener(wa_base_url, wa_event_tx);
-
-        let wa_session_router = Arc::clone(&session_router);
-        let wa_memory_pool = memory.pool().clone();
-        let wa_telegram_tx = telegram_tx.clone();
-        let wa_notify_user_id = config_arc
-            .channels
-            .telegram
-            .allowed_users
-            .first()
-            .copied()
-            .unwrap_or(0);
-
-        tokio::spawn(async move {
-            while let Some(event) = wa_event_rx.recv().await {
-                match event {
-                    wintermute::whatsapp::events::WhatsAppEvent::Message {
-                        jid,
-                        text,
-                        from_me,
-                        ..
-                    } => {
-                        // Skip messages sent by the agent itself
-                        if from_me {
-                            continue;
-                        }
-
-                        match wintermute::whatsapp::router::route_incoming(&wa_memory_pool, &jid)
-                            .await
-                        {
-                            Ok(wintermute::whatsapp::router::RouteResult::Routed {
-                                brief_id,
-                                session_id,
-                            }) => {
-                                // Audit-log the inbound message for compliance.
-                                if let Err(e) = wintermute::messaging::audit::log_outbound(
-                                    &wa_memory_pool,
-                                    Some(&brief_id),
-                                    &session_id,
-                                    "whatsapp",
-                                    &jid,
-                                    &text,
-                                    "inbound",
-                                    None,
-                                    false,
-                                )
-                                .await
-                                {
-                                    warn!(error = %e, "failed to audit-log inbound WhatsApp message");
-                                }
-
-                                if let Err(e) = wa_session_router
-                                    .route_inbound(
-                                        brief_id.clone(),
-                                        session_id.clone(),
-                                        jid.clone(),
-                                        text,
-                                    )
-                                    .await
-                                {
-                                    warn!(
-                                        error = %e,
-                                        %brief_id,
-                                        %session_id,
-                                        "failed to route inbound WhatsApp message"
-                                    );
-                                }
-                            }
-                            Ok(wintermute::whatsapp::router::RouteResult::Unhandled {
-                                jid: unhandled_jid,
-                            }) => {
-                                info!(jid = %unhandled_jid, "unhandled WhatsApp message (no active brief)");
-                                // Forward to user via Telegram for awareness
-                                let preview = if text.len() > 200 {
-                                    // Use char-boundary-safe truncation to avoid
-                                    // panicking on multi-byte UTF-8 (e.g. emoji).
-                                    let end = text.floor_char_boundary(200);
-                                    format!("{}...", &text[..end])
-                                } else {
-                                    text
-                                };
-                                let notify = wintermute::agent::TelegramOutbound {
-                                    user_id: wa_notify_user_id,
-                                    text: Some(format!(
-                                        "[WhatsApp] Unhandled message from {unhandled_jid}: {preview}"
-                                    )),
-                                    file_path: None,
-                                    approval_keyboard: None,
-                                };
-                                let _ = wa_telegram_tx.send(notify).await;
-                            }
-                            Err(e) => {
-                                warn!(error = %e, %jid, "WhatsApp message routing failed");
-                            }
-                        }
-                    }
-                    wintermute::whatsapp::events::WhatsAppEvent::Connected => {
-                        info!("WhatsApp connected");
-                    }
-                    wintermute::whatsapp::events::WhatsAppEvent::Disconnected { reason } => {
-                        warn!(reason = ?reason, "WhatsApp disconnected");
-                    }
-                }
-            }
-        });
-        info!("WhatsApp event listener spawned");
-    }
-
-    // Phase 3: Heartbeat background task with graceful shutdown via Ctrl+C.
-    let (shutdown_tx, shutdown_rx) = watch::channel(false);
-    tokio::spawn(async move {
-        if tokio::signal::ctrl_c().await.is_ok() {
-            info!("ctrl-c received, signalling heartbeat shutdown");
-            let _ = shutdown_tx.send(true);
-        }
-    });
-    if agent_config_arc.heartbeat.enabled {
-        let notify_user_id = match config_arc.channels.telegram.allowed_users.first() {
-            Some(&id) => id,
-            None => {
-                warn!("no allowed_users configured; heartbeat notifications disabled");
-                0
-            }
-        };
-        let heartbeat_deps = wintermute::heartbeat::HeartbeatDeps {
-            config: Arc::clone(&config_arc),
-            agent_config: Arc::clone(&agent_config_arc),
-            memory: Arc::clone(&memory),
-            executor: Arc::clone(&executor),
-            tool_router: Arc::clone(&tool_router),
-            router: Arc::clone(&router_arc),
-            daily_budget: Arc::clone(&daily_budget),
-            telegram_tx: telegram_tx.clone(),
-            notify_user_id,
-            paths: paths.clone(),
-            session_router: Arc::clone(&session_router),
-            browser_mode,
-        };
-        tokio::spawn(wintermute::heartbeat::run_heartbeat(
-            heartbeat_deps,
-            Instant::now(),
-            shutdown_rx,
-        ));
-        info!("heartbeat spawned");
+        tokio::spawn(wintermute::observer::run_observer(observer_deps, rx));
+        info!("observer pipeline spawned");
+        Some(tx)
     } else {
-        info!("heartbeat disabled via heartbeat.enabled = false");
+        info!("observer disabled via learning.enabled = false");
+        None
+    };
+
+    // Session persistence: create manager and recover crashed sessions.
+    let session_manager = Arc::new(SessionManager::new(memory.pool().clone()));
+    if agent_config_arc.sessions.crash_recovery {
+        if let Err(e) = session_manager.mark_crashed_sessions().await {
+            warn!(error = %e, "failed to mark crashed sessions");
+        }
+        match session_manager.recover_sessions().await {
+            Ok(recovered) if !recovered.is_empty() => {
+                info!(count = recovered.len(), "recovered sessions from crash");
+                // Session re-spawn will come in a later phase; for now just log.
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(error = %e, "failed to recover sessions");
+            }
+        }
     }
 
-    info!(
-        default_model = %config_arc.models.default,
-        "starting telegram bot"
-    );
+    let session_router = Arc::new(SessionRouter::new(
+        Arc::clone(&router_arc),
+        Arc::clone(&tool_router),
+        Arc::clone(&memory),
+        Arc::clone(&daily_budget),
+        Arc::clone(&approval_manager),
+        policy_context,
+        telegram_tx.clone(),
+        Arc::clone(&config_arc),
+        Arc::clone(&agent_config_arc),
+        observer_tx.clone(),
+        paths.clone(),
+        Arc::clone(&session_manager),
+    ));
 
-    telegram::run_telegram(
-        &telegram_token,
-        config_arc,
-        session_router,
-        approval_manager,
-        telegram_rx,
+    Ok(AgentStack {
+        config: config_arc,
+        agent_config: agent_config_arc,
+        credentials,
         all_secrets,
         executor,
         memory,
+        router: router_arc,
+        daily_budget,
+        approval_manager,
         registry,
-        paths,
-    )
-    .await?;
+        tool_router,
+        session_router,
+        observer_tx,
+        observer_queue,
+        browser_mode,
+        telegram_tx,
+        telegram_rx,
+    })
+}
 
-    Ok(())
+/// Carry today's usage and yesterday's carry-over across a restart, read
+/// from the usage ledger in the budget's timezone.
+async fn restore_daily_budget(daily_budget: &DailyBudget, pool: &sqlx::SqlitePool) {
+    let timezone = daily_budget.timezone();
+    let today = timezone.date(Utc::now());
+    let today_start = timezone.day_start(today);
+    match wintermute::memory::usage::tokens_since(pool, today_start).await {
+        Ok(used) => daily_budget.record(used),
+        Err(e) => warn!(error = %e, "failed to read today's usage from the ledger"),
+    }
+    let Some(yesterday) = today.pred_opt() else {
+        return;
+    };
+    let since = timezone.day_start(yesterday);
+    match wintermute::memory::usage::usage_between(pool, since, today_start).await {
+        Ok(totals) if totals.calls > 0 => daily_budget.restore_carry_over(totals.total_tokens()),
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "failed to read yesterday's usage from the ledger"),
+    }
 }
 
 async fn handle_status() -> anyhow::Result<()> {
@@ -647,6 +1203,79 @@ async fn handle_status() -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn handle_doctor() -> anyhow::Result<()> {
+    use std::io::{IsTerminal, Write};
+    use wintermute::doctor::{self, Check, Report};
+
+    let paths = runtime_paths()?;
+    let mut report = Report::new();
+
+    let config = match load_default_config() {
+        Ok(config) => {
+            report.push(Check::pass("config.toml", "loaded and validated"));
+            Some(config)
+        }
+        Err(e) => {
+            report.push(Check::fail("config.toml", format!("{e:#}")));
+            None
+        }
+    };
+    report.push(match load_default_agent_config() {
+        Ok(_) => Check::pass("agent.toml", "loaded"),
+        Err(e) => Check::fail("agent.toml", format!("{e:#}")),
+    });
+    let credentials = match load_default_credentials() {
+        Ok(credentials) => {
+            report.push(Check::pass(".env", "loaded with private permissions"));
+            credentials
+        }
+        Err(e) => {
+            report.push(Check::fail(".env", format!("{e:#}")));
+            Credentials::default()
+        }
+    };
+
+    if let Some(ref config) = config {
+        // Never refresh here: doctor must not rewrite .env.
+        let anthropic_auth = resolve_anthropic_auth(&credentials);
+        report.extend(doctor::check_anthropic_auth(anthropic_auth.as_ref()));
+        match ModelRouter::from_config_with_auth(&config.models, &credentials, anthropic_auth) {
+            Ok(router) => report.extend(doctor::check_providers(&config.models, &router).await),
+            Err(e) => report.push(Check::fail("model router", format!("{e:#}"))),
+        }
+    }
+
+    report.push(doctor::check_docker().await);
+    report.extend(doctor::check_memory_db(&paths.memory_db, ALL_MIGRATIONS).await);
+
+    if let Some(ref config) = config {
+        let token_key = &config.channels.telegram.bot_token_env;
+        match credentials.get(token_key) {
+            Some(token) if !token.trim().is_empty() => {
+                report.push(doctor::check_telegram(token).await);
+            }
+            _ => report.push(Check::fail(
+                "telegram token",
+                format!("{token_key} is not set in .env"),
+            )),
+        }
+    }
+
+    // Provider and Telegram errors can echo request details; never print secrets.
+    let redactor = Redactor::new(credentials.known_secrets());
+    let rendered = redactor.redact(&report.render(std::io::stdout().is_terminal()));
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "{rendered}").context("failed to write doctor report")?;
+
+    if report.has_failures() {
+        anyhow::bail!(
+            "{} doctor check(s) failed",
+            report.count(doctor::CheckStatus::Fail)
+        );
+    }
+    Ok(())
+}
+
 async fn handle_reset() -> anyhow::Result<()> {
     let paths = runtime_paths()?;
     let config = load_default_config()
@@ -660,7 +1289,9 @@ async fn handle_reset() -> anyhow::Result<()> {
     }
 
     let credentials = credentials_or_default();
-    let redactor = Redactor::new(credentials.known_secrets());
+    let redactor = Redactor::new(credentials.known_secrets())
+        .with_patterns(&config.privacy.redact_patterns)
+        .context("invalid [privacy] redact_patterns")?;
     let executor = DockerExecutor::new(&config, &paths, redactor).await?;
     executor.reset_container(&config).await?;
 
@@ -668,6 +1299,91 @@ async fn handle_reset() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn handle_logs(
+    since: Option<String>,
+    level: Option<String>,
+    session: Option<String>,
+    grep: Option<String>,
+    json: bool,
+) -> anyhow::Result<()> {
+    use std::io::Write;
+    use wintermute::log_query::{self, LogFilter, Severity};
+
+    let paths = runtime_paths()?;
+    let filter = LogFilter {
+        since: since
+            .map(|s| log_query::parse_since(&s, Utc::now()))
+            .transpose()?,
+        min_level: level
+            .map(|l| Severity::parse(&l).ok_or_else(|| anyhow::anyhow!("unknown log level '{l}'")))
+            .transpose()?,
+        session,
+        grep: grep
+            .map(|g| regex::Regex::new(&g).with_context(|| format!("invalid --grep '{g}'")))
+            .transpose()?,
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    let matched = log_query::query(&paths.data_dir.join("logs"), &filter, |record| {
+        // A closed pipe (e.g. `| head`) just stops the output.
+        let _ = if json {
+            writeln!(stdout, "{}", record.raw)
+        } else {
+            writeln!(stdout, "{}", record.pretty())
+        };
+    })?;
+    if matched == 0 {
+        info!("no matching log entries");
+    }
+    Ok(())
+}
+
+async fn handle_service(action: ServiceAction) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    // Wintermute never spawns host processes: it writes or removes the
+    // service file and prints the launchctl/systemctl step to run.
+    let manager = service::resolve_manager()?;
+    let mut stdout = std::io::stdout().lock();
+    match action {
+        ServiceAction::Install => {
+            let service_paths = service::ServicePaths::for_current_exe()?;
+            let path = service::write_service_file(manager, Service::Agent, &service_paths)?;
+            info!(
+                path = %path.display(),
+                binary = %service_paths.binary.display(),
+                "wintermute service file written"
+            );
+            writeln!(
+                stdout,
+                "Start it with: {}",
+                service::enable_command(manager, Service::Agent, &path)
+            )?;
+        }
+        ServiceAction::Uninstall => {
+            if service::remove_service_file(manager, Service::Agent)? {
+                writeln!(
+                    stdout,
+                    "Service file removed. Stop the running service with: {}",
+                    service::stop_command(manager, Service::Agent)
+                )?;
+            } else {
+                writeln!(stdout, "The wintermute service is not installed.")?;
+            }
+        }
+        ServiceAction::Status => {
+            let path = service::service_file(manager, Service::Agent)?;
+            info!(
+                manager = ?manager,
+                path = %path.display(),
+                installed = path.exists(),
+                "wintermute service status (`flatline service status` also shows whether it runs)"
+            );
+        }
+    }
+    Ok(())
+}
+
 async fn handle_backup(request: Option<BackupRequest>) -> anyhow::Result<()> {
     let paths = runtime_paths()?;
     ensure_runtime_layout(&paths)?;
@@ -942,6 +1658,270 @@ async fn apply_bootstrap_migration(paths: &RuntimePaths) -> anyhow::Result<()> {
             .context("failed to persist briefs migration marker")?;
     }
 
+    // Apply feedback migration (005) if not yet applied.
+    let applied_005: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(FEEDBACK_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check feedback migration")?;
+
+    if applied_005.is_none() {
+        let feedback_script = include_str!("../migrations/005_feedback.sql");
+        sqlx::raw_sql(feedback_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply feedback migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(FEEDBACK_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist feedback migration marker")?;
+    }
+
+    // Apply contacts migration (006) if not yet applied.
+    let applied_006: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(CONTACTS_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check contacts migration")?;
+
+    if applied_006.is_none() {
+        let contacts_script = include_str!("../migrations/006_contacts.sql");
+        sqlx::raw_sql(contacts_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply contacts migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(CONTACTS_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist contacts migration marker")?;
+    }
+
+    // Apply outbound queue migration (007) if not yet applied.
+    let applied_007: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(OUTBOUND_QUEUE_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check outbound queue migration")?;
+
+    if applied_007.is_none() {
+        let queue_script = include_str!("../migrations/007_outbound_queue.sql");
+        sqlx::raw_sql(queue_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply outbound queue migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(OUTBOUND_QUEUE_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist outbound queue migration marker")?;
+    }
+
+    // Apply message templates migration (008) if not yet applied.
+    let applied_008: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(TEMPLATES_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check templates migration")?;
+
+    if applied_008.is_none() {
+        let templates_script = include_str!("../migrations/008_templates.sql");
+        sqlx::raw_sql(templates_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply templates migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(TEMPLATES_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist templates migration marker")?;
+    }
+
+    // Apply delivery state migration (009) if not yet applied.
+    let applied_009: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(DELIVERY_STATE_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check delivery state migration")?;
+
+    if applied_009.is_none() {
+        let delivery_script = include_str!("../migrations/009_delivery_state.sql");
+        sqlx::raw_sql(delivery_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply delivery state migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(DELIVERY_STATE_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist delivery state migration marker")?;
+    }
+
+    // Apply feeds migration (010) if not yet applied.
+    let applied_010: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(FEEDS_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check feeds migration")?;
+
+    if applied_010.is_none() {
+        let feeds_script = include_str!("../migrations/010_feeds.sql");
+        sqlx::raw_sql(feeds_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply feeds migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(FEEDS_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist feeds migration marker")?;
+    }
+
+    // Apply soul versions migration (011) if not yet applied.
+    let applied_011: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(SOUL_VERSIONS_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check soul versions migration")?;
+
+    if applied_011.is_none() {
+        let soul_script = include_str!("../migrations/011_soul_versions.sql");
+        sqlx::raw_sql(soul_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply soul versions migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(SOUL_VERSIONS_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist soul versions migration marker")?;
+    }
+
+    // Apply telegram outbox migration (012) if not yet applied.
+    let applied_012: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(TELEGRAM_OUTBOX_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check telegram outbox migration")?;
+
+    if applied_012.is_none() {
+        let outbox_script = include_str!("../migrations/012_telegram_outbox.sql");
+        sqlx::raw_sql(outbox_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply telegram outbox migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(TELEGRAM_OUTBOX_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist telegram outbox migration marker")?;
+    }
+
+    // Apply telegram updates migration (013) if not yet applied.
+    let applied_013: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(TELEGRAM_UPDATES_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check telegram updates migration")?;
+
+    if applied_013.is_none() {
+        let updates_script = include_str!("../migrations/013_telegram_updates.sql");
+        sqlx::raw_sql(updates_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply telegram updates migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(TELEGRAM_UPDATES_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist telegram updates migration marker")?;
+    }
+
+    // Apply memory audit migration (014) if not yet applied.
+    let applied_014: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(MEMORY_AUDIT_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check memory audit migration")?;
+
+    if applied_014.is_none() {
+        let audit_script = include_str!("../migrations/014_memory_audit.sql");
+        sqlx::raw_sql(audit_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply memory audit migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(MEMORY_AUDIT_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist memory audit migration marker")?;
+    }
+
+    // Apply tool network grants migration (015) if not yet applied.
+    let applied_015: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(TOOL_GRANTS_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check tool grants migration")?;
+
+    if applied_015.is_none() {
+        let grants_script = include_str!("../migrations/015_tool_network_grants.sql");
+        sqlx::raw_sql(grants_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply tool grants migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(TOOL_GRANTS_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist tool grants migration marker")?;
+    }
+
+    // Apply usage ledger migration (016) if not yet applied.
+    let applied_016: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM migrations WHERE name = ?1")
+            .bind(USAGE_LEDGER_MIGRATION)
+            .fetch_optional(&mut connection)
+            .await
+            .context("failed to check usage ledger migration")?;
+
+    if applied_016.is_none() {
+        let ledger_script = include_str!("../migrations/016_usage_ledger.sql");
+        sqlx::raw_sql(ledger_script)
+            .execute(&mut connection)
+            .await
+            .context("failed to apply usage ledger migration")?;
+
+        sqlx::query("INSERT OR IGNORE INTO migrations(name) VALUES (?1)")
+            .bind(USAGE_LEDGER_MIGRATION)
+            .execute(&mut connection)
+            .await
+            .context("failed to persist usage ledger migration marker")?;
+    }
+
     Ok(())
 }
 
@@ -1021,6 +2001,16 @@ auto_promote_threshold = 3
 name = "daily_backup"
 cron = "0 0 3 * * *"
 builtin = "backup"
+
+[[scheduled_tasks]]
+name = "weekly_memory_review"
+cron = "0 0 10 * * 0"
+builtin = "memory_review"
+
+[[scheduled_tasks]]
+name = "daily_feed_digest"
+cron = "0 0 8 * * *"
+builtin = "feed_digest"
 "#
 }
 
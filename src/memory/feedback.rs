@@ -0,0 +1,182 @@
+//! User feedback on assistant turns, linked to the memories and tools used.
+//!
+//! Each completed agent turn is recorded in `turn_feedback` with the ids of
+//! the memories injected into its context and the tools it executed. A 👍/👎
+//! rating later attaches to that row, and the observer pipeline folds rated
+//! rows into per-memory confidence (see [`crate::observer::feedback`]).
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use super::MemoryError;
+
+/// Confidence assumed for memories that have never been scored.
+pub const DEFAULT_CONFIDENCE: f64 = 0.7;
+
+/// Confidence gained per positive rating.
+pub const POSITIVE_STEP: f64 = 0.05;
+
+/// Confidence lost per negative rating.
+pub const NEGATIVE_STEP: f64 = 0.15;
+
+/// Negative ratings required before a memory can be archived.
+pub const ARCHIVE_NEGATIVE_COUNT: u32 = 3;
+
+/// Confidence below which a repeatedly down-voted memory is archived.
+pub const ARCHIVE_CONFIDENCE: f64 = 0.3;
+
+/// A thumbs-up or thumbs-down rating on an assistant turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedbackRating {
+    /// The answer was helpful.
+    Up,
+    /// The answer was wrong or unhelpful.
+    Down,
+}
+
+impl FeedbackRating {
+    /// Returns the integer stored in SQLite (`1` or `-1`).
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            Self::Up => 1,
+            Self::Down => -1,
+        }
+    }
+
+    /// Convert the stored integer back into a rating.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::InvalidEnum`] for values other than `1` / `-1`.
+    pub fn from_i64(value: i64) -> Result<Self, MemoryError> {
+        match value {
+            1 => Ok(Self::Up),
+            -1 => Ok(Self::Down),
+            other => Err(MemoryError::InvalidEnum {
+                field: "rating",
+                value: other.to_string(),
+            }),
+        }
+    }
+
+    /// Parse a user-supplied rating (`up`, `down`, `+`, `-`, `good`, `bad`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "up" | "+" | "+1" | "good" | "yes" | "\u{1F44D}" => Some(Self::Up),
+            "down" | "-" | "-1" | "bad" | "no" | "\u{1F44E}" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    /// Signed confidence adjustment applied to each linked memory.
+    pub fn confidence_delta(&self) -> f64 {
+        match self {
+            Self::Up => POSITIVE_STEP,
+            Self::Down => -NEGATIVE_STEP,
+        }
+    }
+}
+
+/// A completed agent turn and what it drew on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnRecord {
+    /// Unique turn identifier (used in feedback callback data).
+    pub turn_id: String,
+    /// Session the turn belongs to.
+    pub session_id: String,
+    /// Ids of memories injected into the turn's context.
+    pub memory_ids: Vec<i64>,
+    /// Names of tools executed during the turn.
+    pub tools_used: Vec<String>,
+}
+
+/// A rated turn awaiting processing by the observer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnFeedback {
+    /// The turn that was rated.
+    pub turn: TurnRecord,
+    /// The user's rating.
+    pub rating: FeedbackRating,
+    /// Optional free-text comment.
+    pub comment: Option<String>,
+}
+
+/// Row type for `turn_feedback` reads.
+type FeedbackRow = (String, String, String, String, i64, Option<String>);
+
+/// Load rated feedback that the observer has not yet applied.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::Database`] on SQLite failure.
+pub async fn pending_feedback(
+    db: &SqlitePool,
+    limit: usize,
+) -> Result<Vec<TurnFeedback>, MemoryError> {
+    let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows: Vec<FeedbackRow> = sqlx::query_as(
+        "SELECT turn_id, session_id, memory_ids, tools_used, rating, comment \
+         FROM turn_feedback \
+         WHERE rating IS NOT NULL AND processed = FALSE \
+         ORDER BY rated_at ASC LIMIT ?1",
+    )
+    .bind(limit_i64)
+    .fetch_all(db)
+    .await?;
+
+    rows.into_iter()
+        .map(
+            |(turn_id, session_id, memory_ids, tools_used, rating, comment)| {
+                Ok(TurnFeedback {
+                    turn: TurnRecord {
+                        turn_id,
+                        session_id,
+                        memory_ids: serde_json::from_str(&memory_ids).unwrap_or_default(),
+                        tools_used: serde_json::from_str(&tools_used).unwrap_or_default(),
+                    },
+                    rating: FeedbackRating::from_i64(rating)?,
+                    comment,
+                })
+            },
+        )
+        .collect()
+}
+
+/// Return the id of the most recent recorded turn in a session.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::Database`] on SQLite failure.
+pub async fn latest_turn_id(
+    db: &SqlitePool,
+    session_id: &str,
+) -> Result<Option<String>, MemoryError> {
+    let row: Option<(String,)> = sqlx::query_as(
+        "SELECT turn_id FROM turn_feedback WHERE session_id = ?1 \
+         ORDER BY created_at DESC, rowid DESC LIMIT 1",
+    )
+    .bind(session_id)
+    .fetch_optional(db)
+    .await?;
+    Ok(row.map(|(id,)| id))
+}
+
+/// Check whether a turn has been recorded in the given session.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::Database`] on SQLite failure.
+pub async fn turn_in_session(
+    db: &SqlitePool,
+    session_id: &str,
+    turn_id: &str,
+) -> Result<bool, MemoryError> {
+    let row: Option<(i64,)> =
+        sqlx::query_as("SELECT 1 FROM turn_feedback WHERE turn_id = ?1 AND session_id = ?2")
+            .bind(turn_id)
+            .bind(session_id)
+            .fetch_optional(db)
+            .await?;
+    Ok(row.is_some())
+}
@@ -9,7 +9,10 @@
 //! configured, search falls back to FTS5 only.
 
 pub mod embedder;
+pub mod feedback;
 pub mod search;
+pub mod transcript;
+pub mod usage;
 pub mod writer;
 
 use std::sync::Arc;
@@ -20,6 +23,7 @@ use tokio::sync::mpsc;
 use tracing::{info, warn};
 
 use self::embedder::Embedder;
+use self::feedback::{FeedbackRating, TurnFeedback, TurnRecord};
 use self::writer::WriteOp;
 
 // ---------------------------------------------------------------------------
@@ -235,6 +239,10 @@ pub enum MemoryError {
     #[error("memory writer channel closed")]
     WriterClosed,
 
+    /// The writer actor dropped a write that was awaiting its result.
+    #[error("memory write failed")]
+    WriteFailed,
+
     /// Embedding generation failed.
     #[error("embedding error: {0}")]
     Embedding(String),
@@ -340,6 +348,55 @@ impl MemoryEngine {
             });
         }
 
+        self.attach_embedding(&mut memory).await;
+        self.writer_tx
+            .send(WriteOp::SaveMemory(memory))
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
+    /// Save a memory and wait for the writer to return its row id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::ContentTooLarge`] if the content exceeds the
+    /// size limit, [`MemoryError::WriterClosed`] if the writer actor has
+    /// stopped, or [`MemoryError::WriteFailed`] if the insert failed.
+    pub async fn save_memory_returning_id(&self, mut memory: Memory) -> Result<i64, MemoryError> {
+        if memory.content.len() > MAX_CONTENT_SIZE {
+            return Err(MemoryError::ContentTooLarge {
+                size: memory.content.len(),
+                max: MAX_CONTENT_SIZE,
+            });
+        }
+
+        self.attach_embedding(&mut memory).await;
+        self.write_and_wait(|reply| WriteOp::SaveMemoryWithId { memory, reply })
+            .await
+    }
+
+    /// Send a write that answers with a value and wait for it.
+    ///
+    /// `op` builds the operation around the reply channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped,
+    /// or [`MemoryError::WriteFailed`] if the write failed.
+    pub(crate) async fn write_and_wait(
+        &self,
+        op: impl FnOnce(Option<tokio::sync::oneshot::Sender<i64>>) -> WriteOp,
+    ) -> Result<i64, MemoryError> {
+        let (reply, value) = tokio::sync::oneshot::channel();
+        self.writer_tx
+            .send(op(Some(reply)))
+            .await
+            .map_err(|_| MemoryError::WriterClosed)?;
+        value.await.map_err(|_| MemoryError::WriteFailed)
+    }
+
+    /// Compute the embedding, if an embedder is configured, and note it in metadata.
+    async fn attach_embedding(&self, memory: &mut Memory) {
         // Generate embedding if configured and not already present.
         if let Some(ref emb) = self.embedder {
             match emb.embed(&memory.content).await {
@@ -362,10 +419,6 @@ impl MemoryEngine {
                 }
             }
         }
-        self.writer_tx
-            .send(WriteOp::SaveMemory(memory))
-            .await
-            .map_err(|_| MemoryError::WriterClosed)
     }
 
     /// Persist a conversation log entry.
@@ -386,6 +439,57 @@ impl MemoryEngine {
             .map_err(|_| MemoryError::WriterClosed)
     }
 
+    /// Store a fact the user stated explicitly (`/remember`).
+    ///
+    /// The fact is saved active with source [`MemorySource::User`] and an
+    /// audit row naming `actor`, in one write.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::ContentTooLarge`] for oversized content or
+    /// [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn remember(&self, content: &str, actor: &str) -> Result<(), MemoryError> {
+        if content.len() > MAX_CONTENT_SIZE {
+            return Err(MemoryError::ContentTooLarge {
+                size: content.len(),
+                max: MAX_CONTENT_SIZE,
+            });
+        }
+        let mut memory = Memory {
+            id: None,
+            kind: MemoryKind::Fact,
+            content: content.to_owned(),
+            metadata: None,
+            status: MemoryStatus::Active,
+            source: MemorySource::User,
+            created_at: None,
+            updated_at: None,
+        };
+        self.attach_embedding(&mut memory).await;
+        self.writer_tx
+            .send(WriteOp::Remember {
+                memory,
+                actor: actor.to_owned(),
+            })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
+    /// Delete a memory at the user's request (`/forget`), with an audit row.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn forget(&self, id: i64, actor: &str) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::Forget {
+                id,
+                actor: actor.to_owned(),
+            })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
     /// Update the status of an existing memory entry.
     ///
     /// # Errors
@@ -402,6 +506,24 @@ impl MemoryEngine {
             .map_err(|_| MemoryError::WriterClosed)
     }
 
+    /// Merge a JSON object into a memory's metadata.
+    ///
+    /// Keys in `patch` overwrite existing keys; a `null` value removes a key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn merge_metadata(
+        &self,
+        id: i64,
+        patch: serde_json::Value,
+    ) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::MergeMetadata { id, patch })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
     /// Search memories filtered by status, ordered by most recently updated.
     ///
     /// Returns up to `limit` memories with the given status.
@@ -413,6 +535,11 @@ impl MemoryEngine {
         search::search_by_status(&self.db, status.as_str(), limit).await
     }
 
+    /// Fetch a memory by its row id.
+    pub async fn get_memory(&self, id: i64) -> Result<Option<Memory>, MemoryError> {
+        search::memory_by_id(&self.db, id).await
+    }
+
     /// Delete a memory by its row id.
     ///
     /// The deletion is sent to the single-writer actor for serialized execution.
@@ -473,6 +600,50 @@ impl MemoryEngine {
             .map_err(|_| MemoryError::WriterClosed)
     }
 
+    /// Record that the user approved network access for a dynamic tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn grant_tool_network(
+        &self,
+        tool: &str,
+        domains: &[String],
+        approved_by: TrustSource,
+    ) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::GrantToolNetwork {
+                tool: tool.to_owned(),
+                domains: domains.to_vec(),
+                approved_by,
+            })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
+    /// Append a provider call to the usage ledger.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn record_usage(&self, entry: usage::UsageEntry) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::RecordUsage(entry))
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
+    /// Domains a dynamic tool has been granted network access to, sorted.
+    pub async fn tool_network_grants(&self, tool: &str) -> Result<Vec<String>, MemoryError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT domain FROM tool_network_grants WHERE tool_name = ?1 ORDER BY domain",
+        )
+        .bind(tool)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(rows.into_iter().map(|(domain,)| domain).collect())
+    }
+
     /// Returns `true` if an embedding model is configured for vector search.
     pub fn has_embedder(&self) -> bool {
         self.embedder.is_some()
@@ -517,6 +688,75 @@ impl MemoryEngine {
             .collect())
     }
 
+    /// Record a completed agent turn so the user can rate it later.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn record_turn(&self, turn: TurnRecord) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::RecordTurn(turn))
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
+    /// Rate a turn recorded in `session_id`.
+    ///
+    /// Returns `false` when the turn is unknown or belongs to another
+    /// session. Ratings on turns the observer has already processed are
+    /// ignored by the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::Database`] if the lookup fails, or
+    /// [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn rate_turn(
+        &self,
+        session_id: &str,
+        turn_id: &str,
+        rating: FeedbackRating,
+        comment: Option<String>,
+    ) -> Result<bool, MemoryError> {
+        if !feedback::turn_in_session(&self.db, session_id, turn_id).await? {
+            return Ok(false);
+        }
+        self.writer_tx
+            .send(WriteOp::RateTurn {
+                turn_id: turn_id.to_owned(),
+                rating,
+                comment,
+            })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)?;
+        Ok(true)
+    }
+
+    /// Id of the most recent recorded turn in a session, if any.
+    pub async fn latest_turn_id(&self, session_id: &str) -> Result<Option<String>, MemoryError> {
+        feedback::latest_turn_id(&self.db, session_id).await
+    }
+
+    /// Rated turns not yet applied to memory confidence.
+    pub async fn pending_feedback(&self, limit: usize) -> Result<Vec<TurnFeedback>, MemoryError> {
+        feedback::pending_feedback(&self.db, limit).await
+    }
+
+    /// Apply a rated turn to its linked memories and mark it processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::WriterClosed`] if the writer actor has stopped.
+    pub async fn apply_feedback(&self, feedback: &TurnFeedback) -> Result<(), MemoryError> {
+        self.writer_tx
+            .send(WriteOp::ApplyFeedback {
+                turn_id: feedback.turn.turn_id.clone(),
+                memory_ids: feedback.turn.memory_ids.clone(),
+                rating: feedback.rating,
+            })
+            .await
+            .map_err(|_| MemoryError::WriterClosed)
+    }
+
     /// Returns a reference to the underlying SQLite pool (for migrations, etc.).
     pub fn pool(&self) -> &SqlitePool {
         &self.db
@@ -170,6 +170,20 @@ pub async fn search_by_status(
     rows.into_iter().map(row_to_memory).collect()
 }
 
+/// Fetch a single memory by row id.
+pub async fn memory_by_id(db: &SqlitePool, id: i64) -> Result<Option<Memory>, MemoryError> {
+    let row: Option<MemoryRow> = sqlx::query_as(
+        "SELECT id, kind, content, metadata, status, source, created_at, updated_at \
+         FROM memories \
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await?;
+
+    row.map(row_to_memory).transpose()
+}
+
 /// Return the most recently updated active memories.
 ///
 /// Convenience wrapper around [`search_by_status`] used as the fallback
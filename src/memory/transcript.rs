@@ -0,0 +1,326 @@
+//! Conversation transcript export.
+//!
+//! Reads the `conversations` log from `memory.db` and renders it as Markdown
+//! or JSON for `/export`. Every message body goes through the [`Redactor`]
+//! before it is written, so exported files never contain known secrets.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use super::MemoryError;
+use crate::executor::redactor::Redactor;
+
+/// Maximum number of conversation rows included in one export.
+pub const MAX_EXPORT_ENTRIES: i64 = 20_000;
+
+/// Output format of an exported transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Human-readable Markdown.
+    Markdown,
+    /// Machine-readable JSON.
+    Json,
+}
+
+impl ExportFormat {
+    /// Parse a user-supplied format name (`md`, `markdown`, `json`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// File extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Which sessions an export covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportScope {
+    /// A single session.
+    Session(String),
+    /// Every session in the log.
+    All,
+}
+
+impl ExportScope {
+    /// Short label used in file names and headers.
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Session(id) => id,
+            Self::All => "all",
+        }
+    }
+}
+
+/// One row of an exported transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TranscriptEntry {
+    /// Conversation row id.
+    pub id: i64,
+    /// Session the message belongs to.
+    pub session_id: String,
+    /// Message role (`user`, `assistant`, `tool_call`, `tool_result`).
+    pub role: String,
+    /// Message body (redacted once rendered).
+    pub content: String,
+    /// Tokens consumed by this message, if recorded.
+    pub tokens_used: Option<i32>,
+    /// SQLite timestamp (`YYYY-MM-DD HH:MM:SS`, UTC).
+    pub created_at: String,
+}
+
+/// Load conversation rows for the given scope, oldest first.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::Database`] if the query fails.
+pub async fn load_transcript(
+    db: &SqlitePool,
+    scope: &ExportScope,
+) -> Result<Vec<TranscriptEntry>, MemoryError> {
+    type Row = (i64, String, String, String, Option<i32>, String);
+    let rows: Vec<Row> = match scope {
+        ExportScope::Session(session_id) => {
+            sqlx::query_as(
+                "SELECT id, session_id, role, content, tokens_used, created_at \
+                 FROM conversations WHERE session_id = ?1 ORDER BY id ASC LIMIT ?2",
+            )
+            .bind(session_id)
+            .bind(MAX_EXPORT_ENTRIES)
+            .fetch_all(db)
+            .await?
+        }
+        ExportScope::All => {
+            sqlx::query_as(
+                "SELECT id, session_id, role, content, tokens_used, created_at \
+                 FROM conversations ORDER BY session_id ASC, id ASC LIMIT ?1",
+            )
+            .bind(MAX_EXPORT_ENTRIES)
+            .fetch_all(db)
+            .await?
+        }
+    };
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(id, session_id, role, content, tokens_used, created_at)| TranscriptEntry {
+                id,
+                session_id,
+                role,
+                content,
+                tokens_used,
+                created_at,
+            },
+        )
+        .collect())
+}
+
+/// One tool call from the conversation log, paired with its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolInvocation {
+    /// Tool name.
+    pub name: String,
+    /// Wall-clock execution time, if the tool actually ran.
+    pub duration_ms: Option<u64>,
+    /// Whether the call succeeded; `None` for rows logged before this was recorded.
+    pub success: Option<bool>,
+    /// Tool output, empty if no result row was logged.
+    pub output: String,
+    /// SQLite timestamp of the call (`YYYY-MM-DD HH:MM:SS`, UTC).
+    pub created_at: String,
+}
+
+/// Load the most recent tool invocations of a session, oldest first.
+///
+/// Pairs each `tool_call` row with the `tool_result` row logged right after it.
+///
+/// # Errors
+///
+/// Returns [`MemoryError::Database`] if the query fails.
+pub async fn recent_tool_invocations(
+    db: &SqlitePool,
+    session_id: &str,
+    limit: usize,
+) -> Result<Vec<ToolInvocation>, MemoryError> {
+    // Each invocation is a call row followed by its result row.
+    let row_limit = i64::try_from(limit.saturating_mul(2)).unwrap_or(i64::MAX);
+    let mut rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT role, content, created_at FROM conversations \
+         WHERE session_id = ?1 AND role IN ('tool_call', 'tool_result') \
+         ORDER BY id DESC LIMIT ?2",
+    )
+    .bind(session_id)
+    .bind(row_limit)
+    .fetch_all(db)
+    .await?;
+    rows.reverse();
+
+    let mut invocations: Vec<ToolInvocation> = Vec::new();
+    let mut awaiting_result = false;
+    for (role, content, created_at) in rows {
+        match role.as_str() {
+            "tool_call" => {
+                let parsed: Option<serde_json::Value> = serde_json::from_str(&content).ok();
+                let field = |key: &str| parsed.as_ref().and_then(|v| v.get(key)).cloned();
+                invocations.push(ToolInvocation {
+                    name: field("name")
+                        .and_then(|v| v.as_str().map(str::to_owned))
+                        .unwrap_or_else(|| "unknown".to_owned()),
+                    duration_ms: field("duration_ms").and_then(|v| v.as_u64()),
+                    success: field("is_error").and_then(|v| v.as_bool()).map(|e| !e),
+                    output: String::new(),
+                    created_at,
+                });
+                awaiting_result = true;
+            }
+            _ if awaiting_result => {
+                if let Some(last) = invocations.last_mut() {
+                    last.output = content;
+                }
+                awaiting_result = false;
+            }
+            // A result whose call fell outside the window.
+            _ => {}
+        }
+    }
+    let excess = invocations.len().saturating_sub(limit);
+    invocations.drain(..excess);
+    Ok(invocations)
+}
+
+/// Render entries in the requested format, redacting every message body.
+pub fn render_transcript(
+    entries: &[TranscriptEntry],
+    scope: &ExportScope,
+    format: ExportFormat,
+    redactor: &Redactor,
+) -> String {
+    let redacted: Vec<TranscriptEntry> = entries
+        .iter()
+        .map(|e| TranscriptEntry {
+            content: redactor.redact(&e.content),
+            ..e.clone()
+        })
+        .collect();
+    match format {
+        ExportFormat::Markdown => render_markdown(&redacted, scope),
+        ExportFormat::Json => render_json(&redacted, scope),
+    }
+}
+
+/// Render the transcript, write it under `dir`, and return the file path.
+///
+/// # Errors
+///
+/// Returns an I/O error if the directory or file cannot be written.
+pub fn write_transcript(
+    dir: &Path,
+    entries: &[TranscriptEntry],
+    scope: &ExportScope,
+    format: ExportFormat,
+    redactor: &Redactor,
+) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let label: String = scope
+        .label()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("transcript-{label}-{stamp}.{}", format.extension()));
+    std::fs::write(&path, render_transcript(entries, scope, format, redactor))?;
+    Ok(path)
+}
+
+fn render_json(entries: &[TranscriptEntry], scope: &ExportScope) -> String {
+    let doc = serde_json::json!({
+        "scope": scope.label(),
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "entries": entries,
+    });
+    serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_owned())
+}
+
+fn render_markdown(entries: &[TranscriptEntry], scope: &ExportScope) -> String {
+    let mut out = format!(
+        "# Transcript: {}\n\n_Exported {}_\n",
+        scope.label(),
+        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if entries.is_empty() {
+        out.push_str("\nNo messages recorded.\n");
+        return out;
+    }
+
+    let mut current_session: Option<&str> = None;
+    for entry in entries {
+        if current_session != Some(entry.session_id.as_str()) {
+            out.push_str(&format!("\n## Session `{}`\n", entry.session_id));
+            current_session = Some(entry.session_id.as_str());
+        }
+        let at = &entry.created_at;
+        match entry.role.as_str() {
+            "tool_call" => {
+                let parsed: Option<serde_json::Value> = serde_json::from_str(&entry.content).ok();
+                let name = parsed
+                    .as_ref()
+                    .and_then(|v| v.get("name"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown");
+                let input = parsed
+                    .as_ref()
+                    .and_then(|v| v.get("input"))
+                    .and_then(|v| serde_json::to_string_pretty(v).ok())
+                    .unwrap_or_else(|| entry.content.clone());
+                out.push_str(&format!("\n**Tool call** `{name}` — {at}\n\n"));
+                out.push_str(&fenced(&input, "json"));
+            }
+            "tool_result" => {
+                out.push_str(&format!("\n**Tool result** — {at}\n\n"));
+                out.push_str(&fenced(&entry.content, ""));
+            }
+            role => {
+                let title = match role {
+                    "user" => "User",
+                    "assistant" => "Assistant",
+                    other => other,
+                };
+                out.push_str(&format!("\n**{title}** — {at}\n\n{}\n", entry.content));
+            }
+        }
+    }
+    out
+}
+
+/// Wrap text in a code fence longer than any backtick run inside it.
+fn fenced(text: &str, lang: &str) -> String {
+    let mut longest = 0usize;
+    let mut run = 0usize;
+    for c in text.chars() {
+        if c == '`' {
+            run = run.saturating_add(1);
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let fence = "`".repeat(longest.saturating_add(1).max(3));
+    format!("{fence}{lang}\n{}\n{fence}\n", text.trim_end())
+}
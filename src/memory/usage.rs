@@ -0,0 +1,203 @@
+//! Provider usage ledger.
+//!
+//! Every provider call made through the [`crate::providers::router::ModelRouter`]
+//! is appended to `usage_ledger` (via the write actor) with its role, model,
+//! session, token counts and latency. [`crate::agent::budget::DailyBudget`]
+//! enforces limits in memory; the ledger is the durable record behind
+//! `/budget detail` and Flatline's budget checks.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use super::MemoryError;
+
+/// One provider call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageEntry {
+    /// What the call was for (`agent`, `observer`, ...), if known.
+    pub role: Option<String>,
+    /// Model spec that served the call (`provider/model`).
+    pub model: String,
+    /// Session the call belongs to, if any.
+    pub session_id: Option<String>,
+    /// Prompt tokens.
+    pub input_tokens: u32,
+    /// Generated tokens.
+    pub output_tokens: u32,
+    /// Wall-clock time of the call in milliseconds.
+    pub latency_ms: u64,
+    /// Whether the provider returned a response.
+    pub success: bool,
+}
+
+/// Aggregated usage of one group (or of everything) over a period.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    /// Group key: a model spec or role; empty for overall totals.
+    pub key: String,
+    /// Number of calls.
+    pub calls: u64,
+    /// Calls that failed.
+    pub failures: u64,
+    /// Prompt tokens.
+    pub input_tokens: u64,
+    /// Generated tokens.
+    pub output_tokens: u64,
+    /// Mean latency in milliseconds.
+    pub avg_latency_ms: u64,
+}
+
+impl UsageTotals {
+    /// Prompt plus generated tokens.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+}
+
+/// Column to group aggregates by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGroup {
+    /// By model spec.
+    Model,
+    /// By caller role; calls without one are grouped as `other`.
+    Role,
+}
+
+impl UsageGroup {
+    fn column(self) -> &'static str {
+        match self {
+            Self::Model => "model",
+            Self::Role => "COALESCE(role, 'other')",
+        }
+    }
+}
+
+/// Row shape shared by the aggregate queries.
+type TotalsRow = (String, i64, i64, i64, i64, f64);
+
+fn totals_from_row((key, calls, failures, input, output, latency): TotalsRow) -> UsageTotals {
+    let to_u64 = |v: i64| u64::try_from(v).unwrap_or(0);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let avg_latency_ms = if latency.is_finite() && latency > 0.0 {
+        latency.round() as u64
+    } else {
+        0
+    };
+    UsageTotals {
+        key,
+        calls: to_u64(calls),
+        failures: to_u64(failures),
+        input_tokens: to_u64(input),
+        output_tokens: to_u64(output),
+        avg_latency_ms,
+    }
+}
+
+/// SQLite `datetime('now')` representation of `ts`.
+fn sql_timestamp(ts: DateTime<Utc>) -> String {
+    ts.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Upper bound for open-ended queries, later than any `datetime('now')`.
+const OPEN_END: &str = "9999-12-31 23:59:59";
+
+/// Overall usage since `since`.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn usage_totals(
+    db: &SqlitePool,
+    since: DateTime<Utc>,
+) -> Result<UsageTotals, MemoryError> {
+    totals_in(db, sql_timestamp(since), OPEN_END.to_owned()).await
+}
+
+/// Overall usage in `[since, until)`.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn usage_between(
+    db: &SqlitePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<UsageTotals, MemoryError> {
+    totals_in(db, sql_timestamp(since), sql_timestamp(until)).await
+}
+
+/// Usage since `since` grouped by model or role, most tokens first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn usage_by(
+    db: &SqlitePool,
+    since: DateTime<Utc>,
+    group: UsageGroup,
+) -> Result<Vec<UsageTotals>, MemoryError> {
+    grouped_in(db, sql_timestamp(since), OPEN_END.to_owned(), group).await
+}
+
+/// Usage in `[since, until)` grouped by model or role, most tokens first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn usage_by_between(
+    db: &SqlitePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    group: UsageGroup,
+) -> Result<Vec<UsageTotals>, MemoryError> {
+    grouped_in(db, sql_timestamp(since), sql_timestamp(until), group).await
+}
+
+async fn totals_in(
+    db: &SqlitePool,
+    since: String,
+    until: String,
+) -> Result<UsageTotals, MemoryError> {
+    let row: TotalsRow = sqlx::query_as(
+        "SELECT '', COUNT(*), COALESCE(SUM(1 - success), 0), COALESCE(SUM(input_tokens), 0), \
+         COALESCE(SUM(output_tokens), 0), COALESCE(AVG(latency_ms), 0.0) \
+         FROM usage_ledger WHERE created_at >= ?1 AND created_at < ?2",
+    )
+    .bind(since)
+    .bind(until)
+    .fetch_one(db)
+    .await?;
+    Ok(totals_from_row(row))
+}
+
+async fn grouped_in(
+    db: &SqlitePool,
+    since: String,
+    until: String,
+    group: UsageGroup,
+) -> Result<Vec<UsageTotals>, MemoryError> {
+    let column = group.column();
+    let sql = format!(
+        "SELECT {column} AS key, COUNT(*), SUM(1 - success), SUM(input_tokens), \
+         SUM(output_tokens), AVG(latency_ms) \
+         FROM usage_ledger WHERE created_at >= ?1 AND created_at < ?2 \
+         GROUP BY key ORDER BY SUM(input_tokens) + SUM(output_tokens) DESC, key"
+    );
+    let rows: Vec<TotalsRow> = sqlx::query_as(&sql)
+        .bind(since)
+        .bind(until)
+        .fetch_all(db)
+        .await?;
+    Ok(rows.into_iter().map(totals_from_row).collect())
+}
+
+/// Tokens (prompt plus generated) used since `since`.
+///
+/// # Errors
+///
+/// Returns an error if the query fails, e.g. on a database without the ledger.
+pub async fn tokens_since(db: &SqlitePool, since: DateTime<Utc>) -> Result<u64, MemoryError> {
+    usage_totals(db, since)
+        .await
+        .map(|totals| totals.total_tokens())
+}
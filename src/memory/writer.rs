@@ -5,10 +5,15 @@
 //! while allowing concurrent reads through the connection pool.
 
 use sqlx::SqlitePool;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::{error, trace};
 
+use super::feedback::{
+    FeedbackRating, TurnRecord, ARCHIVE_CONFIDENCE, ARCHIVE_NEGATIVE_COUNT, DEFAULT_CONFIDENCE,
+};
+use super::usage::UsageEntry;
 use super::{ConversationEntry, Memory, MemoryStatus, TrustSource};
+use crate::tools::rss::ParsedEntry;
 
 /// Operations that can be sent to the write actor.
 #[derive(Debug)]
@@ -16,6 +21,15 @@ pub enum WriteOp {
     /// Persist a new memory.
     SaveMemory(Memory),
 
+    /// Persist a new memory and reply with its row id.
+    SaveMemoryWithId {
+        /// Memory to insert.
+        memory: Memory,
+        /// Receives the row id once the insert has committed; taken by the
+        /// actor before the write runs.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
     /// Persist a conversation entry.
     SaveConversation(ConversationEntry),
 
@@ -35,11 +49,257 @@ pub enum WriteOp {
         approved_by: TrustSource,
     },
 
+    /// Allow a dynamic tool to reach the given domains.
+    GrantToolNetwork {
+        /// Dynamic tool name.
+        tool: String,
+        /// Domains from the tool's manifest.
+        domains: Vec<String>,
+        /// Who approved the grant.
+        approved_by: TrustSource,
+    },
+
+    /// Append a provider call to the usage ledger.
+    RecordUsage(UsageEntry),
+
     /// Delete a memory by row id.
     DeleteMemory {
         /// Memory row id to delete.
         id: i64,
     },
+
+    /// Merge a JSON object into a memory's metadata (RFC 7396 merge patch).
+    MergeMetadata {
+        /// Memory row id.
+        id: i64,
+        /// JSON object whose keys overwrite existing metadata keys.
+        patch: serde_json::Value,
+    },
+
+    /// Persist a memory the user asked to keep, with an audit row.
+    Remember {
+        /// The memory to insert.
+        memory: Memory,
+        /// Who asked (for example `user_123`).
+        actor: String,
+    },
+
+    /// Delete a memory the user asked to drop, with an audit row.
+    ///
+    /// A memory that no longer exists is skipped without an audit row.
+    Forget {
+        /// Memory row id.
+        id: i64,
+        /// Who asked (for example `user_123`).
+        actor: String,
+    },
+
+    /// Record a completed agent turn so it can receive feedback.
+    RecordTurn(TurnRecord),
+
+    /// Attach a user rating to a recorded turn (ignored once processed).
+    RateTurn {
+        /// Turn identifier.
+        turn_id: String,
+        /// The user's rating.
+        rating: FeedbackRating,
+        /// Optional free-text comment.
+        comment: Option<String>,
+    },
+
+    /// Fold a turn's rating into the confidence of its linked memories.
+    ///
+    /// Archives active memories whose negative count and confidence cross the
+    /// archive thresholds, then marks the turn processed, in one transaction.
+    ApplyFeedback {
+        /// Turn identifier.
+        turn_id: String,
+        /// Memories linked to the turn.
+        memory_ids: Vec<i64>,
+        /// The user's rating.
+        rating: FeedbackRating,
+    },
+
+    /// Add a feed, or update the title and priority of an existing one.
+    /// Replies with the feed id.
+    SubscribeFeed {
+        /// Feed URL.
+        url: String,
+        /// Feed title, kept from the existing row when `None`.
+        title: Option<String>,
+        /// Delivery priority (`normal` or `high`).
+        priority: String,
+        /// Receives the feed id once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Remove a feed and its stored items. Replies with the number of feeds
+    /// removed.
+    UnsubscribeFeed {
+        /// Feed URL.
+        url: String,
+        /// Receives the removed count once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Record the outcome of a feed poll. Replies once committed.
+    MarkFeedPolled {
+        /// Feed row id.
+        feed_id: i64,
+        /// Title from the fetched feed, if any.
+        title: Option<String>,
+        /// Fetch error, or `None` on success.
+        error: Option<String>,
+        /// Receives `0` once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Store feed items not seen before. Replies with how many were new.
+    RecordFeedEntries {
+        /// Feed row id.
+        feed_id: i64,
+        /// Parsed items.
+        entries: Vec<ParsedEntry>,
+        /// Store new items as already delivered.
+        digested: bool,
+        /// Receives the inserted count once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Mark feed items as delivered. Replies once committed.
+    MarkFeedEntriesDigested {
+        /// Feed entry row ids.
+        ids: Vec<i64>,
+        /// Receives the number of ids once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Record a new soul version. Replies with its id.
+    ///
+    /// With `activate` the new version supersedes the active one; with
+    /// `replace_pending` older pending proposals are rejected first. Runs in
+    /// one transaction.
+    AddSoulVersion {
+        /// Soul text.
+        content: String,
+        /// Make the new version active.
+        activate: bool,
+        /// Reject pending proposals before inserting.
+        replace_pending: bool,
+        /// Receives the version id once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Activate a pending soul version. Replies with `1`, or `0` if the
+    /// version is not pending.
+    ApproveSoulVersion {
+        /// Soul version id.
+        id: i64,
+        /// Receives the outcome once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Reject a pending soul version. Replies with `1`, or `0` if the
+    /// version is not pending.
+    RejectSoulVersion {
+        /// Soul version id.
+        id: i64,
+        /// Receives the outcome once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Queue a Telegram message in the outbox, due immediately. Replies
+    /// with the outbox id.
+    QueueTelegramMessage {
+        /// Destination chat.
+        chat_id: i64,
+        /// Serialised message.
+        payload: String,
+        /// Receives the outbox id once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Remove a delivered outbox message. Replies once committed.
+    DeleteTelegramMessage {
+        /// Outbox id.
+        id: i64,
+        /// Receives `0` once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Replace the payload of an outbox message. Replies once committed.
+    UpdateTelegramMessage {
+        /// Outbox id.
+        id: i64,
+        /// Serialised message still to send.
+        payload: String,
+        /// Receives `0` once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Record a failed outbox delivery. Replies once committed.
+    RecordTelegramFailure {
+        /// Outbox id.
+        id: i64,
+        /// Attempts made so far, including this one.
+        attempts: i64,
+        /// Why the send failed.
+        error: String,
+        /// When to retry (outbox timestamp format).
+        next_attempt_at: String,
+        /// Mark the message failed instead of retrying.
+        give_up: bool,
+        /// Receives `0` once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Mark an outbox message failed without further retries. Replies once
+    /// committed.
+    FailTelegramMessage {
+        /// Outbox id.
+        id: i64,
+        /// Why the send failed.
+        error: String,
+        /// Receives `0` once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+
+    /// Record an inbound Telegram update and prune old ones. Replies with
+    /// `1` on its first delivery, `0` for a redelivery.
+    RecordTelegramUpdate {
+        /// Telegram `update_id`.
+        update_id: i64,
+        /// Receive time (outbox timestamp format).
+        received_at: String,
+        /// Updates received before this time are forgotten.
+        prune_before: String,
+        /// Receives the outcome once committed.
+        reply: Option<oneshot::Sender<i64>>,
+    },
+}
+
+impl WriteOp {
+    /// Take the reply channel of ops that answer with a value.
+    fn take_reply(&mut self) -> Option<oneshot::Sender<i64>> {
+        match self {
+            Self::SaveMemoryWithId { reply, .. }
+            | Self::SubscribeFeed { reply, .. }
+            | Self::UnsubscribeFeed { reply, .. }
+            | Self::MarkFeedPolled { reply, .. }
+            | Self::RecordFeedEntries { reply, .. }
+            | Self::MarkFeedEntriesDigested { reply, .. }
+            | Self::AddSoulVersion { reply, .. }
+            | Self::ApproveSoulVersion { reply, .. }
+            | Self::RejectSoulVersion { reply, .. }
+            | Self::QueueTelegramMessage { reply, .. }
+            | Self::DeleteTelegramMessage { reply, .. }
+            | Self::UpdateTelegramMessage { reply, .. }
+            | Self::RecordTelegramFailure { reply, .. }
+            | Self::FailTelegramMessage { reply, .. }
+            | Self::RecordTelegramUpdate { reply, .. } => reply.take(),
+            _ => None,
+        }
+    }
 }
 
 /// Run the single-writer actor loop.
@@ -47,30 +307,49 @@ pub enum WriteOp {
 /// Processes [`WriteOp`] messages until the sender half is dropped.
 /// Each operation is executed as an individual SQL statement.
 pub async fn run_writer(db: SqlitePool, mut rx: mpsc::Receiver<WriteOp>) {
-    while let Some(op) = rx.recv().await {
-        if let Err(err) = handle_op(&db, &op).await {
-            error!(?op, error = %err, "memory write failed");
+    while let Some(mut op) = rx.recv().await {
+        let reply = op.take_reply();
+        match handle_op(&db, &op).await {
+            Ok(Some(value)) => {
+                if let Some(reply) = reply {
+                    // The caller may have stopped waiting; the write is committed either way.
+                    let _ = reply.send(value);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => error!(?op, error = %err, "memory write failed"),
         }
     }
     trace!("memory writer actor stopped");
 }
 
-async fn handle_op(db: &SqlitePool, op: &WriteOp) -> Result<(), sqlx::Error> {
+/// Insert a memory row and return its id.
+async fn insert_memory(db: &SqlitePool, memory: &Memory) -> Result<i64, sqlx::Error> {
+    let metadata_str = memory.metadata.as_ref().map(|v| v.to_string());
+    let result = sqlx::query(
+        "INSERT INTO memories (kind, content, metadata, status, source) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(memory.kind.as_str())
+    .bind(&memory.content)
+    .bind(&metadata_str)
+    .bind(memory.status.as_str())
+    .bind(memory.source.as_str())
+    .execute(db)
+    .await?;
+    trace!(kind = memory.kind.as_str(), "memory saved");
+    Ok(result.last_insert_rowid())
+}
+
+/// Apply one write; returns the reply value for ops that answer with one.
+async fn handle_op(db: &SqlitePool, op: &WriteOp) -> Result<Option<i64>, sqlx::Error> {
     match op {
         WriteOp::SaveMemory(memory) => {
-            let metadata_str = memory.metadata.as_ref().map(|v| v.to_string());
-            sqlx::query(
-                "INSERT INTO memories (kind, content, metadata, status, source) \
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-            )
-            .bind(memory.kind.as_str())
-            .bind(&memory.content)
-            .bind(&metadata_str)
-            .bind(memory.status.as_str())
-            .bind(memory.source.as_str())
-            .execute(db)
-            .await?;
-            trace!(kind = memory.kind.as_str(), "memory saved");
+            insert_memory(db, memory).await?;
+        }
+
+        WriteOp::SaveMemoryWithId { memory, .. } => {
+            return insert_memory(db, memory).await.map(Some);
         }
 
         WriteOp::SaveConversation(entry) => {
@@ -110,6 +389,44 @@ async fn handle_op(db: &SqlitePool, op: &WriteOp) -> Result<(), sqlx::Error> {
             trace!(domain, approved_by = approved_by.as_str(), "domain trusted");
         }
 
+        WriteOp::GrantToolNetwork {
+            tool,
+            domains,
+            approved_by,
+        } => {
+            let mut tx = db.begin().await?;
+            for domain in domains {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO tool_network_grants (tool_name, domain, approved_by) \
+                     VALUES (?1, ?2, ?3)",
+                )
+                .bind(tool)
+                .bind(domain)
+                .bind(approved_by.as_str())
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            trace!(tool, domains = domains.len(), "tool network granted");
+        }
+
+        WriteOp::RecordUsage(entry) => {
+            sqlx::query(
+                "INSERT INTO usage_ledger (role, model, session_id, input_tokens, \
+                 output_tokens, latency_ms, success) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )
+            .bind(&entry.role)
+            .bind(&entry.model)
+            .bind(&entry.session_id)
+            .bind(entry.input_tokens)
+            .bind(entry.output_tokens)
+            .bind(i64::try_from(entry.latency_ms).unwrap_or(i64::MAX))
+            .bind(entry.success)
+            .execute(db)
+            .await?;
+            trace!(model = entry.model, "provider usage recorded");
+        }
+
         WriteOp::DeleteMemory { id } => {
             sqlx::query("DELETE FROM memories WHERE id = ?1")
                 .bind(id)
@@ -117,6 +434,426 @@ async fn handle_op(db: &SqlitePool, op: &WriteOp) -> Result<(), sqlx::Error> {
                 .await?;
             trace!(id, "memory deleted");
         }
+
+        WriteOp::MergeMetadata { id, patch } => {
+            sqlx::query(
+                "UPDATE memories SET metadata = json_patch(COALESCE(metadata, '{}'), ?1), \
+                 updated_at = datetime('now') WHERE id = ?2",
+            )
+            .bind(patch.to_string())
+            .bind(id)
+            .execute(db)
+            .await?;
+            trace!(id, "memory metadata merged");
+        }
+
+        WriteOp::Remember { memory, actor } => {
+            let metadata_str = memory.metadata.as_ref().map(|v| v.to_string());
+            let mut tx = db.begin().await?;
+            let id = sqlx::query(
+                "INSERT INTO memories (kind, content, metadata, status, source) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .bind(memory.kind.as_str())
+            .bind(&memory.content)
+            .bind(&metadata_str)
+            .bind(memory.status.as_str())
+            .bind(memory.source.as_str())
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+            sqlx::query(
+                "INSERT INTO memory_audit (memory_id, action, content, actor) \
+                 VALUES (?1, 'remember', ?2, ?3)",
+            )
+            .bind(id)
+            .bind(&memory.content)
+            .bind(actor)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            trace!(id, actor, "memory remembered");
+        }
+
+        WriteOp::Forget { id, actor } => {
+            let mut tx = db.begin().await?;
+            let content: Option<(String,)> =
+                sqlx::query_as("SELECT content FROM memories WHERE id = ?1")
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some((content,)) = content {
+                sqlx::query("DELETE FROM memories WHERE id = ?1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO memory_audit (memory_id, action, content, actor) \
+                     VALUES (?1, 'forget', ?2, ?3)",
+                )
+                .bind(id)
+                .bind(&content)
+                .bind(actor)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            trace!(id, actor, "memory forgotten");
+        }
+
+        WriteOp::RecordTurn(turn) => {
+            let memory_ids = serde_json::to_string(&turn.memory_ids).unwrap_or_default();
+            let tools_used = serde_json::to_string(&turn.tools_used).unwrap_or_default();
+            sqlx::query(
+                "INSERT OR IGNORE INTO turn_feedback (turn_id, session_id, memory_ids, tools_used) \
+                 VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(&turn.turn_id)
+            .bind(&turn.session_id)
+            .bind(&memory_ids)
+            .bind(&tools_used)
+            .execute(db)
+            .await?;
+            trace!(turn_id = %turn.turn_id, "turn recorded");
+        }
+
+        WriteOp::RateTurn {
+            turn_id,
+            rating,
+            comment,
+        } => {
+            sqlx::query(
+                "UPDATE turn_feedback SET rating = ?1, comment = ?2, rated_at = datetime('now') \
+                 WHERE turn_id = ?3 AND processed = FALSE",
+            )
+            .bind(rating.as_i64())
+            .bind(comment)
+            .bind(turn_id)
+            .execute(db)
+            .await?;
+            trace!(turn_id, rating = rating.as_i64(), "turn rated");
+        }
+
+        WriteOp::ApplyFeedback {
+            turn_id,
+            memory_ids,
+            rating,
+        } => {
+            let (positive, negative) = match rating {
+                FeedbackRating::Up => (1_i64, 0_i64),
+                FeedbackRating::Down => (0_i64, 1_i64),
+            };
+            let mut tx = db.begin().await?;
+            for id in memory_ids {
+                sqlx::query(
+                    "UPDATE memories SET metadata = json_set(COALESCE(metadata, '{}'), \
+                       '$.confidence', \
+                         max(0.0, min(1.0, COALESCE(json_extract(metadata, '$.confidence'), ?1) + ?2)), \
+                       '$.positive_feedback', \
+                         COALESCE(json_extract(metadata, '$.positive_feedback'), 0) + ?3, \
+                       '$.negative_feedback', \
+                         COALESCE(json_extract(metadata, '$.negative_feedback'), 0) + ?4), \
+                     updated_at = datetime('now') \
+                     WHERE id = ?5",
+                )
+                .bind(DEFAULT_CONFIDENCE)
+                .bind(rating.confidence_delta())
+                .bind(positive)
+                .bind(negative)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query(
+                    "UPDATE memories SET status = 'archived', updated_at = datetime('now') \
+                     WHERE id = ?1 AND status = 'active' \
+                       AND json_extract(metadata, '$.negative_feedback') >= ?2 \
+                       AND json_extract(metadata, '$.confidence') < ?3",
+                )
+                .bind(id)
+                .bind(i64::from(ARCHIVE_NEGATIVE_COUNT))
+                .bind(ARCHIVE_CONFIDENCE)
+                .execute(&mut *tx)
+                .await?;
+            }
+            sqlx::query("UPDATE turn_feedback SET processed = TRUE WHERE turn_id = ?1")
+                .bind(turn_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            trace!(turn_id, memories = memory_ids.len(), "feedback applied");
+        }
+
+        WriteOp::SubscribeFeed {
+            url,
+            title,
+            priority,
+            ..
+        } => {
+            let (id,): (i64,) = sqlx::query_as(
+                "INSERT INTO feeds (url, title, priority) VALUES (?1, ?2, ?3) \
+                 ON CONFLICT(url) DO UPDATE SET title = COALESCE(excluded.title, feeds.title), \
+                 priority = excluded.priority \
+                 RETURNING id",
+            )
+            .bind(url)
+            .bind(title)
+            .bind(priority)
+            .fetch_one(db)
+            .await?;
+            trace!(url, id, "feed subscribed");
+            return Ok(Some(id));
+        }
+
+        WriteOp::UnsubscribeFeed { url, .. } => {
+            let mut tx = db.begin().await?;
+            sqlx::query(
+                "DELETE FROM feed_entries WHERE feed_id IN (SELECT id FROM feeds WHERE url = ?1)",
+            )
+            .bind(url)
+            .execute(&mut *tx)
+            .await?;
+            let removed = sqlx::query("DELETE FROM feeds WHERE url = ?1")
+                .bind(url)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            tx.commit().await?;
+            trace!(url, removed, "feed unsubscribed");
+            return Ok(Some(i64::try_from(removed).unwrap_or(i64::MAX)));
+        }
+
+        WriteOp::MarkFeedPolled {
+            feed_id,
+            title,
+            error,
+            ..
+        } => {
+            sqlx::query(
+                "UPDATE feeds SET last_polled_at = datetime('now'), last_error = ?2, \
+                 title = COALESCE(?3, title) WHERE id = ?1",
+            )
+            .bind(feed_id)
+            .bind(error)
+            .bind(title)
+            .execute(db)
+            .await?;
+            trace!(feed_id, failed = error.is_some(), "feed poll recorded");
+            return Ok(Some(0));
+        }
+
+        WriteOp::RecordFeedEntries {
+            feed_id,
+            entries,
+            digested,
+            ..
+        } => {
+            let mut inserted: i64 = 0;
+            let mut tx = db.begin().await?;
+            for entry in entries {
+                let result = sqlx::query(
+                    "INSERT OR IGNORE INTO feed_entries \
+                     (feed_id, guid, title, link, summary, published_at, digested_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, CASE WHEN ?7 THEN datetime('now') END)",
+                )
+                .bind(feed_id)
+                .bind(&entry.guid)
+                .bind(&entry.title)
+                .bind(&entry.link)
+                .bind(&entry.summary)
+                .bind(&entry.published)
+                .bind(digested)
+                .execute(&mut *tx)
+                .await?;
+                if result.rows_affected() > 0 {
+                    inserted = inserted.saturating_add(1);
+                }
+            }
+            tx.commit().await?;
+            trace!(feed_id, inserted, "feed entries recorded");
+            return Ok(Some(inserted));
+        }
+
+        WriteOp::MarkFeedEntriesDigested { ids, .. } => {
+            let mut tx = db.begin().await?;
+            for id in ids {
+                sqlx::query("UPDATE feed_entries SET digested_at = datetime('now') WHERE id = ?1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            trace!(count = ids.len(), "feed entries delivered");
+            return Ok(Some(i64::try_from(ids.len()).unwrap_or(i64::MAX)));
+        }
+
+        WriteOp::AddSoulVersion {
+            content,
+            activate,
+            replace_pending,
+            ..
+        } => {
+            let mut tx = db.begin().await?;
+            if *replace_pending {
+                sqlx::query(
+                    "UPDATE soul_versions SET status = 'rejected', decided_at = datetime('now') \
+                     WHERE status = 'pending'",
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            let id =
+                sqlx::query("INSERT INTO soul_versions (content, status) VALUES (?1, 'pending')")
+                    .bind(content)
+                    .execute(&mut *tx)
+                    .await?
+                    .last_insert_rowid();
+            if *activate {
+                activate_soul(&mut tx, id).await?;
+            }
+            tx.commit().await?;
+            trace!(id, activate, "soul version recorded");
+            return Ok(Some(id));
+        }
+
+        WriteOp::ApproveSoulVersion { id, .. } => {
+            let mut tx = db.begin().await?;
+            let pending: Option<(i64,)> =
+                sqlx::query_as("SELECT id FROM soul_versions WHERE id = ?1 AND status = 'pending'")
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if pending.is_some() {
+                activate_soul(&mut tx, *id).await?;
+            }
+            tx.commit().await?;
+            trace!(id, approved = pending.is_some(), "soul version approval");
+            return Ok(Some(i64::from(pending.is_some())));
+        }
+
+        WriteOp::RejectSoulVersion { id, .. } => {
+            let rejected = sqlx::query(
+                "UPDATE soul_versions SET status = 'rejected', decided_at = datetime('now') \
+                 WHERE id = ?1 AND status = 'pending'",
+            )
+            .bind(id)
+            .execute(db)
+            .await?
+            .rows_affected();
+            trace!(id, rejected, "soul version rejection");
+            return Ok(Some(i64::try_from(rejected).unwrap_or(i64::MAX)));
+        }
+
+        WriteOp::QueueTelegramMessage {
+            chat_id, payload, ..
+        } => {
+            let id = sqlx::query("INSERT INTO telegram_outbox (chat_id, payload) VALUES (?1, ?2)")
+                .bind(chat_id)
+                .bind(payload)
+                .execute(db)
+                .await?
+                .last_insert_rowid();
+            trace!(id, chat_id, "telegram message queued");
+            return Ok(Some(id));
+        }
+
+        WriteOp::DeleteTelegramMessage { id, .. } => {
+            sqlx::query("DELETE FROM telegram_outbox WHERE id = ?1")
+                .bind(id)
+                .execute(db)
+                .await?;
+            trace!(id, "queued telegram message removed");
+            return Ok(Some(0));
+        }
+
+        WriteOp::UpdateTelegramMessage { id, payload, .. } => {
+            sqlx::query("UPDATE telegram_outbox SET payload = ?2 WHERE id = ?1")
+                .bind(id)
+                .bind(payload)
+                .execute(db)
+                .await?;
+            trace!(id, "queued telegram message updated");
+            return Ok(Some(0));
+        }
+
+        WriteOp::RecordTelegramFailure {
+            id,
+            attempts,
+            error,
+            next_attempt_at,
+            give_up,
+            ..
+        } => {
+            sqlx::query(
+                "UPDATE telegram_outbox SET attempts = ?2, last_error = ?3, next_attempt_at = ?4, \
+                 status = CASE WHEN ?5 THEN 'failed' ELSE status END WHERE id = ?1",
+            )
+            .bind(id)
+            .bind(attempts)
+            .bind(error)
+            .bind(next_attempt_at)
+            .bind(give_up)
+            .execute(db)
+            .await?;
+            trace!(id, attempts, give_up, "telegram delivery failure recorded");
+            return Ok(Some(0));
+        }
+
+        WriteOp::FailTelegramMessage { id, error, .. } => {
+            sqlx::query(
+                "UPDATE telegram_outbox SET status = 'failed', attempts = attempts + 1, \
+                 last_error = ?2 WHERE id = ?1",
+            )
+            .bind(id)
+            .bind(error)
+            .execute(db)
+            .await?;
+            trace!(id, "queued telegram message failed");
+            return Ok(Some(0));
+        }
+
+        WriteOp::RecordTelegramUpdate {
+            update_id,
+            received_at,
+            prune_before,
+            ..
+        } => {
+            let mut tx = db.begin().await?;
+            let inserted = sqlx::query(
+                "INSERT OR IGNORE INTO telegram_updates (update_id, received_at) VALUES (?1, ?2)",
+            )
+            .bind(update_id)
+            .bind(received_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            sqlx::query("DELETE FROM telegram_updates WHERE received_at < ?1")
+                .bind(prune_before)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            return Ok(Some(i64::try_from(inserted).unwrap_or(i64::MAX)));
+        }
     }
+    Ok(None)
+}
+
+/// Supersede the active soul version and activate `id`.
+async fn activate_soul(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE soul_versions SET status = 'superseded', decided_at = datetime('now') \
+         WHERE status = 'active'",
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query(
+        "UPDATE soul_versions SET status = 'active', decided_at = datetime('now') WHERE id = ?1",
+    )
+    .bind(id)
+    .execute(&mut **tx)
+    .await?;
     Ok(())
 }
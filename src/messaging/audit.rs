@@ -8,7 +8,10 @@ use super::MessagingError;
 /// Log an outbound or inbound message to the audit trail.
 ///
 /// All messages sent or received through the messaging subsystem are recorded
-/// for compliance and debugging purposes.
+/// for compliance and debugging purposes. `delivery` records how an outbound
+/// message left the system (immediately, queued for quiet hours, sent as an
+/// urgent override, or released from the queue). Returns the new audit row ID
+/// so delivery state can be attached once the channel reports back.
 ///
 /// # Errors
 ///
@@ -24,11 +27,12 @@ pub async fn log_outbound(
     direction: &str,
     redaction_warnings: Option<&str>,
     blocked: bool,
-) -> Result<(), MessagingError> {
-    sqlx::query(
+    delivery: Option<&str>,
+) -> Result<i64, MessagingError> {
+    let result = sqlx::query(
         "INSERT INTO outbound_log (brief_id, session_id, channel, recipient, \
-         message_text, direction, redaction_warnings, blocked) \
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+         message_text, direction, redaction_warnings, blocked, delivery) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
     )
     .bind(brief_id)
     .bind(session_id)
@@ -38,9 +42,16 @@ pub async fn log_outbound(
     .bind(direction)
     .bind(redaction_warnings)
     .bind(blocked)
+    .bind(delivery)
     .execute(db)
     .await?;
 
-    trace!(channel, direction, blocked, "outbound message logged");
-    Ok(())
+    trace!(
+        channel,
+        direction,
+        blocked,
+        ?delivery,
+        "outbound message logged"
+    );
+    Ok(result.last_insert_rowid())
 }
@@ -1,11 +1,31 @@
 //! Contact resolution and persistence.
+//!
+//! Phone numbers, Telegram handles, and WhatsApp JIDs are normalized on write
+//! so the same person is recognised across channels. Contacts can carry any
+//! number of aliases (nicknames the user refers to them by), and probable
+//! duplicates can be detected and merged.
 
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
-use tracing::trace;
+use tracing::{info, trace};
 
 use super::MessagingError;
 
+/// Columns selected for every contact read, in [`ContactRow`] order.
+const CONTACT_COLUMNS: &str = "id, name, phone, whatsapp_jid, organization, notes, telegram_handle";
+
+/// Maximum contacts scanned when looking for duplicates.
+const MAX_CONTACTS_SCANNED: usize = 10_000;
+
+/// Minimum digits in a normalized phone number.
+const MIN_PHONE_DIGITS: usize = 6;
+
+/// Maximum digits in a normalized phone number (E.164 limit).
+const MAX_PHONE_DIGITS: usize = 15;
+
+/// WhatsApp JID domain for individual users.
+const WHATSAPP_USER_DOMAIN: &str = "s.whatsapp.net";
+
 /// Row type returned by SQLite queries for contacts.
 type ContactRow = (
     i64,
@@ -14,10 +34,11 @@ type ContactRow = (
     Option<String>,
     Option<String>,
     Option<String>,
+    Option<String>,
 );
 
 /// A contact the agent can communicate with on behalf of the user.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Contact {
     /// Database ID (None for new contacts).
     pub id: Option<i64>,
@@ -31,41 +52,232 @@ pub struct Contact {
     pub organization: Option<String>,
     /// Freeform notes.
     pub notes: Option<String>,
+    /// Telegram username (lowercase, without `@`).
+    #[serde(default)]
+    pub telegram_handle: Option<String>,
+}
+
+impl Contact {
+    /// Return a copy with phone, handle, and JID normalized.
+    ///
+    /// A WhatsApp JID is derived from an international phone number and vice
+    /// versa when only one of them is known. Unparseable values are kept as
+    /// given so no user input is silently dropped.
+    pub fn normalized(&self) -> Self {
+        let mut out = self.clone();
+        out.name = collapse_whitespace(&self.name);
+        if let Some(ref raw) = self.phone {
+            out.phone = Some(normalize_phone(raw).unwrap_or_else(|| raw.trim().to_owned()));
+        }
+        if let Some(ref raw) = self.telegram_handle {
+            out.telegram_handle =
+                Some(normalize_telegram_handle(raw).unwrap_or_else(|| raw.trim().to_owned()));
+        }
+        if let Some(ref raw) = self.whatsapp_jid {
+            out.whatsapp_jid =
+                Some(normalize_whatsapp_jid(raw).unwrap_or_else(|| raw.trim().to_owned()));
+        }
+        if out.whatsapp_jid.is_none() {
+            out.whatsapp_jid = out.phone.as_deref().and_then(whatsapp_jid_from_phone);
+        }
+        if out.phone.is_none() {
+            out.phone = out
+                .whatsapp_jid
+                .as_deref()
+                .and_then(phone_from_whatsapp_jid);
+        }
+        out
+    }
+
+    /// Apply a single identifier to the matching field.
+    pub fn set_identifier(&mut self, identifier: ContactIdentifier) {
+        match identifier {
+            ContactIdentifier::Phone(p) => self.phone = Some(p),
+            ContactIdentifier::Telegram(h) => self.telegram_handle = Some(h),
+            ContactIdentifier::WhatsApp(j) => self.whatsapp_jid = Some(j),
+        }
+    }
+}
+
+/// A channel-specific identifier parsed from user input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContactIdentifier {
+    /// Normalized phone number.
+    Phone(String),
+    /// Normalized Telegram username.
+    Telegram(String),
+    /// Normalized WhatsApp JID.
+    WhatsApp(String),
+}
+
+/// Classify and normalize a user-supplied identifier.
+///
+/// `@name` is a Telegram handle, `digits@domain` a WhatsApp JID, and anything
+/// else is parsed as a phone number.
+pub fn parse_identifier(raw: &str) -> Option<ContactIdentifier> {
+    let trimmed = raw.trim();
+    if trimmed.starts_with('@') {
+        normalize_telegram_handle(trimmed).map(ContactIdentifier::Telegram)
+    } else if trimmed.contains('@') {
+        normalize_whatsapp_jid(trimmed).map(ContactIdentifier::WhatsApp)
+    } else {
+        normalize_phone(trimmed).map(ContactIdentifier::Phone)
+    }
+}
+
+/// Normalize a phone number to `+<digits>` (international) or `<digits>`.
+///
+/// Accepts spaces, dashes, dots, and parentheses as separators, and `00` as
+/// an international prefix. Returns `None` for anything else.
+pub fn normalize_phone(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')' | '+'))
+    {
+        return None;
+    }
+    if trimmed.chars().skip(1).any(|c| c == '+') {
+        return None;
+    }
+    let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    let (international, digits) = if trimmed.starts_with('+') {
+        (true, digits.as_str())
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        (true, rest)
+    } else {
+        (false, digits.as_str())
+    };
+    if !(MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits.len()) {
+        return None;
+    }
+    Some(if international {
+        format!("+{digits}")
+    } else {
+        digits.to_owned()
+    })
+}
+
+/// Normalize a Telegram username: strip `@`, lowercase, validate charset.
+pub fn normalize_telegram_handle(raw: &str) -> Option<String> {
+    let handle = raw.trim().trim_start_matches('@').to_lowercase();
+    let valid_len = (5..=32).contains(&handle.chars().count());
+    let valid_chars = handle
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (valid_len && valid_chars).then_some(handle)
+}
+
+/// Normalize a WhatsApp JID (legacy `@c.us` becomes `@s.whatsapp.net`).
+pub fn normalize_whatsapp_jid(raw: &str) -> Option<String> {
+    let lowered = raw.trim().to_lowercase();
+    let (user, domain) = lowered.split_once('@')?;
+    if user.is_empty() || domain.is_empty() {
+        return None;
+    }
+    match domain {
+        "c.us" | WHATSAPP_USER_DOMAIN => {
+            let digits = user.trim_start_matches('+');
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            Some(format!("{digits}@{WHATSAPP_USER_DOMAIN}"))
+        }
+        _ => Some(lowered),
+    }
+}
+
+/// Derive a WhatsApp JID from an international phone number.
+pub fn whatsapp_jid_from_phone(phone: &str) -> Option<String> {
+    let normalized = normalize_phone(phone)?;
+    let digits = normalized.strip_prefix('+')?;
+    Some(format!("{digits}@{WHATSAPP_USER_DOMAIN}"))
+}
+
+/// Derive an international phone number from a WhatsApp user JID.
+pub fn phone_from_whatsapp_jid(jid: &str) -> Option<String> {
+    let normalized = normalize_whatsapp_jid(jid)?;
+    let digits = normalized.strip_suffix(&format!("@{WHATSAPP_USER_DOMAIN}"))?;
+    normalize_phone(&format!("+{digits}"))
+}
+
+/// Whether two phone numbers refer to the same line.
+///
+/// Exact matches after normalization always match. A local number (leading
+/// trunk `0` dropped) also matches an international number ending in it.
+pub fn phones_match(a: &str, b: &str) -> bool {
+    let (Some(a), Some(b)) = (normalize_phone(a), normalize_phone(b)) else {
+        return false;
+    };
+    if a == b {
+        return true;
+    }
+    let (intl, local) = match (a.strip_prefix('+'), b.strip_prefix('+')) {
+        (Some(intl), None) => (intl, b.as_str()),
+        (None, Some(intl)) => (intl, a.as_str()),
+        _ => return false,
+    };
+    let local = local.trim_start_matches('0');
+    local.len() >= MIN_PHONE_DIGITS && intl.ends_with(local)
+}
+
+/// Whether two contacts are probably the same person.
+///
+/// Any shared phone, WhatsApp JID, or Telegram handle counts, as does an
+/// identical name (case- and whitespace-insensitive).
+pub fn is_probable_duplicate(a: &Contact, b: &Contact) -> bool {
+    let a = a.normalized();
+    let b = b.normalized();
+    let same =
+        |x: &Option<String>, y: &Option<String>| matches!((x, y), (Some(x), Some(y)) if x == y);
+
+    if let (Some(pa), Some(pb)) = (&a.phone, &b.phone) {
+        if phones_match(pa, pb) {
+            return true;
+        }
+    }
+    same(&a.whatsapp_jid, &b.whatsapp_jid)
+        || same(&a.telegram_handle, &b.telegram_handle)
+        || (!a.name.is_empty() && a.name.to_lowercase() == b.name.to_lowercase())
 }
 
 /// Insert or update a contact.
 ///
-/// If `contact.id` is `Some`, updates the existing row. Otherwise inserts a new
-/// row and returns the auto-generated ID.
+/// Identifiers are normalized before writing. If `contact.id` is `Some`,
+/// updates the existing row. Otherwise inserts a new row and returns the
+/// auto-generated ID.
 ///
 /// # Errors
 ///
 /// Returns [`MessagingError::Database`] on SQLite failure.
 pub async fn upsert_contact(db: &SqlitePool, contact: &Contact) -> Result<i64, MessagingError> {
+    let contact = contact.normalized();
     if let Some(id) = contact.id {
         sqlx::query(
             "UPDATE contacts SET name=?1, phone=?2, whatsapp_jid=?3, \
-             organization=?4, notes=?5 WHERE id=?6",
+             organization=?4, notes=?5, telegram_handle=?6 WHERE id=?7",
         )
         .bind(&contact.name)
         .bind(&contact.phone)
         .bind(&contact.whatsapp_jid)
         .bind(&contact.organization)
         .bind(&contact.notes)
+        .bind(&contact.telegram_handle)
         .bind(id)
         .execute(db)
         .await?;
         return Ok(id);
     }
     let result = sqlx::query(
-        "INSERT INTO contacts (name, phone, whatsapp_jid, organization, notes) \
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO contacts (name, phone, whatsapp_jid, organization, notes, telegram_handle) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
     )
     .bind(&contact.name)
     .bind(&contact.phone)
     .bind(&contact.whatsapp_jid)
     .bind(&contact.organization)
     .bind(&contact.notes)
+    .bind(&contact.telegram_handle)
     .execute(db)
     .await?;
     let id = result.last_insert_rowid();
@@ -73,7 +285,7 @@ pub async fn upsert_contact(db: &SqlitePool, contact: &Contact) -> Result<i64, M
     Ok(id)
 }
 
-/// Search contacts by name (case-insensitive LIKE match).
+/// Search contacts by name or alias (case-insensitive LIKE match).
 ///
 /// # Errors
 ///
@@ -85,25 +297,33 @@ pub async fn search_contacts(
 ) -> Result<Vec<Contact>, MessagingError> {
     let pattern = format!("%{query}%");
     let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
-    let rows: Vec<ContactRow> = sqlx::query_as(
-        "SELECT id, name, phone, whatsapp_jid, organization, notes \
-         FROM contacts WHERE name LIKE ?1 ORDER BY name LIMIT ?2",
-    )
+    let rows: Vec<ContactRow> = sqlx::query_as(&format!(
+        "SELECT {CONTACT_COLUMNS} FROM contacts \
+         WHERE name LIKE ?1 \
+            OR id IN (SELECT contact_id FROM contact_aliases WHERE alias LIKE ?1) \
+         ORDER BY name LIMIT ?2"
+    ))
     .bind(&pattern)
     .bind(limit_i64)
     .fetch_all(db)
     .await?;
-    Ok(rows
-        .into_iter()
-        .map(|(id, name, phone, jid, org, notes)| Contact {
-            id: Some(id),
-            name,
-            phone,
-            whatsapp_jid: jid,
-            organization: org,
-            notes,
-        })
-        .collect())
+    Ok(rows.into_iter().map(contact_from_row).collect())
+}
+
+/// List all contacts ordered by name.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn list_contacts(db: &SqlitePool, limit: usize) -> Result<Vec<Contact>, MessagingError> {
+    let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows: Vec<ContactRow> = sqlx::query_as(&format!(
+        "SELECT {CONTACT_COLUMNS} FROM contacts ORDER BY name LIMIT ?1"
+    ))
+    .bind(limit_i64)
+    .fetch_all(db)
+    .await?;
+    Ok(rows.into_iter().map(contact_from_row).collect())
 }
 
 /// Load a contact by ID.
@@ -113,20 +333,236 @@ pub async fn search_contacts(
 /// Returns [`MessagingError::ContactNotFound`] if no contact matches,
 /// or [`MessagingError::Database`] on SQLite failure.
 pub async fn load_contact(db: &SqlitePool, contact_id: i64) -> Result<Contact, MessagingError> {
-    let row: ContactRow = sqlx::query_as(
-        "SELECT id, name, phone, whatsapp_jid, organization, notes \
-         FROM contacts WHERE id = ?1",
-    )
+    let row: ContactRow = sqlx::query_as(&format!(
+        "SELECT {CONTACT_COLUMNS} FROM contacts WHERE id = ?1"
+    ))
     .bind(contact_id)
     .fetch_optional(db)
     .await?
     .ok_or_else(|| MessagingError::ContactNotFound(contact_id.to_string()))?;
-    Ok(Contact {
-        id: Some(row.0),
-        name: row.1,
-        phone: row.2,
-        whatsapp_jid: row.3,
-        organization: row.4,
-        notes: row.5,
-    })
+    Ok(contact_from_row(row))
+}
+
+/// Resolve a user reference to a single contact.
+///
+/// Tries, in order: `#id`, exact alias, exact name, then phone / handle /
+/// JID identifiers.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn resolve_contact(
+    db: &SqlitePool,
+    reference: &str,
+) -> Result<Option<Contact>, MessagingError> {
+    let reference = reference.trim();
+    if let Some(id) = reference
+        .strip_prefix('#')
+        .and_then(|s| s.parse::<i64>().ok())
+    {
+        return match load_contact(db, id).await {
+            Ok(c) => Ok(Some(c)),
+            Err(MessagingError::ContactNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        };
+    }
+
+    let by_alias: Option<ContactRow> = sqlx::query_as(&format!(
+        "SELECT {CONTACT_COLUMNS} FROM contacts WHERE id = \
+         (SELECT contact_id FROM contact_aliases WHERE alias = ?1 COLLATE NOCASE)"
+    ))
+    .bind(reference)
+    .fetch_optional(db)
+    .await?;
+    if let Some(row) = by_alias {
+        return Ok(Some(contact_from_row(row)));
+    }
+
+    let by_name: Option<ContactRow> = sqlx::query_as(&format!(
+        "SELECT {CONTACT_COLUMNS} FROM contacts WHERE name = ?1 COLLATE NOCASE \
+         ORDER BY id LIMIT 1"
+    ))
+    .bind(collapse_whitespace(reference))
+    .fetch_optional(db)
+    .await?;
+    if let Some(row) = by_name {
+        return Ok(Some(contact_from_row(row)));
+    }
+
+    let Some(identifier) = parse_identifier(reference) else {
+        return Ok(None);
+    };
+    let mut probe = Contact::default();
+    probe.set_identifier(identifier);
+    let probe = probe.normalized();
+    let all = list_contacts(db, MAX_CONTACTS_SCANNED).await?;
+    Ok(all.into_iter().find(|c| {
+        let c = c.normalized();
+        matches!((&c.phone, &probe.phone), (Some(a), Some(b)) if phones_match(a, b))
+            || (probe.telegram_handle.is_some() && c.telegram_handle == probe.telegram_handle)
+            || (probe.whatsapp_jid.is_some() && c.whatsapp_jid == probe.whatsapp_jid)
+    }))
+}
+
+/// Find stored contacts that are probably the same person as `contact`.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn find_duplicates(
+    db: &SqlitePool,
+    contact: &Contact,
+) -> Result<Vec<Contact>, MessagingError> {
+    let all = list_contacts(db, MAX_CONTACTS_SCANNED).await?;
+    Ok(all
+        .into_iter()
+        .filter(|other| other.id != contact.id && is_probable_duplicate(contact, other))
+        .collect())
+}
+
+/// Attach an alias to a contact. Re-pointing an existing alias moves it.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidContact`] for an empty alias,
+/// [`MessagingError::ContactNotFound`] if the contact does not exist,
+/// or [`MessagingError::Database`] on SQLite failure.
+pub async fn add_alias(
+    db: &SqlitePool,
+    contact_id: i64,
+    alias: &str,
+) -> Result<(), MessagingError> {
+    let alias = collapse_whitespace(alias);
+    if alias.is_empty() {
+        return Err(MessagingError::InvalidContact("alias is empty".to_owned()));
+    }
+    load_contact(db, contact_id).await?;
+    sqlx::query(
+        "INSERT INTO contact_aliases (alias, contact_id) VALUES (?1, ?2) \
+         ON CONFLICT(alias) DO UPDATE SET contact_id = excluded.contact_id",
+    )
+    .bind(&alias)
+    .bind(contact_id)
+    .execute(db)
+    .await?;
+    trace!(contact_id, alias = %alias, "contact alias added");
+    Ok(())
+}
+
+/// List the aliases of a contact.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn list_aliases(db: &SqlitePool, contact_id: i64) -> Result<Vec<String>, MessagingError> {
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT alias FROM contact_aliases WHERE contact_id = ?1 ORDER BY alias")
+            .bind(contact_id)
+            .fetch_all(db)
+            .await?;
+    Ok(rows.into_iter().map(|(a,)| a).collect())
+}
+
+/// Merge `drop_id` into `keep_id` and delete the dropped contact.
+///
+/// Empty fields on the kept contact are filled from the dropped one, notes
+/// are concatenated, aliases and task briefs are re-pointed, and the dropped
+/// contact's name becomes an alias of the kept one.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidContact`] when both ids are equal,
+/// [`MessagingError::ContactNotFound`] if either contact is missing,
+/// or [`MessagingError::Database`] on SQLite failure.
+pub async fn merge_contacts(
+    db: &SqlitePool,
+    keep_id: i64,
+    drop_id: i64,
+) -> Result<Contact, MessagingError> {
+    if keep_id == drop_id {
+        return Err(MessagingError::InvalidContact(
+            "cannot merge a contact into itself".to_owned(),
+        ));
+    }
+    let keep = load_contact(db, keep_id).await?;
+    let dropped = load_contact(db, drop_id).await?;
+
+    let notes = match (keep.notes.clone(), dropped.notes.clone()) {
+        (Some(a), Some(b)) if a.trim() != b.trim() => Some(format!("{a}\n{b}")),
+        (a, b) => a.or(b),
+    };
+    let merged = Contact {
+        id: Some(keep_id),
+        name: keep.name.clone(),
+        phone: keep.phone.clone().or(dropped.phone.clone()),
+        whatsapp_jid: keep.whatsapp_jid.clone().or(dropped.whatsapp_jid.clone()),
+        organization: keep.organization.clone().or(dropped.organization.clone()),
+        notes,
+        telegram_handle: keep
+            .telegram_handle
+            .clone()
+            .or(dropped.telegram_handle.clone()),
+    }
+    .normalized();
+
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        "UPDATE contacts SET phone=?1, whatsapp_jid=?2, organization=?3, notes=?4, \
+         telegram_handle=?5 WHERE id=?6",
+    )
+    .bind(&merged.phone)
+    .bind(&merged.whatsapp_jid)
+    .bind(&merged.organization)
+    .bind(&merged.notes)
+    .bind(&merged.telegram_handle)
+    .bind(keep_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("UPDATE contact_aliases SET contact_id = ?1 WHERE contact_id = ?2")
+        .bind(keep_id)
+        .bind(drop_id)
+        .execute(&mut *tx)
+        .await?;
+    if !dropped.name.eq_ignore_ascii_case(&keep.name) {
+        sqlx::query(
+            "INSERT INTO contact_aliases (alias, contact_id) VALUES (?1, ?2) \
+             ON CONFLICT(alias) DO UPDATE SET contact_id = excluded.contact_id",
+        )
+        .bind(&dropped.name)
+        .bind(keep_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query("UPDATE task_briefs SET contact_id = ?1 WHERE contact_id = ?2")
+        .bind(keep_id)
+        .bind(drop_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM contacts WHERE id = ?1")
+        .bind(drop_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    info!(keep_id, drop_id, "contacts merged");
+    Ok(merged)
+}
+
+/// Convert a database row into a [`Contact`].
+fn contact_from_row(row: ContactRow) -> Contact {
+    let (id, name, phone, whatsapp_jid, organization, notes, telegram_handle) = row;
+    Contact {
+        id: Some(id),
+        name,
+        phone,
+        whatsapp_jid,
+        organization,
+        notes,
+        telegram_handle,
+    }
+}
+
+/// Trim and collapse internal runs of whitespace to single spaces.
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -0,0 +1,288 @@
+//! Per-message delivery state for outbound messages.
+//!
+//! Each sent message's audit row carries the channel's message ID and a
+//! delivery state that only moves forward: `sent` → `delivered` → `read`.
+//! WhatsApp receipts from the sidecar advance the state; a send that never
+//! succeeds is recorded as `failed`. Messages that stay unread for too long
+//! are surfaced by the heartbeat so the user can decide on a follow-up.
+
+use sqlx::SqlitePool;
+use tracing::{debug, trace};
+
+use super::MessagingError;
+
+/// Characters of message text shown per line in delivery summaries.
+const EXCERPT_CHARS: usize = 60;
+
+/// Delivery state of an outbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryState {
+    /// Accepted by the channel.
+    Sent,
+    /// Reached the recipient's device.
+    Delivered,
+    /// Opened by the recipient.
+    Read,
+    /// Could not be sent.
+    Failed,
+}
+
+impl DeliveryState {
+    /// Returns the SQLite-stored string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Delivered => "delivered",
+            Self::Read => "read",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Parse the SQLite-stored string representation.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "sent" => Some(Self::Sent),
+            "delivered" => Some(Self::Delivered),
+            "read" => Some(Self::Read),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    /// Map a sidecar receipt status to a delivery state.
+    ///
+    /// Accepts both our own names and Baileys' ack names
+    /// (`server_ack`, `delivery_ack`, `played`, `error`).
+    pub fn from_receipt(status: &str) -> Option<Self> {
+        match status.to_ascii_lowercase().as_str() {
+            "sent" | "server_ack" => Some(Self::Sent),
+            "delivered" | "delivery_ack" => Some(Self::Delivered),
+            "read" | "played" => Some(Self::Read),
+            "failed" | "error" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether moving from `self` to `next` is progress.
+    ///
+    /// States only advance; receipts arriving out of order (a late
+    /// `delivered` after `read`) are ignored. `failed` only replaces `sent`.
+    pub fn can_advance_to(&self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Sent, Self::Delivered | Self::Read | Self::Failed)
+                | (Self::Delivered, Self::Read)
+        )
+    }
+}
+
+/// An outbound message with its delivery state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryEntry {
+    /// Audit row ID.
+    pub id: i64,
+    /// Brief the message belongs to, if any.
+    pub brief_id: Option<String>,
+    /// Recipient identifier (e.g. WhatsApp JID).
+    pub recipient: String,
+    /// Contact name, when the brief links a contact.
+    pub contact_name: Option<String>,
+    /// Message text as sent.
+    pub message_text: String,
+    /// Current delivery state.
+    pub state: DeliveryState,
+    /// When the message was logged.
+    pub created_at: String,
+}
+
+/// Row type returned by delivery queries.
+type DeliveryRow = (
+    i64,
+    Option<String>,
+    String,
+    Option<String>,
+    String,
+    String,
+    String,
+);
+
+/// Columns selected for delivery queries, in [`DeliveryRow`] order.
+const DELIVERY_COLUMNS: &str = "o.id, o.brief_id, o.recipient, c.name, o.message_text, \
+     o.delivery_state, o.created_at";
+
+/// Joins giving every delivery row its brief and contact.
+const DELIVERY_JOINS: &str = "FROM outbound_log o \
+     LEFT JOIN task_briefs b ON b.id = o.brief_id \
+     LEFT JOIN contacts c ON c.id = b.contact_id";
+
+/// Record that an audit row was sent, with the channel's message ID if known.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn mark_sent(
+    db: &SqlitePool,
+    log_id: i64,
+    message_id: Option<&str>,
+) -> Result<(), MessagingError> {
+    set_state(db, log_id, message_id, DeliveryState::Sent).await
+}
+
+/// Record that an audit row could not be sent.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn mark_failed(db: &SqlitePool, log_id: i64) -> Result<(), MessagingError> {
+    set_state(db, log_id, None, DeliveryState::Failed).await
+}
+
+/// Apply a receipt for a channel message ID.
+///
+/// Returns `true` if the state changed; unknown message IDs and
+/// out-of-order receipts are ignored.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn record_receipt(
+    db: &SqlitePool,
+    message_id: &str,
+    state: DeliveryState,
+) -> Result<bool, MessagingError> {
+    let current: Option<(i64, Option<String>)> = sqlx::query_as(
+        "SELECT id, delivery_state FROM outbound_log \
+         WHERE message_id = ?1 AND direction = 'outbound' ORDER BY id DESC LIMIT 1",
+    )
+    .bind(message_id)
+    .fetch_optional(db)
+    .await?;
+
+    let Some((id, current)) = current else {
+        trace!(message_id, "receipt for unknown message ignored");
+        return Ok(false);
+    };
+    let current = current
+        .as_deref()
+        .and_then(DeliveryState::parse)
+        .unwrap_or(DeliveryState::Sent);
+    if !current.can_advance_to(state) {
+        return Ok(false);
+    }
+
+    set_state(db, id, None, state).await?;
+    debug!(
+        id,
+        message_id,
+        state = state.as_str(),
+        "delivery state updated"
+    );
+    Ok(true)
+}
+
+/// Most recent outbound messages for a brief, newest first.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn recent_for_brief(
+    db: &SqlitePool,
+    brief_id: &str,
+    limit: i64,
+) -> Result<Vec<DeliveryEntry>, MessagingError> {
+    let rows: Vec<DeliveryRow> = sqlx::query_as(&format!(
+        "SELECT {DELIVERY_COLUMNS} {DELIVERY_JOINS} \
+         WHERE o.brief_id = ?1 AND o.direction = 'outbound' \
+         AND o.delivery_state IS NOT NULL ORDER BY o.id DESC LIMIT ?2"
+    ))
+    .bind(brief_id)
+    .bind(limit)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(entry_from_row).collect())
+}
+
+/// Sent messages on open briefs still unread after `days` days that have
+/// not been flagged yet.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn stale_unread(
+    db: &SqlitePool,
+    days: u32,
+) -> Result<Vec<DeliveryEntry>, MessagingError> {
+    let rows: Vec<DeliveryRow> = sqlx::query_as(&format!(
+        "SELECT {DELIVERY_COLUMNS} {DELIVERY_JOINS} \
+         WHERE o.direction = 'outbound' \
+         AND o.delivery_state IN ('sent', 'delivered') \
+         AND o.unread_alerted_at IS NULL \
+         AND o.created_at <= datetime('now', ?1) \
+         AND (b.status IS NULL OR b.status NOT IN ('completed', 'cancelled')) \
+         ORDER BY o.id"
+    ))
+    .bind(format!("-{days} days"))
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().filter_map(entry_from_row).collect())
+}
+
+/// Remember that the user was told about an unread message.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn mark_unread_alerted(db: &SqlitePool, log_id: i64) -> Result<(), MessagingError> {
+    sqlx::query("UPDATE outbound_log SET unread_alerted_at = datetime('now') WHERE id = ?1")
+        .bind(log_id)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// One-line delivery summary for brief status output.
+pub fn format_entries(entries: &[DeliveryEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| {
+            let excerpt: String = e.message_text.chars().take(EXCERPT_CHARS).collect();
+            format!("- [{}] {}: {excerpt}", e.state.as_str(), e.created_at)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Write a delivery state (and optionally the message ID) to an audit row.
+async fn set_state(
+    db: &SqlitePool,
+    log_id: i64,
+    message_id: Option<&str>,
+    state: DeliveryState,
+) -> Result<(), MessagingError> {
+    sqlx::query(
+        "UPDATE outbound_log SET delivery_state = ?2, \
+         message_id = COALESCE(?3, message_id), state_updated_at = datetime('now') \
+         WHERE id = ?1",
+    )
+    .bind(log_id)
+    .bind(state.as_str())
+    .bind(message_id)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Convert a [`DeliveryRow`], skipping rows with an unknown state.
+fn entry_from_row(row: DeliveryRow) -> Option<DeliveryEntry> {
+    Some(DeliveryEntry {
+        id: row.0,
+        brief_id: row.1,
+        recipient: row.2,
+        contact_name: row.3,
+        message_text: row.4,
+        state: DeliveryState::parse(&row.5)?,
+        created_at: row.6,
+    })
+}
@@ -1,9 +1,11 @@
-//! Messaging module: task briefs, outbound composition, privacy redaction, and audit.
+//! Messaging module: task briefs, contacts, outbound composition, privacy
+//! redaction, quiet hours, templates, delivery tracking, and audit.
 //!
 //! # SQLite Write Pattern
 //!
-//! Unlike the memory engine (which uses a single-writer actor), messaging tables
-//! (`task_briefs`, `contacts`, `outbound_log`) use direct pool writes. This is
+//! Unlike the memory engine (which uses a single-writer actor), messaging
+//! tables (`task_briefs`, `contacts`, `contact_aliases`, `outbound_log`,
+//! `outbound_queue`, `message_templates`) use direct pool writes. This is
 //! acceptable because: (1) these tables are never written by the memory actor,
 //! (2) SQLite WAL mode allows concurrent writes from different tables, and
 //! (3) messaging writes are low-frequency (one per human-like delayed message).
@@ -11,9 +13,13 @@
 pub mod audit;
 pub mod brief;
 pub mod contacts;
+pub mod delivery;
 pub mod outbound_composer;
 pub mod outbound_context;
+pub mod outbound_queue;
 pub mod outbound_redactor;
+pub mod quiet_hours;
+pub mod templates;
 
 /// Errors from the messaging subsystem.
 #[derive(Debug, thiserror::Error)]
@@ -39,10 +45,26 @@ pub enum MessagingError {
     #[error("contact not found: {0}")]
     ContactNotFound(String),
 
+    /// Contact input was rejected (empty alias, self-merge, ...).
+    #[error("invalid contact: {0}")]
+    InvalidContact(String),
+
     /// Outbound message composition failed.
     #[error("composition failed: {0}")]
     CompositionFailed(String),
 
+    /// The requested message template was not found.
+    #[error("template not found: {0}")]
+    TemplateNotFound(String),
+
+    /// A template could not be saved or filled.
+    #[error("invalid template: {0}")]
+    InvalidTemplate(String),
+
+    /// A quiet-hours window could not be parsed.
+    #[error("invalid quiet hours '{0}': expected HH:MM-HH:MM or \"off\"")]
+    InvalidQuietHours(String),
+
     /// Redactor blocked the outbound message.
     #[error("redaction blocked: {0}")]
     RedactionBlocked(String),
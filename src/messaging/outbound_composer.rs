@@ -4,6 +4,7 @@
 //! brief-scoped context. The outbound composer has NO access to USER.md,
 //! memories, AGENTS.md, or the main conversation.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use rand::Rng;
@@ -11,11 +12,15 @@ use tracing::{debug, warn};
 
 use crate::agent::budget::DailyBudget;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{extract_text, CompletionRequest, Message, MessageContent, Role};
 
 use super::brief::TaskBrief;
+use super::contacts::Contact;
 use super::outbound_context::build_outbound_system_prompt;
 use super::outbound_redactor::{OutboundRedactor, RedactionWarning};
+use super::quiet_hours::QuietHoursPolicy;
+use super::templates::{self, MessageTemplate};
 use super::MessagingError;
 
 /// Result of outbound composition.
@@ -34,6 +39,7 @@ pub struct OutboundComposer {
     model_router: Arc<ModelRouter>,
     daily_budget: Arc<DailyBudget>,
     redactor: OutboundRedactor,
+    quiet_hours: QuietHoursPolicy,
 }
 
 impl OutboundComposer {
@@ -47,12 +53,26 @@ impl OutboundComposer {
             model_router,
             daily_budget,
             redactor,
+            quiet_hours: QuietHoursPolicy::default(),
         }
     }
 
+    /// Enforce the given quiet-hours policy on outbound sends.
+    #[must_use]
+    pub fn with_quiet_hours(mut self, policy: QuietHoursPolicy) -> Self {
+        self.quiet_hours = policy;
+        self
+    }
+
+    /// The quiet-hours policy applied before messages are sent.
+    pub fn quiet_hours(&self) -> &QuietHoursPolicy {
+        &self.quiet_hours
+    }
+
     /// Compose a natural message from agent intent.
     ///
-    /// Uses a separate LLM call with restricted context (brief only).
+    /// Uses a separate LLM call with restricted context (brief and the
+    /// recipient's contact card only).
     /// The composed message is scanned by the outbound redactor before
     /// being returned.
     ///
@@ -63,11 +83,12 @@ impl OutboundComposer {
     pub async fn compose(
         &self,
         brief: &TaskBrief,
+        contact: Option<&Contact>,
         conversation_history: &[OutboundMessage],
         incoming: Option<&str>,
         agent_intent: &str,
     ) -> Result<ComposedMessage, MessagingError> {
-        let system_prompt = build_outbound_system_prompt(brief);
+        let system_prompt = build_outbound_system_prompt(brief, contact);
 
         // Build messages: conversation history + current intent
         let mut messages = Vec::new();
@@ -124,10 +145,12 @@ impl OutboundComposer {
             stop_sequences: vec![],
         };
 
-        let response = provider
-            .complete(request)
-            .await
-            .map_err(|e| MessagingError::CompositionFailed(e.to_string()))?;
+        let response = usage::scoped(
+            UsageScope::current_with_role("outbound"),
+            provider.complete(request),
+        )
+        .await
+        .map_err(|e| MessagingError::CompositionFailed(e.to_string()))?;
 
         // Record budget usage
         let total_tokens = u64::from(response.usage.input_tokens)
@@ -155,6 +178,42 @@ impl OutboundComposer {
             blocked,
         })
     }
+
+    /// Fill a stored template instead of composing with the LLM.
+    ///
+    /// The filled text goes through the same redactor scan as composed
+    /// messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MessagingError::InvalidTemplate`] if a placeholder has no value.
+    pub fn fill_template(
+        &self,
+        template: &MessageTemplate,
+        brief: &TaskBrief,
+        contact: Option<&Contact>,
+        extra: &HashMap<String, String>,
+    ) -> Result<ComposedMessage, MessagingError> {
+        let text = templates::fill(template, brief, contact, extra)?;
+        debug!(brief_id = %brief.id, template = %template.name, "outbound message filled from template");
+
+        let warnings = self.redactor.scan(&text, brief);
+        let blocked = OutboundRedactor::has_blocking_warnings(&warnings);
+        if blocked {
+            warn!(
+                brief_id = %brief.id,
+                template = %template.name,
+                warning_count = warnings.len(),
+                "templated outbound message blocked by redactor"
+            );
+        }
+
+        Ok(ComposedMessage {
+            text,
+            warnings,
+            blocked,
+        })
+    }
 }
 
 /// Minimum human-like delay in milliseconds.
@@ -219,7 +278,8 @@ pub struct OutboundMessage {
 ///
 /// Returns messages ordered chronologically. The `direction` column maps to
 /// `is_from_contact`: `"inbound"` means the contact sent the message,
-/// `"outbound"` means the agent sent it.
+/// `"outbound"` means the agent sent it. Messages held by quiet hours appear
+/// once, at the time they were composed, not again when the queue releases them.
 ///
 /// # Errors
 ///
@@ -231,9 +291,11 @@ pub async fn load_conversation_history(
     let rows: Vec<(String, String)> = sqlx::query_as(
         "SELECT message_text, direction FROM outbound_log \
          WHERE brief_id = ?1 AND blocked = FALSE \
+         AND COALESCE(delivery, '') != ?2 \
          ORDER BY created_at ASC",
     )
     .bind(brief_id)
+    .bind(super::outbound_queue::DELIVERED_FROM_QUEUE)
     .fetch_all(db)
     .await?;
 
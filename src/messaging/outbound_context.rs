@@ -1,15 +1,18 @@
 //! Build isolated outbound context from brief only.
 //!
-//! The outbound composer gets ONLY the brief data -- no USER.md, no memories,
-//! no AGENTS.md, no main conversation history. This is the key privacy mechanism.
+//! The outbound composer gets ONLY the brief data plus the recipient's contact
+//! card -- no USER.md, no memories, no AGENTS.md, no main conversation
+//! history. This is the key privacy mechanism.
 
 use super::brief::{CommitmentLevel, Constraint, TaskBrief};
+use super::contacts::Contact;
 
 /// Build the system prompt for the outbound composer.
 ///
-/// The prompt contains only information from the brief. Budget ceilings are
-/// intentionally omitted to prevent accidental disclosure.
-pub fn build_outbound_system_prompt(brief: &TaskBrief) -> String {
+/// The prompt contains only information from the brief and, when known, the
+/// recipient's name, organization, and notes. Budget ceilings and contact
+/// identifiers are intentionally omitted to prevent accidental disclosure.
+pub fn build_outbound_system_prompt(brief: &TaskBrief, contact: Option<&Contact>) -> String {
     let mut prompt = String::with_capacity(2048);
 
     prompt.push_str(
@@ -21,6 +24,10 @@ pub fn build_outbound_system_prompt(brief: &TaskBrief) -> String {
     prompt.push_str(&brief.objective);
     prompt.push_str("\n\n");
 
+    if let Some(contact) = contact {
+        prompt.push_str(&format_contact(contact));
+    }
+
     prompt.push_str("## Information you CAN share\n");
     for info in &brief.shareable_info {
         prompt.push_str("- ");
@@ -112,3 +119,22 @@ fn format_constraint(constraint: &Constraint) -> String {
         Constraint::Custom(s) => s.clone(),
     }
 }
+
+/// Format the recipient's contact card for the system prompt.
+///
+/// Notes are background for tailoring tone and content; the prompt marks
+/// them as private so they are not quoted back to the contact.
+fn format_contact(contact: &Contact) -> String {
+    let mut section = String::from("## Who you are writing to\n");
+    section.push_str(&format!("Name: {}\n", contact.name));
+    if let Some(ref org) = contact.organization {
+        section.push_str(&format!("Organization: {org}\n"));
+    }
+    if let Some(notes) = contact.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        section.push_str("Private notes (use to tailor the message; never quote or reveal):\n");
+        section.push_str(notes.trim());
+        section.push('\n');
+    }
+    section.push('\n');
+    section
+}
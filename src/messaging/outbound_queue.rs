@@ -0,0 +1,203 @@
+//! Deferred outbound delivery queue.
+//!
+//! Messages held back by quiet hours are stored here with a UTC `deliver_at`
+//! time and released by the heartbeat once it passes. Failed deliveries are
+//! retried a few times before the entry is marked failed.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::{debug, trace};
+
+use super::MessagingError;
+
+/// Audit label for messages sent by the queue rather than the tool call.
+pub const DELIVERED_FROM_QUEUE: &str = "delivered_from_queue";
+
+/// Delivery attempts before a queued message is marked failed.
+const MAX_ATTEMPTS: i64 = 3;
+
+/// Timestamp format matching SQLite's `datetime('now')`.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Row type returned by SQLite queries for queued messages.
+type QueueRow = (
+    i64,
+    Option<String>,
+    String,
+    String,
+    String,
+    String,
+    String,
+    i64,
+);
+
+/// A message waiting in the outbound queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMessage {
+    /// Queue entry ID.
+    pub id: i64,
+    /// Brief the message belongs to, if any.
+    pub brief_id: Option<String>,
+    /// Session that composed the message.
+    pub session_id: String,
+    /// Delivery channel (e.g. "whatsapp").
+    pub channel: String,
+    /// Channel-specific recipient (e.g. WhatsApp JID).
+    pub recipient: String,
+    /// Already composed and redacted message text.
+    pub message_text: String,
+    /// Scheduled delivery time (UTC, `YYYY-MM-DD HH:MM:SS`).
+    pub deliver_at: String,
+    /// Delivery attempts made so far.
+    pub attempts: i64,
+}
+
+impl From<QueueRow> for QueuedMessage {
+    fn from(row: QueueRow) -> Self {
+        Self {
+            id: row.0,
+            brief_id: row.1,
+            session_id: row.2,
+            channel: row.3,
+            recipient: row.4,
+            message_text: row.5,
+            deliver_at: row.6,
+            attempts: row.7,
+        }
+    }
+}
+
+/// Format a UTC timestamp the way the queue stores it.
+pub fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format(TIMESTAMP_FORMAT).to_string()
+}
+
+/// Queue a composed message for delivery at `deliver_at`.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn enqueue(
+    db: &SqlitePool,
+    brief_id: Option<&str>,
+    session_id: &str,
+    channel: &str,
+    recipient: &str,
+    message_text: &str,
+    deliver_at: DateTime<Utc>,
+) -> Result<i64, MessagingError> {
+    let result = sqlx::query(
+        "INSERT INTO outbound_queue (brief_id, session_id, channel, recipient, \
+         message_text, deliver_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )
+    .bind(brief_id)
+    .bind(session_id)
+    .bind(channel)
+    .bind(recipient)
+    .bind(message_text)
+    .bind(format_timestamp(deliver_at))
+    .execute(db)
+    .await?;
+
+    let id = result.last_insert_rowid();
+    trace!(id, channel, "outbound message queued");
+    Ok(id)
+}
+
+/// Load queued messages whose delivery time is at or before `now`.
+///
+/// Messages are returned oldest first so a contact receives them in the
+/// order they were composed.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn due_messages(
+    db: &SqlitePool,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<QueuedMessage>, MessagingError> {
+    let rows: Vec<QueueRow> = sqlx::query_as(
+        "SELECT id, brief_id, session_id, channel, recipient, message_text, deliver_at, \
+         attempts FROM outbound_queue WHERE status = 'queued' AND deliver_at <= ?1 \
+         ORDER BY deliver_at, id LIMIT ?2",
+    )
+    .bind(format_timestamp(now))
+    .bind(limit)
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(QueuedMessage::from).collect())
+}
+
+/// Count messages still waiting for delivery.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn pending_count(db: &SqlitePool) -> Result<i64, MessagingError> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM outbound_queue WHERE status = 'queued'")
+            .fetch_one(db)
+            .await?;
+    Ok(count)
+}
+
+/// Mark a queued message as delivered.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn mark_sent(db: &SqlitePool, id: i64) -> Result<(), MessagingError> {
+    sqlx::query(
+        "UPDATE outbound_queue SET status = 'sent', attempts = attempts + 1, \
+         sent_at = datetime('now') WHERE id = ?1",
+    )
+    .bind(id)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Record a failed delivery attempt.
+///
+/// The message stays queued for the next heartbeat until [`MAX_ATTEMPTS`]
+/// is reached, after which it is marked failed. Returns `true` when the
+/// message was given up on.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn record_failure(db: &SqlitePool, id: i64, error: &str) -> Result<bool, MessagingError> {
+    let (status,): (String,) = sqlx::query_as(
+        "UPDATE outbound_queue SET attempts = attempts + 1, last_error = ?2, \
+         status = CASE WHEN attempts + 1 >= ?3 THEN 'failed' ELSE status END \
+         WHERE id = ?1 RETURNING status",
+    )
+    .bind(id)
+    .bind(error)
+    .bind(MAX_ATTEMPTS)
+    .fetch_one(db)
+    .await?;
+
+    let gave_up = status == "failed";
+    debug!(id, gave_up, "queued outbound delivery failed");
+    Ok(gave_up)
+}
+
+/// Cancel a queued message without sending it.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn cancel(db: &SqlitePool, id: i64, reason: &str) -> Result<(), MessagingError> {
+    sqlx::query(
+        "UPDATE outbound_queue SET status = 'cancelled', last_error = ?2 \
+         WHERE id = ?1 AND status = 'queued'",
+    )
+    .bind(id)
+    .bind(reason)
+    .execute(db)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! Quiet hours for outbound messages.
+//!
+//! A quiet window is a daily local-time range (e.g. `22:00-08:00`) during
+//! which nothing is sent to a contact. Messages composed inside the window
+//! are queued and released shortly after it ends, at a randomized offset so
+//! deferred replies do not all land on the same second. Per-contact windows
+//! override the global one; urgent sends may bypass the window explicitly.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use rand::Rng;
+
+use crate::config::MessagingConfig;
+
+use super::contacts::Contact;
+use super::MessagingError;
+
+/// Minimum delay after a quiet window ends before a queued message is sent.
+const MIN_RELEASE_JITTER_SECS: i64 = 60;
+
+/// Maximum delay after a quiet window ends before a queued message is sent.
+const MAX_RELEASE_JITTER_SECS: i64 = 1_200;
+
+/// Value that disables quiet hours for a single contact.
+const DISABLED: &str = "off";
+
+/// A daily quiet window in local wall-clock time. May wrap past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietWindow {
+    /// Time the window opens.
+    pub start: NaiveTime,
+    /// Time the window closes (exclusive).
+    pub end: NaiveTime,
+}
+
+impl QuietWindow {
+    /// Parse a window from `HH:MM-HH:MM`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MessagingError::InvalidQuietHours`] if the spec is malformed
+    /// or the start and end times are equal.
+    pub fn parse(spec: &str) -> Result<Self, MessagingError> {
+        let invalid = || MessagingError::InvalidQuietHours(spec.to_owned());
+        let (start, end) = spec.split_once('-').ok_or_else(invalid)?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+        if start == end {
+            return Err(invalid());
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether `time` falls inside the window.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// The first moment strictly after `now` at which the window closes.
+    pub fn next_end(&self, now: NaiveDateTime) -> NaiveDateTime {
+        let today = now.date().and_time(self.end);
+        if today > now {
+            today
+        } else {
+            today
+                .checked_add_signed(chrono::Duration::days(1))
+                .unwrap_or(today)
+        }
+    }
+}
+
+/// Parse a quiet-hours setting, treating `"off"` as no window.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidQuietHours`] for malformed windows.
+pub fn parse_setting(spec: &str) -> Result<Option<QuietWindow>, MessagingError> {
+    if spec.trim().eq_ignore_ascii_case(DISABLED) {
+        Ok(None)
+    } else {
+        QuietWindow::parse(spec).map(Some)
+    }
+}
+
+/// Global quiet window plus per-contact overrides.
+#[derive(Debug, Clone, Default)]
+pub struct QuietHoursPolicy {
+    global: Option<QuietWindow>,
+    /// Lower-cased contact name or alias -> window (`None` disables).
+    contacts: HashMap<String, Option<QuietWindow>>,
+}
+
+impl QuietHoursPolicy {
+    /// Create a policy with only a global window.
+    pub fn new(global: Option<QuietWindow>) -> Self {
+        Self {
+            global,
+            contacts: HashMap::new(),
+        }
+    }
+
+    /// Add a per-contact override keyed by contact name or alias.
+    #[must_use]
+    pub fn with_contact(mut self, name: &str, window: Option<QuietWindow>) -> Self {
+        self.contacts.insert(name.trim().to_lowercase(), window);
+        self
+    }
+
+    /// Build the policy from the `[messaging]` section of agent.toml.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MessagingError::InvalidQuietHours`] if any window is malformed.
+    pub fn from_config(config: &MessagingConfig) -> Result<Self, MessagingError> {
+        let global = match config.quiet_hours.as_deref() {
+            Some(spec) => parse_setting(spec)?,
+            None => None,
+        };
+        let mut policy = Self::new(global);
+        for (name, spec) in &config.contact_quiet_hours {
+            policy = policy.with_contact(name, parse_setting(spec)?);
+        }
+        Ok(policy)
+    }
+
+    /// Resolve the window that applies to a contact.
+    ///
+    /// The contact's name is checked first, then its aliases; the global
+    /// window applies when neither has an override.
+    pub fn window_for(&self, contact: &Contact, aliases: &[String]) -> Option<QuietWindow> {
+        std::iter::once(contact.name.as_str())
+            .chain(aliases.iter().map(String::as_str))
+            .find_map(|key| self.contacts.get(&key.trim().to_lowercase()))
+            .copied()
+            .unwrap_or(self.global)
+    }
+}
+
+/// How an outbound message should be delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryPlan {
+    /// Outside quiet hours: send now.
+    Immediate,
+    /// Inside quiet hours: hold until the window closes (local time).
+    Deferred {
+        /// When the quiet window closes.
+        window_ends: NaiveDateTime,
+    },
+    /// Inside quiet hours but flagged urgent: send now and record the override.
+    Override,
+}
+
+impl DeliveryPlan {
+    /// Label stored in `outbound_log.delivery`.
+    pub fn audit_label(&self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::Deferred { .. } => "queued",
+            Self::Override => "quiet_hours_override",
+        }
+    }
+}
+
+/// Decide how a message composed at local time `now` should be delivered.
+pub fn plan_delivery(
+    window: Option<QuietWindow>,
+    now: NaiveDateTime,
+    urgent: bool,
+) -> DeliveryPlan {
+    match window {
+        Some(w) if w.contains(now.time()) => {
+            if urgent {
+                DeliveryPlan::Override
+            } else {
+                DeliveryPlan::Deferred {
+                    window_ends: w.next_end(now),
+                }
+            }
+        }
+        _ => DeliveryPlan::Immediate,
+    }
+}
+
+/// Pick a randomized release time shortly after a quiet window closes.
+pub fn release_time(window_ends: NaiveDateTime) -> NaiveDateTime {
+    let jitter = rand::thread_rng().gen_range(MIN_RELEASE_JITTER_SECS..=MAX_RELEASE_JITTER_SECS);
+    window_ends
+        .checked_add_signed(chrono::Duration::seconds(jitter))
+        .unwrap_or(window_ends)
+}
+
+/// Convert a local wall-clock time to UTC.
+///
+/// Times that fall into a DST gap resolve to the first valid instant after
+/// it; if that also fails, the current time is returned.
+pub fn local_to_utc(local: NaiveDateTime) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            local
+                .checked_add_signed(chrono::Duration::hours(1))
+                .and_then(|shifted| Local.from_local_datetime(&shifted).earliest())
+        })
+        .map_or_else(Utc::now, |dt| dt.with_timezone(&Utc))
+}
@@ -0,0 +1,262 @@
+//! Reusable outbound message templates.
+//!
+//! A template is plain text with `{variable}` placeholders. Placeholders are
+//! filled from the brief, the recipient's contact card, and values supplied
+//! by the agent, so routine messages (scheduling follow-ups, reminders) can
+//! be sent without an LLM call. Braces that do not enclose a plain
+//! identifier are left as-is.
+
+use std::collections::HashMap;
+
+use sqlx::SqlitePool;
+use tracing::trace;
+
+use super::brief::TaskBrief;
+use super::contacts::Contact;
+use super::MessagingError;
+
+/// Maximum template name length.
+const MAX_NAME_LEN: usize = 40;
+
+/// Maximum template body length in characters.
+const MAX_BODY_CHARS: usize = 4_000;
+
+/// A stored message template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTemplate {
+    /// Database ID.
+    pub id: i64,
+    /// Unique name (case-insensitive).
+    pub name: String,
+    /// Template text with `{variable}` placeholders.
+    pub body: String,
+}
+
+/// Placeholder names in `body`, in order of first appearance.
+pub fn placeholders(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in segments(body) {
+        if let Segment::Placeholder(name) = segment {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_owned());
+            }
+        }
+    }
+    names
+}
+
+/// Variables derived from the brief and the recipient's contact card.
+///
+/// Provides `contact_name`, `first_name`, `organization` (when known), and
+/// `objective`.
+pub fn builtin_variables(brief: &TaskBrief, contact: Option<&Contact>) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    vars.insert("objective".to_owned(), brief.objective.clone());
+    if let Some(contact) = contact {
+        vars.insert("contact_name".to_owned(), contact.name.clone());
+        if let Some(first) = contact.name.split_whitespace().next() {
+            vars.insert("first_name".to_owned(), first.to_owned());
+        }
+        if let Some(ref org) = contact.organization {
+            vars.insert("organization".to_owned(), org.clone());
+        }
+    }
+    vars
+}
+
+/// Substitute every placeholder in `body`.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidTemplate`] listing every placeholder
+/// without a value.
+pub fn render(body: &str, vars: &HashMap<String, String>) -> Result<String, MessagingError> {
+    let mut out = String::with_capacity(body.len());
+    let mut missing: Vec<&str> = Vec::new();
+    for segment in segments(body) {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Placeholder(name) => match vars.get(name) {
+                Some(value) => out.push_str(value),
+                None => {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+            },
+        }
+    }
+    if missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(MessagingError::InvalidTemplate(format!(
+            "missing values for: {}",
+            missing.join(", ")
+        )))
+    }
+}
+
+/// Fill a template for a brief and contact.
+///
+/// Brief and contact fields take precedence over `extra`, so the agent
+/// cannot change who a message is addressed to by passing its own
+/// `contact_name`.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidTemplate`] if any placeholder has no value.
+pub fn fill(
+    template: &MessageTemplate,
+    brief: &TaskBrief,
+    contact: Option<&Contact>,
+    extra: &HashMap<String, String>,
+) -> Result<String, MessagingError> {
+    let mut vars = extra.clone();
+    vars.extend(builtin_variables(brief, contact));
+    render(&template.body, &vars)
+}
+
+/// Validate a template name: lowercase letters, digits, `_` and `-`.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidTemplate`] for empty, overlong, or
+/// malformed names.
+pub fn validate_name(name: &str) -> Result<(), MessagingError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(MessagingError::InvalidTemplate(format!(
+            "name must be 1-{MAX_NAME_LEN} chars of a-z, 0-9, _ or -: {name}"
+        )))
+    }
+}
+
+/// Create or replace a template by name. Returns the template ID.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::InvalidTemplate`] for a bad name or body, or
+/// [`MessagingError::Database`] on SQLite failure.
+pub async fn save_template(db: &SqlitePool, name: &str, body: &str) -> Result<i64, MessagingError> {
+    let name = name.trim().to_lowercase();
+    validate_name(&name)?;
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(MessagingError::InvalidTemplate(
+            "template body is empty".to_owned(),
+        ));
+    }
+    if body.chars().count() > MAX_BODY_CHARS {
+        return Err(MessagingError::InvalidTemplate(format!(
+            "template body exceeds {MAX_BODY_CHARS} characters"
+        )));
+    }
+
+    let (id,): (i64,) = sqlx::query_as(
+        "INSERT INTO message_templates (name, body) VALUES (?1, ?2) \
+         ON CONFLICT(name) DO UPDATE SET body = excluded.body, updated_at = datetime('now') \
+         RETURNING id",
+    )
+    .bind(&name)
+    .bind(body)
+    .fetch_one(db)
+    .await?;
+
+    trace!(id, name = %name, "message template saved");
+    Ok(id)
+}
+
+/// Load a template by name.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::TemplateNotFound`] if no template matches, or
+/// [`MessagingError::Database`] on SQLite failure.
+pub async fn load_template(db: &SqlitePool, name: &str) -> Result<MessageTemplate, MessagingError> {
+    let row: Option<(i64, String, String)> =
+        sqlx::query_as("SELECT id, name, body FROM message_templates WHERE name = ?1")
+            .bind(name.trim())
+            .fetch_optional(db)
+            .await?;
+
+    row.map(|(id, name, body)| MessageTemplate { id, name, body })
+        .ok_or_else(|| MessagingError::TemplateNotFound(name.trim().to_owned()))
+}
+
+/// List all templates ordered by name.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn list_templates(db: &SqlitePool) -> Result<Vec<MessageTemplate>, MessagingError> {
+    let rows: Vec<(i64, String, String)> =
+        sqlx::query_as("SELECT id, name, body FROM message_templates ORDER BY name")
+            .fetch_all(db)
+            .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, name, body)| MessageTemplate { id, name, body })
+        .collect())
+}
+
+/// Delete a template by name. Returns `false` if it did not exist.
+///
+/// # Errors
+///
+/// Returns [`MessagingError::Database`] on SQLite failure.
+pub async fn delete_template(db: &SqlitePool, name: &str) -> Result<bool, MessagingError> {
+    let result = sqlx::query("DELETE FROM message_templates WHERE name = ?1")
+        .bind(name.trim())
+        .execute(db)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// A piece of template text.
+enum Segment<'a> {
+    /// Text copied verbatim.
+    Literal(&'a str),
+    /// A `{name}` placeholder (name only).
+    Placeholder(&'a str),
+}
+
+/// Split a template body into literal text and placeholders.
+fn segments(body: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    let mut rest = body;
+    while let Some(open) = rest.find('{') {
+        let after = rest.get(open.saturating_add(1)..).unwrap_or_default();
+        let Some(close) = after.find('}') else {
+            break;
+        };
+        let name = after.get(..close).unwrap_or_default();
+        if is_identifier(name) {
+            out.push(Segment::Literal(rest.get(..open).unwrap_or_default()));
+            out.push(Segment::Placeholder(name));
+            rest = after.get(close.saturating_add(1)..).unwrap_or_default();
+        } else {
+            // Not a placeholder: keep the brace and continue after it.
+            let keep = open.saturating_add(1);
+            out.push(Segment::Literal(rest.get(..keep).unwrap_or_default()));
+            rest = rest.get(keep..).unwrap_or_default();
+        }
+    }
+    out.push(Segment::Literal(rest));
+    out
+}
+
+/// Whether `s` is a plain identifier (`[A-Za-z_][A-Za-z0-9_]*`).
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
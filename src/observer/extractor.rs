@@ -10,6 +10,7 @@ use tracing::{debug, warn};
 use crate::agent::budget::DailyBudget;
 use crate::executor::redactor::Redactor;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{extract_text, CompletionRequest, Message, MessageContent, Role};
 
 /// Estimated tokens per observer extraction call (for budget pre-check).
@@ -18,6 +19,9 @@ const ESTIMATED_EXTRACTION_TOKENS: u64 = 500;
 /// Minimum confidence threshold for keeping an extraction.
 const MIN_CONFIDENCE: f64 = 0.5;
 
+/// Maximum characters kept from an extraction's source span.
+const MAX_SOURCE_SPAN_CHARS: usize = 200;
+
 /// Kind of extracted information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -39,6 +43,9 @@ pub struct Extraction {
     pub content: String,
     /// Confidence score (0.0–1.0) from the LLM.
     pub confidence: f64,
+    /// Short verbatim quote from the conversation the extraction is based on.
+    #[serde(default)]
+    pub source_span: Option<String>,
 }
 
 /// System prompt for the observer extraction model.
@@ -49,7 +56,31 @@ Each extraction must be an object with these fields:
 - \"kind\": one of \"fact\", \"procedure\", or \"preference\"
 - \"content\": a concise, self-contained description of the learned information
 - \"confidence\": a float between 0.0 and 1.0 indicating how confident you are
+- \"source_span\": the short verbatim quote from the conversation that supports it
+
+Score confidence honestly: 0.9+ only when the user stated it directly,
+around 0.6 when it is inferred from context.
+
+Only extract genuinely useful, non-obvious information. Be conservative.
+Do not extract greetings, small talk, or trivial observations.
+Output ONLY the JSON array, no other text. If nothing is worth extracting, output [].";
+
+/// System prompt for extracting from several conversations in one call.
+const BATCH_EXTRACTION_SYSTEM_PROMPT: &str = "\
+You are an observer that extracts learnable facts and procedures from conversations.
+The input holds several separate conversations, each under a header like
+=== Conversation 1 ===. Analyze each one on its own and output a single JSON
+array of extractions. Each extraction must be an object with these fields:
+- \"conversation\": the number of the conversation it comes from
+- \"kind\": one of \"fact\", \"procedure\", or \"preference\"
+- \"content\": a concise, self-contained description of the learned information
+- \"confidence\": a float between 0.0 and 1.0 indicating how confident you are
+- \"source_span\": the short verbatim quote from the conversation that supports it
+
+Score confidence honestly: 0.9+ only when the user stated it directly,
+around 0.6 when it is inferred from context.
 
+Never combine information from different conversations into one extraction.
 Only extract genuinely useful, non-obvious information. Be conservative.
 Do not extract greetings, small talk, or trivial observations.
 Output ONLY the JSON array, no other text. If nothing is worth extracting, output [].";
@@ -93,10 +124,12 @@ pub async fn extract(
         stop_sequences: vec![],
     };
 
-    let response = provider
-        .complete(request)
-        .await
-        .context("observer LLM call failed")?;
+    let response = usage::scoped(
+        UsageScope::current_with_role("observer"),
+        provider.complete(request),
+    )
+    .await
+    .context("observer LLM call failed")?;
 
     // Record actual token usage.
     let total = u64::from(response.usage.input_tokens)
@@ -118,11 +151,138 @@ pub async fn extract(
     parse_extractions(&redacted)
 }
 
+/// Extract from several small conversations in one LLM call.
+///
+/// Each conversation is rendered as a labelled transcript in a single user
+/// message, and the model tags every extraction with the conversation it
+/// came from. Returns `(index into conversations, extraction)` pairs.
+/// Budget and redaction are handled as in [`extract`].
+///
+/// # Errors
+///
+/// Returns an error if the provider is unavailable, budget is exceeded,
+/// or the LLM call fails.
+pub async fn extract_batch(
+    conversations: &[&[Message]],
+    router: &ModelRouter,
+    redactor: &Redactor,
+    daily_budget: &DailyBudget,
+) -> anyhow::Result<Vec<(usize, Extraction)>> {
+    let provider = router
+        .resolve(Some("observer"), None)
+        .context("failed to resolve observer model")?;
+
+    daily_budget
+        .check(ESTIMATED_EXTRACTION_TOKENS)
+        .context("observer budget exceeded")?;
+
+    debug!(
+        model = %provider.model_id(),
+        conversations = conversations.len(),
+        "observer batch extraction starting"
+    );
+
+    let request = CompletionRequest {
+        messages: vec![user_message(&render_batch(conversations))],
+        system: Some(BATCH_EXTRACTION_SYSTEM_PROMPT.to_owned()),
+        tools: vec![],
+        max_tokens: Some(2048),
+        stop_sequences: vec![],
+    };
+
+    let response = usage::scoped(
+        UsageScope::current_with_role("observer"),
+        provider.complete(request),
+    )
+    .await
+    .context("observer LLM call failed")?;
+
+    let total = u64::from(response.usage.input_tokens)
+        .saturating_add(u64::from(response.usage.output_tokens));
+    daily_budget.record(total);
+
+    let response_text = extract_text(&response.content);
+    if response_text.is_empty() {
+        debug!("observer received empty response");
+        return Ok(Vec::new());
+    }
+
+    // Redact before parsing (security invariant #7).
+    let redacted = redactor.redact(&response_text);
+    Ok(parse_batch_extractions(&redacted, conversations.len()))
+}
+
+/// Render conversations as numbered plain-text transcripts.
+///
+/// Only message text is kept; tool calls and results are left out.
+pub fn render_batch(conversations: &[&[Message]]) -> String {
+    let mut out = String::new();
+    for (index, messages) in conversations.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "=== Conversation {} ===\n",
+            index.saturating_add(1)
+        ));
+        for message in messages.iter() {
+            let text = message.content.text();
+            if text.trim().is_empty() {
+                continue;
+            }
+            let role = match message.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+                Role::System => "system",
+                Role::Tool => "tool",
+            };
+            out.push_str(&format!("{role}: {}\n", text.trim()));
+        }
+    }
+    out
+}
+
 /// Parse extraction JSON, filtering by confidence threshold.
 ///
 /// Returns an empty vec on any parse error (logged as warning).
 pub fn parse_extractions(text: &str) -> anyhow::Result<Vec<Extraction>> {
-    // Try to find JSON array in the response (LLM may include extra text).
+    let extractions: Vec<Extraction> = parse_json_array(text);
+    let filtered: Vec<Extraction> = extractions.into_iter().filter_map(normalise).collect();
+
+    debug!(count = filtered.len(), "observer parsed extractions");
+    Ok(filtered)
+}
+
+/// An extraction tagged with its 1-based conversation number.
+#[derive(Debug, Deserialize)]
+struct BatchExtraction {
+    conversation: usize,
+    #[serde(flatten)]
+    extraction: Extraction,
+}
+
+/// Parse batch extraction JSON into `(conversation index, extraction)` pairs.
+///
+/// Extractions naming a conversation outside `1..=count` are dropped, as
+/// are those below the confidence threshold. Returns an empty vec on any
+/// parse error (logged as warning).
+pub fn parse_batch_extractions(text: &str, count: usize) -> Vec<(usize, Extraction)> {
+    let extractions: Vec<BatchExtraction> = parse_json_array(text);
+    let filtered: Vec<(usize, Extraction)> = extractions
+        .into_iter()
+        .filter(|e| (1..=count).contains(&e.conversation))
+        .filter_map(|e| {
+            let index = e.conversation.saturating_sub(1);
+            normalise(e.extraction).map(|extraction| (index, extraction))
+        })
+        .collect();
+
+    debug!(count = filtered.len(), "observer parsed batch extractions");
+    filtered
+}
+
+/// Parse the JSON array in a model response (which may include extra text).
+fn parse_json_array<T: serde::de::DeserializeOwned>(text: &str) -> Vec<T> {
     let trimmed = text.trim();
     let json_text = if let Some(start) = trimmed.find('[') {
         if let Some(end) = trimmed.rfind(']') {
@@ -134,7 +294,7 @@ pub fn parse_extractions(text: &str) -> anyhow::Result<Vec<Extraction>> {
         trimmed
     };
 
-    let extractions: Vec<Extraction> = match serde_json::from_str(json_text) {
+    match serde_json::from_str(json_text) {
         Ok(e) => e,
         Err(e) => {
             warn!(
@@ -142,19 +302,22 @@ pub fn parse_extractions(text: &str) -> anyhow::Result<Vec<Extraction>> {
                 text_preview = &text[..text.len().min(200)],
                 "observer failed to parse extraction JSON"
             );
-            return Ok(Vec::new());
+            Vec::new()
         }
-    };
-
-    // Filter by confidence threshold.
-    let filtered: Vec<Extraction> = extractions
-        .into_iter()
-        .filter(|e| e.confidence >= MIN_CONFIDENCE)
-        .filter(|e| !e.content.is_empty())
-        .collect();
+    }
+}
 
-    debug!(count = filtered.len(), "observer parsed extractions");
-    Ok(filtered)
+/// Apply the confidence threshold and normalise provenance.
+fn normalise(mut e: Extraction) -> Option<Extraction> {
+    if e.confidence < MIN_CONFIDENCE || e.content.is_empty() {
+        return None;
+    }
+    e.confidence = e.confidence.min(1.0);
+    e.source_span = e
+        .source_span
+        .map(|span| span.trim().chars().take(MAX_SOURCE_SPAN_CHARS).collect())
+        .filter(|span: &String| !span.is_empty());
+    Some(e)
 }
 
 /// Build a simple user message from text.
@@ -0,0 +1,72 @@
+//! Feedback loop: fold user ratings into memory confidence.
+//!
+//! Rated turns recorded by the agent loop are drained here on each observer
+//! wake-up. Every memory that fed a rated turn has its confidence nudged up or
+//! down; memories that keep leading to bad answers are archived by the writer
+//! once they cross the thresholds in [`crate::memory::feedback`].
+
+use tracing::{debug, info};
+
+use crate::memory::feedback::FeedbackRating;
+use crate::memory::{MemoryEngine, MemoryError};
+
+/// Maximum rated turns applied per observer wake-up.
+const MAX_FEEDBACK_PER_RUN: usize = 50;
+
+/// Summary of a feedback application pass.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FeedbackSummary {
+    /// Rated turns applied.
+    pub turns: usize,
+    /// Positive ratings among them.
+    pub positive: usize,
+    /// Negative ratings among them.
+    pub negative: usize,
+    /// Memory confidence adjustments queued.
+    pub adjustments: usize,
+}
+
+/// Apply all pending user ratings to the memories they reference.
+///
+/// # Errors
+///
+/// Returns [`MemoryError`] if pending feedback cannot be read or the writer
+/// actor has stopped.
+pub async fn apply_pending_feedback(memory: &MemoryEngine) -> Result<FeedbackSummary, MemoryError> {
+    let pending = memory.pending_feedback(MAX_FEEDBACK_PER_RUN).await?;
+    let mut summary = FeedbackSummary::default();
+
+    for item in &pending {
+        memory.apply_feedback(item).await?;
+        summary.turns = summary.turns.saturating_add(1);
+        summary.adjustments = summary
+            .adjustments
+            .saturating_add(item.turn.memory_ids.len());
+        match item.rating {
+            FeedbackRating::Up => {
+                summary.positive = summary.positive.saturating_add(1);
+            }
+            FeedbackRating::Down => {
+                summary.negative = summary.negative.saturating_add(1);
+            }
+        }
+        debug!(
+            turn_id = %item.turn.turn_id,
+            rating = item.rating.as_i64(),
+            tools = ?item.turn.tools_used,
+            "applied turn feedback"
+        );
+    }
+
+    if summary.turns > 0 {
+        info!(
+            turns = summary.turns,
+            positive = summary.positive,
+            negative = summary.negative,
+            adjustments = summary.adjustments,
+            "observer applied user feedback"
+        );
+    }
+
+    Ok(summary)
+}
@@ -2,13 +2,20 @@
 //!
 //! Receives conversation snapshots from idle sessions, extracts facts and
 //! procedures via LLM, and stages them as pending memories for promotion.
+//! Also applies user 👍/👎 feedback to the confidence of memories in use,
+//! and proposes repeated successful tool sequences as skills.
 //!
 //! The observer runs as an independent Tokio task. Sessions signal idle state
-//! by sending [`ObserverEvent`]s through an mpsc channel. The observer uses
-//! a cheap/local model (resolved via the "observer" role) to minimize cost.
+//! by sending [`ObserverEvent`]s through an mpsc channel, which is drained
+//! into a bounded, coalescing [`queue::ObserverQueue`]; small sessions are
+//! extracted together in one call. The observer uses a cheap/local model
+//! (resolved via the "observer" role) to minimize cost.
 
 pub mod extractor;
+pub mod feedback;
+pub mod queue;
 pub mod reflection;
+pub mod skills;
 pub mod staging;
 
 use std::sync::Arc;
@@ -22,8 +29,11 @@ use crate::config::{LearningConfig, PromotionMode};
 use crate::executor::redactor::Redactor;
 use crate::memory::MemoryEngine;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::Message;
 
+use self::queue::{ObserverQueue, PushOutcome};
+
 /// An event sent from a session loop when it goes idle.
 #[derive(Debug, Clone)]
 pub struct ObserverEvent {
@@ -51,62 +61,149 @@ pub struct ObserverDeps {
     pub learning_config: LearningConfig,
     /// Channel for outbound Telegram messages.
     pub telegram_tx: mpsc::Sender<TelegramOutbound>,
+    /// Queue of idle sessions waiting for extraction; shared with the
+    /// heartbeat for queue metrics.
+    pub queue: Arc<ObserverQueue>,
 }
 
-/// Maximum conversation messages to send to the observer model.
-const MAX_OBSERVER_MESSAGES: usize = 20;
-
 /// Run the observer background task.
 ///
-/// Processes [`ObserverEvent`]s from session loops. For each idle session,
-/// extracts facts and procedures via LLM and stages them as pending memories.
-/// Exits when the channel closes.
-pub async fn run_observer(deps: ObserverDeps, mut event_rx: mpsc::Receiver<ObserverEvent>) {
+/// An intake task moves [`ObserverEvent`]s from session loops into
+/// [`ObserverDeps::queue`], coalescing events per session. The worker takes
+/// batches from the queue, extracts facts and procedures via LLM, and stages
+/// them as pending memories. Exits once the channel closes and the queue is
+/// drained.
+pub async fn run_observer(deps: ObserverDeps, event_rx: mpsc::Receiver<ObserverEvent>) {
     info!("observer pipeline started");
 
+    let intake = tokio::spawn(run_intake(Arc::clone(&deps.queue), event_rx));
+
+    while let Some(batch) = deps.queue.next_batch().await {
+        process_batch(&deps, batch).await;
+    }
+
+    if let Err(e) = intake.await {
+        warn!(error = %e, "observer intake task failed");
+    }
+    info!("observer pipeline shut down (channel closed)");
+}
+
+/// Move events from the channel into the queue until the channel closes.
+async fn run_intake(queue: Arc<ObserverQueue>, mut event_rx: mpsc::Receiver<ObserverEvent>) {
     while let Some(event) = event_rx.recv().await {
-        if deps.learning_config.promotion_mode == PromotionMode::Off {
-            debug!(
-                session_id = %event.session_id,
-                "observer skipping extraction (promotion_mode = off)"
-            );
-            continue;
+        let session_id = event.session_id.clone();
+        if queue.push(event) == PushOutcome::Coalesced {
+            debug!(session_id = %session_id, "observer merged idle event into waiting session");
         }
+    }
+    queue.close();
+}
 
-        // Truncate conversation to avoid sending huge context to cheap model.
-        let messages: Vec<Message> = if event.messages.len() > MAX_OBSERVER_MESSAGES {
-            let start = event.messages.len().saturating_sub(MAX_OBSERVER_MESSAGES);
-            event.messages[start..].to_vec()
-        } else {
-            event.messages
-        };
-
-        if messages.is_empty() {
-            debug!(session_id = %event.session_id, "observer skipping empty conversation");
-            continue;
+/// Run skills, extraction, staging, reflection and promotion for one batch.
+async fn process_batch(deps: &ObserverDeps, batch: Vec<ObserverEvent>) {
+    // Fold any user ratings into memory confidence before extracting.
+    if deps.learning_config.feedback {
+        if let Err(e) = feedback::apply_pending_feedback(&deps.memory).await {
+            warn!(error = %e, "observer feedback application failed");
         }
+    }
 
-        // Extract facts and procedures from the conversation.
-        let extractions =
-            match extractor::extract(&messages, &deps.router, &deps.redactor, &deps.daily_budget)
-                .await
+    if deps.learning_config.promotion_mode == PromotionMode::Off {
+        debug!(
+            sessions = batch.len(),
+            "observer skipping extraction (promotion_mode = off)"
+        );
+        return;
+    }
+
+    // Track repeated tool workflows across the full snapshot.
+    if deps.learning_config.skills {
+        for event in &batch {
+            match skills::stage_skills(
+                &event.messages,
+                &deps.memory,
+                &event.session_id,
+                &deps.telegram_tx,
+                event.user_id,
+            )
+            .await
             {
-                Ok(e) => e,
-                Err(e) => {
-                    warn!(
-                        error = %e,
-                        session_id = %event.session_id,
-                        "observer extraction failed"
+                Ok(result) if result.tracked > 0 || result.proposed > 0 => {
+                    info!(
+                        tracked = result.tracked,
+                        proposed = result.proposed,
+                        "observer skill extraction complete"
                     );
-                    continue;
                 }
-            };
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "observer skill extraction failed"),
+            }
+        }
+    }
+
+    let batch: Vec<ObserverEvent> = batch
+        .into_iter()
+        .filter(|event| {
+            let empty = event.messages.is_empty();
+            if empty {
+                debug!(session_id = %event.session_id, "observer skipping empty conversation");
+            }
+            !empty
+        })
+        .collect();
+
+    // Extract facts and procedures, one call for the whole batch.
+    let extracted = match batch.as_slice() {
+        [] => return,
+        [event] => {
+            let scope = UsageScope::session("observer", &event.session_id);
+            usage::scoped(
+                scope,
+                extractor::extract(
+                    &event.messages,
+                    &deps.router,
+                    &deps.redactor,
+                    &deps.daily_budget,
+                ),
+            )
+            .await
+            .map(|extractions| extractions.into_iter().map(|e| (0, e)).collect())
+        }
+        events => {
+            let conversations: Vec<&[Message]> =
+                events.iter().map(|e| e.messages.as_slice()).collect();
+            info!(sessions = events.len(), "observer batch extraction");
+            usage::scoped(
+                UsageScope::role("observer"),
+                extractor::extract_batch(
+                    &conversations,
+                    &deps.router,
+                    &deps.redactor,
+                    &deps.daily_budget,
+                ),
+            )
+            .await
+        }
+    };
+    let extracted: Vec<(usize, extractor::Extraction)> = match extracted {
+        Ok(e) => e,
+        Err(e) => {
+            warn!(error = %e, sessions = batch.len(), "observer extraction failed");
+            return;
+        }
+    };
 
+    let mut staged_any = false;
+    for (index, event) in batch.iter().enumerate() {
+        let extractions: Vec<extractor::Extraction> = extracted
+            .iter()
+            .filter(|(i, _)| *i == index)
+            .map(|(_, e)| e.clone())
+            .collect();
         if extractions.is_empty() {
             debug!(session_id = %event.session_id, "observer found no extractions");
             continue;
         }
-
         info!(
             session_id = %event.session_id,
             count = extractions.len(),
@@ -116,6 +213,7 @@ pub async fn run_observer(deps: ObserverDeps, mut event_rx: mpsc::Receiver<Obser
         // Stage extractions as pending memories.
         match staging::stage_extractions(&extractions, &deps.memory, &event.session_id).await {
             Ok(result) => {
+                staged_any = true;
                 info!(
                     staged = result.staged,
                     duplicates = result.duplicates,
@@ -124,7 +222,7 @@ pub async fn run_observer(deps: ObserverDeps, mut event_rx: mpsc::Receiver<Obser
                 );
             }
             Err(e) => {
-                error!(error = %e, "observer staging failed");
+                error!(error = %e, session_id = %event.session_id, "observer staging failed");
                 continue;
             }
         }
@@ -143,14 +241,23 @@ pub async fn run_observer(deps: ObserverDeps, mut event_rx: mpsc::Receiver<Obser
                 warn!(error = %e, "post-session reflection failed");
             }
         }
+    }
 
-        // Run promotion check if in auto mode.
-        if deps.learning_config.promotion_mode == PromotionMode::Auto {
+    if !staged_any {
+        return;
+    }
+
+    // Run promotion check if in auto mode, notifying each user once.
+    if deps.learning_config.promotion_mode == PromotionMode::Auto {
+        let mut users: Vec<i64> = batch.iter().map(|e| e.user_id).collect();
+        users.sort_unstable();
+        users.dedup();
+        for user_id in users {
             match staging::check_promotions(
                 &deps.memory,
                 &deps.learning_config,
                 &deps.telegram_tx,
-                event.user_id,
+                user_id,
             )
             .await
             {
@@ -168,6 +275,4 @@ pub async fn run_observer(deps: ObserverDeps, mut event_rx: mpsc::Receiver<Obser
             }
         }
     }
-
-    info!("observer pipeline shut down (channel closed)");
 }
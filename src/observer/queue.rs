@@ -0,0 +1,246 @@
+//! Bounded, coalescing queue between idle sessions and the observer.
+//!
+//! Sessions hand idle events to the observer with a non-blocking send, so a
+//! full channel drops them. When many sessions go idle at once while an
+//! extraction call is in flight, an intake task drains the channel into this
+//! queue straight away:
+//!
+//! - An event for a session that is already waiting is merged into the
+//!   waiting one instead of queued again ([`merge_snapshots`]).
+//! - At most `capacity` sessions wait; past that the oldest is dropped.
+//! - [`ObserverQueue::next_batch`] gives the worker either one session or
+//!   several small ones, which are extracted together in one LLM call.
+//!
+//! Counters are reported in the observer component of `health.json`.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+use tracing::warn;
+
+use super::ObserverEvent;
+use crate::providers::Message;
+
+/// Default number of sessions that may wait for extraction.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 64;
+
+/// Most messages kept in a (merged) session snapshot.
+pub const MAX_SNAPSHOT_MESSAGES: usize = 40;
+
+/// Sessions with at most this many messages may share an extraction call.
+pub const SMALL_SESSION_MESSAGES: usize = 8;
+
+/// Most sessions extracted in one call.
+pub const MAX_BATCH_SESSIONS: usize = 5;
+
+/// Most messages across the sessions of one batch.
+pub const MAX_BATCH_MESSAGES: usize = 24;
+
+/// What happened to a pushed event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Queued as a new waiting session.
+    Queued,
+    /// Merged into the waiting event of the same session.
+    Coalesced,
+    /// Queued after dropping the oldest waiting session.
+    DroppedOldest,
+    /// The queue is closed; the event was discarded.
+    Closed,
+}
+
+/// Point-in-time queue counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Sessions waiting for extraction.
+    pub depth: usize,
+    /// Most sessions that may wait.
+    pub capacity: usize,
+    /// Events merged into a waiting session since start.
+    pub coalesced: u64,
+    /// Waiting sessions dropped because the queue was full.
+    pub dropped: u64,
+    /// Extraction calls that covered more than one session.
+    pub batches: u64,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    pending: VecDeque<ObserverEvent>,
+    closed: bool,
+}
+
+/// Coalescing queue of idle sessions waiting for the observer.
+#[derive(Debug)]
+pub struct ObserverQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+    coalesced: AtomicU64,
+    dropped: AtomicU64,
+    batches: AtomicU64,
+}
+
+impl Default for ObserverQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_CAPACITY)
+    }
+}
+
+impl ObserverQueue {
+    /// Create a queue holding at most `capacity` sessions (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+            capacity: capacity.max(1),
+            coalesced: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            batches: AtomicU64::new(0),
+        }
+    }
+
+    /// Add an idle event, merging it with a waiting event of the same session.
+    pub fn push(&self, mut event: ObserverEvent) -> PushOutcome {
+        let Ok(mut state) = self.state.lock() else {
+            return PushOutcome::Closed;
+        };
+        if state.closed {
+            return PushOutcome::Closed;
+        }
+        truncate_snapshot(&mut event.messages);
+
+        let outcome = if let Some(waiting) = state
+            .pending
+            .iter_mut()
+            .find(|e| e.session_id == event.session_id)
+        {
+            coalesce(waiting, event);
+            self.coalesced.fetch_add(1, Ordering::Relaxed);
+            PushOutcome::Coalesced
+        } else if state.pending.len() >= self.capacity {
+            if let Some(oldest) = state.pending.pop_front() {
+                warn!(
+                    session_id = %oldest.session_id,
+                    capacity = self.capacity,
+                    "observer queue full, dropping oldest session"
+                );
+            }
+            state.pending.push_back(event);
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            PushOutcome::DroppedOldest
+        } else {
+            state.pending.push_back(event);
+            PushOutcome::Queued
+        };
+        drop(state);
+        self.notify.notify_one();
+        outcome
+    }
+
+    /// Stop accepting events; [`Self::next_batch`] drains what is left.
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.notify.notify_one();
+    }
+
+    /// Wait for the next batch to extract.
+    ///
+    /// The oldest waiting session always leads. If it is small, later small
+    /// sessions join it up to [`MAX_BATCH_SESSIONS`] and
+    /// [`MAX_BATCH_MESSAGES`]. Returns `None` once closed and empty.
+    pub async fn next_batch(&self) -> Option<Vec<ObserverEvent>> {
+        loop {
+            {
+                let Ok(mut state) = self.state.lock() else {
+                    return None;
+                };
+                if let Some(batch) = take_batch(&mut state.pending) {
+                    if batch.len() > 1 {
+                        self.batches.fetch_add(1, Ordering::Relaxed);
+                    }
+                    return Some(batch);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Current counters.
+    pub fn stats(&self) -> QueueStats {
+        QueueStats {
+            depth: self.state.lock().map(|s| s.pending.len()).unwrap_or(0),
+            capacity: self.capacity,
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            batches: self.batches.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Pop the next batch from the front of `pending`.
+fn take_batch(pending: &mut VecDeque<ObserverEvent>) -> Option<Vec<ObserverEvent>> {
+    let first = pending.pop_front()?;
+    let mut messages = first.messages.len();
+    let mut batch = vec![first];
+    if messages > SMALL_SESSION_MESSAGES {
+        return Some(batch);
+    }
+    let mut index = 0;
+    while index < pending.len() && batch.len() < MAX_BATCH_SESSIONS {
+        let len = pending[index].messages.len();
+        if len <= SMALL_SESSION_MESSAGES && messages.saturating_add(len) <= MAX_BATCH_MESSAGES {
+            if let Some(event) = pending.remove(index) {
+                messages = messages.saturating_add(len);
+                batch.push(event);
+            }
+        } else {
+            index = index.saturating_add(1);
+        }
+    }
+    Some(batch)
+}
+
+/// Merge a newer idle event into the waiting one for the same session.
+fn coalesce(waiting: &mut ObserverEvent, newer: ObserverEvent) {
+    let older = std::mem::take(&mut waiting.messages);
+    waiting.messages = merge_snapshots(older, newer.messages);
+    waiting.user_id = newer.user_id;
+    for tool in newer.tools_modified {
+        if !waiting.tools_modified.contains(&tool) {
+            waiting.tools_modified.push(tool);
+        }
+    }
+}
+
+/// Join two conversation tails of the same session.
+///
+/// Both are tails of one growing conversation, so the newer one usually
+/// starts somewhere inside the older one. The longest suffix of `older`
+/// that is a prefix of `newer` is kept once. The result is capped at
+/// [`MAX_SNAPSHOT_MESSAGES`], keeping the most recent messages.
+pub fn merge_snapshots(older: Vec<Message>, newer: Vec<Message>) -> Vec<Message> {
+    let max_overlap = older.len().min(newer.len());
+    let overlap = (0..=max_overlap)
+        .rev()
+        .find(|&k| older[older.len().saturating_sub(k)..] == newer[..k])
+        .unwrap_or(0);
+    let mut merged = older;
+    merged.extend(newer.into_iter().skip(overlap));
+    truncate_snapshot(&mut merged);
+    merged
+}
+
+fn truncate_snapshot(messages: &mut Vec<Message>) {
+    if messages.len() > MAX_SNAPSHOT_MESSAGES {
+        let excess = messages.len().saturating_sub(MAX_SNAPSHOT_MESSAGES);
+        messages.drain(..excess);
+    }
+}
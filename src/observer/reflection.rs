@@ -11,6 +11,7 @@ use crate::agent::budget::DailyBudget;
 use crate::executor::redactor::Redactor;
 use crate::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{extract_text, CompletionRequest, Message, MessageContent, Role};
 
 /// Estimated tokens per reflection call (for budget pre-check).
@@ -72,10 +73,12 @@ pub async fn reflect_on_tools(
         stop_sequences: vec![],
     };
 
-    let response = provider
-        .complete(request)
-        .await
-        .context("reflection LLM call failed")?;
+    let response = usage::scoped(
+        UsageScope::current_with_role("reflection"),
+        provider.complete(request),
+    )
+    .await
+    .context("reflection LLM call failed")?;
 
     // Record actual token usage.
     let total = u64::from(response.usage.input_tokens)
@@ -0,0 +1,321 @@
+//! Skill extraction from repeated successful tool sequences.
+//!
+//! Each user turn in a conversation snapshot is reduced to the ordered list
+//! of tools the agent called. Turns where every call succeeded and at least
+//! two tools ran become candidate sequences. Candidates are tracked as
+//! pending [`MemoryKind::Skill`] memories keyed by their tool signature; once
+//! a signature has been seen [`MIN_OCCURRENCES`] times it is proposed to the
+//! user with approve/reject buttons. Skills are never auto-promoted.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::agent::{Keyboard, TelegramOutbound};
+use crate::memory::{Memory, MemoryEngine, MemoryError, MemoryKind, MemorySource, MemoryStatus};
+use crate::providers::{ContentPart, Message, MessageContent, Role};
+use crate::telegram::ui::escape_html;
+
+/// Minimum number of tool calls for a sequence to count as a workflow.
+pub const MIN_SEQUENCE_LEN: usize = 2;
+
+/// Sightings required before a sequence is proposed as a skill.
+pub const MIN_OCCURRENCES: u64 = 2;
+
+/// Maximum skill memories scanned when matching signatures.
+const MAX_SKILLS_SCANNED: usize = 200;
+
+/// Metadata key holding the tool signature of a skill memory.
+const SIGNATURE_KEY: &str = "skill_signature";
+
+/// Metadata key holding how many times the sequence has been seen.
+const OCCURRENCES_KEY: &str = "occurrences";
+
+/// One parameterized step of a tool sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillStep {
+    /// Tool name.
+    pub tool: String,
+    /// Input parameter names, sorted (values become placeholders).
+    pub params: Vec<String>,
+}
+
+/// An ordered, fully successful sequence of tool calls from one turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolSequence {
+    /// Steps in call order.
+    pub steps: Vec<SkillStep>,
+}
+
+impl ToolSequence {
+    /// Stable signature used to recognise the same workflow across sessions.
+    pub fn signature(&self) -> String {
+        self.steps
+            .iter()
+            .map(|s| s.tool.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+
+    /// Render the sequence as skill memory content with parameter placeholders.
+    pub fn render(&self) -> String {
+        let mut lines = vec![format!("Skill: {}", self.signature())];
+        for (i, step) in self.steps.iter().enumerate() {
+            let params = step
+                .params
+                .iter()
+                .map(|p| format!("{p}={{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("{}. {}({params})", i.saturating_add(1), step.tool));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Result of a skill extraction pass.
+#[derive(Debug, Default)]
+pub struct SkillResult {
+    /// Newly tracked candidate sequences.
+    pub tracked: usize,
+    /// Candidates that crossed the threshold and were proposed.
+    pub proposed: usize,
+}
+
+/// Split a conversation into turns and return the successful tool sequences.
+///
+/// A turn starts at each user message carrying text. Turns with any failed
+/// tool result, or fewer than [`MIN_SEQUENCE_LEN`] calls, are dropped.
+pub fn extract_tool_sequences(messages: &[Message]) -> Vec<ToolSequence> {
+    let mut sequences = Vec::new();
+    let mut steps: Vec<SkillStep> = Vec::new();
+    let mut failed = false;
+
+    let mut finish = |steps: &mut Vec<SkillStep>, failed: &mut bool| {
+        if !*failed && steps.len() >= MIN_SEQUENCE_LEN {
+            sequences.push(ToolSequence {
+                steps: std::mem::take(steps),
+            });
+        }
+        steps.clear();
+        *failed = false;
+    };
+
+    for msg in messages {
+        let parts = match &msg.content {
+            MessageContent::Text(_) => {
+                if msg.role == Role::User {
+                    finish(&mut steps, &mut failed);
+                }
+                continue;
+            }
+            MessageContent::Parts(parts) => parts,
+        };
+
+        if msg.role == Role::User && parts.iter().any(|p| matches!(p, ContentPart::Text { .. })) {
+            finish(&mut steps, &mut failed);
+        }
+
+        for part in parts {
+            match part {
+                ContentPart::ToolUse { name, input, .. } => {
+                    let mut params: Vec<String> = input
+                        .as_object()
+                        .map(|o| o.keys().cloned().collect())
+                        .unwrap_or_default();
+                    params.sort();
+                    steps.push(SkillStep {
+                        tool: name.clone(),
+                        params,
+                    });
+                }
+                ContentPart::ToolResult { is_error: true, .. } => failed = true,
+                _ => {}
+            }
+        }
+    }
+    finish(&mut steps, &mut failed);
+
+    sequences
+}
+
+/// Whether a memory is a skill candidate not yet seen often enough to propose.
+///
+/// Such candidates stay pending but are hidden from review listings.
+pub fn is_unripe_skill(mem: &Memory) -> bool {
+    mem.kind == MemoryKind::Skill
+        && mem.status == MemoryStatus::Pending
+        && skill_signature(mem).is_some()
+        && occurrences(mem) < MIN_OCCURRENCES
+}
+
+/// Track tool sequences from a snapshot and propose repeated ones as skills.
+///
+/// Sequences matching an active skill are ignored. Matching pending
+/// candidates have their sighting count bumped through the memory writer;
+/// new sequences are saved as pending skill memories. A proposal is sent
+/// when a candidate first reaches [`MIN_OCCURRENCES`].
+///
+/// # Errors
+///
+/// Returns an error if memory operations fail.
+pub async fn stage_skills(
+    messages: &[Message],
+    memory: &MemoryEngine,
+    session_id: &str,
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+) -> anyhow::Result<SkillResult> {
+    let sequences = extract_tool_sequences(messages);
+    let mut result = SkillResult::default();
+    if sequences.is_empty() {
+        return Ok(result);
+    }
+
+    // Count sightings per signature within this snapshot.
+    let mut counts: HashMap<String, (ToolSequence, u64)> = HashMap::new();
+    for seq in sequences {
+        let entry = counts.entry(seq.signature()).or_insert_with(|| (seq, 0));
+        entry.1 = entry.1.saturating_add(1);
+    }
+
+    let mut known = memory
+        .search_by_status(MemoryStatus::Active, MAX_SKILLS_SCANNED)
+        .await
+        .context("failed to load active memories")?;
+    known.extend(
+        memory
+            .search_by_status(MemoryStatus::Pending, MAX_SKILLS_SCANNED)
+            .await
+            .context("failed to load pending memories")?,
+    );
+
+    for (signature, (seq, seen)) in counts {
+        let existing = known.iter().find(|m| {
+            m.kind == MemoryKind::Skill && skill_signature(m) == Some(signature.as_str())
+        });
+
+        match existing {
+            Some(mem) if mem.status == MemoryStatus::Active => {
+                debug!(%signature, "tool sequence already an active skill");
+            }
+            Some(mem) => {
+                let Some(id) = mem.id else { continue };
+                let before = occurrences(mem);
+                let after = before.saturating_add(seen);
+                memory
+                    .merge_metadata(id, serde_json::json!({ OCCURRENCES_KEY: after }))
+                    .await
+                    .context("failed to update skill occurrences")?;
+                if before < MIN_OCCURRENCES && after >= MIN_OCCURRENCES {
+                    propose_skill(telegram_tx, user_id, id, &mem.content, &signature).await;
+                    result.proposed = result.proposed.saturating_add(1);
+                }
+            }
+            None => {
+                let content = seq.render();
+                let mem = Memory {
+                    id: None,
+                    kind: MemoryKind::Skill,
+                    content: content.clone(),
+                    metadata: Some(serde_json::json!({
+                        "session_id": session_id,
+                        SIGNATURE_KEY: signature,
+                        OCCURRENCES_KEY: seen,
+                    })),
+                    status: MemoryStatus::Pending,
+                    source: MemorySource::Observer,
+                    created_at: None,
+                    updated_at: None,
+                };
+                let id = memory
+                    .save_memory_returning_id(mem)
+                    .await
+                    .context("failed to save skill candidate")?;
+                result.tracked = result.tracked.saturating_add(1);
+                if seen >= MIN_OCCURRENCES {
+                    propose_skill(telegram_tx, user_id, id, &content, &signature).await;
+                    result.proposed = result.proposed.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Send a skill proposal with approve/reject buttons.
+async fn propose_skill(
+    telegram_tx: &mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+    id: i64,
+    content: &str,
+    signature: &str,
+) {
+    let text = format!(
+        "<b>New skill proposed</b>\nI keep running this sequence successfully:\n<pre>{}</pre>\nSave it as a reusable skill?",
+        escape_html(content)
+    );
+    let msg = TelegramOutbound {
+        user_id,
+        text: Some(text),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard: Some(Keyboard::Skill(id)),
+    };
+    if let Err(e) = telegram_tx.send(msg).await {
+        warn!(error = %e, "failed to send skill proposal");
+    } else {
+        info!(id, %signature, "proposed skill from repeated tool sequence");
+    }
+}
+
+/// Approve or reject a skill proposal.
+///
+/// Returns `false`, changing nothing, unless `id` is a pending skill
+/// candidate that has been seen often enough to be proposed.
+///
+/// # Errors
+///
+/// Returns an error if the lookup or status update fails.
+pub async fn resolve_proposal(
+    memory: &MemoryEngine,
+    id: i64,
+    approve: bool,
+) -> Result<bool, MemoryError> {
+    let proposed = memory.get_memory(id).await?.is_some_and(|mem| {
+        mem.kind == MemoryKind::Skill
+            && mem.status == MemoryStatus::Pending
+            && skill_signature(&mem).is_some()
+            && occurrences(&mem) >= MIN_OCCURRENCES
+    });
+    if !proposed {
+        return Ok(false);
+    }
+    let status = if approve {
+        MemoryStatus::Active
+    } else {
+        MemoryStatus::Archived
+    };
+    memory.update_memory_status(id, status).await?;
+    Ok(true)
+}
+
+/// Signature stored in a skill memory's metadata.
+fn skill_signature(mem: &Memory) -> Option<&str> {
+    mem.metadata
+        .as_ref()
+        .and_then(|m| m.get(SIGNATURE_KEY))
+        .and_then(|v| v.as_str())
+}
+
+/// Sighting count stored in a skill memory's metadata.
+fn occurrences(mem: &Memory) -> u64 {
+    mem.metadata
+        .as_ref()
+        .and_then(|m| m.get(OCCURRENCES_KEY))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
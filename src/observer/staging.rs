@@ -84,19 +84,20 @@ pub async fn stage_extractions(
                 sim > 0.4 && sim < 0.9
             });
 
-        let metadata = if has_contradiction {
-            contradictions = contradictions.saturating_add(1);
-            Some(serde_json::json!({
-                "session_id": session_id,
-                "confidence": extraction.confidence,
-                "contradiction": true,
-            }))
-        } else {
-            Some(serde_json::json!({
-                "session_id": session_id,
-                "confidence": extraction.confidence,
-            }))
-        };
+        let mut metadata = serde_json::json!({
+            "session_id": session_id,
+            "confidence": extraction.confidence,
+        });
+        if let Some(obj) = metadata.as_object_mut() {
+            if let Some(ref span) = extraction.source_span {
+                obj.insert("source_span".to_owned(), serde_json::json!(span));
+            }
+            if has_contradiction {
+                contradictions = contradictions.saturating_add(1);
+                obj.insert("contradiction".to_owned(), serde_json::json!(true));
+            }
+        }
+        let metadata = Some(metadata);
 
         let mem = Memory {
             id: None,
@@ -143,7 +144,14 @@ pub async fn check_promotions(
             promoted: 0,
             suggested: 0,
         }),
-        PromotionMode::Auto => auto_promote(memory, config.auto_promote_threshold).await,
+        PromotionMode::Auto => {
+            auto_promote(
+                memory,
+                config.auto_promote_threshold,
+                config.auto_promote_min_confidence,
+            )
+            .await
+        }
         PromotionMode::Suggest => suggest_promote(memory, telegram_tx, user_id).await,
     }
 }
@@ -151,9 +159,14 @@ pub async fn check_promotions(
 /// Auto-promote pending memories that appear consistently.
 ///
 /// Counts pending memories with similar content. When the count reaches
-/// the threshold, promotes to active. Tracks already-promoted IDs to
-/// avoid double-counting similar memories in the same batch.
-async fn auto_promote(memory: &MemoryEngine, threshold: u32) -> anyhow::Result<PromotionResult> {
+/// the threshold, promotes to active. Memories below `min_confidence` are
+/// left pending for manual review. Tracks already-promoted IDs to avoid
+/// double-counting similar memories in the same batch.
+async fn auto_promote(
+    memory: &MemoryEngine,
+    threshold: u32,
+    min_confidence: f64,
+) -> anyhow::Result<PromotionResult> {
     let pending = memory
         .search_by_status(MemoryStatus::Pending, 100)
         .await
@@ -186,7 +199,13 @@ async fn auto_promote(memory: &MemoryEngine, threshold: u32) -> anyhow::Result<P
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        if is_contradiction {
+        // Skills always wait for explicit approval.
+        if is_contradiction || mem.kind == MemoryKind::Skill {
+            continue;
+        }
+
+        if memory_confidence(mem).is_some_and(|c| c < min_confidence) {
+            debug!(content = %mem.content, "low-confidence memory left for review");
             continue;
         }
 
@@ -229,10 +248,13 @@ async fn suggest_promote(
     telegram_tx: &mpsc::Sender<TelegramOutbound>,
     user_id: i64,
 ) -> anyhow::Result<PromotionResult> {
-    let pending = memory
+    let pending: Vec<Memory> = memory
         .search_by_status(MemoryStatus::Pending, 20)
         .await
-        .context("failed to search pending memories")?;
+        .context("failed to search pending memories")?
+        .into_iter()
+        .filter(|m| !super::skills::is_unripe_skill(m))
+        .collect();
 
     if pending.is_empty() {
         return Ok(PromotionResult {
@@ -252,6 +274,7 @@ async fn suggest_promote(
             escape_html(&mem.content)
         };
         lines.push(format!("  [{kind}] {truncated}"));
+        lines.push(format!("    <i>{}</i>", format_provenance(mem)));
     }
     lines.push("\nUse /memory_pending to manage these.".to_owned());
 
@@ -260,6 +283,7 @@ async fn suggest_promote(
         text: Some(lines.join("\n")),
         file_path: None,
         approval_keyboard: None,
+        keyboard: None,
     };
 
     if let Err(e) = telegram_tx.send(msg).await {
@@ -314,15 +338,70 @@ pub async fn undo_last_promotion(memory: &MemoryEngine) -> anyhow::Result<usize>
     Ok(archived)
 }
 
+/// Maximum characters of a source span shown in review messages.
+const PROVENANCE_SPAN_CHARS: usize = 80;
+
+/// Read the confidence score stored in a memory's metadata.
+pub fn memory_confidence(mem: &Memory) -> Option<f64> {
+    mem.metadata
+        .as_ref()
+        .and_then(|m| m.get("confidence"))
+        .and_then(|v| v.as_f64())
+}
+
+/// Describe where a memory came from, as HTML-escaped text.
+///
+/// For example: `learned from conversation on Feb 3, confidence 0.85:
+/// "I moved to Berlin last year"`.
+pub fn format_provenance(mem: &Memory) -> String {
+    let origin = match mem.source {
+        MemorySource::Observer => "learned from conversation",
+        MemorySource::User => "saved by you",
+        MemorySource::Agent => "saved by agent",
+    };
+    let mut text = origin.to_owned();
+
+    let date = mem.created_at.as_deref().and_then(|ts| {
+        chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| dt.format("%b %-d").to_string())
+    });
+    if let Some(date) = date {
+        text.push_str(" on ");
+        text.push_str(&date);
+    }
+
+    if let Some(confidence) = memory_confidence(mem) {
+        text.push_str(&format!(", confidence {confidence:.2}"));
+    }
+
+    let span = mem
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("source_span"))
+        .and_then(|v| v.as_str());
+    if let Some(span) = span {
+        let truncated: String = span.chars().take(PROVENANCE_SPAN_CHARS).collect();
+        let ellipsis = if span.chars().count() > PROVENANCE_SPAN_CHARS {
+            "..."
+        } else {
+            ""
+        };
+        text.push_str(&format!(": \"{truncated}{ellipsis}\""));
+    }
+
+    escape_html(&text)
+}
+
 /// Normalize text for duplicate comparison (lowercase, trim whitespace).
-fn normalize_for_compare(text: &str) -> String {
+pub(crate) fn normalize_for_compare(text: &str) -> String {
     text.trim().to_lowercase()
 }
 
 /// Simple word overlap ratio for contradiction/similarity detection.
 ///
 /// Returns 0.0–1.0 where 1.0 means identical word sets.
-fn word_overlap(a: &str, b: &str) -> f64 {
+pub(crate) fn word_overlap(a: &str, b: &str) -> f64 {
     let words_a: std::collections::HashSet<&str> = a.split_whitespace().collect();
     let words_b: std::collections::HashSet<&str> = b.split_whitespace().collect();
 
@@ -0,0 +1,148 @@
+//! Per-provider call outcomes for health reporting.
+//!
+//! The [`super::router::ModelRouter`] wraps every provider in a
+//! [`TrackedProvider`] that records whether its calls succeed. The
+//! heartbeat reads the snapshot to report each provider's status in
+//! `health.json`. Context-overflow errors are the caller's problem, not the
+//! provider's, and count as successful round trips.
+//!
+//! The same wrapper appends every call to the usage ledger (see
+//! [`super::usage`]).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use super::usage::{current_scope, UsageLedger};
+use super::{CompletionRequest, CompletionResponse, LlmProvider, ProviderError};
+use crate::memory::usage::UsageEntry;
+
+/// Longest error message kept per provider, in characters.
+const MAX_ERROR_CHARS: usize = 500;
+
+/// Latest call outcomes for one provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderOutcome {
+    /// Calls that failed since the last success.
+    pub consecutive_failures: u32,
+    /// Most recent error message, cleared on success.
+    pub last_error: Option<String>,
+    /// ISO 8601 timestamp of the most recent success.
+    pub last_success_at: Option<String>,
+    /// ISO 8601 timestamp of the most recent failure.
+    pub last_failure_at: Option<String>,
+}
+
+/// Shared record of provider call outcomes, keyed by model spec.
+#[derive(Debug, Default)]
+pub struct ProviderHealth {
+    outcomes: Mutex<HashMap<String, ProviderOutcome>>,
+}
+
+impl ProviderHealth {
+    /// Record a successful call.
+    pub fn record_success(&self, spec: &str) {
+        if let Ok(mut outcomes) = self.outcomes.lock() {
+            let outcome = outcomes.entry(spec.to_owned()).or_default();
+            outcome.consecutive_failures = 0;
+            outcome.last_error = None;
+            outcome.last_success_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+    }
+
+    /// Record a failed call.
+    pub fn record_failure(&self, spec: &str, error: &str) {
+        let message: String = error.chars().take(MAX_ERROR_CHARS).collect();
+        if let Ok(mut outcomes) = self.outcomes.lock() {
+            let outcome = outcomes.entry(spec.to_owned()).or_default();
+            outcome.consecutive_failures = outcome.consecutive_failures.saturating_add(1);
+            outcome.last_error = Some(message);
+            outcome.last_failure_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+    }
+
+    /// Outcomes for a spec; default (never called) when unknown.
+    pub fn outcome(&self, spec: &str) -> ProviderOutcome {
+        self.outcomes
+            .lock()
+            .ok()
+            .and_then(|outcomes| outcomes.get(spec).cloned())
+            .unwrap_or_default()
+    }
+}
+
+/// Provider wrapper that records call outcomes in a [`ProviderHealth`]
+/// and each call in the [`UsageLedger`].
+pub struct TrackedProvider {
+    spec: String,
+    inner: Arc<dyn LlmProvider>,
+    health: Arc<ProviderHealth>,
+    ledger: Arc<UsageLedger>,
+}
+
+impl TrackedProvider {
+    /// Wrap `inner`, recording its outcomes and usage under `spec`.
+    pub fn new(
+        spec: String,
+        inner: Arc<dyn LlmProvider>,
+        health: Arc<ProviderHealth>,
+        ledger: Arc<UsageLedger>,
+    ) -> Self {
+        Self {
+            spec,
+            inner,
+            health,
+            ledger,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for TrackedProvider {
+    async fn complete(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let started = Instant::now();
+        let result = self.inner.complete(request).await;
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        match &result {
+            Err(e) if !e.is_context_overflow() => {
+                self.health.record_failure(&self.spec, &e.to_string());
+            }
+            _ => self.health.record_success(&self.spec),
+        }
+
+        let scope = current_scope();
+        let (input_tokens, output_tokens) = match &result {
+            Ok(response) => (response.usage.input_tokens, response.usage.output_tokens),
+            Err(_) => (0, 0),
+        };
+        self.ledger
+            .record(UsageEntry {
+                role: scope.role,
+                model: self.spec.clone(),
+                session_id: scope.session_id,
+                input_tokens,
+                output_tokens,
+                latency_ms,
+                success: result.is_ok(),
+            })
+            .await;
+        result
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        self.inner.supports_tool_calling()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+}
@@ -15,9 +15,12 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 pub mod anthropic;
+pub mod health;
 pub mod ollama;
 pub mod openai;
+pub mod replay;
 pub mod router;
+pub mod usage;
 
 // ---------------------------------------------------------------------------
 // Core types
@@ -139,7 +142,8 @@ pub struct CompletionRequest {
 }
 
 /// The reason a completion stopped generating.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StopReason {
     /// Normal end of turn.
     EndTurn,
@@ -163,7 +167,7 @@ pub struct UsageStats {
 }
 
 /// The response from an LLM provider.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionResponse {
     /// Response content (text and/or tool calls).
     pub content: Vec<ContentPart>,
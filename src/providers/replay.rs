@@ -0,0 +1,255 @@
+//! Record and replay of provider completions for deterministic tests.
+//!
+//! With `[models.fixtures] mode = "record"`, every successful completion of
+//! a model spec is redacted and appended to `<dir>/<spec>.jsonl` (see
+//! [`fixture_path`]); failed calls are not recorded. With `mode = "replay"`,
+//! the router loads those files instead of real providers and answers each
+//! call with the next recorded response, so agent-loop tests of tool calling
+//! and budget accounting run without network access or API spend.
+//!
+//! Replay follows call order only: requests are not matched against the
+//! recording. A call after the last recorded response fails with
+//! [`ProviderError::Unavailable`].
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use super::{CompletionRequest, CompletionResponse, LlmProvider, Message, ProviderError};
+use crate::executor::redactor::Redactor;
+
+/// Fixture file extension.
+const FIXTURE_EXTENSION: &str = "jsonl";
+
+/// One recorded call, stored as a line of a fixture file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureEntry {
+    /// What was asked, kept so fixtures can be read and reviewed.
+    pub request: RecordedRequest,
+    /// What the provider answered.
+    pub response: CompletionResponse,
+}
+
+/// Summary of a recorded request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// Number of messages in the conversation.
+    pub message_count: usize,
+    /// The latest message.
+    pub last_message: Option<Message>,
+    /// Names of the tools offered.
+    pub tools: Vec<String>,
+}
+
+impl From<&CompletionRequest> for RecordedRequest {
+    fn from(request: &CompletionRequest) -> Self {
+        Self {
+            message_count: request.messages.len(),
+            last_message: request.messages.last().cloned(),
+            tools: request.tools.iter().map(|t| t.name.clone()).collect(),
+        }
+    }
+}
+
+/// Fixture file for a model spec inside `dir`.
+///
+/// Characters other than ASCII alphanumerics, `-` and `.` become `_`, so
+/// `anthropic/claude-sonnet` maps to `anthropic_claude-sonnet.jsonl`.
+pub fn fixture_path(dir: &Path, spec: &str) -> PathBuf {
+    let name: String = spec
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{name}.{FIXTURE_EXTENSION}"))
+}
+
+// ---------------------------------------------------------------------------
+// Recording
+// ---------------------------------------------------------------------------
+
+/// Provider wrapper that appends each completion to a fixture file.
+pub struct RecordingProvider {
+    inner: Arc<dyn LlmProvider>,
+    path: PathBuf,
+    file: tokio::sync::Mutex<tokio::fs::File>,
+    redactor: Redactor,
+}
+
+impl std::fmt::Debug for RecordingProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingProvider")
+            .field("model", &self.inner.model_id())
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RecordingProvider {
+    /// Wrap `inner`, starting a fresh fixture file for `spec` in `dir`.
+    ///
+    /// An existing recording for the spec is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or file cannot be created.
+    pub fn create(
+        spec: &str,
+        inner: Arc<dyn LlmProvider>,
+        dir: &Path,
+        redactor: Redactor,
+    ) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create fixture dir {}", dir.display()))?;
+        let path = fixture_path(dir, spec);
+        let file = std::fs::File::create(&path)
+            .with_context(|| format!("failed to create fixture {}", path.display()))?;
+        Ok(Self {
+            inner,
+            path,
+            file: tokio::sync::Mutex::new(tokio::fs::File::from_std(file)),
+            redactor,
+        })
+    }
+
+    /// The fixture file being written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn record(&self, entry: &FixtureEntry) -> anyhow::Result<()> {
+        let value = self.redactor.redact_json(&serde_json::to_value(entry)?);
+        let mut line = serde_json::to_string(&value)?;
+        line.push('\n');
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LlmProvider for RecordingProvider {
+    async fn complete(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let recorded = RecordedRequest::from(&request);
+        let response = self.inner.complete(request).await?;
+        let entry = FixtureEntry {
+            request: recorded,
+            response,
+        };
+        if let Err(e) = self.record(&entry).await {
+            warn!(error = %e, path = %self.path.display(), "failed to record completion");
+        }
+        Ok(entry.response)
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        self.inner.supports_tool_calling()
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn model_id(&self) -> &str {
+        self.inner.model_id()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// Provider answering from recorded responses, in order.
+#[derive(Debug)]
+pub struct ReplayProvider {
+    spec: String,
+    responses: Mutex<VecDeque<CompletionResponse>>,
+}
+
+impl ReplayProvider {
+    /// Replay the given responses for `spec`.
+    pub fn new(spec: impl Into<String>, responses: Vec<CompletionResponse>) -> Self {
+        Self {
+            spec: spec.into(),
+            responses: Mutex::new(responses.into()),
+        }
+    }
+
+    /// Load the fixture file for `spec` from `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or a line is not a
+    /// [`FixtureEntry`].
+    pub fn open(spec: &str, dir: &Path) -> anyhow::Result<Self> {
+        let path = fixture_path(dir, spec);
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read fixture {}", path.display()))?;
+        let responses = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<FixtureEntry>(line)
+                    .map(|entry| entry.response)
+                    .with_context(|| {
+                        format!(
+                            "invalid fixture entry at {}:{}",
+                            path.display(),
+                            index.saturating_add(1)
+                        )
+                    })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self::new(spec, responses))
+    }
+
+    /// Number of recorded responses not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.responses.lock().map(|r| r.len()).unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ReplayProvider {
+    async fn complete(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let next = self
+            .responses
+            .lock()
+            .map_err(|_| ProviderError::Unavailable("replay fixture lock poisoned".to_owned()))?
+            .pop_front();
+        next.ok_or_else(|| {
+            ProviderError::Unavailable(format!("no recorded responses left for {}", self.spec))
+        })
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn model_id(&self) -> &str {
+        &self.spec
+    }
+}
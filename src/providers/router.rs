@@ -1,17 +1,22 @@
 //! Model router resolving providers by skill, role, and default settings.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use anyhow::Context;
 
-use crate::config::{all_model_specs, ModelsConfig};
+use crate::config::{all_model_specs, FixtureMode, ModelsConfig};
 use crate::credentials::{resolve_anthropic_auth, resolve_openai_auth, AnthropicAuth, Credentials};
 
 use super::anthropic::AnthropicProvider;
+use super::health::{ProviderHealth, ProviderOutcome, TrackedProvider};
 use super::ollama::OllamaProvider;
 use super::openai::OpenAiProvider;
+use super::replay::{RecordingProvider, ReplayProvider};
+use super::usage::UsageLedger;
 use super::LlmProvider;
+use crate::executor::redactor::Redactor;
+use crate::memory::MemoryEngine;
 
 /// Provider routing errors.
 #[derive(Debug, thiserror::Error)]
@@ -55,8 +60,16 @@ pub enum RouterError {
 pub struct ModelRouter {
     providers: HashMap<String, Arc<dyn LlmProvider>>,
     default: String,
-    role_overrides: HashMap<String, String>,
-    skill_overrides: HashMap<String, String>,
+    overrides: Arc<RwLock<Overrides>>,
+    health: Arc<ProviderHealth>,
+    ledger: Arc<UsageLedger>,
+}
+
+/// Role and skill model overrides, replaceable on config reload.
+#[derive(Debug, Default)]
+struct Overrides {
+    roles: HashMap<String, String>,
+    skills: HashMap<String, String>,
 }
 
 impl ModelRouter {
@@ -77,29 +90,59 @@ impl ModelRouter {
     /// calling [`resolve_anthropic_auth`]. This allows callers to refresh an
     /// expired token before the router is built.
     ///
+    /// `[models.fixtures]` switches providers to recording or replay, see
+    /// [`super::replay`]. In replay mode no credentials are needed and specs
+    /// without a fixture file are not loaded.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the default provider cannot be instantiated.
+    /// Returns an error if the default provider cannot be instantiated or a
+    /// fixture file cannot be created.
     pub fn from_config_with_auth(
         models: &ModelsConfig,
         credentials: &Credentials,
         anthropic_auth: Option<AnthropicAuth>,
     ) -> anyhow::Result<Self> {
         let mut providers: HashMap<String, Arc<dyn LlmProvider>> = HashMap::new();
+        let health = Arc::new(ProviderHealth::default());
+        let ledger = Arc::new(UsageLedger::default());
         let specs = all_model_specs(models);
+        let fixtures = &models.fixtures;
+        let fixture_dir = fixtures.dir.as_deref().unwrap_or(std::path::Path::new("."));
 
         for spec in specs {
             let parsed = parse_model_spec(&spec)
                 .with_context(|| format!("failed to parse model spec '{spec}'"))?;
-            let instance = instantiate_provider(
-                &spec,
-                &parsed.provider,
-                &parsed.model,
-                credentials,
-                anthropic_auth.as_ref(),
-            );
-            if let Ok(provider) = instance {
-                providers.insert(spec.clone(), provider);
+            let instance: Option<Arc<dyn LlmProvider>> = match fixtures.mode {
+                FixtureMode::Replay => ReplayProvider::open(&spec, fixture_dir)
+                    .map(|p| Arc::new(p) as Arc<dyn LlmProvider>)
+                    .ok(),
+                FixtureMode::Record | FixtureMode::Off => instantiate_provider(
+                    &spec,
+                    &parsed.provider,
+                    &parsed.model,
+                    credentials,
+                    anthropic_auth.as_ref(),
+                )
+                .ok(),
+            };
+            let instance = match (fixtures.mode, instance) {
+                (FixtureMode::Record, Some(provider)) => {
+                    let redactor = Redactor::new(credentials.known_secrets());
+                    let recording =
+                        RecordingProvider::create(&spec, provider, fixture_dir, redactor)?;
+                    Some(Arc::new(recording) as Arc<dyn LlmProvider>)
+                }
+                (_, instance) => instance,
+            };
+            if let Some(provider) = instance {
+                let tracked = TrackedProvider::new(
+                    spec.clone(),
+                    provider,
+                    Arc::clone(&health),
+                    Arc::clone(&ledger),
+                );
+                providers.insert(spec.clone(), Arc::new(tracked));
             }
         }
 
@@ -113,24 +156,44 @@ impl ModelRouter {
         Ok(Self {
             providers,
             default: models.default.clone(),
-            role_overrides: models.roles.clone(),
-            skill_overrides: models.skills.clone(),
+            overrides: Arc::new(RwLock::new(Overrides {
+                roles: models.roles.clone(),
+                skills: models.skills.clone(),
+            })),
+            health,
+            ledger,
         })
     }
 
     /// Create a router backed by a single provider for integration tests.
     #[doc(hidden)]
     pub fn for_testing(default_spec: String, provider: Arc<dyn LlmProvider>) -> Self {
-        let mut providers = HashMap::new();
-        providers.insert(default_spec.clone(), provider);
+        let health = Arc::new(ProviderHealth::default());
+        let ledger = Arc::new(UsageLedger::default());
+        let tracked = TrackedProvider::new(
+            default_spec.clone(),
+            provider,
+            Arc::clone(&health),
+            Arc::clone(&ledger),
+        );
+        let mut providers: HashMap<String, Arc<dyn LlmProvider>> = HashMap::new();
+        providers.insert(default_spec.clone(), Arc::new(tracked));
         Self {
             providers,
             default: default_spec,
-            role_overrides: HashMap::new(),
-            skill_overrides: HashMap::new(),
+            overrides: Arc::new(RwLock::new(Overrides::default())),
+            health,
+            ledger,
         }
     }
 
+    /// Append every provider call to the usage ledger in `memory`.
+    ///
+    /// Only the first call takes effect.
+    pub fn record_usage_to(&self, memory: Arc<MemoryEngine>) {
+        self.ledger.attach(memory);
+    }
+
     /// Resolve a provider by optional role and skill identifiers.
     ///
     /// Resolution order: `skill -> role -> default`.
@@ -152,14 +215,17 @@ impl ModelRouter {
 
     /// Resolve a model spec string by optional role and skill.
     pub fn resolve_spec(&self, role: Option<&str>, skill: Option<&str>) -> String {
+        let Ok(overrides) = self.overrides.read() else {
+            return self.default.clone();
+        };
         if let Some(spec) = skill
-            .and_then(|s| self.skill_overrides.get(s))
+            .and_then(|s| overrides.skills.get(s))
             .filter(|spec| self.providers.contains_key(*spec))
         {
             return spec.clone();
         }
         if let Some(spec) = role
-            .and_then(|r| self.role_overrides.get(r))
+            .and_then(|r| overrides.roles.get(r))
             .filter(|spec| self.providers.contains_key(*spec))
         {
             return spec.clone();
@@ -167,11 +233,57 @@ impl ModelRouter {
         self.default.clone()
     }
 
+    /// Whether `role` resolves to a loaded model other than the default.
+    pub fn has_distinct_role(&self, role: &str) -> bool {
+        self.resolve_spec(Some(role), None) != self.default
+    }
+
+    /// Whether the role and skill overrides differ from the given maps.
+    pub fn overrides_differ(
+        &self,
+        roles: &HashMap<String, String>,
+        skills: &HashMap<String, String>,
+    ) -> bool {
+        self.overrides
+            .read()
+            .map(|o| o.roles != *roles || o.skills != *skills)
+            .unwrap_or(true)
+    }
+
+    /// Replace the role and skill overrides, e.g. after a config reload.
+    ///
+    /// Providers are not instantiated here. Returns the sorted override specs
+    /// that have no loaded provider; those fall back to the default model
+    /// until the next restart.
+    pub fn set_overrides(
+        &self,
+        roles: HashMap<String, String>,
+        skills: HashMap<String, String>,
+    ) -> Vec<String> {
+        let mut missing: Vec<String> = roles
+            .values()
+            .chain(skills.values())
+            .filter(|spec| !self.providers.contains_key(*spec))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing.dedup();
+        if let Ok(mut overrides) = self.overrides.write() {
+            *overrides = Overrides { roles, skills };
+        }
+        missing
+    }
+
     /// Returns true when a specific model spec is available.
     pub fn has_model(&self, spec: &str) -> bool {
         self.providers.contains_key(spec)
     }
 
+    /// Returns the loaded provider for an exact spec, if any.
+    pub fn provider(&self, spec: &str) -> Option<Arc<dyn LlmProvider>> {
+        self.providers.get(spec).cloned()
+    }
+
     /// Returns the default provider.
     pub fn default_provider(&self) -> Arc<dyn LlmProvider> {
         // Safe: from_config guarantees the default is present.
@@ -189,6 +301,22 @@ impl ModelRouter {
         values.sort();
         values
     }
+
+    /// Call outcomes of one provider spec.
+    pub fn provider_outcome(&self, spec: &str) -> ProviderOutcome {
+        self.health.outcome(spec)
+    }
+
+    /// Call outcomes of every loaded provider, sorted by spec.
+    pub fn provider_outcomes(&self) -> Vec<(String, ProviderOutcome)> {
+        self.available_specs()
+            .into_iter()
+            .map(|spec| {
+                let outcome = self.health.outcome(&spec);
+                (spec, outcome)
+            })
+            .collect()
+    }
 }
 
 /// Decomposed `"provider/model"` spec.
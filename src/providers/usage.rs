@@ -0,0 +1,99 @@
+//! Attribution of provider calls to the usage ledger.
+//!
+//! Callers wrap a completion in [`scoped`] to say what it is for; the
+//! [`super::health::TrackedProvider`] reads the scope when the call finishes
+//! and appends a [`UsageEntry`] through the [`UsageLedger`]. Calls made
+//! outside a scope are still recorded, without role or session.
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+use tracing::warn;
+
+use crate::memory::usage::UsageEntry;
+use crate::memory::MemoryEngine;
+
+tokio::task_local! {
+    static SCOPE: UsageScope;
+}
+
+/// What a provider call is for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageScope {
+    /// Caller role (`agent`, `observer`, `oracle`, ...).
+    pub role: Option<String>,
+    /// Session the call belongs to.
+    pub session_id: Option<String>,
+}
+
+impl UsageScope {
+    /// Scope for a call with the given role and no session.
+    pub fn role(role: &str) -> Self {
+        Self {
+            role: Some(role.to_owned()),
+            session_id: None,
+        }
+    }
+
+    /// Scope for a call made on behalf of a session.
+    pub fn session(role: &str, session_id: &str) -> Self {
+        Self {
+            role: Some(role.to_owned()),
+            session_id: Some(session_id.to_owned()),
+        }
+    }
+
+    /// The current scope with its role replaced, keeping the session.
+    pub fn current_with_role(role: &str) -> Self {
+        Self {
+            role: Some(role.to_owned()),
+            ..current_scope()
+        }
+    }
+}
+
+/// Run `future` with provider calls attributed to `scope`.
+pub async fn scoped<F: Future>(scope: UsageScope, future: F) -> F::Output {
+    SCOPE.scope(scope, future).await
+}
+
+/// The scope of the current task, empty outside [`scoped`].
+pub fn current_scope() -> UsageScope {
+    SCOPE.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// Destination for usage entries, attached once the memory engine exists.
+///
+/// Until [`UsageLedger::attach`] is called (and in tools like `doctor` that
+/// never call it) entries are dropped.
+#[derive(Default)]
+pub struct UsageLedger {
+    memory: OnceLock<Arc<MemoryEngine>>,
+}
+
+impl std::fmt::Debug for UsageLedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageLedger")
+            .field("attached", &self.memory.get().is_some())
+            .finish()
+    }
+}
+
+impl UsageLedger {
+    /// Start writing entries to `memory`. Later calls are ignored.
+    pub fn attach(&self, memory: Arc<MemoryEngine>) {
+        if self.memory.set(memory).is_err() {
+            warn!("usage ledger already attached");
+        }
+    }
+
+    /// Append an entry if a memory engine is attached.
+    pub async fn record(&self, entry: UsageEntry) {
+        let Some(memory) = self.memory.get() else {
+            return;
+        };
+        if let Err(e) = memory.record_usage(entry).await {
+            warn!(error = %e, "failed to record provider usage");
+        }
+    }
+}
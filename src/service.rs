@@ -0,0 +1,415 @@
+//! Service installation for launchd (macOS) and systemd (Linux).
+//!
+//! Backs `wintermute service` and `flatline service`. Unit files and plists
+//! are generated with absolute paths to the running binary and the
+//! `~/.wintermute` root, so nothing has to be edited by hand. Both services
+//! are user-level: they run as the installing user, restart after a crash
+//! (10 seconds apart) and load `~/.wintermute/.env` into their environment.
+//!
+//! This module only generates and places files. Wintermute itself never
+//! spawns host processes, so loading a service into launchd or systemd is
+//! left to Flatline (`flatline::services`) or to the printed command.
+//! Detection is shared with Flatline's updater.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use tracing::{debug, info};
+
+/// macOS launchd plist for the Wintermute agent.
+pub const LAUNCHD_AGENT_PLIST: &str = "com.wintermute.agent.plist";
+
+/// macOS launchd plist for the Flatline supervisor.
+pub const LAUNCHD_FLATLINE_PLIST: &str = "com.wintermute.flatline.plist";
+
+/// Linux systemd unit for the Wintermute agent.
+pub const SYSTEMD_AGENT_UNIT: &str = "wintermute.service";
+
+/// Linux systemd unit for the Flatline supervisor.
+pub const SYSTEMD_FLATLINE_UNIT: &str = "flatline.service";
+
+/// Seconds to wait before restarting a crashed service.
+const RESTART_DELAY_SECS: u32 = 10;
+
+/// Detected service manager on the current platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceManager {
+    /// macOS launchd (`~/Library/LaunchAgents/`).
+    Launchd,
+    /// Linux systemd user units (`~/.config/systemd/user/`).
+    Systemd,
+}
+
+impl ServiceManager {
+    /// The service manager native to this platform, if supported.
+    pub fn for_platform() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Self::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Some(Self::Systemd)
+        } else {
+            None
+        }
+    }
+
+    /// Directory the service files for this manager live in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined.
+    pub fn service_dir(self) -> anyhow::Result<PathBuf> {
+        match self {
+            Self::Launchd => launchd_agents_dir(),
+            Self::Systemd => systemd_user_dir(),
+        }
+    }
+}
+
+/// A process that can be installed as a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    /// The Wintermute agent (`wintermute start`).
+    Agent,
+    /// The Flatline supervisor (`flatline start`).
+    Flatline,
+}
+
+impl Service {
+    /// Service file name for `manager`.
+    pub fn file_name(self, manager: ServiceManager) -> &'static str {
+        match (self, manager) {
+            (Self::Agent, ServiceManager::Launchd) => LAUNCHD_AGENT_PLIST,
+            (Self::Flatline, ServiceManager::Launchd) => LAUNCHD_FLATLINE_PLIST,
+            (Self::Agent, ServiceManager::Systemd) => SYSTEMD_AGENT_UNIT,
+            (Self::Flatline, ServiceManager::Systemd) => SYSTEMD_FLATLINE_UNIT,
+        }
+    }
+
+    /// launchd label, also the plist name without its extension.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Agent => "com.wintermute.agent",
+            Self::Flatline => "com.wintermute.flatline",
+        }
+    }
+
+    /// Short name used for log files and messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Agent => "wintermute",
+            Self::Flatline => "flatline",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Agent => "Wintermute AI Agent",
+            Self::Flatline => "Flatline Supervisor for Wintermute",
+        }
+    }
+}
+
+/// Paths baked into a generated service file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServicePaths {
+    /// Absolute path of the binary to run with `start`.
+    pub binary: PathBuf,
+    /// Wintermute root (`~/.wintermute`), used as the working directory.
+    pub root: PathBuf,
+}
+
+impl ServicePaths {
+    /// Paths for the running binary and the default `~/.wintermute` root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the executable or home directory cannot be resolved.
+    pub fn for_current_exe() -> anyhow::Result<Self> {
+        let binary = std::env::current_exe()
+            .and_then(|p| p.canonicalize())
+            .context("failed to resolve the running binary")?;
+        Ok(Self {
+            binary,
+            root: crate::config::config_dir()?,
+        })
+    }
+
+    /// `.env` file loaded into the service environment.
+    pub fn env_file(&self) -> PathBuf {
+        self.root.join(".env")
+    }
+
+    /// Directory for launchd stdout/stderr logs.
+    pub fn log_dir(&self) -> PathBuf {
+        self.root.join("data").join("logs")
+    }
+}
+
+/// Detect which service manager is active based on installed service files.
+///
+/// Checks for the presence of Wintermute service files in the platform's
+/// standard service directory. Returns `None` if no service files are
+/// installed (user runs processes manually).
+pub fn detect() -> Option<ServiceManager> {
+    if cfg!(target_os = "macos") {
+        if let Ok(dir) = launchd_agents_dir() {
+            if dir.join(LAUNCHD_AGENT_PLIST).exists() {
+                return Some(ServiceManager::Launchd);
+            }
+        }
+    }
+
+    if cfg!(target_os = "linux") {
+        if let Ok(dir) = systemd_user_dir() {
+            if dir.join(SYSTEMD_AGENT_UNIT).exists() {
+                return Some(ServiceManager::Systemd);
+            }
+        }
+    }
+
+    None
+}
+
+/// The installed service manager, or the platform's native one.
+///
+/// # Errors
+///
+/// Returns an error on platforms without launchd or systemd.
+pub fn resolve_manager() -> anyhow::Result<ServiceManager> {
+    detect()
+        .or_else(ServiceManager::for_platform)
+        .ok_or_else(|| anyhow::anyhow!("services are only supported with launchd or systemd"))
+}
+
+/// Resolve the macOS LaunchAgents directory (`~/Library/LaunchAgents/`).
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn launchd_agents_dir() -> anyhow::Result<PathBuf> {
+    let home = directories::BaseDirs::new()
+        .ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
+    Ok(home.home_dir().join("Library/LaunchAgents"))
+}
+
+/// Resolve the Linux systemd user units directory (`~/.config/systemd/user/`).
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn systemd_user_dir() -> anyhow::Result<PathBuf> {
+    let home = directories::BaseDirs::new()
+        .ok_or_else(|| anyhow::anyhow!("cannot determine home directory"))?;
+    Ok(home.home_dir().join(".config/systemd/user"))
+}
+
+/// Render a systemd user unit for `service`.
+pub fn systemd_unit(service: Service, paths: &ServicePaths) -> String {
+    let after = match service {
+        Service::Agent => "docker.service network-online.target",
+        Service::Flatline => "wintermute.service docker.service",
+    };
+    let wants = match service {
+        Service::Agent => "Wants=network-online.target\n",
+        Service::Flatline => "",
+    };
+    format!(
+        "# Generated by `{name} service install`. Manage with:\n\
+         #   systemctl --user status {name}\n\
+         [Unit]\n\
+         Description={description}\n\
+         After={after}\n\
+         {wants}\n\
+         [Service]\n\
+         Type=simple\n\
+         ExecStart={binary} start\n\
+         WorkingDirectory={root}\n\
+         Restart=on-failure\n\
+         RestartSec={RESTART_DELAY_SECS}\n\
+         EnvironmentFile=-{env}\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n",
+        name = service.name(),
+        description = service.description(),
+        binary = systemd_quote(&paths.binary),
+        root = systemd_quote(&paths.root),
+        env = systemd_quote(&paths.env_file()),
+    )
+}
+
+/// Render a launchd agent plist for `service`.
+///
+/// launchd has no environment-file support, so the service runs through
+/// `/bin/bash`, which exports `.env` before exec-ing the binary.
+pub fn launchd_plist(service: Service, paths: &ServicePaths) -> String {
+    let command = format!(
+        "set -a; source {} 2>/dev/null; set +a; exec {} start",
+        shell_quote(&paths.env_file()),
+        shell_quote(&paths.binary)
+    );
+    let log_dir = paths.log_dir();
+    let out_log = log_dir.join(format!("{}.out.log", service.name()));
+    let err_log = log_dir.join(format!("{}.err.log", service.name()));
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <!-- Generated by `{name} service install`. -->\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \t<key>Label</key>\n\
+         \t<string>{label}</string>\n\
+         \n\
+         \t<key>ProgramArguments</key>\n\
+         \t<array>\n\
+         \t\t<string>/bin/bash</string>\n\
+         \t\t<string>-c</string>\n\
+         \t\t<string>{command}</string>\n\
+         \t</array>\n\
+         \n\
+         \t<key>WorkingDirectory</key>\n\
+         \t<string>{root}</string>\n\
+         \n\
+         \t<key>RunAtLoad</key>\n\
+         \t<true/>\n\
+         \n\
+         \t<key>KeepAlive</key>\n\
+         \t<dict>\n\
+         \t\t<key>SuccessfulExit</key>\n\
+         \t\t<false/>\n\
+         \t</dict>\n\
+         \n\
+         \t<key>ThrottleInterval</key>\n\
+         \t<integer>{RESTART_DELAY_SECS}</integer>\n\
+         \n\
+         \t<key>StandardOutPath</key>\n\
+         \t<string>{out_log}</string>\n\
+         \t<key>StandardErrorPath</key>\n\
+         \t<string>{err_log}</string>\n\
+         </dict>\n\
+         </plist>\n",
+        name = service.name(),
+        label = service.label(),
+        command = xml_escape(&command),
+        root = xml_escape(&paths.root.to_string_lossy()),
+        out_log = xml_escape(&out_log.to_string_lossy()),
+        err_log = xml_escape(&err_log.to_string_lossy()),
+    )
+}
+
+/// Location of the service file for `service`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn service_file(manager: ServiceManager, service: Service) -> anyhow::Result<PathBuf> {
+    Ok(manager.service_dir()?.join(service.file_name(manager)))
+}
+
+/// Generate and write the service file for `service`, replacing any old one.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be created or the file cannot be
+/// written.
+pub fn write_service_file(
+    manager: ServiceManager,
+    service: Service,
+    paths: &ServicePaths,
+) -> anyhow::Result<PathBuf> {
+    let path = service_file(manager, service)?;
+    let dir = manager.service_dir()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let contents = match manager {
+        ServiceManager::Launchd => {
+            let log_dir = paths.log_dir();
+            std::fs::create_dir_all(&log_dir)
+                .with_context(|| format!("failed to create {}", log_dir.display()))?;
+            launchd_plist(service, paths)
+        }
+        ServiceManager::Systemd => systemd_unit(service, paths),
+    };
+    std::fs::write(&path, contents)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    info!(path = %path.display(), "wrote service file");
+    Ok(path)
+}
+
+/// Remove the service file for `service`. Returns `false` if none existed.
+///
+/// For systemd this also removes the `default.target.wants` link that
+/// `systemctl enable` created, which is all `systemctl disable` does.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be removed.
+pub fn remove_service_file(manager: ServiceManager, service: Service) -> anyhow::Result<bool> {
+    let path = service_file(manager, service)?;
+    if manager == ServiceManager::Systemd {
+        let link = manager
+            .service_dir()?
+            .join("default.target.wants")
+            .join(service.file_name(manager));
+        if link.symlink_metadata().is_ok() {
+            std::fs::remove_file(&link)
+                .with_context(|| format!("failed to remove {}", link.display()))?;
+        }
+    }
+    if !path.exists() {
+        debug!(path = %path.display(), "service file not installed");
+        return Ok(false);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()))?;
+    info!(path = %path.display(), "removed service file");
+    Ok(true)
+}
+
+/// Shell command that loads and starts an installed service.
+///
+/// Wintermute never spawns host processes, so `wintermute service install`
+/// prints this instead of running it; Flatline runs the equivalent itself.
+pub fn enable_command(manager: ServiceManager, service: Service, path: &Path) -> String {
+    match manager {
+        ServiceManager::Launchd => format!("launchctl load -w {}", path.display()),
+        ServiceManager::Systemd => format!(
+            "systemctl --user daemon-reload && systemctl --user enable --now {}",
+            service.name()
+        ),
+    }
+}
+
+/// Shell command that stops a service whose file was already removed.
+pub fn stop_command(manager: ServiceManager, service: Service) -> String {
+    match manager {
+        ServiceManager::Launchd => format!("launchctl remove {}", service.label()),
+        ServiceManager::Systemd => format!(
+            "systemctl --user stop {} && systemctl --user daemon-reload",
+            service.name()
+        ),
+    }
+}
+
+// -- Private helpers --
+
+/// Quote a path for a systemd unit when it contains whitespace.
+fn systemd_quote(path: &Path) -> String {
+    let raw = path.to_string_lossy();
+    if raw.contains(char::is_whitespace) || raw.contains('"') {
+        format!("\"{}\"", raw.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        raw.into_owned()
+    }
+}
+
+/// Single-quote a path for `/bin/bash -c`.
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', r"'\''"))
+}
+
+/// Escape text for a plist `<string>`.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -3,8 +3,15 @@
 //! Each function handles a specific command and returns an HTML-formatted
 //! response string. All output uses HTML parse mode per project convention.
 
+use crate::agent::soul;
+use crate::executor::redactor::Redactor;
 use crate::executor::Executor;
+use crate::memory::feedback::FeedbackRating;
+use crate::memory::transcript::{self, ExportFormat, ExportScope};
+use crate::memory::usage::{self, UsageGroup, UsageTotals};
 use crate::memory::MemoryEngine;
+use crate::messaging::contacts::{self, Contact};
+use crate::messaging::templates;
 use crate::telegram::ui::{escape_html, format_budget};
 use crate::tools::registry::DynamicToolRegistry;
 
@@ -15,16 +22,33 @@ pub fn handle_help() -> String {
         "",
         "/help — show this message",
         "/status — executor health, memory stats, active sessions",
+        "/status tools [count] — recent tool calls in this session",
         "/budget — token budget usage",
+        "/budget detail — provider usage over the last 7 and 30 days",
         "/reset — end current session and start fresh",
         "/memory — search recent memories",
         "/memory_pending — show pending observer memories",
         "/memory_undo — undo last observer promotion",
+        "/remember &lt;fact&gt; — save a fact to memory",
+        "/forget &lt;query&gt; — find memories and delete them",
+        "/feedback up|down [comment] — rate the last answer",
+        "/contacts [list [query]] — list contacts",
+        "/contacts add &lt;name&gt; | &lt;phone or @handle&gt; ... — add a contact",
+        "/contacts merge &lt;keep_id&gt; &lt;drop_id&gt; — merge duplicates",
+        "/contacts alias &lt;id&gt; &lt;alias&gt; — add a nickname",
+        "/contacts note &lt;id&gt; &lt;text&gt; — set notes used when drafting",
+        "/templates — list outbound message templates",
+        "/templates add &lt;name&gt; &lt;text with {placeholders}&gt; — create or replace",
+        "/templates show|delete &lt;name&gt; — show or remove a template",
+        "/soul history — list soul versions",
+        "/soul revert &lt;version&gt; — restore an earlier soul (owner only)",
+        "/export [session|all] [md|json] — download the conversation transcript",
         "/tools — list dynamic tools",
         "/tools &lt;name&gt; — show detail for a specific tool",
         "/sandbox — container/executor status",
         "/revert — git revert HEAD in /scripts",
         "/backup — trigger a backup",
+        "/reload — re-read config files (owner only)",
     ]
     .join("\n")
 }
@@ -54,6 +78,72 @@ pub async fn handle_status(
     )
 }
 
+/// Usage text for `/status` with arguments.
+const STATUS_USAGE: &str = "Usage: /status [tools [count]]";
+
+/// Tool calls shown by `/status tools` without a count.
+const DEFAULT_STATUS_TOOL_CALLS: usize = 10;
+
+/// Upper bound on the count accepted by `/status tools`.
+const MAX_STATUS_TOOL_CALLS: usize = 50;
+
+/// Characters of tool output shown per call by `/status tools`.
+const STATUS_OUTPUT_CHARS: usize = 120;
+
+/// Show the session's recent tool calls: name, duration, outcome and output.
+///
+/// `args` is `tools` optionally followed by how many calls to show. Output
+/// goes through the [`Redactor`] and is truncated.
+pub async fn handle_status_tools(
+    db: &sqlx::SqlitePool,
+    redactor: &Redactor,
+    session_id: &str,
+    args: &str,
+) -> String {
+    let mut parts = args.split_whitespace();
+    if parts.next() != Some("tools") {
+        return STATUS_USAGE.to_owned();
+    }
+    let limit = match parts.next().map(str::parse::<usize>) {
+        None => DEFAULT_STATUS_TOOL_CALLS,
+        Some(Ok(n)) if n > 0 => n.min(MAX_STATUS_TOOL_CALLS),
+        Some(_) => return STATUS_USAGE.to_owned(),
+    };
+
+    let calls = match transcript::recent_tool_invocations(db, session_id, limit).await {
+        Ok(calls) => calls,
+        Err(e) => return format!("Status failed: {}", escape_html(&e.to_string())),
+    };
+    if calls.is_empty() {
+        return "No tool calls recorded in this session.".to_owned();
+    }
+
+    let mut lines = vec![format!("<b>Recent tool calls ({}):</b>", calls.len())];
+    for call in &calls {
+        let outcome = match call.success {
+            Some(true) => "\u{2705}",
+            Some(false) => "\u{274C}",
+            None => "\u{2754}",
+        };
+        let duration = call
+            .duration_ms
+            .map_or_else(|| "not run".to_owned(), |ms| format!("{ms} ms"));
+        let output = redactor.redact(&call.output);
+        let output = output.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut shown: String = output.chars().take(STATUS_OUTPUT_CHARS).collect();
+        if output.chars().count() > STATUS_OUTPUT_CHARS {
+            shown.push_str("...");
+        }
+        lines.push(format!(
+            "{outcome} <code>{}</code> {duration} · {}\n    <i>{}</i>",
+            escape_html(&call.name),
+            escape_html(&call.created_at),
+            escape_html(&shown)
+        ));
+    }
+    lines.join("\n")
+}
+
 /// Format budget usage from pre-fetched values.
 pub fn handle_budget(
     session_used: u64,
@@ -64,6 +154,69 @@ pub fn handle_budget(
     format_budget(session_used, daily_used, session_limit, daily_limit)
 }
 
+/// Periods shown by `/budget detail`, as (label, days).
+const USAGE_PERIODS: [(&str, i64); 2] = [("Last 7 days", 7), ("Last 30 days", 30)];
+
+/// Show provider usage from the ledger: totals for the last 7 and 30 days,
+/// then the last 30 days broken down by model and by role.
+pub async fn handle_budget_detail(
+    db: &sqlx::SqlitePool,
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let mut lines = vec!["<b>Provider usage</b>".to_owned()];
+    let mut since = now;
+    for (label, days) in USAGE_PERIODS {
+        since = now
+            .checked_sub_signed(chrono::Duration::days(days))
+            .unwrap_or(now);
+        match usage::usage_totals(db, since).await {
+            Ok(totals) => lines.push(format!("{label}: {}", format_usage(&totals))),
+            Err(e) => return format!("Budget detail failed: {}", escape_html(&e.to_string())),
+        }
+    }
+
+    for (title, group) in [
+        ("By model", UsageGroup::Model),
+        ("By role", UsageGroup::Role),
+    ] {
+        let groups = match usage::usage_by(db, since, group).await {
+            Ok(groups) => groups,
+            Err(e) => return format!("Budget detail failed: {}", escape_html(&e.to_string())),
+        };
+        if groups.is_empty() {
+            continue;
+        }
+        lines.push(format!("\n<b>{title} (30 days)</b>"));
+        for totals in &groups {
+            lines.push(format!(
+                "<code>{}</code>: {}",
+                escape_html(&totals.key),
+                format_usage(totals)
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// One-line summary of aggregated usage.
+fn format_usage(totals: &UsageTotals) -> String {
+    if totals.calls == 0 {
+        return "no calls".to_owned();
+    }
+    let mut line = format!(
+        "{} tokens ({} in / {} out), {} calls, avg {} ms",
+        totals.total_tokens(),
+        totals.input_tokens,
+        totals.output_tokens,
+        totals.calls,
+        totals.avg_latency_ms
+    );
+    if totals.failures > 0 {
+        line.push_str(&format!(", {} failed", totals.failures));
+    }
+    line
+}
+
 /// Handle the /reset command — confirms session reset to the user.
 ///
 /// The actual session removal is performed by the caller since this module
@@ -104,8 +257,11 @@ pub async fn handle_memory(memory: &MemoryEngine) -> String {
 pub async fn handle_memory_pending(memory: &MemoryEngine) -> String {
     use crate::memory::MemoryStatus;
 
-    let pending = match memory.search_by_status(MemoryStatus::Pending, 20).await {
-        Ok(p) => p,
+    let pending: Vec<_> = match memory.search_by_status(MemoryStatus::Pending, 20).await {
+        Ok(p) => p
+            .into_iter()
+            .filter(|m| !crate::observer::skills::is_unripe_skill(m))
+            .collect(),
         Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
     };
 
@@ -124,6 +280,10 @@ pub async fn handle_memory_pending(memory: &MemoryEngine) -> String {
             escape_html(&mem.content)
         };
         lines.push(format!("  [{kind}] {display}"));
+        lines.push(format!(
+            "    <i>{}</i>",
+            crate::observer::staging::format_provenance(mem)
+        ));
     }
     lines.join("\n")
 }
@@ -137,6 +297,497 @@ pub async fn handle_memory_undo(memory: &MemoryEngine) -> String {
     }
 }
 
+/// Maximum matches offered for deletion by `/forget`.
+const MAX_FORGET_MATCHES: usize = 5;
+
+/// Save a fact the user stated explicitly.
+///
+/// `actor` is recorded in the memory audit trail.
+pub async fn handle_remember(memory: &MemoryEngine, actor: &str, fact: &str) -> String {
+    let fact = fact.trim();
+    if fact.is_empty() {
+        return "Usage: /remember &lt;fact&gt;".to_owned();
+    }
+    match memory.remember(fact, actor).await {
+        Ok(()) => format!("Remembered: {}", escape_html(fact)),
+        Err(e) => format!("Remember failed: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// Memories matching a `/forget` query, to be offered with delete buttons.
+#[derive(Debug)]
+pub struct ForgetPrompt {
+    /// HTML list of the matching memories.
+    pub text: String,
+    /// Ids of the listed memories, in display order.
+    pub memory_ids: Vec<i64>,
+}
+
+/// Find memories matching `query` so the user can pick which to delete.
+///
+/// Returns the matches, or an HTML message when there is nothing to offer.
+pub async fn handle_forget(memory: &MemoryEngine, query: &str) -> Result<ForgetPrompt, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Usage: /forget &lt;query&gt;".to_owned());
+    }
+    let results = memory
+        .search(query, MAX_FORGET_MATCHES)
+        .await
+        .map_err(|e| format!("Memory search error: {}", escape_html(&e.to_string())))?;
+
+    let mut lines = vec!["<b>Matching memories:</b>".to_owned()];
+    let mut memory_ids = Vec::new();
+    for mem in &results {
+        let Some(id) = mem.id else {
+            continue;
+        };
+        memory_ids.push(id);
+        let content: String = mem.content.chars().take(120).collect();
+        let ellipsis = if mem.content.chars().count() > 120 {
+            "..."
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{}. [{}] {}{ellipsis}",
+            memory_ids.len(),
+            mem.kind.as_str(),
+            escape_html(&content)
+        ));
+    }
+    if memory_ids.is_empty() {
+        return Err(format!("No memories match \"{}\".", escape_html(query)));
+    }
+    lines.push("\nTap a number to delete that memory.".to_owned());
+    Ok(ForgetPrompt {
+        text: lines.join("\n"),
+        memory_ids,
+    })
+}
+
+/// Rate the most recent answer in a session.
+///
+/// `args` is `up|down` optionally followed by a free-text comment.
+pub async fn handle_feedback(memory: &MemoryEngine, session_id: &str, args: &str) -> String {
+    let (rating_arg, comment) = match args.split_once(' ') {
+        Some((rating, rest)) => (
+            rating,
+            Some(rest.trim().to_owned()).filter(|c| !c.is_empty()),
+        ),
+        None => (args, None),
+    };
+    let Some(rating) = FeedbackRating::parse(rating_arg) else {
+        return "Usage: /feedback up|down [comment]".to_owned();
+    };
+
+    let turn_id = match memory.latest_turn_id(session_id).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return "No recent answer to rate.".to_owned(),
+        Err(e) => return format!("Feedback failed: {}", escape_html(&e.to_string())),
+    };
+
+    match memory
+        .rate_turn(session_id, &turn_id, rating, comment)
+        .await
+    {
+        Ok(true) => "Thanks — feedback recorded for the last answer.".to_owned(),
+        Ok(false) => "No recent answer to rate.".to_owned(),
+        Err(e) => format!("Feedback failed: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// Usage text for `/contacts`.
+const CONTACTS_USAGE: &str = "Usage: /contacts list [query] | add &lt;name&gt; | &lt;phone or @handle&gt; ... | \
+     merge &lt;keep_id&gt; &lt;drop_id&gt; | alias &lt;id&gt; &lt;alias&gt; | note &lt;id&gt; &lt;text&gt;";
+
+/// Maximum contacts shown by `/contacts list`.
+const MAX_CONTACTS_LISTED: usize = 50;
+
+/// Manage contacts: `list`, `add`, `merge`, `alias`, `note`.
+pub async fn handle_contacts(db: &sqlx::SqlitePool, args: &str) -> String {
+    let (sub, rest) = match args.split_once(' ') {
+        Some((sub, rest)) => (sub, rest.trim()),
+        None => (args, ""),
+    };
+    match sub {
+        "" | "list" => contacts_list(db, rest).await,
+        "add" => contacts_add(db, rest).await,
+        "merge" => contacts_merge(db, rest).await,
+        "alias" => contacts_alias(db, rest).await,
+        "note" => contacts_note(db, rest).await,
+        _ => CONTACTS_USAGE.to_owned(),
+    }
+}
+
+/// `/contacts list [query]`.
+async fn contacts_list(db: &sqlx::SqlitePool, query: &str) -> String {
+    let result = if query.is_empty() {
+        contacts::list_contacts(db, MAX_CONTACTS_LISTED).await
+    } else {
+        contacts::search_contacts(db, query, MAX_CONTACTS_LISTED).await
+    };
+    let found = match result {
+        Ok(c) => c,
+        Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
+    };
+    if found.is_empty() {
+        return "No contacts found.".to_owned();
+    }
+
+    let mut lines = vec![format!("<b>Contacts ({}):</b>", found.len())];
+    for contact in &found {
+        let aliases = match contact.id {
+            Some(id) => contacts::list_aliases(db, id).await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+        lines.push(format_contact_line(contact, &aliases));
+    }
+    lines.join("\n")
+}
+
+/// `/contacts add <name> | <identifier> | ...`.
+async fn contacts_add(db: &sqlx::SqlitePool, rest: &str) -> String {
+    let mut segments = rest.split('|').map(str::trim);
+    let name = segments.next().unwrap_or_default();
+    if name.is_empty() {
+        return CONTACTS_USAGE.to_owned();
+    }
+
+    let mut contact = Contact {
+        name: name.to_owned(),
+        ..Contact::default()
+    };
+    for segment in segments.filter(|s| !s.is_empty()) {
+        match contacts::parse_identifier(segment) {
+            Some(identifier) => contact.set_identifier(identifier),
+            None => {
+                return format!(
+                    "Could not parse <code>{}</code> as a phone number, @handle, or WhatsApp JID.",
+                    escape_html(segment)
+                )
+            }
+        }
+    }
+
+    let duplicates = match contacts::find_duplicates(db, &contact).await {
+        Ok(d) => d,
+        Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
+    };
+    let id = match contacts::upsert_contact(db, &contact).await {
+        Ok(id) => id,
+        Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
+    };
+
+    let mut lines = vec![format!(
+        "Added contact #{id} {}.",
+        escape_html(&contact.name)
+    )];
+    for dup in &duplicates {
+        if let Some(dup_id) = dup.id {
+            lines.push(format!(
+                "\u{26A0} Possible duplicate of #{dup_id} {} — /contacts merge {dup_id} {id}",
+                escape_html(&dup.name)
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// `/contacts merge <keep_id> <drop_id>`.
+async fn contacts_merge(db: &sqlx::SqlitePool, rest: &str) -> String {
+    let ids: Vec<i64> = rest
+        .split_whitespace()
+        .filter_map(parse_contact_id)
+        .collect();
+    let [keep_id, drop_id] = ids.as_slice() else {
+        return "Usage: /contacts merge &lt;keep_id&gt; &lt;drop_id&gt;".to_owned();
+    };
+    match contacts::merge_contacts(db, *keep_id, *drop_id).await {
+        Ok(merged) => format!(
+            "Merged #{drop_id} into #{keep_id} {}.",
+            escape_html(&merged.name)
+        ),
+        Err(e) => format!("Merge failed: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// `/contacts alias <id> <alias>`.
+async fn contacts_alias(db: &sqlx::SqlitePool, rest: &str) -> String {
+    let Some((id, alias)) = rest
+        .split_once(' ')
+        .and_then(|(id, alias)| parse_contact_id(id).map(|id| (id, alias.trim())))
+    else {
+        return "Usage: /contacts alias &lt;id&gt; &lt;alias&gt;".to_owned();
+    };
+    match contacts::add_alias(db, id, alias).await {
+        Ok(()) => format!("Alias <i>{}</i> added to #{id}.", escape_html(alias)),
+        Err(e) => format!("Alias failed: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// `/contacts note <id> <text>` — an empty text clears the notes.
+async fn contacts_note(db: &sqlx::SqlitePool, rest: &str) -> String {
+    let (id_arg, text) = rest.split_once(' ').unwrap_or((rest, ""));
+    let Some(id) = parse_contact_id(id_arg) else {
+        return "Usage: /contacts note &lt;id&gt; &lt;text&gt;".to_owned();
+    };
+    let mut contact = match contacts::load_contact(db, id).await {
+        Ok(c) => c,
+        Err(e) => return format!("Note failed: {}", escape_html(&e.to_string())),
+    };
+    let text = text.trim();
+    contact.notes = (!text.is_empty()).then(|| text.to_owned());
+    match contacts::upsert_contact(db, &contact).await {
+        Ok(_) if text.is_empty() => format!("Notes cleared for #{id}."),
+        Ok(_) => format!("Notes updated for #{id}."),
+        Err(e) => format!("Note failed: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// Parse a contact id written as `12` or `#12`.
+fn parse_contact_id(s: &str) -> Option<i64> {
+    s.trim().trim_start_matches('#').parse().ok()
+}
+
+/// Format one contact as a single HTML line.
+fn format_contact_line(contact: &Contact, aliases: &[String]) -> String {
+    let mut parts = Vec::new();
+    if let Some(ref phone) = contact.phone {
+        parts.push(escape_html(phone));
+    }
+    if let Some(ref handle) = contact.telegram_handle {
+        parts.push(format!("@{}", escape_html(handle)));
+    }
+    if let Some(ref org) = contact.organization {
+        parts.push(escape_html(org));
+    }
+    if !aliases.is_empty() {
+        parts.push(format!("aka {}", escape_html(&aliases.join(", "))));
+    }
+    let id = contact.id.map_or_else(String::new, |id| format!("#{id} "));
+    let mut line = format!("  {id}<b>{}</b>", escape_html(&contact.name));
+    if !parts.is_empty() {
+        line.push_str(" — ");
+        line.push_str(&parts.join(" · "));
+    }
+    if contact.notes.is_some() {
+        line.push_str(" \u{1F4DD}");
+    }
+    line
+}
+
+/// Usage text for `/templates`.
+const TEMPLATES_USAGE: &str =
+    "Usage: /templates list | add &lt;name&gt; &lt;text&gt; | show &lt;name&gt; | \
+     delete &lt;name&gt;\nPlaceholders: {contact_name}, {first_name}, {organization}, {objective}, \
+     or any name the agent fills in.";
+
+/// Characters of a template body shown per line in `/templates list`.
+const TEMPLATE_PREVIEW_CHARS: usize = 60;
+
+/// Manage outbound message templates: `list`, `add`, `show`, `delete`.
+pub async fn handle_templates(db: &sqlx::SqlitePool, args: &str) -> String {
+    let (sub, rest) = match args.split_once(char::is_whitespace) {
+        Some((sub, rest)) => (sub, rest.trim()),
+        None => (args, ""),
+    };
+    match sub {
+        "" | "list" => templates_list(db).await,
+        "add" => {
+            let Some((name, body)) = rest.split_once(char::is_whitespace) else {
+                return TEMPLATES_USAGE.to_owned();
+            };
+            match templates::save_template(db, name, body).await {
+                Ok(_) => {
+                    let vars = templates::placeholders(body);
+                    let mut reply = format!("Template <code>{}</code> saved.", escape_html(name));
+                    if !vars.is_empty() {
+                        reply.push_str(&format!(
+                            "\nPlaceholders: {}",
+                            escape_html(&vars.join(", "))
+                        ));
+                    }
+                    reply
+                }
+                Err(e) => format!("Save failed: {}", escape_html(&e.to_string())),
+            }
+        }
+        "show" if !rest.is_empty() => match templates::load_template(db, rest).await {
+            Ok(t) => format!(
+                "<b>Template</b> <code>{}</code>\n<pre>{}</pre>",
+                escape_html(&t.name),
+                escape_html(&t.body)
+            ),
+            Err(e) => format!("Error: {}", escape_html(&e.to_string())),
+        },
+        "delete" if !rest.is_empty() => match templates::delete_template(db, rest).await {
+            Ok(true) => format!("Template <code>{}</code> deleted.", escape_html(rest)),
+            Ok(false) => format!("Template <code>{}</code> not found.", escape_html(rest)),
+            Err(e) => format!("Error: {}", escape_html(&e.to_string())),
+        },
+        _ => TEMPLATES_USAGE.to_owned(),
+    }
+}
+
+/// `/templates list`.
+async fn templates_list(db: &sqlx::SqlitePool) -> String {
+    let all = match templates::list_templates(db).await {
+        Ok(t) => t,
+        Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
+    };
+    if all.is_empty() {
+        return format!("No templates yet.\n{TEMPLATES_USAGE}");
+    }
+
+    let mut lines = vec![format!("<b>Templates ({}):</b>", all.len())];
+    for t in &all {
+        let mut preview: String = t.body.chars().take(TEMPLATE_PREVIEW_CHARS).collect();
+        if t.body.chars().count() > TEMPLATE_PREVIEW_CHARS {
+            preview.push('\u{2026}');
+        }
+        lines.push(format!(
+            "  <code>{}</code> — {}",
+            escape_html(&t.name),
+            escape_html(&preview.replace('\n', " "))
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Usage text for `/soul`.
+const SOUL_USAGE: &str = "Usage: /soul history | revert &lt;version&gt;";
+
+/// Soul versions listed by `/soul history`.
+const SOUL_HISTORY_LIMIT: usize = 10;
+
+/// Characters of a soul shown per line in `/soul history`.
+const SOUL_PREVIEW_CHARS: usize = 60;
+
+/// Inspect or roll back soul versions: `history`, `revert <version>`.
+pub async fn handle_soul(
+    memory: &MemoryEngine,
+    agent_toml: &std::path::Path,
+    args: &str,
+    is_owner: bool,
+) -> String {
+    let (sub, rest) = match args.split_once(char::is_whitespace) {
+        Some((sub, rest)) => (sub, rest.trim()),
+        None => (args, ""),
+    };
+    match sub {
+        "" | "history" => soul_history(memory.pool()).await,
+        "revert" => {
+            if !is_owner {
+                return "Only the owner can change the soul.".to_owned();
+            }
+            let Ok(id) = rest.trim_start_matches('v').parse::<i64>() else {
+                return SOUL_USAGE.to_owned();
+            };
+            match soul::revert(memory, id, agent_toml).await {
+                Ok(Some((previous, current))) => {
+                    let old = previous.map(|p| p.content).unwrap_or_default();
+                    format!(
+                        "Soul reverted to v{id} (now v{}).\n{}",
+                        current.id,
+                        soul::format_diff_html(&old, &current.content)
+                    )
+                }
+                Ok(None) => format!("Soul version v{id} not found."),
+                Err(e) => format!("Revert failed: {}", escape_html(&e.to_string())),
+            }
+        }
+        _ => SOUL_USAGE.to_owned(),
+    }
+}
+
+/// `/soul history`.
+async fn soul_history(db: &sqlx::SqlitePool) -> String {
+    let versions = match soul::history(db, SOUL_HISTORY_LIMIT).await {
+        Ok(v) => v,
+        Err(e) => return format!("Error: {}", escape_html(&e.to_string())),
+    };
+    if versions.is_empty() {
+        return "No soul versions recorded yet.".to_owned();
+    }
+
+    let mut lines = vec!["<b>Soul versions:</b>".to_owned()];
+    for v in &versions {
+        let flat = v.content.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut preview: String = flat.chars().take(SOUL_PREVIEW_CHARS).collect();
+        if flat.chars().count() > SOUL_PREVIEW_CHARS {
+            preview.push('\u{2026}');
+        }
+        lines.push(format!(
+            "  v{} [{}] {} — {}",
+            v.id,
+            v.status.as_str(),
+            escape_html(&v.created_at),
+            escape_html(&preview)
+        ));
+    }
+    lines.join("\n")
+}
+
+const EXPORT_USAGE: &str = "Usage: /export [session|all] [md|json]";
+
+/// A transcript written to disk, ready to be sent as a document.
+#[derive(Debug)]
+pub struct TranscriptExport {
+    /// Path of the rendered file.
+    pub path: std::path::PathBuf,
+    /// Caption describing what was exported.
+    pub caption: String,
+}
+
+/// Export conversation history as a redacted Markdown or JSON file.
+///
+/// Returns the written file, or an HTML message explaining why nothing
+/// was exported. Exporting `all` sessions is restricted to the owner.
+pub async fn handle_export(
+    db: &sqlx::SqlitePool,
+    exports_dir: &std::path::Path,
+    redactor: &Redactor,
+    args: &str,
+    user_id: i64,
+    is_owner: bool,
+) -> Result<TranscriptExport, String> {
+    let mut scope = ExportScope::Session(format!("user_{user_id}"));
+    let mut format = ExportFormat::Markdown;
+    for arg in args.split_whitespace() {
+        match arg {
+            "session" => scope = ExportScope::Session(format!("user_{user_id}")),
+            "all" => scope = ExportScope::All,
+            other => match ExportFormat::parse(other) {
+                Some(f) => format = f,
+                None => return Err(EXPORT_USAGE.to_owned()),
+            },
+        }
+    }
+    if scope == ExportScope::All && !is_owner {
+        return Err("Only the owner can export all sessions.".to_owned());
+    }
+
+    let entries = transcript::load_transcript(db, &scope)
+        .await
+        .map_err(|e| format!("Export failed: {}", escape_html(&e.to_string())))?;
+    if entries.is_empty() {
+        return Err("No conversation history to export.".to_owned());
+    }
+
+    let path = transcript::write_transcript(exports_dir, &entries, &scope, format, redactor)
+        .map_err(|e| format!("Export failed: {}", escape_html(&e.to_string())))?;
+    Ok(TranscriptExport {
+        path,
+        caption: format!(
+            "Exported {} messages ({}, {}).",
+            entries.len(),
+            escape_html(scope.label()),
+            format.extension()
+        ),
+    })
+}
+
 /// List all dynamic tools with descriptions.
 pub fn handle_tools(registry: &DynamicToolRegistry) -> String {
     let defs = registry.all_definitions();
@@ -195,6 +846,7 @@ pub async fn handle_revert(executor: &dyn Executor) -> String {
     let opts = crate::executor::ExecOptions {
         timeout: std::time::Duration::from_secs(30),
         working_dir: Some(std::path::PathBuf::from("/scripts")),
+        restrictions: None,
     };
 
     match executor.execute("git revert HEAD --no-edit", opts).await {
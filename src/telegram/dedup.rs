@@ -0,0 +1,48 @@
+//! Inbound update deduplication.
+//!
+//! After a reconnect Telegram can deliver the same update twice. Every
+//! processed `update_id` is recorded in `telegram_updates`; an update seen
+//! within [`DEDUP_WINDOW`] is dropped before any handler runs. Telegram
+//! keeps undelivered updates for 24 hours, so older IDs are pruned.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::trace;
+
+use crate::memory::writer::WriteOp;
+use crate::memory::{MemoryEngine, MemoryError};
+use crate::messaging::outbound_queue::format_timestamp;
+
+/// How long processed update IDs are remembered.
+pub const DEDUP_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Record an update and report whether this is its first delivery.
+///
+/// Also forgets updates older than [`DEDUP_WINDOW`].
+///
+/// # Errors
+///
+/// Returns [`MemoryError`] if the write fails.
+pub async fn first_delivery(
+    memory: &MemoryEngine,
+    update_id: u32,
+    now: DateTime<Utc>,
+) -> Result<bool, MemoryError> {
+    let cutoff = chrono::Duration::from_std(DEDUP_WINDOW)
+        .ok()
+        .and_then(|window| now.checked_sub_signed(window))
+        .unwrap_or(now);
+    let inserted = memory
+        .write_and_wait(|reply| WriteOp::RecordTelegramUpdate {
+            update_id: i64::from(update_id),
+            received_at: format_timestamp(now),
+            prune_before: format_timestamp(cutoff),
+            reply,
+        })
+        .await?;
+
+    let first = inserted == 1;
+    trace!(update_id, first, "telegram update recorded");
+    Ok(first)
+}
@@ -4,23 +4,33 @@
 //! slash command handling, and the main teloxide-based bot event loop.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::Utc;
 use teloxide::dispatching::UpdateFilterExt;
 use teloxide::prelude::*;
-use teloxide::types::{InputFile, ParseMode};
+use teloxide::types::{InlineKeyboardMarkup, InputFile, ParseMode};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use crate::agent::approval::{ApprovalManager, ApprovalResult};
-use crate::agent::{SessionRouter, TelegramOutbound};
-use crate::config::{Config, RuntimePaths};
+use crate::agent::reload::{self, ConfigReloader};
+use crate::agent::soul;
+use crate::agent::{Keyboard, SessionRouter, TelegramOutbound};
+use crate::config::RuntimePaths;
+use crate::executor::redactor::Redactor;
 use crate::executor::Executor;
+use crate::heartbeat::memory_review;
+use crate::memory::feedback::FeedbackRating;
 use crate::memory::MemoryEngine;
+use crate::observer::skills;
 use crate::tools::registry::DynamicToolRegistry;
 
 pub mod commands;
+pub mod dedup;
 pub mod input_guard;
 pub mod media;
+pub mod outbox;
 pub mod ui;
 
 /// Check whether a response text should be suppressed (not sent to Telegram).
@@ -32,6 +42,18 @@ pub fn is_no_reply(text: &str) -> bool {
     trimmed == "[NO_REPLY]" || trimmed.starts_with("[NO_REPLY]")
 }
 
+/// Inline markup for an outbound [`Keyboard`].
+fn keyboard_markup(keyboard: &Keyboard) -> InlineKeyboardMarkup {
+    match keyboard {
+        Keyboard::Feedback(turn_id) => ui::feedback_keyboard(turn_id),
+        Keyboard::Review(older_id, newer_id) => ui::memory_review_keyboard(*older_id, *newer_id),
+        Keyboard::Skill(memory_id) => ui::skill_keyboard(*memory_id),
+        Keyboard::Soul(version_id) => ui::soul_keyboard(*version_id),
+        Keyboard::ToolDisabled(name) => ui::tool_disabled_keyboard(name),
+        Keyboard::HeldMessage => ui::held_message_keyboard(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shared state for handler injection
 // ---------------------------------------------------------------------------
@@ -39,10 +61,11 @@ pub fn is_no_reply(text: &str) -> bool {
 /// Shared dependencies injected into teloxide handlers via `dptree::deps!`.
 #[derive(Clone)]
 struct SharedState {
-    config: Arc<Config>,
+    reloader: Arc<ConfigReloader>,
     session_router: Arc<SessionRouter>,
     approval_manager: Arc<ApprovalManager>,
     known_secrets: Vec<String>,
+    redactor: Redactor,
     executor: Arc<dyn Executor>,
     memory: Arc<MemoryEngine>,
     registry: Arc<DynamicToolRegistry>,
@@ -56,20 +79,22 @@ struct SharedState {
 /// Run the Telegram bot adapter.
 ///
 /// Starts three concurrent tasks:
-/// 1. **Inbound handler** -- receives messages, checks allowed_users, scans for
-///    credentials, routes to sessions
+/// 1. **Inbound handler** -- drops redelivered updates ([`dedup`]), checks
+///    allowed_users, scans for credentials, routes to sessions
 /// 2. **Callback handler** -- processes inline keyboard callbacks for approvals
-/// 3. **Outbound sender** -- sends agent responses back to Telegram
+/// 3. **Outbound sender** -- sends agent responses back to Telegram, queueing
+///    them in the [`outbox`] while the Bot API is unreachable
 ///
 /// Blocks until the bot is stopped (Ctrl+C).
 #[allow(clippy::too_many_arguments)]
 pub async fn run_telegram(
     bot_token: &str,
-    config: Arc<Config>,
+    reloader: Arc<ConfigReloader>,
     session_router: Arc<SessionRouter>,
     approval_manager: Arc<ApprovalManager>,
-    mut outbound_rx: mpsc::Receiver<TelegramOutbound>,
+    outbound_rx: mpsc::Receiver<TelegramOutbound>,
     known_secrets: Vec<String>,
+    redactor: Redactor,
     executor: Arc<dyn Executor>,
     memory: Arc<MemoryEngine>,
     registry: Arc<DynamicToolRegistry>,
@@ -78,49 +103,15 @@ pub async fn run_telegram(
     let bot = Bot::new(bot_token);
 
     // Spawn outbound sender task
-    let outbound_bot = bot.clone();
-    let _outbound_handle = tokio::spawn(async move {
-        while let Some(msg) = outbound_rx.recv().await {
-            let chat_id = ChatId(msg.user_id);
-
-            if let Some(ref text) = msg.text {
-                // Suppress [NO_REPLY] responses (used by agent to signal silence).
-                if is_no_reply(text) {
-                    info!(
-                        event = "no_reply",
-                        user_id = msg.user_id,
-                        "suppressing [NO_REPLY] response"
-                    );
-                    continue;
-                }
-
-                let mut req = outbound_bot
-                    .send_message(chat_id, text)
-                    .parse_mode(ParseMode::Html);
-
-                if let Some((ref approval_id, _)) = msg.approval_keyboard {
-                    req = req.reply_markup(ui::approval_keyboard(approval_id));
-                }
-
-                if let Err(e) = req.await {
-                    warn!(error = %e, "failed to send telegram message");
-                }
-            }
-
-            if let Some(ref file_path) = msg.file_path {
-                let input_file = InputFile::file(file_path);
-                if let Err(e) = outbound_bot.send_document(chat_id, input_file).await {
-                    warn!(error = %e, "failed to send telegram file");
-                }
-            }
-        }
-    });
+    let _outbound_handle =
+        tokio::spawn(run_outbound(bot.clone(), outbound_rx, Arc::clone(&memory)));
 
     let shared = SharedState {
-        config,
+        reloader,
         session_router,
         approval_manager,
         known_secrets,
+        redactor,
         executor,
         memory,
         registry,
@@ -129,6 +120,7 @@ pub async fn run_telegram(
 
     // Build dptree handler schema
     let handler = dptree::entry()
+        .filter_async(is_first_delivery)
         .branch(Update::filter_message().endpoint(handle_message))
         .branch(Update::filter_callback_query().endpoint(handle_callback));
 
@@ -144,10 +136,234 @@ pub async fn run_telegram(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Outbound sender
+// ---------------------------------------------------------------------------
+
+/// A message that did not (fully) reach Telegram.
+struct Undelivered {
+    /// What is left to send.
+    remaining: TelegramOutbound,
+    /// Whether the text part already went out.
+    text_sent: bool,
+    /// Why the send failed.
+    error: teloxide::RequestError,
+}
+
+/// Whether a failed send is worth retrying later.
+fn is_transient(error: &teloxide::RequestError) -> bool {
+    matches!(
+        error,
+        teloxide::RequestError::Network(_) | teloxide::RequestError::RetryAfter(_)
+    )
+}
+
+/// Minimum wait Telegram asked for before the next attempt.
+fn retry_after(error: &teloxide::RequestError) -> Duration {
+    match error {
+        teloxide::RequestError::RetryAfter(secs) => secs.duration(),
+        _ => Duration::ZERO,
+    }
+}
+
+/// Send agent output to Telegram, falling back to the [`outbox`].
+///
+/// Messages for a chat with queued messages go to the back of that queue so
+/// the chat never sees them out of order. The first tick flushes whatever a
+/// previous run left behind.
+async fn run_outbound(
+    bot: Bot,
+    mut outbound_rx: mpsc::Receiver<TelegramOutbound>,
+    memory: Arc<MemoryEngine>,
+) {
+    let mut flush = tokio::time::interval(outbox::FLUSH_INTERVAL);
+    flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            received = outbound_rx.recv() => {
+                let Some(msg) = received else { break };
+
+                // Suppress [NO_REPLY] responses (used by agent to signal silence).
+                if msg.text.as_deref().is_some_and(is_no_reply) {
+                    info!(
+                        event = "no_reply",
+                        user_id = msg.user_id,
+                        "suppressing [NO_REPLY] response"
+                    );
+                    continue;
+                }
+
+                match outbox::has_pending(memory.pool(), msg.user_id).await {
+                    Ok(true) => {
+                        if let Err(e) = outbox::enqueue(&memory, &msg).await {
+                            warn!(error = %e, "failed to queue telegram message behind pending ones");
+                        }
+                        continue;
+                    }
+                    Ok(false) => {}
+                    Err(e) => warn!(error = %e, "failed to check telegram outbox"),
+                }
+
+                if let Err(undelivered) = deliver(&bot, msg).await {
+                    queue_undelivered(&memory, undelivered).await;
+                }
+            }
+            _ = flush.tick() => flush_outbox(&bot, &memory).await,
+        }
+    }
+}
+
+/// Send a message's text (with keyboard) and then its file.
+async fn deliver(bot: &Bot, msg: TelegramOutbound) -> Result<(), Undelivered> {
+    let chat_id = ChatId(msg.user_id);
+
+    if let Some(ref text) = msg.text {
+        let mut req = bot.send_message(chat_id, text).parse_mode(ParseMode::Html);
+
+        if let Some((ref approval_id, _)) = msg.approval_keyboard {
+            req = req.reply_markup(ui::approval_keyboard(approval_id));
+        } else if let Some(ref keyboard) = msg.keyboard {
+            req = req.reply_markup(keyboard_markup(keyboard));
+        }
+
+        if let Err(error) = req.await {
+            return Err(Undelivered {
+                remaining: msg,
+                text_sent: false,
+                error,
+            });
+        }
+    }
+
+    if let Some(ref file_path) = msg.file_path {
+        let input_file = InputFile::file(file_path);
+        if let Err(error) = bot.send_document(chat_id, input_file).await {
+            let text_sent = msg.text.is_some();
+            return Err(Undelivered {
+                remaining: TelegramOutbound {
+                    text: None,
+                    approval_keyboard: None,
+                    keyboard: None,
+                    ..msg
+                },
+                text_sent,
+                error,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Queue a message whose first send failed, or drop it if retrying is pointless.
+async fn queue_undelivered(memory: &MemoryEngine, undelivered: Undelivered) {
+    let Undelivered {
+        remaining, error, ..
+    } = undelivered;
+    if !is_transient(&error) {
+        warn!(error = %error, "failed to send telegram message");
+        return;
+    }
+
+    let queued = match outbox::enqueue(memory, &remaining).await {
+        Ok(id) => id,
+        Err(e) => {
+            warn!(error = %e, send_error = %error, "telegram message lost: could not queue it");
+            return;
+        }
+    };
+    info!(id = queued, error = %error, "telegram unreachable, message queued for retry");
+    if let Err(e) = outbox::record_failure(
+        memory,
+        queued,
+        &error.to_string(),
+        Utc::now(),
+        retry_after(&error),
+    )
+    .await
+    {
+        warn!(error = %e, "failed to record telegram delivery attempt");
+    }
+}
+
+/// Retry due outbox messages, oldest first per chat.
+async fn flush_outbox(bot: &Bot, memory: &MemoryEngine) {
+    let due = match outbox::due(memory.pool(), Utc::now(), outbox::FLUSH_BATCH).await {
+        Ok(due) => due,
+        Err(e) => {
+            warn!(error = %e, "failed to read telegram outbox");
+            return;
+        }
+    };
+
+    for entry in due {
+        let result = match deliver(bot, entry.message).await {
+            Ok(()) => {
+                info!(
+                    id = entry.id,
+                    attempts = entry.attempts,
+                    "queued telegram message delivered"
+                );
+                outbox::mark_sent(memory, entry.id).await
+            }
+            Err(undelivered) if is_transient(&undelivered.error) => {
+                if undelivered.text_sent {
+                    if let Err(e) =
+                        outbox::update_message(memory, entry.id, &undelivered.remaining).await
+                    {
+                        warn!(error = %e, "failed to update partially sent telegram message");
+                    }
+                }
+                outbox::record_failure(
+                    memory,
+                    entry.id,
+                    &undelivered.error.to_string(),
+                    Utc::now(),
+                    retry_after(&undelivered.error),
+                )
+                .await
+                .map(|gave_up| {
+                    if gave_up {
+                        warn!(id = entry.id, error = %undelivered.error, "giving up on queued telegram message");
+                    }
+                })
+            }
+            Err(undelivered) => {
+                warn!(id = entry.id, error = %undelivered.error, "queued telegram message rejected");
+                outbox::mark_failed(memory, entry.id, &undelivered.error.to_string()).await
+            }
+        };
+        if let Err(e) = result {
+            warn!(error = %e, "failed to update telegram outbox");
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Message handler
 // ---------------------------------------------------------------------------
 
+/// Dispatcher filter letting each `update_id` through only once.
+///
+/// Fails open: if the database is unavailable the update is handled.
+async fn is_first_delivery(update: Update, state: SharedState) -> bool {
+    match dedup::first_delivery(&state.memory, update.id.0, Utc::now()).await {
+        Ok(true) => true,
+        Ok(false) => {
+            info!(
+                update_id = update.id.0,
+                "dropping redelivered telegram update"
+            );
+            false
+        }
+        Err(e) => {
+            warn!(error = %e, "failed to record telegram update; handling it anyway");
+            true
+        }
+    }
+}
+
 /// Handle an incoming Telegram message.
 ///
 /// Checks allowed_users, dispatches slash commands, and routes
@@ -165,16 +381,10 @@ async fn handle_message(bot: Bot, msg: Message, state: SharedState) -> ResponseR
     debug!(user_id, "telegram message received");
 
     // Check if user is in allowed_users
-    if !state
-        .config
-        .channels
-        .telegram
-        .allowed_users
-        .contains(&user_id)
-    {
+    if !state.reloader.is_allowed(user_id) {
         warn!(
             user_id,
-            allowed = ?state.config.channels.telegram.allowed_users,
+            allowed = ?state.reloader.allowed_users(),
             "message dropped: user not in allowed_users"
         );
         return Ok(());
@@ -212,10 +422,12 @@ async fn handle_message(bot: Bot, msg: Message, state: SharedState) -> ResponseR
 
     // Handle slash commands
     if text.starts_with('/') {
-        let reply = dispatch_command(&text, &state, user_id).await;
-        bot.send_message(msg.chat.id, reply)
-            .parse_mode(ParseMode::Html)
-            .await?;
+        let reply = dispatch_command(&text, &state, user_id, &bot, msg.chat.id).await;
+        if !reply.is_empty() {
+            bot.send_message(msg.chat.id, reply)
+                .parse_mode(ParseMode::Html)
+                .await?;
+        }
         return Ok(());
     }
 
@@ -248,7 +460,16 @@ async fn handle_message(bot: Bot, msg: Message, state: SharedState) -> ResponseR
 // ---------------------------------------------------------------------------
 
 /// Parse and dispatch a slash command, returning the HTML response.
-async fn dispatch_command(text: &str, state: &SharedState, user_id: i64) -> String {
+///
+/// Commands that produce files (`/export`) or keyboards (`/forget`) send
+/// them to `chat_id` directly; an empty return means nothing is left to send.
+async fn dispatch_command(
+    text: &str,
+    state: &SharedState,
+    user_id: i64,
+    bot: &Bot,
+    chat_id: ChatId,
+) -> String {
     // Strip the leading "/" and split into command and args
     let without_slash = &text[1..];
     // Handle bot-mention suffixes like "/help@wintermute_bot"
@@ -265,10 +486,18 @@ async fn dispatch_command(text: &str, state: &SharedState, user_id: i64) -> Stri
             let had_session = state.session_router.remove_session(user_id).await;
             commands::handle_reset(had_session)
         }
+        "status" if !args.is_empty() => {
+            let session_id = format!("user_{user_id}");
+            commands::handle_status_tools(state.memory.pool(), &state.redactor, &session_id, args)
+                .await
+        }
         "status" => {
             let session_count = state.session_router.session_count().await;
             commands::handle_status(&*state.executor, &state.memory, session_count).await
         }
+        "budget" if args == "detail" => {
+            commands::handle_budget_detail(state.memory.pool(), chrono::Utc::now()).await
+        }
         "budget" => {
             // We don't have per-session budget info from this context,
             // so show daily-level summary with zeros for session values.
@@ -277,6 +506,70 @@ async fn dispatch_command(text: &str, state: &SharedState, user_id: i64) -> Stri
         "memory" => commands::handle_memory(&state.memory).await,
         "memory_pending" => commands::handle_memory_pending(&state.memory).await,
         "memory_undo" => commands::handle_memory_undo(&state.memory).await,
+        "remember" => match input_guard::scan_message(args, &state.known_secrets) {
+            input_guard::GuardAction::Pass(fact) => {
+                commands::handle_remember(&state.memory, &format!("user_{user_id}"), &fact).await
+            }
+            _ => "That looks like a credential. Add it to your .env file instead.".to_owned(),
+        },
+        "forget" => match commands::handle_forget(&state.memory, args).await {
+            Ok(prompt) => {
+                let sent = bot
+                    .send_message(chat_id, prompt.text)
+                    .parse_mode(ParseMode::Html)
+                    .reply_markup(ui::forget_keyboard(&prompt.memory_ids))
+                    .await;
+                match sent {
+                    Ok(_) => String::new(),
+                    Err(e) => {
+                        warn!(error = %e, "failed to send forget prompt");
+                        "Forget failed: could not send the matches.".to_owned()
+                    }
+                }
+            }
+            Err(message) => message,
+        },
+        "feedback" => {
+            let session_id = format!("user_{user_id}");
+            commands::handle_feedback(&state.memory, &session_id, args).await
+        }
+        "contacts" => commands::handle_contacts(state.memory.pool(), args).await,
+        "templates" => commands::handle_templates(state.memory.pool(), args).await,
+        "soul" => {
+            let is_owner = state.reloader.owner() == Some(user_id);
+            commands::handle_soul(&state.memory, &state.paths.agent_toml, args, is_owner).await
+        }
+        "export" => {
+            let is_owner = state.reloader.owner() == Some(user_id);
+            let exports_dir = state.paths.data_dir.join("exports");
+            match commands::handle_export(
+                state.memory.pool(),
+                &exports_dir,
+                &state.redactor,
+                args,
+                user_id,
+                is_owner,
+            )
+            .await
+            {
+                Ok(export) => {
+                    let sent = bot
+                        .send_document(chat_id, InputFile::file(&export.path))
+                        .await;
+                    if let Err(e) = std::fs::remove_file(&export.path) {
+                        warn!(error = %e, "failed to remove exported transcript");
+                    }
+                    match sent {
+                        Ok(_) => export.caption,
+                        Err(e) => {
+                            warn!(error = %e, "failed to send exported transcript");
+                            "Export failed: could not send the file.".to_owned()
+                        }
+                    }
+                }
+                Err(message) => message,
+            }
+        }
         "tools" => {
             if args.is_empty() {
                 commands::handle_tools(&state.registry)
@@ -294,6 +587,18 @@ async fn dispatch_command(text: &str, state: &SharedState, user_id: i64) -> Stri
             )
             .await
         }
+        "reload" => {
+            if state.reloader.owner() != Some(user_id) {
+                return "Only the owner can reload the config.".to_owned();
+            }
+            match state.reloader.reload().await {
+                Ok(report) => reload::format_report(&report),
+                Err(e) => format!(
+                    "Config reload failed, keeping the running config: {}",
+                    ui::escape_html(&e.to_string())
+                ),
+            }
+        }
         _ => format!("Unknown command: /{}", ui::escape_html(command)),
     }
 }
@@ -302,7 +607,7 @@ async fn dispatch_command(text: &str, state: &SharedState, user_id: i64) -> Stri
 // Callback query handler
 // ---------------------------------------------------------------------------
 
-/// Handle inline keyboard callback queries for approval responses.
+/// Handle inline keyboard callback queries for approval and feedback responses.
 async fn handle_callback(bot: Bot, query: CallbackQuery, state: SharedState) -> ResponseResult<()> {
     let user_id = {
         let uid_u64 = query.from.id.0;
@@ -317,6 +622,185 @@ async fn handle_callback(bot: Bot, query: CallbackQuery, state: SharedState) ->
         }
     };
 
+    // Feedback buttons: "f+:{turn_id}" / "f-:{turn_id}"
+    let feedback = if let Some(id) = data.strip_prefix("f+:") {
+        Some((FeedbackRating::Up, id))
+    } else {
+        data.strip_prefix("f-:")
+            .map(|id| (FeedbackRating::Down, id))
+    };
+    if let Some((rating, turn_id)) = feedback {
+        let answer_text = if !state.reloader.is_allowed(user_id) {
+            "You are not authorized to rate answers."
+        } else {
+            let session_id = format!("user_{user_id}");
+            match state
+                .memory
+                .rate_turn(&session_id, turn_id, rating, None)
+                .await
+            {
+                Ok(true) => "Thanks for the feedback.",
+                Ok(false) => "That answer can no longer be rated.",
+                Err(e) => {
+                    warn!(error = %e, "failed to record feedback");
+                    "Failed to record feedback."
+                }
+            }
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Skill proposal buttons: "sk+:{memory_id}" / "sk-:{memory_id}"
+    let skill = if let Some(id) = data.strip_prefix("sk+:") {
+        Some((true, id))
+    } else {
+        data.strip_prefix("sk-:").map(|id| (false, id))
+    };
+    if let Some((approve, id)) = skill {
+        let answer_text = match id.parse::<i64>() {
+            Ok(_) if state.reloader.owner() != Some(user_id) => "Only the owner can manage skills.",
+            Ok(memory_id) => {
+                match skills::resolve_proposal(&state.memory, memory_id, approve).await {
+                    Ok(true) if approve => "Skill saved.",
+                    Ok(true) => "Skill discarded.",
+                    Ok(false) => "That skill proposal was already decided.",
+                    Err(e) => {
+                        warn!(error = %e, "failed to resolve skill proposal");
+                        "Failed to update skill."
+                    }
+                }
+            }
+            Err(_) => "Unknown action",
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Soul change buttons: "sv+:{version_id}" / "sv-:{version_id}"
+    if let Some((approve, version_id)) = soul::parse_soul_callback(data) {
+        let answer_text = if state.reloader.owner() != Some(user_id) {
+            "Only the owner can change the soul."
+        } else {
+            let result = if approve {
+                soul::approve(&state.memory, version_id).await
+            } else {
+                soul::reject(&state.memory, version_id, &state.paths.agent_toml).await
+            };
+            match result {
+                Ok(true) if approve => "Soul change applied.",
+                Ok(true) => "Soul change rejected.",
+                Ok(false) => "That change was already decided.",
+                Err(e) => {
+                    warn!(error = %e, "failed to resolve soul change");
+                    "Failed to update the soul."
+                }
+            }
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Forget buttons: "mf:{memory_id}"
+    if let Some(memory_id) = ui::parse_forget_callback(data) {
+        let answer_text = if !state.reloader.is_allowed(user_id) {
+            "You are not authorized to change memories."
+        } else {
+            match state
+                .memory
+                .forget(memory_id, &format!("user_{user_id}"))
+                .await
+            {
+                Ok(()) => "Forgotten.",
+                Err(e) => {
+                    warn!(error = %e, "failed to forget memory");
+                    "Failed to forget memory."
+                }
+            }
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Disabled tool buttons: "te:{tool}" / "tf:{tool}"
+    if let Some((action, name)) = ui::parse_tool_callback(data) {
+        let answer_text = if state.reloader.owner() != Some(user_id) {
+            "Only the owner can manage tools."
+        } else {
+            match action {
+                ui::ToolAction::Enable if state.registry.enable(name) => "Tool re-enabled.",
+                ui::ToolAction::Enable => "That tool no longer exists.",
+                ui::ToolAction::Fix => match state.registry.get(name) {
+                    Some(schema) => {
+                        let request = state.redactor.redact(&ui::tool_fix_request(&schema));
+                        match state.session_router.route_message(user_id, request).await {
+                            Ok(()) => "Asked the agent to fix it.",
+                            Err(e) => {
+                                warn!(error = %e, "failed to route tool fix request");
+                                "Failed to reach the agent."
+                            }
+                        }
+                    }
+                    None => "That tool no longer exists.",
+                },
+            }
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Held message buttons: "hm:send" / "hm:drop"
+    if let Some(send) = ui::parse_held_callback(data) {
+        let answer_text = match state.session_router.release_held(user_id, send).await {
+            Ok(0) => "That message is no longer waiting.".to_owned(),
+            Ok(1) if send => "Sent. I'll answer without running commands.".to_owned(),
+            Ok(1) => "Message dropped.".to_owned(),
+            Ok(n) if send => format!("Sent {n} messages. I'll answer without running commands."),
+            Ok(n) => format!("{n} messages dropped."),
+            Err(e) => {
+                warn!(error = %e, "failed to release held message");
+                "Failed to reach the agent.".to_owned()
+            }
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
+    // Memory review buttons: "mr:{action}:{older_id}:{newer_id}"
+    if data.starts_with("mr:") {
+        let answer_text = match memory_review::parse_review_callback(data) {
+            Some(_) if state.reloader.owner() != Some(user_id) => {
+                "Only the owner can review memories."
+            }
+            Some((action, older_id, newer_id)) => {
+                match memory_review::apply_review(&state.memory, action, older_id, newer_id).await {
+                    Ok(()) => "Memory review applied.",
+                    Err(e) => {
+                        warn!(error = %e, "failed to apply memory review");
+                        "Failed to apply memory review."
+                    }
+                }
+            }
+            None => "Unknown action",
+        };
+        bot.answer_callback_query(&query.id)
+            .text(answer_text)
+            .await?;
+        return Ok(());
+    }
+
     // Parse callback data: "a:{id}" for approve, "d:{id}" for deny
     let (approved, approval_id) = if let Some(id) = data.strip_prefix("a:") {
         (true, id)
@@ -339,6 +823,14 @@ async fn handle_callback(bot: Bot, query: CallbackQuery, state: SharedState) ->
         ApprovalResult::Expired => "Approval expired.".to_owned(),
         ApprovalResult::NotFound => "Approval not found.".to_owned(),
         ApprovalResult::WrongUser => "You are not authorized for this approval.".to_owned(),
+        ApprovalResult::AlreadyResolved {
+            approved: true,
+            tool_name,
+        } => format!("Already approved: {tool_name}"),
+        ApprovalResult::AlreadyResolved {
+            approved: false,
+            tool_name,
+        } => format!("Already denied: {tool_name}"),
     };
 
     // Route the result to the session
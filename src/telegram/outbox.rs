@@ -0,0 +1,240 @@
+//! Persistent outbox for Telegram messages that could not be sent.
+//!
+//! When the Bot API is unreachable, outbound messages are stored in
+//! `telegram_outbox` instead of being dropped, and retried with exponential
+//! backoff. Delivery is ordered per chat: while a chat has queued messages,
+//! new ones for that chat wait behind them, and only the oldest queued
+//! message of each chat is ever due. The adapter flushes the outbox on
+//! startup and every [`FLUSH_INTERVAL`] after that.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::{debug, trace};
+
+use crate::agent::TelegramOutbound;
+use crate::memory::writer::WriteOp;
+use crate::memory::{MemoryEngine, MemoryError};
+use crate::messaging::outbound_queue::format_timestamp;
+
+/// How often the adapter retries queued messages.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum queued messages retried per flush.
+pub const FLUSH_BATCH: i64 = 20;
+
+/// Delivery attempts before a queued message is marked failed.
+pub const MAX_ATTEMPTS: i64 = 20;
+
+/// Delay after the first failed attempt; doubles with each further failure.
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Upper bound for the retry delay.
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+/// Errors from the outbox.
+#[derive(Debug, thiserror::Error)]
+pub enum OutboxError {
+    /// Database operation failed.
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// A message could not be serialised or a stored payload is corrupt.
+    #[error("invalid payload: {0}")]
+    Payload(#[from] serde_json::Error),
+
+    /// The memory writer could not apply a change.
+    #[error("write failed: {0}")]
+    Write(#[from] MemoryError),
+}
+
+/// A message waiting in the outbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    /// Outbox entry ID.
+    pub id: i64,
+    /// The message to deliver.
+    pub message: TelegramOutbound,
+    /// Delivery attempts made so far.
+    pub attempts: i64,
+}
+
+/// Retry delay after `attempts` failed attempts.
+pub fn backoff(attempts: i64) -> Duration {
+    let doublings = u32::try_from(attempts.saturating_sub(1).max(0)).unwrap_or(u32::MAX);
+    let factor = 2_u32.checked_pow(doublings).unwrap_or(u32::MAX);
+    BASE_BACKOFF.saturating_mul(factor).min(MAX_BACKOFF)
+}
+
+/// Store a message for delivery, due immediately.
+///
+/// # Errors
+///
+/// Returns [`OutboxError`] on write or serialisation failure.
+pub async fn enqueue(
+    memory: &MemoryEngine,
+    message: &TelegramOutbound,
+) -> Result<i64, OutboxError> {
+    let payload = serde_json::to_string(message)?;
+    let id = memory
+        .write_and_wait(|reply| WriteOp::QueueTelegramMessage {
+            chat_id: message.user_id,
+            payload,
+            reply,
+        })
+        .await?;
+    trace!(id, chat_id = message.user_id, "telegram message queued");
+    Ok(id)
+}
+
+/// Whether `chat_id` has messages waiting, which new messages must queue behind.
+///
+/// # Errors
+///
+/// Returns [`OutboxError::Database`] on SQLite failure.
+pub async fn has_pending(db: &SqlitePool, chat_id: i64) -> Result<bool, OutboxError> {
+    let row: Option<(i64,)> = sqlx::query_as(
+        "SELECT 1 FROM telegram_outbox WHERE status = 'queued' AND chat_id = ?1 LIMIT 1",
+    )
+    .bind(chat_id)
+    .fetch_optional(db)
+    .await?;
+    Ok(row.is_some())
+}
+
+/// Load the oldest queued message of each chat whose retry time has passed.
+///
+/// # Errors
+///
+/// Returns [`OutboxError`] on SQLite failure or a corrupt payload.
+pub async fn due(
+    db: &SqlitePool,
+    now: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<OutboxEntry>, OutboxError> {
+    let rows: Vec<(i64, String, i64)> = sqlx::query_as(
+        "SELECT o.id, o.payload, o.attempts FROM telegram_outbox o \
+         WHERE o.status = 'queued' AND o.next_attempt_at <= ?1 \
+         AND o.id = (SELECT MIN(id) FROM telegram_outbox \
+                     WHERE status = 'queued' AND chat_id = o.chat_id) \
+         ORDER BY o.id LIMIT ?2",
+    )
+    .bind(format_timestamp(now))
+    .bind(limit)
+    .fetch_all(db)
+    .await?;
+
+    rows.into_iter()
+        .map(|(id, payload, attempts)| {
+            Ok(OutboxEntry {
+                id,
+                message: serde_json::from_str(&payload)?,
+                attempts,
+            })
+        })
+        .collect()
+}
+
+/// Count messages still waiting for delivery.
+///
+/// # Errors
+///
+/// Returns [`OutboxError::Database`] on SQLite failure.
+pub async fn pending_count(db: &SqlitePool) -> Result<i64, OutboxError> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM telegram_outbox WHERE status = 'queued'")
+            .fetch_one(db)
+            .await?;
+    Ok(count)
+}
+
+/// Remove a delivered message.
+///
+/// # Errors
+///
+/// Returns [`OutboxError::Write`] if the write fails.
+pub async fn mark_sent(memory: &MemoryEngine, id: i64) -> Result<(), OutboxError> {
+    memory
+        .write_and_wait(|reply| WriteOp::DeleteTelegramMessage { id, reply })
+        .await?;
+    Ok(())
+}
+
+/// Replace what is left to send, after part of a message went through.
+///
+/// # Errors
+///
+/// Returns [`OutboxError`] on write or serialisation failure.
+pub async fn update_message(
+    memory: &MemoryEngine,
+    id: i64,
+    message: &TelegramOutbound,
+) -> Result<(), OutboxError> {
+    let payload = serde_json::to_string(message)?;
+    memory
+        .write_and_wait(|reply| WriteOp::UpdateTelegramMessage { id, payload, reply })
+        .await?;
+    Ok(())
+}
+
+/// Record a failed delivery attempt and schedule the next one.
+///
+/// The retry waits at least [`backoff`] for the attempt count, or
+/// `min_delay` if longer (Telegram's `retry_after`). After
+/// [`MAX_ATTEMPTS`] the message is marked failed, which also unblocks the
+/// rest of its chat. Returns `true` when the message was given up on.
+///
+/// # Errors
+///
+/// Returns [`OutboxError`] if the attempt count cannot be read or the
+/// write fails.
+pub async fn record_failure(
+    memory: &MemoryEngine,
+    id: i64,
+    error: &str,
+    now: DateTime<Utc>,
+    min_delay: Duration,
+) -> Result<bool, OutboxError> {
+    let (attempts,): (i64,) = sqlx::query_as("SELECT attempts FROM telegram_outbox WHERE id = ?1")
+        .bind(id)
+        .fetch_one(memory.pool())
+        .await?;
+    let attempts = attempts.saturating_add(1);
+    let delay = backoff(attempts).max(min_delay);
+    let next_attempt_at = chrono::Duration::from_std(delay)
+        .ok()
+        .and_then(|d| now.checked_add_signed(d))
+        .unwrap_or(now);
+    let gave_up = attempts >= MAX_ATTEMPTS;
+
+    memory
+        .write_and_wait(|reply| WriteOp::RecordTelegramFailure {
+            id,
+            attempts,
+            error: error.to_owned(),
+            next_attempt_at: format_timestamp(next_attempt_at),
+            give_up: gave_up,
+            reply,
+        })
+        .await?;
+
+    debug!(id, attempts, gave_up, "queued telegram delivery failed");
+    Ok(gave_up)
+}
+
+/// Mark a message failed without further retries.
+///
+/// # Errors
+///
+/// Returns [`OutboxError::Write`] if the write fails.
+pub async fn mark_failed(memory: &MemoryEngine, id: i64, error: &str) -> Result<(), OutboxError> {
+    memory
+        .write_and_wait(|reply| WriteOp::FailTelegramMessage {
+            id,
+            error: error.to_owned(),
+            reply,
+        })
+        .await?;
+    Ok(())
+}
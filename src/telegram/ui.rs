@@ -4,6 +4,10 @@
 
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
+use crate::agent::soul::soul_callback_data;
+use crate::heartbeat::memory_review::{review_callback_data, ReviewAction};
+use crate::tools::registry::{DynamicToolSchema, ToolDisabled};
+
 /// Escape special HTML characters in user-provided text.
 pub fn escape_html(text: &str) -> String {
     text.replace('&', "&amp;")
@@ -20,6 +24,210 @@ pub fn approval_keyboard(approval_id: &str) -> InlineKeyboardMarkup {
     InlineKeyboardMarkup::new(vec![vec![approve, deny]])
 }
 
+/// Build an inline keyboard with 👍 and 👎 buttons for rating a turn.
+pub fn feedback_keyboard(turn_id: &str) -> InlineKeyboardMarkup {
+    let up = InlineKeyboardButton::callback("\u{1F44D}".to_owned(), format!("f+:{turn_id}"));
+    let down = InlineKeyboardButton::callback("\u{1F44E}".to_owned(), format!("f-:{turn_id}"));
+    InlineKeyboardMarkup::new(vec![vec![up, down]])
+}
+
+/// Build an inline keyboard for resolving a contradicting memory pair.
+pub fn memory_review_keyboard(older_id: i64, newer_id: i64) -> InlineKeyboardMarkup {
+    let button = |label: &str, action: ReviewAction| {
+        InlineKeyboardButton::callback(
+            label.to_owned(),
+            review_callback_data(action, older_id, newer_id),
+        )
+    };
+    InlineKeyboardMarkup::new(vec![
+        vec![
+            button("Keep both", ReviewAction::KeepBoth),
+            button("Keep older", ReviewAction::KeepOlder),
+        ],
+        vec![
+            button("Replace with newer", ReviewAction::KeepNewer),
+            button("Archive both", ReviewAction::ArchiveBoth),
+        ],
+    ])
+}
+
+/// Build an inline keyboard to approve or reject a proposed skill memory.
+pub fn skill_keyboard(memory_id: i64) -> InlineKeyboardMarkup {
+    let approve = InlineKeyboardButton::callback(
+        "\u{2705} Save skill".to_owned(),
+        format!("sk+:{memory_id}"),
+    );
+    let reject =
+        InlineKeyboardButton::callback("\u{274C} Discard".to_owned(), format!("sk-:{memory_id}"));
+    InlineKeyboardMarkup::new(vec![vec![approve, reject]])
+}
+
+/// Build an inline keyboard to approve or reject a proposed soul version.
+pub fn soul_keyboard(version_id: i64) -> InlineKeyboardMarkup {
+    let approve = InlineKeyboardButton::callback(
+        "\u{2705} Apply".to_owned(),
+        soul_callback_data(true, version_id),
+    );
+    let reject = InlineKeyboardButton::callback(
+        "\u{274C} Reject".to_owned(),
+        soul_callback_data(false, version_id),
+    );
+    InlineKeyboardMarkup::new(vec![vec![approve, reject]])
+}
+
+/// Callback data prefix for `/forget` delete buttons.
+const FORGET_PREFIX: &str = "mf:";
+
+/// Build an inline keyboard with one numbered delete button per memory.
+///
+/// Buttons are numbered in the order the memories were listed.
+pub fn forget_keyboard(memory_ids: &[i64]) -> InlineKeyboardMarkup {
+    let buttons: Vec<InlineKeyboardButton> = memory_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            InlineKeyboardButton::callback(
+                format!("\u{1F5D1} {}", i.saturating_add(1)),
+                format!("{FORGET_PREFIX}{id}"),
+            )
+        })
+        .collect();
+    InlineKeyboardMarkup::new(vec![buttons])
+}
+
+/// Parse `/forget` callback data into the memory id to delete.
+pub fn parse_forget_callback(data: &str) -> Option<i64> {
+    data.strip_prefix(FORGET_PREFIX)?.parse().ok()
+}
+
+/// Callback data prefix for re-enabling a disabled tool.
+const TOOL_ENABLE_PREFIX: &str = "te:";
+
+/// Callback data prefix for asking the agent to fix a disabled tool.
+const TOOL_FIX_PREFIX: &str = "tf:";
+
+/// Telegram's limit on callback data, in bytes.
+const MAX_CALLBACK_DATA: usize = 64;
+
+/// Button pressed on a disabled tool notice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolAction {
+    /// Re-enable the tool as it is.
+    Enable,
+    /// Ask the agent to repair the tool.
+    Fix,
+}
+
+/// Whether the disabled tool buttons for `name` fit in callback data.
+pub fn tool_callbacks_fit(name: &str) -> bool {
+    TOOL_ENABLE_PREFIX.len().saturating_add(name.len()) <= MAX_CALLBACK_DATA
+}
+
+/// Build the re-enable / fix keyboard for a disabled tool.
+pub fn tool_disabled_keyboard(name: &str) -> InlineKeyboardMarkup {
+    let enable = InlineKeyboardButton::callback(
+        "\u{1F504} Re-enable".to_owned(),
+        format!("{TOOL_ENABLE_PREFIX}{name}"),
+    );
+    let fix = InlineKeyboardButton::callback(
+        "\u{1F6E0} Ask agent to fix".to_owned(),
+        format!("{TOOL_FIX_PREFIX}{name}"),
+    );
+    InlineKeyboardMarkup::new(vec![vec![enable, fix]])
+}
+
+/// Parse disabled tool callback data into the action and tool name.
+pub fn parse_tool_callback(data: &str) -> Option<(ToolAction, &str)> {
+    let (action, name) = if let Some(name) = data.strip_prefix(TOOL_ENABLE_PREFIX) {
+        (ToolAction::Enable, name)
+    } else {
+        (ToolAction::Fix, data.strip_prefix(TOOL_FIX_PREFIX)?)
+    };
+    (!name.is_empty()).then_some((action, name))
+}
+
+/// Callback data for sending a message held while the executor is down.
+const HELD_SEND: &str = "hm:send";
+
+/// Callback data for dropping a message held while the executor is down.
+const HELD_DROP: &str = "hm:drop";
+
+/// Build the send / drop keyboard for a message held while the executor is down.
+pub fn held_message_keyboard() -> InlineKeyboardMarkup {
+    let send = InlineKeyboardButton::callback(
+        "\u{1F4AC} Answer without commands".to_owned(),
+        HELD_SEND.to_owned(),
+    );
+    let drop = InlineKeyboardButton::callback("\u{274C} Drop".to_owned(), HELD_DROP.to_owned());
+    InlineKeyboardMarkup::new(vec![vec![send, drop]])
+}
+
+/// Parse held message callback data; `true` means send, `false` drop.
+pub fn parse_held_callback(data: &str) -> Option<bool> {
+    match data {
+        HELD_SEND => Some(true),
+        HELD_DROP => Some(false),
+        _ => None,
+    }
+}
+
+/// Format the notice sent when a dynamic tool is disabled for failing.
+///
+/// `errors` should already be redacted.
+pub fn format_tool_disabled(
+    name: &str,
+    disabled: Option<&ToolDisabled>,
+    errors: &[String],
+) -> String {
+    let mut text = format!(
+        "\u{26A0}\u{FE0F} <b>Tool disabled:</b> <code>{}</code>\n",
+        escape_html(name)
+    );
+    if let Some(disabled) = disabled {
+        text.push_str(&format!(
+            "{:.0}% success over the last {} runs.\n",
+            disabled.success_rate * 100.0,
+            disabled.window
+        ));
+    }
+    if !errors.is_empty() {
+        text.push_str("\n<b>Recent errors:</b>\n");
+        for error in errors {
+            let sample: String = error.chars().take(MAX_ERROR_SAMPLE_CHARS).collect();
+            text.push_str(&format!("<pre>{}</pre>\n", escape_html(&sample)));
+        }
+    }
+    if !tool_callbacks_fit(name) {
+        text.push_str("\nRecreate it with create_tool to enable it again.");
+    }
+    text
+}
+
+/// Longest error sample shown in a disabled tool notice, in characters.
+const MAX_ERROR_SAMPLE_CHARS: usize = 300;
+
+/// Message routed to the agent when the owner asks it to fix a disabled tool.
+pub fn tool_fix_request(schema: &DynamicToolSchema) -> String {
+    let mut request = format!(
+        "The dynamic tool `{}` was disabled because it kept failing. \
+         Read /scripts/{}.py, find the cause and replace it with create_tool.",
+        schema.name, schema.name
+    );
+    let errors = schema
+        .meta
+        .as_ref()
+        .map(|m| m.recent_errors.as_slice())
+        .unwrap_or_default();
+    if !errors.is_empty() {
+        request.push_str("\n\nRecent errors:");
+        for error in errors {
+            request.push_str("\n- ");
+            request.push_str(error);
+        }
+    }
+    request
+}
+
 /// Format a tool call description as HTML.
 pub fn format_tool_call(tool_name: &str, input: &serde_json::Value) -> String {
     let escaped_name = escape_html(tool_name);
@@ -6,6 +6,8 @@
 //! browser automation is delegated to an optional external bridge (e.g. MCP or
 //! future subprocess integration) configured at runtime.
 
+use std::path::{Path, PathBuf};
+
 use async_trait::async_trait;
 use serde_json::json;
 use tracing::debug;
@@ -76,6 +78,8 @@ const ALLOWED_ACTIONS: &[&str] = &[
     "switch_tab",
     "new_tab",
     "close_tab",
+    "list_sessions",
+    "close_session",
 ];
 
 /// Maximum length for string parameters (URL, selector, text, etc.).
@@ -107,6 +111,15 @@ pub trait BrowserBridge: Send + Sync {
     async fn execute(&self, _action: &str, _input: &serde_json::Value) -> Result<String, String> {
         Err("browser bridge not implemented".to_owned())
     }
+
+    /// Check that the bridge can take actions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem when the bridge is down.
+    async fn health(&self) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -169,6 +182,16 @@ pub fn validate_browser_input(input: &serde_json::Value) -> Result<serde_json::V
         }
     }
 
+    if let Some(session) = input.get("session").and_then(|v| v.as_str()) {
+        super::browser_sessions::validate_session_name(session).map_err(ToolError::InvalidInput)?;
+        sanitised.insert("session".to_owned(), json!(session));
+    }
+    if action == "close_session" && !sanitised.contains_key("session") {
+        return Err(ToolError::InvalidInput(
+            "close_session action requires session".to_owned(),
+        ));
+    }
+
     let timeout_ms = input
         .get("timeout_ms")
         .and_then(|v| v.as_u64())
@@ -243,6 +266,26 @@ pub async fn run_browser(
         .map_err(|e| ToolError::ExecutionFailed(format!("browser bridge error: {e}")))
 }
 
+/// Host path of a screenshot reported by the bridge.
+///
+/// The sidecar writes screenshots under `/workspace/screenshots/` and
+/// returns `{"path": ...}`; the same directory is mounted at
+/// `workspace_dir` on the host. Returns `None` for any other output or a
+/// path outside the screenshots directory.
+pub fn screenshot_host_path(output: &str, workspace_dir: &Path) -> Option<PathBuf> {
+    let value: serde_json::Value = serde_json::from_str(output).ok()?;
+    let path = value.get("path")?.as_str()?;
+    let relative = path.strip_prefix("/workspace/")?;
+    if !relative.starts_with("screenshots/")
+        || Path::new(relative)
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return None;
+    }
+    Some(workspace_dir.join(relative))
+}
+
 // ---------------------------------------------------------------------------
 // Tool definition
 // ---------------------------------------------------------------------------
@@ -251,13 +294,13 @@ pub async fn run_browser(
 pub fn browser_tool_definition() -> ToolDefinition {
     ToolDefinition {
         name: "browser".to_owned(),
-        description: "Control the browser. Can use your existing Chrome session (same cookies/logins) or a standalone instance. Navigate, click, type, screenshot, extract.".to_owned(),
+        description: "Control the browser. Can use your existing Chrome session (same cookies/logins) or a standalone instance. Navigate, click, type, screenshot, extract. Each site gets its own persistent session (cookie jar) that is reused across calls; screenshots are also sent to the user.".to_owned(),
         input_schema: json!({
             "type": "object",
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["navigate", "click", "type", "screenshot", "extract", "wait", "scroll", "evaluate", "list_tabs", "switch_tab", "new_tab", "close_tab"],
+                    "enum": ["navigate", "click", "type", "screenshot", "extract", "wait", "scroll", "evaluate", "list_tabs", "switch_tab", "new_tab", "close_tab", "list_sessions", "close_session"],
                     "description": "Browser action to perform"
                 },
                 "url": { "type": "string", "description": "URL for navigate action" },
@@ -266,6 +309,7 @@ pub fn browser_tool_definition() -> ToolDefinition {
                 "javascript": { "type": "string", "description": "JS code for evaluate action" },
                 "wait_for": { "type": "string", "description": "Selector or 'networkidle' for wait action" },
                 "tab_id": { "type": "string", "description": "Target tab (from list_tabs). Default: active tab." },
+                "session": { "type": "string", "description": "Browser session (cookie jar) to use. Default: the site of url, else the last used session. Set it to keep one session across sites (e.g. SSO logins)." },
                 "timeout_ms": { "type": "integer", "default": 30000, "description": "Timeout in milliseconds" }
             },
             "required": ["action"]
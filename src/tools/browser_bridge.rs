@@ -1,13 +1,20 @@
 //! Concrete [`BrowserBridge`] implementation via HTTP.
 //!
 //! Connects to the Playwright sidecar container's Flask bridge server
-//! and translates browser actions into HTTP POST requests.
+//! and translates browser actions into HTTP POST requests. Every request
+//! names the session (per-site context) it runs in, chosen by the
+//! [`SessionPool`], along with sessions the sidecar should close first.
+
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use serde::Deserialize;
+use serde_json::json;
 use tracing::{debug, warn};
 
 use super::browser::BrowserBridge;
+use super::browser_sessions::SessionPool;
+use crate::config::BrowserConfig;
 
 /// Default request timeout in milliseconds when the input does not specify one.
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
@@ -20,6 +27,9 @@ const TIMEOUT_BUFFER_MS: u64 = 5_000;
 /// HTTP connect timeout for the reqwest client.
 const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// Timeout for the sidecar health probe.
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// HTTP-based browser bridge connecting to the Playwright sidecar.
 ///
 /// Sends validated browser actions as JSON POST requests to the bridge
@@ -28,6 +38,12 @@ const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 pub struct PlaywrightBridge {
     client: reqwest::Client,
     base_url: String,
+    /// Open sessions and which one each action uses.
+    sessions: SessionPool,
+    /// User agent for new sessions.
+    user_agent: Option<String>,
+    /// Whether new sessions hide automation markers.
+    stealth: bool,
 }
 
 impl PlaywrightBridge {
@@ -47,42 +63,42 @@ impl PlaywrightBridge {
                 reqwest::Client::default()
             });
 
-        Self { client, base_url }
+        let defaults = BrowserConfig::default();
+        Self {
+            client,
+            base_url,
+            sessions: session_pool(&defaults),
+            user_agent: None,
+            stealth: false,
+        }
     }
-}
 
-/// Response envelope from the bridge server.
-#[derive(Debug, Deserialize)]
-struct BridgeResponse {
-    success: bool,
-    result: Option<String>,
-    error: Option<String>,
-}
+    /// Apply session pool size, idle timeout, user agent, and stealth
+    /// settings from the browser config.
+    #[must_use]
+    pub fn with_config(mut self, config: &BrowserConfig) -> Self {
+        self.sessions = session_pool(config);
+        self.user_agent = config.user_agent.clone();
+        self.stealth = config.stealth;
+        self
+    }
 
-#[async_trait]
-impl BrowserBridge for PlaywrightBridge {
-    /// Execute a validated browser action via the sidecar HTTP API.
-    ///
-    /// Sends a POST to `{base_url}/execute` with the sanitised input JSON.
-    /// The HTTP timeout is derived from `timeout_ms` in the input plus a
-    /// 5-second buffer.
-    async fn execute(&self, action: &str, input: &serde_json::Value) -> Result<String, String> {
-        let timeout_ms = input
-            .get("timeout_ms")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(DEFAULT_TIMEOUT_MS);
+    /// Open browser sessions, most recently used first.
+    pub fn sessions(&self) -> Vec<String> {
+        self.sessions.sessions()
+    }
 
+    /// Send a request to the sidecar and unwrap the response envelope.
+    async fn send(&self, payload: &serde_json::Value, timeout_ms: u64) -> Result<String, String> {
         let request_timeout_ms = timeout_ms.saturating_add(TIMEOUT_BUFFER_MS);
-        let request_timeout = std::time::Duration::from_millis(request_timeout_ms);
+        let request_timeout = Duration::from_millis(request_timeout_ms);
 
         let url = format!("{}/execute", self.base_url);
 
-        debug!(action, timeout_ms, "sending browser action to sidecar");
-
         let response = self
             .client
             .post(&url)
-            .json(input)
+            .json(payload)
             .timeout(request_timeout)
             .send()
             .await
@@ -108,3 +124,95 @@ impl BrowserBridge for PlaywrightBridge {
         }
     }
 }
+
+/// Build the session pool described by the browser config.
+fn session_pool(config: &BrowserConfig) -> SessionPool {
+    SessionPool::new(
+        config.max_sessions,
+        Duration::from_secs(config.session_idle_mins.saturating_mul(60)),
+    )
+}
+
+/// Response envelope from the bridge server.
+#[derive(Debug, Deserialize)]
+struct BridgeResponse {
+    success: bool,
+    result: Option<String>,
+    error: Option<String>,
+}
+
+#[async_trait]
+impl BrowserBridge for PlaywrightBridge {
+    /// Execute a validated browser action via the sidecar HTTP API.
+    ///
+    /// Sends a POST to `{base_url}/execute` with the sanitised input JSON,
+    /// extended with the session to use, sessions to close, and the user
+    /// agent and stealth settings for new sessions. `list_sessions` is
+    /// answered locally. The HTTP timeout is derived from `timeout_ms` in
+    /// the input plus a 5-second buffer.
+    async fn execute(&self, action: &str, input: &serde_json::Value) -> Result<String, String> {
+        let timeout_ms = input
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_TIMEOUT_MS);
+        let session = input.get("session").and_then(|v| v.as_str());
+
+        match action {
+            "list_sessions" => {
+                return Ok(json!({ "sessions": self.sessions.sessions() }).to_string());
+            }
+            "close_session" => {
+                let session = session.ok_or_else(|| "close_session requires session".to_owned())?;
+                self.sessions.release(session);
+                let payload = json!({
+                    "action": "close_session",
+                    "session": session,
+                    "timeout_ms": timeout_ms,
+                });
+                return self.send(&payload, timeout_ms).await;
+            }
+            _ => {}
+        }
+
+        let url = input.get("url").and_then(|v| v.as_str());
+        let lease = self.sessions.acquire(session, url, Instant::now());
+
+        let mut payload = input.clone();
+        if let Some(map) = payload.as_object_mut() {
+            map.insert("session".to_owned(), json!(lease.session));
+            map.insert("close_sessions".to_owned(), json!(lease.evicted));
+            map.insert("user_agent".to_owned(), json!(self.user_agent));
+            map.insert("stealth".to_owned(), json!(self.stealth));
+        }
+
+        debug!(
+            action,
+            timeout_ms,
+            session = %lease.session,
+            evicted = lease.evicted.len(),
+            "sending browser action to sidecar"
+        );
+
+        self.send(&payload, timeout_ms).await
+    }
+
+    /// Probe the sidecar's `/health` endpoint.
+    async fn health(&self) -> Result<(), String> {
+        let url = format!("{}/health", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(HEALTH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("sidecar unreachable: {e}"))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "sidecar health returned HTTP {}",
+                response.status()
+            ))
+        }
+    }
+}
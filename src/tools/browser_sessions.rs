@@ -0,0 +1,189 @@
+//! Browser session pool: persistent per-site contexts in the sidecar.
+//!
+//! Each session is a Playwright browser context with its own cookie jar,
+//! named after the site it was opened for (`github.com`) or given
+//! explicitly. The pool decides which session an action runs in, reuses
+//! warm sessions across tool calls, and closes sessions that sat idle or
+//! that exceed the pool size (least recently used first). The sidecar saves
+//! a closed session's cookies and reloads them when the site is visited
+//! again, so logins survive eviction.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+use url::Url;
+
+/// Session used when neither a session name nor a URL is known.
+pub const DEFAULT_SESSION: &str = "default";
+
+/// Maximum session name length.
+const MAX_SESSION_NAME_LEN: usize = 64;
+
+/// Which session an action runs in, plus sessions to close first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionLease {
+    /// Session for this action.
+    pub session: String,
+    /// Sessions evicted (idle or over capacity) that the sidecar should close.
+    pub evicted: Vec<String>,
+}
+
+/// Pool bookkeeping: last use per open session and the active one.
+#[derive(Debug, Default)]
+struct PoolState {
+    last_used: HashMap<String, Instant>,
+    active: Option<String>,
+}
+
+/// Tracks open browser sessions and picks one per action.
+#[derive(Debug)]
+pub struct SessionPool {
+    state: Mutex<PoolState>,
+    max_sessions: usize,
+    idle_timeout: Duration,
+}
+
+impl SessionPool {
+    /// Create a pool holding at most `max_sessions` open sessions, each
+    /// closed after `idle_timeout` without use.
+    pub fn new(max_sessions: usize, idle_timeout: Duration) -> Self {
+        Self {
+            state: Mutex::new(PoolState::default()),
+            max_sessions: max_sessions.max(1),
+            idle_timeout,
+        }
+    }
+
+    /// Pick the session for an action and mark it used.
+    ///
+    /// Preference: the explicit `session` name, then the site of `url`,
+    /// then the session used last, then [`DEFAULT_SESSION`]. Sessions idle
+    /// past the timeout, and the least recently used ones beyond the pool
+    /// size, are returned in [`SessionLease::evicted`].
+    pub fn acquire(&self, session: Option<&str>, url: Option<&str>, now: Instant) -> SessionLease {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => {
+                warn!("browser session pool lock poisoned, recovering");
+                poisoned.into_inner()
+            }
+        };
+
+        let name = session
+            .map(str::to_owned)
+            .or_else(|| url.and_then(site_key))
+            .or_else(|| state.active.clone())
+            .unwrap_or_else(|| DEFAULT_SESSION.to_owned());
+
+        let mut evicted: Vec<String> = state
+            .last_used
+            .iter()
+            .filter(|(key, used)| {
+                **key != name && now.saturating_duration_since(**used) >= self.idle_timeout
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &evicted {
+            state.last_used.remove(key);
+        }
+
+        state.last_used.insert(name.clone(), now);
+        while state.last_used.len() > self.max_sessions {
+            let Some(oldest) = state
+                .last_used
+                .iter()
+                .filter(|(key, _)| **key != name)
+                .min_by_key(|(_, used)| **used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            state.last_used.remove(&oldest);
+            evicted.push(oldest);
+        }
+
+        state.active = Some(name.clone());
+        evicted.sort();
+
+        SessionLease {
+            session: name,
+            evicted,
+        }
+    }
+
+    /// Forget a session. Returns whether it was open.
+    pub fn release(&self, session: &str) -> bool {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if state.active.as_deref() == Some(session) {
+            state.active = None;
+        }
+        state.last_used.remove(session).is_some()
+    }
+
+    /// Open sessions, most recently used first.
+    pub fn sessions(&self) -> Vec<String> {
+        let state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut sessions: Vec<(&String, &Instant)> = state.last_used.iter().collect();
+        sessions.sort_by(|a, b| b.1.cmp(a.1));
+        sessions.into_iter().map(|(key, _)| key.clone()).collect()
+    }
+}
+
+/// Session name for a URL: its host, lowercased, without a leading `www.`.
+///
+/// Characters not allowed in session names (IPv6 brackets and colons)
+/// become `_`.
+pub fn site_key(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let key: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    (!key.is_empty()).then_some(key)
+}
+
+/// Check a session name given by the agent.
+///
+/// Names become file names in the sidecar, so only `[a-z0-9._-]` is
+/// allowed, starting with a letter or digit.
+///
+/// # Errors
+///
+/// Returns a description of the problem.
+pub fn validate_session_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_SESSION_NAME_LEN {
+        return Err(format!(
+            "session must be 1-{MAX_SESSION_NAME_LEN} characters"
+        ));
+    }
+    if !name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err("session must start with a lowercase letter or digit".to_owned());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'))
+    {
+        return Err("session may only contain a-z, 0-9, '.', '_' and '-'".to_owned());
+    }
+    Ok(())
+}
@@ -16,12 +16,21 @@ use crate::executor::{ExecOptions, Executor};
 use crate::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
 use crate::providers::ToolDefinition;
 
-use super::registry::DynamicToolRegistry;
+use super::page_cache::{CachedPage, PageCache};
+use super::readability;
+use super::registry::{DynamicToolRegistry, ToolManifest};
 use super::ToolError;
 
-/// Maximum response body size in bytes for web_fetch and web_request.
+/// Maximum response body size in bytes for web_request.
 const MAX_RESPONSE_BODY_BYTES: usize = 100 * 1024;
 
+/// Maximum raw page size web_fetch downloads; readability extraction runs
+/// on this before the text cap applies.
+const MAX_FETCH_RAW_BYTES: usize = 1024 * 1024;
+
+/// Maximum page text web_fetch returns, after extraction.
+const MAX_FETCH_TEXT_BYTES: usize = 100 * 1024;
+
 /// Maximum number of redirect hops for web_fetch.
 const MAX_REDIRECT_HOPS: usize = 10;
 
@@ -66,6 +75,7 @@ pub async fn execute_command(
     let opts = ExecOptions {
         timeout: Duration::from_secs(timeout_secs),
         working_dir: None,
+        restrictions: None,
     };
 
     debug!(command, timeout_secs, "executing command");
@@ -96,12 +106,23 @@ const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 500 * 1024 * 1024;
 /// Approval threshold for large file downloads (50 MB).
 const LARGE_FILE_THRESHOLD: u64 = 50 * 1024 * 1024;
 
+/// Default token budget for web_fetch output.
+const DEFAULT_FETCH_TOKENS: usize = 4_000;
+
+/// Maximum token budget a caller may request from web_fetch.
+const MAX_FETCH_TOKENS: usize = 25_000;
+
 /// Fetch a URL via GET with SSRF protection and manual redirect following.
 ///
 /// When `save_to` is provided, downloads the response body to the given path
 /// under `/workspace/`. Supports binary downloads. Files >50 MB return a
 /// large-file warning.
 ///
+/// Otherwise HTML pages are reduced to their main content as markdown
+/// (`format: "raw"` returns the body as-is), cut to a `max_tokens` budget,
+/// and labelled with the domain's trust-ledger status. Pages are cached per
+/// session, so re-reading one does not hit the network or the rate limit.
+///
 /// `max_download_bytes` overrides the default 500 MB limit when provided.
 ///
 /// # Errors
@@ -111,6 +132,9 @@ pub async fn web_fetch(
     input: &serde_json::Value,
     limiter: &RateLimiter,
     max_download_bytes: Option<u64>,
+    memory: &MemoryEngine,
+    cache: &PageCache,
+    session: &str,
 ) -> Result<String, ToolError> {
     let download_limit = max_download_bytes.unwrap_or(DEFAULT_MAX_DOWNLOAD_BYTES);
     let url_str = input
@@ -119,25 +143,168 @@ pub async fn web_fetch(
         .ok_or_else(|| ToolError::InvalidInput("missing required field: url".to_owned()))?;
 
     let save_to = input.get("save_to").and_then(|v| v.as_str());
+    let raw = match input.get("format").and_then(|v| v.as_str()) {
+        None | Some("markdown") => false,
+        Some("raw") => true,
+        Some(other) => {
+            return Err(ToolError::InvalidInput(format!(
+                "unknown format: {other} (expected markdown or raw)"
+            )))
+        }
+    };
+    let max_tokens = input
+        .get("max_tokens")
+        .and_then(|v| v.as_u64())
+        .and_then(|n| usize::try_from(n).ok())
+        .unwrap_or(DEFAULT_FETCH_TOKENS)
+        .clamp(1, MAX_FETCH_TOKENS);
 
     // Validate save_to path before making network request.
     if let Some(path) = save_to {
         validate_save_path(path)?;
     }
 
-    limiter.check("web_fetch")?;
-    limiter.record();
+    let page = match save_to {
+        None => cache.get(session, url_str),
+        Some(_) => None,
+    };
+    let page = match page {
+        Some(page) => {
+            debug!(url = url_str, "web_fetch served from session cache");
+            page
+        }
+        None => {
+            limiter.check("web_fetch")?;
+            limiter.record();
 
-    let mut current_url =
-        Url::parse(url_str).map_err(|e| ToolError::InvalidInput(format!("invalid URL: {e}")))?;
+            let url = Url::parse(url_str)
+                .map_err(|e| ToolError::InvalidInput(format!("invalid URL: {e}")))?;
+            let response = get_following_redirects(url).await?;
+
+            // save_to mode: stream file to disk.
+            if let Some(path) = save_to {
+                return download_to_file(response, path, download_limit).await;
+            }
+
+            let final_url = response.url().to_string();
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned);
+            let body = read_body_capped(response, MAX_FETCH_RAW_BYTES).await?;
+            let page = CachedPage {
+                final_url,
+                content_type,
+                body,
+            };
+            cache.insert(session, url_str, page.clone());
+            page
+        }
+    };
+
+    let trusted = match Url::parse(&page.final_url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_owned))
+    {
+        Some(domain) => memory.is_domain_trusted(&domain).await.unwrap_or(false),
+        None => false,
+    };
+
+    Ok(render_fetched_page(&page, raw, max_tokens, trusted))
+}
+
+/// Render a fetched page for the agent.
+///
+/// HTML is reduced to readable markdown unless `raw` is set; the result
+/// is capped at 100 KB and then cut to `max_tokens`. The header names the source and whether its domain
+/// is trusted, since follow-up web_request or browser calls to untrusted
+/// domains need approval.
+pub fn render_fetched_page(
+    page: &CachedPage,
+    raw: bool,
+    max_tokens: usize,
+    trusted: bool,
+) -> String {
+    let is_html = page
+        .content_type
+        .as_deref()
+        .is_some_and(|ct| ct.contains("html"));
+    let base = Url::parse(&page.final_url).ok();
+    let domain = base
+        .as_ref()
+        .and_then(|u| u.host_str())
+        .unwrap_or("unknown");
+    let trust = if trusted {
+        "trusted"
+    } else {
+        "not in trust ledger; web_request/browser need approval"
+    };
+
+    let mut out = String::new();
+    let content = if is_html && !raw {
+        let readable = readability::extract(&page.body, base.as_ref());
+        if let Some(title) = readable.title {
+            out.push_str(&format!("Title: {title}\n"));
+        }
+        readable.markdown
+    } else {
+        page.body.clone()
+    };
+    out.push_str(&format!("URL: {}\n", page.final_url));
+    out.push_str(&format!("Domain: {domain} ({trust})\n\n"));
+    let content = truncate_body(&content, MAX_FETCH_TEXT_BYTES);
+    out.push_str(&readability::truncate_to_tokens(&content, max_tokens));
+    out
+}
+
+/// Read a response body, stopping once `max_bytes` have arrived.
+///
+/// Bytes past the cap are never buffered; invalid UTF-8 (including a
+/// character split at the cap) is replaced rather than rejected.
+pub(crate) async fn read_body_capped(
+    response: reqwest::Response,
+    max_bytes: usize,
+) -> Result<String, ToolError> {
+    let body = read_bytes_capped(response, max_bytes).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Read at most `max_bytes` of a response body as raw bytes.
+pub(crate) async fn read_bytes_capped(
+    response: reqwest::Response,
+    max_bytes: usize,
+) -> Result<Vec<u8>, ToolError> {
+    use tokio_stream::StreamExt;
+
+    let mut stream = response.bytes_stream();
+    let mut body: Vec<u8> = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            ToolError::ExecutionFailed(format!("failed to read response body: {e}"))
+        })?;
+        let room = max_bytes.saturating_sub(body.len());
+        if chunk.len() >= room {
+            body.extend_from_slice(chunk.get(..room).unwrap_or(&chunk));
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
 
+/// GET a URL, following redirects manually with an SSRF check on each hop.
+pub(crate) async fn get_following_redirects(
+    mut current_url: Url,
+) -> Result<reqwest::Response, ToolError> {
     let client = reqwest::Client::builder()
         .redirect(reqwest::redirect::Policy::none())
         .no_proxy()
         .build()
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to build HTTP client: {e}")))?;
 
-    // Follow redirects manually, running SSRF check on each hop.
     for hop in 0..MAX_REDIRECT_HOPS {
         ssrf_check(&current_url).await?;
 
@@ -149,36 +316,21 @@ pub async fn web_fetch(
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("GET request failed: {e}")))?;
 
-        let status = response.status();
-
-        if status.is_redirection() {
-            let location = response
-                .headers()
-                .get("location")
-                .and_then(|v| v.to_str().ok())
-                .ok_or_else(|| {
-                    ToolError::ExecutionFailed(
-                        "redirect response missing Location header".to_owned(),
-                    )
-                })?;
-
-            current_url = current_url
-                .join(location)
-                .map_err(|e| ToolError::ExecutionFailed(format!("invalid redirect URL: {e}")))?;
-            continue;
+        if !response.status().is_redirection() {
+            return Ok(response);
         }
 
-        // save_to mode: stream file to disk.
-        if let Some(path) = save_to {
-            return download_to_file(response, path, download_limit).await;
-        }
-
-        let body = response.text().await.map_err(|e| {
-            ToolError::ExecutionFailed(format!("failed to read response body: {e}"))
-        })?;
-
-        let truncated = truncate_body(&body, MAX_RESPONSE_BODY_BYTES);
-        return Ok(truncated);
+        let location = response
+            .headers()
+            .get("location")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                ToolError::ExecutionFailed("redirect response missing Location header".to_owned())
+            })?;
+
+        current_url = current_url
+            .join(location)
+            .map_err(|e| ToolError::ExecutionFailed(format!("invalid redirect URL: {e}")))?;
     }
 
     Err(ToolError::ExecutionFailed(format!(
@@ -374,10 +526,8 @@ pub async fn web_request(
         .map_err(|e| ToolError::ExecutionFailed(format!("request failed: {e}")))?;
 
     let status = response.status();
-    let response_body = response
-        .text()
-        .await
-        .map_err(|e| ToolError::ExecutionFailed(format!("failed to read response body: {e}")))?;
+    let response_body =
+        read_body_capped(response, MAX_RESPONSE_BODY_BYTES.saturating_add(1)).await?;
 
     let truncated = truncate_body(&response_body, MAX_RESPONSE_BODY_BYTES);
     Ok(format!("Status: {status}\n\n{truncated}"))
@@ -523,6 +673,17 @@ pub async fn handle_create_tool(
         )));
     }
 
+    let examples = super::self_test::parse_examples(input.get("examples"), parameters_schema)?;
+    let output_schema = input.get("output_schema").filter(|v| !v.is_null());
+    if output_schema.is_some_and(|schema| !schema.is_object()) {
+        return Err(ToolError::InvalidInput(
+            "output_schema must be a JSON Schema object".to_owned(),
+        ));
+    }
+
+    let mut manifest = parse_manifest(input)?;
+    manifest.validate().map_err(ToolError::InvalidInput)?;
+
     super::create_tool::create_tool(
         executor,
         registry,
@@ -531,10 +692,28 @@ pub async fn handle_create_tool(
         parameters_schema,
         implementation,
         timeout_secs,
+        &examples,
+        output_schema,
+        &manifest,
     )
     .await
 }
 
+/// Read the optional `manifest` field of a `create_tool` call.
+///
+/// A missing manifest means no network and a read-only workspace.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if the manifest is malformed.
+pub fn parse_manifest(input: &serde_json::Value) -> Result<ToolManifest, ToolError> {
+    match input.get("manifest").filter(|v| !v.is_null()) {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| ToolError::InvalidInput(format!("invalid manifest: {e}"))),
+        None => Ok(ToolManifest::default()),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tool definitions
 // ---------------------------------------------------------------------------
@@ -563,7 +742,7 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "web_fetch".to_owned(),
-            description: "Fetch a URL via GET request with SSRF protection. With save_to: downloads file (binary ok) to /workspace path.".to_owned(),
+            description: "Fetch a URL via GET request with SSRF protection. HTML is returned as readable markdown, cached for the session. With save_to: downloads file (binary ok) to /workspace path.".to_owned(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -574,6 +753,17 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                     "save_to": {
                         "type": "string",
                         "description": "Optional path under /workspace/ to save the response body as a file. Supports binary downloads."
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["markdown", "raw"],
+                        "default": "markdown",
+                        "description": "markdown: main content of HTML pages without boilerplate. raw: the body as returned."
+                    },
+                    "max_tokens": {
+                        "type": "integer",
+                        "default": 4000,
+                        "description": "Approximate output budget (at most 25000)."
                     }
                 },
                 "required": ["url"]
@@ -609,6 +799,24 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                 "required": ["url", "method"]
             }),
         },
+        ToolDefinition {
+            name: "web_search".to_owned(),
+            description: "Search the web. Returns title, URL, and snippet for each result, with whether its domain is trusted. Use web_fetch to read a result.".to_owned(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "The search query."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of results (default from config, at most 20)."
+                    }
+                },
+                "required": ["query"]
+            }),
+        },
         ToolDefinition {
             name: "memory_search".to_owned(),
             description: "Search memories using full-text and optional vector similarity."
@@ -673,9 +881,28 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                         "type": "string",
                         "description": "Required for WhatsApp messages."
                     },
+                    "template": {
+                        "type": "string",
+                        "description": "WhatsApp only: name of a stored message template to fill instead of composing. The user previews and approves the filled text before it is sent."
+                    },
+                    "vars": {
+                        "type": "object",
+                        "additionalProperties": {"type": "string"},
+                        "description": "Values for template placeholders beyond contact_name, first_name, organization and objective."
+                    },
+                    "urgent": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "WhatsApp only: send now even during the contact's quiet hours. Otherwise the message is queued until they end."
+                    },
                     "file": {
                         "type": "string",
-                        "description": "Optional file path from /workspace."
+                        "description": "Optional file path from /workspace. On WhatsApp the composed text becomes its caption."
+                    },
+                    "voice": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "WhatsApp only: send the audio `file` as a voice note. No text is composed."
                     }
                 },
                 "required": ["text"]
@@ -689,7 +916,7 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                 "properties": {
                     "action": {
                         "type": "string",
-                        "enum": ["create", "update", "escalate", "propose", "complete", "cancel"],
+                        "enum": ["create", "update", "status", "escalate", "propose", "complete", "cancel"],
                         "description": "Action to perform on the brief."
                     },
                     "session_id": {
@@ -698,7 +925,7 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                     },
                     "brief_id": {
                         "type": "string",
-                        "description": "Brief ID (required for update/escalate/propose/complete/cancel)."
+                        "description": "Brief ID (required for update/status/escalate/propose/complete/cancel)."
                     },
                     "objective": {
                         "type": "string",
@@ -760,7 +987,7 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "create_tool".to_owned(),
-            description: "Create or update a dynamic tool with a Python implementation.".to_owned(),
+            description: "Create or update a dynamic tool with a Python implementation. The tool is self-tested in the sandbox against the examples and only registered if every example passes.".to_owned(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -784,12 +1011,49 @@ pub fn core_tool_definitions() -> Vec<ToolDefinition> {
                         "type": "integer",
                         "description": "Maximum execution time in seconds (default 120).",
                         "default": 120
+                    },
+                    "examples": {
+                        "type": "array",
+                        "description": "1-10 example calls run as a self-test before the tool is registered. Each input must match parameters_schema; expect lists output fields that must match exactly.",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "input": { "type": "object" },
+                                "expect": { "type": "object" }
+                            },
+                            "required": ["input"]
+                        }
+                    },
+                    "output_schema": {
+                        "type": "object",
+                        "description": "Optional JSON Schema the tool's JSON output must match."
+                    },
+                    "manifest": {
+                        "type": "object",
+                        "description": "Resources the tool needs. Omitted fields mean no network, a read-only /workspace and timeout_secs as the runtime limit. Requesting network access asks the user for approval.",
+                        "properties": {
+                            "needs_network": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Host names the tool calls, e.g. [\"api.github.com\"]."
+                            },
+                            "max_runtime_secs": {
+                                "type": "integer",
+                                "description": "Hard cap on one run, at most timeout_secs."
+                            },
+                            "needs_workspace_write": {
+                                "type": "boolean",
+                                "description": "Whether the tool writes files under /workspace."
+                            }
+                        }
                     }
                 },
-                "required": ["name", "description", "parameters_schema", "implementation"]
+                "required": ["name", "description", "parameters_schema", "implementation", "examples"]
             }),
         },
         super::browser::browser_tool_definition(),
+        super::rss::rss_subscribe_tool_definition(),
+        super::rss::rss_list_tool_definition(),
     ]
 }
 
@@ -1,8 +1,11 @@
 //! Tool creation: writes implementation + schema to /scripts/ and commits to git.
 //!
-//! The [`create_tool`] function validates the tool name, writes the Python
-//! implementation and JSON schema to the scripts directory, commits the
-//! changes to git, and reloads the registry.
+//! The [`create_tool`] function validates the tool name, stages the Python
+//! implementation, self-tests it against the declared examples (see
+//! [`super::self_test`]) under its manifest's restrictions, and only on
+//! success writes the implementation and JSON schema (including the
+//! manifest) to the scripts directory, commits the changes to git, and
+//! reloads the registry.
 
 use serde_json::json;
 use tracing::debug;
@@ -10,7 +13,8 @@ use tracing::debug;
 use crate::executor::docker::shell_escape;
 use crate::executor::{ExecOptions, Executor};
 
-use super::registry::{DynamicToolRegistry, ToolMeta};
+use super::registry::{DynamicToolRegistry, ToolManifest, ToolMeta};
+use super::self_test::{run_self_test, ToolExample};
 use super::ToolError;
 
 /// Maximum allowed tool name length.
@@ -24,6 +28,7 @@ fn create_tool_exec_opts() -> ExecOptions {
     ExecOptions {
         timeout: std::time::Duration::from_secs(CREATE_TOOL_TIMEOUT_SECS),
         working_dir: None,
+        restrictions: None,
     }
 }
 
@@ -89,12 +94,20 @@ pub fn validate_tool_name(name: &str) -> Result<(), ToolError> {
 
 /// Create or update a dynamic tool.
 ///
-/// Writes the Python implementation and JSON schema to `/scripts/`, commits
-/// the changes to git, and reloads the tool in the registry.
+/// Stages the implementation as `/scripts/.{name}.candidate.py` and runs it
+/// against every example with the manifest's restrictions. Network access
+/// is assumed approved here; the policy gate asks before a tool that needs
+/// network is created. If any example fails, the candidate is deleted
+/// and the active tool (if any) is left untouched. Otherwise the candidate
+/// replaces `/scripts/{name}.py`, the JSON schema is written with the test
+/// report in `_meta.self_test`, the changes are committed to git, and the
+/// tool is reloaded in the registry.
 ///
 /// # Errors
 ///
-/// Returns [`ToolError`] on validation failure, write failure, or git failure.
+/// Returns [`ToolError`] on validation failure, self-test failure, write
+/// failure, or git failure.
+#[allow(clippy::too_many_arguments)]
 pub async fn create_tool(
     executor: &dyn Executor,
     registry: &DynamicToolRegistry,
@@ -103,6 +116,9 @@ pub async fn create_tool(
     parameters_schema: &serde_json::Value,
     implementation: &str,
     timeout_secs: u64,
+    examples: &[ToolExample],
+    output_schema: Option<&serde_json::Value>,
+    manifest: &ToolManifest,
 ) -> Result<String, ToolError> {
     validate_tool_name(name)?;
 
@@ -121,43 +137,98 @@ pub async fn create_tool(
         "create"
     };
 
-    // Step 1: Write implementation file.
+    // Step 1: Stage the implementation and self-test it.
+    let candidate = format!("{scripts_dir}/.{name}.candidate.py");
     let escaped_impl = shell_escape(implementation);
-    let write_impl_cmd = format!(
-        "printf '%s' {escaped_impl} > {scripts_dir}/{name}.py && chmod +x {scripts_dir}/{name}.py"
-    );
+    let write_impl_cmd = format!("printf '%s' {escaped_impl} > {candidate}");
     executor
         .execute(&write_impl_cmd, create_tool_exec_opts())
         .await
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to write implementation: {e}")))?;
 
-    debug!(tool = name, "wrote implementation file");
+    let runtime_secs = manifest
+        .max_runtime_secs
+        .map_or(timeout_secs, |max| max.min(timeout_secs));
+    let report = run_self_test(
+        executor,
+        &candidate,
+        examples,
+        output_schema,
+        runtime_secs,
+        Some(manifest.restrictions(name, true)),
+    )
+    .await;
+    if !report.is_success() {
+        if let Err(e) = executor
+            .execute(&format!("rm -f {candidate}"), create_tool_exec_opts())
+            .await
+        {
+            tracing::warn!(tool = name, error = %e, "failed to remove candidate script");
+        }
+        tracing::info!(
+            tool = name,
+            passed = report.passed,
+            examples = report.examples,
+            "tool self-test failed"
+        );
+        let kept = if action == "update" {
+            "the current version stays active"
+        } else {
+            "the tool was not registered"
+        };
+        return Err(ToolError::ExecutionFailed(format!(
+            "self-test failed for '{name}', {kept}: {}",
+            report.failure_summary()
+        )));
+    }
+
+    let activate_cmd =
+        format!("mv -f {candidate} {scripts_dir}/{name}.py && chmod +x {scripts_dir}/{name}.py");
+    executor
+        .execute(&activate_cmd, create_tool_exec_opts())
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("failed to write implementation: {e}")))?;
+
+    debug!(
+        tool = name,
+        examples = report.examples,
+        "self-test passed, wrote implementation file"
+    );
 
     // Step 2: Write schema JSON file with _meta.
-    let meta = if action == "update" {
-        // Preserve existing _meta, bump version.
+    let mut meta = if action == "update" {
+        // Preserve existing _meta, bump version. The new version passed its
+        // self-test, so a disabled tool starts over with a clean record.
         registry
             .get(name)
             .and_then(|s| s.meta)
             .map(|mut m| {
                 m.version = m.version.saturating_add(1);
+                m.reset_health();
                 m
             })
             .unwrap_or_else(ToolMeta::new_initial)
     } else {
         ToolMeta::new_initial()
     };
+    let examples_run = report.examples;
+    meta.self_test = Some(report);
 
     let meta_json = serde_json::to_value(&meta)
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to serialize _meta: {e}")))?;
 
-    let schema = json!({
+    let mut schema = json!({
         "name": name,
         "description": description,
         "parameters": parameters_schema,
         "timeout_secs": timeout_secs,
+        "examples": examples,
+        "manifest": manifest,
         "_meta": meta_json,
     });
+    if let Some(output_schema) = output_schema {
+        schema["output_schema"] = output_schema.clone();
+    }
     let schema_json = serde_json::to_string_pretty(&schema)
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to serialize schema: {e}")))?;
     let escaped_schema = shell_escape(&schema_json);
@@ -202,5 +273,7 @@ pub async fn create_tool(
         tracing::warn!(tool = name, error = %e, "failed to reload tool in registry");
     }
 
-    Ok(format!("Tool '{name}' {action}d successfully"))
+    Ok(format!(
+        "Tool '{name}' {action}d successfully (self-test passed {examples_run}/{examples_run} examples)"
+    ))
 }
@@ -0,0 +1,1033 @@
+//! Email tool: list and read mail over IMAP, send over SMTP.
+//!
+//! The account is configured in `[email]`; the password comes from `.env`
+//! (`EMAIL_PASSWORD`, plus `EMAIL_USERNAME` when the login is not the
+//! address). Reading is read-only (`EXAMINE` and `BODY.PEEK`), attachments
+//! of a message read are saved to the workspace inbox, and every send goes
+//! through user approval (see [`crate::agent::policy`]). Outgoing subject
+//! and body pass through the [`Redactor`] so credentials are never mailed
+//! out, and the approval preview shows the redacted text that will be sent.
+
+use std::path::Path;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials as SmtpCredentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use mail_parser::{Addr, Address, MessageParser, MimeHeaders};
+use regex::Regex;
+use serde::Serialize;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_native_tls::TlsStream;
+use tracing::{debug, info};
+
+use super::readability::truncate_to_tokens;
+use super::send_message::resolve_workspace_file;
+use super::ToolError;
+use crate::config::EmailConfig;
+use crate::credentials::Credentials;
+use crate::executor::redactor::Redactor;
+use crate::providers::ToolDefinition;
+use crate::telegram::media::sanitize_filename;
+use crate::whatsapp::media::mime_for_path;
+
+/// Credential key for the IMAP/SMTP password.
+pub const EMAIL_PASSWORD: &str = "EMAIL_PASSWORD";
+
+/// Credential key for the login, when it differs from the address.
+pub const EMAIL_USERNAME: &str = "EMAIL_USERNAME";
+
+/// Largest total attachment size for an outgoing email.
+pub const MAX_ATTACHMENT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Largest message accepted from the IMAP server.
+const MAX_MESSAGE_BYTES: usize = 50 * 1024 * 1024;
+
+/// Mailbox used when none is given.
+const DEFAULT_MAILBOX: &str = "INBOX";
+
+/// Messages listed when no limit is given.
+const DEFAULT_LIST_LIMIT: usize = 10;
+
+/// Upper bound on the list limit.
+const MAX_LIST_LIMIT: usize = 50;
+
+/// Token budget for a message body returned by `read`.
+const MAX_BODY_TOKENS: usize = 5_000;
+
+/// Timeout for a whole IMAP or SMTP operation.
+const OPERATION_TIMEOUT_SECS: u64 = 60;
+
+/// Untagged FETCH response.
+static FETCH_RE: LazyLock<Option<Regex>> = LazyLock::new(|| Regex::new(r"^\* \d+ FETCH").ok());
+
+/// UID item of a FETCH response.
+static UID_RE: LazyLock<Option<Regex>> = LazyLock::new(|| Regex::new(r"\bUID (\d+)").ok());
+
+/// FLAGS item of a FETCH response.
+static FLAGS_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"\bFLAGS \(([^)]*)\)").ok());
+
+// ---------------------------------------------------------------------------
+// Account
+// ---------------------------------------------------------------------------
+
+/// Connection details and credentials for the configured mailbox.
+#[derive(Clone)]
+pub struct EmailAccount {
+    address: String,
+    username: String,
+    password: String,
+    imap_host: String,
+    imap_port: u16,
+    smtp_host: String,
+    smtp_port: u16,
+}
+
+impl std::fmt::Debug for EmailAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmailAccount")
+            .field("address", &self.address)
+            .field("imap_host", &self.imap_host)
+            .field("smtp_host", &self.smtp_host)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EmailAccount {
+    /// Build the account from `[email]` and `.env`.
+    ///
+    /// Returns `None` when email is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if email is enabled but a host or the address is
+    /// missing, the address is invalid, or `EMAIL_PASSWORD` is not set.
+    pub fn from_config(
+        config: &EmailConfig,
+        credentials: &Credentials,
+    ) -> anyhow::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        for (field, value) in [
+            ("address", &config.address),
+            ("imap_host", &config.imap_host),
+            ("smtp_host", &config.smtp_host),
+        ] {
+            if value.trim().is_empty() {
+                anyhow::bail!("[email] is enabled but {field} is empty");
+            }
+        }
+        config
+            .address
+            .parse::<Mailbox>()
+            .map_err(|e| anyhow::anyhow!("[email] address is invalid: {e}"))?;
+
+        let username = credentials
+            .get(EMAIL_USERNAME)
+            .map(str::to_owned)
+            .unwrap_or_else(|| config.address.clone());
+
+        Ok(Some(Self {
+            address: config.address.clone(),
+            username,
+            password: credentials.require(EMAIL_PASSWORD)?,
+            imap_host: config.imap_host.clone(),
+            imap_port: config.imap_port,
+            smtp_host: config.smtp_host.clone(),
+            smtp_port: config.smtp_port,
+        }))
+    }
+
+    /// The account's email address.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tool entry point
+// ---------------------------------------------------------------------------
+
+/// Execute the email tool.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] for a missing or unknown action or
+/// bad arguments, and [`ToolError::ExecutionFailed`] if the server cannot
+/// be reached, rejects the login, or the message cannot be sent.
+pub async fn email(
+    account: &EmailAccount,
+    redactor: &Redactor,
+    workspace_dir: &Path,
+    input: &serde_json::Value,
+) -> Result<String, ToolError> {
+    let action = input
+        .get("action")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ToolError::InvalidInput("email requires an 'action' field".into()))?;
+
+    let operation = async {
+        match action {
+            "list" => list(account, input).await,
+            "read" => read(account, workspace_dir, input).await,
+            "send" => send(account, redactor, workspace_dir, input).await,
+            other => Err(ToolError::InvalidInput(format!(
+                "unknown email action '{other}' (expected list, read, or send)"
+            ))),
+        }
+    };
+
+    tokio::time::timeout(Duration::from_secs(OPERATION_TIMEOUT_SECS), operation)
+        .await
+        .map_err(|_| {
+            ToolError::ExecutionFailed(format!(
+                "email {action} timed out after {OPERATION_TIMEOUT_SECS}s"
+            ))
+        })?
+}
+
+/// Render the message an email `send` would deliver, for its approval prompt.
+///
+/// Returns `None` for actions other than `send`.
+pub fn send_preview(input: &serde_json::Value, redactor: &Redactor) -> Option<String> {
+    if input.get("action").and_then(|v| v.as_str()) != Some("send") {
+        return None;
+    }
+    Some(match Draft::from_input(input) {
+        Ok(draft) => redactor.redact(&draft.preview()),
+        Err(e) => format!("(preview unavailable: {e})"),
+    })
+}
+
+/// List recent messages in a mailbox, newest first.
+async fn list(account: &EmailAccount, input: &serde_json::Value) -> Result<String, ToolError> {
+    let mailbox = mailbox_name(input);
+    let limit = input
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| usize::try_from(v).ok())
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+    let unread_only = input
+        .get("unread_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let mut criteria = if unread_only { "UNSEEN" } else { "ALL" }.to_owned();
+    if let Some(query) = input
+        .get("query")
+        .and_then(|v| v.as_str())
+        .filter(|q| !q.trim().is_empty())
+    {
+        criteria.push_str(" TEXT ");
+        criteria.push_str(&quote_imap(query.trim())?);
+    }
+
+    let mut session = ImapSession::connect(account).await?;
+    session
+        .command(&format!("EXAMINE {}", quote_imap(mailbox)?))
+        .await?;
+    let mut uids = parse_search(&session.command(&format!("UID SEARCH {criteria}")).await?);
+    uids.sort_unstable();
+
+    let newest: Vec<String> = uids.iter().rev().take(limit).map(u32::to_string).collect();
+    let mut summaries = Vec::new();
+    if !newest.is_empty() {
+        let responses = session
+            .command(&format!(
+                "UID FETCH {} (UID FLAGS BODY.PEEK[HEADER.FIELDS (FROM SUBJECT DATE)])",
+                newest.join(",")
+            ))
+            .await?;
+        summaries = responses
+            .iter()
+            .filter_map(parse_fetch)
+            .map(|fetched| summarize(&fetched))
+            .collect();
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.uid));
+    }
+    session.logout().await;
+
+    debug!(mailbox, matched = uids.len(), "email list");
+    Ok(format_summaries(mailbox, uids.len(), &summaries))
+}
+
+/// Read one message by UID and save its attachments to the inbox.
+async fn read(
+    account: &EmailAccount,
+    workspace_dir: &Path,
+    input: &serde_json::Value,
+) -> Result<String, ToolError> {
+    let mailbox = mailbox_name(input);
+    let uid = input
+        .get("uid")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| ToolError::InvalidInput("read requires a numeric 'uid'".into()))?;
+
+    let mut session = ImapSession::connect(account).await?;
+    session
+        .command(&format!("EXAMINE {}", quote_imap(mailbox)?))
+        .await?;
+    let responses = session
+        .command(&format!("UID FETCH {uid} (UID FLAGS BODY.PEEK[])"))
+        .await?;
+    session.logout().await;
+
+    let fetched = responses
+        .iter()
+        .filter_map(parse_fetch)
+        .find(|f| f.uid == uid)
+        .ok_or_else(|| {
+            ToolError::ExecutionFailed(format!("no message with uid {uid} in {mailbox}"))
+        })?;
+    let parsed = parse_email(&fetched.data)
+        .ok_or_else(|| ToolError::ExecutionFailed(format!("could not parse message {uid}")))?;
+
+    let saved = save_attachments(uid, &parsed.attachments, workspace_dir).await?;
+    Ok(format_email(uid, &parsed, &saved))
+}
+
+/// Send a new message or a reply.
+async fn send(
+    account: &EmailAccount,
+    redactor: &Redactor,
+    workspace_dir: &Path,
+    input: &serde_json::Value,
+) -> Result<String, ToolError> {
+    let mut draft = Draft::from_input(input)?;
+    draft.subject = redactor.redact(&draft.subject);
+    draft.body = redactor.redact(&draft.body);
+
+    let mut files = Vec::with_capacity(draft.attachments.len());
+    let mut total_bytes: u64 = 0;
+    for file in &draft.attachments {
+        let path = resolve_workspace_file(file, workspace_dir)?;
+        let data = tokio::fs::read(&path)
+            .await
+            .map_err(|e| ToolError::InvalidInput(format!("cannot read attachment {file}: {e}")))?;
+        total_bytes = total_bytes.saturating_add(u64::try_from(data.len()).unwrap_or(u64::MAX));
+        if total_bytes > MAX_ATTACHMENT_BYTES {
+            return Err(ToolError::InvalidInput(format!(
+                "attachments exceed {} MB",
+                MAX_ATTACHMENT_BYTES.saturating_div(1024 * 1024)
+            )));
+        }
+        files.push(EmailAttachment {
+            file_name: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            mime_type: mime_for_path(&path).to_owned(),
+            data,
+        });
+    }
+
+    let attachment_count = files.len();
+    let message = build_message(&account.address, &draft, files)?;
+    deliver(account, message).await?;
+
+    info!(
+        recipients = draft.to.len().saturating_add(draft.cc.len()),
+        attachments = attachment_count,
+        "email sent"
+    );
+    Ok(format!(
+        "Email sent to {} (subject: {:?}, {attachment_count} attachment(s))",
+        draft.to.join(", "),
+        draft.subject
+    ))
+}
+
+/// Deliver a built message over SMTP.
+async fn deliver(account: &EmailAccount, message: Message) -> Result<(), ToolError> {
+    let builder = if account.smtp_port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&account.smtp_host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&account.smtp_host)
+    }
+    .map_err(|e| ToolError::ExecutionFailed(format!("invalid SMTP host: {e}")))?;
+
+    let transport = builder
+        .port(account.smtp_port)
+        .credentials(SmtpCredentials::new(
+            account.username.clone(),
+            account.password.clone(),
+        ))
+        .timeout(Some(Duration::from_secs(OPERATION_TIMEOUT_SECS)))
+        .build();
+
+    transport
+        .send(message)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("SMTP send failed: {e}")))?;
+    Ok(())
+}
+
+/// Save attachments to `{workspace}/inbox/email/` and describe them.
+async fn save_attachments(
+    uid: u32,
+    attachments: &[EmailAttachment],
+    workspace_dir: &Path,
+) -> Result<Vec<String>, ToolError> {
+    if attachments.is_empty() {
+        return Ok(Vec::new());
+    }
+    let dir = workspace_dir.join("inbox").join("email");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("failed to create email inbox: {e}")))?;
+
+    let mut saved = Vec::with_capacity(attachments.len());
+    for (index, attachment) in attachments.iter().enumerate() {
+        let name = attachment_filename(uid, index, attachment.file_name.as_deref());
+        tokio::fs::write(dir.join(&name), &attachment.data)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("failed to save {name}: {e}")))?;
+        saved.push(format!(
+            "/workspace/inbox/email/{name} ({}, {} bytes)",
+            attachment.mime_type,
+            attachment.data.len()
+        ));
+    }
+    Ok(saved)
+}
+
+/// Mailbox named in the input, or `INBOX`.
+fn mailbox_name(input: &serde_json::Value) -> &str {
+    input
+        .get("mailbox")
+        .and_then(|v| v.as_str())
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or(DEFAULT_MAILBOX)
+}
+
+// ---------------------------------------------------------------------------
+// IMAP
+// ---------------------------------------------------------------------------
+
+/// One server response: its text with literal payloads removed, and the
+/// literals it carried in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImapResponse {
+    /// Response text; lines joined, literal markers (`{n}`) kept.
+    pub text: String,
+    /// Literal payloads.
+    pub literals: Vec<Vec<u8>>,
+}
+
+/// A message (or header block) returned by `UID FETCH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedMessage {
+    /// Message UID.
+    pub uid: u32,
+    /// Whether the message has the `\Seen` flag.
+    pub seen: bool,
+    /// Fetched body section.
+    pub data: Vec<u8>,
+}
+
+/// Minimal IMAP client over implicit TLS.
+struct ImapSession {
+    reader: BufReader<TlsStream<TcpStream>>,
+    next_tag: u32,
+}
+
+impl ImapSession {
+    /// Connect, read the greeting, and log in.
+    async fn connect(account: &EmailAccount) -> Result<Self, ToolError> {
+        let tcp = TcpStream::connect((account.imap_host.as_str(), account.imap_port))
+            .await
+            .map_err(|e| {
+                ToolError::ExecutionFailed(format!(
+                    "cannot reach IMAP server {}:{}: {e}",
+                    account.imap_host, account.imap_port
+                ))
+            })?;
+        let connector = native_tls::TlsConnector::new()
+            .map_err(|e| ToolError::ExecutionFailed(format!("TLS setup failed: {e}")))?;
+        let tls = tokio_native_tls::TlsConnector::from(connector)
+            .connect(&account.imap_host, tcp)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("IMAP TLS handshake failed: {e}")))?;
+
+        let mut session = Self {
+            reader: BufReader::new(tls),
+            next_tag: 1,
+        };
+        let greeting = read_imap_response(&mut session.reader).await?;
+        if !greeting.text.starts_with("* OK") && !greeting.text.starts_with("* PREAUTH") {
+            return Err(ToolError::ExecutionFailed(format!(
+                "unexpected IMAP greeting: {}",
+                greeting.text
+            )));
+        }
+
+        let login = format!(
+            "LOGIN {} {}",
+            quote_imap(&account.username)?,
+            quote_imap(&account.password)?
+        );
+        session.command(&login).await.map_err(|_| {
+            ToolError::ExecutionFailed(format!(
+                "IMAP login failed for {}; check {EMAIL_PASSWORD}",
+                account.address
+            ))
+        })?;
+        Ok(session)
+    }
+
+    /// Send a command and collect its untagged responses.
+    async fn command(&mut self, command: &str) -> Result<Vec<ImapResponse>, ToolError> {
+        let tag = format!("a{}", self.next_tag);
+        self.next_tag = self.next_tag.saturating_add(1);
+        // Errors name the verb only: LOGIN carries the password.
+        let verb = command
+            .split_whitespace()
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let stream = self.reader.get_mut();
+        stream
+            .write_all(format!("{tag} {command}\r\n").as_bytes())
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("IMAP write failed: {e}")))?;
+        stream
+            .flush()
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("IMAP write failed: {e}")))?;
+
+        let mut untagged = Vec::new();
+        loop {
+            let response = read_imap_response(&mut self.reader).await?;
+            let status = response
+                .text
+                .strip_prefix(tag.as_str())
+                .and_then(|rest| rest.strip_prefix(' '));
+            match status {
+                Some(status) if status.starts_with("OK") => return Ok(untagged),
+                Some(status) => {
+                    return Err(ToolError::ExecutionFailed(format!(
+                        "IMAP {verb} failed: {status}"
+                    )))
+                }
+                None => untagged.push(response),
+            }
+        }
+    }
+
+    /// Log out, ignoring errors: the work is already done.
+    async fn logout(mut self) {
+        if let Err(e) = self.command("LOGOUT").await {
+            debug!(error = %e, "IMAP logout failed");
+        }
+    }
+}
+
+/// Read one IMAP response, following `{n}` literals across lines.
+///
+/// # Errors
+///
+/// Returns [`ToolError::ExecutionFailed`] if the connection closes, a read
+/// fails, or a literal exceeds the message size limit.
+pub async fn read_imap_response<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<ImapResponse, ToolError> {
+    let mut response = ImapResponse::default();
+    loop {
+        let mut line = Vec::new();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("IMAP read failed: {e}")))?;
+        if read == 0 {
+            return Err(ToolError::ExecutionFailed(
+                "IMAP server closed the connection".into(),
+            ));
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim_end_matches(['\r', '\n']);
+        response.text.push_str(text);
+
+        let Some(len) = literal_length(text) else {
+            return Ok(response);
+        };
+        if len > MAX_MESSAGE_BYTES {
+            return Err(ToolError::ExecutionFailed(format!(
+                "IMAP message larger than {} MB",
+                MAX_MESSAGE_BYTES.saturating_div(1024 * 1024)
+            )));
+        }
+        let mut literal = vec![0; len];
+        reader
+            .read_exact(&mut literal)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("IMAP read failed: {e}")))?;
+        response.literals.push(literal);
+    }
+}
+
+/// Length of the literal announced at the end of a line (`... {123}`).
+fn literal_length(line: &str) -> Option<usize> {
+    let inner = line.strip_suffix('}')?;
+    let open = inner.rfind('{')?;
+    inner.get(open.saturating_add(1)..)?.parse().ok()
+}
+
+/// Quote a string for an IMAP command.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] for non-ASCII text or line breaks,
+/// which quoted strings cannot carry.
+pub fn quote_imap(value: &str) -> Result<String, ToolError> {
+    if !value.is_ascii() || value.contains(['\r', '\n']) {
+        return Err(ToolError::InvalidInput(
+            "IMAP arguments must be ASCII without line breaks".into(),
+        ));
+    }
+    Ok(format!(
+        "\"{}\"",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    ))
+}
+
+/// UIDs from `* SEARCH` responses.
+pub fn parse_search(responses: &[ImapResponse]) -> Vec<u32> {
+    responses
+        .iter()
+        .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+        .flat_map(|rest| rest.split_whitespace().filter_map(|n| n.parse().ok()))
+        .collect()
+}
+
+/// A `* n FETCH` response carrying a UID and a body literal.
+pub fn parse_fetch(response: &ImapResponse) -> Option<FetchedMessage> {
+    if !FETCH_RE.as_ref()?.is_match(&response.text) {
+        return None;
+    }
+    let uid = UID_RE
+        .as_ref()?
+        .captures(&response.text)?
+        .get(1)?
+        .as_str()
+        .parse()
+        .ok()?;
+    let seen = FLAGS_RE
+        .as_ref()
+        .and_then(|re| re.captures(&response.text))
+        .and_then(|c| c.get(1))
+        .is_some_and(|flags| {
+            flags
+                .as_str()
+                .split_whitespace()
+                .any(|f| f.eq_ignore_ascii_case("\\Seen"))
+        });
+    let data = response.literals.first()?.clone();
+    Some(FetchedMessage { uid, seen, data })
+}
+
+// ---------------------------------------------------------------------------
+// Parsing and formatting
+// ---------------------------------------------------------------------------
+
+/// One line of a mailbox listing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EmailSummary {
+    /// Message UID, used by `read`.
+    pub uid: u32,
+    /// Sender.
+    pub from: String,
+    /// Subject line.
+    pub subject: String,
+    /// Date header, RFC 3339.
+    pub date: Option<String>,
+    /// Whether the message is unread.
+    pub unread: bool,
+}
+
+/// An attachment of a received or outgoing message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailAttachment {
+    /// Original filename, if the sender gave one.
+    pub file_name: Option<String>,
+    /// MIME type.
+    pub mime_type: String,
+    /// File contents.
+    pub data: Vec<u8>,
+}
+
+/// A parsed received message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedEmail {
+    /// Sender.
+    pub from: String,
+    /// Recipients.
+    pub to: String,
+    /// Carbon-copy recipients.
+    pub cc: String,
+    /// Subject line.
+    pub subject: String,
+    /// Date header, RFC 3339.
+    pub date: Option<String>,
+    /// `Message-ID`, for replies.
+    pub message_id: Option<String>,
+    /// Plain-text body (HTML-only mail is converted).
+    pub text: String,
+    /// Attachments.
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// Summarize a header block fetched for a listing.
+pub fn summarize(fetched: &FetchedMessage) -> EmailSummary {
+    let message = MessageParser::default().parse(&fetched.data);
+    let (from, subject, date) = match &message {
+        Some(m) => (
+            format_address(m.from()),
+            m.subject().unwrap_or("(no subject)").to_owned(),
+            m.date().map(|d| d.to_rfc3339()),
+        ),
+        None => (String::new(), "(unparseable headers)".to_owned(), None),
+    };
+    EmailSummary {
+        uid: fetched.uid,
+        from,
+        subject,
+        date,
+        unread: !fetched.seen,
+    }
+}
+
+/// Parse a full RFC 5322 message.
+pub fn parse_email(raw: &[u8]) -> Option<ParsedEmail> {
+    let message = MessageParser::default().parse(raw)?;
+    let attachments = message
+        .attachments()
+        .map(|part| EmailAttachment {
+            file_name: part.attachment_name().map(str::to_owned),
+            mime_type: part
+                .content_type()
+                .map(|ct| match ct.subtype() {
+                    Some(sub) => format!("{}/{sub}", ct.ctype()),
+                    None => ct.ctype().to_owned(),
+                })
+                .unwrap_or_else(|| "application/octet-stream".to_owned()),
+            data: part.contents().to_vec(),
+        })
+        .collect();
+
+    Some(ParsedEmail {
+        from: format_address(message.from()),
+        to: format_address(message.to()),
+        cc: format_address(message.cc()),
+        subject: message.subject().unwrap_or("(no subject)").to_owned(),
+        date: message.date().map(|d| d.to_rfc3339()),
+        message_id: message.message_id().map(str::to_owned),
+        text: message
+            .body_text(0)
+            .map(|t| t.into_owned())
+            .unwrap_or_default(),
+        attachments,
+    })
+}
+
+/// Render an address header as `Name <addr>, ...`.
+fn format_address(address: Option<&Address<'_>>) -> String {
+    address
+        .map(|a| {
+            a.iter()
+                .map(format_addr)
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+/// Render one address.
+fn format_addr(addr: &Addr<'_>) -> String {
+    match (addr.name(), addr.address()) {
+        (Some(name), Some(email)) => format!("{name} <{email}>"),
+        (None, Some(email)) => email.to_owned(),
+        (Some(name), None) => name.to_owned(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Inbox filename for an attachment: UID-prefixed and sanitized.
+pub fn attachment_filename(uid: u32, index: usize, name: Option<&str>) -> String {
+    match name.filter(|n| !n.trim().is_empty()) {
+        Some(name) => format!("{uid}_{}", sanitize_filename(name)),
+        None => format!("{uid}_attachment_{}", index.saturating_add(1)),
+    }
+}
+
+/// Format a mailbox listing.
+pub fn format_summaries(mailbox: &str, matched: usize, summaries: &[EmailSummary]) -> String {
+    if summaries.is_empty() {
+        return format!("{mailbox}: no matching messages");
+    }
+    let mut out = format!(
+        "{mailbox}: showing {} of {matched} messages, newest first\n",
+        summaries.len()
+    );
+    for s in summaries {
+        out.push_str(&format!(
+            "\n- uid {}{} | {} | {} | {}",
+            s.uid,
+            if s.unread { " (unread)" } else { "" },
+            s.date.as_deref().unwrap_or("no date"),
+            s.from,
+            s.subject
+        ));
+    }
+    out
+}
+
+/// Format a read message with its saved attachments.
+pub fn format_email(uid: u32, email: &ParsedEmail, saved: &[String]) -> String {
+    let mut out = format!("UID: {uid}\nFrom: {}\nTo: {}\n", email.from, email.to);
+    if !email.cc.is_empty() {
+        out.push_str(&format!("Cc: {}\n", email.cc));
+    }
+    if let Some(date) = &email.date {
+        out.push_str(&format!("Date: {date}\n"));
+    }
+    out.push_str(&format!("Subject: {}\n", email.subject));
+    if let Some(id) = &email.message_id {
+        out.push_str(&format!("Message-ID: <{id}>\n"));
+    }
+    if !saved.is_empty() {
+        out.push_str("Attachments saved:\n");
+        for line in saved {
+            out.push_str(&format!("- {line}\n"));
+        }
+    }
+    out.push('\n');
+    out.push_str(&truncate_to_tokens(email.text.trim(), MAX_BODY_TOKENS));
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Composing
+// ---------------------------------------------------------------------------
+
+/// An outgoing message as requested by the agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    /// Recipients.
+    pub to: Vec<String>,
+    /// Carbon-copy recipients.
+    pub cc: Vec<String>,
+    /// Subject line.
+    pub subject: String,
+    /// Plain-text body.
+    pub body: String,
+    /// `Message-ID` being replied to.
+    pub in_reply_to: Option<String>,
+    /// Workspace paths (`/workspace/...`) to attach.
+    pub attachments: Vec<String>,
+}
+
+impl Draft {
+    /// Parse a `send` request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::InvalidInput`] if there is no recipient or body,
+    /// or an address does not parse.
+    pub fn from_input(input: &serde_json::Value) -> Result<Self, ToolError> {
+        let to = string_list(input, "to");
+        if to.is_empty() {
+            return Err(ToolError::InvalidInput(
+                "send requires at least one 'to' address".into(),
+            ));
+        }
+        let cc = string_list(input, "cc");
+        for address in to.iter().chain(&cc) {
+            parse_mailbox(address)?;
+        }
+        let body = input
+            .get("body")
+            .and_then(|v| v.as_str())
+            .filter(|b| !b.trim().is_empty())
+            .ok_or_else(|| ToolError::InvalidInput("send requires a 'body'".into()))?
+            .to_owned();
+
+        Ok(Self {
+            to,
+            cc,
+            subject: input
+                .get("subject")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_owned(),
+            body,
+            in_reply_to: input
+                .get("in_reply_to")
+                .and_then(|v| v.as_str())
+                .map(|id| id.trim().trim_start_matches('<').trim_end_matches('>'))
+                .filter(|id| !id.is_empty())
+                .map(|id| format!("<{id}>")),
+            attachments: string_list(input, "attachments"),
+        })
+    }
+
+    /// Human-readable rendering for the approval prompt.
+    pub fn preview(&self) -> String {
+        let mut out = format!("To: {}\n", self.to.join(", "));
+        if !self.cc.is_empty() {
+            out.push_str(&format!("Cc: {}\n", self.cc.join(", ")));
+        }
+        out.push_str(&format!("Subject: {}\n", self.subject));
+        if let Some(id) = &self.in_reply_to {
+            out.push_str(&format!("In-Reply-To: {id}\n"));
+        }
+        if !self.attachments.is_empty() {
+            out.push_str(&format!("Attachments: {}\n", self.attachments.join(", ")));
+        }
+        out.push('\n');
+        out.push_str(&self.body);
+        out
+    }
+}
+
+/// A string or array-of-strings field; strings are split on commas.
+fn string_list(input: &serde_json::Value, key: &str) -> Vec<String> {
+    let values: Vec<&str> = match input.get(key) {
+        Some(serde_json::Value::String(s)) => s.split(',').collect(),
+        Some(serde_json::Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    values
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Parse an address, as `addr` or `Name <addr>`.
+fn parse_mailbox(address: &str) -> Result<Mailbox, ToolError> {
+    address
+        .parse()
+        .map_err(|e| ToolError::InvalidInput(format!("invalid email address '{address}': {e}")))
+}
+
+/// Build the MIME message for a draft.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if an address does not parse or the
+/// message cannot be assembled.
+pub fn build_message(
+    from: &str,
+    draft: &Draft,
+    attachments: Vec<EmailAttachment>,
+) -> Result<Message, ToolError> {
+    let mut builder = Message::builder()
+        .from(parse_mailbox(from)?)
+        .subject(draft.subject.clone());
+    for to in &draft.to {
+        builder = builder.to(parse_mailbox(to)?);
+    }
+    for cc in &draft.cc {
+        builder = builder.cc(parse_mailbox(cc)?);
+    }
+    if let Some(id) = &draft.in_reply_to {
+        builder = builder.in_reply_to(id.clone()).references(id.clone());
+    }
+
+    let text = SinglePart::plain(draft.body.clone());
+    let message = if attachments.is_empty() {
+        builder.singlepart(text)
+    } else {
+        let mut multipart = MultiPart::mixed().singlepart(text);
+        for (index, attachment) in attachments.into_iter().enumerate() {
+            let content_type = ContentType::parse(&attachment.mime_type)
+                .or_else(|_| ContentType::parse("application/octet-stream"))
+                .map_err(|e| ToolError::InvalidInput(format!("invalid attachment type: {e}")))?;
+            let name = attachment
+                .file_name
+                .unwrap_or_else(|| format!("attachment_{}", index.saturating_add(1)));
+            multipart =
+                multipart.singlepart(Attachment::new(name).body(attachment.data, content_type));
+        }
+        builder.multipart(multipart)
+    };
+    message.map_err(|e| ToolError::InvalidInput(format!("cannot build email: {e}")))
+}
+
+/// Return the tool definition for `email`.
+pub fn email_tool_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "email".to_owned(),
+        description: "Read and send email from the configured account. \
+            list: recent messages with their uid (unread_only, query to filter). \
+            read: full message by uid; attachments are saved to /workspace/inbox/email/. \
+            send: new message or reply (in_reply_to = Message-ID), files from /workspace \
+            as attachments. Every send needs the user's approval."
+            .to_owned(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "read", "send"],
+                    "description": "What to do."
+                },
+                "mailbox": {
+                    "type": "string",
+                    "description": "IMAP mailbox for list/read (default INBOX)."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "list: number of messages (default 10, max 50)."
+                },
+                "unread_only": {
+                    "type": "boolean",
+                    "description": "list: only unread messages."
+                },
+                "query": {
+                    "type": "string",
+                    "description": "list: only messages containing this text."
+                },
+                "uid": {
+                    "type": "integer",
+                    "description": "read: message uid from list."
+                },
+                "to": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "send: recipient addresses."
+                },
+                "cc": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "send: carbon-copy addresses."
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "send: subject line."
+                },
+                "body": {
+                    "type": "string",
+                    "description": "send: plain-text body."
+                },
+                "in_reply_to": {
+                    "type": "string",
+                    "description": "send: Message-ID of the message being answered."
+                },
+                "attachments": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "send: /workspace/ paths of files to attach."
+                }
+            },
+            "required": ["action"]
+        }),
+    }
+}
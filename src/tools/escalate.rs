@@ -8,6 +8,7 @@ use tracing::info;
 
 use crate::agent::budget::DailyBudget;
 use crate::providers::router::ModelRouter;
+use crate::providers::usage::{self, UsageScope};
 use crate::providers::{
     extract_text, CompletionRequest, Message, MessageContent, Role, ToolDefinition,
 };
@@ -76,10 +77,12 @@ pub async fn escalate(
         "escalating to oracle model"
     );
 
-    let response = provider
-        .complete(request)
-        .await
-        .map_err(|e| ToolError::ExecutionFailed(format!("oracle model call failed: {e}")))?;
+    let response = usage::scoped(
+        UsageScope::current_with_role("oracle"),
+        provider.complete(request),
+    )
+    .await
+    .map_err(|e| ToolError::ExecutionFailed(format!("oracle model call failed: {e}")))?;
 
     // Record actual token usage.
     let total_tokens = u64::from(response.usage.input_tokens)
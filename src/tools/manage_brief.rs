@@ -8,7 +8,8 @@ use super::ToolError;
 
 /// Handle the manage_brief tool call.
 ///
-/// Supports actions: `create`, `update`, `escalate`, `propose`, `complete`, `cancel`.
+/// Supports actions: `create`, `update`, `status`, `escalate`, `propose`,
+/// `complete`, `cancel`.
 ///
 /// # Errors
 ///
@@ -27,6 +28,7 @@ pub async fn manage_brief(
     match action {
         "create" => create_brief(db, session_id, input).await,
         "update" => update_brief(db, input).await,
+        "status" => brief_status(db, input).await,
         "escalate" => transition_brief(db, input, BriefStatus::Escalated).await,
         "propose" => transition_brief(db, input, BriefStatus::Proposed).await,
         "complete" => complete_brief(db, input).await,
@@ -171,6 +173,37 @@ async fn transition_brief(
     ))
 }
 
+/// Maximum outbound messages listed by the `status` action.
+const STATUS_MESSAGE_LIMIT: i64 = 5;
+
+/// Report a brief's status and the delivery state of its latest messages.
+async fn brief_status(db: &SqlitePool, input: &serde_json::Value) -> Result<String, ToolError> {
+    let brief_id = input
+        .get("brief_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ToolError::InvalidInput("missing required field: brief_id".to_owned()))?;
+
+    let brief = brief::load_brief(db, brief_id)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+    let recent = crate::messaging::delivery::recent_for_brief(db, brief_id, STATUS_MESSAGE_LIMIT)
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+
+    let mut out = format!(
+        "Brief {brief_id}: {} (status: {})",
+        brief.objective,
+        brief.status.as_str()
+    );
+    if recent.is_empty() {
+        out.push_str("\nNo messages sent yet.");
+    } else {
+        out.push_str("\nLatest messages (newest first):\n");
+        out.push_str(&crate::messaging::delivery::format_entries(&recent));
+    }
+    Ok(out)
+}
+
 /// Complete a brief with an optional outcome summary.
 async fn complete_brief(db: &SqlitePool, input: &serde_json::Value) -> Result<String, ToolError> {
     let brief_id = input
@@ -0,0 +1,752 @@
+//! MCP (Model Context Protocol) client: tools from external MCP servers.
+//!
+//! Servers are declared in `config.toml` as `[[mcp_servers]]` and spawned
+//! on the host at startup, speaking JSON-RPC 2.0 over stdio (one message
+//! per line). After the `initialize` handshake their tools are listed and
+//! exposed through the [`super::ToolRouter`] as `mcp_{server}_{tool}`.
+//!
+//! MCP tools run outside the sandbox, so only tools listed in the
+//! server's `auto_approve` run without approval; everything else goes
+//! through the approval flow. Server-declared annotations such as
+//! `readOnlyHint` are not trusted. Lines longer than [`MAX_LINE_BYTES`]
+//! are discarded unparsed.
+//! Servers inherit no host environment beyond `PATH` and `HOME`; secrets
+//! are passed only for the `.env` keys named in `credentials`.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::oneshot;
+use tracing::{debug, info, warn};
+
+use crate::config::McpServerConfig;
+use crate::credentials::Credentials;
+use crate::providers::ToolDefinition;
+
+use super::ToolError;
+
+/// MCP protocol revision requested in `initialize`.
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Prefix of every MCP tool name exposed to the model.
+pub const TOOL_PREFIX: &str = "mcp_";
+
+/// Maximum exposed tool name length (provider limit).
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Maximum characters of tool output returned to the model.
+const MAX_RESULT_CHARS: usize = 50_000;
+
+/// Maximum `tools/list` pages followed per server.
+const MAX_LIST_PAGES: usize = 20;
+
+/// Maximum bytes of one line read from a server's stdout or stderr.
+pub const MAX_LINE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Host environment variables passed through to every server.
+const PASSTHROUGH_ENV: &[&str] = &["PATH", "HOME"];
+
+// ---------------------------------------------------------------------------
+// Errors
+// ---------------------------------------------------------------------------
+
+/// Errors from talking to an MCP server.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum McpError {
+    /// The server process could not be started.
+    #[error("failed to start MCP server: {0}")]
+    Spawn(String),
+
+    /// The server sent something that is not valid JSON-RPC/MCP.
+    #[error("MCP protocol error: {0}")]
+    Protocol(String),
+
+    /// The server answered with a JSON-RPC error.
+    #[error("MCP error {code}: {message}")]
+    Rpc {
+        /// JSON-RPC error code.
+        code: i64,
+        /// Error message from the server.
+        message: String,
+    },
+
+    /// No response within the configured timeout.
+    #[error("MCP request {0} timed out")]
+    Timeout(String),
+
+    /// The server process exited or closed stdout.
+    #[error("MCP server closed the connection")]
+    Closed,
+}
+
+// ---------------------------------------------------------------------------
+// Tool metadata
+// ---------------------------------------------------------------------------
+
+/// A tool advertised by an MCP server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpToolInfo {
+    /// Tool name as known to the server.
+    pub name: String,
+    /// Tool description.
+    pub description: String,
+    /// JSON Schema of the tool arguments.
+    pub input_schema: Value,
+}
+
+impl McpToolInfo {
+    /// Parse one entry of a `tools/list` result. Entries without a name
+    /// are skipped.
+    pub fn from_listing(value: &Value) -> Option<Self> {
+        let name = value.get("name")?.as_str()?.to_owned();
+        let description = value
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let input_schema = value
+            .get("inputSchema")
+            .filter(|s| s.is_object())
+            .cloned()
+            .unwrap_or_else(|| json!({"type": "object", "properties": {}}));
+        Some(Self {
+            name,
+            description,
+            input_schema,
+        })
+    }
+}
+
+/// Name under which a server's tool is exposed: `mcp_{server}_{tool}`,
+/// with characters outside `[A-Za-z0-9_-]` replaced by `_`, cut to 64.
+pub fn exposed_name(server: &str, tool: &str) -> String {
+    format!("{TOOL_PREFIX}{server}_{tool}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_TOOL_NAME_LEN)
+        .collect()
+}
+
+/// Whether a server name is usable in tool names.
+pub fn is_valid_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-'))
+}
+
+/// Render a `tools/call` result as text. Returns the text and whether the
+/// server flagged the call as failed (`isError`).
+pub fn format_call_result(result: &Value) -> (String, bool) {
+    let is_error = result
+        .get("isError")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut parts = Vec::new();
+    for block in result
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let kind = block.get("type").and_then(Value::as_str).unwrap_or("");
+        let part = match kind {
+            "text" => block
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            "image" | "audio" => format!(
+                "[{kind}: {}]",
+                block
+                    .get("mimeType")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown type")
+            ),
+            "resource" => match block.pointer("/resource/text").and_then(Value::as_str) {
+                Some(text) => text.to_owned(),
+                None => format!(
+                    "[resource: {}]",
+                    block
+                        .pointer("/resource/uri")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                ),
+            },
+            other => format!("[unsupported content: {other}]"),
+        };
+        parts.push(part);
+    }
+
+    if parts.is_empty() {
+        if let Some(structured) = result.get("structuredContent") {
+            parts.push(structured.to_string());
+        }
+    }
+
+    let mut text = parts.join("\n");
+    if let Some((cut, _)) = text.char_indices().nth(MAX_RESULT_CHARS) {
+        text.truncate(cut);
+        text.push_str("\n... (truncated)");
+    }
+    (text, is_error)
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// Responses awaited by request ID.
+type PendingMap = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, McpError>>>>>;
+
+/// Connection to one MCP server process over stdio.
+pub struct McpClient {
+    /// Server name from config.
+    name: String,
+    /// Server stdin; one JSON-RPC message per line.
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    /// Requests awaiting a response.
+    pending: PendingMap,
+    /// Set once the server's stdout has closed.
+    closed: Arc<AtomicBool>,
+    /// Next request ID.
+    next_id: AtomicI64,
+    /// Server process, killed on shutdown or drop.
+    child: tokio::sync::Mutex<Child>,
+    /// Per-request timeout.
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for McpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpClient")
+            .field("name", &self.name)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpClient {
+    /// Spawn the server process and start reading its responses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Spawn`] if the process cannot be started or a
+    /// listed credential is missing from `.env`.
+    pub fn spawn(config: &McpServerConfig, credentials: &Credentials) -> Result<Self, McpError> {
+        let mut command = Command::new(&config.command);
+        command
+            .args(&config.args)
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for key in PASSTHROUGH_ENV {
+            if let Ok(value) = std::env::var(key) {
+                command.env(key, value);
+            }
+        }
+        for key in &config.credentials {
+            let value = credentials
+                .get(key)
+                .ok_or_else(|| McpError::Spawn(format!("credential {key} is not set in .env")))?;
+            command.env(key, value);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| McpError::Spawn(format!("{}: {e}", config.command)))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| McpError::Spawn("stdin not captured".to_owned()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| McpError::Spawn("stdout not captured".to_owned()))?;
+
+        if let Some(stderr) = child.stderr.take() {
+            let server = config.name.clone();
+            tokio::spawn(async move {
+                let mut reader = BufReader::new(stderr);
+                loop {
+                    match read_capped_line(&mut reader, MAX_LINE_BYTES).await {
+                        Ok(CappedLine::Text(line)) => {
+                            debug!(server = %server, "mcp stderr: {line}")
+                        }
+                        Ok(CappedLine::Oversized) => {
+                            debug!(server = %server, "dropping oversized mcp stderr line");
+                        }
+                        Ok(CappedLine::Eof) | Err(_) => break,
+                    }
+                }
+            });
+        }
+
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let reader_pending = Arc::clone(&pending);
+        let closed = Arc::new(AtomicBool::new(false));
+        let reader_closed = Arc::clone(&closed);
+        let server = config.name.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                match read_capped_line(&mut reader, MAX_LINE_BYTES).await {
+                    Ok(CappedLine::Text(line)) => handle_line(&server, &line, &reader_pending),
+                    Ok(CappedLine::Oversized) => {
+                        warn!(server = %server, max = MAX_LINE_BYTES, "dropping oversized mcp message");
+                    }
+                    Ok(CappedLine::Eof) | Err(_) => break,
+                }
+            }
+            debug!(server = %server, "mcp server stdout closed");
+            reader_closed.store(true, Ordering::SeqCst);
+            if let Ok(mut map) = reader_pending.lock() {
+                for (_, tx) in map.drain() {
+                    let _ = tx.send(Err(McpError::Closed));
+                }
+            }
+        });
+
+        Ok(Self {
+            name: config.name.clone(),
+            stdin: tokio::sync::Mutex::new(stdin),
+            pending,
+            closed,
+            next_id: AtomicI64::new(1),
+            child: tokio::sync::Mutex::new(child),
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    /// Server name from config.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Send a request and wait for its response.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError`] on write failure, timeout, server error, or a
+    /// closed connection.
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value, McpError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut map) = self.pending.lock() {
+            map.insert(id, tx);
+        }
+        // Checked after registering so the reader's final drain covers us.
+        if self.closed.load(Ordering::SeqCst) {
+            self.forget(id);
+            return Err(McpError::Closed);
+        }
+
+        let message = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        if let Err(e) = self.write(&message).await {
+            self.forget(id);
+            return Err(e);
+        }
+
+        match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(McpError::Closed),
+            Err(_) => {
+                self.forget(id);
+                Err(McpError::Timeout(method.to_owned()))
+            }
+        }
+    }
+
+    /// Send a notification (no response expected).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Closed`] if the server's stdin is closed.
+    pub async fn notify(&self, method: &str, params: Value) -> Result<(), McpError> {
+        self.write(&json!({"jsonrpc": "2.0", "method": method, "params": params}))
+            .await
+    }
+
+    /// Run the `initialize` handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError`] if the server rejects the handshake.
+    pub async fn initialize(&self) -> Result<(), McpError> {
+        let result = self
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "wintermute", "version": env!("CARGO_PKG_VERSION")},
+                }),
+            )
+            .await?;
+        let version = result
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        debug!(server = %self.name, version, "mcp server initialized");
+        self.notify("notifications/initialized", json!({})).await
+    }
+
+    /// List the server's tools, following pagination cursors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError`] if a `tools/list` request fails.
+    pub async fn list_tools(&self) -> Result<Vec<McpToolInfo>, McpError> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = match &cursor {
+                Some(c) => json!({"cursor": c}),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+            let page = result
+                .get("tools")
+                .and_then(Value::as_array)
+                .ok_or_else(|| McpError::Protocol("tools/list result has no tools".to_owned()))?;
+            tools.extend(page.iter().filter_map(McpToolInfo::from_listing));
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(str::to_owned);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(tools)
+    }
+
+    /// Call a tool. Returns its text output and whether it reported an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError`] if the request itself fails.
+    pub async fn call_tool(
+        &self,
+        tool: &str,
+        arguments: &Value,
+    ) -> Result<(String, bool), McpError> {
+        let arguments = if arguments.is_object() {
+            arguments.clone()
+        } else {
+            json!({})
+        };
+        let result = self
+            .request("tools/call", json!({"name": tool, "arguments": arguments}))
+            .await?;
+        Ok(format_call_result(&result))
+    }
+
+    /// Kill the server process.
+    pub async fn shutdown(&self) {
+        if let Err(e) = self.child.lock().await.kill().await {
+            debug!(server = %self.name, error = %e, "mcp server already exited");
+        }
+    }
+
+    async fn write(&self, message: &Value) -> Result<(), McpError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|_| McpError::Closed)?;
+        stdin.flush().await.map_err(|_| McpError::Closed)
+    }
+
+    fn forget(&self, id: i64) {
+        if let Ok(mut map) = self.pending.lock() {
+            map.remove(&id);
+        }
+    }
+}
+
+/// One line read by [`read_capped_line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CappedLine {
+    /// A complete line, without its trailing newline.
+    Text(String),
+    /// A line longer than the cap; its bytes were consumed and dropped.
+    Oversized,
+    /// End of input.
+    Eof,
+}
+
+/// Read one newline-terminated line, holding at most `max` bytes of it.
+///
+/// Unlike `lines()`, a peer that never sends a newline cannot make the
+/// reader buffer without bound: once a line passes `max` bytes the rest of
+/// it is skipped and [`CappedLine::Oversized`] is returned.
+///
+/// # Errors
+///
+/// Returns the underlying I/O error.
+pub async fn read_capped_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    max: usize,
+) -> std::io::Result<CappedLine> {
+    let mut line = Vec::new();
+    let mut oversized = false;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Ok(if oversized {
+                CappedLine::Oversized
+            } else if line.is_empty() {
+                CappedLine::Eof
+            } else {
+                CappedLine::Text(String::from_utf8_lossy(&line).into_owned())
+            });
+        }
+        let newline = available.iter().position(|b| *b == b'\n');
+        let chunk = available
+            .get(..newline.unwrap_or(available.len()))
+            .unwrap_or_default();
+        if !oversized {
+            if line.len().saturating_add(chunk.len()) > max {
+                oversized = true;
+                line = Vec::new();
+            } else {
+                line.extend_from_slice(chunk);
+            }
+        }
+        let consumed = chunk.len().saturating_add(usize::from(newline.is_some()));
+        reader.consume(consumed);
+        if newline.is_some() {
+            return Ok(if oversized {
+                CappedLine::Oversized
+            } else {
+                CappedLine::Text(String::from_utf8_lossy(&line).into_owned())
+            });
+        }
+    }
+}
+
+/// Route one line from the server to the request waiting for it.
+///
+/// Notifications and server-initiated requests are logged and dropped;
+/// this client advertises no capabilities the server could call.
+fn handle_line(server: &str, line: &str, pending: &PendingMap) {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(_) => {
+            debug!(server, "ignoring non-JSON line from mcp server");
+            return;
+        }
+    };
+    let id = message.get("id").and_then(Value::as_i64);
+    let is_response = message.get("result").is_some() || message.get("error").is_some();
+    let Some(id) = id.filter(|_| is_response) else {
+        if let Some(method) = message.get("method").and_then(Value::as_str) {
+            debug!(server, method, "ignoring mcp server message");
+        }
+        return;
+    };
+
+    let outcome = match message.get("error") {
+        Some(error) => Err(McpError::Rpc {
+            code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error")
+                .to_owned(),
+        }),
+        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+    };
+
+    let waiter = pending.lock().ok().and_then(|mut map| map.remove(&id));
+    match waiter {
+        Some(tx) => {
+            let _ = tx.send(outcome);
+        }
+        None => debug!(server, id, "mcp response for unknown request"),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Manager
+// ---------------------------------------------------------------------------
+
+/// An exposed MCP tool and where it lives.
+#[derive(Debug, Clone)]
+struct McpRoute {
+    /// Server name.
+    server: String,
+    /// Tool metadata (original name).
+    tool: McpToolInfo,
+    /// Whether calls need user approval.
+    requires_approval: bool,
+}
+
+/// Running MCP servers and the tools they expose.
+#[derive(Debug, Default)]
+pub struct McpManager {
+    /// Connected clients by server name.
+    clients: HashMap<String, Arc<McpClient>>,
+    /// Exposed tool name → route.
+    routes: HashMap<String, McpRoute>,
+}
+
+impl McpManager {
+    /// Start every enabled server. Servers that fail to start, initialize,
+    /// or list tools are logged and skipped.
+    pub async fn start(configs: &[McpServerConfig], credentials: &Credentials) -> Self {
+        let mut manager = Self::default();
+        for config in configs.iter().filter(|c| c.enabled) {
+            if !is_valid_server_name(&config.name) {
+                warn!(server = %config.name, "skipping MCP server: name must be [a-z0-9_-], max 32");
+                continue;
+            }
+            if manager.clients.contains_key(&config.name) {
+                warn!(server = %config.name, "skipping duplicate MCP server name");
+                continue;
+            }
+            match connect(config, credentials).await {
+                Ok((client, tools)) => {
+                    info!(server = %config.name, tools = tools.len(), "MCP server connected");
+                    manager.add_server(config, Arc::new(client), tools);
+                }
+                Err(e) => warn!(server = %config.name, error = %e, "MCP server unavailable"),
+            }
+        }
+        manager
+    }
+
+    /// Register a connected server and its tools.
+    fn add_server(
+        &mut self,
+        config: &McpServerConfig,
+        client: Arc<McpClient>,
+        tools: Vec<McpToolInfo>,
+    ) {
+        for tool in tools {
+            let exposed = exposed_name(&config.name, &tool.name);
+            if self.routes.contains_key(&exposed) {
+                warn!(tool = %exposed, "skipping MCP tool with colliding name");
+                continue;
+            }
+            let requires_approval = !config.auto_approve.contains(&tool.name);
+            self.routes.insert(
+                exposed,
+                McpRoute {
+                    server: config.name.clone(),
+                    tool,
+                    requires_approval,
+                },
+            );
+        }
+        self.clients.insert(config.name.clone(), client);
+    }
+
+    /// Number of connected servers.
+    pub fn server_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Number of exposed tools.
+    pub fn tool_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Whether `name` is an exposed MCP tool.
+    pub fn handles(&self, name: &str) -> bool {
+        self.routes.contains_key(name)
+    }
+
+    /// Whether calling `name` needs user approval. Unknown tools do not.
+    pub fn requires_approval(&self, name: &str) -> bool {
+        self.routes.get(name).is_some_and(|r| r.requires_approval)
+    }
+
+    /// Tool definitions for all exposed tools, sorted by name.
+    pub fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        let mut defs: Vec<ToolDefinition> = self
+            .routes
+            .iter()
+            .map(|(exposed, route)| ToolDefinition {
+                name: exposed.clone(),
+                description: format!("[MCP {}] {}", route.server, route.tool.description),
+                input_schema: route.tool.input_schema.clone(),
+            })
+            .collect();
+        defs.sort_by(|a, b| a.name.cmp(&b.name));
+        defs
+    }
+
+    /// Call an exposed tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::UnknownTool`] for names not exposed by any
+    /// server and [`ToolError::ExecutionFailed`] if the call fails or the
+    /// tool reports an error.
+    pub async fn call(&self, name: &str, input: &Value) -> Result<String, ToolError> {
+        let route = self
+            .routes
+            .get(name)
+            .ok_or_else(|| ToolError::UnknownTool(name.to_owned()))?;
+        let client = self.clients.get(&route.server).ok_or_else(|| {
+            ToolError::ExecutionFailed(format!("MCP server {} is not running", route.server))
+        })?;
+
+        debug!(server = %route.server, tool = %route.tool.name, "calling MCP tool");
+        let (text, is_error) = client
+            .call_tool(&route.tool.name, input)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(e.to_string()))?;
+        if is_error {
+            Err(ToolError::ExecutionFailed(text))
+        } else {
+            Ok(text)
+        }
+    }
+
+    /// Stop every server process.
+    pub async fn shutdown(&self) {
+        for client in self.clients.values() {
+            client.shutdown().await;
+        }
+    }
+}
+
+/// Spawn, initialize, and list the tools of one server.
+async fn connect(
+    config: &McpServerConfig,
+    credentials: &Credentials,
+) -> Result<(McpClient, Vec<McpToolInfo>), McpError> {
+    let client = McpClient::spawn(config, credentials)?;
+    let setup = async {
+        client.initialize().await?;
+        client.list_tools().await
+    };
+    match setup.await {
+        Ok(tools) => Ok((client, tools)),
+        Err(e) => {
+            client.shutdown().await;
+            Err(e)
+        }
+    }
+}
@@ -6,26 +6,35 @@
 
 pub mod browser;
 pub mod browser_bridge;
+pub mod browser_sessions;
 pub mod core;
 pub mod create_tool;
 pub mod docker;
+pub mod email;
 pub mod escalate;
 pub mod flatline;
 pub mod manage_brief;
+pub mod mcp;
+pub mod page_cache;
 pub mod read_messages;
+pub mod readability;
 pub mod registry;
+pub mod rss;
+pub mod self_test;
 pub mod send_message;
+pub mod web_search;
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 use tokio::sync::mpsc;
 use tracing::{debug, warn};
 
 use crate::agent::budget::DailyBudget;
 use crate::agent::policy::{PolicyError, RateLimiter};
-use crate::agent::TelegramOutbound;
+use crate::agent::{Keyboard, TelegramOutbound};
 use crate::executor::redactor::Redactor;
-use crate::executor::Executor;
+use crate::executor::{Executor, ExecutorError};
 use crate::memory::MemoryEngine;
 use crate::messaging::outbound_composer::OutboundComposer;
 use crate::providers::router::ModelRouter;
@@ -146,6 +155,19 @@ pub struct ToolRouter {
     whatsapp_client: Option<Arc<WhatsAppClient>>,
     /// Optional outbound composer for brief-scoped message generation.
     outbound_composer: Option<Arc<OutboundComposer>>,
+    /// Search backend for web_search (DuckDuckGo unless configured).
+    web_search: Arc<web_search::WebSearch>,
+    /// Pages fetched by web_fetch, cached per session.
+    page_cache: page_cache::PageCache,
+    /// Optional email account; when None, the email tool is not offered.
+    email: Option<Arc<email::EmailAccount>>,
+    /// Optional MCP servers; their tools are offered alongside core tools.
+    mcp: Option<Arc<mcp::McpManager>>,
+    /// Telegram user told when a dynamic tool is disabled.
+    owner: Option<i64>,
+    /// Compiled input validators for built-in and MCP tools, built on first
+    /// dispatch. `None` marks a schema that does not compile.
+    builtin_validators: OnceLock<HashMap<String, Option<InputValidator>>>,
 }
 
 impl std::fmt::Debug for ToolRouter {
@@ -196,9 +218,52 @@ impl ToolRouter {
             daily_budget,
             whatsapp_client,
             outbound_composer,
+            web_search: Arc::new(web_search::WebSearch::default()),
+            page_cache: page_cache::PageCache::default(),
+            email: None,
+            mcp: None,
+            owner: None,
+            builtin_validators: OnceLock::new(),
         }
     }
 
+    /// Use the given search backend for web_search.
+    #[must_use]
+    pub fn with_web_search(mut self, search: web_search::WebSearch) -> Self {
+        self.web_search = Arc::new(search);
+        self
+    }
+
+    /// Enable the email tool for the given account.
+    #[must_use]
+    pub fn with_email(mut self, account: email::EmailAccount) -> Self {
+        self.email = Some(Arc::new(account));
+        self
+    }
+
+    /// Expose the tools of the given MCP servers.
+    #[must_use]
+    pub fn with_mcp(mut self, manager: Arc<mcp::McpManager>) -> Self {
+        self.mcp = Some(manager);
+        self
+    }
+
+    /// Notify `owner` when a failing dynamic tool is disabled.
+    #[must_use]
+    pub fn with_owner(mut self, owner: Option<i64>) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Whether a call needs approval regardless of policy.
+    ///
+    /// True for MCP tools the server did not annotate read-only.
+    pub fn requires_approval(&self, name: &str) -> bool {
+        self.mcp
+            .as_ref()
+            .is_some_and(|mcp| mcp.requires_approval(name))
+    }
+
     /// Execute a tool by name with the given JSON input.
     ///
     /// Dispatches to core tools first, then dynamic registry.
@@ -212,6 +277,54 @@ impl ToolRouter {
         &self.redactor
     }
 
+    /// Drop per-session tool state (cached web_fetch pages) when a session
+    /// ends.
+    pub fn end_session(&self, session_id: &str) {
+        self.page_cache.clear_session(session_id);
+    }
+
+    /// Render what a tool call would send, for display in its approval prompt.
+    ///
+    /// Templated `send_message` calls and email sends have a preview; a
+    /// preview that cannot be rendered is returned as an explanatory message
+    /// so the user still sees why the send is likely to fail.
+    pub async fn approval_preview(&self, name: &str, input: &serde_json::Value) -> Option<String> {
+        match name {
+            "send_message" => match send_message::template_preview(
+                input,
+                self.outbound_composer.as_ref(),
+                self.memory.pool(),
+            )
+            .await
+            {
+                Ok(preview) => preview,
+                Err(e) => Some(format!("(preview unavailable: {e})")),
+            },
+            "email" => email::send_preview(input, &self.redactor),
+            "create_tool" => core::parse_manifest(input)
+                .ok()
+                .filter(|manifest| !manifest.needs_network.is_empty())
+                .map(|manifest| {
+                    format!(
+                        "Tool {} asks for network access to: {}",
+                        input.get("name").and_then(|v| v.as_str()).unwrap_or("?"),
+                        manifest.needs_network.join(", ")
+                    )
+                }),
+            _ => None,
+        }
+    }
+
+    /// Return the WhatsApp client, if WhatsApp is enabled.
+    pub fn whatsapp_client(&self) -> Option<&Arc<WhatsAppClient>> {
+        self.whatsapp_client.as_ref()
+    }
+
+    /// Return the browser bridge, if a browser is available.
+    pub fn browser_bridge(&self) -> Option<&Arc<dyn BrowserBridge>> {
+        self.browser_bridge.as_ref()
+    }
+
     /// Execute a tool with optional session user context.
     ///
     /// When `session_user_id` is provided, it is used by tools that need an
@@ -224,10 +337,16 @@ impl ToolRouter {
     ) -> ToolResult {
         debug!(tool = name, "dispatching tool call");
 
-        let raw_result = self.dispatch(name, input, session_user_id).await;
+        let raw_result = match self.validator(name) {
+            Some(validator) => match check_input(name, &validator, input) {
+                Ok(()) => self.dispatch(name, input, session_user_id).await,
+                Err(e) => ToolResult::error(e.to_string()),
+            },
+            None => self.dispatch(name, input, session_user_id).await,
+        };
 
         // CRITICAL: ALL output passes through the redactor.
-        let redacted_content = self.redactor.redact(&raw_result.content);
+        let redacted_content = self.redactor.redact_output(&raw_result.content);
         ToolResult {
             content: redacted_content,
             is_error: raw_result.is_error,
@@ -246,15 +365,37 @@ impl ToolRouter {
                 into_tool_result(core::execute_command(&*self.executor, input).await)
             }
             "web_fetch" => into_tool_result(
-                core::web_fetch(input, &self.fetch_limiter, self.max_download_bytes).await,
+                core::web_fetch(
+                    input,
+                    &self.fetch_limiter,
+                    self.max_download_bytes,
+                    &self.memory,
+                    &self.page_cache,
+                    &session_key(session_user_id),
+                )
+                .await,
             ),
             "web_request" => {
                 into_tool_result(core::web_request(input, &self.request_limiter).await)
             }
-            "browser" => into_tool_result(
-                browser::run_browser(input, &self.browser_limiter, self.browser_bridge.as_deref())
+            "web_search" => into_tool_result(
+                web_search::web_search(&self.web_search, &self.memory, &self.fetch_limiter, input)
                     .await,
             ),
+            "browser" => {
+                let result = browser::run_browser(
+                    input,
+                    &self.browser_limiter,
+                    self.browser_bridge.as_deref(),
+                )
+                .await;
+                if input.get("action").and_then(|v| v.as_str()) == Some("screenshot") {
+                    if let Ok(output) = &result {
+                        self.send_screenshot(output, session_user_id).await;
+                    }
+                }
+                into_tool_result(result)
+            }
             "memory_search" => into_tool_result(core::memory_search(&self.memory, input).await),
             "memory_save" => into_tool_result(core::memory_save(&self.memory, input).await),
             "send_message" => {
@@ -283,9 +424,7 @@ impl ToolRouter {
                 // Use authenticated session key from the caller context, not
                 // from the LLM-controlled input, to prevent cross-session
                 // brief manipulation.
-                let session_id = session_user_id
-                    .map(|uid| format!("user_{uid}"))
-                    .unwrap_or_else(|| "unknown".to_owned());
+                let session_id = session_key(session_user_id);
                 into_tool_result(
                     manage_brief::manage_brief(self.memory.pool(), &session_id, input).await,
                 )
@@ -302,6 +441,22 @@ impl ToolRouter {
                 Some(root) => into_tool_result(flatline::flatline_status(root, input).await),
                 None => ToolResult::error("flatline supervisor not installed"),
             },
+            "email" => match &self.email {
+                Some(account) => into_tool_result(
+                    email::email(
+                        account,
+                        &self.redactor,
+                        self.executor.workspace_dir(),
+                        input,
+                    )
+                    .await,
+                ),
+                None => ToolResult::error("email not configured"),
+            },
+            "rss_subscribe" => {
+                into_tool_result(rss::rss_subscribe(&self.memory, &self.fetch_limiter, input).await)
+            }
+            "rss_list" => into_tool_result(rss::rss_list(self.memory.pool(), input).await),
             "escalate" => match (&self.model_router, &self.daily_budget) {
                 (Some(router), Some(budget)) => {
                     into_tool_result(escalate::escalate(router, budget, input).await)
@@ -310,8 +465,18 @@ impl ToolRouter {
                     "escalate requires model router and daily budget configuration",
                 ),
             },
+            _ if self.mcp.as_ref().is_some_and(|mcp| mcp.handles(name)) => match &self.mcp {
+                Some(mcp) => into_tool_result(mcp.call(name, input).await),
+                None => ToolResult::error("MCP not configured"),
+            },
             _ => {
                 if let Some(schema) = self.registry.get(name) {
+                    if schema.is_disabled() {
+                        return ToolResult::error(format!(
+                            "tool '{name}' is disabled after repeated failures; \
+                             fix it with create_tool or ask the owner to re-enable it"
+                        ));
+                    }
                     self.registry.record_usage(name);
                     self.execute_dynamic(name, &schema, input).await
                 } else {
@@ -322,13 +487,58 @@ impl ToolRouter {
         }
     }
 
+    /// Forward a browser screenshot to the session user on Telegram.
+    async fn send_screenshot(&self, output: &str, session_user_id: Option<i64>) {
+        let (Some(tx), Some(user_id)) = (&self.telegram_tx, session_user_id) else {
+            return;
+        };
+        let Some(path) = browser::screenshot_host_path(output, self.executor.workspace_dir())
+        else {
+            return;
+        };
+        let msg = TelegramOutbound {
+            user_id,
+            text: None,
+            file_path: Some(path.display().to_string()),
+            approval_keyboard: None,
+            keyboard: None,
+        };
+        if let Err(e) = tx.send(msg).await {
+            warn!(error = %e, "failed to send browser screenshot");
+        }
+    }
+
     /// Execute a dynamically registered tool by running its script.
+    ///
+    /// The tool's manifest is enforced here: it runs with its runtime limit,
+    /// a read-only workspace unless it declared writes, and network access
+    /// only if every domain it declared has been approved.
     async fn execute_dynamic(
         &self,
         name: &str,
         schema: &registry::DynamicToolSchema,
         input: &serde_json::Value,
     ) -> ToolResult {
+        let manifest = &schema.manifest;
+        if !manifest.needs_network.is_empty() {
+            let granted = match self.memory.tool_network_grants(name).await {
+                Ok(granted) => granted,
+                Err(e) => {
+                    return ToolResult::error(format!(
+                        "failed to check network grants for '{name}': {e}"
+                    ))
+                }
+            };
+            let missing = manifest.ungranted_domains(&granted);
+            if !missing.is_empty() {
+                return ToolResult::error(format!(
+                    "tool '{name}' needs network access to {} which was not approved; \
+                     recreate it with create_tool to ask the user",
+                    missing.join(", ")
+                ));
+            }
+        }
+
         let scripts_dir = self.executor.scripts_dir().display();
         let input_json = input.to_string();
         let escaped_input = crate::executor::docker::shell_escape(&input_json);
@@ -336,36 +546,172 @@ impl ToolRouter {
         let command = format!("echo {escaped_input} | python3 {scripts_dir}/{name}.py");
 
         let opts = crate::executor::ExecOptions {
-            timeout: std::time::Duration::from_secs(schema.timeout_secs),
+            timeout: std::time::Duration::from_secs(schema.max_runtime_secs()),
             working_dir: None,
+            restrictions: Some(manifest.restrictions(name, true)),
         };
 
         let start = std::time::Instant::now();
         let exec_result = self.executor.execute(&command, opts).await;
         let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
 
-        let result = match exec_result {
+        let (result, disabled) = match exec_result {
             Ok(result) => {
                 let success = result.success();
                 let error_msg = if success { None } else { Some(result.output()) };
-                self.registry
-                    .record_execution(name, success, duration_ms, error_msg.as_deref());
+                let disabled = self.registry.record_execution(
+                    name,
+                    success,
+                    duration_ms,
+                    error_msg.as_deref(),
+                );
                 if success {
-                    ToolResult::success(result.stdout)
+                    (ToolResult::success(result.stdout), disabled)
                 } else {
-                    ToolResult::error(result.output())
+                    (ToolResult::error(result.output()), disabled)
                 }
             }
-            Err(e) => {
+            // A timeout is the script's fault; sandbox and policy failures are
+            // not, so they stay out of the tool's health record.
+            Err(e @ ExecutorError::Timeout { .. }) => {
                 let msg = format!("dynamic tool execution failed: {e}");
-                self.registry
+                let disabled = self
+                    .registry
                     .record_execution(name, false, duration_ms, Some(&msg));
-                ToolResult::error(msg)
+                (ToolResult::error(msg), disabled)
+            }
+            Err(e) => {
+                warn!(tool = name, error = %e, "dynamic tool could not be executed");
+                (
+                    ToolResult::error(format!("dynamic tool execution failed: {e}")),
+                    None,
+                )
             }
         };
 
+        if let Some(meta) = disabled {
+            self.notify_tool_disabled(name, &meta).await;
+        }
+
         result
     }
+
+    /// Tell the owner a dynamic tool was disabled, with its recent errors.
+    ///
+    /// The buttons re-enable the tool or ask the agent to fix it.
+    async fn notify_tool_disabled(&self, name: &str, meta: &registry::ToolMeta) {
+        let (Some(tx), Some(user_id)) = (&self.telegram_tx, self.owner) else {
+            return;
+        };
+        let errors: Vec<String> = meta
+            .recent_errors
+            .iter()
+            .map(|e| self.redactor.redact(e))
+            .collect();
+        let msg = TelegramOutbound {
+            user_id,
+            text: Some(crate::telegram::ui::format_tool_disabled(
+                name,
+                meta.disabled.as_ref(),
+                &errors,
+            )),
+            file_path: None,
+            approval_keyboard: None,
+            keyboard: crate::telegram::ui::tool_callbacks_fit(name)
+                .then(|| Keyboard::ToolDisabled(name.to_owned())),
+        };
+        if let Err(e) = tx.send(msg).await {
+            warn!(tool = name, error = %e, "failed to send tool disabled notice");
+        }
+    }
+}
+
+/// Maximum schema violations listed in one [`ToolError::InvalidInput`].
+const MAX_REPORTED_VIOLATIONS: usize = 5;
+
+/// A compiled tool input schema, shared between dispatches.
+pub type InputValidator = Arc<jsonschema::Validator>;
+
+/// Compile a tool's input schema.
+///
+/// A schema that fails to compile (for example a hand-edited dynamic tool)
+/// is logged and yields `None`, so the tool runs without validation rather
+/// than being blocked.
+pub fn compile_input_schema(name: &str, schema: &serde_json::Value) -> Option<InputValidator> {
+    match jsonschema::validator_for(schema) {
+        Ok(validator) => Some(Arc::new(validator)),
+        Err(e) => {
+            warn!(tool = name, error = %e, "tool input schema does not compile; skipping validation");
+            None
+        }
+    }
+}
+
+/// Validate tool arguments against the tool's JSON Schema.
+///
+/// Compiles the schema on every call; dispatch uses validators compiled
+/// once per tool instead. See [`check_input`] for the error format.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] listing the violations.
+pub fn validate_input(
+    name: &str,
+    schema: &serde_json::Value,
+    input: &serde_json::Value,
+) -> Result<(), ToolError> {
+    match compile_input_schema(name, schema) {
+        Some(validator) => check_input(name, &validator, input),
+        None => Ok(()),
+    }
+}
+
+/// Check tool arguments against a compiled schema.
+///
+/// Each violation is reported with its JSON pointer so the model can fix
+/// the exact field and retry.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] listing the violations.
+pub fn check_input(
+    name: &str,
+    validator: &jsonschema::Validator,
+    input: &serde_json::Value,
+) -> Result<(), ToolError> {
+    let violations = schema_violations(validator, input);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(ToolError::InvalidInput(format!(
+            "arguments for {name} do not match its schema: {}",
+            violations.join("; ")
+        )))
+    }
+}
+
+/// Up to [`MAX_REPORTED_VIOLATIONS`] schema violations of `value`, each
+/// prefixed with its JSON pointer.
+pub(crate) fn schema_violations(
+    validator: &jsonschema::Validator,
+    value: &serde_json::Value,
+) -> Vec<String> {
+    validator
+        .iter_errors(value)
+        .take(MAX_REPORTED_VIOLATIONS)
+        .map(|e| {
+            let path = e.instance_path.as_str();
+            let path = if path.is_empty() { "/" } else { path };
+            format!("{path}: {e}")
+        })
+        .collect()
+}
+
+/// Session ID for the calling user, matching the agent's session keys.
+fn session_key(session_user_id: Option<i64>) -> String {
+    session_user_id
+        .map(|uid| format!("user_{uid}"))
+        .unwrap_or_else(|| "unknown".to_owned())
 }
 
 /// Convert a tool function result into a [`ToolResult`].
@@ -383,6 +729,38 @@ impl ToolRouter {
     /// When `query` is provided, tools whose descriptions overlap with the query are
     /// preferred; otherwise tools are ordered by most recently used first.
     pub fn tool_definitions(&self, max_dynamic: u32, query: Option<&str>) -> Vec<ToolDefinition> {
+        let mut defs = self.builtin_definitions();
+        let max_dynamic = match usize::try_from(max_dynamic) {
+            Ok(value) => value,
+            Err(_) => usize::MAX,
+        };
+        let dynamic = self.registry.ranked_definitions(max_dynamic, query);
+        defs.extend(dynamic);
+        defs
+    }
+
+    /// Compiled input validator for a tool, compiled at most once.
+    ///
+    /// Built-in and MCP schemas are fixed once the router is built; dynamic
+    /// tool validators are compiled by the registry when a tool loads.
+    fn validator(&self, name: &str) -> Option<InputValidator> {
+        let builtins = self.builtin_validators.get_or_init(|| {
+            self.builtin_definitions()
+                .into_iter()
+                .map(|def| {
+                    let validator = compile_input_schema(&def.name, &def.input_schema);
+                    (def.name, validator)
+                })
+                .collect()
+        });
+        match builtins.get(name) {
+            Some(validator) => validator.clone(),
+            None => self.registry.validator(name),
+        }
+    }
+
+    /// Core, optional built-in, and MCP tool definitions.
+    fn builtin_definitions(&self) -> Vec<ToolDefinition> {
         let mut defs = core::core_tool_definitions();
         if self.browser_bridge.is_none() {
             defs.retain(|def| def.name != "browser");
@@ -396,12 +774,12 @@ impl ToolRouter {
         if self.model_router.is_some() && self.daily_budget.is_some() {
             defs.push(escalate::escalate_tool_definition());
         }
-        let max_dynamic = match usize::try_from(max_dynamic) {
-            Ok(value) => value,
-            Err(_) => usize::MAX,
-        };
-        let dynamic = self.registry.ranked_definitions(max_dynamic, query);
-        defs.extend(dynamic);
+        if self.email.is_some() {
+            defs.push(email::email_tool_definition());
+        }
+        if let Some(mcp) = &self.mcp {
+            defs.extend(mcp.tool_definitions());
+        }
         defs
     }
 
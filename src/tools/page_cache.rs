@@ -0,0 +1,105 @@
+//! Per-session cache of pages fetched by web_fetch.
+//!
+//! Re-reading a page within a session (a different token budget, the raw
+//! body after the markdown view) is served from memory instead of going back
+//! to the network or spending the fetch rate limit. Entries expire after a
+//! TTL and the oldest entry is evicted when the cache is full.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time a fetched page stays cached.
+pub const DEFAULT_TTL_SECS: u64 = 900;
+
+/// Default maximum number of cached pages across all sessions.
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// A fetched page body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPage {
+    /// URL after redirects.
+    pub final_url: String,
+    /// `Content-Type` header, if present.
+    pub content_type: Option<String>,
+    /// Response body.
+    pub body: String,
+}
+
+/// Cache key: the session ID and the requested URL.
+type CacheKey = (String, String);
+
+/// In-memory page cache scoped by session.
+#[derive(Debug)]
+pub struct PageCache {
+    entries: Mutex<HashMap<CacheKey, (Instant, CachedPage)>>,
+    ttl: Duration,
+    capacity: usize,
+}
+
+impl Default for PageCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_TTL_SECS), DEFAULT_CAPACITY)
+    }
+}
+
+impl PageCache {
+    /// Create a cache with the given TTL and capacity.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Look up a page fetched earlier in this session.
+    pub fn get(&self, session: &str, url: &str) -> Option<CachedPage> {
+        let mut entries = self.entries.lock().ok()?;
+        let key = (session.to_owned(), url.to_owned());
+        match entries.get(&key) {
+            Some((stored_at, page)) if stored_at.elapsed() < self.ttl => Some(page.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a fetched page, evicting expired entries and then the oldest.
+    pub fn insert(&self, session: &str, url: &str, page: CachedPage) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        let ttl = self.ttl;
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < ttl);
+        if entries.len() >= self.capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                entries.remove(&key);
+            }
+        }
+        entries.insert((session.to_owned(), url.to_owned()), (Instant::now(), page));
+    }
+
+    /// Drop every page cached for a session.
+    pub fn clear_session(&self, session: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|(s, _), _| s != session);
+        }
+    }
+
+    /// Number of cached pages.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
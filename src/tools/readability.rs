@@ -0,0 +1,499 @@
+//! Readability extraction: reduce an HTML page to its main content as markdown.
+//!
+//! A small take on the readability algorithm. The page is tokenized, every
+//! block container (`div`, `section`, `article`, ...) is scored by the text
+//! it holds (half of it credited to the parent as well), link-heavy and
+//! boilerplate containers (nav, footer, sidebar, comments, ads) are
+//! penalised, and the best-scoring container is rendered to markdown.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use url::Url;
+
+/// Tokens of an HTML document: comments, tags, or text runs.
+static TOKEN_RE: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<(/?)([a-zA-Z][a-zA-Z0-9]*)([^>]*)>|[^<]+|<").ok()
+});
+
+/// Document title.
+static TITLE_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").ok());
+
+/// `href` attribute value.
+static HREF_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bhref\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok());
+
+/// `class` and `id` attribute values.
+static CLASS_ID_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(?:class|id)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok());
+
+/// Class/id hints for boilerplate containers.
+static NEGATIVE_RE: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(comment|footer|footnote|nav|navbar|menu|sidebar|widget|share|social|related|promo|advert|ads?|banner|cookie|popup|modal|subscribe|newsletter|breadcrumbs?)\b",
+    )
+    .ok()
+});
+
+/// Class/id hints for main-content containers.
+static POSITIVE_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?i)(article|content|post|entry|main|story|text|body)").ok());
+
+/// Elements whose content is never readable text.
+const SKIP_CONTENT_TAGS: &[&str] = &[
+    "script", "style", "noscript", "svg", "template", "iframe", "title", "canvas", "object",
+];
+
+/// Elements dropped from the rendered output.
+const BOILERPLATE_TAGS: &[&str] = &[
+    "nav", "aside", "footer", "header", "form", "button", "select", "dialog",
+];
+
+/// Elements that never have a closing tag.
+const VOID_TAGS: &[&str] = &[
+    "br", "hr", "img", "input", "meta", "link", "area", "base", "col", "embed", "source", "track",
+    "wbr",
+];
+
+/// Block containers that can be chosen as the main content.
+const CONTAINER_TAGS: &[&str] = &["div", "section", "article", "main", "body", "td"];
+
+/// Minimum score for a container to beat the whole-body fallback.
+const MIN_CANDIDATE_SCORE: usize = 140;
+
+/// Approximate characters per token for budget truncation.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Readable content extracted from a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readable {
+    /// Page title, from `<title>` or the first heading.
+    pub title: Option<String>,
+    /// Main content as markdown.
+    pub markdown: String,
+}
+
+/// Extract the main content of an HTML page as markdown.
+///
+/// Relative links are resolved against `base_url` when given.
+pub fn extract(html: &str, base_url: Option<&Url>) -> Readable {
+    let tokens = tokenize(html);
+    let (start, end) = best_container(&tokens).unwrap_or((0, tokens.len()));
+    let markdown = render(tokens.get(start..end).unwrap_or_default(), base_url);
+
+    let title = TITLE_RE
+        .as_ref()
+        .and_then(|re| re.captures(html))
+        .map(|c| collapse_whitespace(&decode_entities(&c[1])))
+        .filter(|t| !t.is_empty())
+        .or_else(|| {
+            markdown
+                .lines()
+                .find_map(|l| l.strip_prefix("# "))
+                .map(str::to_owned)
+        });
+
+    Readable { title, markdown }
+}
+
+/// Cut text to roughly `max_tokens` tokens, preferring a paragraph break.
+pub fn truncate_to_tokens(text: &str, max_tokens: usize) -> String {
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text.to_owned();
+    };
+    let head = &text[..cut];
+    // Back up to the last paragraph break if it keeps most of the budget.
+    let end = match head.rfind("\n\n") {
+        Some(pos) if pos > cut.saturating_div(2) => pos,
+        _ => cut,
+    };
+    let remaining = text.len().saturating_sub(end);
+    format!(
+        "{}\n\n...[truncated at ~{max_tokens} tokens; {remaining} bytes omitted]",
+        text[..end].trim_end()
+    )
+}
+
+/// Decode HTML character references (named and numeric).
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(semi) = tail.get(..12).unwrap_or(tail).find(';') {
+            if let Some(c) = entity_char(&tail[1..semi]) {
+                out.push(c);
+                rest = &tail[semi.saturating_add(1)..];
+                continue;
+            }
+        }
+        out.push('&');
+        rest = &tail[1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Character for an entity name without `&` and `;`.
+fn entity_char(entity: &str) -> Option<char> {
+    if let Some(num) = entity.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    let c = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "copy" => '\u{a9}',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// A lexical HTML token.
+#[derive(Debug)]
+enum Token<'a> {
+    Open { name: String, attrs: &'a str },
+    Close { name: String },
+    Text(&'a str),
+}
+
+/// Tokenize HTML, dropping comments and the content of non-text elements.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut skipping: Option<String> = None;
+
+    let Some(token_re) = TOKEN_RE.as_ref() else {
+        return tokens;
+    };
+    for caps in token_re.captures_iter(html) {
+        let whole = caps.get(0).map_or("", |m| m.as_str());
+        if whole.starts_with("<!--") {
+            continue;
+        }
+        let token = match caps.get(2) {
+            Some(name) => {
+                let name = name.as_str().to_ascii_lowercase();
+                let attrs = caps.get(3).map_or("", |m| m.as_str());
+                if caps.get(1).is_some_and(|m| !m.as_str().is_empty()) {
+                    Token::Close { name }
+                } else {
+                    Token::Open { name, attrs }
+                }
+            }
+            None => Token::Text(whole),
+        };
+
+        if let Some(skip) = &skipping {
+            if matches!(&token, Token::Close { name } if name == skip) {
+                skipping = None;
+            }
+            continue;
+        }
+        if let Token::Open { name, attrs } = &token {
+            if SKIP_CONTENT_TAGS.contains(&name.as_str()) && !attrs.ends_with('/') {
+                skipping = Some(name.clone());
+                continue;
+            }
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Class and id values of a tag.
+fn class_and_id(attrs: &str) -> String {
+    let Some(re) = CLASS_ID_RE.as_ref() else {
+        return String::new();
+    };
+    re.captures_iter(attrs)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether an element is boilerplate that should not be rendered or scored.
+fn is_boilerplate(name: &str, attrs: &str) -> bool {
+    if BOILERPLATE_TAGS.contains(&name) {
+        return true;
+    }
+    let hints = class_and_id(attrs);
+    !hints.is_empty() && matches_hint(&NEGATIVE_RE, &hints) && !matches_hint(&POSITIVE_RE, &hints)
+}
+
+/// Whether a class/id hint regex matches; an unavailable regex never does.
+fn matches_hint(re: &LazyLock<Option<Regex>>, hints: &str) -> bool {
+    re.as_ref().is_some_and(|re| re.is_match(hints))
+}
+
+/// A scored block container.
+struct Candidate {
+    start: usize,
+    end: usize,
+    text: usize,
+    links: usize,
+    weight_pct: usize,
+}
+
+impl Candidate {
+    fn score(&self) -> usize {
+        self.text
+            .saturating_sub(self.links)
+            .saturating_mul(self.weight_pct)
+            .saturating_div(100)
+    }
+}
+
+/// Token range `[start, end)` of the best main-content container.
+fn best_container(tokens: &[Token<'_>]) -> Option<(usize, usize)> {
+    let mut candidates: Vec<Candidate> = Vec::new();
+    // Open elements: (tag name, candidate index for containers).
+    let mut stack: Vec<(String, Option<usize>)> = Vec::new();
+    let mut link_depth: usize = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open { name, attrs } => {
+                if VOID_TAGS.contains(&name.as_str()) || attrs.ends_with('/') {
+                    continue;
+                }
+                if name == "a" {
+                    link_depth = link_depth.saturating_add(1);
+                }
+                let candidate = CONTAINER_TAGS.contains(&name.as_str()).then(|| {
+                    let hints = class_and_id(attrs);
+                    let weight_pct = if is_boilerplate(name, attrs) {
+                        0
+                    } else if matches!(name.as_str(), "article" | "main")
+                        || matches_hint(&POSITIVE_RE, &hints)
+                    {
+                        150
+                    } else {
+                        100
+                    };
+                    candidates.push(Candidate {
+                        start: i,
+                        end: tokens.len(),
+                        text: 0,
+                        links: 0,
+                        weight_pct,
+                    });
+                    candidates.len().saturating_sub(1)
+                });
+                stack.push((name.clone(), candidate));
+            }
+            Token::Close { name } => {
+                let Some(pos) = stack.iter().rposition(|(open, _)| open == name) else {
+                    continue;
+                };
+                for (open, candidate) in stack.drain(pos..) {
+                    if open == "a" {
+                        link_depth = link_depth.saturating_sub(1);
+                    }
+                    if let Some(c) = candidate.and_then(|idx| candidates.get_mut(idx)) {
+                        c.end = i.saturating_add(1);
+                    }
+                }
+            }
+            Token::Text(text) => {
+                let len = text.split_whitespace().map(str::len).sum::<usize>();
+                if len == 0 {
+                    continue;
+                }
+                let mut containers = stack.iter().rev().filter_map(|(_, c)| *c);
+                let shares = [
+                    containers.next().map(|c| (c, len)),
+                    containers.next().map(|c| (c, len.saturating_div(2))),
+                ];
+                for (idx, share) in shares.into_iter().flatten() {
+                    if let Some(c) = candidates.get_mut(idx) {
+                        c.text = c.text.saturating_add(share);
+                        if link_depth > 0 {
+                            c.links = c.links.saturating_add(share);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    candidates
+        .iter()
+        .filter(|c| c.score() >= MIN_CANDIDATE_SCORE)
+        .max_by_key(|c| c.score())
+        .map(|c| (c.start, c.end))
+}
+
+/// Render a token range to markdown.
+fn render(tokens: &[Token<'_>], base_url: Option<&Url>) -> String {
+    let mut out = String::new();
+    // Boilerplate element being skipped and its nesting depth.
+    let mut skip: Option<(String, usize)> = None;
+    let mut links: Vec<Option<String>> = Vec::new();
+    let mut pre_depth: usize = 0;
+
+    for token in tokens {
+        if let Some((skip_name, depth)) = skip.as_mut() {
+            match token {
+                Token::Open { name, .. } if name.as_str() == skip_name.as_str() => {
+                    *depth = depth.saturating_add(1);
+                }
+                Token::Close { name } if name.as_str() == skip_name.as_str() => {
+                    *depth = depth.saturating_sub(1);
+                    if *depth == 0 {
+                        skip = None;
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match token {
+            Token::Open { name, attrs } => {
+                if is_boilerplate(name, attrs) && !VOID_TAGS.contains(&name.as_str()) {
+                    skip = Some((name.clone(), 1));
+                    continue;
+                }
+                match name.as_str() {
+                    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                        let level = name[1..].parse::<usize>().unwrap_or(1);
+                        out.push_str("\n\n");
+                        out.push_str(&"#".repeat(level));
+                        out.push(' ');
+                    }
+                    "p" | "div" | "section" | "article" | "main" | "table" | "tr" | "ul" | "ol"
+                    | "dl" => out.push_str("\n\n"),
+                    "br" => out.push('\n'),
+                    "hr" => out.push_str("\n\n---\n\n"),
+                    "li" => out.push_str("\n- "),
+                    "dt" | "dd" => out.push('\n'),
+                    "td" | "th" => out.push_str(" | "),
+                    "blockquote" => out.push_str("\n\n> "),
+                    "strong" | "b" => out.push_str("**"),
+                    "em" | "i" => out.push('_'),
+                    "pre" => {
+                        pre_depth = pre_depth.saturating_add(1);
+                        out.push_str("\n\n```\n");
+                    }
+                    "code" if pre_depth == 0 => out.push('`'),
+                    "a" => {
+                        let href = link_target(attrs, base_url);
+                        if href.is_some() {
+                            out.push('[');
+                        }
+                        links.push(href);
+                    }
+                    _ => {}
+                }
+            }
+            Token::Close { name } => match name.as_str() {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "div" | "section" | "article"
+                | "main" | "table" | "ul" | "ol" | "dl" | "blockquote" => out.push_str("\n\n"),
+                "tr" => out.push('\n'),
+                "strong" | "b" => out.push_str("**"),
+                "em" | "i" => out.push('_'),
+                "pre" => {
+                    pre_depth = pre_depth.saturating_sub(1);
+                    out.push_str("\n```\n\n");
+                }
+                "code" if pre_depth == 0 => out.push('`'),
+                "a" => {
+                    if let Some(Some(href)) = links.pop() {
+                        out.push_str(&format!("]({href})"));
+                    }
+                }
+                _ => {}
+            },
+            Token::Text(text) => {
+                let decoded = decode_entities(text);
+                if pre_depth > 0 {
+                    out.push_str(&decoded);
+                } else {
+                    let collapsed = collapse_whitespace(&decoded);
+                    if collapsed.is_empty() {
+                        continue;
+                    }
+                    if decoded.starts_with(char::is_whitespace)
+                        && !out.ends_with(char::is_whitespace)
+                    {
+                        out.push(' ');
+                    }
+                    out.push_str(&collapsed);
+                    if decoded.ends_with(char::is_whitespace) {
+                        out.push(' ');
+                    }
+                }
+            }
+        }
+    }
+
+    tidy(&out)
+}
+
+/// Resolve a link target; fragment-only and script links yield `None`.
+fn link_target(attrs: &str, base_url: Option<&Url>) -> Option<String> {
+    let caps = HREF_RE.as_ref()?.captures(attrs)?;
+    let raw = decode_entities(caps.get(1).or_else(|| caps.get(2))?.as_str().trim());
+    if raw.is_empty() || raw.starts_with('#') || raw.to_ascii_lowercase().starts_with("javascript:")
+    {
+        return None;
+    }
+    match base_url {
+        Some(base) => base.join(&raw).ok().map(String::from),
+        None => Some(raw),
+    }
+}
+
+/// Collapse runs of whitespace to single spaces and trim.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Trim lines outside code fences and collapse blank-line runs.
+fn tidy(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut in_fence = false;
+    let mut blank_run: usize = 0;
+
+    for line in markdown.lines() {
+        let line = if in_fence {
+            line.trim_end()
+        } else {
+            line.trim()
+        };
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if line.is_empty() || (!in_fence && matches!(line, "-" | "|" | ">")) {
+            blank_run = blank_run.saturating_add(1);
+            if blank_run > 1 && !in_fence {
+                continue;
+            }
+            out.push('\n');
+            continue;
+        }
+        blank_run = 0;
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    out.trim().to_owned()
+}
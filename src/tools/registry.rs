@@ -13,11 +13,25 @@ use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
+use crate::config::ToolHealthConfig;
+use crate::executor::ExecRestrictions;
 use crate::providers::ToolDefinition;
 
+use super::self_test::{SelfTestReport, ToolExample};
+use super::{compile_input_schema, InputValidator};
+
 /// Upper bound for dynamic tool timeout loaded from schema files.
 const MAX_DYNAMIC_TIMEOUT_SECS: u64 = 3600;
 
+/// Most invocation outcomes kept in `_meta.recent_outcomes`.
+const MAX_RECENT_OUTCOMES: usize = 100;
+
+/// Most error samples kept in `_meta.recent_errors`.
+const MAX_RECENT_ERRORS: usize = 3;
+
+/// Longest error sample kept, in characters.
+const MAX_ERROR_CHARS: usize = 500;
+
 // ---------------------------------------------------------------------------
 // DynamicToolSchema
 // ---------------------------------------------------------------------------
@@ -39,6 +53,29 @@ pub struct ToolMeta {
     pub last_error: Option<String>,
     /// Schema version (incremented on tool updates).
     pub version: u32,
+    /// Result of the self-test run before this version was registered.
+    #[serde(default)]
+    pub self_test: Option<SelfTestReport>,
+    /// Outcomes of the latest invocations, oldest first (`true` = success).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_outcomes: Vec<bool>,
+    /// Latest error messages, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_errors: Vec<String>,
+    /// Set while the tool is disabled for failing too often.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<ToolDisabled>,
+}
+
+/// Why and when a dynamic tool was disabled automatically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDisabled {
+    /// ISO 8601 timestamp when the tool was disabled.
+    pub at: String,
+    /// Success rate over the window that triggered it (0.0–1.0).
+    pub success_rate: f64,
+    /// Number of invocations the rate was computed over.
+    pub window: u32,
 }
 
 impl ToolMeta {
@@ -52,6 +89,118 @@ impl ToolMeta {
             avg_duration_ms: 0,
             last_error: None,
             version: 1,
+            self_test: None,
+            recent_outcomes: Vec::new(),
+            recent_errors: Vec::new(),
+            disabled: None,
+        }
+    }
+
+    /// Success rate over the last `window` invocations.
+    ///
+    /// `None` until at least `window` outcomes have been recorded.
+    pub fn rolling_success_rate(&self, window: u32) -> Option<f64> {
+        let window = usize::try_from(window).ok()?.min(MAX_RECENT_OUTCOMES);
+        if window == 0 || self.recent_outcomes.len() < window {
+            return None;
+        }
+        let start = self.recent_outcomes.len().saturating_sub(window);
+        let successes = self.recent_outcomes[start..]
+            .iter()
+            .filter(|ok| **ok)
+            .count();
+        Some(
+            f64::from(u32::try_from(successes).unwrap_or(u32::MAX))
+                / f64::from(u32::try_from(window).unwrap_or(u32::MAX)),
+        )
+    }
+
+    /// Forget the health history, e.g. after the tool was re-enabled or replaced.
+    pub fn reset_health(&mut self) {
+        self.recent_outcomes.clear();
+        self.recent_errors.clear();
+        self.disabled = None;
+    }
+}
+
+/// Maximum number of domains a tool manifest may request.
+const MAX_MANIFEST_DOMAINS: usize = 20;
+
+/// Resources and permissions a dynamic tool declares.
+///
+/// A tool without a manifest gets the most restrictive one: no network and
+/// a read-only workspace.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolManifest {
+    /// Domains the tool needs to reach. Empty means no network access.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub needs_network: Vec<String>,
+    /// Upper bound on a single run in seconds, lowering `timeout_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_secs: Option<u64>,
+    /// Whether the tool writes to `/workspace`.
+    #[serde(default)]
+    pub needs_workspace_write: bool,
+}
+
+impl ToolManifest {
+    /// Check the manifest and normalise its domains (lowercase, sorted, deduplicated).
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first invalid field.
+    pub fn validate(&mut self) -> Result<(), String> {
+        if self.needs_network.len() > MAX_MANIFEST_DOMAINS {
+            return Err(format!(
+                "needs_network lists more than {MAX_MANIFEST_DOMAINS} domains"
+            ));
+        }
+        for domain in &mut self.needs_network {
+            *domain = domain.trim().to_ascii_lowercase();
+            let valid = !domain.is_empty()
+                && !domain.starts_with(['.', '-'])
+                && !domain.ends_with(['.', '-'])
+                && domain
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+            if !valid {
+                return Err(format!(
+                    "invalid domain '{domain}' in needs_network; use bare host names like api.example.com"
+                ));
+            }
+        }
+        self.needs_network.sort();
+        self.needs_network.dedup();
+        if let Some(secs) = self.max_runtime_secs {
+            if secs == 0 || secs > MAX_DYNAMIC_TIMEOUT_SECS {
+                return Err(format!(
+                    "max_runtime_secs {secs} is out of range; expected 1..={MAX_DYNAMIC_TIMEOUT_SECS}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Declared domains that are not in `granted`.
+    pub fn ungranted_domains<'a>(&'a self, granted: &[String]) -> Vec<&'a str> {
+        self.needs_network
+            .iter()
+            .filter(|d| !granted.contains(d))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Executor restrictions for a run of `tool`, given whether network was granted.
+    pub fn restrictions(&self, tool: &str, network_granted: bool) -> ExecRestrictions {
+        ExecRestrictions {
+            tool: tool.to_owned(),
+            domains: if network_granted {
+                self.needs_network.clone()
+            } else {
+                Vec::new()
+            },
+            workspace_write: self.needs_workspace_write,
         }
     }
 }
@@ -68,11 +217,34 @@ pub struct DynamicToolSchema {
     /// Maximum execution timeout in seconds.
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
+    /// Example invocations the tool was self-tested against.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<ToolExample>,
+    /// JSON Schema the tool's stdout must match, if declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
+    /// Resources and permissions the executor enforces at dispatch.
+    #[serde(default)]
+    pub manifest: ToolManifest,
     /// Health metadata, updated after each invocation.
     #[serde(default, rename = "_meta")]
     pub meta: Option<ToolMeta>,
 }
 
+impl DynamicToolSchema {
+    /// Whether the tool is disabled for failing too often.
+    pub fn is_disabled(&self) -> bool {
+        self.meta.as_ref().is_some_and(|m| m.disabled.is_some())
+    }
+
+    /// Runtime limit in seconds: `timeout_secs`, lowered by the manifest's `max_runtime_secs`.
+    pub fn max_runtime_secs(&self) -> u64 {
+        self.manifest
+            .max_runtime_secs
+            .map_or(self.timeout_secs, |max| max.min(self.timeout_secs))
+    }
+}
+
 /// Default timeout for dynamic tools: 120 seconds.
 fn default_timeout() -> u64 {
     120
@@ -92,8 +264,12 @@ fn default_timeout() -> u64 {
 pub struct DynamicToolRegistry {
     /// Map from tool name to its schema.
     tools: RwLock<HashMap<String, DynamicToolSchema>>,
+    /// Compiled parameter validators, rebuilt whenever a tool is (re)loaded.
+    validators: RwLock<HashMap<String, InputValidator>>,
     /// Last-used timestamp per tool name (for ranked selection).
     last_used: RwLock<HashMap<String, Instant>>,
+    /// When failing tools are disabled.
+    health: RwLock<ToolHealthConfig>,
     /// Directory containing tool scripts and schema files.
     scripts_dir: PathBuf,
     /// File watcher handle (kept alive to maintain notifications).
@@ -142,7 +318,9 @@ impl DynamicToolRegistry {
 
         let registry = Arc::new(Self {
             tools,
+            validators: RwLock::new(HashMap::new()),
             last_used: RwLock::new(HashMap::new()),
+            health: RwLock::new(ToolHealthConfig::default()),
             scripts_dir: scripts_dir.clone(),
             _watcher: Some(watcher),
         });
@@ -174,9 +352,7 @@ impl DynamicToolRegistry {
                     } else {
                         // File was deleted — remove from registry.
                         debug!(tool = file_stem, "removing deleted dynamic tool");
-                        if let Ok(mut map) = registry_for_thread.tools.write() {
-                            map.remove(file_stem);
-                        }
+                        registry_for_thread.remove(file_stem);
                     }
                 }
             }
@@ -198,7 +374,9 @@ impl DynamicToolRegistry {
 
         let registry = Arc::new(Self {
             tools,
+            validators: RwLock::new(HashMap::new()),
             last_used: RwLock::new(HashMap::new()),
+            health: RwLock::new(ToolHealthConfig::default()),
             scripts_dir,
             _watcher: None,
         });
@@ -219,6 +397,21 @@ impl DynamicToolRegistry {
         }
     }
 
+    /// Compiled parameter validator for a tool, if its schema compiles.
+    pub fn validator(&self, name: &str) -> Option<InputValidator> {
+        self.validators
+            .read()
+            .ok()
+            .and_then(|map| map.get(name).cloned())
+    }
+
+    /// Replace the policy deciding when failing tools are disabled.
+    pub fn set_health_policy(&self, policy: ToolHealthConfig) {
+        if let Ok(mut health) = self.health.write() {
+            *health = policy;
+        }
+    }
+
     /// Record that a dynamic tool was used (for recency-based ranking).
     pub fn record_usage(&self, name: &str) {
         if let Ok(mut last) = self.last_used.write() {
@@ -236,7 +429,10 @@ impl DynamicToolRegistry {
             }
         };
 
-        map.values().map(schema_to_definition).collect()
+        map.values()
+            .filter(|schema| !schema.is_disabled())
+            .map(schema_to_definition)
+            .collect()
     }
 
     /// Return dynamic tool definitions ranked by relevance and recency.
@@ -267,6 +463,7 @@ impl DynamicToolRegistry {
 
         let mut scored: Vec<(f64, ToolDefinition)> = map
             .values()
+            .filter(|schema| !schema.is_disabled())
             .map(|schema| {
                 let def = schema_to_definition(schema);
                 let score = score_tool_for_ranking(schema, &last, &query_tokens);
@@ -292,14 +489,19 @@ impl DynamicToolRegistry {
 
         if !path.exists() {
             // Remove if it was previously registered.
-            if let Ok(mut map) = self.tools.write() {
-                map.remove(name);
-            }
+            self.remove(name);
             return Ok(());
         }
 
         let schema = load_tool_schema(&path)?;
+        let validator = compile_input_schema(&schema.name, &schema.parameters);
 
+        if let Ok(mut validators) = self.validators.write() {
+            match validator {
+                Some(v) => validators.insert(schema.name.clone(), v),
+                None => validators.remove(&schema.name),
+            };
+        }
         if let Ok(mut map) = self.tools.write() {
             map.insert(schema.name.clone(), schema);
         } else {
@@ -346,6 +548,16 @@ impl DynamicToolRegistry {
             }
         }
 
+        let compiled = loaded
+            .values()
+            .filter_map(|schema| {
+                compile_input_schema(&schema.name, &schema.parameters)
+                    .map(|v| (schema.name.clone(), v))
+            })
+            .collect();
+        if let Ok(mut validators) = self.validators.write() {
+            *validators = compiled;
+        }
         if let Ok(mut map) = self.tools.write() {
             *map = loaded;
         } else {
@@ -355,31 +567,46 @@ impl DynamicToolRegistry {
         Ok(())
     }
 
+    /// Drop a tool whose file was deleted.
+    fn remove(&self, name: &str) {
+        if let Ok(mut validators) = self.validators.write() {
+            validators.remove(name);
+        }
+        if let Ok(mut map) = self.tools.write() {
+            map.remove(name);
+        }
+    }
+
     /// Record an execution result, updating `_meta` and persisting to disk.
     ///
     /// Updates invocation count, success rate (running average), average
-    /// duration, last-used timestamp, and last error. Writes the updated
-    /// schema back to disk on a blocking thread.
+    /// duration, last-used timestamp, last error and the rolling window of
+    /// outcomes. Writes the updated schema back to disk on a blocking thread.
+    ///
+    /// When this run drops the rolling success rate below the health
+    /// policy, the tool is disabled and its updated `_meta` is returned so
+    /// the caller can notify the owner.
     pub fn record_execution(
         &self,
         name: &str,
         success: bool,
         duration_ms: u64,
         error: Option<&str>,
-    ) {
-        let updated_schema = {
+    ) -> Option<ToolMeta> {
+        let policy = match self.health.read() {
+            Ok(health) => health.clone(),
+            Err(_) => ToolHealthConfig::default(),
+        };
+        let (updated_schema, newly_disabled) = {
             let mut map = match self.tools.write() {
                 Ok(m) => m,
                 Err(e) => {
                     warn!(error = %e, "registry lock poisoned in record_execution");
-                    return;
+                    return None;
                 }
             };
 
-            let schema = match map.get_mut(name) {
-                Some(s) => s,
-                None => return,
-            };
+            let schema = map.get_mut(name)?;
 
             let meta = schema.meta.get_or_insert_with(ToolMeta::new_initial);
             meta.invocations = meta.invocations.saturating_add(1);
@@ -403,11 +630,80 @@ impl DynamicToolRegistry {
             }
 
             if !success {
-                meta.last_error = error.map(|e| e.chars().take(500).collect());
+                meta.last_error = error.map(|e| e.chars().take(MAX_ERROR_CHARS).collect());
+                if let Some(sample) = &meta.last_error {
+                    meta.recent_errors.push(sample.clone());
+                    let excess = meta.recent_errors.len().saturating_sub(MAX_RECENT_ERRORS);
+                    meta.recent_errors.drain(..excess);
+                }
             }
+            meta.recent_outcomes.push(success);
+            let excess = meta
+                .recent_outcomes
+                .len()
+                .saturating_sub(MAX_RECENT_OUTCOMES);
+            meta.recent_outcomes.drain(..excess);
+
+            let newly_disabled = policy.auto_disable
+                && meta.disabled.is_none()
+                && match meta.rolling_success_rate(policy.window) {
+                    Some(rate) if rate < policy.min_success_rate => {
+                        meta.disabled = Some(ToolDisabled {
+                            at: chrono::Utc::now().to_rfc3339(),
+                            success_rate: rate,
+                            window: policy.window,
+                        });
+                        warn!(
+                            tool = name,
+                            success_rate = rate,
+                            window = policy.window,
+                            "dynamic tool disabled after repeated failures"
+                        );
+                        true
+                    }
+                    _ => false,
+                };
+
+            (schema.clone(), newly_disabled)
+        };
 
+        let disabled_meta = if newly_disabled {
+            updated_schema.meta.clone()
+        } else {
+            None
+        };
+        self.persist(updated_schema);
+        disabled_meta
+    }
+
+    /// Re-enable a disabled tool and clear its health history.
+    ///
+    /// Returns `false` if no tool with that name is registered.
+    pub fn enable(&self, name: &str) -> bool {
+        let updated_schema = {
+            let mut map = match self.tools.write() {
+                Ok(m) => m,
+                Err(e) => {
+                    warn!(error = %e, "registry lock poisoned in enable");
+                    return false;
+                }
+            };
+            let Some(schema) = map.get_mut(name) else {
+                return false;
+            };
+            if let Some(meta) = schema.meta.as_mut() {
+                meta.reset_health();
+            }
             schema.clone()
         };
+        info!(tool = name, "dynamic tool re-enabled");
+        self.persist(updated_schema);
+        true
+    }
+
+    /// Write a schema back to its JSON file on a blocking thread.
+    fn persist(&self, updated_schema: DynamicToolSchema) {
+        let name = updated_schema.name.clone();
 
         // Persist to disk on a bounded blocking thread.
         let path = self.scripts_dir.join(format!("{name}.json"));
@@ -494,7 +790,11 @@ fn score_tool_for_ranking(
 /// Load and validate a tool schema from a JSON file.
 fn load_tool_schema(path: &Path) -> anyhow::Result<DynamicToolSchema> {
     let content = std::fs::read_to_string(path)?;
-    let schema: DynamicToolSchema = serde_json::from_str(&content)?;
+    let mut schema: DynamicToolSchema = serde_json::from_str(&content)?;
+    schema
+        .manifest
+        .validate()
+        .map_err(|e| anyhow::anyhow!("invalid manifest in {}: {e}", path.display()))?;
     if schema.timeout_secs == 0 || schema.timeout_secs > MAX_DYNAMIC_TIMEOUT_SECS {
         anyhow::bail!(
             "invalid timeout_secs {} in {}; expected 1..={MAX_DYNAMIC_TIMEOUT_SECS}",
@@ -0,0 +1,775 @@
+//! RSS/Atom feed subscriptions: `rss_subscribe` and `rss_list`.
+//!
+//! Subscribing fetches the feed once (through the SSRF filter) to check it
+//! parses and records its current items as already seen, so only items
+//! published afterwards are surfaced. The heartbeat polls subscriptions
+//! ([`crate::heartbeat::feeds`]); entries are deduplicated per feed by GUID
+//! in SQLite. Items of high-priority feeds are pushed to Telegram as they
+//! arrive, the rest wait for the daily feed digest.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use anyhow::Context;
+use regex::Regex;
+use serde_json::json;
+use sqlx::SqlitePool;
+use tracing::{debug, info};
+use url::Url;
+
+use super::core::{get_following_redirects, read_bytes_capped};
+use super::readability::decode_entities;
+use super::ToolError;
+use crate::agent::policy::RateLimiter;
+use crate::memory::writer::WriteOp;
+use crate::memory::MemoryEngine;
+use crate::providers::ToolDefinition;
+
+/// Largest feed document accepted.
+pub const MAX_FEED_BYTES: usize = 2 * 1024 * 1024;
+
+/// Longest item summary kept, in characters.
+const MAX_SUMMARY_CHARS: usize = 280;
+
+/// Items shown by `rss_list` when no limit is given.
+const DEFAULT_LIST_ITEMS: i64 = 10;
+
+/// Upper bound on items shown by `rss_list`.
+const MAX_LIST_ITEMS: i64 = 50;
+
+/// `<item>` (RSS) and `<entry>` (Atom) blocks.
+static ITEM_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<(?:item|entry)\b[^>]*>(.*?)</(?:item|entry)>").ok());
+
+/// Document root of a feed.
+static ROOT_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?i)<(?:rss|feed|rdf:RDF)\b").ok());
+
+/// Title element.
+static TITLE_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<title\b[^>]*>(.*?)</title>").ok());
+
+/// RSS `<guid>` or Atom `<id>`.
+static GUID_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<(?:guid|id)\b[^>]*>(.*?)</(?:guid|id)>").ok());
+
+/// RSS `<link>` with text content.
+static LINK_TEXT_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<link\b[^>]*>([^<]+)</link>").ok());
+
+/// Atom `<link>` tag attributes.
+static LINK_TAG_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?is)<link\b([^>]*)>").ok());
+
+/// `href` attribute.
+static HREF_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bhref\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok());
+
+/// `rel` attribute.
+static REL_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r#"(?i)\brel\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok());
+
+/// Publication date, in order of preference.
+static DATE_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    ["pubDate", "published", "updated", "dc:date"]
+        .iter()
+        .filter_map(|tag| Regex::new(&format!(r"(?is)<{tag}\b[^>]*>(.*?)</{tag}>")).ok())
+        .collect()
+});
+
+/// Summary, in order of preference.
+static SUMMARY_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    ["description", "summary", "content:encoded", "content"]
+        .iter()
+        .filter_map(|tag| Regex::new(&format!(r"(?is)<{tag}\b[^>]*>(.*?)</{tag}>")).ok())
+        .collect()
+});
+
+/// `<![CDATA[...]]>` section.
+static CDATA_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"(?s)<!\[CDATA\[(.*?)\]\]>").ok());
+
+/// Any markup tag.
+static TAG_RE: LazyLock<Option<Regex>> = LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").ok());
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// How a feed's new items reach the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedPriority {
+    /// Collected into the daily feed digest.
+    #[default]
+    Normal,
+    /// Sent to Telegram as soon as the poller sees them.
+    High,
+}
+
+impl FeedPriority {
+    /// Database representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    /// Parse the database or tool-input representation.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "normal" => Some(Self::Normal),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+/// A subscribed feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// Database ID.
+    pub id: i64,
+    /// Feed URL.
+    pub url: String,
+    /// Feed title, once known.
+    pub title: Option<String>,
+    /// Delivery priority.
+    pub priority: FeedPriority,
+    /// Last poll time (SQLite datetime, UTC).
+    pub last_polled_at: Option<String>,
+    /// Error from the last poll, if it failed.
+    pub last_error: Option<String>,
+}
+
+impl Feed {
+    /// Title, or the URL when the feed has none.
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.url)
+    }
+}
+
+/// An item parsed from a feed document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedEntry {
+    /// Stable identifier: GUID/ID, else link, else title and date.
+    pub guid: String,
+    /// Item title.
+    pub title: String,
+    /// Item link.
+    pub link: Option<String>,
+    /// Plain-text summary, shortened.
+    pub summary: Option<String>,
+    /// Publication date (RFC 3339 when parseable, else as given).
+    pub published: Option<String>,
+}
+
+/// A parsed feed document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedFeed {
+    /// Channel/feed title.
+    pub title: Option<String>,
+    /// Items in document order.
+    pub entries: Vec<ParsedEntry>,
+}
+
+/// A stored feed item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// Database ID.
+    pub id: i64,
+    /// Feed the item belongs to.
+    pub feed_id: i64,
+    /// Feed title (or URL).
+    pub feed_title: String,
+    /// Item title.
+    pub title: String,
+    /// Item link.
+    pub link: Option<String>,
+    /// Plain-text summary.
+    pub summary: Option<String>,
+    /// Publication date.
+    pub published: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parse an RSS 2.0, RSS 1.0 (RDF), or Atom document.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if the document is not a feed, and
+/// [`ToolError::ExecutionFailed`] if the feed patterns failed to compile.
+pub fn parse_feed(xml: &str) -> Result<ParsedFeed, ToolError> {
+    let (Some(root_re), Some(item_re)) = (ROOT_RE.as_ref(), ITEM_RE.as_ref()) else {
+        return Err(ToolError::ExecutionFailed(
+            "feed parser unavailable".to_owned(),
+        ));
+    };
+    if !root_re.is_match(xml) {
+        return Err(ToolError::InvalidInput(
+            "not an RSS or Atom feed".to_owned(),
+        ));
+    }
+
+    let header_end = item_re.find(xml).map_or(xml.len(), |m| m.start());
+    let title = xml
+        .get(..header_end)
+        .and_then(|header| capture_text(TITLE_RE.as_ref(), header));
+
+    let entries = item_re
+        .captures_iter(xml)
+        .filter_map(|c| c.get(1))
+        .filter_map(|block| parse_entry(block.as_str()))
+        .collect();
+
+    Ok(ParsedFeed { title, entries })
+}
+
+/// Parse one `<item>`/`<entry>` body. Items without a title or link are skipped.
+fn parse_entry(block: &str) -> Option<ParsedEntry> {
+    let link = entry_link(block);
+    let title = capture_text(TITLE_RE.as_ref(), block).or_else(|| link.clone())?;
+    let published = DATE_RES
+        .iter()
+        .find_map(|re| capture_text(Some(re), block))
+        .map(|d| normalize_date(&d));
+    let summary = SUMMARY_RES
+        .iter()
+        .find_map(|re| capture_text(Some(re), block))
+        .map(|s| shorten(&s, MAX_SUMMARY_CHARS));
+    let guid = capture_text(GUID_RE.as_ref(), block)
+        .or_else(|| link.clone())
+        .unwrap_or_else(|| format!("{title}|{}", published.as_deref().unwrap_or("")));
+
+    Some(ParsedEntry {
+        guid,
+        title,
+        link,
+        summary,
+        published,
+    })
+}
+
+/// Item link: RSS text content, else the Atom `alternate` (or rel-less) link.
+fn entry_link(block: &str) -> Option<String> {
+    if let Some(text) = LINK_TEXT_RE
+        .as_ref()
+        .and_then(|re| re.captures(block))
+        .and_then(|c| c.get(1))
+        .map(|m| decode_entities(m.as_str().trim()))
+        .filter(|t| !t.is_empty())
+    {
+        return Some(text);
+    }
+    LINK_TAG_RE
+        .as_ref()?
+        .captures_iter(block)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .find(|attrs| {
+            attr(REL_RE.as_ref(), attrs).is_none_or(|rel| rel.eq_ignore_ascii_case("alternate"))
+        })
+        .and_then(|attrs| attr(HREF_RE.as_ref(), attrs))
+        .map(|href| decode_entities(&href))
+}
+
+/// Value of a quoted attribute.
+fn attr(re: Option<&Regex>, attrs: &str) -> Option<String> {
+    let caps = re?.captures(attrs)?;
+    caps.get(1)
+        .or_else(|| caps.get(2))
+        .map(|m| m.as_str().to_owned())
+}
+
+/// Text of the first match of `re`: CDATA unwrapped, entities decoded,
+/// markup stripped, whitespace collapsed. Empty text yields `None`.
+fn capture_text(re: Option<&Regex>, haystack: &str) -> Option<String> {
+    let raw = re?.captures(haystack)?.get(1)?.as_str();
+    let unwrapped = match CDATA_RE.as_ref() {
+        Some(cdata_re) => cdata_re.replace_all(raw, "$1"),
+        None => Cow::Borrowed(raw),
+    };
+    // Escaped HTML (`&lt;p&gt;`) becomes markup after decoding.
+    let decoded = decode_entities(&unwrapped);
+    let text = match TAG_RE.as_ref() {
+        Some(tag_re) => tag_re.replace_all(&decoded, " "),
+        None => Cow::Borrowed(decoded.as_str()),
+    };
+    let text = decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Normalize RFC 2822 / RFC 3339 dates to RFC 3339 UTC; keep others as given.
+fn normalize_date(raw: &str) -> String {
+    chrono::DateTime::parse_from_rfc2822(raw)
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(raw))
+        .map(|d| {
+            d.with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        })
+        .unwrap_or_else(|_| raw.to_owned())
+}
+
+/// Cut text to `max_chars`, ending with an ellipsis when shortened.
+fn shorten(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}…", text[..cut].trim_end()),
+        None => text.to_owned(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Fetching
+// ---------------------------------------------------------------------------
+
+/// Download and parse a feed through the SSRF filter.
+///
+/// # Errors
+///
+/// Returns [`ToolError::ExecutionFailed`] if the request fails, returns a
+/// non-success status, or exceeds [`MAX_FEED_BYTES`], and
+/// [`ToolError::InvalidInput`] if the URL is invalid or the body is not a feed.
+pub async fn fetch_feed(url: &str) -> Result<ParsedFeed, ToolError> {
+    let parsed =
+        Url::parse(url).map_err(|e| ToolError::InvalidInput(format!("invalid feed URL: {e}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(ToolError::InvalidInput(
+            "feed URL must be http or https".to_owned(),
+        ));
+    }
+
+    let response = get_following_redirects(parsed).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ToolError::ExecutionFailed(format!(
+            "feed request returned {status}"
+        )));
+    }
+    if response
+        .content_length()
+        .and_then(|len| usize::try_from(len).ok())
+        .is_some_and(|len| len > MAX_FEED_BYTES)
+    {
+        return Err(ToolError::ExecutionFailed("feed is too large".to_owned()));
+    }
+    // Content-Length can be missing or wrong; read one byte past the cap
+    // so an oversized body is detected without buffering it.
+    let body = read_bytes_capped(response, MAX_FEED_BYTES.saturating_add(1)).await?;
+    if body.len() > MAX_FEED_BYTES {
+        return Err(ToolError::ExecutionFailed("feed is too large".to_owned()));
+    }
+
+    parse_feed(&String::from_utf8_lossy(&body))
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+/// Row type for feed reads, in [`Feed`] order.
+type FeedRow = (
+    i64,
+    String,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
+/// Row type for entry reads, in [`FeedEntry`] order.
+type EntryRow = (
+    i64,
+    i64,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Columns selected for every entry read, in [`EntryRow`] order.
+const ENTRY_COLUMNS: &str = "e.id, e.feed_id, COALESCE(f.title, f.url), e.title, e.link, \
+                             e.summary, e.published_at";
+
+fn feed_from_row(row: FeedRow) -> Feed {
+    let (id, url, title, priority, last_polled_at, last_error) = row;
+    Feed {
+        id,
+        url,
+        title,
+        priority: FeedPriority::parse(&priority).unwrap_or_default(),
+        last_polled_at,
+        last_error,
+    }
+}
+
+fn entry_from_row(row: EntryRow) -> FeedEntry {
+    let (id, feed_id, feed_title, title, link, summary, published) = row;
+    FeedEntry {
+        id,
+        feed_id,
+        feed_title,
+        title,
+        link,
+        summary,
+        published,
+    }
+}
+
+/// Add a feed, or update the title and priority of an existing one.
+///
+/// Returns the feed ID.
+pub async fn subscribe(
+    memory: &MemoryEngine,
+    url: &str,
+    title: Option<&str>,
+    priority: FeedPriority,
+) -> anyhow::Result<i64> {
+    memory
+        .write_and_wait(|reply| WriteOp::SubscribeFeed {
+            url: url.to_owned(),
+            title: title.map(str::to_owned),
+            priority: priority.as_str().to_owned(),
+            reply,
+        })
+        .await
+        .context("failed to save feed subscription")
+}
+
+/// Remove a feed and its stored items. Returns whether it existed.
+pub async fn unsubscribe(memory: &MemoryEngine, url: &str) -> anyhow::Result<bool> {
+    let removed = memory
+        .write_and_wait(|reply| WriteOp::UnsubscribeFeed {
+            url: url.to_owned(),
+            reply,
+        })
+        .await
+        .context("failed to delete feed")?;
+    Ok(removed > 0)
+}
+
+/// All subscribed feeds, high priority first.
+pub async fn list_feeds(pool: &SqlitePool) -> anyhow::Result<Vec<Feed>> {
+    let rows: Vec<FeedRow> = sqlx::query_as(
+        "SELECT id, url, title, priority, last_polled_at, last_error FROM feeds \
+         ORDER BY priority = 'high' DESC, id",
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to list feeds")?;
+    Ok(rows.into_iter().map(feed_from_row).collect())
+}
+
+/// Feeds not polled within the last `interval_mins` minutes.
+pub async fn feeds_due(pool: &SqlitePool, interval_mins: u32) -> anyhow::Result<Vec<Feed>> {
+    let rows: Vec<FeedRow> = sqlx::query_as(
+        "SELECT id, url, title, priority, last_polled_at, last_error FROM feeds \
+         WHERE last_polled_at IS NULL \
+            OR last_polled_at <= datetime('now', '-' || ?1 || ' minutes') \
+         ORDER BY id",
+    )
+    .bind(i64::from(interval_mins))
+    .fetch_all(pool)
+    .await
+    .context("failed to load due feeds")?;
+    Ok(rows.into_iter().map(feed_from_row).collect())
+}
+
+/// Record the outcome of a poll.
+pub async fn mark_polled(
+    memory: &MemoryEngine,
+    feed_id: i64,
+    title: Option<&str>,
+    error: Option<&str>,
+) -> anyhow::Result<()> {
+    memory
+        .write_and_wait(|reply| WriteOp::MarkFeedPolled {
+            feed_id,
+            title: title.map(str::to_owned),
+            error: error.map(str::to_owned),
+            reply,
+        })
+        .await
+        .context("failed to record feed poll")?;
+    Ok(())
+}
+
+/// Store items not seen before. Returns how many were new.
+///
+/// With `digested` set, new items are stored as already delivered (used
+/// for the baseline taken when subscribing).
+pub async fn record_entries(
+    memory: &MemoryEngine,
+    feed_id: i64,
+    entries: &[ParsedEntry],
+    digested: bool,
+) -> anyhow::Result<usize> {
+    let inserted = memory
+        .write_and_wait(|reply| WriteOp::RecordFeedEntries {
+            feed_id,
+            entries: entries.to_vec(),
+            digested,
+            reply,
+        })
+        .await
+        .context("failed to store feed entries")?;
+    Ok(usize::try_from(inserted).unwrap_or_default())
+}
+
+/// Items not yet delivered, oldest first, for feeds of the given priority.
+pub async fn pending_entries(
+    pool: &SqlitePool,
+    priority: FeedPriority,
+    limit: i64,
+) -> anyhow::Result<Vec<FeedEntry>> {
+    let rows: Vec<EntryRow> = sqlx::query_as(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM feed_entries e JOIN feeds f ON f.id = e.feed_id \
+         WHERE e.digested_at IS NULL AND f.priority = ?1 \
+         ORDER BY e.feed_id, e.id LIMIT ?2"
+    ))
+    .bind(priority.as_str())
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to load pending feed entries")?;
+    Ok(rows.into_iter().map(entry_from_row).collect())
+}
+
+/// Mark items as delivered.
+pub async fn mark_digested(memory: &MemoryEngine, ids: &[i64]) -> anyhow::Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    memory
+        .write_and_wait(|reply| WriteOp::MarkFeedEntriesDigested {
+            ids: ids.to_vec(),
+            reply,
+        })
+        .await
+        .context("failed to mark feed entries delivered")?;
+    Ok(())
+}
+
+/// Most recently seen items, optionally for one feed.
+pub async fn recent_entries(
+    pool: &SqlitePool,
+    feed_id: Option<i64>,
+    limit: i64,
+) -> anyhow::Result<Vec<FeedEntry>> {
+    let rows: Vec<EntryRow> = sqlx::query_as(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM feed_entries e JOIN feeds f ON f.id = e.feed_id \
+         WHERE ?1 IS NULL OR e.feed_id = ?1 \
+         ORDER BY e.id DESC LIMIT ?2"
+    ))
+    .bind(feed_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .context("failed to load recent feed entries")?;
+    Ok(rows.into_iter().map(entry_from_row).collect())
+}
+
+// ---------------------------------------------------------------------------
+// Tools
+// ---------------------------------------------------------------------------
+
+/// Execute `rss_subscribe`: add, update, or remove a feed.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] for a missing URL or unknown
+/// priority, [`ToolError::RateLimited`] when the fetch limit is hit, and
+/// [`ToolError::ExecutionFailed`] if the feed cannot be fetched or stored.
+pub async fn rss_subscribe(
+    memory: &MemoryEngine,
+    limiter: &RateLimiter,
+    input: &serde_json::Value,
+) -> Result<String, ToolError> {
+    let url = input
+        .get("url")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| ToolError::InvalidInput("rss_subscribe requires a 'url'".to_owned()))?;
+
+    if input
+        .get("unsubscribe")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        let removed = unsubscribe(memory, url).await.map_err(storage_error)?;
+        return Ok(if removed {
+            format!("Unsubscribed from {url}")
+        } else {
+            format!("Not subscribed to {url}")
+        });
+    }
+
+    let priority = match input.get("priority").and_then(|v| v.as_str()) {
+        None => FeedPriority::Normal,
+        Some(p) => FeedPriority::parse(p).ok_or_else(|| {
+            ToolError::InvalidInput(format!("unknown priority '{p}' (expected normal or high)"))
+        })?,
+    };
+
+    limiter.check("web_fetch")?;
+    limiter.record();
+    let feed = fetch_feed(url).await?;
+
+    let feed_id = subscribe(memory, url, feed.title.as_deref(), priority)
+        .await
+        .map_err(storage_error)?;
+    // Items already in the feed are the baseline; only later ones are news.
+    let baseline = record_entries(memory, feed_id, &feed.entries, true)
+        .await
+        .map_err(storage_error)?;
+    mark_polled(memory, feed_id, feed.title.as_deref(), None)
+        .await
+        .map_err(storage_error)?;
+
+    info!(
+        url,
+        priority = priority.as_str(),
+        baseline,
+        "feed subscribed"
+    );
+    let delivery = match priority {
+        FeedPriority::High => "sent to you as they arrive",
+        FeedPriority::Normal => "included in the daily feed digest",
+    };
+    Ok(format!(
+        "Subscribed to {} ({url}), priority {}. {} current items recorded; new items will be {delivery}.",
+        feed.title.as_deref().unwrap_or(url),
+        priority.as_str(),
+        feed.entries.len()
+    ))
+}
+
+/// Execute `rss_list`: subscriptions and their most recent items.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if `url` names a feed that is not
+/// subscribed, and [`ToolError::ExecutionFailed`] on storage errors.
+pub async fn rss_list(pool: &SqlitePool, input: &serde_json::Value) -> Result<String, ToolError> {
+    let limit = input
+        .get("limit")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(DEFAULT_LIST_ITEMS)
+        .clamp(1, MAX_LIST_ITEMS);
+    let feeds = list_feeds(pool).await.map_err(storage_error)?;
+    if feeds.is_empty() {
+        return Ok("No feed subscriptions.".to_owned());
+    }
+
+    let selected = match input.get("url").and_then(|v| v.as_str()) {
+        Some(url) => Some(
+            feeds
+                .iter()
+                .find(|f| f.url == url.trim())
+                .ok_or_else(|| ToolError::InvalidInput(format!("not subscribed to {url}")))?
+                .id,
+        ),
+        None => None,
+    };
+    let entries = recent_entries(pool, selected, limit)
+        .await
+        .map_err(storage_error)?;
+
+    debug!(feeds = feeds.len(), entries = entries.len(), "rss_list");
+    Ok(format_feed_list(&feeds, &entries))
+}
+
+/// Format subscriptions and recent items for `rss_list`.
+pub fn format_feed_list(feeds: &[Feed], entries: &[FeedEntry]) -> String {
+    let mut out = format!("Subscribed feeds ({}):\n", feeds.len());
+    for feed in feeds {
+        out.push_str(&format!(
+            "- [{}] {} — {}",
+            feed.priority.as_str(),
+            feed.display_name(),
+            feed.url
+        ));
+        match (&feed.last_error, &feed.last_polled_at) {
+            (Some(error), _) => out.push_str(&format!(" (last poll failed: {error})")),
+            (None, Some(at)) => out.push_str(&format!(" (polled {at} UTC)")),
+            (None, None) => {}
+        }
+        out.push('\n');
+    }
+
+    if !entries.is_empty() {
+        out.push_str("\nRecent items:\n");
+        for entry in entries {
+            out.push_str(&format!("- {}: {}", entry.feed_title, entry.title));
+            if let Some(link) = &entry.link {
+                out.push_str(&format!(" — {link}"));
+            }
+            if let Some(published) = &entry.published {
+                out.push_str(&format!(" ({published})"));
+            }
+            out.push('\n');
+        }
+    }
+    out.trim_end().to_owned()
+}
+
+/// Tool definition for `rss_subscribe`.
+pub fn rss_subscribe_tool_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "rss_subscribe".to_owned(),
+        description: "Subscribe to an RSS/Atom feed. New items go into the daily feed digest, or are sent immediately for high-priority feeds. Set unsubscribe to remove a feed.".to_owned(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "Feed URL (http or https)."
+                },
+                "priority": {
+                    "type": "string",
+                    "enum": ["normal", "high"],
+                    "description": "normal: daily digest (default). high: notify as items arrive."
+                },
+                "unsubscribe": {
+                    "type": "boolean",
+                    "description": "Remove the subscription instead of adding it.",
+                    "default": false
+                }
+            },
+            "required": ["url"]
+        }),
+    }
+}
+
+/// Tool definition for `rss_list`.
+pub fn rss_list_tool_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "rss_list".to_owned(),
+        description: "List RSS/Atom feed subscriptions and their most recent items.".to_owned(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "Only show items from this subscribed feed."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum recent items to show (default 10, max 50).",
+                    "default": 10
+                }
+            }
+        }),
+    }
+}
+
+/// Map a storage error into a tool error.
+fn storage_error(e: anyhow::Error) -> ToolError {
+    ToolError::ExecutionFailed(format!("{e:#}"))
+}
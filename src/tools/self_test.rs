@@ -0,0 +1,245 @@
+//! Self-test harness for dynamic tools.
+//!
+//! `create_tool` requires example inputs. Before a new or updated tool is
+//! registered, its implementation is staged next to the active script and
+//! run in the sandbox against every example. Each run must exit cleanly and
+//! print JSON that matches the declared `output_schema` and the example's
+//! `expect` fields. Only a passing tool replaces the active script; the
+//! report is stored in the tool's `_meta.self_test`.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::executor::docker::shell_escape;
+use crate::executor::{ExecOptions, ExecRestrictions, Executor};
+
+use super::{schema_violations, validate_input, ToolError};
+
+/// Maximum examples accepted per tool.
+pub const MAX_EXAMPLES: usize = 10;
+
+/// Longest output excerpt quoted in a failure message, in characters.
+const MAX_FAILURE_EXCERPT: usize = 300;
+
+/// An example invocation declared with a tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolExample {
+    /// JSON input passed to the tool on stdin.
+    pub input: Value,
+    /// Fields the output must contain (subset match), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<Value>,
+}
+
+/// Why one example failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExampleFailure {
+    /// Zero-based example index.
+    pub index: usize,
+    /// What went wrong.
+    pub reason: String,
+}
+
+/// Outcome of a self-test run, stored in `_meta.self_test`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// ISO 8601 timestamp of the run.
+    pub ran_at: String,
+    /// Number of examples run.
+    pub examples: usize,
+    /// Number of examples that passed.
+    pub passed: usize,
+    /// Total wall-clock time of the run in milliseconds.
+    pub duration_ms: u64,
+    /// Failed examples (empty for a registered tool).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<ExampleFailure>,
+}
+
+impl SelfTestReport {
+    /// Whether every example passed.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty() && self.passed == self.examples
+    }
+
+    /// Human-readable summary of the failures, for the agent.
+    pub fn failure_summary(&self) -> String {
+        let mut out = format!(
+            "{} of {} example(s) failed",
+            self.examples.saturating_sub(self.passed),
+            self.examples
+        );
+        for failure in &self.failures {
+            out.push_str(&format!(
+                "\n- example {}: {}",
+                failure.index.saturating_add(1),
+                failure.reason
+            ));
+        }
+        out
+    }
+}
+
+/// Parse and check the `examples` field of a `create_tool` call.
+///
+/// Every example input must satisfy the tool's parameters schema.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if examples are missing, malformed,
+/// too many, or do not match `parameters_schema`.
+pub fn parse_examples(
+    value: Option<&Value>,
+    parameters_schema: &Value,
+) -> Result<Vec<ToolExample>, ToolError> {
+    let value = value.ok_or_else(|| {
+        ToolError::InvalidInput(
+            "missing required field: examples (at least one example input to self-test the tool)"
+                .to_owned(),
+        )
+    })?;
+    let examples: Vec<ToolExample> = serde_json::from_value(value.clone()).map_err(|e| {
+        ToolError::InvalidInput(format!(
+            "examples must be an array of {{\"input\": {{...}}, \"expect\": {{...}}}}: {e}"
+        ))
+    })?;
+
+    if examples.is_empty() {
+        return Err(ToolError::InvalidInput(
+            "examples must contain at least one example".to_owned(),
+        ));
+    }
+    if examples.len() > MAX_EXAMPLES {
+        return Err(ToolError::InvalidInput(format!(
+            "at most {MAX_EXAMPLES} examples are allowed"
+        )));
+    }
+
+    for (index, example) in examples.iter().enumerate() {
+        let name = format!("example {}", index.saturating_add(1));
+        validate_input(&name, parameters_schema, &example.input)?;
+    }
+    Ok(examples)
+}
+
+/// Check one example's stdout against the output schema and expectations.
+///
+/// # Errors
+///
+/// Returns the failure reason.
+pub fn check_output(
+    example: &ToolExample,
+    stdout: &str,
+    output_schema: Option<&Value>,
+) -> Result<(), String> {
+    let output: Value = serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("stdout is not valid JSON ({e}): {}", excerpt(stdout.trim())))?;
+
+    if let Some(schema) = output_schema {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| format!("output_schema does not compile: {e}"))?;
+        let errors = schema_violations(&validator, &output);
+        if !errors.is_empty() {
+            return Err(format!(
+                "output does not match output_schema: {}",
+                errors.join("; ")
+            ));
+        }
+    }
+
+    if let Some(expected) = &example.expect {
+        let mut mismatches = Vec::new();
+        match_expected(&output, expected, "$", &mut mismatches);
+        if !mismatches.is_empty() {
+            return Err(format!("unexpected output: {}", mismatches.join("; ")));
+        }
+    }
+    Ok(())
+}
+
+/// Subset match: objects in `expected` need only their listed keys to match.
+fn match_expected(actual: &Value, expected: &Value, path: &str, mismatches: &mut Vec<String>) {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            for (key, expected_child) in expected {
+                let child_path = format!("{path}.{key}");
+                match actual.get(key) {
+                    Some(actual_child) => {
+                        match_expected(actual_child, expected_child, &child_path, mismatches);
+                    }
+                    None => mismatches.push(format!("{child_path} is missing")),
+                }
+            }
+        }
+        _ if actual == expected => {}
+        _ => mismatches.push(format!(
+            "{path} expected {}, got {}",
+            excerpt(&expected.to_string()),
+            excerpt(&actual.to_string())
+        )),
+    }
+}
+
+/// Run every example against the staged script in the sandbox.
+///
+/// Each example gets the tool's own timeout and manifest restrictions.
+/// Examples run one after another so a failing tool cannot fork-bomb the
+/// container.
+pub async fn run_self_test(
+    executor: &dyn Executor,
+    script_path: &str,
+    examples: &[ToolExample],
+    output_schema: Option<&Value>,
+    timeout_secs: u64,
+    restrictions: Option<ExecRestrictions>,
+) -> SelfTestReport {
+    let start = Instant::now();
+    let mut failures = Vec::new();
+
+    for (index, example) in examples.iter().enumerate() {
+        let escaped_input = shell_escape(&example.input.to_string());
+        let command = format!("echo {escaped_input} | python3 {script_path}");
+        let opts = ExecOptions {
+            timeout: Duration::from_secs(timeout_secs),
+            working_dir: None,
+            restrictions: restrictions.clone(),
+        };
+
+        let outcome = match executor.execute(&command, opts).await {
+            Ok(result) if result.timed_out => Err(format!("timed out after {timeout_secs}s")),
+            Ok(result) if !result.success() => Err(format!(
+                "exited with {}: {}",
+                result
+                    .exit_code
+                    .map_or_else(|| "no exit code".to_owned(), |c| format!("code {c}")),
+                excerpt(result.output().trim())
+            )),
+            Ok(result) => check_output(example, &result.stdout, output_schema),
+            Err(e) => Err(format!("failed to run: {e}")),
+        };
+
+        if let Err(reason) = outcome {
+            debug!(index, reason = %reason, "tool self-test example failed");
+            failures.push(ExampleFailure { index, reason });
+        }
+    }
+
+    SelfTestReport {
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        examples: examples.len(),
+        passed: examples.len().saturating_sub(failures.len()),
+        duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+        failures,
+    }
+}
+
+/// Shorten text for inclusion in a failure message.
+fn excerpt(text: &str) -> String {
+    match text.char_indices().nth(MAX_FAILURE_EXCERPT) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_owned(),
+    }
+}
@@ -1,6 +1,7 @@
 //! Unified send_message tool: dispatches to Telegram or WhatsApp.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use sqlx::SqlitePool;
@@ -8,7 +9,8 @@ use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use crate::agent::TelegramOutbound;
-use crate::messaging::outbound_composer::OutboundComposer;
+use crate::messaging::outbound_composer::{ComposedMessage, OutboundComposer};
+use crate::messaging::quiet_hours::{self, DeliveryPlan};
 use crate::whatsapp::client::WhatsAppClient;
 
 use super::ToolError;
@@ -17,7 +19,11 @@ use super::ToolError;
 ///
 /// For Telegram: sends directly (same as old send_telegram).
 /// For WhatsApp: requires brief_id, routes through outbound composer with
-/// human-like delay, typing indicators, and read receipts.
+/// human-like delay, typing indicators, and read receipts. Messages composed
+/// during the contact's quiet hours are queued unless `urgent` is set. With
+/// `template`, the message is filled from a stored template instead of being
+/// composed by the LLM. `file` attaches a workspace file on either channel;
+/// on WhatsApp, `voice` sends an audio file as a voice note.
 ///
 /// # Errors
 ///
@@ -40,7 +46,16 @@ pub async fn send_message(
 
     match channel {
         "telegram" => send_telegram_direct(tx, user_id, input, workspace_dir).await,
-        "whatsapp" => send_whatsapp(input, whatsapp_client, outbound_composer, memory_pool).await,
+        "whatsapp" => {
+            send_whatsapp(
+                input,
+                workspace_dir,
+                whatsapp_client,
+                outbound_composer,
+                memory_pool,
+            )
+            .await
+        }
         other => Err(ToolError::InvalidInput(format!("unknown channel: {other}"))),
     }
 }
@@ -57,38 +72,19 @@ async fn send_telegram_direct(
         .and_then(|v| v.as_str())
         .ok_or_else(|| ToolError::InvalidInput("missing required field: text".to_owned()))?;
 
-    let file = input.get("file").and_then(|v| v.as_str());
-
-    // Map container paths (/workspace/...) to host paths.
-    let resolved_file = if let Some(f) = file {
-        let relative = f.strip_prefix("/workspace/").ok_or_else(|| {
-            ToolError::InvalidInput("file path must start with /workspace/".to_owned())
-        })?;
-        Some(workspace_dir.join(relative).to_string_lossy().into_owned())
-    } else {
-        None
-    };
-
-    // Validate the file exists and is within the workspace directory.
-    if let Some(ref path) = resolved_file {
-        let canonical = Path::new(path)
-            .canonicalize()
-            .map_err(|e| ToolError::InvalidInput(format!("file not accessible: {e}")))?;
-        let canonical_workspace = workspace_dir
-            .canonicalize()
-            .map_err(|e| ToolError::ExecutionFailed(format!("workspace not accessible: {e}")))?;
-        if !canonical.starts_with(&canonical_workspace) {
-            return Err(ToolError::InvalidInput(
-                "file path must be within the workspace directory".to_owned(),
-            ));
-        }
-    }
+    let resolved_file = input
+        .get("file")
+        .and_then(|v| v.as_str())
+        .map(|f| resolve_workspace_file(f, workspace_dir))
+        .transpose()?
+        .map(|path| path.to_string_lossy().into_owned());
 
     let outbound = TelegramOutbound {
         user_id,
         text: Some(text.to_owned()),
         file_path: resolved_file,
         approval_keyboard: None,
+        keyboard: None,
     };
 
     tx.try_send(outbound).map_err(|e| {
@@ -99,23 +95,53 @@ async fn send_telegram_direct(
     Ok("Message sent to Telegram".to_owned())
 }
 
+/// Map a container path (`/workspace/...`) to the host path and check that
+/// it exists inside the workspace directory.
+pub(crate) fn resolve_workspace_file(
+    file: &str,
+    workspace_dir: &Path,
+) -> Result<PathBuf, ToolError> {
+    let relative = file.strip_prefix("/workspace/").ok_or_else(|| {
+        ToolError::InvalidInput("file path must start with /workspace/".to_owned())
+    })?;
+    let path = workspace_dir.join(relative);
+
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| ToolError::InvalidInput(format!("file not accessible: {e}")))?;
+    let canonical_workspace = workspace_dir
+        .canonicalize()
+        .map_err(|e| ToolError::ExecutionFailed(format!("workspace not accessible: {e}")))?;
+    if !canonical.starts_with(&canonical_workspace) {
+        return Err(ToolError::InvalidInput(
+            "file path must be within the workspace directory".to_owned(),
+        ));
+    }
+
+    Ok(path)
+}
+
 /// Send a message via WhatsApp through the outbound composer pipeline.
 ///
 /// Full flow:
 /// 1. Parse brief_id and text from input
 /// 2. Load the brief from SQLite
 /// 3. Load conversation history for context
-/// 4. Compose message via OutboundComposer (restricted context)
+/// 4. Fill the template if one is named, else compose via OutboundComposer
+///    (voice notes skip composition)
 /// 5. If blocked by redactor, return error
-/// 6. Send read receipt (mark_read)
-/// 7. Calculate human-like delay
-/// 8. Send typing indicator
-/// 9. Wait for the delay
-/// 10. Send text via WhatsAppClient
-/// 11. Log to audit trail
-/// 12. Return success
+/// 6. Apply quiet hours: queue for later unless the send is urgent (files
+///    cannot be queued)
+/// 7. Send read receipt (mark_read)
+/// 8. Calculate human-like delay
+/// 9. Send typing indicator
+/// 10. Wait for the delay
+/// 11. Send text, or the file with the composed text as caption
+/// 12. Log to audit trail with the delivery state (sent or failed)
+/// 13. Return success
 async fn send_whatsapp(
     input: &serde_json::Value,
+    workspace_dir: &Path,
     whatsapp_client: Option<&Arc<WhatsAppClient>>,
     outbound_composer: Option<&Arc<OutboundComposer>>,
     memory_pool: &SqlitePool,
@@ -150,15 +176,41 @@ async fn send_whatsapp(
 
     let incoming_text = input.get("incoming_text").and_then(|v| v.as_str());
 
+    let media_path = input
+        .get("file")
+        .and_then(|v| v.as_str())
+        .map(|f| resolve_workspace_file(f, workspace_dir))
+        .transpose()?;
+    let voice = input
+        .get("voice")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if voice && media_path.is_none() {
+        return Err(ToolError::InvalidInput(
+            "voice=true requires an audio file".to_owned(),
+        ));
+    }
+    let media_name = media_path.as_ref().map(|path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+
     // Step 1: Load the brief
     let brief = crate::messaging::brief::load_brief(memory_pool, brief_id)
         .await
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to load brief: {e}")))?;
 
-    // Step 2: Resolve the contact's WhatsApp JID
-    let jid = resolve_jid_for_brief(&brief, memory_pool)
+    // Step 2: Resolve the linked contact and its WhatsApp JID
+    let contact = load_contact_for_brief(&brief, memory_pool)
         .await
         .map_err(|e| ToolError::ExecutionFailed(format!("failed to resolve contact JID: {e}")))?;
+    let jid = contact.whatsapp_jid.clone().ok_or_else(|| {
+        ToolError::ExecutionFailed(format!(
+            "failed to resolve contact JID: contact '{}' has no WhatsApp JID",
+            contact.name
+        ))
+    })?;
 
     // Step 3: Load conversation history for multi-turn context
     let history =
@@ -168,11 +220,36 @@ async fn send_whatsapp(
                 ToolError::ExecutionFailed(format!("failed to load conversation history: {e}"))
             })?;
 
-    // Step 4: Compose message via OutboundComposer (restricted context)
-    let composed = composer
-        .compose(&brief, &history, incoming_text, agent_intent)
-        .await
-        .map_err(|e| ToolError::ExecutionFailed(format!("composition failed: {e}")))?;
+    // Step 4: Fill the requested template, or compose via OutboundComposer
+    // (restricted context). Voice notes carry no text to compose.
+    let composed = match input.get("template").and_then(|v| v.as_str()) {
+        _ if voice => ComposedMessage {
+            text: format!(
+                "[Voice message: {}]",
+                media_name.as_deref().unwrap_or_default()
+            ),
+            warnings: Vec::new(),
+            blocked: false,
+        },
+        Some(name) => {
+            let template = crate::messaging::templates::load_template(memory_pool, name)
+                .await
+                .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+            composer
+                .fill_template(&template, &brief, Some(&contact), &template_vars(input))
+                .map_err(|e| ToolError::InvalidInput(e.to_string()))?
+        }
+        None => composer
+            .compose(
+                &brief,
+                Some(&contact),
+                &history,
+                incoming_text,
+                agent_intent,
+            )
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("composition failed: {e}")))?,
+    };
 
     // Step 5: If blocked by redactor, return error
     if composed.blocked {
@@ -194,6 +271,7 @@ async fn send_whatsapp(
             "outbound",
             Some(&warnings_json),
             true,
+            None,
         )
         .await
         {
@@ -206,56 +284,169 @@ async fn send_whatsapp(
         )));
     }
 
-    // Step 6: Send read receipt
+    let warning_summary: Option<String> = if composed.warnings.is_empty() {
+        None
+    } else {
+        let summaries: Vec<String> = composed
+            .warnings
+            .iter()
+            .map(|w| w.category.clone())
+            .collect();
+        serde_json::to_string(&summaries).ok()
+    };
+
+    // Step 6: Apply quiet hours for this contact
+    let urgent = input
+        .get("urgent")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let aliases = match contact.id {
+        Some(id) => crate::messaging::contacts::list_aliases(memory_pool, id)
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let window = composer.quiet_hours().window_for(&contact, &aliases);
+    let plan = quiet_hours::plan_delivery(window, chrono::Local::now().naive_local(), urgent);
+
+    if let DeliveryPlan::Deferred { window_ends } = plan {
+        if media_path.is_some() {
+            return Err(ToolError::InvalidInput(format!(
+                "Quiet hours for {}: files and voice messages cannot be queued. \
+                 Send after quiet hours end, or set urgent=true only if it cannot wait.",
+                contact.name
+            )));
+        }
+        let release = quiet_hours::release_time(window_ends);
+        let queue_id = crate::messaging::outbound_queue::enqueue(
+            memory_pool,
+            Some(brief_id),
+            &brief.session_id,
+            "whatsapp",
+            &jid,
+            &composed.text,
+            quiet_hours::local_to_utc(release),
+        )
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("failed to queue message: {e}")))?;
+
+        if let Err(e) = crate::messaging::audit::log_outbound(
+            memory_pool,
+            Some(brief_id),
+            &brief.session_id,
+            "whatsapp",
+            &jid,
+            &composed.text,
+            "outbound",
+            warning_summary.as_deref(),
+            false,
+            Some(plan.audit_label()),
+        )
+        .await
+        {
+            warn!(error = %e, "failed to log queued outbound message to audit trail");
+        }
+
+        info!(
+            brief_id,
+            queue_id,
+            deliver_at = %release,
+            "WhatsApp message queued for quiet hours"
+        );
+        return Ok(format!(
+            "Quiet hours for {}: message queued (#{queue_id}) for delivery around {} local time. \
+             Set urgent=true only if it cannot wait.",
+            contact.name,
+            release.format("%Y-%m-%d %H:%M")
+        ));
+    }
+
+    if plan == DeliveryPlan::Override {
+        warn!(brief_id, jid = %jid, "quiet hours overridden by urgent send");
+    }
+
+    // Read the attachment before the human-like delay so a bad file fails fast.
+    let outgoing_media = match &media_path {
+        Some(path) => {
+            let caption = (!voice).then(|| composed.text.clone());
+            Some(
+                crate::whatsapp::media::load_outgoing(path, caption, voice)
+                    .await
+                    .map_err(|e| ToolError::InvalidInput(e.to_string()))?,
+            )
+        }
+        None => None,
+    };
+    let logged_text = match (&media_name, voice) {
+        (Some(name), false) => format!("[File: {name}]\n{}", composed.text),
+        _ => composed.text.clone(),
+    };
+
+    // Step 7: Send read receipt
     if let Err(e) = wa_client.mark_read(&jid).await {
         debug!(error = %e, "read receipt failed (non-critical)");
     }
 
-    // Step 7: Calculate human-like delay
+    // Step 8: Calculate human-like delay
     let incoming_len = incoming_text.map_or(0, str::len);
     let delay_ms =
         crate::messaging::outbound_composer::human_like_delay_ms(incoming_len, composed.text.len());
 
-    // Step 8: Send typing indicator
+    // Step 9: Send typing indicator
     if let Err(e) = wa_client.send_typing(&jid).await {
         debug!(error = %e, "typing indicator failed (non-critical)");
     }
 
-    // Step 9: Wait for human-like delay
+    // Step 10: Wait for human-like delay
     tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
 
-    // Step 10: Send the message
-    wa_client
-        .send_text(&jid, &composed.text)
-        .await
-        .map_err(|e| ToolError::ExecutionFailed(format!("WhatsApp send failed: {e}")))?;
-
-    // Step 11: Log to audit trail
-    let warning_summary: Option<String> = if composed.warnings.is_empty() {
-        None
-    } else {
-        let summaries: Vec<String> = composed
-            .warnings
-            .iter()
-            .map(|w| w.category.clone())
-            .collect();
-        serde_json::to_string(&summaries).ok()
+    // Step 11: Send the message
+    let sent = match &outgoing_media {
+        Some(media) => wa_client.send_media(&jid, media).await,
+        None => wa_client.send_text(&jid, &composed.text).await,
     };
 
-    if let Err(e) = crate::messaging::audit::log_outbound(
+    // Step 12: Log to audit trail with the delivery state
+    let log_id = match crate::messaging::audit::log_outbound(
         memory_pool,
         Some(brief_id),
         &brief.session_id,
         "whatsapp",
         &jid,
-        &composed.text,
+        &logged_text,
         "outbound",
         warning_summary.as_deref(),
         false,
+        Some(plan.audit_label()),
     )
     .await
     {
-        warn!(error = %e, "failed to log outbound message to audit trail");
+        Ok(id) => Some(id),
+        Err(e) => {
+            warn!(error = %e, "failed to log outbound message to audit trail");
+            None
+        }
+    };
+
+    let message_id = match sent {
+        Ok(message_id) => message_id,
+        Err(e) => {
+            if let Some(id) = log_id {
+                if let Err(e) = crate::messaging::delivery::mark_failed(memory_pool, id).await {
+                    warn!(error = %e, "failed to record failed delivery");
+                }
+            }
+            return Err(ToolError::ExecutionFailed(format!(
+                "WhatsApp send failed: {e}"
+            )));
+        }
+    };
+    if let Some(id) = log_id {
+        if let Err(e) =
+            crate::messaging::delivery::mark_sent(memory_pool, id, message_id.as_deref()).await
+        {
+            warn!(error = %e, "failed to record delivery state");
+        }
     }
 
     info!(
@@ -270,20 +461,75 @@ async fn send_whatsapp(
     ))
 }
 
-/// Resolve the WhatsApp JID for a brief's linked contact.
-async fn resolve_jid_for_brief(
+/// Render the templated WhatsApp message a `send_message` call would send.
+///
+/// Used to show the user exactly what will go out before they approve a
+/// templated send. Returns `None` for calls that do not use a template.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if the brief, contact, or template
+/// cannot be resolved or a placeholder has no value.
+pub async fn template_preview(
+    input: &serde_json::Value,
+    outbound_composer: Option<&Arc<OutboundComposer>>,
+    memory_pool: &SqlitePool,
+) -> Result<Option<String>, ToolError> {
+    let Some(name) = input.get("template").and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    let composer = outbound_composer.ok_or_else(|| {
+        ToolError::ExecutionFailed("Outbound composer not configured.".to_owned())
+    })?;
+    let brief_id = input
+        .get("brief_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ToolError::InvalidInput("templated sends require a brief_id".to_owned()))?;
+
+    let brief = crate::messaging::brief::load_brief(memory_pool, brief_id)
+        .await
+        .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+    let contact = load_contact_for_brief(&brief, memory_pool)
+        .await
+        .map_err(ToolError::InvalidInput)?;
+    let template = crate::messaging::templates::load_template(memory_pool, name)
+        .await
+        .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+
+    let composed = composer
+        .fill_template(&template, &brief, Some(&contact), &template_vars(input))
+        .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+    Ok(Some(format!("To {}:\n{}", contact.name, composed.text)))
+}
+
+/// Extra template values from the `vars` input object.
+///
+/// Non-string JSON values are rendered in their JSON form.
+fn template_vars(input: &serde_json::Value) -> HashMap<String, String> {
+    input
+        .get("vars")
+        .and_then(|v| v.as_object())
+        .map(|vars| {
+            vars.iter()
+                .map(|(k, v)| {
+                    let value = v.as_str().map_or_else(|| v.to_string(), str::to_owned);
+                    (k.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the contact linked to a brief.
+async fn load_contact_for_brief(
     brief: &crate::messaging::brief::TaskBrief,
     db: &SqlitePool,
-) -> Result<String, String> {
+) -> Result<crate::messaging::contacts::Contact, String> {
     let contact_id = brief
         .contact_id
         .ok_or_else(|| "brief has no linked contact".to_owned())?;
 
-    let contact = crate::messaging::contacts::load_contact(db, contact_id)
+    crate::messaging::contacts::load_contact(db, contact_id)
         .await
-        .map_err(|e| format!("contact lookup failed: {e}"))?;
-
-    contact
-        .whatsapp_jid
-        .ok_or_else(|| format!("contact '{}' has no WhatsApp JID", contact.name))
+        .map_err(|e| format!("contact lookup failed: {e}"))
 }
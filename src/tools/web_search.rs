@@ -0,0 +1,393 @@
+//! Web search tool with pluggable backends.
+//!
+//! Queries SearXNG, the Brave Search API, or DuckDuckGo's HTML endpoint and
+//! returns structured results (title, URL, snippet). Each result is checked
+//! against the domain trust ledger so the agent knows which follow-up
+//! `web_request` or `browser` calls will need approval.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use crate::agent::policy::RateLimiter;
+use crate::config::{SearchBackendKind, SearchConfig};
+use crate::credentials::Credentials;
+use crate::memory::MemoryEngine;
+
+use super::readability::decode_entities;
+use super::ToolError;
+
+/// Credential key for the Brave Search API.
+pub const BRAVE_API_KEY: &str = "BRAVE_API_KEY";
+
+/// Hard ceiling on results per query.
+const MAX_RESULTS_LIMIT: usize = 20;
+
+/// HTTP timeout for search requests.
+const SEARCH_TIMEOUT_SECS: u64 = 15;
+
+/// Maximum snippet length in characters.
+const MAX_SNIPPET_CHARS: usize = 300;
+
+/// DuckDuckGo HTML endpoint.
+const DUCKDUCKGO_URL: &str = "https://html.duckduckgo.com/html/";
+
+/// Brave Search API endpoint.
+const BRAVE_URL: &str = "https://api.search.brave.com/res/v1/web/search";
+
+/// Result title links on the DuckDuckGo HTML page.
+static DDG_TITLE_RE: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<a[^>]*class="result__a"[^>]*href="([^"]+)"[^>]*>(.*?)</a>"#).ok()
+});
+
+/// Result snippets on the DuckDuckGo HTML page.
+static DDG_SNIPPET_RE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r#"(?s)<a[^>]*class="result__snippet"[^>]*>(.*?)</a>"#).ok());
+
+/// HTML tags, stripped from titles and snippets.
+static TAG_RE: LazyLock<Option<Regex>> = LazyLock::new(|| Regex::new(r"<[^>]+>").ok());
+
+/// A single search result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchResult {
+    /// Page title.
+    pub title: String,
+    /// Result URL.
+    pub url: String,
+    /// Text snippet from the page.
+    pub snippet: String,
+    /// Whether the result's domain is in the trust ledger.
+    pub trusted: bool,
+}
+
+/// Configured search backend.
+///
+/// Deliberately not `Debug`: the Brave variant holds the API key.
+#[derive(Clone)]
+pub enum SearchBackend {
+    /// DuckDuckGo HTML results page.
+    DuckDuckGo,
+    /// SearXNG instance at the given base URL.
+    Searxng {
+        /// Base URL, e.g. `http://127.0.0.1:8888`.
+        base_url: String,
+    },
+    /// Brave Search API.
+    Brave {
+        /// Subscription token.
+        api_key: String,
+    },
+}
+
+impl SearchBackend {
+    /// Short backend name for logs and output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DuckDuckGo => "duckduckgo",
+            Self::Searxng { .. } => "searxng",
+            Self::Brave { .. } => "brave",
+        }
+    }
+}
+
+/// Web search client bound to one backend.
+#[derive(Clone)]
+pub struct WebSearch {
+    backend: SearchBackend,
+    max_results: usize,
+    client: reqwest::Client,
+}
+
+impl Default for WebSearch {
+    fn default() -> Self {
+        Self::new(
+            SearchBackend::DuckDuckGo,
+            SearchConfig::default().max_results,
+        )
+    }
+}
+
+impl WebSearch {
+    /// Create a search client for the given backend.
+    pub fn new(backend: SearchBackend, max_results: usize) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(SEARCH_TIMEOUT_SECS))
+            .user_agent(concat!("wintermute/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        Self {
+            backend,
+            max_results: max_results.clamp(1, MAX_RESULTS_LIMIT),
+            client,
+        }
+    }
+
+    /// Build the search client described by `[search]` in config.toml.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SearXNG has no `searxng_url` or Brave has no
+    /// `BRAVE_API_KEY` credential.
+    pub fn from_config(config: &SearchConfig, credentials: &Credentials) -> anyhow::Result<Self> {
+        let backend = match config.backend {
+            SearchBackendKind::DuckDuckGo => SearchBackend::DuckDuckGo,
+            SearchBackendKind::Searxng => SearchBackend::Searxng {
+                base_url: config.searxng_url.clone().ok_or_else(|| {
+                    anyhow::anyhow!("[search] backend = \"searxng\" requires searxng_url")
+                })?,
+            },
+            SearchBackendKind::Brave => SearchBackend::Brave {
+                api_key: credentials.require(BRAVE_API_KEY)?,
+            },
+        };
+        Ok(Self::new(backend, config.max_results))
+    }
+
+    /// The configured backend.
+    pub fn backend(&self) -> &SearchBackend {
+        &self.backend
+    }
+
+    /// Run a query and return up to `limit` results (the configured default
+    /// when `None`). Results are not yet checked against the trust ledger.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::ExecutionFailed`] if the backend request fails
+    /// or returns an unexpected response.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<SearchResult>, ToolError> {
+        let limit = limit
+            .unwrap_or(self.max_results)
+            .clamp(1, MAX_RESULTS_LIMIT);
+        debug!(backend = self.backend.name(), limit, "web_search request");
+
+        let mut results = match &self.backend {
+            SearchBackend::DuckDuckGo => {
+                let html = self
+                    .client
+                    .post(DUCKDUCKGO_URL)
+                    .form(&[("q", query)])
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| search_failed("duckduckgo", &e))?
+                    .text()
+                    .await
+                    .map_err(|e| search_failed("duckduckgo", &e))?;
+                parse_duckduckgo_html(&html)
+            }
+            SearchBackend::Searxng { base_url } => {
+                let url = format!("{}/search", base_url.trim_end_matches('/'));
+                let body: serde_json::Value = self
+                    .client
+                    .get(&url)
+                    .query(&[("q", query), ("format", "json")])
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| search_failed("searxng", &e))?
+                    .json()
+                    .await
+                    .map_err(|e| search_failed("searxng", &e))?;
+                parse_searxng_json(&body)
+            }
+            SearchBackend::Brave { api_key } => {
+                let count = limit.to_string();
+                let body: serde_json::Value = self
+                    .client
+                    .get(BRAVE_URL)
+                    .header("X-Subscription-Token", api_key)
+                    .header("Accept", "application/json")
+                    .query(&[("q", query), ("count", count.as_str())])
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|e| search_failed("brave", &e))?
+                    .json()
+                    .await
+                    .map_err(|e| search_failed("brave", &e))?;
+                parse_brave_json(&body)
+            }
+        };
+
+        results.truncate(limit);
+        Ok(results)
+    }
+}
+
+/// Run the `web_search` tool.
+///
+/// Extracts `query` (required) and optional `limit`, runs the search, marks
+/// each result with its trust-ledger status, and returns the results as JSON.
+///
+/// # Errors
+///
+/// Returns [`ToolError::InvalidInput`] if `query` is missing or empty,
+/// [`ToolError::RateLimited`] if the fetch rate limit is exceeded, or
+/// [`ToolError::ExecutionFailed`] if the backend fails.
+pub async fn web_search(
+    search: &WebSearch,
+    memory: &MemoryEngine,
+    limiter: &RateLimiter,
+    input: &serde_json::Value,
+) -> Result<String, ToolError> {
+    let query = input
+        .get("query")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .ok_or_else(|| ToolError::InvalidInput("missing required field: query".to_owned()))?;
+    let limit = input
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .and_then(|n| usize::try_from(n).ok());
+
+    limiter.check("web_search")?;
+    limiter.record();
+
+    let mut results = search.search(query, limit).await?;
+    for result in &mut results {
+        if let Some(domain) = result_domain(&result.url) {
+            result.trusted = memory.is_domain_trusted(&domain).await.unwrap_or(false);
+        }
+    }
+
+    Ok(format_results(search.backend().name(), query, &results))
+}
+
+/// Render results as the JSON document returned to the agent.
+pub fn format_results(backend: &str, query: &str, results: &[SearchResult]) -> String {
+    let body = serde_json::json!({
+        "backend": backend,
+        "query": query,
+        "results": results,
+        "note": "Results from untrusted domains need user approval before web_request or browser navigation.",
+    });
+    serde_json::to_string_pretty(&body).unwrap_or_default()
+}
+
+/// Host of a result URL, used for the trust-ledger lookup.
+pub fn result_domain(url: &str) -> Option<String> {
+    Url::parse(url).ok()?.host_str().map(str::to_owned)
+}
+
+/// Parse the DuckDuckGo HTML results page.
+///
+/// Result links point at DuckDuckGo's redirector (`/l/?uddg=<target>`); the
+/// real target URL is extracted from the `uddg` parameter. Ads are skipped.
+pub fn parse_duckduckgo_html(html: &str) -> Vec<SearchResult> {
+    let (Some(title_re), Some(snippet_re)) = (DDG_TITLE_RE.as_ref(), DDG_SNIPPET_RE.as_ref())
+    else {
+        return Vec::new();
+    };
+    let snippets: Vec<String> = snippet_re
+        .captures_iter(html)
+        .map(|c| clean_text(&c[1]))
+        .collect();
+
+    title_re
+        .captures_iter(html)
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let url = duckduckgo_target(&decode_entities(&c[1]))?;
+            Some(SearchResult {
+                title: clean_text(&c[2]),
+                url,
+                snippet: snippets.get(i).cloned().unwrap_or_default(),
+                trusted: false,
+            })
+        })
+        .collect()
+}
+
+/// Parse a SearXNG `format=json` response.
+pub fn parse_searxng_json(body: &serde_json::Value) -> Vec<SearchResult> {
+    json_results(body.get("results"), "content")
+}
+
+/// Parse a Brave Search API response.
+pub fn parse_brave_json(body: &serde_json::Value) -> Vec<SearchResult> {
+    json_results(
+        body.get("web").and_then(|w| w.get("results")),
+        "description",
+    )
+}
+
+/// Map a JSON array of `{title, url, <snippet_field>}` objects to results.
+fn json_results(items: Option<&serde_json::Value>, snippet_field: &str) -> Vec<SearchResult> {
+    items
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let url = item.get("url")?.as_str()?.to_owned();
+                    let title = item.get("title").and_then(|v| v.as_str()).unwrap_or("");
+                    let snippet = item
+                        .get(snippet_field)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("");
+                    Some(SearchResult {
+                        title: clean_text(title),
+                        url,
+                        snippet: clean_text(snippet),
+                        trusted: false,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Resolve a DuckDuckGo result href to the target URL. Ad links are dropped.
+fn duckduckgo_target(href: &str) -> Option<String> {
+    let absolute = if href.starts_with("//") {
+        format!("https:{href}")
+    } else {
+        href.to_owned()
+    };
+    let parsed = Url::parse(&absolute).ok()?;
+    let is_redirect = parsed
+        .host_str()
+        .is_some_and(|h| h.ends_with("duckduckgo.com"));
+    if !is_redirect {
+        return Some(absolute);
+    }
+    let target = parsed
+        .query_pairs()
+        .find(|(k, _)| k == "uddg")
+        .map(|(_, v)| v.into_owned())?;
+    // Sponsored results route through DuckDuckGo's ad click tracker.
+    if target.contains("duckduckgo.com/y.js") {
+        return None;
+    }
+    Some(target)
+}
+
+/// Strip tags, decode entities, collapse whitespace, and cap the length.
+fn clean_text(raw: &str) -> String {
+    let stripped = match TAG_RE.as_ref() {
+        Some(re) => re.replace_all(raw, ""),
+        None => Cow::Borrowed(raw),
+    };
+    let decoded = decode_entities(&stripped);
+    let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+    match collapsed.char_indices().nth(MAX_SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}...", &collapsed[..end]),
+        None => collapsed,
+    }
+}
+
+/// Build the error for a failed backend request.
+fn search_failed(backend: &str, err: &reqwest::Error) -> ToolError {
+    ToolError::ExecutionFailed(format!("{backend} search failed: {err}"))
+}
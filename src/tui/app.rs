@@ -0,0 +1,209 @@
+//! TUI state and input handling.
+//!
+//! Kept free of terminal I/O so the key handling and message formatting
+//! can be tested without a TTY.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::agent::TelegramOutbound;
+
+/// Maximum chat lines kept in memory; older lines scroll away.
+pub const MAX_CHAT_LINES: usize = 1000;
+
+/// Who a chat line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speaker {
+    /// Typed by the local user.
+    User,
+    /// Sent by the agent.
+    Agent,
+    /// Local status notes (approvals, errors, file paths).
+    System,
+}
+
+/// One entry in the chat pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatLine {
+    /// Origin of the line.
+    pub speaker: Speaker,
+    /// Plain-text body.
+    pub text: String,
+}
+
+/// Something the event loop must do in response to input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Route text to the session as a user message.
+    Send(String),
+    /// Resolve a pending approval.
+    Resolve {
+        /// Approval ID.
+        approval_id: String,
+        /// `true` to approve, `false` to deny.
+        approved: bool,
+    },
+    /// Leave the TUI.
+    Quit,
+}
+
+/// A tool call waiting for local approval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingApproval {
+    /// Approval ID from the [`ApprovalManager`](crate::agent::approval::ApprovalManager).
+    pub id: String,
+    /// Tool that needs approval.
+    pub tool_name: String,
+}
+
+/// Everything the TUI renders.
+#[derive(Debug, Default)]
+pub struct App {
+    /// Chat history, oldest first.
+    pub chat: Vec<ChatLine>,
+    /// Current contents of the input line.
+    pub input: String,
+    /// Active session IDs.
+    pub sessions: Vec<String>,
+    /// Tokens used today.
+    pub budget_used: u64,
+    /// Daily token limit.
+    pub budget_limit: u64,
+    /// Approvals awaiting `/approve` or `/deny`, oldest first.
+    pub pending: Vec<PendingApproval>,
+}
+
+impl App {
+    /// Create an empty app with a short usage hint in the chat pane.
+    pub fn new() -> Self {
+        let mut app = Self::default();
+        app.push(
+            Speaker::System,
+            "Type a message and press Enter. /approve, /deny, /quit. Esc or Ctrl-C exits.",
+        );
+        app
+    }
+
+    /// Append a chat line, dropping the oldest past [`MAX_CHAT_LINES`].
+    pub fn push(&mut self, speaker: Speaker, text: impl Into<String>) {
+        self.chat.push(ChatLine {
+            speaker,
+            text: text.into(),
+        });
+        if self.chat.len() > MAX_CHAT_LINES {
+            let excess = self.chat.len().saturating_sub(MAX_CHAT_LINES);
+            self.chat.drain(..excess);
+        }
+    }
+
+    /// Record an outbound agent message.
+    pub fn push_outbound(&mut self, msg: &TelegramOutbound) {
+        if let Some(ref text) = msg.text {
+            self.push(Speaker::Agent, strip_html(text));
+        }
+        if let Some((ref id, ref tool_name)) = msg.approval_keyboard {
+            self.pending.push(PendingApproval {
+                id: id.clone(),
+                tool_name: tool_name.clone(),
+            });
+            self.push(
+                Speaker::System,
+                format!("Approval {id} pending for {tool_name}: /approve or /deny"),
+            );
+        }
+        if let Some(ref path) = msg.file_path {
+            self.push(Speaker::System, format!("File: {path}"));
+        }
+    }
+
+    /// Share of the daily budget used, clamped to `0.0..=1.0`.
+    pub fn budget_ratio(&self) -> f64 {
+        if self.budget_limit == 0 {
+            return 0.0;
+        }
+        // Token counts stay far below 2^52, so the f64 conversion is exact.
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = self.budget_used as f64 / self.budget_limit as f64;
+        ratio.clamp(0.0, 1.0)
+    }
+
+    /// Apply a key press, returning the action it triggers, if any.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        match key.code {
+            KeyCode::Esc => Some(Action::Quit),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Action::Quit)
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                None
+            }
+            KeyCode::Enter => {
+                let line = std::mem::take(&mut self.input);
+                self.submit(line.trim())
+            }
+            _ => None,
+        }
+    }
+
+    /// Interpret a submitted input line.
+    fn submit(&mut self, line: &str) -> Option<Action> {
+        if line.is_empty() {
+            return None;
+        }
+        let (command, arg) = match line.split_once(char::is_whitespace) {
+            Some((cmd, rest)) => (cmd, rest.trim()),
+            None => (line, ""),
+        };
+        match command {
+            "/quit" | "/exit" => Some(Action::Quit),
+            "/approve" | "/deny" => {
+                let approved = command == "/approve";
+                let index = if arg.is_empty() {
+                    self.pending.len().checked_sub(1)
+                } else {
+                    self.pending.iter().position(|p| p.id == arg)
+                };
+                let Some(pending) = index.map(|i| self.pending.remove(i)) else {
+                    self.push(Speaker::System, "No matching approval is pending.");
+                    return None;
+                };
+                let verb = if approved { "Approved" } else { "Denied" };
+                self.push(Speaker::System, format!("{verb}: {}", pending.tool_name));
+                Some(Action::Resolve {
+                    approval_id: pending.id,
+                    approved,
+                })
+            }
+            _ => {
+                self.push(Speaker::User, line);
+                Some(Action::Send(line.to_owned()))
+            }
+        }
+    }
+}
+
+/// Convert Telegram HTML into plain text for the terminal.
+pub fn strip_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,131 @@
+//! Local terminal UI (`wintermute tui`).
+//!
+//! A chat pane, live log tail, budget gauge, and session list. Messages go
+//! through the same [`SessionRouter`] as Telegram; agent replies arrive on
+//! the outbound channel the Telegram adapter would otherwise drain. Meant for
+//! headless servers where Telegram isn't configured yet.
+
+pub mod app;
+pub mod ui;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::agent::approval::ApprovalManager;
+use crate::agent::budget::DailyBudget;
+use crate::agent::{SessionRouter, TelegramOutbound};
+use crate::logging::LogTail;
+
+use self::app::{Action, App, Speaker};
+
+/// How often sessions, budget, and logs are refreshed without input.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the input thread waits for a terminal event per poll.
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+/// Dependencies for [`run_tui`].
+pub struct TuiDeps {
+    /// Routes typed messages and approvals to agent sessions.
+    pub session_router: Arc<SessionRouter>,
+    /// Resolves `/approve` and `/deny`.
+    pub approval_manager: Arc<ApprovalManager>,
+    /// Source for the budget gauge.
+    pub daily_budget: Arc<DailyBudget>,
+    /// Agent output addressed to any user.
+    pub outbound_rx: mpsc::Receiver<TelegramOutbound>,
+    /// Recent log lines for the log pane.
+    pub log_tail: LogTail,
+    /// User ID the local operator acts as.
+    pub user_id: i64,
+}
+
+/// Run the terminal UI until the user quits.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be initialised or drawn to.
+pub async fn run_tui(deps: TuiDeps) -> anyhow::Result<()> {
+    let TuiDeps {
+        session_router,
+        approval_manager,
+        daily_budget,
+        mut outbound_rx,
+        log_tail,
+        user_id,
+    } = deps;
+
+    // crossterm's blocking reader lives on its own thread; it stops once
+    // the receiver is dropped.
+    let (key_tx, mut key_rx) = mpsc::unbounded_channel::<Event>();
+    std::thread::spawn(move || loop {
+        match event::poll(INPUT_POLL) {
+            Ok(true) => match event::read() {
+                Ok(ev) => {
+                    if key_tx.send(ev).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            },
+            Ok(false) => {
+                if key_tx.is_closed() {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    });
+
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new();
+    let mut refresh = tokio::time::interval(REFRESH_INTERVAL);
+    info!(user_id, "tui started");
+
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &app, &log_tail.lines())) {
+            break Err(e.into());
+        }
+
+        tokio::select! {
+            Some(ev) = key_rx.recv() => {
+                let Event::Key(key) = ev else { continue };
+                match app.handle_key(key) {
+                    Some(Action::Quit) => break Ok(()),
+                    Some(Action::Send(text)) => {
+                        if let Err(e) = session_router.route_message(user_id, text).await {
+                            app.push(Speaker::System, format!("Failed to send: {e}"));
+                        }
+                    }
+                    Some(Action::Resolve { approval_id, approved }) => {
+                        let result = approval_manager.resolve(&approval_id, approved, user_id);
+                        if let Err(e) = session_router.route_approval(result).await {
+                            warn!(error = %e, "failed to route approval result");
+                        }
+                    }
+                    None => {}
+                }
+            }
+            Some(msg) = outbound_rx.recv() => {
+                if msg.user_id == user_id {
+                    app.push_outbound(&msg);
+                } else {
+                    info!(user_id = msg.user_id, "outbound message for another user not shown");
+                }
+            }
+            _ = refresh.tick() => {
+                app.sessions = session_router.session_ids().await;
+                app.budget_used = daily_budget.used();
+                app.budget_limit = daily_budget.limit();
+            }
+        }
+    };
+
+    ratatui::restore();
+    session_router.shutdown_all().await;
+    result
+}
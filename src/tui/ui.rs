@@ -0,0 +1,137 @@
+//! TUI layout and rendering.
+//!
+//! ```text
+//! ┌ Sessions ┐┌ Chat ─────────────────────┐
+//! │ user_1   ││ you: hi                   │
+//! │          ││ wintermute: hello         │
+//! ├ Budget ──┤├ Logs ─────────────────────┤
+//! │ ███ 12%  ││ INFO agent turn finished  │
+//! └──────────┘├ Input ────────────────────┤
+//!             │ > _                       │
+//!             └───────────────────────────┘
+//! ```
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::app::{App, Speaker};
+
+/// Draw the whole UI for one frame.
+pub fn draw(frame: &mut Frame<'_>, app: &App, logs: &[String]) {
+    let [side, main] =
+        Layout::horizontal([Constraint::Length(24), Constraint::Min(20)]).areas(frame.area());
+    let [sessions_area, budget_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(side);
+    let [chat_area, logs_area, input_area] = Layout::vertical([
+        Constraint::Min(5),
+        Constraint::Length(8),
+        Constraint::Length(3),
+    ])
+    .areas(main);
+
+    draw_sessions(frame, app, sessions_area);
+    draw_budget(frame, app, budget_area);
+    draw_chat(frame, app, chat_area);
+    draw_logs(frame, logs, logs_area);
+    draw_input(frame, app, input_area);
+}
+
+fn draw_sessions(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let items: Vec<ListItem<'_>> = if app.sessions.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "(none)",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.sessions
+            .iter()
+            .map(|s| ListItem::new(s.as_str()))
+            .collect()
+    };
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Sessions"));
+    frame.render_widget(list, area);
+}
+
+fn draw_budget(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let ratio = app.budget_ratio();
+    let color = if ratio >= 0.9 {
+        Color::Red
+    } else if ratio >= 0.7 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title("Budget"))
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio)
+        .label(format!("{}/{}", app.budget_used, app.budget_limit));
+    frame.render_widget(gauge, area);
+}
+
+fn draw_chat(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let lines: Vec<Line<'_>> = app
+        .chat
+        .iter()
+        .flat_map(|entry| {
+            let (label, style) = match entry.speaker {
+                Speaker::User => ("you: ", Style::default().fg(Color::Cyan)),
+                Speaker::Agent => ("wintermute: ", Style::default().fg(Color::Magenta)),
+                Speaker::System => ("* ", Style::default().fg(Color::DarkGray)),
+            };
+            let label_style = style.add_modifier(Modifier::BOLD);
+            entry.text.lines().enumerate().map(move |(i, text)| {
+                let prefix = if i == 0 { label } else { "  " };
+                Line::from(vec![
+                    Span::styled(prefix, label_style),
+                    Span::raw(text.to_owned()),
+                ])
+            })
+        })
+        .collect();
+
+    // Keep the newest lines in view; wrapping may push a few more out.
+    let visible = usize::from(area.height.saturating_sub(2));
+    let skip = lines.len().saturating_sub(visible);
+    let paragraph = Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>())
+        .block(Block::default().borders(Borders::ALL).title("Chat"))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_logs(frame: &mut Frame<'_>, logs: &[String], area: Rect) {
+    let visible = usize::from(area.height.saturating_sub(2));
+    let skip = logs.len().saturating_sub(visible);
+    let lines: Vec<Line<'_>> = logs
+        .iter()
+        .skip(skip)
+        .map(|l| Line::from(l.as_str()))
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::DarkGray))
+        .block(Block::default().borders(Borders::ALL).title("Logs"));
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_input(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let title = if app.pending.is_empty() {
+        "Input".to_owned()
+    } else {
+        format!("Input — {} approval(s) pending", app.pending.len())
+    };
+    let paragraph = Paragraph::new(format!("> {}", app.input))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(paragraph, area);
+
+    // Cursor after "> " and the typed text, inside the border.
+    let typed = u16::try_from(app.input.chars().count()).unwrap_or(u16::MAX);
+    let x = area
+        .x
+        .saturating_add(3)
+        .saturating_add(typed)
+        .min(area.right().saturating_sub(2));
+    frame.set_cursor_position((x, area.y.saturating_add(1)));
+}
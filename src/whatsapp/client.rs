@@ -3,6 +3,7 @@
 //! All WhatsApp operations go through this client, which communicates
 //! with the baileys-based Node.js bridge via HTTP on port 3001.
 
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
@@ -62,6 +63,31 @@ pub struct WhatsAppStatus {
     pub connected: bool,
     /// The phone number linked, if connected.
     pub phone_number: Option<String>,
+    /// Whether WhatsApp logged this device out, so it must be re-paired.
+    #[serde(default)]
+    pub logged_out: bool,
+}
+
+/// A file to send through the bridge.
+#[derive(Debug, Clone)]
+pub struct OutgoingMedia {
+    /// Raw file contents.
+    pub data: Vec<u8>,
+    /// MIME type, used by WhatsApp to pick the message kind.
+    pub mime_type: String,
+    /// Filename shown to the recipient for documents.
+    pub file_name: String,
+    /// Optional caption sent with the file.
+    pub caption: Option<String>,
+    /// Deliver audio as a voice note instead of an attachment.
+    pub voice: bool,
+}
+
+/// Result of a successful send, as reported by the bridge.
+#[derive(Debug, Deserialize)]
+struct SendResult {
+    /// Bridge-assigned message identifier, used to match delivery receipts.
+    message_id: Option<String>,
 }
 
 /// Response envelope from the bridge HTTP API.
@@ -131,6 +157,20 @@ impl WhatsAppClient {
         body.data.ok_or(WhatsAppError::SidecarNotRunning)
     }
 
+    /// Ask the sidecar to re-open its WhatsApp connection.
+    ///
+    /// The bridge reuses the stored session; a logged-out session needs a
+    /// fresh QR pairing instead.
+    pub async fn reconnect(&self) -> Result<(), WhatsAppError> {
+        let url = format!("{}/reconnect", self.base_url);
+        let resp = self.client.post(&url).send().await?;
+        if !resp.status().is_success() {
+            debug!(status = %resp.status(), "WhatsApp reconnect request rejected");
+            return Err(WhatsAppError::NotConnected);
+        }
+        Ok(())
+    }
+
     /// Get a QR code for WhatsApp Web linking (returned as base64 PNG).
     pub async fn get_qr(&self) -> Result<String, WhatsAppError> {
         let url = format!("{}/qr", self.base_url);
@@ -145,7 +185,10 @@ impl WhatsAppClient {
     }
 
     /// Send a text message to the given JID.
-    pub async fn send_text(&self, jid: &str, text: &str) -> Result<(), WhatsAppError> {
+    ///
+    /// Returns the bridge's message ID when it reports one, so later
+    /// delivery and read receipts can be matched to this message.
+    pub async fn send_text(&self, jid: &str, text: &str) -> Result<Option<String>, WhatsAppError> {
         let url = format!("{}/send", self.base_url);
         let body = serde_json::json!({ "jid": jid, "text": text });
         let resp = self.client.post(&url).json(&body).send().await?;
@@ -155,8 +198,68 @@ impl WhatsAppClient {
             warn!(%status, "WhatsApp send failed: {body_text}");
             return Err(WhatsAppError::NotConnected);
         }
-        debug!(jid, "message sent via WhatsApp");
-        Ok(())
+        // Older bridges reply without a body; the send still succeeded.
+        let message_id = resp
+            .json::<BridgeResponse<SendResult>>()
+            .await
+            .ok()
+            .and_then(|r| r.data)
+            .and_then(|d| d.message_id);
+        debug!(jid, ?message_id, "message sent via WhatsApp");
+        Ok(message_id)
+    }
+
+    /// Download the media attached to a message.
+    ///
+    /// The bridge keeps the decrypted bytes of recent media messages and
+    /// serves them by message ID.
+    pub async fn download_media(&self, message_id: &str) -> Result<Vec<u8>, WhatsAppError> {
+        let url = format!("{}/media/{message_id}", self.base_url);
+        let resp = self.client.get(&url).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            warn!(%status, message_id, "WhatsApp media download failed");
+            return Err(WhatsAppError::MediaUnavailable(message_id.to_owned()));
+        }
+        let bytes = resp.bytes().await?;
+        debug!(message_id, size = bytes.len(), "WhatsApp media downloaded");
+        Ok(bytes.to_vec())
+    }
+
+    /// Send a file to the given JID.
+    ///
+    /// With `voice` set, audio is delivered as a push-to-talk voice note
+    /// rather than an attachment. Returns the bridge's message ID, like
+    /// [`send_text`](Self::send_text).
+    pub async fn send_media(
+        &self,
+        jid: &str,
+        media: &OutgoingMedia,
+    ) -> Result<Option<String>, WhatsAppError> {
+        let url = format!("{}/send-media", self.base_url);
+        let body = serde_json::json!({
+            "jid": jid,
+            "data": base64::engine::general_purpose::STANDARD.encode(&media.data),
+            "mime_type": media.mime_type,
+            "file_name": media.file_name,
+            "caption": media.caption,
+            "ptt": media.voice,
+        });
+        let resp = self.client.post(&url).json(&body).send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body_text = resp.text().await.unwrap_or_default();
+            warn!(%status, "WhatsApp media send failed: {body_text}");
+            return Err(WhatsAppError::NotConnected);
+        }
+        let message_id = resp
+            .json::<BridgeResponse<SendResult>>()
+            .await
+            .ok()
+            .and_then(|r| r.data)
+            .and_then(|d| d.message_id);
+        debug!(jid, file_name = %media.file_name, ?message_id, "media sent via WhatsApp");
+        Ok(message_id)
     }
 
     /// Get recent messages from a contact by JID.
@@ -1,7 +1,8 @@
 //! Event listener for incoming WhatsApp messages.
 //!
 //! Connects to the baileys sidecar's `/events/poll` HTTP long-polling endpoint
-//! and dispatches incoming messages to the router via an mpsc channel.
+//! and dispatches incoming messages (with media metadata) and delivery
+//! receipts to the router via an mpsc channel.
 
 use serde::Deserialize;
 use tokio::sync::mpsc;
@@ -16,12 +17,27 @@ pub enum WhatsAppEvent {
     Message {
         /// WhatsApp JID of the conversation.
         jid: String,
-        /// Message text content.
+        /// Message text content (the caption for media messages).
+        #[serde(default)]
         text: String,
         /// Whether this message was sent by us.
         from_me: bool,
         /// Bridge-assigned message identifier.
         message_id: Option<String>,
+        /// Attached media, downloadable from the bridge by `message_id`.
+        #[serde(default)]
+        media: Option<WhatsAppMedia>,
+    },
+    /// Delivery or read receipt for a message we sent.
+    #[serde(rename = "receipt")]
+    Receipt {
+        /// Bridge-assigned identifier of the message the receipt is for.
+        message_id: String,
+        /// Receipt status (e.g. "delivered", "read").
+        status: String,
+        /// WhatsApp JID of the conversation, if reported.
+        #[serde(default)]
+        jid: Option<String>,
     },
     /// WhatsApp connection established.
     #[serde(rename = "connected")]
@@ -31,9 +47,47 @@ pub enum WhatsAppEvent {
     Disconnected {
         /// Human-readable reason, if available.
         reason: Option<String>,
+        /// Whether WhatsApp ended the session (device unlinked or expired).
+        #[serde(default)]
+        logged_out: bool,
     },
 }
 
+/// Kind of media attached to a WhatsApp message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    /// Photo.
+    Image,
+    /// Voice note (push-to-talk).
+    Voice,
+    /// Audio file.
+    Audio,
+    /// Video clip.
+    Video,
+    /// Document or other file.
+    Document,
+    /// Stickers and anything else the bridge reports.
+    #[serde(other)]
+    Other,
+}
+
+/// Media metadata for a WhatsApp message.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct WhatsAppMedia {
+    /// What kind of media this is.
+    pub kind: MediaKind,
+    /// MIME type reported by WhatsApp.
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    /// Original filename, for documents.
+    #[serde(default)]
+    pub file_name: Option<String>,
+    /// Duration in seconds, for voice notes, audio, and video.
+    #[serde(default)]
+    pub duration_secs: Option<u32>,
+}
+
 /// Long-poll timeout for the HTTP client (seconds).
 const POLL_TIMEOUT_SECS: u64 = 60;
 
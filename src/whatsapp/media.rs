@@ -0,0 +1,191 @@
+//! WhatsApp media handling: save incoming images, voice notes, and documents,
+//! and prepare files for outbound sends.
+//!
+//! Mirrors [`crate::telegram::media`]: incoming media is downloaded from the
+//! bridge to the workspace inbox and replaced by a description string
+//! (`[Voice message: /path, 12s]`, `[Photo: /path]`, ...) so voice notes take
+//! the same transcription path as Telegram voice messages.
+
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::Utc;
+use tracing::debug;
+
+use super::client::{OutgoingMedia, WhatsAppClient};
+use super::events::{MediaKind, WhatsAppMedia};
+use super::WhatsAppError;
+use crate::telegram::media::{sanitize_filename, MediaDescription};
+
+/// Largest file accepted for an outbound send (WhatsApp's media limit).
+pub const MAX_OUTGOING_MEDIA_BYTES: u64 = 16_777_216;
+
+/// Download the media of an incoming message and produce a description.
+///
+/// Saves to `{inbox_dir}` under a name from [`incoming_filename`]. The
+/// message caption, if any, follows the description on its own line.
+///
+/// # Errors
+///
+/// Returns an error if the bridge no longer holds the media or the file
+/// cannot be written.
+pub async fn save_incoming(
+    client: &WhatsAppClient,
+    message_id: &str,
+    media: &WhatsAppMedia,
+    caption: &str,
+    inbox_dir: &Path,
+) -> anyhow::Result<MediaDescription> {
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
+    let file_path = inbox_dir.join(incoming_filename(media, &timestamp));
+
+    let bytes = client
+        .download_media(message_id)
+        .await
+        .context("failed to download media from WhatsApp bridge")?;
+
+    tokio::fs::create_dir_all(inbox_dir)
+        .await
+        .with_context(|| format!("failed to create inbox directory: {}", inbox_dir.display()))?;
+    tokio::fs::write(&file_path, &bytes)
+        .await
+        .with_context(|| format!("failed to write media to {}", file_path.display()))?;
+
+    debug!(path = %file_path.display(), size = bytes.len(), "WhatsApp media saved");
+
+    let text = describe(media, &file_path, caption);
+    Ok(MediaDescription { text, file_path })
+}
+
+/// Inbox filename for incoming media.
+///
+/// Documents keep their original (sanitized) name; everything else is named
+/// by kind and timestamp with an extension derived from the MIME type.
+pub fn incoming_filename(media: &WhatsAppMedia, timestamp: &str) -> String {
+    let mime = media.mime_type.as_deref();
+    match media.kind {
+        MediaKind::Voice => format!("wa_voice_{timestamp}.{}", extension_for(mime, "ogg")),
+        MediaKind::Image => format!("wa_photo_{timestamp}.{}", extension_for(mime, "jpg")),
+        MediaKind::Audio => format!("wa_audio_{timestamp}.{}", extension_for(mime, "m4a")),
+        MediaKind::Video => format!("wa_video_{timestamp}.{}", extension_for(mime, "mp4")),
+        MediaKind::Document | MediaKind::Other => match media.file_name.as_deref() {
+            Some(name) => sanitize_filename(name),
+            None => format!("wa_doc_{timestamp}"),
+        },
+    }
+}
+
+/// Description string routed to the agent for a saved media file.
+///
+/// Uses the same shapes as the Telegram media handlers.
+pub fn describe(media: &WhatsAppMedia, path: &Path, caption: &str) -> String {
+    let path = path.display();
+    let mut text = match (media.kind, media.duration_secs) {
+        (MediaKind::Voice, Some(secs)) => format!("[Voice message: {path}, {secs}s]"),
+        (MediaKind::Voice, None) => format!("[Voice message: {path}]"),
+        (MediaKind::Audio, Some(secs)) => format!("[Audio: {path}, {secs}s]"),
+        (MediaKind::Audio, None) => format!("[Audio: {path}]"),
+        (MediaKind::Image, _) => format!("[Photo: {path}]"),
+        (MediaKind::Video, _) => format!("[Video: {path}]"),
+        (MediaKind::Document | MediaKind::Other, _) => format!("[Document: {path}]"),
+    };
+    let caption = caption.trim();
+    if !caption.is_empty() {
+        text.push('\n');
+        text.push_str(caption);
+    }
+    text
+}
+
+/// Guess a MIME type from a file extension.
+pub fn mime_for_path(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "ogg" | "oga" | "opus" => "audio/ogg; codecs=opus",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Read a local file and prepare it for [`WhatsAppClient::send_media`].
+///
+/// # Errors
+///
+/// Returns [`WhatsAppError::InvalidMedia`] if the file cannot be read, is
+/// larger than [`MAX_OUTGOING_MEDIA_BYTES`], or `voice` is set for a file
+/// that is not audio.
+pub async fn load_outgoing(
+    path: &Path,
+    caption: Option<String>,
+    voice: bool,
+) -> Result<OutgoingMedia, WhatsAppError> {
+    let mime_type = mime_for_path(path);
+    if voice && !mime_type.starts_with("audio/") {
+        return Err(WhatsAppError::InvalidMedia(format!(
+            "voice messages must be audio files, got {}",
+            path.display()
+        )));
+    }
+
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| WhatsAppError::InvalidMedia(format!("{}: {e}", path.display())))?;
+    if metadata.len() > MAX_OUTGOING_MEDIA_BYTES {
+        return Err(WhatsAppError::InvalidMedia(format!(
+            "{} is {} bytes, over the {MAX_OUTGOING_MEDIA_BYTES} byte limit",
+            path.display(),
+            metadata.len()
+        )));
+    }
+
+    let data = tokio::fs::read(path)
+        .await
+        .map_err(|e| WhatsAppError::InvalidMedia(format!("{}: {e}", path.display())))?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "file".to_owned());
+
+    Ok(OutgoingMedia {
+        data,
+        mime_type: mime_type.to_owned(),
+        file_name,
+        caption,
+        voice,
+    })
+}
+
+/// File extension for a MIME type, or `fallback` when it is unknown.
+fn extension_for<'a>(mime: Option<&str>, fallback: &'a str) -> &'a str {
+    let essence = mime
+        .and_then(|m| m.split(';').next())
+        .map(str::trim)
+        .unwrap_or_default();
+    match essence {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/webp" => "webp",
+        "image/gif" => "gif",
+        "audio/ogg" => "ogg",
+        "audio/mpeg" => "mp3",
+        "audio/mp4" | "audio/aac" => "m4a",
+        "video/mp4" => "mp4",
+        "video/3gpp" => "3gp",
+        _ => fallback,
+    }
+}
@@ -1,10 +1,13 @@
-//! WhatsApp adapter: HTTP bridge client, event listener, setup flow, and message router.
+//! WhatsApp adapter: HTTP bridge client, event listener, setup flow, connection
+//! supervisor, and message router.
 //!
 //! Communicates with a baileys-based Docker sidecar (`wintermute-whatsapp`) via
 //! HTTP on port 3001 and long-polling for real-time incoming messages.
 
 pub mod client;
 pub mod events;
+pub mod media;
+pub mod reconnect;
 pub mod router;
 pub mod setup;
 
@@ -31,6 +34,14 @@ pub enum WhatsAppError {
     #[error("rate limited: {0}")]
     RateLimited(String),
 
+    /// The bridge no longer holds the media for this message.
+    #[error("media unavailable for message {0}")]
+    MediaUnavailable(String),
+
+    /// Outbound file could not be read or is not sendable.
+    #[error("invalid media: {0}")]
+    InvalidMedia(String),
+
     /// Container setup or lifecycle operation failed.
     #[error("setup failed: {0}")]
     SetupFailed(String),
@@ -0,0 +1,314 @@
+//! Connection supervisor: automatic reconnect and guided QR re-pairing.
+//!
+//! Listens for connection signals from the event stream. A dropped
+//! connection is retried with exponential backoff; a logged-out session (or
+//! one that will not come back) starts a re-pairing flow that sends the
+//! sidecar's QR code to the owner on Telegram and keeps them posted until
+//! the device is linked again.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::Engine;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::client::WhatsAppClient;
+use crate::agent::TelegramOutbound;
+use crate::telegram::ui::escape_html;
+
+/// First reconnect delay in milliseconds.
+const INITIAL_BACKOFF_MS: u64 = 2_000;
+
+/// Maximum reconnect delay in milliseconds.
+const MAX_BACKOFF_MS: u64 = 60_000;
+
+/// Reconnect attempts before falling back to re-pairing.
+const MAX_RECONNECT_ATTEMPTS: u32 = 6;
+
+/// How often the pairing flow checks for a new QR code or a completed link.
+const PAIRING_POLL_SECS: u64 = 5;
+
+/// QR codes sent in one pairing round before pausing.
+const MAX_QR_SENDS: u32 = 5;
+
+/// Pause between pairing rounds when nobody scans the code.
+const PAIRING_PAUSE_SECS: u64 = 3_600;
+
+/// Filename of the QR image written to the data directory.
+const QR_FILENAME: &str = "whatsapp_qr.png";
+
+/// Connection change reported to the supervisor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionSignal {
+    /// WhatsApp is connected.
+    Connected,
+    /// WhatsApp dropped the connection.
+    Disconnected {
+        /// Human-readable reason, if available.
+        reason: Option<String>,
+        /// Whether the session was logged out and needs a new QR pairing.
+        logged_out: bool,
+    },
+}
+
+/// Exponential backoff with a ceiling.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial_ms: u64,
+    max_ms: u64,
+    next_ms: u64,
+}
+
+impl Backoff {
+    /// Create a backoff starting at `initial_ms` and doubling up to `max_ms`.
+    pub fn new(initial_ms: u64, max_ms: u64) -> Self {
+        Self {
+            initial_ms,
+            max_ms,
+            next_ms: initial_ms,
+        }
+    }
+
+    /// Delay to wait before the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next_ms.min(self.max_ms);
+        self.next_ms = delay.saturating_mul(2).min(self.max_ms);
+        Duration::from_millis(delay)
+    }
+
+    /// Start again from the initial delay.
+    pub fn reset(&mut self) {
+        self.next_ms = self.initial_ms;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(INITIAL_BACKOFF_MS, MAX_BACKOFF_MS)
+    }
+}
+
+/// Whether a disconnect reason means the session was logged out.
+///
+/// Older bridges only report baileys' reason text or status code (401).
+pub fn is_logged_out(reason: Option<&str>) -> bool {
+    reason.is_some_and(|r| {
+        let lower = r.to_ascii_lowercase();
+        lower.contains("logged out") || lower.contains("loggedout") || lower.contains("401")
+    })
+}
+
+/// Decode a QR code returned by the sidecar into PNG bytes.
+///
+/// Accepts plain base64 or a `data:image/png;base64,` URL.
+pub fn decode_qr(code: &str) -> Option<Vec<u8>> {
+    let encoded = match code.split_once(',') {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => code,
+    };
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()
+}
+
+/// Spawn the connection supervisor.
+///
+/// Returns the sender the event loop uses to report connection changes.
+/// Status updates and QR codes go to `user_id` on Telegram; QR images are
+/// written to `data_dir` and removed once pairing completes.
+pub fn spawn_supervisor(
+    client: Arc<WhatsAppClient>,
+    data_dir: PathBuf,
+    telegram_tx: mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+) -> mpsc::Sender<ConnectionSignal> {
+    let (signal_tx, mut signal_rx) = mpsc::channel::<ConnectionSignal>(16);
+    let notifier = Notifier {
+        telegram_tx,
+        user_id,
+    };
+
+    tokio::spawn(async move {
+        while let Some(signal) = signal_rx.recv().await {
+            let ConnectionSignal::Disconnected { reason, logged_out } = signal else {
+                continue;
+            };
+
+            let logged_out = logged_out || is_logged_out(reason.as_deref());
+            let recovered = !logged_out && reconnect(&client, &notifier, reason.as_deref()).await;
+            if !recovered {
+                pair(&client, &notifier, &data_dir).await;
+            }
+
+            // Signals queued while we were busy describe a state we have
+            // already resolved.
+            while signal_rx.try_recv().is_ok() {}
+        }
+        debug!("WhatsApp connection supervisor stopped");
+    });
+
+    signal_tx
+}
+
+/// Sends supervisor status updates to the owner on Telegram.
+struct Notifier {
+    telegram_tx: mpsc::Sender<TelegramOutbound>,
+    user_id: i64,
+}
+
+impl Notifier {
+    async fn send(&self, text: String, file_path: Option<&Path>) {
+        let outbound = TelegramOutbound {
+            user_id: self.user_id,
+            text: Some(text),
+            file_path: file_path.map(|p| p.to_string_lossy().into_owned()),
+            approval_keyboard: None,
+            keyboard: None,
+        };
+        if let Err(e) = self.telegram_tx.send(outbound).await {
+            warn!(error = %e, "failed to send WhatsApp status to Telegram");
+        }
+    }
+}
+
+/// Retry the connection with backoff. Returns `true` once connected, or
+/// `false` if the session is logged out or the attempts run out.
+async fn reconnect(client: &WhatsAppClient, notifier: &Notifier, reason: Option<&str>) -> bool {
+    let mut backoff = Backoff::default();
+
+    for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+        let delay = backoff.next_delay();
+        info!(
+            attempt,
+            delay_ms = delay.as_millis(),
+            ?reason,
+            "reconnecting WhatsApp"
+        );
+        tokio::time::sleep(delay).await;
+
+        if let Err(e) = client.reconnect().await {
+            debug!(error = %e, attempt, "WhatsApp reconnect request failed");
+        }
+        match client.status().await {
+            Ok(status) if status.connected => {
+                info!(attempt, "WhatsApp reconnected");
+                return true;
+            }
+            Ok(status) if status.logged_out => {
+                info!("WhatsApp session logged out, re-pairing required");
+                return false;
+            }
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, attempt, "WhatsApp status check failed"),
+        }
+    }
+
+    warn!(
+        attempts = MAX_RECONNECT_ATTEMPTS,
+        "WhatsApp did not reconnect"
+    );
+    notifier
+        .send(
+            format!(
+                "<b>WhatsApp</b> is still disconnected after {MAX_RECONNECT_ATTEMPTS} attempts \
+                 ({}). Starting re-pairing.",
+                escape_html(reason.unwrap_or("no reason given"))
+            ),
+            None,
+        )
+        .await;
+    false
+}
+
+/// Guided re-pairing: send each new QR code to Telegram until the device
+/// is linked, pausing between rounds if nobody scans it.
+async fn pair(client: &WhatsAppClient, notifier: &Notifier, data_dir: &Path) {
+    let qr_path = data_dir.join(QR_FILENAME);
+    notifier
+        .send(
+            "<b>WhatsApp</b> needs to be linked again. On your phone open \
+             WhatsApp → Settings → Linked devices → Link a device, then scan \
+             the QR code below."
+                .to_owned(),
+            None,
+        )
+        .await;
+
+    let mut last_qr: Option<String> = None;
+    let mut sends: u32 = 0;
+
+    loop {
+        match client.status().await {
+            Ok(status) if status.connected => {
+                let phone = status
+                    .phone_number
+                    .unwrap_or_else(|| "this device".to_owned());
+                info!("WhatsApp paired");
+                notifier
+                    .send(
+                        format!("<b>WhatsApp</b> linked as {}.", escape_html(&phone)),
+                        None,
+                    )
+                    .await;
+                if let Err(e) = tokio::fs::remove_file(&qr_path).await {
+                    debug!(error = %e, "failed to remove WhatsApp QR image");
+                }
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "WhatsApp status check failed during pairing"),
+        }
+
+        if sends >= MAX_QR_SENDS {
+            notifier
+                .send(
+                    format!(
+                        "<b>WhatsApp</b> pairing paused: no scan after {MAX_QR_SENDS} codes. \
+                         I'll send a fresh one in {} minutes.",
+                        PAIRING_PAUSE_SECS.saturating_div(60)
+                    ),
+                    None,
+                )
+                .await;
+            tokio::time::sleep(Duration::from_secs(PAIRING_PAUSE_SECS)).await;
+            sends = 0;
+            last_qr = None;
+            continue;
+        }
+
+        match client.get_qr().await {
+            Ok(code) if last_qr.as_deref() != Some(code.as_str()) => {
+                if send_qr(notifier, &code, &qr_path, sends).await {
+                    sends = sends.saturating_add(1);
+                }
+                last_qr = Some(code);
+            }
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "no WhatsApp QR code available yet"),
+        }
+
+        tokio::time::sleep(Duration::from_secs(PAIRING_POLL_SECS)).await;
+    }
+}
+
+/// Write a QR code to disk and send it to Telegram. Returns whether it was sent.
+async fn send_qr(notifier: &Notifier, code: &str, qr_path: &Path, previous_sends: u32) -> bool {
+    let Some(png) = decode_qr(code) else {
+        warn!("WhatsApp QR code is not valid base64");
+        return false;
+    };
+    if let Err(e) = tokio::fs::write(qr_path, png).await {
+        warn!(error = %e, path = %qr_path.display(), "failed to write WhatsApp QR image");
+        return false;
+    }
+
+    let text = if previous_sends == 0 {
+        "WhatsApp QR code (expires in about a minute):".to_owned()
+    } else {
+        "The previous code expired. New WhatsApp QR code:".to_owned()
+    };
+    notifier.send(text, Some(qr_path)).await;
+    true
+}
@@ -0,0 +1,4 @@
+//! Integration tests for `src/admin/`.
+
+#[path = "admin/admin_test.rs"]
+mod admin_test;
@@ -0,0 +1,45 @@
+//! Tests for `src/admin/mod.rs` — bind checks, bearer auth, and limits.
+
+use wintermute::admin::{bearer_token, check_bind, search_limit, token_matches, AdminError};
+
+#[test]
+fn bind_must_be_loopback() {
+    assert!(check_bind("127.0.0.1:8787").is_ok());
+    assert!(check_bind("[::1]:8787").is_ok());
+    assert!(matches!(
+        check_bind("0.0.0.0:8787"),
+        Err(AdminError::NotLoopback(_))
+    ));
+    assert!(matches!(
+        check_bind("192.168.1.10:8787"),
+        Err(AdminError::NotLoopback(_))
+    ));
+    assert!(matches!(
+        check_bind("localhost"),
+        Err(AdminError::InvalidBind(_))
+    ));
+}
+
+#[test]
+fn bearer_token_is_extracted_from_header() {
+    assert_eq!(bearer_token("Bearer abc123"), Some("abc123"));
+    assert_eq!(bearer_token("Bearer "), None);
+    assert_eq!(bearer_token("Basic abc123"), None);
+    assert_eq!(bearer_token("abc123"), None);
+}
+
+#[test]
+fn token_comparison_requires_exact_match() {
+    assert!(token_matches("s3cret-token", "s3cret-token"));
+    assert!(!token_matches("s3cret-tokeN", "s3cret-token"));
+    assert!(!token_matches("s3cret", "s3cret-token"));
+    assert!(!token_matches("", "s3cret-token"));
+}
+
+#[test]
+fn search_limit_is_clamped() {
+    assert_eq!(search_limit(None), 10);
+    assert_eq!(search_limit(Some(0)), 1);
+    assert_eq!(search_limit(Some(25)), 25);
+    assert_eq!(search_limit(Some(1_000)), 50);
+}
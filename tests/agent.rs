@@ -1,5 +1,7 @@
 //! Integration tests for `src/agent/`.
 
+#[path = "agent/admission_test.rs"]
+mod admission_test;
 #[path = "agent/approval_test.rs"]
 mod approval_test;
 #[path = "agent/budget_test.rs"]
@@ -12,5 +14,9 @@ mod identity_test;
 mod loop_test;
 #[path = "agent/policy_test.rs"]
 mod policy_test;
+#[path = "agent/reload_test.rs"]
+mod reload_test;
 #[path = "agent/session_test.rs"]
 mod session_test;
+#[path = "agent/soul_test.rs"]
+mod soul_test;
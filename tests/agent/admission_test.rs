@@ -0,0 +1,148 @@
+//! Tests for `src/agent/admission.rs` — health-aware message admission.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use wintermute::agent::admission::{
+    check, format_executor_down, format_provider_down, Admission, PROVIDER_RETRY_AFTER_SECS,
+};
+use wintermute::heartbeat::health::{
+    BudgetReport, ComponentHealth, ComponentState, HealthReport, COMPONENT_EXECUTOR,
+};
+use wintermute::providers::health::ProviderOutcome;
+
+const SPEC: &str = "anthropic/claude-sonnet";
+
+fn now() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+        .expect("valid timestamp")
+        .with_timezone(&Utc)
+}
+
+fn ago(secs: i64) -> String {
+    now()
+        .checked_sub_signed(Duration::seconds(secs))
+        .expect("in range")
+        .to_rfc3339()
+}
+
+fn report(executor: ComponentState, heartbeat_secs_ago: i64) -> HealthReport {
+    let mut components = BTreeMap::new();
+    components.insert(
+        COMPONENT_EXECUTOR.to_owned(),
+        ComponentHealth {
+            status: executor,
+            last_error: (executor != ComponentState::Healthy)
+                .then(|| "container wintermute-sandbox is not running".to_owned()),
+            metrics: BTreeMap::new(),
+        },
+    );
+    HealthReport {
+        status: "running".to_owned(),
+        uptime_secs: 60,
+        last_heartbeat: ago(heartbeat_secs_ago),
+        executor: "Docker".to_owned(),
+        container_healthy: executor == ComponentState::Healthy,
+        active_sessions: 0,
+        memory_db_size_mb: 0.0,
+        scripts_count: 0,
+        dynamic_tools_count: 0,
+        budget_today: BudgetReport {
+            used: 0,
+            limit: 1_000,
+        },
+        last_error: None,
+        components,
+    }
+}
+
+fn failing(failures: u32, last_failure_secs_ago: i64) -> ProviderOutcome {
+    ProviderOutcome {
+        consecutive_failures: failures,
+        last_error: Some("HTTP 529 overloaded".to_owned()),
+        last_success_at: None,
+        last_failure_at: Some(ago(last_failure_secs_ago)),
+    }
+}
+
+#[test]
+fn healthy_system_admits() {
+    let healthy = report(ComponentState::Healthy, 30);
+    assert_eq!(
+        check(Some(&healthy), SPEC, &ProviderOutcome::default(), now()),
+        Admission::Admit
+    );
+    assert_eq!(
+        check(None, SPEC, &ProviderOutcome::default(), now()),
+        Admission::Admit
+    );
+}
+
+#[test]
+fn failing_provider_is_refused_until_cooldown() {
+    assert_eq!(
+        check(None, SPEC, &failing(3, 10), now()),
+        Admission::ProviderDown {
+            spec: SPEC.to_owned(),
+            error: Some("HTTP 529 overloaded".to_owned()),
+            retry_in_secs: PROVIDER_RETRY_AFTER_SECS - 10,
+        }
+    );
+    // A single failure is not enough to refuse.
+    assert_eq!(check(None, SPEC, &failing(1, 10), now()), Admission::Admit);
+    // After the cooldown one message is let through to probe the provider.
+    assert_eq!(
+        check(None, SPEC, &failing(5, PROVIDER_RETRY_AFTER_SECS), now()),
+        Admission::Admit
+    );
+}
+
+#[test]
+fn down_executor_is_reported_from_fresh_report() {
+    for state in [ComponentState::Degraded, ComponentState::Unavailable] {
+        let down = report(state, 30);
+        assert_eq!(
+            check(Some(&down), SPEC, &ProviderOutcome::default(), now()),
+            Admission::ExecutorDown {
+                executor: "Docker".to_owned(),
+                error: Some("container wintermute-sandbox is not running".to_owned()),
+            }
+        );
+    }
+
+    // A stale report is ignored.
+    let stale = report(ComponentState::Unavailable, 3_600);
+    assert_eq!(
+        check(Some(&stale), SPEC, &ProviderOutcome::default(), now()),
+        Admission::Admit
+    );
+}
+
+#[test]
+fn provider_down_takes_precedence_over_executor() {
+    let down = report(ComponentState::Unavailable, 30);
+    assert!(matches!(
+        check(Some(&down), SPEC, &failing(3, 5), now()),
+        Admission::ProviderDown { .. }
+    ));
+}
+
+#[test]
+fn notices_name_the_cause_and_escape_errors() {
+    let docker = format_executor_down("Docker", Some("<socket> refused"), 1);
+    assert!(docker.contains("Docker is down, so I can't run commands right now."));
+    assert!(docker.contains("&lt;socket&gt; refused"));
+    assert!(docker.contains("direct mode"));
+    assert!(!docker.contains("waiting"));
+
+    let direct = format_executor_down("Direct", None, 3);
+    assert!(direct.contains("The direct executor is down"));
+    assert!(!direct.contains("Last error"));
+    assert!(!direct.contains("direct mode"));
+    assert!(direct.contains("3 messages are waiting."));
+
+    let provider = format_provider_down(SPEC, Some("HTTP 529"), 42);
+    assert!(provider.contains("<code>anthropic/claude-sonnet</code>"));
+    assert!(provider.contains("Last error: HTTP 529"));
+    assert!(provider.contains("about 42 seconds"));
+}
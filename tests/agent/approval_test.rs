@@ -118,7 +118,38 @@ fn resolve_is_single_use() {
     assert!(matches!(first, ApprovalResult::Approved { .. }));
 
     let second = mgr.resolve(&id, true, 12345);
-    assert_eq!(second, ApprovalResult::NotFound);
+    assert_eq!(
+        second,
+        ApprovalResult::AlreadyResolved {
+            approved: true,
+            tool_name: "web_request".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn double_tap_keeps_the_first_decision() {
+    let mgr = ApprovalManager::new();
+    let id = mgr.request(
+        "execute_command".to_owned(),
+        serde_json::json!({"command": "rm -rf /tmp/x"}),
+        "session-1".to_owned(),
+        12345,
+    );
+
+    assert!(matches!(
+        mgr.resolve(&id, false, 12345),
+        ApprovalResult::Denied { .. }
+    ));
+    assert_eq!(
+        mgr.resolve(&id, true, 12345),
+        ApprovalResult::AlreadyResolved {
+            approved: false,
+            tool_name: "execute_command".to_owned(),
+        },
+        "a later approve tap must not override the denial"
+    );
+    assert_eq!(mgr.resolve(&id, true, 99999), ApprovalResult::WrongUser);
 }
 
 #[test]
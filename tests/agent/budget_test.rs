@@ -1,11 +1,13 @@
 //! Budget tracking tests.
 
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
 use wintermute::agent::budget::{
-    BudgetError, BudgetScope, BudgetStatus, DailyBudget, SessionBudget,
+    BudgetError, BudgetScope, BudgetStatus, BudgetTimezone, DailyBudget, Degradation, SessionBudget,
 };
-use wintermute::config::BudgetConfig;
+use wintermute::config::{BudgetConfig, DegradationConfig};
 
 fn test_config(session: u64, daily: u64, tool_calls: u32) -> BudgetConfig {
     BudgetConfig {
@@ -13,6 +15,8 @@ fn test_config(session: u64, daily: u64, tool_calls: u32) -> BudgetConfig {
         max_tokens_per_day: daily,
         max_tool_calls_per_turn: tool_calls,
         max_dynamic_tools_per_turn: 20,
+        degradation: DegradationConfig::default(),
+        ..BudgetConfig::default()
     }
 }
 
@@ -22,6 +26,32 @@ fn daily_budget_limit_returns_configured_value() {
     assert_eq!(daily.limit(), 42_000);
 }
 
+#[test]
+fn daily_budget_set_limit_keeps_usage() {
+    let daily = DailyBudget::new(1_000);
+    daily.record(900);
+    assert!(daily.check(200).is_err());
+
+    daily.set_limit(2_000);
+    assert_eq!(daily.limit(), 2_000);
+    assert_eq!(daily.used(), 900);
+    assert!(daily.check(200).is_ok());
+}
+
+#[test]
+fn session_budget_set_config_applies_new_limits() {
+    let daily = Arc::new(DailyBudget::new(100_000));
+    let mut budget = SessionBudget::new(daily, test_config(10_000, 100_000, 20));
+    budget.record_usage(6_000, 0);
+    assert!(budget.check_budget(5_000).is_err());
+
+    budget.set_config(test_config(20_000, 100_000, 5));
+    assert_eq!(budget.session_limit(), 20_000);
+    assert_eq!(budget.session_used(), 6_000);
+    assert!(budget.check_budget(5_000).is_ok());
+    assert!(budget.check_tool_calls(6).is_err());
+}
+
 #[test]
 fn session_budget_allows_when_under_limit() {
     let daily = Arc::new(DailyBudget::new(100_000));
@@ -60,6 +90,77 @@ fn daily_budget_resets_on_day_change() {
     assert_eq!(daily.used(), 0);
 }
 
+#[test]
+fn budget_timezone_parses_names_and_offsets() {
+    assert_eq!(BudgetTimezone::parse("UTC"), Some(BudgetTimezone::Utc));
+    assert_eq!(BudgetTimezone::parse("local"), Some(BudgetTimezone::Local));
+    let offset = FixedOffset::east_opt(8 * 3600).expect("offset");
+    assert_eq!(
+        BudgetTimezone::parse("+08:00"),
+        Some(BudgetTimezone::Fixed(offset))
+    );
+    assert_eq!(BudgetTimezone::parse("Europe/Paris"), None);
+}
+
+#[test]
+fn budget_day_follows_fixed_offset() {
+    let tz = BudgetTimezone::parse("+08:00").expect("offset");
+    // 17:30 UTC is already the next day at +08:00.
+    let now = Utc
+        .with_ymd_and_hms(2026, 3, 14, 17, 30, 0)
+        .single()
+        .expect("valid time");
+    let date = tz.date(now);
+    assert_eq!(date, NaiveDate::from_ymd_opt(2026, 3, 15).expect("date"));
+    assert_eq!(
+        tz.today_start(now),
+        Utc.with_ymd_and_hms(2026, 3, 14, 16, 0, 0)
+            .single()
+            .expect("valid time")
+    );
+    assert_eq!(BudgetTimezone::Utc.date(now).to_string(), "2026-03-14");
+}
+
+#[test]
+fn rollover_carries_over_unused_budget() {
+    let daily = DailyBudget::with_rollover(1_000, BudgetTimezone::Utc, 50);
+    daily.record(400);
+
+    // Pretend the current day started yesterday.
+    let today = daily.reset_day.load(Ordering::Relaxed);
+    daily.reset_day.store(today - 1, Ordering::Relaxed);
+
+    assert_eq!(daily.used(), 0);
+    assert_eq!(daily.carried_over(), 300);
+    assert_eq!(daily.limit(), 1_300);
+    assert_eq!(daily.base_limit(), 1_000);
+    assert!(daily.check(1_200).is_ok());
+}
+
+#[test]
+fn rollover_after_idle_gap_carries_nothing() {
+    let daily = DailyBudget::with_rollover(1_000, BudgetTimezone::Utc, 50);
+    daily.restore_carry_over(0);
+    assert_eq!(daily.carried_over(), 500);
+
+    let today = daily.reset_day.load(Ordering::Relaxed);
+    daily.reset_day.store(today - 3, Ordering::Relaxed);
+    assert_eq!(daily.carried_over(), 0);
+    assert_eq!(daily.limit(), 1_000);
+}
+
+#[test]
+fn carry_over_counts_toward_session_daily_limit() {
+    let daily = Arc::new(DailyBudget::with_rollover(1_000, BudgetTimezone::Utc, 100));
+    daily.restore_carry_over(800);
+    let budget = SessionBudget::new(Arc::clone(&daily), test_config(10_000, 1_000, 20));
+
+    assert_eq!(budget.daily_limit(), 1_200);
+    budget.record_usage(600, 0);
+    assert_eq!(budget.daily_percent(), 50);
+    assert_eq!(BudgetScope::Daily.remaining(&budget), 600);
+}
+
 #[test]
 fn record_usage_increments_both_session_and_daily() {
     let daily = Arc::new(DailyBudget::new(100_000));
@@ -293,3 +394,78 @@ fn renew_succeeds_when_session_exhausted_but_daily_has_room() {
     assert_eq!(budget.session_used(), 0);
     assert!(budget.check_budget(1).is_ok());
 }
+
+// ---------------------------------------------------------------------------
+// Degradation ladder
+// ---------------------------------------------------------------------------
+
+#[test]
+fn degradation_steps_follow_thresholds() {
+    let config = DegradationConfig::default();
+    assert!(!Degradation::at(&config, 79).is_active());
+
+    let cheap = Degradation::at(&config, 80);
+    assert!(cheap.cheap_model && !cheap.pause_background && !cheap.shorten_context);
+
+    let paused = Degradation::at(&config, 85);
+    assert!(paused.cheap_model && paused.pause_background && !paused.shorten_context);
+
+    let all = Degradation::at(&config, 100);
+    assert!(all.cheap_model && all.pause_background && all.shorten_context);
+    assert_eq!(all.describe().len(), 3);
+}
+
+#[test]
+fn disabled_degradation_never_applies() {
+    let config = DegradationConfig {
+        enabled: false,
+        ..DegradationConfig::default()
+    };
+    assert_eq!(Degradation::at(&config, 99), Degradation::default());
+}
+
+#[test]
+fn threshold_above_100_disables_a_step() {
+    let config = DegradationConfig {
+        cheap_model_at: 101,
+        ..DegradationConfig::default()
+    };
+    let d = Degradation::at(&config, 100);
+    assert!(!d.cheap_model && d.pause_background && d.shorten_context);
+}
+
+#[test]
+fn degradation_uses_higher_of_session_and_daily_usage() {
+    let daily = Arc::new(DailyBudget::new(1_000));
+    let budget = SessionBudget::new(Arc::clone(&daily), test_config(10_000, 1_000, 20));
+
+    daily.record(860);
+    assert_eq!(daily.percent(), 86);
+    let d = budget.degradation();
+    assert!(d.cheap_model && d.pause_background && !d.shorten_context);
+}
+
+#[test]
+fn each_degradation_step_is_announced_once() {
+    let daily = Arc::new(DailyBudget::new(1_000_000));
+    let budget = SessionBudget::new(Arc::clone(&daily), test_config(1_000, 1_000_000, 20));
+
+    budget.record_usage(810, 0);
+    let (current, new) = budget.announce_degradation();
+    assert!(current.cheap_model);
+    assert!(new.cheap_model && !new.pause_background);
+
+    let (_, new) = budget.announce_degradation();
+    assert!(!new.is_active(), "nothing new to announce");
+
+    budget.record_usage(100, 0);
+    let (_, new) = budget.announce_degradation();
+    assert!(!new.cheap_model && new.pause_background && new.shorten_context);
+
+    assert!(budget.renew());
+    let (current, _) = budget.announce_degradation();
+    assert!(!current.is_active(), "renewal lifts degradation");
+    budget.record_usage(810, 0);
+    let (_, new) = budget.announce_degradation();
+    assert!(new.cheap_model, "returning step is announced again");
+}
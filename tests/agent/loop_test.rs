@@ -15,8 +15,8 @@ use wintermute::agent::r#loop::{SessionConfig, SessionEvent};
 use wintermute::agent::session_manager::SessionManager;
 use wintermute::agent::TelegramOutbound;
 use wintermute::config::{
-    AgentConfig, BudgetConfig, ChannelsConfig, Config, EgressConfig, HeartbeatConfig,
-    LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig, SandboxConfig,
+    AgentConfig, BudgetConfig, ChannelsConfig, Config, DegradationConfig, EgressConfig,
+    HeartbeatConfig, LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig, SandboxConfig,
     SoulModificationMode, TelegramConfig,
 };
 use wintermute::executor::ExecutorKind;
@@ -37,6 +37,7 @@ fn make_config() -> Config {
             default: "ollama/llama3".to_owned(),
             roles: std::collections::HashMap::new(),
             skills: std::collections::HashMap::new(),
+            fixtures: wintermute::config::FixtureConfig::default(),
         },
         channels: ChannelsConfig {
             telegram: TelegramConfig {
@@ -50,11 +51,19 @@ fn make_config() -> Config {
             max_tokens_per_day: 1_000_000,
             max_tool_calls_per_turn: 20,
             max_dynamic_tools_per_turn: 10,
+            degradation: DegradationConfig::default(),
+            ..BudgetConfig::default()
         },
         egress: EgressConfig::default(),
         privacy: PrivacyConfig::default(),
         browser: wintermute::config::BrowserConfig::default(),
         whatsapp: wintermute::config::WhatsAppConfig::default(),
+        search: wintermute::config::SearchConfig::default(),
+        email: wintermute::config::EmailConfig::default(),
+        mcp_servers: vec![],
+        admin_api: wintermute::config::AdminApiConfig::default(),
+        telemetry: wintermute::config::TelemetryConfig::default(),
+        tool_health: wintermute::config::ToolHealthConfig::default(),
     }
 }
 
@@ -234,6 +243,7 @@ async fn run_session_completes_on_shutdown() {
         default: "ollama/llama3".to_owned(),
         roles: std::collections::HashMap::new(),
         skills: std::collections::HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let router = Arc::new(
         ModelRouter::from_config(&models_config, &creds).expect("failed to build model router"),
@@ -337,6 +347,7 @@ async fn run_session_completes_on_channel_close() {
         default: "ollama/llama3".to_owned(),
         roles: std::collections::HashMap::new(),
         skills: std::collections::HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let router = Arc::new(
         ModelRouter::from_config(&models_config, &creds).expect("failed to build model router"),
@@ -1082,6 +1093,132 @@ async fn run_session_applies_browser_policy_to_tool_use_integration() {
     assert!(join.is_ok(), "session should stop cleanly");
 }
 
+#[tokio::test]
+async fn run_session_replays_recorded_tool_call_and_charges_budget() {
+    let db = sqlx::SqlitePool::connect("sqlite::memory:")
+        .await
+        .expect("failed to create in-memory db");
+    setup_memory_db(&db).await;
+
+    let memory = Arc::new(
+        MemoryEngine::new(db, None)
+            .await
+            .expect("failed to create memory engine"),
+    );
+
+    let (telegram_tx, mut telegram_rx) = mpsc::channel::<TelegramOutbound>(16);
+    let daily = Arc::new(DailyBudget::new(1_000_000));
+    let budget = SessionBudget::new(Arc::clone(&daily), BudgetConfig::default());
+    let approval_manager = Arc::new(ApprovalManager::new());
+
+    let policy_context = PolicyContext {
+        allowed_domains: vec![],
+        blocked_domains: vec![],
+        always_approve_domains: vec![],
+        executor_kind: ExecutorKind::Direct,
+    };
+
+    // Replay mode needs no credentials; responses come from the fixture file.
+    let models = ModelsConfig {
+        default: "replay/agent".to_owned(),
+        roles: std::collections::HashMap::new(),
+        skills: std::collections::HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig {
+            mode: wintermute::config::FixtureMode::Replay,
+            dir: Some(std::path::PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/providers"
+            ))),
+        },
+    };
+    let router = Arc::new(
+        ModelRouter::from_config(&models, &wintermute::credentials::Credentials::default())
+            .expect("replay router should init"),
+    );
+
+    let executor = Arc::new(TestExecutor);
+    let redactor = wintermute::executor::redactor::Redactor::new(vec![]);
+    let registry = wintermute::tools::registry::DynamicToolRegistry::new_without_watcher(
+        std::path::PathBuf::from("/tmp/wintermute-test-scripts"),
+    )
+    .expect("failed to create test registry");
+    let fetch_limiter = Arc::new(wintermute::agent::policy::RateLimiter::new(60, 30));
+    let request_limiter = Arc::new(wintermute::agent::policy::RateLimiter::new(60, 10));
+    let browser_limiter = Arc::new(wintermute::agent::policy::RateLimiter::new(60, 60));
+    let tool_router = Arc::new(wintermute::tools::ToolRouter::new(
+        executor,
+        redactor,
+        Arc::clone(&memory),
+        registry,
+        None,
+        fetch_limiter,
+        request_limiter,
+        browser_limiter,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ));
+
+    let (event_tx, event_rx) = mpsc::channel::<SessionEvent>(16);
+    let session_manager = Arc::new(SessionManager::new(memory.pool().clone()));
+    let cfg = SessionConfig {
+        session_id: "replay-session".to_owned(),
+        user_id: 12345,
+        router,
+        tool_router,
+        memory,
+        budget,
+        approval_manager,
+        policy_context,
+        telegram_tx,
+        config: Arc::new(make_config()),
+        agent_config: Arc::new(make_agent_config()),
+        observer_tx: None,
+        identity_document: None,
+        agents_md_content: None,
+        user_md_content: None,
+        session_manager,
+    };
+
+    let handle = tokio::spawn(wintermute::agent::r#loop::run_session(cfg, event_rx));
+    event_tx
+        .send(SessionEvent::UserMessage(
+            "What did I say about the dentist?".to_owned(),
+        ))
+        .await
+        .expect("failed to send user message");
+
+    let outbound = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            if let Some(msg) = telegram_rx.recv().await {
+                if msg
+                    .text
+                    .as_deref()
+                    .is_some_and(|text| text.contains("nothing saved about the dentist"))
+                {
+                    break msg;
+                }
+            }
+        }
+    })
+    .await;
+    assert!(outbound.is_ok(), "expected the replayed final answer");
+    // Both recorded calls are charged: (120 + 30) + (200 + 15).
+    assert_eq!(daily.used(), 365);
+
+    event_tx
+        .send(SessionEvent::Shutdown)
+        .await
+        .expect("failed to send shutdown");
+    let join = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+    assert!(join.is_ok(), "session should stop cleanly");
+}
+
 // ---------------------------------------------------------------------------
 // Test executor (minimal)
 // ---------------------------------------------------------------------------
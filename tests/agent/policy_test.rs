@@ -122,6 +122,54 @@ fn policy_allows_safe_tools() {
     }
 }
 
+#[test]
+fn policy_requires_approval_for_tool_needing_network() {
+    let ctx = default_ctx(ExecutorKind::Docker);
+    let offline = serde_json::json!({"name": "t", "manifest": {"needs_network": []}});
+    assert_eq!(
+        check_policy("create_tool", &offline, &ctx, &always_false),
+        PolicyDecision::Allow
+    );
+    let online =
+        serde_json::json!({"name": "t", "manifest": {"needs_network": ["api.github.com"]}});
+    assert_eq!(
+        check_policy("create_tool", &online, &ctx, &always_false),
+        PolicyDecision::RequireApproval
+    );
+}
+
+#[test]
+fn policy_requires_approval_for_templated_send() {
+    let ctx = default_ctx(ExecutorKind::Docker);
+    let input = serde_json::json!({
+        "channel": "whatsapp",
+        "brief_id": "b1",
+        "text": "follow up",
+        "template": "follow_up"
+    });
+    let result = check_policy("send_message", &input, &ctx, &always_false);
+    assert_eq!(result, PolicyDecision::RequireApproval);
+}
+
+#[test]
+fn policy_requires_approval_for_email_send_only() {
+    let ctx = default_ctx(ExecutorKind::Docker);
+    let send = serde_json::json!({"action": "send", "to": ["a@example.com"], "body": "hi"});
+    assert_eq!(
+        check_policy("email", &send, &ctx, &always_true),
+        PolicyDecision::RequireApproval
+    );
+
+    for action in ["list", "read"] {
+        let input = serde_json::json!({"action": action});
+        assert_eq!(
+            check_policy("email", &input, &ctx, &always_false),
+            PolicyDecision::Allow,
+            "action: {action}"
+        );
+    }
+}
+
 #[test]
 fn policy_requires_approval_for_web_request_unknown_domain() {
     let ctx = default_ctx(ExecutorKind::Docker);
@@ -0,0 +1,106 @@
+//! Config reload diff and report tests.
+
+use wintermute::agent::reload::{format_report, restart_required_changes, ReloadReport};
+use wintermute::config::{AgentConfig, Config};
+
+const BASE_CONFIG: &str = r#"
+[models]
+default = "ollama/qwen3:8b"
+
+[channels.telegram]
+bot_token_env = "TOK"
+allowed_users = [1]
+"#;
+
+fn config(extra: &str) -> Config {
+    toml::from_str(&format!("{BASE_CONFIG}{extra}")).expect("config should parse")
+}
+
+fn agent_config(toml_str: &str) -> AgentConfig {
+    toml::from_str(toml_str).expect("agent config should parse")
+}
+
+#[test]
+fn identical_configs_need_no_restart() {
+    let changes = restart_required_changes(
+        &config(""),
+        &agent_config(""),
+        &config(""),
+        &agent_config(""),
+    );
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn hot_reloadable_changes_need_no_restart() {
+    let old = config("");
+    let new_toml = r#"
+[models]
+default = "ollama/qwen3:8b"
+
+[models.roles]
+observer = "ollama/qwen3:8b"
+
+[channels.telegram]
+bot_token_env = "TOK"
+allowed_users = [1, 2]
+
+[budget]
+max_tokens_per_day = 1
+"#;
+    let new: Config = toml::from_str(new_toml).expect("config should parse");
+    let changes = restart_required_changes(
+        &old,
+        &agent_config(""),
+        &new,
+        &agent_config("[heartbeat]\ninterval_secs = 5\n"),
+    );
+    assert!(changes.is_empty(), "unexpected: {changes:?}");
+}
+
+#[test]
+fn restart_only_changes_are_listed() {
+    let changes = restart_required_changes(
+        &config(""),
+        &agent_config(""),
+        &config("[sandbox]\nmemory_mb = 4096\n"),
+        &agent_config("[heartbeat]\nenabled = false\n"),
+    );
+    assert_eq!(changes, vec!["sandbox", "heartbeat.enabled"]);
+}
+
+#[test]
+fn quiet_hours_map_compares_by_value() {
+    let agent_toml = r#"
+[messaging.contact_quiet_hours]
+alice = "off"
+bob = "22:00-08:00"
+carol = "23:00-07:00"
+"#;
+    let changes = restart_required_changes(
+        &config(""),
+        &agent_config(agent_toml),
+        &config(""),
+        &agent_config(agent_toml),
+    );
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn format_report_without_changes() {
+    let text = format_report(&ReloadReport::default());
+    assert!(text.contains("No changes"));
+}
+
+#[test]
+fn format_report_lists_applied_and_restart_required() {
+    let report = ReloadReport {
+        applied: vec!["budget".to_owned()],
+        restart_required: vec!["models: load provider <x>".to_owned()],
+    };
+    let text = format_report(&report);
+    assert!(text.contains("Applied:\n• budget"));
+    assert!(text.contains("Needs restart:"));
+    assert!(text.contains("&lt;x&gt;"));
+    assert!(!text.contains("No changes"));
+}
@@ -13,8 +13,8 @@ use wintermute::agent::session_manager::SessionManager;
 use wintermute::agent::SessionRouter;
 use wintermute::agent::TelegramOutbound;
 use wintermute::config::{
-    AgentConfig, BudgetConfig, ChannelsConfig, Config, EgressConfig, HeartbeatConfig,
-    LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig, SandboxConfig,
+    AgentConfig, BudgetConfig, ChannelsConfig, Config, DegradationConfig, EgressConfig,
+    HeartbeatConfig, LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig, SandboxConfig,
     SoulModificationMode, TelegramConfig,
 };
 use wintermute::executor::ExecutorKind;
@@ -31,6 +31,7 @@ fn make_config() -> Config {
             default: "ollama/llama3".to_owned(),
             roles: std::collections::HashMap::new(),
             skills: std::collections::HashMap::new(),
+            fixtures: wintermute::config::FixtureConfig::default(),
         },
         channels: ChannelsConfig {
             telegram: TelegramConfig {
@@ -44,11 +45,19 @@ fn make_config() -> Config {
             max_tokens_per_day: 1_000_000,
             max_tool_calls_per_turn: 20,
             max_dynamic_tools_per_turn: 10,
+            degradation: DegradationConfig::default(),
+            ..BudgetConfig::default()
         },
         egress: EgressConfig::default(),
         privacy: PrivacyConfig::default(),
         browser: wintermute::config::BrowserConfig::default(),
         whatsapp: wintermute::config::WhatsAppConfig::default(),
+        search: wintermute::config::SearchConfig::default(),
+        email: wintermute::config::EmailConfig::default(),
+        mcp_servers: vec![],
+        admin_api: wintermute::config::AdminApiConfig::default(),
+        telemetry: wintermute::config::TelemetryConfig::default(),
+        tool_health: wintermute::config::ToolHealthConfig::default(),
     }
 }
 
@@ -170,6 +179,7 @@ async fn build_session_router() -> (SessionRouter, mpsc::Receiver<TelegramOutbou
         default: "ollama/llama3".to_owned(),
         roles: std::collections::HashMap::new(),
         skills: std::collections::HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let router = Arc::new(
         ModelRouter::from_config(&models_config, &creds).expect("failed to build model router"),
@@ -388,3 +398,78 @@ async fn remove_session_nonexistent_returns_false() {
     let removed = router.remove_session(99999).await;
     assert!(!removed);
 }
+
+#[tokio::test]
+async fn message_is_held_while_executor_is_down() {
+    use wintermute::agent::Keyboard;
+    use wintermute::heartbeat::health::{
+        BudgetReport, ComponentHealth, ComponentState, HealthReport, COMPONENT_EXECUTOR,
+    };
+
+    let (router, mut tg_rx) = build_session_router().await;
+    let mut components = BTreeMap::new();
+    components.insert(
+        COMPONENT_EXECUTOR.to_owned(),
+        ComponentHealth {
+            status: ComponentState::Unavailable,
+            last_error: Some("docker daemon unreachable".to_owned()),
+            metrics: BTreeMap::new(),
+        },
+    );
+    router.record_health(&HealthReport {
+        status: "degraded".to_owned(),
+        uptime_secs: 60,
+        last_heartbeat: chrono::Utc::now().to_rfc3339(),
+        executor: "Docker".to_owned(),
+        container_healthy: false,
+        active_sessions: 0,
+        memory_db_size_mb: 0.0,
+        scripts_count: 0,
+        dynamic_tools_count: 0,
+        budget_today: BudgetReport {
+            used: 0,
+            limit: 1_000,
+        },
+        last_error: None,
+        components,
+    });
+
+    router
+        .route_message(12345, "run ls".to_owned())
+        .await
+        .expect("route should succeed");
+    assert_eq!(router.session_count().await, 0);
+
+    let notice = tg_rx.try_recv().expect("status notice");
+    assert!(notice
+        .text
+        .as_deref()
+        .is_some_and(|t| t.contains("Docker is down")));
+    assert_eq!(notice.keyboard, Some(Keyboard::HeldMessage));
+
+    router
+        .route_message(12345, "and then pwd".to_owned())
+        .await
+        .expect("route should succeed");
+    let notice = tg_rx.try_recv().expect("second status notice");
+    assert!(notice
+        .text
+        .as_deref()
+        .is_some_and(|t| t.contains("2 messages are waiting")));
+
+    assert_eq!(
+        router
+            .release_held(12345, false)
+            .await
+            .expect("drop should succeed"),
+        2
+    );
+    assert_eq!(
+        router
+            .release_held(12345, true)
+            .await
+            .expect("nothing left to send"),
+        0
+    );
+    assert_eq!(router.session_count().await, 0);
+}
@@ -0,0 +1,242 @@
+//! Tests for `src/agent/soul.rs` and the `/soul` command.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::agent::soul::{
+    active_soul, approve, check_soul, history, parse_soul_callback, reject, revert,
+    soul_callback_data, soul_diff, sync_soul, write_soul, SoulChange, SoulStatus,
+};
+use wintermute::agent::Keyboard;
+use wintermute::config::SoulModificationMode;
+use wintermute::memory::MemoryEngine;
+use wintermute::telegram::commands::handle_soul;
+
+async fn setup_memory() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    sqlx::raw_sql(include_str!("../../migrations/011_soul_versions.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("memory engine should start")
+}
+
+fn write_agent_toml(dir: &std::path::Path, soul: &str) -> std::path::PathBuf {
+    let path = dir.join("agent.toml");
+    let contents = format!(
+        "# agent-owned\n[personality]\nname = \"Wintermute\"\nsoul = \"\"\"\n{soul}\"\"\"\n\n[heartbeat]\nenabled = true\n"
+    );
+    std::fs::write(&path, contents).expect("write agent.toml");
+    path
+}
+
+#[tokio::test]
+async fn first_sync_seeds_active_version() {
+    let memory = setup_memory().await;
+    let change = sync_soul(&memory, "Be direct.", SoulModificationMode::Approve)
+        .await
+        .expect("sync");
+    assert!(matches!(change, Some(SoulChange::Seeded(ref v)) if v.status == SoulStatus::Active));
+
+    let again = sync_soul(&memory, "Be direct.", SoulModificationMode::Approve)
+        .await
+        .expect("sync");
+    assert!(again.is_none());
+}
+
+#[tokio::test]
+async fn notify_mode_activates_change_immediately() {
+    let memory = setup_memory().await;
+    sync_soul(&memory, "Be direct.", SoulModificationMode::Notify)
+        .await
+        .expect("seed");
+
+    let change = sync_soul(&memory, "Be funny.", SoulModificationMode::Notify)
+        .await
+        .expect("sync");
+    let Some(SoulChange::Activated { previous, current }) = change else {
+        panic!("expected activation, got {change:?}");
+    };
+    assert_eq!(previous.content, "Be direct.");
+    assert_eq!(current.content, "Be funny.");
+
+    let active = active_soul(memory.pool())
+        .await
+        .expect("load")
+        .expect("active");
+    assert_eq!(active.content, "Be funny.");
+}
+
+#[tokio::test]
+async fn approve_mode_keeps_active_until_approved() {
+    let memory = setup_memory().await;
+    sync_soul(&memory, "Be direct.", SoulModificationMode::Approve)
+        .await
+        .expect("seed");
+
+    let change = sync_soul(&memory, "Be funny.", SoulModificationMode::Approve)
+        .await
+        .expect("sync");
+    let Some(SoulChange::Proposed { pending, .. }) = change else {
+        panic!("expected proposal, got {change:?}");
+    };
+    let active = active_soul(memory.pool())
+        .await
+        .expect("load")
+        .expect("active");
+    assert_eq!(active.content, "Be direct.");
+
+    // The unchanged file is not proposed again on the next tick.
+    let again = sync_soul(&memory, "Be funny.", SoulModificationMode::Approve)
+        .await
+        .expect("sync");
+    assert!(again.is_none());
+
+    assert!(approve(&memory, pending.id).await.expect("approve"));
+    let active = active_soul(memory.pool())
+        .await
+        .expect("load")
+        .expect("active");
+    assert_eq!(active.content, "Be funny.");
+    assert!(!approve(&memory, pending.id).await.expect("second approve"));
+}
+
+#[tokio::test]
+async fn reject_restores_active_soul_in_agent_toml() {
+    let memory = setup_memory().await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_agent_toml(dir.path(), "Be funny.\n");
+
+    sync_soul(&memory, "Be direct.\n", SoulModificationMode::Approve)
+        .await
+        .expect("seed");
+    let Some(SoulChange::Proposed { pending, .. }) =
+        sync_soul(&memory, "Be funny.\n", SoulModificationMode::Approve)
+            .await
+            .expect("sync")
+    else {
+        panic!("expected proposal");
+    };
+
+    assert!(reject(&memory, pending.id, &path).await.expect("reject"));
+    let config = wintermute::config::load_agent_config(&path).expect("reparse");
+    assert_eq!(config.personality.soul, "Be direct.\n");
+    assert!(config.heartbeat.enabled);
+
+    let versions = history(memory.pool(), 10).await.expect("history");
+    assert_eq!(versions[0].status, SoulStatus::Rejected);
+}
+
+#[tokio::test]
+async fn revert_records_new_version_with_old_content() {
+    let memory = setup_memory().await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_agent_toml(dir.path(), "v2\n");
+
+    sync_soul(&memory, "v1\n", SoulModificationMode::Notify)
+        .await
+        .expect("seed");
+    sync_soul(&memory, "v2\n", SoulModificationMode::Notify)
+        .await
+        .expect("change");
+
+    let (previous, current) = revert(&memory, 1, &path)
+        .await
+        .expect("revert")
+        .expect("version exists");
+    assert_eq!(previous.map(|p| p.content).as_deref(), Some("v2\n"));
+    assert_eq!(current.id, 3);
+    assert_eq!(current.content, "v1\n");
+
+    let config = wintermute::config::load_agent_config(&path).expect("reparse");
+    assert_eq!(config.personality.soul, "v1\n");
+    assert!(revert(&memory, 99, &path).await.expect("revert").is_none());
+}
+
+#[tokio::test]
+async fn check_soul_sends_diff_with_buttons_in_approve_mode() {
+    let memory = setup_memory().await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_agent_toml(dir.path(), "Be direct.\n");
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+
+    check_soul(&memory, &path, SoulModificationMode::Approve, &tx, 42)
+        .await
+        .expect("seed check");
+    assert!(rx.try_recv().is_err(), "seeding is silent");
+
+    write_agent_toml(dir.path(), "Be <funny>.\n");
+    check_soul(&memory, &path, SoulModificationMode::Approve, &tx, 42)
+        .await
+        .expect("check");
+    let msg = rx.try_recv().expect("notification");
+    assert_eq!(msg.user_id, 42);
+    assert_eq!(msg.keyboard, Some(Keyboard::Soul(2)));
+    let text = msg.text.expect("text");
+    assert!(text.contains("- Be direct."));
+    assert!(text.contains("+ Be &lt;funny&gt;."));
+}
+
+#[test]
+fn write_soul_keeps_other_settings_and_comments() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_agent_toml(dir.path(), "old\n");
+    write_soul(&path, "new\nsoul\n").expect("write");
+    let contents = std::fs::read_to_string(&path).expect("read");
+    assert!(contents.starts_with("# agent-owned"));
+    let config = wintermute::config::load_agent_config(&path).expect("reparse");
+    assert_eq!(config.personality.soul, "new\nsoul\n");
+    assert_eq!(config.personality.name, "Wintermute");
+}
+
+#[test]
+fn diff_marks_added_and_removed_lines() {
+    let diff = soul_diff("a\nb\nc", "a\nx\nc\nd");
+    assert_eq!(diff, "  a\n- b\n+ x\n  c\n+ d");
+}
+
+#[test]
+fn callback_data_round_trips() {
+    assert_eq!(
+        parse_soul_callback(&soul_callback_data(true, 7)),
+        Some((true, 7))
+    );
+    assert_eq!(
+        parse_soul_callback(&soul_callback_data(false, 7)),
+        Some((false, 7))
+    );
+    assert_eq!(parse_soul_callback("sv+:x"), None);
+    assert_eq!(parse_soul_callback("sk+:7"), None);
+}
+
+#[tokio::test]
+async fn soul_command_lists_history_and_guards_revert() {
+    let memory = setup_memory().await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_agent_toml(dir.path(), "v1\n");
+
+    let empty = handle_soul(&memory, &path, "history", true).await;
+    assert!(empty.contains("No soul versions"));
+
+    sync_soul(&memory, "v1\n", SoulModificationMode::Notify)
+        .await
+        .expect("seed");
+    let listed = handle_soul(&memory, &path, "", true).await;
+    assert!(listed.contains("v1 [active]"));
+
+    let denied = handle_soul(&memory, &path, "revert 1", false).await;
+    assert!(denied.contains("Only the owner"));
+    let usage = handle_soul(&memory, &path, "revert abc", true).await;
+    assert!(usage.starts_with("Usage"));
+    let reverted = handle_soul(&memory, &path, "revert v1", true).await;
+    assert!(reverted.contains("Soul reverted to v1"));
+}
@@ -4,9 +4,10 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use wintermute::config::{
-    all_model_specs, config_dir, runtime_paths, AgentConfig, BrowserConfig, BudgetConfig, Config,
-    EgressConfig, HeartbeatConfig, LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig,
-    PromotionMode, SandboxConfig, SoulModificationMode,
+    all_model_specs, config_dir, runtime_paths, validate_scheduled_tasks, AdminApiConfig,
+    AgentConfig, BrowserConfig, BudgetConfig, Config, EgressConfig, EmailConfig, HeartbeatConfig,
+    LearningConfig, ModelsConfig, PersonalityConfig, PrivacyConfig, PromotionMode, SandboxConfig,
+    SoulModificationMode, TelemetryConfig, ToolHealthConfig,
 };
 
 // ---------------------------------------------------------------------------
@@ -20,6 +21,10 @@ fn default_budget_values() {
     assert_eq!(budget.max_tokens_per_day, 10_000_000);
     assert_eq!(budget.max_tool_calls_per_turn, 20);
     assert_eq!(budget.max_dynamic_tools_per_turn, 20);
+    assert!(budget.degradation.enabled);
+    assert_eq!(budget.degradation.cheap_model_at, 80);
+    assert_eq!(budget.degradation.pause_background_at, 85);
+    assert_eq!(budget.degradation.shorten_context_at, 90);
 }
 
 #[test]
@@ -54,6 +59,7 @@ fn default_heartbeat_values() {
     assert!(!heartbeat.proactive);
     assert_eq!(heartbeat.proactive_interval_mins, 30);
     assert_eq!(heartbeat.proactive_budget, 5000);
+    assert_eq!(heartbeat.feed_poll_mins, 30);
 }
 
 #[test]
@@ -94,6 +100,41 @@ fn default_browser_values() {
     assert!(!browser.auto_submit);
     assert!(browser.standalone_fallback);
     assert!(!browser.image.is_empty());
+    assert!(browser.user_agent.is_none());
+    assert!(!browser.stealth);
+    assert_eq!(browser.max_sessions, 4);
+    assert_eq!(browser.session_idle_mins, 30);
+}
+
+#[test]
+fn default_admin_api_values() {
+    let admin = AdminApiConfig::default();
+    assert!(!admin.enabled);
+    assert_eq!(admin.bind, "127.0.0.1:8787");
+    assert_eq!(admin.token_env, "WINTERMUTE_ADMIN_TOKEN");
+}
+
+#[test]
+fn default_telemetry_values() {
+    let telemetry = TelemetryConfig::default();
+    assert!(telemetry.otlp_endpoint.is_none());
+    assert_eq!(telemetry.service_name, "wintermute");
+}
+
+#[test]
+fn default_tool_health_values() {
+    let health = ToolHealthConfig::default();
+    assert!(health.auto_disable);
+    assert!((health.min_success_rate - 0.5).abs() < f64::EPSILON);
+    assert_eq!(health.window, 10);
+}
+
+#[test]
+fn default_email_values() {
+    let email = EmailConfig::default();
+    assert!(!email.enabled);
+    assert_eq!(email.imap_port, 993);
+    assert_eq!(email.smtp_port, 465);
 }
 
 // ---------------------------------------------------------------------------
@@ -168,6 +209,32 @@ runtime = "runsc"
     assert_eq!(config.sandbox.runtime, Some("runsc".to_owned()));
 }
 
+#[test]
+fn parse_config_with_mcp_servers() {
+    let toml_str = r#"
+[models]
+default = "ollama/qwen3:8b"
+
+[channels.telegram]
+bot_token_env = "TOK"
+allowed_users = [1]
+
+[[mcp_servers]]
+name = "github"
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-github"]
+credentials = ["GITHUB_PERSONAL_ACCESS_TOKEN"]
+"#;
+    let config: Config = toml::from_str(toml_str).expect("config with MCP servers should parse");
+    assert_eq!(config.mcp_servers.len(), 1);
+    let server = &config.mcp_servers[0];
+    assert_eq!(server.name, "github");
+    assert_eq!(server.args.len(), 2);
+    assert!(server.enabled);
+    assert!(server.auto_approve.is_empty());
+    assert_eq!(server.timeout_secs, 60);
+}
+
 #[test]
 fn parse_agent_config_with_defaults() {
     let toml_str = r#"
@@ -270,6 +337,100 @@ builtin = "backup"
     assert!(agent.scheduled_tasks[0].enabled);
 }
 
+#[test]
+fn scheduled_task_depends_on_parses() {
+    let toml_str = r#"
+[[scheduled_tasks]]
+name = "daily_backup"
+cron = "0 0 3 * * *"
+builtin = "backup"
+
+[[scheduled_tasks]]
+name = "daily_digest"
+cron = "0 0 4 * * *"
+builtin = "digest"
+depends_on = ["daily_backup"]
+"#;
+    let agent: AgentConfig = toml::from_str(toml_str).expect("scheduled tasks should parse");
+    assert!(agent.scheduled_tasks[0].depends_on.is_empty());
+    assert_eq!(agent.scheduled_tasks[1].depends_on, vec!["daily_backup"]);
+    assert!(validate_scheduled_tasks(&agent.scheduled_tasks).is_ok());
+}
+
+#[test]
+fn scheduled_task_cycle_is_rejected() {
+    let toml_str = r#"
+[[scheduled_tasks]]
+name = "a"
+cron = "0 0 3 * * *"
+builtin = "backup"
+depends_on = ["c"]
+
+[[scheduled_tasks]]
+name = "b"
+cron = "0 0 3 * * *"
+builtin = "backup"
+depends_on = ["a"]
+
+[[scheduled_tasks]]
+name = "c"
+cron = "0 0 3 * * *"
+builtin = "backup"
+depends_on = ["b"]
+"#;
+    let agent: AgentConfig = toml::from_str(toml_str).expect("scheduled tasks should parse");
+    let err = validate_scheduled_tasks(&agent.scheduled_tasks).expect_err("cycle should fail");
+    assert!(err.to_string().contains("cycle"), "got: {err}");
+}
+
+#[test]
+fn scheduled_task_self_and_unknown_dependencies_are_rejected() {
+    let self_dep = r#"
+[[scheduled_tasks]]
+name = "a"
+cron = "0 0 3 * * *"
+builtin = "backup"
+depends_on = ["a"]
+"#;
+    let agent: AgentConfig = toml::from_str(self_dep).expect("scheduled task should parse");
+    assert!(validate_scheduled_tasks(&agent.scheduled_tasks).is_err());
+
+    let unknown = r#"
+[[scheduled_tasks]]
+name = "a"
+cron = "0 0 3 * * *"
+builtin = "backup"
+depends_on = ["missing"]
+"#;
+    let agent: AgentConfig = toml::from_str(unknown).expect("scheduled task should parse");
+    let err = validate_scheduled_tasks(&agent.scheduled_tasks).expect_err("unknown should fail");
+    assert!(err.to_string().contains("missing"), "got: {err}");
+}
+
+#[test]
+fn scheduled_task_depending_on_disabled_task_is_rejected() {
+    let toml_str = r#"
+[[scheduled_tasks]]
+name = "daily_backup"
+cron = "0 0 3 * * *"
+builtin = "backup"
+enabled = false
+
+[[scheduled_tasks]]
+name = "daily_digest"
+cron = "0 0 4 * * *"
+builtin = "digest"
+depends_on = ["daily_backup"]
+"#;
+    let mut agent: AgentConfig = toml::from_str(toml_str).expect("scheduled tasks should parse");
+    let err = validate_scheduled_tasks(&agent.scheduled_tasks).expect_err("disabled should fail");
+    assert!(err.to_string().contains("disabled"), "got: {err}");
+
+    // A disabled dependent is harmless.
+    agent.scheduled_tasks[1].enabled = false;
+    assert!(validate_scheduled_tasks(&agent.scheduled_tasks).is_ok());
+}
+
 // ---------------------------------------------------------------------------
 // all_model_specs
 // ---------------------------------------------------------------------------
@@ -280,6 +441,7 @@ fn all_model_specs_deduplicates() {
         default: "ollama/qwen3:8b".to_owned(),
         roles: HashMap::from([("observer".to_owned(), "ollama/qwen3:8b".to_owned())]),
         skills: HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let specs = all_model_specs(&models);
     assert_eq!(specs.len(), 1);
@@ -292,6 +454,7 @@ fn all_model_specs_preserves_order() {
         default: "ollama/qwen3:8b".to_owned(),
         roles: HashMap::from([("observer".to_owned(), "anthropic/claude-haiku".to_owned())]),
         skills: HashMap::from([("deploy".to_owned(), "anthropic/claude-sonnet".to_owned())]),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let specs = all_model_specs(&models);
     assert_eq!(specs[0], "ollama/qwen3:8b");
@@ -394,3 +557,89 @@ fn runtime_paths_has_agents_md_and_docs_dir() {
     assert!(paths.agents_md.ends_with("AGENTS.md"));
     assert!(paths.docs_dir.ends_with("docs"));
 }
+
+#[test]
+fn invalid_redact_pattern_is_rejected() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("config.toml");
+    std::fs::write(
+        &path,
+        r#"
+[models]
+default = "anthropic/claude-sonnet-4-5-20250929"
+
+[channels.telegram]
+bot_token_env = "WINTERMUTE_TELEGRAM_TOKEN"
+allowed_users = [123456789]
+
+[privacy]
+redact_patterns = ["(unclosed"]
+"#,
+    )
+    .expect("write config");
+    let err = wintermute::config::load_config(&path).expect_err("bad regex");
+    assert!(err.to_string().contains("redact_patterns"));
+}
+
+#[test]
+fn degradation_section_parses_and_validates_fraction() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("config.toml");
+    let base = r#"
+[models]
+default = "anthropic/claude-sonnet-4-5-20250929"
+
+[channels.telegram]
+bot_token_env = "WINTERMUTE_TELEGRAM_TOKEN"
+allowed_users = [123456789]
+
+[budget.degradation]
+cheap_model_at = 60
+"#;
+    std::fs::write(&path, format!("{base}shortened_context_fraction = 0.25\n")).expect("write");
+    let config = wintermute::config::load_config(&path).expect("valid config");
+    assert_eq!(config.budget.degradation.cheap_model_at, 60);
+    assert_eq!(config.budget.degradation.pause_background_at, 85);
+    assert!((config.budget.degradation.shortened_context_fraction - 0.25).abs() < f64::EPSILON);
+
+    std::fs::write(&path, format!("{base}shortened_context_fraction = 0.0\n")).expect("write");
+    let err = wintermute::config::load_config(&path).expect_err("zero fraction");
+    assert!(err.to_string().contains("shortened_context_fraction"));
+}
+
+#[test]
+fn budget_rollover_settings_parse_and_validate() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("config.toml");
+    let base = r#"
+[models]
+default = "anthropic/claude-sonnet-4-5-20250929"
+
+[channels.telegram]
+bot_token_env = "WINTERMUTE_TELEGRAM_TOKEN"
+allowed_users = [123456789]
+
+[budget]
+"#;
+    std::fs::write(&path, base).expect("write");
+    let config = wintermute::config::load_config(&path).expect("valid config");
+    assert_eq!(config.budget.timezone, "local");
+    assert_eq!(config.budget.carry_over_percent, 0);
+
+    std::fs::write(
+        &path,
+        format!("{base}timezone = \"+08:00\"\ncarry_over_percent = 25\n"),
+    )
+    .expect("write");
+    let config = wintermute::config::load_config(&path).expect("valid config");
+    assert_eq!(config.budget.timezone, "+08:00");
+    assert_eq!(config.budget.carry_over_percent, 25);
+
+    std::fs::write(&path, format!("{base}timezone = \"Mars/Olympus\"\n")).expect("write");
+    let err = wintermute::config::load_config(&path).expect_err("bad timezone");
+    assert!(err.to_string().contains("budget.timezone"));
+
+    std::fs::write(&path, format!("{base}carry_over_percent = 150\n")).expect("write");
+    let err = wintermute::config::load_config(&path).expect_err("too much carry-over");
+    assert!(err.to_string().contains("carry_over_percent"));
+}
@@ -0,0 +1,4 @@
+//! Integration tests for `src/doctor.rs`.
+
+#[path = "doctor/doctor_test.rs"]
+mod doctor_test;
@@ -0,0 +1,186 @@
+//! Tests for `src/doctor.rs` — installation checks and report rendering.
+
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode};
+use sqlx::{ConnectOptions, Connection};
+
+use wintermute::doctor::{
+    check_memory_db, journal_check, migration_check, probe_provider, Check, CheckStatus, Report,
+    WAL_WARN_BYTES,
+};
+use wintermute::providers::{
+    CompletionRequest, CompletionResponse, ContentPart, LlmProvider, ProviderError, StopReason,
+    UsageStats,
+};
+
+/// Succeeds or fails every call, depending on `ok`.
+struct FixedProvider {
+    ok: bool,
+}
+
+#[async_trait]
+impl LlmProvider for FixedProvider {
+    async fn complete(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        assert_eq!(request.max_tokens, Some(1));
+        if !self.ok {
+            return Err(ProviderError::HttpStatus {
+                status: 401,
+                body: "invalid x-api-key".to_owned(),
+            });
+        }
+        Ok(CompletionResponse {
+            content: vec![ContentPart::Text {
+                text: "p".to_owned(),
+            }],
+            stop_reason: StopReason::MaxTokens,
+            usage: UsageStats {
+                input_tokens: 1,
+                output_tokens: 1,
+            },
+            model: "mock".to_owned(),
+        })
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        false
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn model_id(&self) -> &str {
+        "mock/fixed"
+    }
+}
+
+#[test]
+fn report_renders_plain_lines_and_summary() {
+    let mut report = Report::new();
+    report.push(Check::pass("config.toml", "loaded"));
+    report.push(Check::warn("docker", "daemon unreachable"));
+    report.push(Check::fail("telegram token", "Unauthorized"));
+
+    let plain = report.render(false);
+    assert!(plain.contains("[PASS] config.toml     loaded"));
+    assert!(plain.contains("[WARN] docker          daemon unreachable"));
+    assert!(plain.contains("[FAIL] telegram token  Unauthorized"));
+    assert!(plain.contains("1 passed, 1 warnings, 1 failed"));
+    assert!(!plain.contains('\x1b'));
+    assert!(report.has_failures());
+
+    let colored = report.render(true);
+    assert!(colored.contains("\x1b[31mFAIL\x1b[0m"));
+}
+
+#[test]
+fn warnings_alone_are_not_failures() {
+    let mut report = Report::new();
+    report.extend([Check::pass("a", ""), Check::warn("b", "")]);
+    assert!(!report.has_failures());
+    assert_eq!(report.count(CheckStatus::Warn), 1);
+}
+
+#[test]
+fn journal_check_flags_non_wal_and_large_wal() {
+    assert_eq!(journal_check("wal", None).status, CheckStatus::Pass);
+    assert_eq!(journal_check("WAL", Some(4096)).status, CheckStatus::Pass);
+    assert_eq!(journal_check("delete", None).status, CheckStatus::Warn);
+    let large = journal_check("wal", Some(WAL_WARN_BYTES.saturating_add(1)));
+    assert_eq!(large.status, CheckStatus::Warn);
+}
+
+#[test]
+fn migration_check_lists_missing_migrations() {
+    let applied = vec!["001_schema.sql".to_owned(), "002_memory.sql".to_owned()];
+    let ok = migration_check(&applied, &["001_schema.sql", "002_memory.sql"]);
+    assert_eq!(ok.status, CheckStatus::Pass);
+
+    let missing = migration_check(&applied, &["001_schema.sql", "003_sessions.sql"]);
+    assert_eq!(missing.status, CheckStatus::Warn);
+    assert!(missing.detail.contains("003_sessions.sql"));
+    assert!(!missing.detail.contains("001_schema.sql"));
+}
+
+#[tokio::test]
+async fn memory_db_checks_a_healthy_database() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("memory.db");
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&path)
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .connect()
+        .await
+        .expect("create db");
+    sqlx::raw_sql(include_str!("../../migrations/001_schema.sql"))
+        .execute(&mut conn)
+        .await
+        .expect("schema");
+    sqlx::query("INSERT INTO migrations(name) VALUES ('001_schema.sql')")
+        .execute(&mut conn)
+        .await
+        .expect("marker");
+    conn.close().await.expect("close");
+
+    let checks = check_memory_db(&path, &["001_schema.sql"]).await;
+    assert_eq!(checks.len(), 3);
+    assert!(
+        checks.iter().all(|c| c.status == CheckStatus::Pass),
+        "{checks:?}"
+    );
+
+    let checks = check_memory_db(&path, &["001_schema.sql", "002_memory.sql"]).await;
+    assert_eq!(checks[2].status, CheckStatus::Warn);
+}
+
+#[tokio::test]
+async fn missing_memory_db_is_a_warning() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let checks = check_memory_db(&dir.path().join("memory.db"), &[]).await;
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0].status, CheckStatus::Warn);
+    assert!(!dir.path().join("memory.db").exists());
+}
+
+#[tokio::test]
+async fn probe_reports_provider_errors() {
+    let ok = probe_provider("provider a/b".to_owned(), &FixedProvider { ok: true }).await;
+    assert_eq!(ok.status, CheckStatus::Pass);
+
+    let failed = probe_provider("provider a/b".to_owned(), &FixedProvider { ok: false }).await;
+    assert_eq!(failed.status, CheckStatus::Fail);
+    assert!(failed.detail.contains("401"));
+}
+
+#[test]
+fn anthropic_auth_check_warns_on_expired_oauth_without_refreshing() {
+    use wintermute::credentials::AnthropicAuth;
+    use wintermute::doctor::check_anthropic_auth;
+
+    assert_eq!(check_anthropic_auth(None), None);
+    assert_eq!(
+        check_anthropic_auth(Some(&AnthropicAuth::ApiKey("sk-ant".to_owned()))),
+        None
+    );
+
+    let expired = AnthropicAuth::OAuth {
+        access_token: "tok".to_owned(),
+        refresh_token: Some("refresh".to_owned()),
+        expires_at: Some(0),
+    };
+    let check = check_anthropic_auth(Some(&expired)).expect("oauth is checked");
+    assert_eq!(check.status, CheckStatus::Warn);
+    assert!(check.detail.contains("refreshes it"));
+
+    let valid = AnthropicAuth::OAuth {
+        access_token: "tok".to_owned(),
+        refresh_token: None,
+        expires_at: Some(i64::MAX / 2),
+    };
+    let check = check_anthropic_auth(Some(&valid)).expect("oauth is checked");
+    assert_eq!(check.status, CheckStatus::Pass);
+}
@@ -6,6 +6,8 @@ mod direct_policy_test;
 mod docker_invariants_test;
 #[path = "executor/egress_test.rs"]
 mod egress_test;
+#[path = "executor/exec_restrictions_test.rs"]
+mod exec_restrictions_test;
 #[path = "executor/exec_result_test.rs"]
 mod exec_result_test;
 #[path = "executor/health_status_test.rs"]
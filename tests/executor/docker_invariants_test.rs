@@ -24,7 +24,7 @@ fn docker_container_network_uses_proxy_or_none() {
 #[test]
 fn docker_container_env_sets_proxy_vars() {
     let source = docker_source();
-    // Container env sets proxy variables for egress routing.
+    // Each exec gets proxy variables carrying its own credential.
     assert!(source.contains("HTTP_PROXY="));
     assert!(source.contains("HTTPS_PROXY="));
     assert!(source.contains("http_proxy="));
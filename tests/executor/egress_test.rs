@@ -1,6 +1,25 @@
 //! Tests for `src/executor/egress.rs` — egress proxy configuration generation.
 
-use wintermute::executor::egress::generate_squid_config;
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use wintermute::executor::egress::{generate_grants_config, generate_squid_config, ToolGrant};
+
+fn tool_grants() -> BTreeMap<String, ToolGrant> {
+    let mut grants = BTreeMap::new();
+    grants.insert(
+        "fetcher".to_owned(),
+        ToolGrant {
+            token: "fetchertoken".to_owned(),
+            domains: vec!["api.example.com".to_owned()],
+        },
+    );
+    grants
+}
+
+fn credential(user: &str, token: &str) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("{user}:{token}"))
+}
 
 // ---------------------------------------------------------------------------
 // Config generation tests
@@ -52,7 +71,74 @@ fn generate_squid_config_denies_all_by_default() {
 fn generate_squid_config_allows_connect_for_https() {
     let config = generate_squid_config(&[]);
     assert!(config.contains("acl CONNECT method CONNECT"));
-    assert!(config.contains("http_access allow CONNECT wintermute_allowed SSL_ports"));
+    let grants = generate_grants_config("sandboxtoken", &BTreeMap::new());
+    assert!(grants
+        .contains("http_access allow CONNECT wintermute_sandbox wintermute_allowed SSL_ports"));
+}
+
+#[test]
+fn generate_squid_config_includes_grants_before_deny() {
+    let config = generate_squid_config(&[]);
+    let include = config
+        .find("include /etc/squid/grants.conf")
+        .expect("grants include");
+    let deny = config.find("http_access deny all").expect("deny all");
+    assert!(include < deny);
+}
+
+// ---------------------------------------------------------------------------
+// Grant tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn requests_without_a_credential_match_no_allow_rule() {
+    // A tool denied network gets no credential; every allow rule must
+    // require one, so it falls through to `deny all`.
+    let config = generate_squid_config(&["example.com".to_owned()]);
+    let grants = generate_grants_config("sandboxtoken", &tool_grants());
+
+    let allow_rules: Vec<&str> = config
+        .lines()
+        .chain(grants.lines())
+        .filter(|line| line.starts_with("http_access allow"))
+        .collect();
+    assert!(!allow_rules.is_empty());
+    for rule in allow_rules {
+        assert!(
+            rule.contains("wintermute_sandbox") || rule.contains("wintermute_tool_"),
+            "allow rule without a credential ACL: {rule}"
+        );
+    }
+}
+
+#[test]
+fn credentials_match_the_exact_proxy_authorization_header() {
+    let grants = generate_grants_config("sandboxtoken", &tool_grants());
+    let sandbox = credential("sandbox", "sandboxtoken").replace('+', "\\+");
+    let tool = credential("tool", "fetchertoken").replace('+', "\\+");
+
+    assert!(grants.contains(&format!(
+        "acl wintermute_sandbox req_header Proxy-Authorization ^Basic.{sandbox}$"
+    )));
+    assert!(grants.contains(&format!(
+        "acl wintermute_tool_0 req_header Proxy-Authorization ^Basic.{tool}$"
+    )));
+}
+
+#[test]
+fn tool_credential_is_limited_to_its_declared_domains() {
+    let grants = generate_grants_config("sandboxtoken", &tool_grants());
+
+    assert!(grants.contains("acl wintermute_tool_0_domains dstdomain .api.example.com\n"));
+    let tool_rules: Vec<&str> = grants
+        .lines()
+        .filter(|line| line.starts_with("http_access allow") && line.contains("wintermute_tool_0"))
+        .collect();
+    assert_eq!(tool_rules.len(), 2);
+    for rule in tool_rules {
+        assert!(rule.contains("wintermute_tool_0_domains"), "{rule}");
+        assert!(rule.contains("wintermute_allowed"), "{rule}");
+    }
 }
 
 #[test]
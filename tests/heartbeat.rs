@@ -2,10 +2,18 @@
 
 #[path = "heartbeat/backup_test.rs"]
 mod backup_test;
+#[path = "heartbeat/budget_notice_test.rs"]
+mod budget_notice_test;
 #[path = "heartbeat/digest_test.rs"]
 mod digest_test;
+#[path = "heartbeat/feeds_test.rs"]
+mod feeds_test;
 #[path = "heartbeat/health_test.rs"]
 mod health_test;
+#[path = "heartbeat/memory_review_test.rs"]
+mod memory_review_test;
+#[path = "heartbeat/outbound_delivery_test.rs"]
+mod outbound_delivery_test;
 #[path = "heartbeat/proactive_test.rs"]
 mod proactive_test;
 #[path = "heartbeat/scheduler_test.rs"]
@@ -0,0 +1,103 @@
+//! Tests for `src/heartbeat/budget_notice.rs` — the morning budget reset notice.
+
+use chrono::{NaiveDate, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use wintermute::agent::budget::{BudgetTimezone, DailyBudget};
+use wintermute::heartbeat::budget_notice::{format_reset_notice, reset_notice};
+use wintermute::memory::usage::UsageTotals;
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+    sqlx::raw_sql(include_str!("../../migrations/016_usage_ledger.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    pool
+}
+
+async fn insert_usage(pool: &SqlitePool, model: &str, tokens: i64, success: bool, when: &str) {
+    sqlx::query(
+        "INSERT INTO usage_ledger (model, input_tokens, output_tokens, latency_ms, success, created_at) \
+         VALUES (?1, ?2, 0, 10, ?3, datetime('now', 'start of day', ?4))",
+    )
+    .bind(model)
+    .bind(tokens)
+    .bind(success)
+    .bind(when)
+    .execute(pool)
+    .await
+    .expect("insert should succeed");
+}
+
+fn totals(key: &str, calls: u64, failures: u64, tokens: u64) -> UsageTotals {
+    UsageTotals {
+        key: key.to_owned(),
+        calls,
+        failures,
+        input_tokens: tokens,
+        output_tokens: 0,
+        avg_latency_ms: 0,
+    }
+}
+
+#[test]
+fn notice_reports_usage_and_carry_over() {
+    let day = NaiveDate::from_ymd_opt(2026, 3, 14).expect("date");
+    let text = format_reset_notice(
+        day,
+        &totals("", 12, 2, 250_000),
+        Some(&totals("ollama/<q>", 10, 0, 200_000)),
+        1_000_000,
+        75_000,
+    );
+    assert!(text.starts_with("<b>Budget reset</b> — 2026-03-14"));
+    assert!(text.contains("Yesterday: 250000 of 1000000 tokens (25%) in 12 calls, 2 failed"));
+    assert!(text.contains("<code>ollama/&lt;q&gt;</code> (200000 tokens)"));
+    assert!(text.contains("Today: 1075000 tokens (75000 carried over)"));
+}
+
+#[test]
+fn notice_without_carry_over_shows_plain_limit() {
+    let day = NaiveDate::from_ymd_opt(2026, 3, 14).expect("date");
+    let text = format_reset_notice(day, &totals("", 1, 0, 10), None, 100, 0);
+    assert!(text.contains("in 1 calls\nToday: 100 tokens"));
+    assert!(!text.contains("failed"));
+    assert!(!text.contains("Top model"));
+}
+
+#[tokio::test]
+async fn reset_notice_summarises_yesterday_only() {
+    let pool = setup_pool().await;
+    insert_usage(&pool, "anthropic/a", 300, true, "-12 hours").await;
+    insert_usage(&pool, "ollama/b", 100, false, "-6 hours").await;
+    // Today's and older usage is excluded.
+    insert_usage(&pool, "ollama/b", 5_000, true, "+1 minute").await;
+    insert_usage(&pool, "ollama/b", 7_000, true, "-2 days").await;
+
+    let budget = DailyBudget::with_rollover(1_000, BudgetTimezone::Utc, 0);
+    let notice = reset_notice(&pool, &budget, Utc::now())
+        .await
+        .expect("notice should build")
+        .expect("yesterday had usage");
+    assert!(notice.contains("Yesterday: 400 of 1000 tokens (40%) in 2 calls, 1 failed"));
+    assert!(notice.contains("<code>anthropic/a</code> (300 tokens)"));
+    assert!(notice.contains("Today: 1000 tokens"));
+}
+
+#[tokio::test]
+async fn reset_notice_skips_idle_days() {
+    let pool = setup_pool().await;
+    let budget = DailyBudget::new(1_000);
+    let notice = reset_notice(&pool, &budget, Utc::now())
+        .await
+        .expect("notice should build");
+    assert!(notice.is_none());
+}
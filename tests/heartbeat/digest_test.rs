@@ -110,6 +110,7 @@ fn scheduler_recognizes_digest_builtin() {
         budget_tokens: None,
         notify: true,
         enabled: true,
+        depends_on: vec![],
     };
     // The task can be constructed and enabled — this validates the config shape.
     // Full execution requires HeartbeatDeps which can't be constructed in a unit test.
@@ -0,0 +1,242 @@
+//! Tests for `src/heartbeat/feeds.rs` — high-priority alerts and the feed digest.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use tokio::sync::mpsc;
+use wintermute::heartbeat::feeds::{execute_feed_digest, format_digest, notify_high_priority};
+use wintermute::memory::MemoryEngine;
+use wintermute::tools::rss::{
+    pending_entries, record_entries, subscribe, FeedEntry, FeedPriority, ParsedEntry,
+};
+
+async fn setup_memory() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+    sqlx::raw_sql(include_str!("../../migrations/010_feeds.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("memory engine should start")
+}
+
+fn parsed(guid: &str, title: &str) -> ParsedEntry {
+    ParsedEntry {
+        guid: guid.to_owned(),
+        title: title.to_owned(),
+        link: Some(format!("https://example.com/{guid}")),
+        summary: None,
+        published: None,
+    }
+}
+
+fn entry(feed_id: i64, feed_title: &str, title: &str) -> FeedEntry {
+    FeedEntry {
+        id: 1,
+        feed_id,
+        feed_title: feed_title.to_owned(),
+        title: title.to_owned(),
+        link: None,
+        summary: None,
+        published: None,
+    }
+}
+
+#[test]
+fn digest_groups_by_feed_and_escapes() {
+    let (text, included) = format_digest(
+        &[
+            entry(1, "News", "A < B"),
+            entry(1, "News", "Second"),
+            entry(2, "Blog", "Post"),
+        ],
+        4096,
+    );
+    assert_eq!(included, 3);
+    assert!(text.starts_with("<b>Feed digest</b> — 3 new item(s)"));
+    assert_eq!(text.matches("<b>News</b>").count(), 1);
+    assert!(text.contains("<b>Blog</b>"));
+    assert!(text.contains("A &lt; B"));
+}
+
+#[tokio::test]
+async fn digest_sends_normal_items_once() {
+    let memory = setup_memory().await;
+    let id = subscribe(
+        &memory,
+        "https://example.com/feed",
+        Some("News"),
+        FeedPriority::Normal,
+    )
+    .await
+    .expect("subscribe");
+    record_entries(
+        &memory,
+        id,
+        &[parsed("a", "Alpha"), parsed("b", "Beta")],
+        false,
+    )
+    .await
+    .expect("record");
+
+    let (tx, mut rx) = mpsc::channel(4);
+    let summary = execute_feed_digest(&memory, &tx, 42, None)
+        .await
+        .expect("digest");
+    assert_eq!(summary, "feed digest: sent 2 item(s)");
+
+    let msg = rx.try_recv().expect("digest message");
+    assert_eq!(msg.user_id, 42);
+    let text = msg.text.expect("text");
+    assert!(text.contains("<a href=\"https://example.com/a\">Alpha</a>"));
+
+    let again = execute_feed_digest(&memory, &tx, 42, None)
+        .await
+        .expect("digest");
+    assert_eq!(again, "feed digest: no new items");
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn digest_stops_at_the_length_limit() {
+    let long = "x".repeat(300);
+    let entries: Vec<FeedEntry> = (0..40).map(|_| entry(1, "News", &long)).collect();
+    let (text, included) = format_digest(&entries, 4096);
+    assert!(included > 0 && included < 40);
+    assert!(text.chars().count() <= 4096);
+    assert!(text.starts_with(&format!("<b>Feed digest</b> — {included} new item(s)")));
+    assert_eq!(text.matches('•').count(), included);
+}
+
+#[test]
+fn oversized_item_falls_back_to_a_short_title() {
+    let (text, included) = format_digest(&[entry(1, "News", &"y".repeat(5000))], 4096);
+    assert_eq!(included, 1);
+    assert!(text.chars().count() <= 4096);
+    assert!(text.contains('…'));
+}
+
+#[tokio::test]
+async fn digest_marks_only_sent_items() {
+    let memory = setup_memory().await;
+    let id = subscribe(
+        &memory,
+        "https://example.com/feed",
+        Some("News"),
+        FeedPriority::Normal,
+    )
+    .await
+    .expect("subscribe");
+    let long = "z".repeat(300);
+    let items: Vec<ParsedEntry> = (0..30).map(|i| parsed(&format!("g{i}"), &long)).collect();
+    record_entries(&memory, id, &items, false)
+        .await
+        .expect("record");
+
+    let (tx, mut rx) = mpsc::channel(4);
+    execute_feed_digest(&memory, &tx, 42, None)
+        .await
+        .expect("digest");
+    let text = rx.try_recv().expect("digest message").text.expect("text");
+    assert!(text.chars().count() <= 4096);
+    let sent = text.matches('•').count();
+    assert!(sent < 30);
+
+    let left = pending_entries(memory.pool(), FeedPriority::Normal, 100)
+        .await
+        .expect("pending");
+    assert_eq!(left.len(), 30 - sent);
+}
+
+#[tokio::test]
+async fn digest_opens_with_budget_notice() {
+    let memory = setup_memory().await;
+    let (tx, mut rx) = mpsc::channel(4);
+    let notice = Some("<b>Budget reset</b>".to_owned());
+
+    let summary = execute_feed_digest(&memory, &tx, 42, notice.clone())
+        .await
+        .expect("digest");
+    assert_eq!(summary, "feed digest: no new items, budget notice sent");
+    let text = rx.try_recv().expect("notice message").text.expect("text");
+    assert_eq!(text, "<b>Budget reset</b>");
+
+    let id = subscribe(
+        &memory,
+        "https://example.com/feed",
+        Some("News"),
+        FeedPriority::Normal,
+    )
+    .await
+    .expect("subscribe");
+    record_entries(&memory, id, &[parsed("a", "Alpha")], false)
+        .await
+        .expect("record");
+    execute_feed_digest(&memory, &tx, 42, notice)
+        .await
+        .expect("digest");
+    let text = rx.try_recv().expect("digest message").text.expect("text");
+    assert!(text.starts_with("<b>Budget reset</b>\n\n<b>Feed digest</b>"));
+}
+
+#[tokio::test]
+async fn high_priority_items_are_sent_individually() {
+    let memory = setup_memory().await;
+    let high = subscribe(
+        &memory,
+        "https://status.example.com/feed",
+        Some("Status"),
+        FeedPriority::High,
+    )
+    .await
+    .expect("subscribe high");
+    let normal = subscribe(
+        &memory,
+        "https://example.com/feed",
+        Some("News"),
+        FeedPriority::Normal,
+    )
+    .await
+    .expect("subscribe normal");
+    record_entries(
+        &memory,
+        high,
+        &[parsed("x", "Outage"), parsed("y", "Resolved")],
+        false,
+    )
+    .await
+    .expect("record high");
+    record_entries(&memory, normal, &[parsed("n", "Daily")], false)
+        .await
+        .expect("record normal");
+
+    let (tx, mut rx) = mpsc::channel(4);
+    let sent = notify_high_priority(&memory, &tx, 7).await.expect("notify");
+    assert_eq!(sent, 2);
+    assert!(rx
+        .try_recv()
+        .expect("first alert")
+        .text
+        .expect("text")
+        .contains("Outage"));
+    assert!(rx.try_recv().is_ok());
+
+    // Normal items stay for the digest.
+    assert_eq!(
+        pending_entries(memory.pool(), FeedPriority::Normal, 10)
+            .await
+            .expect("pending")
+            .len(),
+        1
+    );
+    assert_eq!(
+        notify_high_priority(&memory, &tx, 7).await.expect("notify"),
+        0
+    );
+}
@@ -1,6 +1,13 @@
 //! Tests for `src/heartbeat/health.rs` — health report serialization and file writing.
 
-use wintermute::heartbeat::health::{BudgetReport, HealthReport};
+use std::collections::BTreeMap;
+
+use wintermute::heartbeat::health::{
+    observer_health, overall_status, provider_component, provider_health, BudgetReport,
+    ComponentHealth, ComponentState, HealthReport, COMPONENT_BROWSER, COMPONENT_MEMORY,
+    COMPONENT_WHATSAPP,
+};
+use wintermute::providers::health::ProviderOutcome;
 
 #[test]
 fn health_report_serializes_to_json() {
@@ -19,6 +26,7 @@ fn health_report_serializes_to_json() {
             limit: 5_000_000,
         },
         last_error: None,
+        components: BTreeMap::new(),
     };
 
     let json = serde_json::to_string_pretty(&report).expect("should serialize");
@@ -47,6 +55,7 @@ fn health_report_with_error() {
             limit: 5_000_000,
         },
         last_error: Some("container not found".to_owned()),
+        components: BTreeMap::new(),
     };
 
     let json = serde_json::to_string(&report).expect("should serialize");
@@ -74,6 +83,7 @@ async fn write_health_file_creates_file() {
             limit: 5_000_000,
         },
         last_error: None,
+        components: BTreeMap::new(),
     };
 
     wintermute::heartbeat::health::write_health_file(&report, &path)
@@ -110,6 +120,7 @@ async fn write_health_file_is_atomic() {
                 limit: 5_000_000,
             },
             last_error: None,
+            components: BTreeMap::new(),
         };
 
         wintermute::heartbeat::health::write_health_file(&report, &path)
@@ -125,3 +136,104 @@ async fn write_health_file_is_atomic() {
     let tmp_path = path.with_extension("json.tmp");
     assert!(!tmp_path.exists(), "temp file should be cleaned up");
 }
+
+#[test]
+fn health_report_without_components_still_parses() {
+    let json = r#"{
+        "status": "running",
+        "uptime_secs": 5,
+        "last_heartbeat": "2025-01-01T00:00:00Z",
+        "executor": "Docker",
+        "container_healthy": true,
+        "active_sessions": 0,
+        "memory_db_size_mb": 0.0,
+        "scripts_count": 0,
+        "dynamic_tools_count": 0,
+        "budget_today": {"used": 0, "limit": 1},
+        "last_error": null
+    }"#;
+    let report: HealthReport = serde_json::from_str(json).expect("old format should parse");
+    assert!(report.components.is_empty());
+}
+
+#[test]
+fn components_serialize_with_state_and_error() {
+    let mut components = BTreeMap::new();
+    components.insert(COMPONENT_MEMORY.to_owned(), ComponentHealth::healthy());
+    components.insert(
+        provider_component("anthropic/claude"),
+        ComponentHealth::degraded("HTTP 529"),
+    );
+    let json = serde_json::to_value(&components).expect("should serialize");
+    assert_eq!(json["memory"]["status"], "healthy");
+    assert_eq!(json["memory"]["last_error"], serde_json::Value::Null);
+    assert_eq!(json["provider:anthropic/claude"]["status"], "degraded");
+    assert_eq!(json["provider:anthropic/claude"]["last_error"], "HTTP 529");
+}
+
+#[test]
+fn provider_health_follows_consecutive_failures() {
+    let mut outcome = ProviderOutcome::default();
+    assert_eq!(provider_health(&outcome).status, ComponentState::Healthy);
+
+    outcome.consecutive_failures = 1;
+    outcome.last_error = Some("timeout".to_owned());
+    let health = provider_health(&outcome);
+    assert_eq!(health.status, ComponentState::Degraded);
+    assert_eq!(health.last_error.as_deref(), Some("timeout"));
+
+    outcome.consecutive_failures = 3;
+    let health = provider_health(&outcome);
+    assert_eq!(health.status, ComponentState::Unavailable);
+    assert_eq!(health.metrics.get("consecutive_failures"), Some(&3));
+}
+
+#[test]
+fn observer_health_reports_queue_pressure() {
+    let idle = observer_health(0, 64, false);
+    assert_eq!(idle.status, ComponentState::Healthy);
+    assert_eq!(idle.metrics.get("queue_capacity"), Some(&64));
+
+    let backed_up = observer_health(48, 64, false);
+    assert_eq!(backed_up.status, ComponentState::Degraded);
+    assert_eq!(backed_up.metrics.get("queue_depth"), Some(&48));
+
+    assert_eq!(
+        observer_health(0, 64, true).status,
+        ComponentState::Unavailable
+    );
+}
+
+#[test]
+fn overall_status_accounts_for_components() {
+    let mut components = BTreeMap::new();
+    components.insert(COMPONENT_MEMORY.to_owned(), ComponentHealth::healthy());
+    assert_eq!(overall_status(true, None, &components), "running");
+    assert_eq!(overall_status(false, None, &components), "unhealthy");
+
+    components.insert(
+        COMPONENT_WHATSAPP.to_owned(),
+        ComponentHealth::unavailable("sidecar down"),
+    );
+    assert_eq!(overall_status(true, None, &components), "degraded");
+
+    let report = HealthReport {
+        status: "degraded".to_owned(),
+        uptime_secs: 1,
+        last_heartbeat: "2025-01-01T00:00:00Z".to_owned(),
+        executor: "Docker".to_owned(),
+        container_healthy: true,
+        active_sessions: 0,
+        memory_db_size_mb: 0.0,
+        scripts_count: 0,
+        dynamic_tools_count: 0,
+        budget_today: BudgetReport { used: 0, limit: 1 },
+        last_error: None,
+        components,
+    };
+    assert_eq!(
+        report.components_in(ComponentState::Unavailable),
+        vec![COMPONENT_WHATSAPP]
+    );
+    assert!(report.component(COMPONENT_BROWSER).is_none());
+}
@@ -0,0 +1,263 @@
+//! Tests for `src/heartbeat/memory_review.rs` — weekly contradiction review.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use tokio::sync::mpsc;
+
+use wintermute::agent::Keyboard;
+use wintermute::heartbeat::memory_review::{
+    apply_review, execute_memory_review, find_contradictions, parse_review_callback,
+    review_callback_data, ReviewAction,
+};
+use wintermute::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
+
+async fn setup_engine() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/002_memory.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("engine should initialise")
+}
+
+async fn flush() {
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+fn memory(id: i64, kind: MemoryKind, content: &str) -> Memory {
+    Memory {
+        id: Some(id),
+        kind,
+        content: content.to_owned(),
+        metadata: None,
+        status: MemoryStatus::Active,
+        source: MemorySource::Observer,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+async fn insert_memory(engine: &MemoryEngine, content: &str) -> i64 {
+    let mut mem = memory(0, MemoryKind::Fact, content);
+    mem.id = None;
+    engine.save_memory(mem).await.expect("save should succeed");
+    flush().await;
+    let row: (i64,) = sqlx::query_as("SELECT id FROM memories WHERE content = ?1")
+        .bind(content)
+        .fetch_one(engine.pool())
+        .await
+        .expect("memory should exist");
+    row.0
+}
+
+async fn status_of(engine: &MemoryEngine, id: i64) -> String {
+    let row: (String,) = sqlx::query_as("SELECT status FROM memories WHERE id = ?1")
+        .bind(id)
+        .fetch_one(engine.pool())
+        .await
+        .expect("memory should exist");
+    row.0
+}
+
+// ---------------------------------------------------------------------------
+// find_contradictions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn detects_conflicting_values_for_same_entity() {
+    let memories = vec![
+        memory(2, MemoryKind::Fact, "User lives in Paris France"),
+        memory(1, MemoryKind::Fact, "User lives in Berlin Germany"),
+    ];
+    let pairs = find_contradictions(&memories);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].older.id, Some(1));
+    assert_eq!(pairs[0].newer.id, Some(2));
+}
+
+#[test]
+fn ignores_unrelated_and_identical_memories() {
+    let memories = vec![
+        memory(1, MemoryKind::Fact, "User lives in Berlin Germany"),
+        memory(2, MemoryKind::Fact, "Prefers espresso over tea"),
+        memory(3, MemoryKind::Fact, "user lives in berlin germany"),
+    ];
+    assert!(find_contradictions(&memories).is_empty());
+}
+
+#[test]
+fn ignores_pairs_of_different_kinds() {
+    let memories = vec![
+        memory(1, MemoryKind::Fact, "User lives in Berlin Germany"),
+        memory(2, MemoryKind::Procedure, "User lives in Paris France"),
+    ];
+    assert!(find_contradictions(&memories).is_empty());
+}
+
+#[test]
+fn skips_pairs_already_reviewed() {
+    let mut reviewed = memory(1, MemoryKind::Fact, "User lives in Berlin Germany");
+    reviewed.metadata = Some(serde_json::json!({"contradiction_reviewed_with": {"2": true}}));
+    let memories = vec![
+        reviewed,
+        memory(2, MemoryKind::Fact, "User lives in Paris France"),
+    ];
+    assert!(find_contradictions(&memories).is_empty());
+}
+
+#[test]
+fn reviewed_pair_does_not_hide_new_contradictions() {
+    let mut reviewed = memory(1, MemoryKind::Fact, "User lives in Berlin Germany");
+    reviewed.metadata = Some(serde_json::json!({"contradiction_reviewed_with": {"2": true}}));
+    let memories = vec![
+        reviewed,
+        memory(2, MemoryKind::Fact, "User lives in Paris France"),
+        memory(3, MemoryKind::Fact, "User lives in Rome Italy"),
+    ];
+    let pairs = find_contradictions(&memories);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].older.id, Some(1));
+    assert_eq!(pairs[0].newer.id, Some(3));
+}
+
+// ---------------------------------------------------------------------------
+// Callback data
+// ---------------------------------------------------------------------------
+
+#[test]
+fn callback_data_roundtrips() {
+    for action in [
+        ReviewAction::KeepBoth,
+        ReviewAction::KeepOlder,
+        ReviewAction::KeepNewer,
+        ReviewAction::ArchiveBoth,
+    ] {
+        let data = review_callback_data(action, 7, 42);
+        assert!(data.len() <= 64, "callback data must fit Telegram's limit");
+        assert_eq!(parse_review_callback(&data), Some((action, 7, 42)));
+    }
+}
+
+#[test]
+fn malformed_callback_data_is_rejected() {
+    assert_eq!(parse_review_callback("mr:z:1:2"), None);
+    assert_eq!(parse_review_callback("mr:k:1"), None);
+    assert_eq!(parse_review_callback("mr:k:one:2"), None);
+    assert_eq!(parse_review_callback("a:k:1:2"), None);
+}
+
+// ---------------------------------------------------------------------------
+// apply_review
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn replace_archives_older_memory() {
+    let engine = setup_engine().await;
+    let older = insert_memory(&engine, "User lives in Berlin Germany").await;
+    let newer = insert_memory(&engine, "User lives in Paris France").await;
+
+    apply_review(&engine, ReviewAction::KeepNewer, older, newer)
+        .await
+        .expect("review should apply");
+    flush().await;
+
+    assert_eq!(status_of(&engine, older).await, "archived");
+    assert_eq!(status_of(&engine, newer).await, "active");
+}
+
+#[tokio::test]
+async fn archive_both_archives_both_memories() {
+    let engine = setup_engine().await;
+    let older = insert_memory(&engine, "User lives in Berlin Germany").await;
+    let newer = insert_memory(&engine, "User lives in Paris France").await;
+
+    apply_review(&engine, ReviewAction::ArchiveBoth, older, newer)
+        .await
+        .expect("review should apply");
+    flush().await;
+
+    assert_eq!(status_of(&engine, older).await, "archived");
+    assert_eq!(status_of(&engine, newer).await, "archived");
+}
+
+#[tokio::test]
+async fn keep_both_marks_pair_reviewed() {
+    let engine = setup_engine().await;
+    let older = insert_memory(&engine, "User lives in Berlin Germany").await;
+    let newer = insert_memory(&engine, "User lives in Paris France").await;
+
+    apply_review(&engine, ReviewAction::KeepBoth, older, newer)
+        .await
+        .expect("review should apply");
+    flush().await;
+
+    assert_eq!(status_of(&engine, older).await, "active");
+    assert_eq!(status_of(&engine, newer).await, "active");
+    let active = engine
+        .search_by_status(MemoryStatus::Active, 10)
+        .await
+        .expect("search should succeed");
+    assert!(find_contradictions(&active).is_empty());
+
+    let later = insert_memory(&engine, "User lives in Rome Italy").await;
+    let active = engine
+        .search_by_status(MemoryStatus::Active, 10)
+        .await
+        .expect("search should succeed");
+    let pairs = find_contradictions(&active);
+    assert_eq!(pairs.len(), 1, "kept memories still pair with new ones");
+    assert!(pairs[0].newer.id == Some(later) || pairs[0].older.id == Some(later));
+}
+
+// ---------------------------------------------------------------------------
+// execute_memory_review
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn review_sends_one_message_per_pair_with_keyboard() {
+    let engine = setup_engine().await;
+    let older = insert_memory(&engine, "User lives in Berlin Germany").await;
+    let newer = insert_memory(&engine, "User lives in Paris France").await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let summary = execute_memory_review(&engine, &tx, 99)
+        .await
+        .expect("review should run");
+    assert!(summary.contains("sent 1"));
+
+    let msg = rx.try_recv().expect("a review message should be sent");
+    assert_eq!(msg.user_id, 99);
+    assert_eq!(msg.keyboard, Some(Keyboard::Review(older, newer)));
+    let text = msg.text.expect("message should have text");
+    assert!(text.contains("Berlin"));
+    assert!(text.contains("Paris"));
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn review_without_conflicts_sends_nothing() {
+    let engine = setup_engine().await;
+    insert_memory(&engine, "User lives in Berlin Germany").await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let summary = execute_memory_review(&engine, &tx, 99)
+        .await
+        .expect("review should run");
+    assert!(summary.contains("no contradictions"));
+    assert!(rx.try_recv().is_err());
+}
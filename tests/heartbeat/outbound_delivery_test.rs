@@ -0,0 +1,132 @@
+//! Tests for `src/heartbeat/outbound_delivery.rs`.
+
+use chrono::{TimeZone, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+
+use wintermute::heartbeat::outbound_delivery::{
+    deliver_due_messages, notify_unread, DeliveryReport,
+};
+use wintermute::messaging::audit::log_outbound;
+use wintermute::messaging::delivery::mark_sent;
+use wintermute::messaging::outbound_queue::{enqueue, pending_count};
+use wintermute::whatsapp::client::WhatsAppClient;
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/004_briefs.sql"),
+        include_str!("../../migrations/006_contacts.sql"),
+        include_str!("../../migrations/007_outbound_queue.sql"),
+        include_str!("../../migrations/009_delivery_state.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+    pool
+}
+
+async fn insert_brief(pool: &SqlitePool, id: &str, status: &str) {
+    sqlx::query(
+        "INSERT INTO task_briefs (id, session_id, objective, shareable_info, constraints, \
+         commitment_level, status) VALUES (?1, 's', 'o', '[]', '[]', 'can_commit', ?2)",
+    )
+    .bind(id)
+    .bind(status)
+    .execute(pool)
+    .await
+    .expect("brief insert should work");
+}
+
+#[tokio::test]
+async fn closed_briefs_are_cancelled_and_offline_sidecar_holds_the_rest() {
+    let pool = setup_pool().await;
+    insert_brief(&pool, "open", "active").await;
+    insert_brief(&pool, "done", "cancelled").await;
+
+    let due = Utc
+        .with_ymd_and_hms(2026, 3, 10, 8, 5, 0)
+        .single()
+        .expect("valid datetime");
+    for brief in ["open", "done", "missing"] {
+        enqueue(&pool, Some(brief), "s", "whatsapp", "jid", "hello", due)
+            .await
+            .expect("enqueue should work");
+    }
+
+    // Nothing listens on port 1, so the health check fails fast.
+    let client = WhatsAppClient::with_port(1);
+    let report = deliver_due_messages(&pool, &client, due)
+        .await
+        .expect("delivery pass should succeed");
+
+    assert_eq!(
+        report,
+        DeliveryReport {
+            sent: 0,
+            failed: 0,
+            cancelled: 2,
+        }
+    );
+    assert_eq!(pending_count(&pool).await.expect("count should work"), 1);
+
+    let (attempts,): (i64,) =
+        sqlx::query_as("SELECT attempts FROM outbound_queue WHERE brief_id = 'open'")
+            .fetch_one(&pool)
+            .await
+            .expect("queued row should exist");
+    assert_eq!(attempts, 0);
+}
+
+#[tokio::test]
+async fn unread_messages_are_flagged_once() {
+    let pool = setup_pool().await;
+    insert_brief(&pool, "open", "active").await;
+
+    let id = log_outbound(
+        &pool,
+        Some("open"),
+        "s",
+        "whatsapp",
+        "491701234567@s.whatsapp.net",
+        "Are we still on for <Friday>?",
+        "outbound",
+        None,
+        false,
+        Some("immediate"),
+    )
+    .await
+    .expect("log should work");
+    mark_sent(&pool, id, Some("m1"))
+        .await
+        .expect("mark should work");
+    sqlx::query("UPDATE outbound_log SET created_at = datetime('now', '-5 days')")
+        .execute(&pool)
+        .await
+        .expect("backdate should work");
+
+    let (tx, mut rx) = mpsc::channel(8);
+    assert_eq!(notify_unread(&pool, &tx, 7, 0).await.expect("disabled"), 0);
+    assert_eq!(notify_unread(&pool, &tx, 7, 3).await.expect("check"), 1);
+
+    let alert = rx.try_recv().expect("alert should be sent");
+    assert_eq!(alert.user_id, 7);
+    let text = alert.text.expect("alert should have text");
+    assert!(text.contains("brief <code>open</code>"));
+    assert!(text.contains("&lt;Friday&gt;"));
+
+    assert_eq!(notify_unread(&pool, &tx, 7, 3).await.expect("check"), 0);
+    assert!(rx.try_recv().is_err());
+}
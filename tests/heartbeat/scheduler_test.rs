@@ -3,7 +3,7 @@
 use chrono::Utc;
 
 use wintermute::config::ScheduledTaskConfig;
-use wintermute::heartbeat::scheduler::{due_tasks, SchedulerState};
+use wintermute::heartbeat::scheduler::{due_tasks, unmet_dependencies, SchedulerState};
 
 fn test_task(name: &str, cron: &str) -> ScheduledTaskConfig {
     ScheduledTaskConfig {
@@ -14,6 +14,7 @@ fn test_task(name: &str, cron: &str) -> ScheduledTaskConfig {
         budget_tokens: None,
         notify: false,
         enabled: true,
+        depends_on: vec![],
     }
 }
 
@@ -117,3 +118,59 @@ fn multiple_tasks_with_different_schedules() {
         "every-second task should be due"
     );
 }
+
+#[test]
+fn due_tasks_orders_dependencies_first() {
+    let mut digest = test_task("digest", "0 * * * * *");
+    digest.depends_on = vec!["backup".to_owned(), "consolidate".to_owned()];
+    let mut consolidate = test_task("consolidate", "0 * * * * *");
+    consolidate.depends_on = vec!["backup".to_owned()];
+    let tasks = vec![digest, consolidate, test_task("backup", "0 * * * * *")];
+
+    let mut state = SchedulerState::new();
+    let due = due_tasks(&tasks, &mut state, Utc::now());
+
+    let names: Vec<&str> = due.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["backup", "consolidate", "digest"]);
+}
+
+#[test]
+fn unmet_dependencies_until_dependency_succeeds() {
+    let mut digest = test_task("digest", "0 * * * * *");
+    digest.depends_on = vec!["backup".to_owned()];
+
+    let mut state = SchedulerState::new();
+    assert_eq!(
+        unmet_dependencies(&digest, &state),
+        vec!["backup".to_owned()]
+    );
+
+    state.record_run("backup", Utc::now());
+    assert!(unmet_dependencies(&digest, &state).is_empty());
+}
+
+#[test]
+fn stale_dependency_run_does_not_satisfy() {
+    let mut digest = test_task("digest", "0 * * * * *");
+    digest.depends_on = vec!["backup".to_owned()];
+
+    let mut state = SchedulerState::new();
+    let now = Utc::now();
+    state.record_run("backup", now - chrono::Duration::hours(2));
+    state.record_run("digest", now - chrono::Duration::hours(1));
+
+    assert_eq!(
+        unmet_dependencies(&digest, &state),
+        vec!["backup".to_owned()]
+    );
+}
+
+#[test]
+fn skip_is_reported_once_per_window() {
+    let mut state = SchedulerState::new();
+    assert!(state.mark_skip_reported("digest"));
+    assert!(!state.mark_skip_reported("digest"));
+
+    state.record_run("digest", Utc::now());
+    assert!(state.mark_skip_reported("digest"));
+}
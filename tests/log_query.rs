@@ -0,0 +1,4 @@
+//! Integration tests for `src/log_query.rs`.
+
+#[path = "log_query/log_query_test.rs"]
+mod log_query_test;
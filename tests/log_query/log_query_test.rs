@@ -0,0 +1,121 @@
+//! Tests for `src/log_query.rs` — filtering the JSONL production logs.
+
+use chrono::{TimeZone, Utc};
+use regex::Regex;
+
+use wintermute::log_query::{log_files, parse_since, query, LogFilter, LogRecord, Severity};
+
+const SESSION_LINE: &str = r#"{"timestamp":"2026-10-16T10:00:00.000000Z","level":"INFO","fields":{"message":"session started","session_id":"user_123","user_id":123},"target":"wintermute::agent::loop"}"#;
+const SPAN_LINE: &str = r#"{"timestamp":"2026-10-16T11:00:00.000000Z","level":"ERROR","fields":{"message":"provider call failed","error":"request timeout"},"target":"wintermute::agent::loop","span":{"name":"llm.complete"},"spans":[{"name":"agent.turn","session_id":"user_123"},{"name":"llm.complete"}]}"#;
+const OTHER_LINE: &str = r#"{"timestamp":"2026-10-16T11:30:00.000000Z","level":"WARN","fields":{"message":"docker is unavailable"},"target":"wintermute"}"#;
+
+fn record(line: &str) -> LogRecord {
+    LogRecord::parse(line).expect("line should parse")
+}
+
+#[test]
+fn parses_tracing_json_lines() {
+    let r = record(SESSION_LINE);
+    assert_eq!(r.level, "INFO");
+    assert_eq!(r.message, "session started");
+    assert_eq!(r.session_id(), Some("user_123"));
+    assert!(r.pretty().contains("session_id=user_123"));
+    assert!(r
+        .pretty()
+        .starts_with("2026-10-16 10:00:00 INFO  wintermute::agent::loop"));
+
+    assert!(LogRecord::parse("not json").is_none());
+    assert!(LogRecord::parse(r#"{"level":"INFO"}"#).is_none());
+}
+
+#[test]
+fn session_comes_from_enclosing_spans() {
+    let r = record(SPAN_LINE);
+    assert_eq!(r.session_id(), Some("user_123"));
+    assert!(r.pretty().ends_with("session_id=user_123"));
+    assert!(record(OTHER_LINE).session_id().is_none());
+}
+
+#[test]
+fn filter_combines_level_session_grep_and_since() {
+    let all = [record(SESSION_LINE), record(SPAN_LINE), record(OTHER_LINE)];
+    let kept = |filter: &LogFilter| all.iter().filter(|r| filter.matches(r)).count();
+
+    assert_eq!(kept(&LogFilter::default()), 3);
+    let warn = LogFilter {
+        min_level: Some(Severity::Warn),
+        ..LogFilter::default()
+    };
+    assert_eq!(kept(&warn), 2);
+    let session = LogFilter {
+        session: Some("user_123".to_owned()),
+        ..LogFilter::default()
+    };
+    assert_eq!(kept(&session), 2);
+    let grep = LogFilter {
+        grep: Some(Regex::new("timeout").expect("regex")),
+        session: Some("user_123".to_owned()),
+        ..LogFilter::default()
+    };
+    assert_eq!(kept(&grep), 1);
+    let since = LogFilter {
+        since: Some(Utc.with_ymd_and_hms(2026, 10, 16, 10, 30, 0).unwrap()),
+        ..LogFilter::default()
+    };
+    assert_eq!(kept(&since), 2);
+}
+
+#[test]
+fn parse_since_accepts_ages_and_timestamps() {
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    assert_eq!(
+        parse_since("2h", now).expect("2h"),
+        Utc.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap()
+    );
+    assert_eq!(
+        parse_since("1w", now).expect("1w"),
+        Utc.with_ymd_and_hms(2026, 10, 9, 12, 0, 0).unwrap()
+    );
+    assert_eq!(
+        parse_since("2026-10-15T08:00:00Z", now).expect("rfc3339"),
+        Utc.with_ymd_and_hms(2026, 10, 15, 8, 0, 0).unwrap()
+    );
+    assert!(parse_since("2", now).is_err());
+    assert!(parse_since("2y", now).is_err());
+}
+
+#[test]
+fn query_reads_rotated_files_in_order_and_skips_old_ones() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let old = r#"{"timestamp":"2026-10-14T09:00:00Z","level":"ERROR","fields":{"message":"old"},"target":"wintermute"}"#;
+    let yesterday = r#"{"timestamp":"2026-10-15T23:59:00Z","level":"ERROR","fields":{"message":"yesterday"},"target":"wintermute"}"#;
+    std::fs::write(
+        dir.path().join("wintermute.log.2026-10-14"),
+        format!("{old}\n"),
+    )
+    .expect("write");
+    std::fs::write(
+        dir.path().join("wintermute.log.2026-10-15"),
+        format!("{yesterday}\ngarbage\n"),
+    )
+    .expect("write");
+    std::fs::write(
+        dir.path().join("wintermute.log.2026-10-16"),
+        format!("{SESSION_LINE}\n{SPAN_LINE}\n"),
+    )
+    .expect("write");
+    std::fs::write(dir.path().join("other.log"), format!("{OTHER_LINE}\n")).expect("write");
+
+    let since = Utc.with_ymd_and_hms(2026, 10, 15, 0, 0, 0).unwrap();
+    assert_eq!(log_files(dir.path(), Some(since)).expect("files").len(), 2);
+
+    let filter = LogFilter {
+        since: Some(since),
+        min_level: Some(Severity::Error),
+        ..LogFilter::default()
+    };
+    let mut messages = Vec::new();
+    let count = query(dir.path(), &filter, |r| messages.push(r.message.clone())).expect("query");
+    assert_eq!(count, 2);
+    assert_eq!(messages, vec!["yesterday", "provider call failed"]);
+}
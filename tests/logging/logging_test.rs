@@ -22,6 +22,22 @@ fn init_production_creates_logs_dir() {
     // Note: this may fail if another test already initialised the global
     // subscriber. In that case the function returns an Err from .init(),
     // but the directory should still be created.
-    let _result = wintermute::logging::init_production(&logs_dir);
+    let _result = wintermute::logging::init_production(
+        &logs_dir,
+        &wintermute::config::TelemetryConfig::default(),
+    );
     assert!(logs_dir.exists(), "logs directory should be created");
 }
+
+#[test]
+fn log_tail_keeps_most_recent_lines() {
+    use std::io::Write;
+    use tracing_subscriber::fmt::MakeWriter;
+
+    let tail = wintermute::logging::LogTail::new(2);
+    for line in ["one\n", "two\n", "three\nfour\n"] {
+        let mut writer = tail.make_writer();
+        writer.write_all(line.as_bytes()).expect("write");
+    }
+    assert_eq!(tail.lines(), vec!["three".to_owned(), "four".to_owned()]);
+}
@@ -18,7 +18,11 @@ fn main_defines_primary_subcommands() {
     let source = main_source();
     assert!(source.contains("Init"));
     assert!(source.contains("Start"));
+    assert!(source.contains("Tui"));
     assert!(source.contains("Status"));
+    assert!(source.contains("Doctor"));
     assert!(source.contains("Reset"));
     assert!(source.contains("Backup"));
+    assert!(source.contains("Service"));
+    assert!(source.contains("Logs"));
 }
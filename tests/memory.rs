@@ -2,9 +2,15 @@
 
 #[path = "memory/embedder_test.rs"]
 mod embedder_test;
+#[path = "memory/feedback_test.rs"]
+mod feedback_test;
 #[path = "memory/migration_test.rs"]
 mod migration_test;
 #[path = "memory/search_test.rs"]
 mod search_test;
+#[path = "memory/transcript_test.rs"]
+mod transcript_test;
+#[path = "memory/usage_test.rs"]
+mod usage_test;
 #[path = "memory/writer_test.rs"]
 mod writer_test;
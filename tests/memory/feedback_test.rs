@@ -0,0 +1,209 @@
+//! Tests for `src/memory/feedback.rs` — turn feedback and confidence updates.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::memory::feedback::{FeedbackRating, TurnRecord, DEFAULT_CONFIDENCE};
+use wintermute::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
+
+async fn setup_engine() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/002_memory.sql"),
+        include_str!("../../migrations/005_feedback.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("engine should initialise")
+}
+
+async fn flush() {
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+async fn insert_memory(engine: &MemoryEngine, content: &str) -> i64 {
+    engine
+        .save_memory(Memory {
+            id: None,
+            kind: MemoryKind::Fact,
+            content: content.to_owned(),
+            metadata: None,
+            status: MemoryStatus::Active,
+            source: MemorySource::Observer,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .expect("save should succeed");
+    flush().await;
+    let row: (i64,) = sqlx::query_as("SELECT id FROM memories WHERE content = ?1")
+        .bind(content)
+        .fetch_one(engine.pool())
+        .await
+        .expect("memory should exist");
+    row.0
+}
+
+fn turn(turn_id: &str, memory_ids: Vec<i64>) -> TurnRecord {
+    TurnRecord {
+        turn_id: turn_id.to_owned(),
+        session_id: "user_1".to_owned(),
+        memory_ids,
+        tools_used: vec!["web_fetch".to_owned()],
+    }
+}
+
+async fn confidence(engine: &MemoryEngine, id: i64) -> f64 {
+    let row: (f64,) =
+        sqlx::query_as("SELECT json_extract(metadata, '$.confidence') FROM memories WHERE id = ?1")
+            .bind(id)
+            .fetch_one(engine.pool())
+            .await
+            .expect("confidence should be set");
+    row.0
+}
+
+#[test]
+fn rating_parse_accepts_aliases() {
+    assert_eq!(FeedbackRating::parse("up"), Some(FeedbackRating::Up));
+    assert_eq!(FeedbackRating::parse(" Bad "), Some(FeedbackRating::Down));
+    assert_eq!(FeedbackRating::parse("maybe"), None);
+    assert!(FeedbackRating::from_i64(0).is_err());
+}
+
+#[tokio::test]
+async fn rate_unknown_turn_returns_false() {
+    let engine = setup_engine().await;
+    let rated = engine
+        .rate_turn("user_1", "missing", FeedbackRating::Up, None)
+        .await
+        .expect("rate should not error");
+    assert!(!rated);
+    engine.shutdown().await;
+}
+
+#[tokio::test]
+async fn rating_another_sessions_turn_returns_false() {
+    let engine = setup_engine().await;
+    engine
+        .record_turn(turn("t1", vec![]))
+        .await
+        .expect("record should succeed");
+    flush().await;
+
+    let rated = engine
+        .rate_turn("user_2", "t1", FeedbackRating::Down, None)
+        .await
+        .expect("rate should not error");
+    assert!(!rated);
+    assert!(engine
+        .pending_feedback(10)
+        .await
+        .expect("pending")
+        .is_empty());
+    engine.shutdown().await;
+}
+
+#[tokio::test]
+async fn rated_turn_is_pending_until_applied() {
+    let engine = setup_engine().await;
+    let id = insert_memory(&engine, "user lives in Berlin").await;
+
+    engine
+        .record_turn(turn("t1", vec![id]))
+        .await
+        .expect("record should succeed");
+    flush().await;
+    assert_eq!(
+        engine.latest_turn_id("user_1").await.expect("lookup"),
+        Some("t1".to_owned())
+    );
+    assert!(engine
+        .pending_feedback(10)
+        .await
+        .expect("pending")
+        .is_empty());
+
+    let rated = engine
+        .rate_turn(
+            "user_1",
+            "t1",
+            FeedbackRating::Up,
+            Some("spot on".to_owned()),
+        )
+        .await
+        .expect("rate should succeed");
+    assert!(rated);
+    flush().await;
+
+    let pending = engine.pending_feedback(10).await.expect("pending");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].turn.memory_ids, vec![id]);
+    assert_eq!(pending[0].comment.as_deref(), Some("spot on"));
+
+    engine.apply_feedback(&pending[0]).await.expect("apply");
+    flush().await;
+
+    assert!(engine
+        .pending_feedback(10)
+        .await
+        .expect("pending")
+        .is_empty());
+    let conf = confidence(&engine, id).await;
+    assert!(
+        conf > DEFAULT_CONFIDENCE,
+        "confidence should rise, got {conf}"
+    );
+
+    engine.shutdown().await;
+}
+
+#[tokio::test]
+async fn repeated_negative_feedback_archives_memory() {
+    let engine = setup_engine().await;
+    let id = insert_memory(&engine, "user prefers tabs").await;
+
+    for n in 0..3 {
+        let turn_id = format!("t{n}");
+        engine
+            .record_turn(turn(&turn_id, vec![id]))
+            .await
+            .expect("record");
+        flush().await;
+        engine
+            .rate_turn("user_1", &turn_id, FeedbackRating::Down, None)
+            .await
+            .expect("rate");
+        flush().await;
+        for item in engine.pending_feedback(10).await.expect("pending") {
+            engine.apply_feedback(&item).await.expect("apply");
+        }
+        flush().await;
+    }
+
+    let row: (String, i64) = sqlx::query_as(
+        "SELECT status, json_extract(metadata, '$.negative_feedback') FROM memories WHERE id = ?1",
+    )
+    .bind(id)
+    .fetch_one(engine.pool())
+    .await
+    .expect("memory should exist");
+    assert_eq!(row.0, "archived");
+    assert_eq!(row.1, 3);
+
+    engine.shutdown().await;
+}
@@ -0,0 +1,222 @@
+//! Tests for `src/memory/transcript.rs` and the `/export` and `/status tools` commands.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use wintermute::executor::redactor::Redactor;
+use wintermute::memory::transcript::{
+    load_transcript, recent_tool_invocations, render_transcript, ExportFormat, ExportScope,
+};
+use wintermute::telegram::commands::{handle_export, handle_status_tools};
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    sqlx::raw_sql(include_str!("../../migrations/002_memory.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    pool
+}
+
+async fn insert(pool: &SqlitePool, session: &str, role: &str, content: &str) {
+    sqlx::query("INSERT INTO conversations (session_id, role, content) VALUES (?1, ?2, ?3)")
+        .bind(session)
+        .bind(role)
+        .bind(content)
+        .execute(pool)
+        .await
+        .expect("insert");
+}
+
+async fn seed(pool: &SqlitePool) {
+    insert(pool, "user_1", "user", "what is my token?").await;
+    insert(
+        pool,
+        "user_1",
+        "tool_call",
+        r#"{"name":"execute_command","input":{"command":"echo sk-supersecret"}}"#,
+    )
+    .await;
+    insert(
+        pool,
+        "user_1",
+        "tool_result",
+        "sk-supersecret\n```nested```",
+    )
+    .await;
+    insert(pool, "user_1", "assistant", "It is sk-supersecret.").await;
+    insert(pool, "user_2", "user", "hello from another user").await;
+}
+
+fn redactor() -> Redactor {
+    Redactor::new(vec!["sk-supersecret".to_owned()])
+}
+
+#[tokio::test]
+async fn load_transcript_filters_by_session() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+
+    let session = load_transcript(&pool, &ExportScope::Session("user_1".to_owned()))
+        .await
+        .expect("load");
+    assert_eq!(session.len(), 4);
+    assert_eq!(session[0].role, "user");
+    assert_eq!(session[3].role, "assistant");
+
+    let all = load_transcript(&pool, &ExportScope::All)
+        .await
+        .expect("load");
+    assert_eq!(all.len(), 5);
+}
+
+#[tokio::test]
+async fn markdown_export_is_redacted_and_fences_tool_output() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+    let scope = ExportScope::Session("user_1".to_owned());
+    let entries = load_transcript(&pool, &scope).await.expect("load");
+
+    let md = render_transcript(&entries, &scope, ExportFormat::Markdown, &redactor());
+    assert!(!md.contains("sk-supersecret"));
+    assert!(md.contains("# Transcript: user_1"));
+    assert!(md.contains("**Tool call** `execute_command`"));
+    assert!(md.contains("````\n"), "fence must outgrow inner backticks");
+    assert!(md.contains("**Assistant**"));
+}
+
+#[tokio::test]
+async fn json_export_is_valid_and_redacted() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+    let entries = load_transcript(&pool, &ExportScope::All)
+        .await
+        .expect("load");
+
+    let json = render_transcript(&entries, &ExportScope::All, ExportFormat::Json, &redactor());
+    assert!(!json.contains("sk-supersecret"));
+    let parsed: serde_json::Value = serde_json::from_str(&json).expect("valid json");
+    assert_eq!(parsed["scope"], "all");
+    assert_eq!(parsed["entries"].as_array().map(Vec::len), Some(5));
+    assert_eq!(parsed["entries"][1]["role"], "tool_call");
+}
+
+#[test]
+fn export_format_parses_aliases() {
+    assert_eq!(ExportFormat::parse("md"), Some(ExportFormat::Markdown));
+    assert_eq!(
+        ExportFormat::parse("Markdown"),
+        Some(ExportFormat::Markdown)
+    );
+    assert_eq!(ExportFormat::parse("json"), Some(ExportFormat::Json));
+    assert_eq!(ExportFormat::parse("pdf"), None);
+}
+
+#[tokio::test]
+async fn export_command_writes_file_and_guards_all_scope() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+
+    let export = handle_export(&pool, dir.path(), &redactor(), "json", 1, false)
+        .await
+        .expect("export");
+    assert!(export.caption.contains("4 messages"));
+    assert_eq!(
+        export.path.extension().and_then(|e| e.to_str()),
+        Some("json")
+    );
+    let written = std::fs::read_to_string(&export.path).expect("read export");
+    assert!(!written.contains("sk-supersecret"));
+
+    let denied = handle_export(&pool, dir.path(), &redactor(), "all", 1, false)
+        .await
+        .expect_err("non-owner");
+    assert!(denied.contains("Only the owner"));
+
+    let empty = handle_export(&pool, dir.path(), &redactor(), "session", 3, true)
+        .await
+        .expect_err("no history");
+    assert!(empty.contains("No conversation history"));
+
+    let usage = handle_export(&pool, dir.path(), &redactor(), "pdf", 1, true)
+        .await
+        .expect_err("bad format");
+    assert!(usage.starts_with("Usage"));
+}
+
+#[tokio::test]
+async fn recent_tool_invocations_pair_calls_with_results() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+    insert(
+        &pool,
+        "user_1",
+        "tool_call",
+        r#"{"name":"web_fetch","input":{},"is_error":true,"duration_ms":1500}"#,
+    )
+    .await;
+    insert(&pool, "user_1", "tool_result", "timeout").await;
+    insert(
+        &pool,
+        "user_1",
+        "tool_call",
+        r#"{"name":"memory_save","input":{},"is_error":false,"duration_ms":4}"#,
+    )
+    .await;
+    insert(&pool, "user_1", "tool_result", "saved").await;
+
+    let calls = recent_tool_invocations(&pool, "user_1", 2)
+        .await
+        .expect("query should succeed");
+    let summary: Vec<(&str, Option<u64>, Option<bool>, &str)> = calls
+        .iter()
+        .map(|c| (c.name.as_str(), c.duration_ms, c.success, c.output.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("web_fetch", Some(1500), Some(false), "timeout"),
+            ("memory_save", Some(4), Some(true), "saved"),
+        ]
+    );
+
+    // Rows logged before outcomes were recorded have no duration or success.
+    let all = recent_tool_invocations(&pool, "user_1", 10)
+        .await
+        .expect("query should succeed");
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].name, "execute_command");
+    assert_eq!((all[0].duration_ms, all[0].success), (None, None));
+
+    let none = recent_tool_invocations(&pool, "user_2", 10)
+        .await
+        .expect("query should succeed");
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn status_tools_redacts_and_validates_arguments() {
+    let pool = setup_pool().await;
+    seed(&pool).await;
+
+    let reply = handle_status_tools(&pool, &redactor(), "user_1", "tools").await;
+    assert!(reply.contains("<code>execute_command</code> not run"));
+    assert!(!reply.contains("sk-supersecret"));
+
+    let empty = handle_status_tools(&pool, &redactor(), "user_2", "tools 5").await;
+    assert!(empty.contains("No tool calls"));
+
+    for bad in ["bogus", "tools 0", "tools many"] {
+        let usage = handle_status_tools(&pool, &redactor(), "user_1", bad).await;
+        assert!(usage.starts_with("Usage:"), "{bad}: {usage}");
+    }
+}
@@ -0,0 +1,214 @@
+//! Tests for `src/memory/usage.rs` — the provider usage ledger.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::memory::usage::{self, UsageEntry, UsageGroup};
+use wintermute::memory::MemoryEngine;
+use wintermute::providers::router::ModelRouter;
+use wintermute::providers::usage::{scoped, UsageScope};
+use wintermute::providers::{
+    CompletionRequest, CompletionResponse, ContentPart, LlmProvider, ProviderError, StopReason,
+    UsageStats,
+};
+
+async fn setup_engine() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/002_memory.sql"),
+        include_str!("../../migrations/016_usage_ledger.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("engine should initialise")
+}
+
+async fn flush() {
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+fn entry(role: Option<&str>, model: &str, input: u32, output: u32, success: bool) -> UsageEntry {
+    UsageEntry {
+        role: role.map(str::to_owned),
+        model: model.to_owned(),
+        session_id: None,
+        input_tokens: input,
+        output_tokens: output,
+        latency_ms: 100,
+        success,
+    }
+}
+
+fn an_hour_ago() -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now()
+        .checked_sub_signed(chrono::Duration::hours(1))
+        .expect("in range")
+}
+
+/// Returns a fixed response with 10 input and 5 output tokens.
+struct FixedProvider;
+
+#[async_trait]
+impl LlmProvider for FixedProvider {
+    async fn complete(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        Ok(CompletionResponse {
+            content: vec![ContentPart::Text {
+                text: "ok".to_owned(),
+            }],
+            stop_reason: StopReason::EndTurn,
+            usage: UsageStats {
+                input_tokens: 10,
+                output_tokens: 5,
+            },
+            model: "mock".to_owned(),
+        })
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        false
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn model_id(&self) -> &str {
+        "mock/fixed"
+    }
+}
+
+#[tokio::test]
+async fn empty_ledger_has_zero_totals() {
+    let engine = setup_engine().await;
+    let totals = usage::usage_totals(engine.pool(), an_hour_ago())
+        .await
+        .expect("query should succeed");
+    assert_eq!(totals.calls, 0);
+    assert_eq!(totals.total_tokens(), 0);
+    assert!(
+        usage::usage_by(engine.pool(), an_hour_ago(), UsageGroup::Model)
+            .await
+            .expect("query should succeed")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn record_usage_aggregates_totals() {
+    let engine = setup_engine().await;
+    engine
+        .record_usage(entry(Some("agent"), "anthropic/a", 100, 20, true))
+        .await
+        .expect("record should succeed");
+    engine
+        .record_usage(entry(Some("observer"), "ollama/b", 30, 10, false))
+        .await
+        .expect("record should succeed");
+    flush().await;
+
+    let totals = usage::usage_totals(engine.pool(), an_hour_ago())
+        .await
+        .expect("query should succeed");
+    assert_eq!(totals.calls, 2);
+    assert_eq!(totals.failures, 1);
+    assert_eq!(totals.input_tokens, 130);
+    assert_eq!(totals.output_tokens, 30);
+    assert_eq!(totals.avg_latency_ms, 100);
+    assert_eq!(
+        usage::tokens_since(engine.pool(), an_hour_ago())
+            .await
+            .expect("query should succeed"),
+        160
+    );
+
+    let later = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::hours(1))
+        .expect("in range");
+    assert_eq!(
+        usage::tokens_since(engine.pool(), later)
+            .await
+            .expect("query should succeed"),
+        0
+    );
+}
+
+#[tokio::test]
+async fn usage_by_groups_and_sorts_by_tokens() {
+    let engine = setup_engine().await;
+    for e in [
+        entry(Some("agent"), "anthropic/a", 10, 0, true),
+        entry(Some("observer"), "ollama/b", 500, 0, true),
+        entry(None, "anthropic/a", 20, 0, true),
+    ] {
+        engine.record_usage(e).await.expect("record should succeed");
+    }
+    flush().await;
+
+    let by_model = usage::usage_by(engine.pool(), an_hour_ago(), UsageGroup::Model)
+        .await
+        .expect("query should succeed");
+    let keys: Vec<&str> = by_model.iter().map(|t| t.key.as_str()).collect();
+    assert_eq!(keys, ["ollama/b", "anthropic/a"]);
+    assert_eq!(by_model[1].calls, 2);
+    assert_eq!(by_model[1].input_tokens, 30);
+
+    let by_role = usage::usage_by(engine.pool(), an_hour_ago(), UsageGroup::Role)
+        .await
+        .expect("query should succeed");
+    let keys: Vec<&str> = by_role.iter().map(|t| t.key.as_str()).collect();
+    assert_eq!(keys, ["observer", "other", "agent"]);
+}
+
+#[tokio::test]
+async fn router_records_scoped_calls() {
+    let engine = Arc::new(setup_engine().await);
+    let router = ModelRouter::for_testing("mock/fixed".to_owned(), Arc::new(FixedProvider));
+    router.record_usage_to(Arc::clone(&engine));
+
+    let provider = router.default_provider();
+    let request = CompletionRequest {
+        messages: Vec::new(),
+        system: None,
+        tools: Vec::new(),
+        max_tokens: None,
+        stop_sequences: Vec::new(),
+    };
+    scoped(
+        UsageScope::session("agent", "telegram:1"),
+        provider.complete(request),
+    )
+    .await
+    .expect("completion should succeed");
+    flush().await;
+
+    let row: (Option<String>, String, Option<String>, i64, i64, i64) = sqlx::query_as(
+        "SELECT role, model, session_id, input_tokens, output_tokens, success FROM usage_ledger",
+    )
+    .fetch_one(engine.pool())
+    .await
+    .expect("ledger row should exist");
+    assert_eq!(row.0.as_deref(), Some("agent"));
+    assert_eq!(row.1, "mock/fixed");
+    assert_eq!(row.2.as_deref(), Some("telegram:1"));
+    assert_eq!((row.3, row.4, row.5), (10, 5, 1));
+}
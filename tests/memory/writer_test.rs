@@ -28,6 +28,12 @@ async fn setup_engine() -> MemoryEngine {
         .await
         .expect("002 should apply");
 
+    let audit_sql = include_str!("../../migrations/014_memory_audit.sql");
+    sqlx::raw_sql(audit_sql)
+        .execute(&pool)
+        .await
+        .expect("014 should apply");
+
     MemoryEngine::new(pool, None)
         .await
         .expect("engine should initialise")
@@ -390,6 +396,63 @@ async fn delete_memory_removes_from_database() {
     engine.shutdown().await;
 }
 
+#[tokio::test]
+async fn remember_and_forget_write_audit_rows() {
+    let engine = setup_engine().await;
+
+    engine
+        .remember("the boat is called Marlin", "user_7")
+        .await
+        .expect("remember should succeed");
+
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let (id, kind, status, source): (i64, String, String, String) =
+        sqlx::query_as("SELECT id, kind, status, source FROM memories LIMIT 1")
+            .fetch_one(engine.pool())
+            .await
+            .expect("should find the remembered memory");
+    assert_eq!(
+        (kind.as_str(), status.as_str(), source.as_str()),
+        ("fact", "active", "user")
+    );
+
+    engine
+        .forget(id, "user_7")
+        .await
+        .expect("forget should succeed");
+    // Forgetting a missing memory is a no-op and writes no audit row.
+    engine
+        .forget(id, "user_7")
+        .await
+        .expect("forget should succeed");
+
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let (remaining,): (i64,) = sqlx::query_as("SELECT count(*) FROM memories")
+        .fetch_one(engine.pool())
+        .await
+        .expect("count should succeed");
+    assert_eq!(remaining, 0);
+
+    let audit: Vec<(i64, String, String, String)> =
+        sqlx::query_as("SELECT memory_id, action, content, actor FROM memory_audit ORDER BY id")
+            .fetch_all(engine.pool())
+            .await
+            .expect("audit query should succeed");
+    let expected = |action: &str| {
+        (
+            id,
+            action.to_owned(),
+            "the boat is called Marlin".to_owned(),
+            "user_7".to_owned(),
+        )
+    };
+    assert_eq!(audit, vec![expected("remember"), expected("forget")]);
+
+    engine.shutdown().await;
+}
+
 #[tokio::test]
 async fn count_by_status_returns_correct_count() {
     let engine = setup_engine().await;
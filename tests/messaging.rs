@@ -0,0 +1,10 @@
+//! Integration tests for `src/messaging/`.
+
+#[path = "messaging/contacts_test.rs"]
+mod contacts_test;
+#[path = "messaging/delivery_test.rs"]
+mod delivery_test;
+#[path = "messaging/quiet_hours_test.rs"]
+mod quiet_hours_test;
+#[path = "messaging/templates_test.rs"]
+mod templates_test;
@@ -0,0 +1,337 @@
+//! Tests for `src/messaging/contacts.rs` — normalization, dedup, aliases, merge.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use wintermute::messaging::contacts::{
+    add_alias, find_duplicates, is_probable_duplicate, list_aliases, load_contact, merge_contacts,
+    normalize_phone, normalize_telegram_handle, normalize_whatsapp_jid, parse_identifier,
+    phones_match, resolve_contact, search_contacts, upsert_contact, Contact, ContactIdentifier,
+};
+use wintermute::telegram::commands::handle_contacts;
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true)
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/004_briefs.sql"),
+        include_str!("../../migrations/006_contacts.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+    pool
+}
+
+fn contact(name: &str) -> Contact {
+    Contact {
+        name: name.to_owned(),
+        ..Contact::default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Normalization
+// ---------------------------------------------------------------------------
+
+#[test]
+fn phone_normalization_strips_separators() {
+    assert_eq!(
+        normalize_phone("+49 (170) 123-45.67"),
+        Some("+491701234567".to_owned())
+    );
+    assert_eq!(
+        normalize_phone("0049 170 1234567"),
+        Some("+491701234567".to_owned())
+    );
+    assert_eq!(
+        normalize_phone("0170 1234567"),
+        Some("01701234567".to_owned())
+    );
+}
+
+#[test]
+fn phone_normalization_rejects_garbage() {
+    assert_eq!(normalize_phone("call me"), None);
+    assert_eq!(normalize_phone("12345"), None);
+    assert_eq!(normalize_phone("+49+170123456"), None);
+    assert_eq!(normalize_phone("1234567890123456"), None);
+}
+
+#[test]
+fn local_and_international_numbers_match() {
+    assert!(phones_match("+49 170 1234567", "0170 1234567"));
+    assert!(phones_match("0049 170 1234567", "+491701234567"));
+    assert!(!phones_match("+49 170 1234567", "+49 170 7654321"));
+}
+
+#[test]
+fn telegram_handle_normalization() {
+    assert_eq!(
+        normalize_telegram_handle("@Jane_Doe"),
+        Some("jane_doe".to_owned())
+    );
+    assert_eq!(normalize_telegram_handle("@ab"), None);
+    assert_eq!(normalize_telegram_handle("@not-valid"), None);
+}
+
+#[test]
+fn whatsapp_jid_normalization() {
+    assert_eq!(
+        normalize_whatsapp_jid("491701234567@c.us"),
+        Some("491701234567@s.whatsapp.net".to_owned())
+    );
+    assert_eq!(
+        normalize_whatsapp_jid("123-456@g.us"),
+        Some("123-456@g.us".to_owned())
+    );
+    assert_eq!(normalize_whatsapp_jid("abc@s.whatsapp.net"), None);
+}
+
+#[test]
+fn identifiers_are_classified() {
+    assert_eq!(
+        parse_identifier("@jane_doe"),
+        Some(ContactIdentifier::Telegram("jane_doe".to_owned()))
+    );
+    assert_eq!(
+        parse_identifier("491701234567@s.whatsapp.net"),
+        Some(ContactIdentifier::WhatsApp(
+            "491701234567@s.whatsapp.net".to_owned()
+        ))
+    );
+    assert_eq!(
+        parse_identifier("+49 170 1234567"),
+        Some(ContactIdentifier::Phone("+491701234567".to_owned()))
+    );
+}
+
+#[test]
+fn normalized_contact_derives_jid_from_phone_and_back() {
+    let mut a = contact("Jane");
+    a.phone = Some("+49 170 1234567".to_owned());
+    assert_eq!(
+        a.normalized().whatsapp_jid.as_deref(),
+        Some("491701234567@s.whatsapp.net")
+    );
+
+    let mut b = contact("Jane");
+    b.whatsapp_jid = Some("491701234567@c.us".to_owned());
+    assert_eq!(b.normalized().phone.as_deref(), Some("+491701234567"));
+}
+
+#[test]
+fn duplicates_detected_across_channels() {
+    let mut wa = contact("Jane Doe");
+    wa.whatsapp_jid = Some("491701234567@s.whatsapp.net".to_owned());
+    let mut local = contact("J. Doe");
+    local.phone = Some("0170 1234567".to_owned());
+    assert!(is_probable_duplicate(&wa, &local));
+
+    assert!(is_probable_duplicate(
+        &contact("jane  doe"),
+        &contact("Jane Doe")
+    ));
+    assert!(!is_probable_duplicate(&contact("Jane"), &contact("John")));
+}
+
+// ---------------------------------------------------------------------------
+// Persistence
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn upsert_stores_normalized_identifiers() {
+    let pool = setup_pool().await;
+    let mut c = contact("Jane");
+    c.phone = Some("+49 170 1234567".to_owned());
+    c.telegram_handle = Some("@Jane_Doe".to_owned());
+    let id = upsert_contact(&pool, &c).await.expect("insert should work");
+
+    let loaded = load_contact(&pool, id).await.expect("load should work");
+    assert_eq!(loaded.phone.as_deref(), Some("+491701234567"));
+    assert_eq!(loaded.telegram_handle.as_deref(), Some("jane_doe"));
+    assert_eq!(
+        loaded.whatsapp_jid.as_deref(),
+        Some("491701234567@s.whatsapp.net")
+    );
+}
+
+#[tokio::test]
+async fn find_duplicates_excludes_self() {
+    let pool = setup_pool().await;
+    let mut c = contact("Jane");
+    c.phone = Some("+491701234567".to_owned());
+    let id = upsert_contact(&pool, &c).await.expect("insert should work");
+
+    let stored = load_contact(&pool, id).await.expect("load should work");
+    assert!(find_duplicates(&pool, &stored)
+        .await
+        .expect("query should work")
+        .is_empty());
+
+    let mut other = contact("Jane at work");
+    other.phone = Some("0170 1234567".to_owned());
+    let dups = find_duplicates(&pool, &other)
+        .await
+        .expect("query should work");
+    assert_eq!(dups.len(), 1);
+    assert_eq!(dups[0].id, Some(id));
+}
+
+#[tokio::test]
+async fn aliases_resolve_and_search() {
+    let pool = setup_pool().await;
+    let id = upsert_contact(&pool, &contact("Margaret Smith"))
+        .await
+        .expect("insert should work");
+    add_alias(&pool, id, "Mom")
+        .await
+        .expect("alias should work");
+
+    let resolved = resolve_contact(&pool, "mom")
+        .await
+        .expect("resolve should work")
+        .expect("alias should resolve");
+    assert_eq!(resolved.id, Some(id));
+
+    let found = search_contacts(&pool, "Mo", 10)
+        .await
+        .expect("search should work");
+    assert_eq!(found.len(), 1);
+
+    assert!(add_alias(&pool, id, "   ").await.is_err());
+    assert!(add_alias(&pool, 999, "Ghost").await.is_err());
+}
+
+#[tokio::test]
+async fn resolve_by_identifier() {
+    let pool = setup_pool().await;
+    let mut c = contact("Jane");
+    c.telegram_handle = Some("jane_doe".to_owned());
+    c.phone = Some("+491701234567".to_owned());
+    let id = upsert_contact(&pool, &c).await.expect("insert should work");
+
+    for reference in ["@Jane_Doe", "0170 1234567", &format!("#{id}")] {
+        let resolved = resolve_contact(&pool, reference)
+            .await
+            .expect("resolve should work");
+        assert_eq!(resolved.and_then(|c| c.id), Some(id), "{reference}");
+    }
+    assert!(resolve_contact(&pool, "nobody")
+        .await
+        .expect("resolve should work")
+        .is_none());
+}
+
+#[tokio::test]
+async fn merge_fills_fields_moves_aliases_and_deletes() {
+    let pool = setup_pool().await;
+    let mut keep = contact("Jane Doe");
+    keep.notes = Some("Prefers mornings".to_owned());
+    let keep_id = upsert_contact(&pool, &keep)
+        .await
+        .expect("insert should work");
+
+    let mut dropped = contact("Janey");
+    dropped.phone = Some("+491701234567".to_owned());
+    dropped.notes = Some("Speaks German".to_owned());
+    let drop_id = upsert_contact(&pool, &dropped)
+        .await
+        .expect("insert should work");
+    add_alias(&pool, drop_id, "JD")
+        .await
+        .expect("alias should work");
+
+    sqlx::query(
+        "INSERT INTO task_briefs (id, session_id, contact_id, objective, shareable_info, \
+         constraints, commitment_level) VALUES ('b1', 's', ?1, 'o', '[]', '[]', 'can_commit')",
+    )
+    .bind(drop_id)
+    .execute(&pool)
+    .await
+    .expect("brief insert should work");
+
+    let merged = merge_contacts(&pool, keep_id, drop_id)
+        .await
+        .expect("merge should work");
+    assert_eq!(merged.phone.as_deref(), Some("+491701234567"));
+    assert_eq!(
+        merged.notes.as_deref(),
+        Some("Prefers mornings\nSpeaks German")
+    );
+
+    assert!(load_contact(&pool, drop_id).await.is_err());
+    let mut aliases = list_aliases(&pool, keep_id)
+        .await
+        .expect("aliases should load");
+    aliases.sort();
+    assert_eq!(aliases, vec!["JD".to_owned(), "Janey".to_owned()]);
+
+    let (brief_contact,): (Option<i64>,) =
+        sqlx::query_as("SELECT contact_id FROM task_briefs WHERE id = 'b1'")
+            .fetch_one(&pool)
+            .await
+            .expect("brief should exist");
+    assert_eq!(brief_contact, Some(keep_id));
+
+    assert!(merge_contacts(&pool, keep_id, keep_id).await.is_err());
+}
+
+// ---------------------------------------------------------------------------
+// /contacts command
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn contacts_command_add_warns_about_duplicates() {
+    let pool = setup_pool().await;
+    let first = handle_contacts(&pool, "add Jane Doe | +49 170 1234567").await;
+    assert!(first.contains("Added contact #1 Jane Doe"));
+
+    let second = handle_contacts(&pool, "add Janey | 0170 1234567 | @janey_d").await;
+    assert!(second.contains("Added contact #2"));
+    assert!(second.contains("Possible duplicate of #1"));
+    assert!(second.contains("/contacts merge 1 2"));
+
+    let bad = handle_contacts(&pool, "add Bob | not a number").await;
+    assert!(bad.contains("Could not parse"));
+}
+
+#[tokio::test]
+async fn contacts_command_alias_note_list_merge() {
+    let pool = setup_pool().await;
+    handle_contacts(&pool, "add Jane Doe | @jane_doe").await;
+    handle_contacts(&pool, "add Janey | +491701234567").await;
+
+    assert!(handle_contacts(&pool, "alias 1 Boss")
+        .await
+        .contains("added to #1"));
+    assert!(handle_contacts(&pool, "note #1 Formal tone please")
+        .await
+        .contains("Notes updated"));
+
+    let list = handle_contacts(&pool, "list").await;
+    assert!(list.contains("Contacts (2)"));
+    assert!(list.contains("aka Boss"));
+    assert!(list.contains("@jane_doe"));
+
+    assert!(handle_contacts(&pool, "merge 1 2")
+        .await
+        .contains("Merged #2 into #1"));
+    assert!(handle_contacts(&pool, "list")
+        .await
+        .contains("Contacts (1)"));
+
+    assert!(handle_contacts(&pool, "merge 1").await.starts_with("Usage"));
+    assert!(handle_contacts(&pool, "bogus").await.starts_with("Usage"));
+}
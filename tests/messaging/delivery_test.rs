@@ -0,0 +1,170 @@
+//! Tests for `src/messaging/delivery.rs` — delivery state and unread tracking.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use wintermute::messaging::audit::log_outbound;
+use wintermute::messaging::delivery::{
+    format_entries, mark_failed, mark_sent, mark_unread_alerted, recent_for_brief, record_receipt,
+    stale_unread, DeliveryState,
+};
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/004_briefs.sql"),
+        include_str!("../../migrations/006_contacts.sql"),
+        include_str!("../../migrations/007_outbound_queue.sql"),
+        include_str!("../../migrations/009_delivery_state.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    sqlx::query("INSERT INTO contacts (id, name) VALUES (1, 'Jane Doe')")
+        .execute(&pool)
+        .await
+        .expect("contact insert should work");
+    for (id, status) in [("open", "active"), ("done", "completed")] {
+        sqlx::query(
+            "INSERT INTO task_briefs (id, session_id, contact_id, objective, shareable_info, \
+             constraints, commitment_level, status) \
+             VALUES (?1, 's', 1, 'book a table', '[]', '[]', 'can_commit', ?2)",
+        )
+        .bind(id)
+        .bind(status)
+        .execute(&pool)
+        .await
+        .expect("brief insert should work");
+    }
+    pool
+}
+
+/// Log an outbound message on `brief` and mark it sent as `message_id`.
+async fn sent(pool: &SqlitePool, brief: &str, message_id: &str) -> i64 {
+    let id = log_outbound(
+        pool,
+        Some(brief),
+        "s",
+        "whatsapp",
+        "491701234567@s.whatsapp.net",
+        "Hi, can we move dinner to 8?",
+        "outbound",
+        None,
+        false,
+        Some("immediate"),
+    )
+    .await
+    .expect("log should work");
+    mark_sent(pool, id, Some(message_id))
+        .await
+        .expect("mark should work");
+    id
+}
+
+async fn backdate(pool: &SqlitePool, id: i64, days: u32) {
+    sqlx::query("UPDATE outbound_log SET created_at = datetime('now', ?2) WHERE id = ?1")
+        .bind(id)
+        .bind(format!("-{days} days"))
+        .execute(pool)
+        .await
+        .expect("backdate should work");
+}
+
+#[test]
+fn receipts_map_to_states() {
+    assert_eq!(
+        DeliveryState::from_receipt("delivery_ack"),
+        Some(DeliveryState::Delivered)
+    );
+    assert_eq!(
+        DeliveryState::from_receipt("READ"),
+        Some(DeliveryState::Read)
+    );
+    assert_eq!(
+        DeliveryState::from_receipt("played"),
+        Some(DeliveryState::Read)
+    );
+    assert_eq!(DeliveryState::from_receipt("pending"), None);
+}
+
+#[test]
+fn states_only_advance() {
+    assert!(DeliveryState::Sent.can_advance_to(DeliveryState::Delivered));
+    assert!(DeliveryState::Delivered.can_advance_to(DeliveryState::Read));
+    assert!(!DeliveryState::Read.can_advance_to(DeliveryState::Delivered));
+    assert!(!DeliveryState::Delivered.can_advance_to(DeliveryState::Failed));
+    assert!(!DeliveryState::Failed.can_advance_to(DeliveryState::Read));
+}
+
+#[tokio::test]
+async fn receipts_advance_state_in_order() {
+    let pool = setup_pool().await;
+    sent(&pool, "open", "m1").await;
+
+    assert!(record_receipt(&pool, "m1", DeliveryState::Read)
+        .await
+        .expect("receipt should apply"));
+    assert!(!record_receipt(&pool, "m1", DeliveryState::Delivered)
+        .await
+        .expect("late receipt should be ignored"));
+    assert!(!record_receipt(&pool, "unknown", DeliveryState::Read)
+        .await
+        .expect("unknown id should be ignored"));
+
+    let recent = recent_for_brief(&pool, "open", 5)
+        .await
+        .expect("query should work");
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].state, DeliveryState::Read);
+    assert_eq!(recent[0].contact_name.as_deref(), Some("Jane Doe"));
+    assert!(format_entries(&recent).starts_with("- [read]"));
+}
+
+#[tokio::test]
+async fn stale_unread_finds_old_unread_on_open_briefs_once() {
+    let pool = setup_pool().await;
+    let old_unread = sent(&pool, "open", "m1").await;
+    backdate(&pool, old_unread, 4).await;
+
+    let old_read = sent(&pool, "open", "m2").await;
+    backdate(&pool, old_read, 4).await;
+    record_receipt(&pool, "m2", DeliveryState::Read)
+        .await
+        .expect("receipt should apply");
+
+    let fresh = sent(&pool, "open", "m3").await;
+    backdate(&pool, fresh, 1).await;
+
+    let closed = sent(&pool, "done", "m4").await;
+    backdate(&pool, closed, 4).await;
+
+    let failed = sent(&pool, "open", "m5").await;
+    backdate(&pool, failed, 4).await;
+    mark_failed(&pool, failed).await.expect("mark should work");
+
+    let stale = stale_unread(&pool, 3).await.expect("query should work");
+    assert_eq!(
+        stale.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![old_unread]
+    );
+
+    mark_unread_alerted(&pool, old_unread)
+        .await
+        .expect("mark should work");
+    assert!(stale_unread(&pool, 3)
+        .await
+        .expect("query should work")
+        .is_empty());
+}
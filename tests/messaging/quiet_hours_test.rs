@@ -0,0 +1,233 @@
+//! Tests for `src/messaging/quiet_hours.rs` and `src/messaging/outbound_queue.rs`.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use wintermute::config::MessagingConfig;
+use wintermute::messaging::contacts::Contact;
+use wintermute::messaging::outbound_queue::{
+    cancel, due_messages, enqueue, mark_sent, pending_count, record_failure,
+};
+use wintermute::messaging::quiet_hours::{
+    parse_setting, plan_delivery, release_time, DeliveryPlan, QuietHoursPolicy, QuietWindow,
+};
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/004_briefs.sql"),
+        include_str!("../../migrations/007_outbound_queue.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+    pool
+}
+
+fn at(hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, 3, 10)
+        .and_then(|d| d.and_hms_opt(hour, minute, 0))
+        .expect("valid datetime")
+}
+
+fn utc(hour: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 10, hour, 0, 0)
+        .single()
+        .expect("valid datetime")
+}
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).expect("valid time")
+}
+
+fn window(spec: &str) -> QuietWindow {
+    QuietWindow::parse(spec).expect("window should parse")
+}
+
+fn contact(name: &str) -> Contact {
+    Contact {
+        name: name.to_owned(),
+        ..Contact::default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// QuietWindow
+// ---------------------------------------------------------------------------
+
+#[test]
+fn window_parse_accepts_and_rejects() {
+    let w = window("22:00-08:00");
+    assert_eq!(w.start, time(22, 0));
+    assert_eq!(w.end, time(8, 0));
+
+    assert!(QuietWindow::parse("22:00").is_err());
+    assert!(QuietWindow::parse("25:00-08:00").is_err());
+    assert!(QuietWindow::parse("08:00-08:00").is_err());
+    assert_eq!(parse_setting("off").expect("off is valid"), None);
+}
+
+#[test]
+fn window_wrapping_midnight() {
+    let w = window("22:00-08:00");
+    assert!(w.contains(time(23, 30)));
+    assert!(w.contains(time(3, 0)));
+    assert!(!w.contains(time(8, 0)));
+    assert!(!w.contains(time(12, 0)));
+}
+
+#[test]
+fn window_within_one_day() {
+    let w = window("13:00-14:30");
+    assert!(w.contains(time(13, 0)));
+    assert!(!w.contains(time(14, 30)));
+    assert!(!w.contains(time(23, 0)));
+}
+
+#[test]
+fn next_end_rolls_to_following_day() {
+    let w = window("22:00-08:00");
+    assert_eq!(w.next_end(at(3, 0)), at(8, 0));
+    let late = w.next_end(at(23, 0));
+    assert_eq!(late.date(), at(8, 0).date().succ_opt().expect("next day"));
+    assert_eq!(late.time(), time(8, 0));
+}
+
+// ---------------------------------------------------------------------------
+// Policy and delivery plan
+// ---------------------------------------------------------------------------
+
+#[test]
+fn contact_override_beats_global() {
+    let mut config = MessagingConfig {
+        quiet_hours: Some("22:00-08:00".to_owned()),
+        ..MessagingConfig::default()
+    };
+    config
+        .contact_quiet_hours
+        .insert("Mom".to_owned(), "21:00-09:00".to_owned());
+    config
+        .contact_quiet_hours
+        .insert("Jane Doe".to_owned(), "off".to_owned());
+    let policy = QuietHoursPolicy::from_config(&config).expect("config should parse");
+
+    assert_eq!(
+        policy.window_for(&contact("Margaret"), &["mom".to_owned()]),
+        Some(window("21:00-09:00"))
+    );
+    assert_eq!(policy.window_for(&contact("jane doe"), &[]), None);
+    assert_eq!(
+        policy.window_for(&contact("Bob"), &[]),
+        Some(window("22:00-08:00"))
+    );
+}
+
+#[test]
+fn invalid_config_is_rejected() {
+    let config = MessagingConfig {
+        quiet_hours: Some("late".to_owned()),
+        ..MessagingConfig::default()
+    };
+    assert!(QuietHoursPolicy::from_config(&config).is_err());
+}
+
+#[test]
+fn plan_defers_inside_window_unless_urgent() {
+    let w = Some(window("22:00-08:00"));
+    assert_eq!(plan_delivery(w, at(12, 0), false), DeliveryPlan::Immediate);
+    assert_eq!(
+        plan_delivery(w, at(3, 0), false),
+        DeliveryPlan::Deferred {
+            window_ends: at(8, 0)
+        }
+    );
+    assert_eq!(plan_delivery(w, at(3, 0), true), DeliveryPlan::Override);
+    assert_eq!(
+        plan_delivery(None, at(3, 0), false),
+        DeliveryPlan::Immediate
+    );
+
+    assert_eq!(DeliveryPlan::Override.audit_label(), "quiet_hours_override");
+}
+
+#[test]
+fn release_time_is_jittered_after_window() {
+    for _ in 0..20 {
+        let released = release_time(at(8, 0));
+        assert!(released >= at(8, 1));
+        assert!(released <= at(8, 20));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Outbound queue
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn queue_returns_only_due_messages_in_order() {
+    let pool = setup_pool().await;
+    let early = utc(7);
+    let late = utc(9);
+
+    let second = enqueue(&pool, Some("b1"), "s", "whatsapp", "jid", "later", late)
+        .await
+        .expect("enqueue should work");
+    let first = enqueue(&pool, Some("b1"), "s", "whatsapp", "jid", "sooner", early)
+        .await
+        .expect("enqueue should work");
+
+    let now = utc(8);
+    let due = due_messages(&pool, now, 10)
+        .await
+        .expect("query should work");
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, first);
+
+    let all = due_messages(&pool, late, 10)
+        .await
+        .expect("query should work");
+    assert_eq!(
+        all.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![first, second]
+    );
+
+    mark_sent(&pool, first).await.expect("mark should work");
+    assert_eq!(pending_count(&pool).await.expect("count should work"), 1);
+}
+
+#[tokio::test]
+async fn queue_gives_up_after_repeated_failures() {
+    let pool = setup_pool().await;
+    let when = utc(7);
+    let id = enqueue(&pool, None, "s", "whatsapp", "jid", "hi", when)
+        .await
+        .expect("enqueue should work");
+
+    assert!(!record_failure(&pool, id, "offline").await.expect("update"));
+    assert!(!record_failure(&pool, id, "offline").await.expect("update"));
+    assert!(record_failure(&pool, id, "offline").await.expect("update"));
+    assert_eq!(pending_count(&pool).await.expect("count should work"), 0);
+
+    let other = enqueue(&pool, None, "s", "whatsapp", "jid", "hi", when)
+        .await
+        .expect("enqueue should work");
+    cancel(&pool, other, "changed mind")
+        .await
+        .expect("cancel should work");
+    assert!(due_messages(&pool, when, 10)
+        .await
+        .expect("query should work")
+        .is_empty());
+}
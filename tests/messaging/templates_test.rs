@@ -0,0 +1,155 @@
+//! Tests for `src/messaging/templates.rs` and the `/templates` command.
+
+use std::collections::HashMap;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+
+use wintermute::messaging::brief::{BriefStatus, CommitmentLevel, TaskBrief};
+use wintermute::messaging::contacts::Contact;
+use wintermute::messaging::templates::{
+    delete_template, fill, list_templates, load_template, placeholders, render, save_template,
+};
+use wintermute::telegram::commands::handle_templates;
+
+async fn setup_pool() -> SqlitePool {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    sqlx::raw_sql(include_str!("../../migrations/008_templates.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    pool
+}
+
+fn brief() -> TaskBrief {
+    TaskBrief {
+        id: "b1".to_owned(),
+        session_id: "s".to_owned(),
+        contact_id: Some(1),
+        objective: "reschedule the dentist appointment".to_owned(),
+        shareable_info: vec![],
+        constraints: vec![],
+        escalation_triggers: vec![],
+        commitment_level: CommitmentLevel::CanCommit,
+        tone: None,
+        status: BriefStatus::Active,
+        outcome_summary: None,
+        created_at: None,
+        completed_at: None,
+    }
+}
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+        .collect()
+}
+
+#[test]
+fn placeholders_are_unique_and_ordered() {
+    assert_eq!(
+        placeholders("Hi {first_name}, is {day} ok? {first_name}"),
+        vec!["first_name".to_owned(), "day".to_owned()]
+    );
+    assert!(placeholders("json {\"a\": 1} and {} and { spaced }").is_empty());
+}
+
+#[test]
+fn render_substitutes_and_keeps_literal_braces() {
+    let out = render("Hi {name} {not valid} {", &vars(&[("name", "Ann")]))
+        .expect("render should succeed");
+    assert_eq!(out, "Hi Ann {not valid} {");
+}
+
+#[test]
+fn render_reports_all_missing_values() {
+    let err = render("{a} {b} {a}", &HashMap::new())
+        .expect_err("render should fail")
+        .to_string();
+    assert!(err.contains("a, b"), "{err}");
+}
+
+#[test]
+fn fill_uses_brief_and_contact_fields_over_extra() {
+    let template = wintermute::messaging::templates::MessageTemplate {
+        id: 1,
+        name: "follow_up".to_owned(),
+        body: "Hi {first_name}, following up to {objective} on {day}.".to_owned(),
+    };
+    let contact = Contact {
+        name: "Jane Doe".to_owned(),
+        ..Contact::default()
+    };
+    let extra = vars(&[("day", "Tuesday"), ("first_name", "Mallory")]);
+
+    let text = fill(&template, &brief(), Some(&contact), &extra).expect("fill should succeed");
+    assert_eq!(
+        text,
+        "Hi Jane, following up to reschedule the dentist appointment on Tuesday."
+    );
+}
+
+#[tokio::test]
+async fn save_replaces_by_name_and_validates() {
+    let pool = setup_pool().await;
+    let first = save_template(&pool, "Follow_Up", "v1")
+        .await
+        .expect("save should work");
+    let second = save_template(&pool, "follow_up", "v2")
+        .await
+        .expect("save should work");
+    assert_eq!(first, second);
+    assert_eq!(
+        load_template(&pool, "FOLLOW_UP")
+            .await
+            .expect("load should work")
+            .body,
+        "v2"
+    );
+
+    assert!(save_template(&pool, "bad name", "x").await.is_err());
+    assert!(save_template(&pool, "empty", "   ").await.is_err());
+    assert!(load_template(&pool, "missing").await.is_err());
+
+    assert!(delete_template(&pool, "follow_up")
+        .await
+        .expect("delete should work"));
+    assert!(list_templates(&pool)
+        .await
+        .expect("list should work")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn templates_command_round_trip() {
+    let pool = setup_pool().await;
+    let added = handle_templates(&pool, "add follow_up Hi {first_name}, still on for {day}?").await;
+    assert!(added.contains("saved"));
+    assert!(added.contains("first_name, day"));
+
+    let list = handle_templates(&pool, "").await;
+    assert!(list.contains("Templates (1)"));
+    assert!(list.contains("follow_up"));
+
+    let shown = handle_templates(&pool, "show follow_up").await;
+    assert!(shown.contains("<pre>Hi {first_name}, still on for {day}?</pre>"));
+
+    assert!(handle_templates(&pool, "delete follow_up")
+        .await
+        .contains("deleted"));
+    assert!(handle_templates(&pool, "delete follow_up")
+        .await
+        .contains("not found"));
+    assert!(handle_templates(&pool, "add onlyname")
+        .await
+        .starts_with("Usage"));
+}
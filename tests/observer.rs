@@ -4,7 +4,13 @@
 mod extractor_budget_test;
 #[path = "observer/extractor_test.rs"]
 mod extractor_test;
+#[path = "observer/feedback_test.rs"]
+mod feedback_test;
+#[path = "observer/queue_test.rs"]
+mod queue_test;
 #[path = "observer/reflection_test.rs"]
 mod reflection_test;
+#[path = "observer/skills_test.rs"]
+mod skills_test;
 #[path = "observer/staging_test.rs"]
 mod staging_test;
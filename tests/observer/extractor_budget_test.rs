@@ -6,7 +6,7 @@ use async_trait::async_trait;
 
 use wintermute::agent::budget::DailyBudget;
 use wintermute::executor::redactor::Redactor;
-use wintermute::observer::extractor::{extract, user_message};
+use wintermute::observer::extractor::{extract, extract_batch, user_message};
 use wintermute::providers::router::ModelRouter;
 use wintermute::providers::{
     CompletionRequest, CompletionResponse, ContentPart, LlmProvider, ProviderError, StopReason,
@@ -99,3 +99,31 @@ async fn extract_redacts_secrets_from_llm_output() {
         );
     }
 }
+
+#[tokio::test]
+async fn extract_batch_attributes_redacts_and_records_usage() {
+    let secret = "sk-ant-secret-key-12345";
+    let response_json = format!(
+        r#"[{{"conversation": 2, "kind": "fact", "content": "Token is {secret}", "confidence": 0.9}}]"#
+    );
+    let router = mock_router(&response_json);
+    let redactor = Redactor::new(vec![secret.to_owned()]);
+    let budget = DailyBudget::new(100_000);
+
+    let first = vec![user_message("hello")];
+    let second = vec![user_message("my token")];
+    let result = extract_batch(
+        &[first.as_slice(), second.as_slice()],
+        &router,
+        &redactor,
+        &budget,
+    )
+    .await
+    .expect("batch extract should succeed");
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].0, 1);
+    assert!(!result[0].1.content.contains(secret));
+    // One call for both conversations.
+    assert_eq!(budget.used(), 150);
+}
@@ -1,6 +1,9 @@
 //! Tests for `src/observer/extractor.rs` — extraction parsing and filtering.
 
-use wintermute::observer::extractor::{parse_extractions, Extraction, ExtractionKind};
+use wintermute::observer::extractor::{
+    parse_batch_extractions, parse_extractions, render_batch, user_message, Extraction,
+    ExtractionKind,
+};
 
 #[test]
 fn parse_valid_json_array() {
@@ -83,6 +86,7 @@ fn extraction_serialization_roundtrip() {
         kind: ExtractionKind::Fact,
         content: "test content".to_owned(),
         confidence: 0.85,
+        source_span: Some("I always use dark mode".to_owned()),
     };
 
     let json = serde_json::to_string(&extraction).expect("should serialize");
@@ -91,4 +95,57 @@ fn extraction_serialization_roundtrip() {
     assert_eq!(deserialized.kind, extraction.kind);
     assert_eq!(deserialized.content, extraction.content);
     assert!((deserialized.confidence - extraction.confidence).abs() < f64::EPSILON);
+    assert_eq!(deserialized.source_span, extraction.source_span);
+}
+
+#[test]
+fn parse_extractions_keeps_source_span() {
+    let json = r#"[
+        {"kind": "fact", "content": "Lives in Berlin", "confidence": 0.9,
+         "source_span": "  I moved to Berlin last year  "},
+        {"kind": "fact", "content": "Likes tea", "confidence": 0.7}
+    ]"#;
+    let result = parse_extractions(json).expect("should parse");
+    assert_eq!(result.len(), 2);
+    assert_eq!(
+        result[0].source_span.as_deref(),
+        Some("I moved to Berlin last year")
+    );
+    assert!(result[1].source_span.is_none());
+}
+
+#[test]
+fn parse_batch_extractions_attributes_conversations() {
+    let json = r#"[
+        {"conversation": 1, "kind": "fact", "content": "User lives in Lisbon", "confidence": 0.9},
+        {"conversation": 2, "kind": "preference", "content": "Prefers tea", "confidence": 0.7},
+        {"conversation": 3, "kind": "fact", "content": "Out of range", "confidence": 0.9},
+        {"conversation": 2, "kind": "fact", "content": "Unsure", "confidence": 0.2},
+        {"kind": "fact", "content": "No conversation", "confidence": 0.9}
+    ]"#;
+    assert!(parse_batch_extractions(json, 2).is_empty());
+
+    let json = r#"[
+        {"conversation": 1, "kind": "fact", "content": "User lives in Lisbon", "confidence": 0.9},
+        {"conversation": 2, "kind": "preference", "content": "Prefers tea", "confidence": 0.7},
+        {"conversation": 3, "kind": "fact", "content": "Out of range", "confidence": 0.9},
+        {"conversation": 2, "kind": "fact", "content": "Unsure", "confidence": 0.2}
+    ]"#;
+    let parsed = parse_batch_extractions(json, 2);
+    let pairs: Vec<(usize, &str)> = parsed
+        .iter()
+        .map(|(i, e)| (*i, e.content.as_str()))
+        .collect();
+    assert_eq!(pairs, vec![(0, "User lives in Lisbon"), (1, "Prefers tea")]);
+}
+
+#[test]
+fn render_batch_labels_each_conversation() {
+    let first = vec![user_message("I moved to Lisbon")];
+    let second = vec![user_message("Tea, please")];
+    let text = render_batch(&[first.as_slice(), second.as_slice()]);
+    assert_eq!(
+        text,
+        "=== Conversation 1 ===\nuser: I moved to Lisbon\n\n=== Conversation 2 ===\nuser: Tea, please\n"
+    );
 }
@@ -0,0 +1,77 @@
+//! Tests for `src/observer/feedback.rs` — applying user ratings.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::memory::feedback::{FeedbackRating, TurnRecord};
+use wintermute::memory::MemoryEngine;
+use wintermute::observer::feedback::apply_pending_feedback;
+
+async fn setup_engine() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/002_memory.sql"),
+        include_str!("../../migrations/005_feedback.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("engine should initialise")
+}
+
+#[tokio::test]
+async fn apply_pending_feedback_drains_rated_turns() {
+    let engine = setup_engine().await;
+
+    for (turn_id, rating) in [("a", FeedbackRating::Up), ("b", FeedbackRating::Down)] {
+        engine
+            .record_turn(TurnRecord {
+                turn_id: turn_id.to_owned(),
+                session_id: "user_1".to_owned(),
+                memory_ids: vec![],
+                tools_used: vec![],
+            })
+            .await
+            .expect("record");
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        engine
+            .rate_turn("user_1", turn_id, rating, None)
+            .await
+            .expect("rate");
+    }
+    // Unrated turns are left alone.
+    engine
+        .record_turn(TurnRecord {
+            turn_id: "c".to_owned(),
+            session_id: "user_1".to_owned(),
+            memory_ids: vec![],
+            tools_used: vec![],
+        })
+        .await
+        .expect("record");
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let summary = apply_pending_feedback(&engine).await.expect("apply");
+    assert_eq!(summary.turns, 2);
+    assert_eq!(summary.positive, 1);
+    assert_eq!(summary.negative, 1);
+
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let again = apply_pending_feedback(&engine).await.expect("apply");
+    assert_eq!(again.turns, 0);
+
+    engine.shutdown().await;
+}
@@ -0,0 +1,119 @@
+//! Tests for `src/observer/queue.rs` — coalescing, bounding and batching.
+
+use wintermute::observer::extractor::user_message;
+use wintermute::observer::queue::{
+    merge_snapshots, ObserverQueue, PushOutcome, MAX_BATCH_SESSIONS, MAX_SNAPSHOT_MESSAGES,
+    SMALL_SESSION_MESSAGES,
+};
+use wintermute::observer::ObserverEvent;
+use wintermute::providers::Message;
+
+fn messages(texts: &[&str]) -> Vec<Message> {
+    texts.iter().map(|t| user_message(t)).collect()
+}
+
+fn event(session: &str, count: usize) -> ObserverEvent {
+    ObserverEvent {
+        session_id: session.to_owned(),
+        user_id: 1,
+        messages: (0..count).map(|i| user_message(&format!("m{i}"))).collect(),
+        tools_modified: Vec::new(),
+    }
+}
+
+#[test]
+fn merge_keeps_overlap_once() {
+    let merged = merge_snapshots(messages(&["a", "b", "c"]), messages(&["b", "c", "d"]));
+    assert_eq!(merged, messages(&["a", "b", "c", "d"]));
+
+    // Unrelated snapshots are concatenated.
+    let merged = merge_snapshots(messages(&["a"]), messages(&["x", "y"]));
+    assert_eq!(merged, messages(&["a", "x", "y"]));
+
+    // Identical snapshots collapse.
+    let merged = merge_snapshots(messages(&["a", "b"]), messages(&["a", "b"]));
+    assert_eq!(merged, messages(&["a", "b"]));
+}
+
+#[test]
+fn merge_caps_snapshot_at_most_recent_messages() {
+    let older: Vec<Message> = (0..MAX_SNAPSHOT_MESSAGES)
+        .map(|i| user_message(&format!("old{i}")))
+        .collect();
+    let merged = merge_snapshots(older, messages(&["new"]));
+    assert_eq!(merged.len(), MAX_SNAPSHOT_MESSAGES);
+    assert_eq!(merged.last(), Some(&user_message("new")));
+    assert_eq!(merged.first(), Some(&user_message("old1")));
+}
+
+#[tokio::test]
+async fn events_from_the_same_session_are_coalesced() {
+    let queue = ObserverQueue::new(8);
+    let mut first = event("s1", 0);
+    first.messages = messages(&["hi", "hello"]);
+    first.tools_modified = vec!["rss".to_owned()];
+    let mut second = event("s1", 0);
+    second.messages = messages(&["hello", "bye"]);
+    second.tools_modified = vec!["rss".to_owned(), "weather".to_owned()];
+
+    assert_eq!(queue.push(first), PushOutcome::Queued);
+    assert_eq!(queue.push(second), PushOutcome::Coalesced);
+    let stats = queue.stats();
+    assert_eq!(stats.depth, 1);
+    assert_eq!(stats.coalesced, 1);
+
+    let batch = queue.next_batch().await.expect("batch");
+    assert_eq!(batch.len(), 1);
+    assert_eq!(batch[0].messages, messages(&["hi", "hello", "bye"]));
+    assert_eq!(batch[0].tools_modified, vec!["rss", "weather"]);
+}
+
+#[tokio::test]
+async fn full_queue_drops_oldest_session() {
+    let queue = ObserverQueue::new(2);
+    assert_eq!(queue.push(event("s1", 20)), PushOutcome::Queued);
+    assert_eq!(queue.push(event("s2", 20)), PushOutcome::Queued);
+    assert_eq!(queue.push(event("s3", 20)), PushOutcome::DroppedOldest);
+    // Coalescing never drops.
+    assert_eq!(queue.push(event("s3", 20)), PushOutcome::Coalesced);
+
+    let stats = queue.stats();
+    assert_eq!((stats.depth, stats.capacity, stats.dropped), (2, 2, 1));
+    let first = queue.next_batch().await.expect("batch");
+    assert_eq!(first[0].session_id, "s2");
+}
+
+#[tokio::test]
+async fn small_sessions_are_batched_and_large_ones_run_alone() {
+    let queue = ObserverQueue::new(16);
+    queue.push(event("big", SMALL_SESSION_MESSAGES + 1));
+    queue.push(event("a", 2));
+    queue.push(event("big2", SMALL_SESSION_MESSAGES + 5));
+    queue.push(event("b", 2));
+    for i in 0..MAX_BATCH_SESSIONS {
+        queue.push(event(&format!("tiny{i}"), 1));
+    }
+
+    let first = queue.next_batch().await.expect("batch");
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].session_id, "big");
+
+    // Small sessions join the oldest small one, skipping the large one.
+    let second = queue.next_batch().await.expect("batch");
+    let ids: Vec<&str> = second.iter().map(|e| e.session_id.as_str()).collect();
+    assert_eq!(ids, vec!["a", "b", "tiny0", "tiny1", "tiny2"]);
+
+    let third = queue.next_batch().await.expect("batch");
+    assert_eq!(third[0].session_id, "big2");
+    assert_eq!(queue.stats().batches, 1);
+}
+
+#[tokio::test]
+async fn closed_queue_drains_then_ends() {
+    let queue = ObserverQueue::new(4);
+    queue.push(event("s1", 20));
+    queue.close();
+    assert_eq!(queue.push(event("s2", 1)), PushOutcome::Closed);
+    assert!(queue.next_batch().await.is_some());
+    assert!(queue.next_batch().await.is_none());
+}
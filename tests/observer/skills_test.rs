@@ -0,0 +1,282 @@
+//! Tests for `src/observer/skills.rs` — skill extraction from tool sequences.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use tokio::sync::mpsc;
+
+use wintermute::agent::Keyboard;
+use wintermute::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
+use wintermute::observer::skills::{
+    extract_tool_sequences, is_unripe_skill, resolve_proposal, stage_skills,
+};
+use wintermute::providers::{ContentPart, Message, MessageContent, Role};
+
+async fn setup_engine() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    for script in [
+        include_str!("../../migrations/001_schema.sql"),
+        include_str!("../../migrations/002_memory.sql"),
+    ] {
+        sqlx::raw_sql(script)
+            .execute(&pool)
+            .await
+            .expect("migration should apply");
+    }
+
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("engine should initialise")
+}
+
+async fn flush() {
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+}
+
+fn user(text: &str) -> Message {
+    Message {
+        role: Role::User,
+        content: MessageContent::Text(text.to_owned()),
+    }
+}
+
+fn call(id: &str, name: &str, input: serde_json::Value) -> Message {
+    Message {
+        role: Role::Assistant,
+        content: MessageContent::Parts(vec![ContentPart::ToolUse {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            input,
+        }]),
+    }
+}
+
+fn result(id: &str, is_error: bool) -> Message {
+    Message {
+        role: Role::User,
+        content: MessageContent::Parts(vec![ContentPart::ToolResult {
+            tool_use_id: id.to_owned(),
+            content: "ok".to_owned(),
+            is_error,
+        }]),
+    }
+}
+
+/// One user turn that fetches a page and saves a note, optionally failing.
+fn fetch_and_save_turn(url: &str, fail: bool) -> Vec<Message> {
+    vec![
+        user(&format!("summarise {url}")),
+        call("c1", "web_fetch", serde_json::json!({"url": url})),
+        result("c1", false),
+        call(
+            "c2",
+            "save_note",
+            serde_json::json!({"title": "t", "body": "b"}),
+        ),
+        result("c2", fail),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// extract_tool_sequences
+// ---------------------------------------------------------------------------
+
+#[test]
+fn extracts_parameterized_sequence_per_turn() {
+    let messages = fetch_and_save_turn("https://a.example", false);
+    let sequences = extract_tool_sequences(&messages);
+    assert_eq!(sequences.len(), 1);
+    assert_eq!(sequences[0].signature(), "web_fetch -> save_note");
+
+    let rendered = sequences[0].render();
+    assert!(rendered.contains("1. web_fetch(url={url})"));
+    assert!(rendered.contains("2. save_note(body={body}, title={title})"));
+    assert!(!rendered.contains("a.example"));
+}
+
+#[test]
+fn drops_turns_with_failed_tools() {
+    let messages = fetch_and_save_turn("https://a.example", true);
+    assert!(extract_tool_sequences(&messages).is_empty());
+}
+
+#[test]
+fn drops_single_tool_turns() {
+    let messages = vec![
+        user("what time is it"),
+        call("c1", "clock", serde_json::json!({})),
+        result("c1", false),
+    ];
+    assert!(extract_tool_sequences(&messages).is_empty());
+}
+
+#[test]
+fn splits_sequences_on_user_messages() {
+    let mut messages = fetch_and_save_turn("https://a.example", false);
+    messages.extend(fetch_and_save_turn("https://b.example", false));
+    assert_eq!(extract_tool_sequences(&messages).len(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// stage_skills
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn single_sighting_is_tracked_but_not_proposed() {
+    let engine = setup_engine().await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let messages = fetch_and_save_turn("https://a.example", false);
+    let result = stage_skills(&messages, &engine, "user_1", &tx, 1)
+        .await
+        .expect("staging should succeed");
+    flush().await;
+
+    assert_eq!(result.tracked, 1);
+    assert_eq!(result.proposed, 0);
+    assert!(rx.try_recv().is_err());
+
+    let pending = engine
+        .search_by_status(MemoryStatus::Pending, 10)
+        .await
+        .expect("search should succeed");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].kind, MemoryKind::Skill);
+    assert!(is_unripe_skill(&pending[0]));
+}
+
+#[tokio::test]
+async fn repeated_sequence_across_sessions_is_proposed() {
+    let engine = setup_engine().await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let first = fetch_and_save_turn("https://a.example", false);
+    stage_skills(&first, &engine, "user_1", &tx, 1)
+        .await
+        .expect("first staging should succeed");
+    flush().await;
+
+    let second = fetch_and_save_turn("https://b.example", false);
+    let result = stage_skills(&second, &engine, "user_1", &tx, 1)
+        .await
+        .expect("second staging should succeed");
+    flush().await;
+
+    assert_eq!(result.tracked, 0);
+    assert_eq!(result.proposed, 1);
+
+    let msg = rx.try_recv().expect("proposal should be sent");
+    assert!(matches!(msg.keyboard, Some(Keyboard::Skill(_))));
+    assert!(msg
+        .text
+        .expect("proposal should have text")
+        .contains("web_fetch -&gt; save_note"));
+
+    let pending = engine
+        .search_by_status(MemoryStatus::Pending, 10)
+        .await
+        .expect("search should succeed");
+    assert_eq!(pending.len(), 1);
+    assert!(!is_unripe_skill(&pending[0]));
+}
+
+#[tokio::test]
+async fn repeated_sequence_in_one_snapshot_is_proposed() {
+    let engine = setup_engine().await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let mut messages = fetch_and_save_turn("https://a.example", false);
+    messages.extend(fetch_and_save_turn("https://b.example", false));
+    let result = stage_skills(&messages, &engine, "user_1", &tx, 1)
+        .await
+        .expect("staging should succeed");
+
+    assert_eq!(result.tracked, 1);
+    assert_eq!(result.proposed, 1);
+    let msg = rx.try_recv().expect("proposal should be sent");
+    assert!(matches!(msg.keyboard, Some(Keyboard::Skill(_))));
+}
+
+#[tokio::test]
+async fn active_skill_is_not_proposed_again() {
+    let engine = setup_engine().await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let mut messages = fetch_and_save_turn("https://a.example", false);
+    messages.extend(fetch_and_save_turn("https://b.example", false));
+    stage_skills(&messages, &engine, "user_1", &tx, 1)
+        .await
+        .expect("staging should succeed");
+    let msg = rx.try_recv().expect("proposal should be sent");
+    let Some(Keyboard::Skill(id)) = msg.keyboard else {
+        panic!("proposal should carry memory id");
+    };
+
+    engine
+        .update_memory_status(id, MemoryStatus::Active)
+        .await
+        .expect("approve should succeed");
+    flush().await;
+
+    let result = stage_skills(&messages, &engine, "user_1", &tx, 1)
+        .await
+        .expect("staging should succeed");
+    assert_eq!(result.tracked, 0);
+    assert_eq!(result.proposed, 0);
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn resolving_a_proposal_only_touches_proposed_skills() {
+    let engine = setup_engine().await;
+    let (tx, mut rx) = mpsc::channel(8);
+
+    let fact = engine
+        .save_memory_returning_id(Memory {
+            id: None,
+            kind: MemoryKind::Fact,
+            content: "user lives in Berlin".to_owned(),
+            metadata: None,
+            status: MemoryStatus::Pending,
+            source: MemorySource::Observer,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .expect("save should succeed");
+    assert!(!resolve_proposal(&engine, fact, true)
+        .await
+        .expect("resolve should not error"));
+
+    let mut messages = fetch_and_save_turn("https://a.example", false);
+    messages.extend(fetch_and_save_turn("https://b.example", false));
+    stage_skills(&messages, &engine, "user_1", &tx, 1)
+        .await
+        .expect("staging should succeed");
+    let msg = rx.try_recv().expect("proposal should be sent");
+    let Some(Keyboard::Skill(id)) = msg.keyboard else {
+        panic!("proposal should carry memory id");
+    };
+    assert!(resolve_proposal(&engine, id, true)
+        .await
+        .expect("resolve should succeed"));
+    flush().await;
+
+    let fact = engine
+        .get_memory(fact)
+        .await
+        .expect("lookup")
+        .expect("fact");
+    assert_eq!(fact.status, MemoryStatus::Pending);
+    let skill = engine.get_memory(id).await.expect("lookup").expect("skill");
+    assert_eq!(skill.status, MemoryStatus::Active);
+    assert!(!resolve_proposal(&engine, id, false)
+        .await
+        .expect("resolve should not error"));
+}
@@ -5,7 +5,9 @@ use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use wintermute::config::{LearningConfig, PromotionMode};
 use wintermute::memory::{Memory, MemoryEngine, MemoryKind, MemorySource, MemoryStatus};
 use wintermute::observer::extractor::{Extraction, ExtractionKind};
-use wintermute::observer::staging::{check_promotions, stage_extractions, undo_last_promotion};
+use wintermute::observer::staging::{
+    check_promotions, format_provenance, stage_extractions, undo_last_promotion,
+};
 
 async fn setup_engine() -> MemoryEngine {
     let opts = SqliteConnectOptions::new()
@@ -39,6 +41,7 @@ fn fact(content: &str, confidence: f64) -> Extraction {
         kind: ExtractionKind::Fact,
         content: content.to_owned(),
         confidence,
+        source_span: None,
     }
 }
 
@@ -47,6 +50,7 @@ fn procedure(content: &str) -> Extraction {
         kind: ExtractionKind::Procedure,
         content: content.to_owned(),
         confidence: 0.8,
+        source_span: None,
     }
 }
 
@@ -197,7 +201,10 @@ fn auto_config(threshold: u32) -> LearningConfig {
         enabled: true,
         promotion_mode: PromotionMode::Auto,
         auto_promote_threshold: threshold,
+        auto_promote_min_confidence: 0.8,
         reflection: true,
+        feedback: true,
+        skills: true,
     }
 }
 
@@ -278,7 +285,10 @@ async fn promotion_mode_off_is_noop() {
         enabled: true,
         promotion_mode: PromotionMode::Off,
         auto_promote_threshold: 1,
+        auto_promote_min_confidence: 0.8,
         reflection: true,
+        feedback: true,
+        skills: true,
     };
 
     let result = check_promotions(&engine, &off_config, &tx, 12345)
@@ -290,3 +300,78 @@ async fn promotion_mode_off_is_noop() {
 
     engine.shutdown().await;
 }
+
+#[tokio::test]
+async fn auto_promote_leaves_low_confidence_pending() {
+    let engine = setup_engine().await;
+    let (tx, _rx) = tokio::sync::mpsc::channel(16);
+
+    let extractions = vec![
+        fact("the user might enjoy jazz", 0.6),
+        fact("the user might enjoy jazz", 0.65),
+    ];
+    stage_extractions(&extractions, &engine, "sess-1")
+        .await
+        .expect("staging should succeed");
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let result = check_promotions(&engine, &auto_config(2), &tx, 12345)
+        .await
+        .expect("promotion should succeed");
+    assert_eq!(result.promoted, 0, "low-confidence memories need review");
+
+    engine.shutdown().await;
+}
+
+#[tokio::test]
+async fn staged_memory_records_source_span() {
+    let engine = setup_engine().await;
+
+    let mut extraction = fact("user lives in Berlin", 0.9);
+    extraction.source_span = Some("I moved to Berlin last year".to_owned());
+    stage_extractions(&[extraction], &engine, "sess-1")
+        .await
+        .expect("staging should succeed");
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let pending = engine
+        .search_by_status(MemoryStatus::Pending, 10)
+        .await
+        .expect("search should succeed");
+    assert_eq!(pending.len(), 1);
+    let meta = pending[0]
+        .metadata
+        .as_ref()
+        .expect("metadata should be set");
+    assert_eq!(meta["source_span"], "I moved to Berlin last year");
+    assert_eq!(meta["session_id"], "sess-1");
+
+    engine.shutdown().await;
+}
+
+#[test]
+fn provenance_mentions_origin_date_and_quote() {
+    let mem = Memory {
+        id: Some(1),
+        kind: MemoryKind::Fact,
+        content: "user lives in Berlin".to_owned(),
+        metadata: Some(serde_json::json!({
+            "confidence": 0.85,
+            "source_span": "I moved to <Berlin>",
+        })),
+        status: MemoryStatus::Pending,
+        source: MemorySource::Observer,
+        created_at: Some("2026-02-03 10:15:00".to_owned()),
+        updated_at: None,
+    };
+
+    let text = format_provenance(&mem);
+    assert!(
+        text.starts_with("learned from conversation on Feb 3"),
+        "got: {text}"
+    );
+    assert!(text.contains("confidence 0.85"));
+    assert!(text.contains("&lt;Berlin&gt;"), "quote should be escaped");
+}
@@ -2,6 +2,8 @@
 
 #[path = "providers/anthropic_test.rs"]
 mod anthropic_test;
+#[path = "providers/health_test.rs"]
+mod health_test;
 #[path = "providers/http_response_test.rs"]
 mod http_response_test;
 #[path = "providers/ollama_test.rs"]
@@ -10,6 +12,8 @@ mod ollama_test;
 mod openai_test;
 #[path = "providers/provider_contract_test.rs"]
 mod provider_contract_test;
+#[path = "providers/replay_test.rs"]
+mod replay_test;
 #[path = "providers/router_test.rs"]
 mod router_test;
 #[path = "providers/types_test.rs"]
@@ -0,0 +1,130 @@
+//! Tests for `src/providers/health.rs` — provider call outcome tracking.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use wintermute::providers::health::{ProviderHealth, ProviderOutcome};
+use wintermute::providers::router::ModelRouter;
+use wintermute::providers::{
+    CompletionRequest, CompletionResponse, ContentPart, LlmProvider, ProviderError, StopReason,
+    UsageStats,
+};
+
+/// Fails the first `failures` calls with the given error, then succeeds.
+struct FlakyProvider {
+    failures: usize,
+    calls: AtomicUsize,
+    body: &'static str,
+}
+
+#[async_trait]
+impl LlmProvider for FlakyProvider {
+    async fn complete(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        if call < self.failures {
+            return Err(ProviderError::HttpStatus {
+                status: 529,
+                body: self.body.to_owned(),
+            });
+        }
+        Ok(CompletionResponse {
+            content: vec![ContentPart::Text {
+                text: "ok".to_owned(),
+            }],
+            stop_reason: StopReason::EndTurn,
+            usage: UsageStats {
+                input_tokens: 1,
+                output_tokens: 1,
+            },
+            model: "mock".to_owned(),
+        })
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        false
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn model_id(&self) -> &str {
+        "mock/flaky"
+    }
+}
+
+fn request() -> CompletionRequest {
+    CompletionRequest {
+        messages: Vec::new(),
+        system: None,
+        tools: Vec::new(),
+        max_tokens: None,
+        stop_sequences: Vec::new(),
+    }
+}
+
+fn flaky_router(failures: usize, body: &'static str) -> ModelRouter {
+    let provider = Arc::new(FlakyProvider {
+        failures,
+        calls: AtomicUsize::new(0),
+        body,
+    });
+    ModelRouter::for_testing("mock/flaky".to_owned(), provider)
+}
+
+#[test]
+fn unknown_spec_has_no_failures() {
+    let health = ProviderHealth::default();
+    assert_eq!(health.outcome("mock/none"), ProviderOutcome::default());
+}
+
+#[test]
+fn success_clears_failures() {
+    let health = ProviderHealth::default();
+    health.record_failure("mock/a", "boom");
+    health.record_failure("mock/a", "boom again");
+    let outcome = health.outcome("mock/a");
+    assert_eq!(outcome.consecutive_failures, 2);
+    assert_eq!(outcome.last_error.as_deref(), Some("boom again"));
+    assert!(outcome.last_failure_at.is_some());
+
+    health.record_success("mock/a");
+    let outcome = health.outcome("mock/a");
+    assert_eq!(outcome.consecutive_failures, 0);
+    assert!(outcome.last_error.is_none());
+    assert!(outcome.last_success_at.is_some());
+}
+
+#[tokio::test]
+async fn router_records_provider_outcomes() {
+    let router = flaky_router(1, "overloaded");
+    let provider = router.default_provider();
+    assert_eq!(provider.model_id(), "mock/flaky");
+
+    assert!(provider.complete(request()).await.is_err());
+    let outcomes = router.provider_outcomes();
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].0, "mock/flaky");
+    assert_eq!(outcomes[0].1.consecutive_failures, 1);
+    assert!(outcomes[0]
+        .1
+        .last_error
+        .as_deref()
+        .is_some_and(|e| e.contains("overloaded")));
+
+    assert!(provider.complete(request()).await.is_ok());
+    assert_eq!(router.provider_outcomes()[0].1.consecutive_failures, 0);
+}
+
+#[tokio::test]
+async fn context_overflow_is_not_a_provider_failure() {
+    let router = flaky_router(1, "context_length_exceeded");
+    let provider = router.default_provider();
+    assert!(provider.complete(request()).await.is_err());
+    assert_eq!(router.provider_outcomes()[0].1.consecutive_failures, 0);
+}
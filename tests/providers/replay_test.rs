@@ -0,0 +1,162 @@
+//! Tests for `src/providers/replay.rs` — recording and replaying completions.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use wintermute::config::{FixtureConfig, FixtureMode, ModelsConfig};
+use wintermute::credentials::Credentials;
+use wintermute::executor::redactor::Redactor;
+use wintermute::providers::replay::{fixture_path, RecordingProvider, ReplayProvider};
+use wintermute::providers::router::ModelRouter;
+use wintermute::providers::{
+    CompletionRequest, CompletionResponse, ContentPart, LlmProvider, Message, MessageContent,
+    ProviderError, Role, StopReason, UsageStats,
+};
+
+const SECRET: &str = "sk-live-0123456789abcdef";
+
+/// Answers with a tool call that leaks a secret, then with text.
+#[derive(Debug, Default)]
+struct ScriptedProvider {
+    calls: std::sync::atomic::AtomicU32,
+}
+
+#[async_trait]
+impl LlmProvider for ScriptedProvider {
+    async fn complete(
+        &self,
+        _request: CompletionRequest,
+    ) -> Result<CompletionResponse, ProviderError> {
+        let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let (content, stop_reason) = if call == 0 {
+            (
+                vec![ContentPart::ToolUse {
+                    id: "call-1".to_owned(),
+                    name: "web_request".to_owned(),
+                    input: json!({ "headers": { "Authorization": format!("Bearer {SECRET}") } }),
+                }],
+                StopReason::ToolUse,
+            )
+        } else {
+            (
+                vec![ContentPart::Text {
+                    text: "done".to_owned(),
+                }],
+                StopReason::EndTurn,
+            )
+        };
+        Ok(CompletionResponse {
+            content,
+            stop_reason,
+            usage: UsageStats {
+                input_tokens: 10,
+                output_tokens: 5,
+            },
+            model: "test/scripted".to_owned(),
+        })
+    }
+
+    fn supports_tool_calling(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn model_id(&self) -> &str {
+        "test/scripted"
+    }
+}
+
+fn request(text: &str) -> CompletionRequest {
+    CompletionRequest {
+        messages: vec![Message {
+            role: Role::User,
+            content: MessageContent::Text(text.to_owned()),
+        }],
+        system: None,
+        tools: Vec::new(),
+        max_tokens: None,
+        stop_sequences: Vec::new(),
+    }
+}
+
+#[test]
+fn fixture_path_flattens_model_spec() {
+    let path = fixture_path(std::path::Path::new("/fx"), "ollama/qwen3:8b");
+    assert_eq!(path, std::path::PathBuf::from("/fx/ollama_qwen3_8b.jsonl"));
+}
+
+#[tokio::test]
+async fn recorded_completions_are_redacted_and_replay_in_order() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let recorder = RecordingProvider::create(
+        "test/scripted",
+        Arc::new(ScriptedProvider::default()),
+        dir.path(),
+        Redactor::new(vec![SECRET.to_owned()]),
+    )
+    .expect("recorder");
+
+    let first = recorder
+        .complete(request(&format!("use {SECRET}")))
+        .await
+        .expect("first call");
+    // The live caller still gets the real response.
+    assert!(matches!(first.content[0], ContentPart::ToolUse { .. }));
+    recorder.complete(request("go on")).await.expect("second");
+
+    let written = std::fs::read_to_string(recorder.path()).expect("fixture written");
+    assert_eq!(written.lines().count(), 2);
+    assert!(!written.contains(SECRET), "fixture must be redacted");
+    assert!(written.contains("[REDACTED]"));
+
+    let replay = ReplayProvider::open("test/scripted", dir.path()).expect("replay");
+    assert_eq!(replay.remaining(), 2);
+    let replayed = replay.complete(request("anything")).await.expect("first");
+    assert_eq!(replayed.stop_reason, StopReason::ToolUse);
+    assert_eq!(replayed.usage.input_tokens, 10);
+    let replayed = replay.complete(request("anything")).await.expect("second");
+    assert_eq!(
+        replayed.content,
+        vec![ContentPart::Text {
+            text: "done".to_owned()
+        }]
+    );
+
+    let exhausted = replay.complete(request("more")).await;
+    assert!(matches!(exhausted, Err(ProviderError::Unavailable(_))));
+}
+
+#[test]
+fn replay_router_loads_fixtures_without_credentials() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(fixture_path(dir.path(), "anthropic/claude-sonnet"), "").expect("write");
+
+    let models = ModelsConfig {
+        default: "anthropic/claude-sonnet".to_owned(),
+        roles: HashMap::from([("observer".to_owned(), "anthropic/claude-haiku".to_owned())]),
+        skills: HashMap::new(),
+        fixtures: FixtureConfig {
+            mode: FixtureMode::Replay,
+            dir: Some(dir.path().to_path_buf()),
+        },
+    };
+    let router =
+        ModelRouter::from_config(&models, &Credentials::default()).expect("router should init");
+    assert!(router.has_model("anthropic/claude-sonnet"));
+    // No fixture file, so the override is not loaded.
+    assert!(!router.has_model("anthropic/claude-haiku"));
+}
+
+#[test]
+fn invalid_fixture_line_is_an_error() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(fixture_path(dir.path(), "a/b"), "{not json}\n").expect("write");
+    let err = ReplayProvider::open("a/b", dir.path()).expect_err("should fail");
+    assert!(err.to_string().contains(":1"));
+}
@@ -11,6 +11,7 @@ fn ollama_default_config() -> ModelsConfig {
         default: "ollama/qwen3:8b".to_owned(),
         roles: HashMap::new(),
         skills: HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     }
 }
 
@@ -19,6 +20,7 @@ fn openai_default_config() -> ModelsConfig {
         default: "openai/gpt-5".to_owned(),
         roles: HashMap::new(),
         skills: HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     }
 }
 
@@ -30,6 +32,7 @@ fn multi_provider_config() -> (ModelsConfig, Credentials) {
             "deploy_check".to_owned(),
             "anthropic/claude-haiku-4-5-20251001".to_owned(),
         )]),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let mut vars = BTreeMap::new();
     vars.insert("ANTHROPIC_API_KEY".to_owned(), "test-key".to_owned());
@@ -69,6 +72,7 @@ fn falls_back_to_default_when_skill_provider_unavailable() {
             "deploy_check".to_owned(),
             "anthropic/claude-haiku-4-5-20251001".to_owned(),
         )]),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let credentials = Credentials::default();
     let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
@@ -76,6 +80,38 @@ fn falls_back_to_default_when_skill_provider_unavailable() {
     assert_eq!(resolved, "ollama/qwen3:8b");
 }
 
+#[test]
+fn set_overrides_replaces_role_and_skill_overrides() {
+    let (models, credentials) = multi_provider_config();
+    let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
+    assert!(!router.overrides_differ(&models.roles, &models.skills));
+
+    let roles = HashMap::from([(
+        "observer".to_owned(),
+        "anthropic/claude-haiku-4-5-20251001".to_owned(),
+    )]);
+    let missing = router.set_overrides(roles.clone(), HashMap::new());
+    assert!(missing.is_empty());
+    assert!(!router.overrides_differ(&roles, &HashMap::new()));
+    assert_eq!(
+        router.resolve_spec(Some("observer"), Some("deploy_check")),
+        "anthropic/claude-haiku-4-5-20251001"
+    );
+}
+
+#[test]
+fn set_overrides_reports_unloaded_specs() {
+    let router = ModelRouter::from_config(&ollama_default_config(), &Credentials::default())
+        .expect("router should init");
+    let skills = HashMap::from([("deploy_check".to_owned(), "openai/gpt-5".to_owned())]);
+    let missing = router.set_overrides(HashMap::new(), skills);
+    assert_eq!(missing, vec!["openai/gpt-5".to_owned()]);
+    assert_eq!(
+        router.resolve_spec(None, Some("deploy_check")),
+        "ollama/qwen3:8b"
+    );
+}
+
 #[test]
 fn has_model_returns_true_for_registered() {
     let models = ollama_default_config();
@@ -121,6 +157,7 @@ fn router_errors_on_unavailable_default() {
         default: "anthropic/claude-sonnet".to_owned(),
         roles: HashMap::new(),
         skills: HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let credentials = Credentials::default(); // no API key
     let result = ModelRouter::from_config(&models, &credentials);
@@ -146,6 +183,7 @@ fn from_config_with_auth_uses_pre_resolved_anthropic_auth() {
         default: "anthropic/claude-haiku-4-5-20251001".to_owned(),
         roles: HashMap::new(),
         skills: HashMap::new(),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     // No Anthropic credentials in .env — only the pre-resolved auth should work.
     let credentials = Credentials::default();
@@ -193,6 +231,7 @@ fn resolves_openai_skill_override_when_credentials_present() {
         default: "ollama/qwen3:8b".to_owned(),
         roles: HashMap::new(),
         skills: HashMap::from([("gpt_task".to_owned(), "openai/gpt-5".to_owned())]),
+        fixtures: wintermute::config::FixtureConfig::default(),
     };
     let mut vars = BTreeMap::new();
     vars.insert("OPENAI_API_KEY".to_owned(), "api-key".to_owned());
@@ -200,3 +239,23 @@ fn resolves_openai_skill_override_when_credentials_present() {
     let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
     assert_eq!(router.resolve_spec(None, Some("gpt_task")), "openai/gpt-5");
 }
+
+#[test]
+fn has_distinct_role_ignores_missing_and_default_roles() {
+    let mut models = ollama_default_config();
+    let credentials = Credentials::default();
+    let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
+    assert!(!router.has_distinct_role("budget"));
+
+    models
+        .roles
+        .insert("budget".to_owned(), "ollama/qwen3:8b".to_owned());
+    let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
+    assert!(!router.has_distinct_role("budget"));
+
+    models
+        .roles
+        .insert("budget".to_owned(), "ollama/qwen3:1.7b".to_owned());
+    let router = ModelRouter::from_config(&models, &credentials).expect("router should init");
+    assert!(router.has_distinct_role("budget"));
+}
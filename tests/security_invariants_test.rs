@@ -108,7 +108,7 @@ fn security_invariant_3_container_network_via_proxy() -> Result<(), Box<dyn std:
     let content = std::fs::read_to_string(docker_src)?;
     // The sandbox joins the egress proxy network (or falls back to none for tests).
     assert!(
-        content.contains("network_name") && content.contains("proxy_address"),
+        content.contains("network_name") && content.contains("proxy_url"),
         "docker sandbox must accept network and proxy configuration for egress proxy"
     );
     // The egress module must define the proxy network and enforce domain allowlist.
@@ -142,7 +142,7 @@ fn security_invariant_7_redactor_is_tool_output_chokepoint(
     let tools_src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tools/mod.rs");
     let content = std::fs::read_to_string(tools_src)?;
     assert!(
-        content.contains("self.redactor.redact(&raw_result.content)"),
+        content.contains("self.redactor.redact_output(&raw_result.content)"),
         "tool router must redact all tool output before returning"
     );
     Ok(())
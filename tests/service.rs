@@ -0,0 +1,4 @@
+//! Integration tests for `src/service.rs`.
+
+#[path = "service/service_test.rs"]
+mod service_test;
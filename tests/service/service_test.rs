@@ -0,0 +1,103 @@
+//! Tests for `src/service.rs` — generated systemd units and launchd plists.
+
+use std::path::PathBuf;
+
+use wintermute::service::{
+    enable_command, launchd_plist, stop_command, systemd_unit, Service, ServiceManager,
+    ServicePaths, SYSTEMD_AGENT_UNIT,
+};
+
+fn paths() -> ServicePaths {
+    ServicePaths {
+        binary: PathBuf::from("/opt/wintermute/bin/wintermute"),
+        root: PathBuf::from("/home/case/.wintermute"),
+    }
+}
+
+#[test]
+fn file_names_match_shipped_service_files() {
+    assert_eq!(
+        Service::Agent.file_name(ServiceManager::Systemd),
+        SYSTEMD_AGENT_UNIT
+    );
+    assert_eq!(
+        Service::Flatline.file_name(ServiceManager::Systemd),
+        "flatline.service"
+    );
+    assert_eq!(
+        Service::Agent.file_name(ServiceManager::Launchd),
+        format!("{}.plist", Service::Agent.label())
+    );
+    assert_eq!(
+        Service::Flatline.file_name(ServiceManager::Launchd),
+        format!("{}.plist", Service::Flatline.label())
+    );
+}
+
+#[test]
+fn systemd_unit_uses_absolute_paths_and_restarts() {
+    let unit = systemd_unit(Service::Agent, &paths());
+    assert!(unit.contains("ExecStart=/opt/wintermute/bin/wintermute start\n"));
+    assert!(unit.contains("WorkingDirectory=/home/case/.wintermute\n"));
+    assert!(unit.contains("EnvironmentFile=-/home/case/.wintermute/.env\n"));
+    assert!(unit.contains("Restart=on-failure\n"));
+    assert!(unit.contains("RestartSec=10\n"));
+    assert!(unit.contains("WantedBy=default.target\n"));
+    assert!(!unit.contains('%'));
+}
+
+#[test]
+fn flatline_unit_starts_after_the_agent() {
+    let unit = systemd_unit(Service::Flatline, &paths());
+    assert!(unit.contains("Description=Flatline Supervisor for Wintermute\n"));
+    assert!(unit.contains("After=wintermute.service"));
+}
+
+#[test]
+fn systemd_unit_quotes_paths_with_spaces() {
+    let paths = ServicePaths {
+        binary: PathBuf::from("/Users/case ii/bin/wintermute"),
+        root: PathBuf::from("/Users/case ii/.wintermute"),
+    };
+    let unit = systemd_unit(Service::Agent, &paths);
+    assert!(unit.contains("ExecStart=\"/Users/case ii/bin/wintermute\" start\n"));
+}
+
+#[test]
+fn launchd_plist_sources_env_and_logs_under_root() {
+    let plist = launchd_plist(Service::Flatline, &paths());
+    assert!(plist.contains("<string>com.wintermute.flatline</string>"));
+    assert!(plist.contains(
+        "set -a; source '/home/case/.wintermute/.env' 2&gt;/dev/null; set +a; \
+         exec '/opt/wintermute/bin/wintermute' start"
+    ));
+    assert!(plist.contains("<string>/home/case/.wintermute/data/logs/flatline.err.log</string>"));
+    assert!(plist.contains("<key>SuccessfulExit</key>"));
+}
+
+#[test]
+fn launchd_plist_escapes_quotes_in_paths() {
+    let paths = ServicePaths {
+        binary: PathBuf::from("/Users/o'neil/bin/wintermute"),
+        root: PathBuf::from("/Users/o'neil/.wintermute"),
+    };
+    let plist = launchd_plist(Service::Agent, &paths);
+    assert!(plist.contains(r"exec '/Users/o'\''neil/bin/wintermute' start"));
+}
+
+#[test]
+fn printed_commands_target_the_right_service() {
+    let path = PathBuf::from("/home/case/Library/LaunchAgents/com.wintermute.agent.plist");
+    assert_eq!(
+        enable_command(ServiceManager::Launchd, Service::Agent, &path),
+        "launchctl load -w /home/case/Library/LaunchAgents/com.wintermute.agent.plist"
+    );
+    assert!(
+        enable_command(ServiceManager::Systemd, Service::Agent, &path)
+            .ends_with("systemctl --user enable --now wintermute")
+    );
+    assert_eq!(
+        stop_command(ServiceManager::Launchd, Service::Flatline),
+        "launchctl remove com.wintermute.flatline"
+    );
+}
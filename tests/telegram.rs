@@ -2,11 +2,15 @@
 
 #[path = "telegram/commands_test.rs"]
 mod commands_test;
+#[path = "telegram/dedup_test.rs"]
+mod dedup_test;
 #[path = "telegram/input_guard_test.rs"]
 mod input_guard_test;
 #[path = "telegram/media_test.rs"]
 mod media_test;
 #[path = "telegram/no_reply_test.rs"]
 mod no_reply_test;
+#[path = "telegram/outbox_test.rs"]
+mod outbox_test;
 #[path = "telegram/ui_test.rs"]
 mod ui_test;
@@ -2,6 +2,7 @@
 
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 
+use wintermute::memory::usage::UsageEntry;
 use wintermute::memory::MemoryEngine;
 use wintermute::telegram::commands;
 
@@ -55,6 +56,53 @@ fn budget_returns_formatted_budget_string() {
     assert!(result.contains("5000000"));
 }
 
+#[tokio::test]
+async fn budget_detail_breaks_usage_down_by_model_and_role() {
+    let engine = setup_engine().await;
+    sqlx::raw_sql(include_str!("../../migrations/016_usage_ledger.sql"))
+        .execute(engine.pool())
+        .await
+        .expect("016 should apply");
+    for (role, model, tokens) in [
+        ("agent", "anthropic/a", 300),
+        ("observer", "ollama/<b>", 40),
+    ] {
+        engine
+            .record_usage(UsageEntry {
+                role: Some(role.to_owned()),
+                model: model.to_owned(),
+                session_id: None,
+                input_tokens: tokens,
+                output_tokens: 0,
+                latency_ms: 50,
+                success: true,
+            })
+            .await
+            .expect("record should succeed");
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let result = commands::handle_budget_detail(engine.pool(), chrono::Utc::now()).await;
+    assert!(result.contains("Last 7 days: 340 tokens"));
+    assert!(result.contains("Last 30 days: 340 tokens"));
+    assert!(result.contains("By model"));
+    assert!(result.contains("<code>anthropic/a</code>: 300 tokens"));
+    assert!(result.contains("ollama/&lt;b&gt;"));
+    assert!(result.contains("<code>observer</code>: 40 tokens"));
+}
+
+#[tokio::test]
+async fn budget_detail_without_usage() {
+    let engine = setup_engine().await;
+    sqlx::raw_sql(include_str!("../../migrations/016_usage_ledger.sql"))
+        .execute(engine.pool())
+        .await
+        .expect("016 should apply");
+    let result = commands::handle_budget_detail(engine.pool(), chrono::Utc::now()).await;
+    assert!(result.contains("Last 7 days: no calls"));
+    assert!(!result.contains("By model"));
+}
+
 #[tokio::test]
 async fn memory_undo_with_no_observer_memories() {
     let engine = setup_engine().await;
@@ -71,6 +119,30 @@ async fn memory_pending_with_no_pending() {
     engine.shutdown().await;
 }
 
+#[tokio::test]
+async fn remember_and_forget_require_arguments() {
+    let engine = setup_engine().await;
+    assert!(commands::handle_remember(&engine, "user_1", "  ")
+        .await
+        .starts_with("Usage:"));
+    let err = commands::handle_forget(&engine, "")
+        .await
+        .expect_err("empty query should be rejected");
+    assert!(err.starts_with("Usage:"));
+    engine.shutdown().await;
+}
+
+#[tokio::test]
+async fn forget_with_no_matches_explains() {
+    let engine = setup_engine().await;
+    let err = commands::handle_forget(&engine, "<nothing>")
+        .await
+        .expect_err("no matches expected");
+    assert!(err.contains("No memories match"));
+    assert!(err.contains("&lt;nothing&gt;"));
+    engine.shutdown().await;
+}
+
 #[tokio::test]
 async fn backup_trigger_creates_backup() {
     let tmp = tempfile::tempdir().expect("should create temp dir");
@@ -0,0 +1,54 @@
+//! Tests for `src/telegram/dedup.rs`.
+
+use chrono::{Duration, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::memory::MemoryEngine;
+use wintermute::telegram::dedup::first_delivery;
+
+async fn setup_memory() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    sqlx::raw_sql(include_str!("../../migrations/013_telegram_updates.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("memory engine should start")
+}
+
+#[tokio::test]
+async fn redelivered_update_is_detected() {
+    let memory = setup_memory().await;
+    let now = Utc::now();
+
+    assert!(first_delivery(&memory, 100, now).await.expect("record"));
+    assert!(!first_delivery(&memory, 100, now).await.expect("record"));
+    assert!(first_delivery(&memory, 101, now).await.expect("record"));
+}
+
+#[tokio::test]
+async fn ids_outside_the_window_are_forgotten() {
+    let memory = setup_memory().await;
+    let then = Utc::now();
+    let two_days_later = then + Duration::days(2);
+
+    assert!(first_delivery(&memory, 7, then).await.expect("record"));
+    assert!(first_delivery(&memory, 8, two_days_later)
+        .await
+        .expect("record"));
+
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM telegram_updates")
+        .fetch_one(memory.pool())
+        .await
+        .expect("count");
+    assert_eq!(remaining, 1, "update 7 should have been pruned");
+}
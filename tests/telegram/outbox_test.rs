@@ -0,0 +1,187 @@
+//! Tests for `src/telegram/outbox.rs`.
+
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use wintermute::agent::{Keyboard, TelegramOutbound};
+use wintermute::memory::MemoryEngine;
+use wintermute::telegram::outbox;
+
+async fn setup_memory() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+
+    sqlx::raw_sql(include_str!("../../migrations/012_telegram_outbox.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("memory engine should start")
+}
+
+fn message(chat_id: i64, text: &str) -> TelegramOutbound {
+    TelegramOutbound {
+        user_id: chat_id,
+        text: Some(text.to_owned()),
+        file_path: None,
+        approval_keyboard: None,
+        keyboard: None,
+    }
+}
+
+fn far_future() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0)
+        .single()
+        .expect("valid date")
+}
+
+#[tokio::test]
+async fn queued_message_round_trips_with_keyboards() {
+    let memory = setup_memory().await;
+    let msg = TelegramOutbound {
+        approval_keyboard: Some(("a1".to_owned(), "run it".to_owned())),
+        keyboard: Some(Keyboard::Review(3, 4)),
+        file_path: Some("/tmp/shot.png".to_owned()),
+        ..message(7, "<b>approve?</b>")
+    };
+    let id = outbox::enqueue(&memory, &msg).await.expect("enqueue");
+
+    let due = outbox::due(memory.pool(), Utc::now(), 10)
+        .await
+        .expect("due");
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, id);
+    assert_eq!(due[0].attempts, 0);
+    assert_eq!(due[0].message, msg);
+    assert!(outbox::has_pending(memory.pool(), 7)
+        .await
+        .expect("pending"));
+    assert!(!outbox::has_pending(memory.pool(), 8)
+        .await
+        .expect("pending"));
+}
+
+#[tokio::test]
+async fn only_oldest_message_per_chat_is_due() {
+    let memory = setup_memory().await;
+    let first = outbox::enqueue(&memory, &message(1, "first"))
+        .await
+        .expect("enqueue");
+    outbox::enqueue(&memory, &message(1, "second"))
+        .await
+        .expect("enqueue");
+    let other = outbox::enqueue(&memory, &message(2, "other chat"))
+        .await
+        .expect("enqueue");
+
+    let due = outbox::due(memory.pool(), Utc::now(), 10)
+        .await
+        .expect("due");
+    let ids: Vec<i64> = due.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![first, other]);
+
+    outbox::mark_sent(&memory, first).await.expect("sent");
+    let due = outbox::due(memory.pool(), Utc::now(), 10)
+        .await
+        .expect("due");
+    assert_eq!(due[0].message.text.as_deref(), Some("second"));
+    assert_eq!(
+        outbox::pending_count(memory.pool()).await.expect("count"),
+        2
+    );
+}
+
+#[tokio::test]
+async fn failed_head_blocks_its_chat_until_retry_time() {
+    let memory = setup_memory().await;
+    let now = Utc::now();
+    let head = outbox::enqueue(&memory, &message(1, "head"))
+        .await
+        .expect("enqueue");
+    outbox::enqueue(&memory, &message(1, "tail"))
+        .await
+        .expect("enqueue");
+
+    let gave_up = outbox::record_failure(&memory, head, "network down", now, Duration::ZERO)
+        .await
+        .expect("record failure");
+    assert!(!gave_up);
+
+    assert!(outbox::due(memory.pool(), now, 10)
+        .await
+        .expect("due")
+        .is_empty());
+    let later = outbox::due(memory.pool(), far_future(), 10)
+        .await
+        .expect("due");
+    assert_eq!(later.len(), 1);
+    assert_eq!(later[0].id, head);
+    assert_eq!(later[0].attempts, 1);
+}
+
+#[tokio::test]
+async fn message_is_marked_failed_after_max_attempts() {
+    let memory = setup_memory().await;
+    let now = Utc::now();
+    let id = outbox::enqueue(&memory, &message(1, "doomed"))
+        .await
+        .expect("enqueue");
+    let next = outbox::enqueue(&memory, &message(1, "next"))
+        .await
+        .expect("enqueue");
+
+    let mut gave_up = false;
+    for _ in 0..outbox::MAX_ATTEMPTS {
+        gave_up = outbox::record_failure(&memory, id, "timeout", now, Duration::ZERO)
+            .await
+            .expect("record failure");
+    }
+    assert!(gave_up);
+
+    let due = outbox::due(memory.pool(), far_future(), 10)
+        .await
+        .expect("due");
+    assert_eq!(due.len(), 1);
+    assert_eq!(
+        due[0].id, next,
+        "a failed message no longer blocks its chat"
+    );
+}
+
+#[tokio::test]
+async fn partial_delivery_keeps_only_the_rest() {
+    let memory = setup_memory().await;
+    let msg = TelegramOutbound {
+        file_path: Some("/tmp/report.pdf".to_owned()),
+        ..message(1, "here you go")
+    };
+    let id = outbox::enqueue(&memory, &msg).await.expect("enqueue");
+
+    let rest = TelegramOutbound { text: None, ..msg };
+    outbox::update_message(&memory, id, &rest)
+        .await
+        .expect("update");
+
+    let due = outbox::due(memory.pool(), Utc::now(), 10)
+        .await
+        .expect("due");
+    assert_eq!(due[0].message, rest);
+}
+
+#[test]
+fn backoff_doubles_and_is_capped() {
+    assert_eq!(outbox::backoff(1), Duration::from_secs(5));
+    assert_eq!(outbox::backoff(2), Duration::from_secs(10));
+    assert_eq!(outbox::backoff(4), Duration::from_secs(40));
+    assert_eq!(outbox::backoff(30), Duration::from_secs(600));
+    assert_eq!(outbox::backoff(i64::MAX), Duration::from_secs(600));
+}
@@ -1,6 +1,12 @@
 //! Telegram UI formatting tests.
 
-use wintermute::telegram::ui::{approval_keyboard, escape_html, format_budget, format_tool_call};
+use wintermute::telegram::ui::{
+    approval_keyboard, escape_html, feedback_keyboard, forget_keyboard, format_budget,
+    format_tool_call, format_tool_disabled, held_message_keyboard, memory_review_keyboard,
+    parse_forget_callback, parse_held_callback, parse_tool_callback, skill_keyboard,
+    tool_callbacks_fit, tool_disabled_keyboard, ToolAction,
+};
+use wintermute::tools::registry::ToolDisabled;
 
 #[test]
 fn escape_html_escapes_special_chars() {
@@ -73,3 +79,127 @@ fn format_budget_produces_html_with_values() {
     assert!(html.contains("100000"));
     assert!(html.contains("<b>Budget</b>"));
 }
+
+#[test]
+fn feedback_keyboard_has_up_and_down_callbacks() {
+    let kb = feedback_keyboard("turn42");
+    let rows = &kb.inline_keyboard;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].len(), 2);
+
+    let datas: Vec<String> = rows[0]
+        .iter()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["f+:turn42", "f-:turn42"]);
+}
+
+#[test]
+fn memory_review_keyboard_has_four_resolutions() {
+    let kb = memory_review_keyboard(3, 8);
+    let datas: Vec<String> = kb
+        .inline_keyboard
+        .iter()
+        .flatten()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["mr:k:3:8", "mr:o:3:8", "mr:n:3:8", "mr:x:3:8"]);
+}
+
+#[test]
+fn skill_keyboard_has_save_and_discard_callbacks() {
+    let kb = skill_keyboard(12);
+    let datas: Vec<String> = kb.inline_keyboard[0]
+        .iter()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["sk+:12", "sk-:12"]);
+}
+
+#[test]
+fn forget_keyboard_round_trips_memory_ids() {
+    let kb = forget_keyboard(&[4, 19]);
+    let datas: Vec<String> = kb.inline_keyboard[0]
+        .iter()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["mf:4", "mf:19"]);
+    assert_eq!(parse_forget_callback("mf:19"), Some(19));
+    assert_eq!(parse_forget_callback("mr:k:3:8"), None);
+    assert_eq!(parse_forget_callback("mf:x"), None);
+}
+
+#[test]
+fn tool_disabled_keyboard_round_trips_tool_name() {
+    let kb = tool_disabled_keyboard("rss_digest");
+    let datas: Vec<String> = kb.inline_keyboard[0]
+        .iter()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["te:rss_digest", "tf:rss_digest"]);
+    assert_eq!(
+        parse_tool_callback("te:rss_digest"),
+        Some((ToolAction::Enable, "rss_digest"))
+    );
+    assert_eq!(
+        parse_tool_callback("tf:rss_digest"),
+        Some((ToolAction::Fix, "rss_digest"))
+    );
+    assert_eq!(parse_tool_callback("tf:"), None);
+    assert_eq!(parse_tool_callback("mf:3"), None);
+
+    assert!(tool_callbacks_fit(&"a".repeat(61)));
+    assert!(!tool_callbacks_fit(&"a".repeat(62)));
+}
+
+#[test]
+fn held_message_keyboard_round_trips_choice() {
+    let kb = held_message_keyboard();
+    let datas: Vec<String> = kb.inline_keyboard[0]
+        .iter()
+        .map(|b| match &b.kind {
+            teloxide::types::InlineKeyboardButtonKind::CallbackData(data) => data.clone(),
+            _ => panic!("expected CallbackData"),
+        })
+        .collect();
+    assert_eq!(datas, vec!["hm:send", "hm:drop"]);
+    assert_eq!(parse_held_callback("hm:send"), Some(true));
+    assert_eq!(parse_held_callback("hm:drop"), Some(false));
+    assert_eq!(parse_held_callback("hm:other"), None);
+}
+
+#[test]
+fn format_tool_disabled_lists_escaped_errors() {
+    let disabled = ToolDisabled {
+        at: "2026-03-01T00:00:00Z".to_owned(),
+        success_rate: 0.3,
+        window: 10,
+    };
+    let text = format_tool_disabled(
+        "rss_digest",
+        Some(&disabled),
+        &["KeyError: <title>".to_owned()],
+    );
+    assert!(text.contains("<code>rss_digest</code>"));
+    assert!(text.contains("30% success over the last 10 runs"));
+    assert!(text.contains("KeyError: &lt;title&gt;"));
+    assert!(!text.contains("create_tool"));
+
+    let long_name = "a".repeat(64);
+    assert!(format_tool_disabled(&long_name, None, &[]).contains("create_tool"));
+}
@@ -2,6 +2,8 @@
 
 #[path = "tools/browser_bridge_test.rs"]
 mod browser_bridge_test;
+#[path = "tools/browser_sessions_test.rs"]
+mod browser_sessions_test;
 #[path = "tools/browser_test.rs"]
 mod browser_test;
 #[path = "tools/core_test.rs"]
@@ -10,11 +12,25 @@ mod core_test;
 mod create_tool_test;
 #[path = "tools/docker_test.rs"]
 mod docker_test;
+#[path = "tools/email_test.rs"]
+mod email_test;
 #[path = "tools/escalate_test.rs"]
 mod escalate_test;
 #[path = "tools/flatline_test.rs"]
 mod flatline_test;
+#[path = "tools/mcp_test.rs"]
+mod mcp_test;
+#[path = "tools/page_cache_test.rs"]
+mod page_cache_test;
+#[path = "tools/readability_test.rs"]
+mod readability_test;
 #[path = "tools/registry_test.rs"]
 mod registry_test;
+#[path = "tools/rss_test.rs"]
+mod rss_test;
+#[path = "tools/self_test_test.rs"]
+mod self_test_test;
 #[path = "tools/tool_router_test.rs"]
 mod tool_router_test;
+#[path = "tools/web_search_test.rs"]
+mod web_search_test;
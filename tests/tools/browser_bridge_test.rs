@@ -52,3 +52,29 @@ async fn execute_uses_timeout_from_input() {
         "should not wait for the default 30s timeout"
     );
 }
+
+#[tokio::test]
+async fn list_sessions_is_answered_without_the_sidecar() {
+    let bridge = PlaywrightBridge::new("http://127.0.0.1:19999".to_owned());
+    let result = bridge
+        .execute(
+            "list_sessions",
+            &serde_json::json!({"action": "list_sessions"}),
+        )
+        .await
+        .expect("answered locally");
+    assert_eq!(result, r#"{"sessions":[]}"#);
+}
+
+#[tokio::test]
+async fn failed_requests_still_open_a_session_for_the_site() {
+    let bridge = PlaywrightBridge::new("http://127.0.0.1:19999".to_owned())
+        .with_config(&wintermute::config::BrowserConfig::default());
+    let input = serde_json::json!({
+        "action": "navigate",
+        "url": "https://www.example.com/",
+        "timeout_ms": 500
+    });
+    let _ = bridge.execute("navigate", &input).await;
+    assert_eq!(bridge.sessions(), vec!["example.com"]);
+}
@@ -0,0 +1,99 @@
+//! Tests for `src/tools/browser_sessions.rs` — session selection, reuse, and eviction.
+
+use std::time::{Duration, Instant};
+
+use wintermute::tools::browser_sessions::{
+    site_key, validate_session_name, SessionPool, DEFAULT_SESSION,
+};
+
+#[test]
+fn site_key_uses_host_without_www() {
+    assert_eq!(
+        site_key("https://www.GitHub.com/login").as_deref(),
+        Some("github.com")
+    );
+    assert_eq!(
+        site_key("https://docs.rs:8443/x").as_deref(),
+        Some("docs.rs")
+    );
+    assert_eq!(site_key("not a url"), None);
+}
+
+#[test]
+fn session_names_are_file_safe() {
+    assert!(validate_session_name("github.com").is_ok());
+    assert!(validate_session_name("work_sso-1").is_ok());
+    assert!(validate_session_name("").is_err());
+    assert!(validate_session_name("../etc").is_err());
+    assert!(validate_session_name("Work").is_err());
+    assert!(validate_session_name(&"a".repeat(65)).is_err());
+}
+
+#[test]
+fn actions_without_url_reuse_the_active_session() {
+    let pool = SessionPool::new(4, Duration::from_secs(600));
+    let now = Instant::now();
+
+    assert_eq!(pool.acquire(None, None, now).session, DEFAULT_SESSION);
+
+    let lease = pool.acquire(None, Some("https://github.com/login"), now);
+    assert_eq!(lease.session, "github.com");
+    assert!(lease.evicted.is_empty());
+
+    // click/type/extract carry no URL and stay on the same site.
+    assert_eq!(pool.acquire(None, None, now).session, "github.com");
+
+    // An explicit session wins over the URL.
+    let lease = pool.acquire(Some("sso"), Some("https://accounts.example.com"), now);
+    assert_eq!(lease.session, "sso");
+}
+
+#[test]
+fn least_recently_used_session_is_evicted_over_capacity() {
+    let pool = SessionPool::new(2, Duration::from_secs(600));
+    let start = Instant::now();
+
+    pool.acquire(None, Some("https://a.example"), start);
+    pool.acquire(
+        None,
+        Some("https://b.example"),
+        start + Duration::from_secs(1),
+    );
+    pool.acquire(
+        None,
+        Some("https://a.example"),
+        start + Duration::from_secs(2),
+    );
+
+    let lease = pool.acquire(
+        None,
+        Some("https://c.example"),
+        start + Duration::from_secs(3),
+    );
+    assert_eq!(lease.evicted, vec!["b.example".to_owned()]);
+    assert_eq!(pool.sessions(), vec!["c.example", "a.example"]);
+}
+
+#[test]
+fn idle_sessions_are_evicted_and_release_forgets() {
+    let pool = SessionPool::new(4, Duration::from_secs(60));
+    let start = Instant::now();
+
+    pool.acquire(None, Some("https://a.example"), start);
+    let lease = pool.acquire(
+        None,
+        Some("https://b.example"),
+        start + Duration::from_secs(61),
+    );
+    assert_eq!(lease.evicted, vec!["a.example".to_owned()]);
+
+    assert!(pool.release("b.example"));
+    assert!(!pool.release("b.example"));
+    assert!(pool.sessions().is_empty());
+    // With the active session released, URL-less actions fall back to default.
+    assert_eq!(
+        pool.acquire(None, None, start + Duration::from_secs(62))
+            .session,
+        DEFAULT_SESSION
+    );
+}
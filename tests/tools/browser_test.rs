@@ -6,8 +6,8 @@ use serde_json::json;
 use wintermute::agent::policy::RateLimiter;
 use wintermute::config::BrowserConfig;
 use wintermute::tools::browser::{
-    browser_tool_definition, detect_browser, run_browser, validate_browser_input, BrowserBridge,
-    BrowserMode, SIDECAR_PORT,
+    browser_tool_definition, detect_browser, run_browser, screenshot_host_path,
+    validate_browser_input, BrowserBridge, BrowserMode, SIDECAR_PORT,
 };
 
 // ---------------------------------------------------------------------------
@@ -286,3 +286,42 @@ fn browser_mode_debug_and_clone() {
     assert_eq!(mode, cloned);
     assert_eq!(format!("{mode:?}"), "Attached { port: 9222 }");
 }
+
+// ---------------------------------------------------------------------------
+// Session and screenshot tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn validate_browser_input_checks_session_name() {
+    let ok = validate_browser_input(&json!({"action": "navigate", "session": "github.com"}))
+        .expect("valid session");
+    assert_eq!(
+        ok.get("session").and_then(|v| v.as_str()),
+        Some("github.com")
+    );
+
+    assert!(validate_browser_input(&json!({"action": "navigate", "session": "../x"})).is_err());
+    assert!(validate_browser_input(&json!({"action": "close_session"})).is_err());
+    assert!(validate_browser_input(&json!({"action": "list_sessions"})).is_ok());
+}
+
+#[test]
+fn screenshot_host_path_maps_workspace_screenshots_only() {
+    let workspace = std::path::Path::new("/home/u/.wintermute/workspace");
+    assert_eq!(
+        screenshot_host_path(
+            r#"{"path": "/workspace/screenshots/github.com-1.png"}"#,
+            workspace
+        ),
+        Some(workspace.join("screenshots/github.com-1.png"))
+    );
+    assert_eq!(
+        screenshot_host_path(r#"{"path": "/workspace/secrets.txt"}"#, workspace),
+        None
+    );
+    assert_eq!(
+        screenshot_host_path(r#"{"path": "/workspace/screenshots/../.env"}"#, workspace),
+        None
+    );
+    assert_eq!(screenshot_host_path("clicked #submit", workspace), None);
+}
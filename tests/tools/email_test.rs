@@ -0,0 +1,267 @@
+//! Tests for `src/tools/email.rs` — IMAP response parsing, message parsing,
+//! composing, and the send preview.
+
+use wintermute::config::EmailConfig;
+use wintermute::credentials::Credentials;
+use wintermute::executor::redactor::Redactor;
+use wintermute::tools::email::{
+    attachment_filename, build_message, email_tool_definition, format_summaries, parse_email,
+    parse_fetch, parse_search, quote_imap, read_imap_response, send_preview, summarize, Draft,
+    EmailAccount, EmailAttachment, EmailSummary, EMAIL_PASSWORD,
+};
+
+const MULTIPART_EMAIL: &str = "From: Alice Example <alice@example.com>\r\n\
+To: me@example.com\r\n\
+Subject: Quarterly report\r\n\
+Date: Thu, 15 Oct 2026 09:12:00 +0000\r\n\
+Message-ID: <abc123@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"XYZ\"\r\n\
+\r\n\
+--XYZ\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+Numbers attached.\r\n\
+--XYZ\r\n\
+Content-Type: text/csv\r\n\
+Content-Disposition: attachment; filename=\"q3.csv\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+YSxiCjEsMgo=\r\n\
+--XYZ--\r\n";
+
+fn email_config() -> EmailConfig {
+    EmailConfig {
+        enabled: true,
+        address: "me@example.com".to_owned(),
+        imap_host: "imap.example.com".to_owned(),
+        smtp_host: "smtp.example.com".to_owned(),
+        ..EmailConfig::default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Account
+// ---------------------------------------------------------------------------
+
+#[test]
+fn account_disabled_by_default() {
+    let account = EmailAccount::from_config(&EmailConfig::default(), &Credentials::default())
+        .expect("disabled config is valid");
+    assert!(account.is_none());
+}
+
+#[test]
+fn account_requires_password() {
+    let err = EmailAccount::from_config(&email_config(), &Credentials::default())
+        .expect_err("password is required");
+    assert!(err.to_string().contains(EMAIL_PASSWORD));
+}
+
+#[test]
+fn account_debug_hides_password() {
+    let credentials = Credentials::from_map(
+        [(EMAIL_PASSWORD.to_owned(), "hunter2-secret".to_owned())]
+            .into_iter()
+            .collect(),
+    );
+    let account = EmailAccount::from_config(&email_config(), &credentials)
+        .expect("valid config")
+        .expect("enabled");
+    assert_eq!(account.address(), "me@example.com");
+    assert!(!format!("{account:?}").contains("hunter2-secret"));
+}
+
+// ---------------------------------------------------------------------------
+// IMAP protocol
+// ---------------------------------------------------------------------------
+
+#[test]
+fn quote_imap_escapes_and_rejects_line_breaks() {
+    assert_eq!(
+        quote_imap(r#"pa"ss\word"#).expect("ascii"),
+        r#""pa\"ss\\word""#
+    );
+    assert!(quote_imap("a\r\nb").is_err());
+    assert!(quote_imap("naïve").is_err());
+}
+
+#[tokio::test]
+async fn read_imap_response_follows_literals() {
+    let wire: &[u8] = b"* 1 FETCH (UID 42 FLAGS (\\Seen) BODY[] {5}\r\nhello)\r\na1 OK done\r\n";
+    let mut reader = wire;
+
+    let fetch = read_imap_response(&mut reader).await.expect("fetch");
+    assert_eq!(fetch.literals, vec![b"hello".to_vec()]);
+    assert!(fetch.text.starts_with("* 1 FETCH (UID 42"));
+
+    let tagged = read_imap_response(&mut reader).await.expect("tagged");
+    assert_eq!(tagged.text, "a1 OK done");
+    assert!(tagged.literals.is_empty());
+
+    assert!(read_imap_response(&mut reader).await.is_err());
+}
+
+#[tokio::test]
+async fn parse_fetch_reads_uid_flags_and_body() {
+    let wire: &[u8] = b"* 3 FETCH (FLAGS (\\Answered \\Seen) UID 7 BODY[] {2}\r\nhi)\r\n";
+    let mut reader = wire;
+    let response = read_imap_response(&mut reader).await.expect("fetch");
+
+    let fetched = parse_fetch(&response).expect("parsed");
+    assert_eq!(fetched.uid, 7);
+    assert!(fetched.seen);
+    assert_eq!(fetched.data, b"hi".to_vec());
+}
+
+#[tokio::test]
+async fn parse_search_collects_uids() {
+    let wire: &[u8] = b"* SEARCH 3 10 42\r\n";
+    let mut reader = wire;
+    let response = read_imap_response(&mut reader).await.expect("search");
+    assert_eq!(parse_search(&[response]), vec![3, 10, 42]);
+}
+
+// ---------------------------------------------------------------------------
+// Parsing and formatting
+// ---------------------------------------------------------------------------
+
+#[test]
+fn parse_email_extracts_body_and_attachments() {
+    let parsed = parse_email(MULTIPART_EMAIL.as_bytes()).expect("parsed");
+    assert_eq!(parsed.from, "Alice Example <alice@example.com>");
+    assert_eq!(parsed.subject, "Quarterly report");
+    assert_eq!(parsed.message_id.as_deref(), Some("abc123@example.com"));
+    assert!(parsed.text.contains("Numbers attached."));
+
+    assert_eq!(parsed.attachments.len(), 1);
+    let attachment = parsed.attachments.first().expect("attachment");
+    assert_eq!(attachment.file_name.as_deref(), Some("q3.csv"));
+    assert_eq!(attachment.mime_type, "text/csv");
+    assert_eq!(attachment.data, b"a,b\n1,2\n".to_vec());
+}
+
+#[tokio::test]
+async fn summarize_marks_unread() {
+    let header = "From: bob@example.com\r\nSubject: Lunch?\r\n\r\n";
+    let wire = format!(
+        "* 1 FETCH (UID 9 FLAGS () BODY[HEADER.FIELDS (FROM SUBJECT DATE)] {{{}}}\r\n{header})\r\n",
+        header.len()
+    );
+    let mut reader = wire.as_bytes();
+    let response = read_imap_response(&mut reader).await.expect("fetch");
+
+    let summary = summarize(&parse_fetch(&response).expect("parsed"));
+    assert_eq!(summary.uid, 9);
+    assert_eq!(summary.from, "bob@example.com");
+    assert_eq!(summary.subject, "Lunch?");
+    assert!(summary.unread);
+}
+
+#[test]
+fn format_summaries_lists_newest_with_uid() {
+    let summaries = vec![EmailSummary {
+        uid: 42,
+        from: "Alice <alice@example.com>".to_owned(),
+        subject: "Hello".to_owned(),
+        date: None,
+        unread: true,
+    }];
+    let out = format_summaries("INBOX", 5, &summaries);
+    assert!(out.starts_with("INBOX: showing 1 of 5 messages"));
+    assert!(out.contains("uid 42 (unread)"));
+    assert_eq!(
+        format_summaries("Archive", 0, &[]),
+        "Archive: no matching messages"
+    );
+}
+
+#[test]
+fn attachment_filename_is_prefixed_and_sanitized() {
+    assert_eq!(
+        attachment_filename(42, 0, Some("reports/q3.csv")),
+        "42_reports_q3.csv"
+    );
+    assert_eq!(attachment_filename(42, 1, None), "42_attachment_2");
+}
+
+// ---------------------------------------------------------------------------
+// Composing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn draft_requires_recipient_and_body() {
+    assert!(Draft::from_input(&serde_json::json!({"action": "send", "body": "hi"})).is_err());
+    assert!(
+        Draft::from_input(&serde_json::json!({"action": "send", "to": "a@example.com"})).is_err()
+    );
+    assert!(Draft::from_input(
+        &serde_json::json!({"action": "send", "to": "not an address", "body": "hi"})
+    )
+    .is_err());
+}
+
+#[test]
+fn draft_accepts_comma_separated_recipients_and_normalizes_reply_id() {
+    let draft = Draft::from_input(&serde_json::json!({
+        "action": "send",
+        "to": "a@example.com, Bob <b@example.com>",
+        "subject": "Re: Quarterly report",
+        "body": "Thanks!",
+        "in_reply_to": "abc123@example.com"
+    }))
+    .expect("valid draft");
+    assert_eq!(draft.to, vec!["a@example.com", "Bob <b@example.com>"]);
+    assert_eq!(draft.in_reply_to.as_deref(), Some("<abc123@example.com>"));
+}
+
+#[test]
+fn build_message_includes_headers_and_attachment() {
+    let draft = Draft::from_input(&serde_json::json!({
+        "action": "send",
+        "to": ["alice@example.com"],
+        "subject": "Report",
+        "body": "See attached.",
+        "in_reply_to": "<abc123@example.com>"
+    }))
+    .expect("valid draft");
+    let attachment = EmailAttachment {
+        file_name: Some("q3.csv".to_owned()),
+        mime_type: "text/csv".to_owned(),
+        data: b"a,b\n".to_vec(),
+    };
+
+    let message = build_message("me@example.com", &draft, vec![attachment]).expect("built");
+    let formatted = String::from_utf8(message.formatted()).expect("utf-8");
+    assert!(formatted.contains("From: me@example.com"));
+    assert!(formatted.contains("To: alice@example.com"));
+    assert!(formatted.contains("Subject: Report"));
+    assert!(formatted.contains("In-Reply-To: <abc123@example.com>"));
+    assert!(formatted.contains("multipart/mixed"));
+    assert!(formatted.contains("q3.csv"));
+}
+
+#[test]
+fn send_preview_is_redacted() {
+    let redactor = Redactor::new(vec!["hunter2-secret".to_owned()]);
+    let input = serde_json::json!({
+        "action": "send",
+        "to": ["alice@example.com"],
+        "subject": "Login",
+        "body": "The password is hunter2-secret"
+    });
+
+    let preview = send_preview(&input, &redactor).expect("send has a preview");
+    assert!(preview.contains("To: alice@example.com"));
+    assert!(preview.contains("[REDACTED]"));
+    assert!(!preview.contains("hunter2-secret"));
+
+    assert!(send_preview(&serde_json::json!({"action": "list"}), &redactor).is_none());
+}
+
+#[test]
+fn tool_definition_requires_action() {
+    let def = email_tool_definition();
+    assert_eq!(def.name, "email");
+    assert_eq!(def.input_schema["required"], serde_json::json!(["action"]));
+}
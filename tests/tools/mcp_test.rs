@@ -0,0 +1,175 @@
+//! Tests for `src/tools/mcp.rs` — tool naming, listing, results, and a stdio server.
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use wintermute::config::McpServerConfig;
+use wintermute::credentials::Credentials;
+use wintermute::tools::mcp::{
+    exposed_name, format_call_result, is_valid_server_name, read_capped_line, CappedLine,
+    McpManager, McpToolInfo,
+};
+
+/// Fake MCP server: answers initialize, tools/list, and one tools/call.
+const FAKE_SERVER: &str = r#"
+read -r _init
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{"tools":{}},"serverInfo":{"name":"fake","version":"0"}}}'
+read -r _initialized
+read -r _list
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info"}}'
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"lookup","description":"Look up a word","inputSchema":{"type":"object","properties":{"word":{"type":"string"}}},"annotations":{"readOnlyHint":true}},{"name":"create_issue","description":"Create an issue","inputSchema":{"type":"object"}}]}}'
+read -r _call
+echo "{\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{\"content\":[{\"type\":\"text\",\"text\":\"token=$FAKE_TOKEN\"}]}}"
+read -r _rest
+"#;
+
+fn server_config(name: &str, script: &str) -> McpServerConfig {
+    McpServerConfig {
+        name: name.to_owned(),
+        command: "sh".to_owned(),
+        args: vec!["-c".to_owned(), script.to_owned()],
+        credentials: vec!["FAKE_TOKEN".to_owned()],
+        auto_approve: Vec::new(),
+        enabled: true,
+        timeout_secs: 10,
+    }
+}
+
+fn credentials() -> Credentials {
+    let mut vars = BTreeMap::new();
+    vars.insert("FAKE_TOKEN".to_owned(), "abc123".to_owned());
+    Credentials::from_map(vars)
+}
+
+#[test]
+fn exposed_names_are_prefixed_and_sanitized() {
+    assert_eq!(
+        exposed_name("github", "create_issue"),
+        "mcp_github_create_issue"
+    );
+    assert_eq!(exposed_name("fs", "read.file/v2"), "mcp_fs_read_file_v2");
+    assert_eq!(exposed_name("s", &"x".repeat(100)).len(), 64);
+}
+
+#[test]
+fn server_names_must_be_tool_name_safe() {
+    assert!(is_valid_server_name("github"));
+    assert!(is_valid_server_name("my-server_2"));
+    assert!(!is_valid_server_name(""));
+    assert!(!is_valid_server_name("GitHub"));
+    assert!(!is_valid_server_name("a b"));
+    assert!(!is_valid_server_name(&"a".repeat(33)));
+}
+
+#[test]
+fn tool_listing_reads_schema() {
+    let tool = McpToolInfo::from_listing(&json!({
+        "name": "lookup",
+        "description": "Look up",
+        "inputSchema": {"type": "object", "properties": {"q": {"type": "string"}}},
+        "annotations": {"readOnlyHint": true}
+    }))
+    .expect("tool should parse");
+    assert_eq!(tool.name, "lookup");
+    assert_eq!(tool.input_schema["properties"]["q"]["type"], "string");
+
+    let bare = McpToolInfo::from_listing(&json!({"name": "write"})).expect("tool should parse");
+    assert_eq!(bare.description, "");
+    assert_eq!(bare.input_schema["type"], "object");
+
+    assert!(McpToolInfo::from_listing(&json!({"description": "no name"})).is_none());
+}
+
+#[tokio::test]
+async fn oversized_lines_are_dropped_and_reading_continues() {
+    let input = format!("short\n{}\nafter\ntail", "x".repeat(64));
+    let mut reader = input.as_bytes();
+    let mut lines = Vec::new();
+    loop {
+        let line = read_capped_line(&mut reader, 16)
+            .await
+            .expect("read should succeed");
+        if line == CappedLine::Eof {
+            break;
+        }
+        lines.push(line);
+    }
+    assert_eq!(
+        lines,
+        vec![
+            CappedLine::Text("short".to_owned()),
+            CappedLine::Oversized,
+            CappedLine::Text("after".to_owned()),
+            CappedLine::Text("tail".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn call_results_render_content_blocks() {
+    let (text, is_error) = format_call_result(&json!({
+        "content": [
+            {"type": "text", "text": "hello"},
+            {"type": "image", "data": "AAAA", "mimeType": "image/png"},
+            {"type": "resource", "resource": {"uri": "file:///a.txt", "text": "file body"}}
+        ]
+    }));
+    assert!(!is_error);
+    assert_eq!(text, "hello\n[image: image/png]\nfile body");
+
+    let (text, is_error) = format_call_result(&json!({
+        "content": [{"type": "text", "text": "not found"}],
+        "isError": true
+    }));
+    assert!(is_error);
+    assert_eq!(text, "not found");
+}
+
+#[tokio::test]
+async fn manager_exposes_and_calls_server_tools() {
+    let manager = McpManager::start(&[server_config("fake", FAKE_SERVER)], &credentials()).await;
+    assert_eq!(manager.server_count(), 1);
+    assert_eq!(manager.tool_count(), 2);
+
+    let names: Vec<String> = manager
+        .tool_definitions()
+        .into_iter()
+        .map(|d| d.name)
+        .collect();
+    assert_eq!(names, vec!["mcp_fake_create_issue", "mcp_fake_lookup"]);
+
+    // readOnlyHint is server-declared and not trusted.
+    assert!(manager.requires_approval("mcp_fake_lookup"));
+    assert!(manager.requires_approval("mcp_fake_create_issue"));
+    assert!(!manager.handles("lookup"));
+
+    let output = manager
+        .call("mcp_fake_lookup", &json!({"word": "x"}))
+        .await
+        .expect("call should succeed");
+    assert_eq!(output, "token=abc123");
+
+    manager.shutdown().await;
+}
+
+#[tokio::test]
+async fn auto_approve_skips_approval_for_write_tools() {
+    let mut config = server_config("fake", FAKE_SERVER);
+    config.auto_approve = vec!["create_issue".to_owned()];
+    let manager = McpManager::start(&[config], &credentials()).await;
+    assert!(!manager.requires_approval("mcp_fake_create_issue"));
+    manager.shutdown().await;
+}
+
+#[tokio::test]
+async fn failing_servers_are_skipped() {
+    let mut missing_credential = server_config("nocred", FAKE_SERVER);
+    missing_credential.credentials = vec!["UNSET_KEY".to_owned()];
+    let mut exits = server_config("exits", "exit 0");
+    exits.credentials.clear();
+    let bad_name = server_config("Bad Name", FAKE_SERVER);
+
+    let manager = McpManager::start(&[missing_credential, exits, bad_name], &credentials()).await;
+    assert_eq!(manager.server_count(), 0);
+    assert!(manager.tool_definitions().is_empty());
+}
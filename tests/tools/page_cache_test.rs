@@ -0,0 +1,58 @@
+//! Tests for `src/tools/page_cache.rs` — session scoping, expiry, and eviction.
+
+use std::time::Duration;
+
+use wintermute::tools::page_cache::{CachedPage, PageCache};
+
+fn page(body: &str) -> CachedPage {
+    CachedPage {
+        final_url: "https://example.com/".to_owned(),
+        content_type: Some("text/html".to_owned()),
+        body: body.to_owned(),
+    }
+}
+
+#[test]
+fn cached_page_is_scoped_to_session() {
+    let cache = PageCache::default();
+    cache.insert("user_1", "https://example.com/", page("one"));
+
+    assert_eq!(
+        cache.get("user_1", "https://example.com/"),
+        Some(page("one"))
+    );
+    assert_eq!(cache.get("user_2", "https://example.com/"), None);
+    assert_eq!(cache.get("unknown", "https://example.com/"), None);
+}
+
+#[test]
+fn expired_pages_are_not_returned() {
+    let cache = PageCache::new(Duration::ZERO, 8);
+    cache.insert("user_1", "https://example.com/", page("stale"));
+    assert_eq!(cache.get("user_1", "https://example.com/"), None);
+}
+
+#[test]
+fn full_cache_evicts_oldest_page() {
+    let cache = PageCache::new(Duration::from_secs(60), 2);
+    cache.insert("user_1", "https://a.example/", page("a"));
+    std::thread::sleep(Duration::from_millis(5));
+    cache.insert("user_1", "https://b.example/", page("b"));
+    std::thread::sleep(Duration::from_millis(5));
+    cache.insert("user_1", "https://c.example/", page("c"));
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get("user_1", "https://a.example/"), None);
+    assert!(cache.get("user_1", "https://c.example/").is_some());
+}
+
+#[test]
+fn clear_session_leaves_other_sessions() {
+    let cache = PageCache::default();
+    cache.insert("user_1", "https://example.com/", page("one"));
+    cache.insert("user_2", "https://example.com/", page("two"));
+
+    cache.clear_session("user_1");
+    assert_eq!(cache.get("user_1", "https://example.com/"), None);
+    assert!(cache.get("user_2", "https://example.com/").is_some());
+}
@@ -0,0 +1,142 @@
+//! Tests for `src/tools/readability.rs` and web_fetch page rendering.
+
+use url::Url;
+use wintermute::tools::core::render_fetched_page;
+use wintermute::tools::page_cache::CachedPage;
+use wintermute::tools::readability::{decode_entities, extract, truncate_to_tokens};
+
+const ARTICLE_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Rust 2026 &amp; Beyond</title>
+  <style>body { color: red; }</style>
+  <script>var tracking = "should not appear";</script>
+</head>
+<body>
+  <nav class="top-nav"><a href="/">Home</a> <a href="/blog">Blog</a> <a href="/about">About</a></nav>
+  <div class="sidebar">
+    <h3>Related posts</h3>
+    <ul><li><a href="/x">Something else entirely</a></li></ul>
+  </div>
+  <div class="post-content">
+    <h1>Rust 2026 and beyond</h1>
+    <p>The Rust project published its plans for the next edition, with a focus on
+       <strong>async ergonomics</strong> and faster compile times.</p>
+    <p>Read the <a href="/rfcs/1234">full RFC</a> for the details of the proposal,
+       including migration tooling for existing crates and libraries.</p>
+    <pre><code>fn main() {
+    println!("hi");
+}</code></pre>
+    <ul><li>Async closures</li><li>Faster builds</li></ul>
+  </div>
+  <footer class="site-footer">Copyright 2026. All rights reserved. Cookie settings.</footer>
+</body>
+</html>"#;
+
+fn base() -> Url {
+    Url::parse("https://blog.example.com/posts/rust-2026").expect("valid url")
+}
+
+#[test]
+fn extracts_title_and_main_content() {
+    let readable = extract(ARTICLE_PAGE, Some(&base()));
+    assert_eq!(readable.title.as_deref(), Some("Rust 2026 & Beyond"));
+
+    let md = &readable.markdown;
+    assert!(
+        md.contains("# Rust 2026 and beyond"),
+        "heading missing: {md}"
+    );
+    assert!(md.contains("**async ergonomics**"), "bold missing: {md}");
+    assert!(md.contains("- Async closures"), "list missing: {md}");
+}
+
+#[test]
+fn drops_boilerplate_and_scripts() {
+    let md = extract(ARTICLE_PAGE, Some(&base())).markdown;
+    assert!(!md.contains("tracking"), "script leaked: {md}");
+    assert!(!md.contains("Related posts"), "sidebar leaked: {md}");
+    assert!(!md.contains("Copyright"), "footer leaked: {md}");
+    assert!(!md.contains("About"), "nav leaked: {md}");
+}
+
+#[test]
+fn resolves_relative_links() {
+    let md = extract(ARTICLE_PAGE, Some(&base())).markdown;
+    assert!(
+        md.contains("[full RFC](https://blog.example.com/rfcs/1234)"),
+        "link missing: {md}"
+    );
+}
+
+#[test]
+fn preserves_preformatted_code() {
+    let md = extract(ARTICLE_PAGE, Some(&base())).markdown;
+    assert!(
+        md.contains("```\nfn main() {\n    println!(\"hi\");\n}\n```"),
+        "code block mangled: {md}"
+    );
+}
+
+#[test]
+fn short_page_falls_back_to_whole_body() {
+    let readable = extract("<p>Just a line.</p>", None);
+    assert_eq!(readable.markdown, "Just a line.");
+    assert_eq!(readable.title, None);
+}
+
+#[test]
+fn decodes_named_and_numeric_entities() {
+    assert_eq!(decode_entities("a &amp;lt; b"), "a &lt; b");
+    assert_eq!(decode_entities("&#65;&#x42;&mdash;"), "AB\u{2014}");
+    assert_eq!(
+        decode_entities("fish & chips; &bogus;"),
+        "fish & chips; &bogus;"
+    );
+}
+
+#[test]
+fn truncate_to_tokens_prefers_paragraph_break() {
+    let text = format!("{}\n\n{}", "a".repeat(30), "b".repeat(30));
+    let cut = truncate_to_tokens(&text, 10);
+    assert!(cut.starts_with(&"a".repeat(30)));
+    assert!(!cut.contains("bbb"));
+    assert!(cut.contains("[truncated at ~10 tokens"));
+
+    assert_eq!(truncate_to_tokens("short", 10), "short");
+}
+
+#[test]
+fn render_fetched_page_labels_trust_and_format() {
+    let page = CachedPage {
+        final_url: "https://blog.example.com/posts/rust-2026".to_owned(),
+        content_type: Some("text/html; charset=utf-8".to_owned()),
+        body: ARTICLE_PAGE.to_owned(),
+    };
+
+    let markdown = render_fetched_page(&page, false, 4_000, false);
+    assert!(markdown.starts_with("Title: Rust 2026 & Beyond\n"));
+    assert!(markdown.contains("Domain: blog.example.com (not in trust ledger"));
+    assert!(!markdown.contains("<p>"));
+
+    let raw = render_fetched_page(&page, true, 4_000, true);
+    assert!(raw.contains("Domain: blog.example.com (trusted)"));
+    assert!(raw.contains("<p>"));
+}
+
+#[test]
+fn render_fetched_page_caps_extracted_text_not_markup() {
+    // Heavy markup ahead of the article must not push it past the cap.
+    let padding = format!("<script>{}</script>", "x".repeat(300 * 1024));
+    let page = CachedPage {
+        final_url: "https://blog.example.com/posts/rust-2026".to_owned(),
+        content_type: Some("text/html".to_owned()),
+        body: ARTICLE_PAGE.replace("<head>", &format!("<head>{padding}")),
+    };
+    let markdown = render_fetched_page(&page, false, 100_000, false);
+    assert!(markdown.contains("async ergonomics"));
+
+    let raw = render_fetched_page(&page, true, 1_000_000, false);
+    assert!(raw.contains("...[truncated]"));
+    assert!(raw.len() < 110 * 1024);
+}
@@ -5,7 +5,8 @@ use std::path::PathBuf;
 use serde_json::json;
 use tempfile::TempDir;
 
-use wintermute::tools::registry::DynamicToolRegistry;
+use wintermute::config::ToolHealthConfig;
+use wintermute::tools::registry::{DynamicToolRegistry, ToolManifest};
 
 /// Create a temp directory with some tool JSON files.
 fn setup_temp_dir_with_tools() -> (TempDir, PathBuf) {
@@ -191,6 +192,41 @@ fn registry_reload_tool_updates_specific_tool() {
     assert_eq!(tool.timeout_secs, 30);
 }
 
+#[test]
+fn registry_recompiles_validator_on_reload() {
+    let (_dir, path) = setup_temp_dir_with_tools();
+    let registry =
+        DynamicToolRegistry::new_without_watcher(path.clone()).expect("registry should initialise");
+    let before = registry.validator("test_tool").expect("validator");
+    assert!(before.is_valid(&json!({"count": "many"})));
+
+    let updated = json!({
+        "name": "test_tool",
+        "description": "A test tool",
+        "parameters": {
+            "type": "object",
+            "properties": { "count": { "type": "integer" } }
+        }
+    });
+    std::fs::write(
+        path.join("test_tool.json"),
+        serde_json::to_string_pretty(&updated).expect("serialize"),
+    )
+    .expect("write");
+    registry
+        .reload_tool("test_tool")
+        .expect("reload should succeed");
+
+    let after = registry.validator("test_tool").expect("validator");
+    assert!(!after.is_valid(&json!({"count": "many"})));
+
+    std::fs::remove_file(path.join("test_tool.json")).expect("remove");
+    registry
+        .reload_tool("test_tool")
+        .expect("reload should succeed");
+    assert!(registry.validator("test_tool").is_none());
+}
+
 #[test]
 fn registry_handles_nonexistent_directory() {
     let path = PathBuf::from("/tmp/nonexistent_wintermute_test_dir_12345");
@@ -420,3 +456,179 @@ fn registry_rejects_invalid_timeout_values() {
         "invalid timeout schema should be skipped"
     );
 }
+
+#[test]
+fn manifest_validation_normalises_domains_and_bounds_runtime() {
+    let mut manifest = ToolManifest {
+        needs_network: vec![" API.GitHub.com ".to_owned(), "api.github.com".to_owned()],
+        max_runtime_secs: Some(30),
+        needs_workspace_write: false,
+    };
+    manifest.validate().expect("valid manifest");
+    assert_eq!(manifest.needs_network, vec!["api.github.com"]);
+    assert_eq!(
+        manifest.ungranted_domains(&[]),
+        vec!["api.github.com"],
+        "nothing granted yet"
+    );
+    assert!(manifest
+        .ungranted_domains(&["api.github.com".to_owned()])
+        .is_empty());
+
+    for bad in [
+        "https://example.com",
+        "example.com/path",
+        ".example.com",
+        "",
+    ] {
+        let mut manifest = ToolManifest {
+            needs_network: vec![bad.to_owned()],
+            ..ToolManifest::default()
+        };
+        assert!(manifest.validate().is_err(), "{bad} should be rejected");
+    }
+    let mut manifest = ToolManifest {
+        max_runtime_secs: Some(0),
+        ..ToolManifest::default()
+    };
+    assert!(manifest.validate().is_err());
+}
+
+#[test]
+fn registry_loads_manifest_and_caps_runtime() {
+    let dir = TempDir::new().expect("should create temp dir");
+    let path = dir.path().to_path_buf();
+
+    let with_manifest = json!({
+        "name": "fetcher",
+        "description": "Fetch things",
+        "parameters": { "type": "object" },
+        "timeout_secs": 60,
+        "manifest": { "needs_network": ["api.example.com"], "max_runtime_secs": 10 }
+    });
+    let legacy = json!({
+        "name": "legacy",
+        "description": "Tool from before manifests",
+        "parameters": { "type": "object" },
+        "timeout_secs": 60
+    });
+    let bad = json!({
+        "name": "bad_manifest",
+        "description": "Unknown manifest field",
+        "parameters": { "type": "object" },
+        "manifest": { "needs_root": true }
+    });
+    for schema in [&with_manifest, &legacy, &bad] {
+        let name = schema["name"].as_str().expect("name");
+        std::fs::write(
+            path.join(format!("{name}.json")),
+            serde_json::to_string_pretty(schema).expect("serialize"),
+        )
+        .expect("write");
+    }
+
+    let registry =
+        DynamicToolRegistry::new_without_watcher(path).expect("registry should initialise");
+    assert_eq!(registry.count(), 2, "bad manifest should be skipped");
+
+    let fetcher = registry.get("fetcher").expect("fetcher loaded");
+    assert_eq!(fetcher.max_runtime_secs(), 10);
+    assert!(!fetcher
+        .manifest
+        .restrictions("fetcher", true)
+        .domains
+        .is_empty());
+    assert!(
+        !fetcher
+            .manifest
+            .restrictions("fetcher", true)
+            .workspace_write
+    );
+
+    let legacy = registry.get("legacy").expect("legacy loaded");
+    assert_eq!(legacy.manifest, ToolManifest::default());
+    assert_eq!(legacy.max_runtime_secs(), 60);
+    assert!(legacy
+        .manifest
+        .restrictions("legacy", true)
+        .domains
+        .is_empty());
+}
+
+#[tokio::test]
+async fn registry_disables_tool_below_rolling_success_rate() {
+    let (_dir, path) = setup_temp_dir_with_tools();
+    let registry =
+        DynamicToolRegistry::new_without_watcher(path).expect("registry should initialise");
+    registry.set_health_policy(ToolHealthConfig {
+        auto_disable: true,
+        min_success_rate: 0.5,
+        window: 4,
+    });
+
+    for _ in 0..5 {
+        assert!(registry
+            .record_execution("test_tool", true, 10, None)
+            .is_none());
+    }
+    assert!(registry
+        .record_execution("test_tool", false, 10, Some("first"))
+        .is_none());
+    assert!(registry
+        .record_execution("test_tool", false, 10, Some("second"))
+        .is_none());
+    let disabled = registry
+        .record_execution("test_tool", false, 10, Some("third"))
+        .expect("1 of the last 4 runs succeeded");
+    assert_eq!(disabled.recent_errors, vec!["first", "second", "third"]);
+    let info = disabled.disabled.expect("disabled info");
+    assert!((info.success_rate - 0.25).abs() < f64::EPSILON);
+    assert_eq!(info.window, 4);
+
+    // Reported once, hidden from the model, and kept in the registry.
+    assert!(registry
+        .record_execution("test_tool", false, 10, Some("fourth"))
+        .is_none());
+    let tool = registry.get("test_tool").expect("still registered");
+    assert!(tool.is_disabled());
+    assert!(!registry
+        .all_definitions()
+        .iter()
+        .any(|d| d.name == "test_tool"));
+    assert!(!registry
+        .ranked_definitions(10, None)
+        .iter()
+        .any(|d| d.name == "test_tool"));
+
+    assert!(registry.enable("test_tool"));
+    let tool = registry.get("test_tool").expect("still registered");
+    assert!(!tool.is_disabled());
+    let meta = tool.meta.expect("_meta kept");
+    assert!(meta.recent_outcomes.is_empty());
+    assert!(meta.recent_errors.is_empty());
+    assert_eq!(meta.invocations, 9);
+    assert!(!registry.enable("missing_tool"));
+}
+
+#[tokio::test]
+async fn registry_keeps_failing_tool_when_auto_disable_is_off() {
+    let (_dir, path) = setup_temp_dir_with_tools();
+    let registry =
+        DynamicToolRegistry::new_without_watcher(path).expect("registry should initialise");
+    registry.set_health_policy(ToolHealthConfig {
+        auto_disable: false,
+        min_success_rate: 0.5,
+        window: 2,
+    });
+
+    for _ in 0..3 {
+        assert!(registry
+            .record_execution("test_tool", false, 10, Some("boom"))
+            .is_none());
+    }
+    let tool = registry.get("test_tool").expect("registered");
+    assert!(!tool.is_disabled());
+    let meta = tool.meta.expect("_meta");
+    assert_eq!(meta.rolling_success_rate(2), Some(0.0));
+    assert_eq!(meta.rolling_success_rate(4), None);
+}
@@ -0,0 +1,195 @@
+//! Tests for `src/tools/rss.rs` — feed parsing and subscription storage.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use wintermute::memory::MemoryEngine;
+use wintermute::tools::rss::{
+    format_feed_list, list_feeds, parse_feed, pending_entries, recent_entries, record_entries,
+    subscribe, unsubscribe, FeedPriority,
+};
+
+const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+  <title>Example &amp; Co</title>
+  <link>https://example.com/</link>
+  <item>
+    <title><![CDATA[First & best]]></title>
+    <link>https://example.com/1</link>
+    <guid isPermaLink="false">post-1</guid>
+    <pubDate>Thu, 15 Oct 2026 09:12:00 +0200</pubDate>
+    <description>&lt;p&gt;Hello &lt;b&gt;world&lt;/b&gt;&lt;/p&gt;</description>
+  </item>
+  <item>
+    <title>Second</title>
+    <link>https://example.com/2</link>
+  </item>
+</channel></rss>"#;
+
+const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Atom Blog</title>
+  <link rel="self" href="https://blog.example.com/feed.xml"/>
+  <entry>
+    <title>Release 1.0</title>
+    <link rel="alternate" href="https://blog.example.com/release"/>
+    <id>tag:blog.example.com,2026:1</id>
+    <updated>2026-10-15T08:00:00Z</updated>
+    <summary>Shipped.</summary>
+  </entry>
+</feed>"#;
+
+async fn setup_memory() -> MemoryEngine {
+    let opts = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .expect("pool should connect");
+    sqlx::raw_sql(include_str!("../../migrations/010_feeds.sql"))
+        .execute(&pool)
+        .await
+        .expect("migration should apply");
+    MemoryEngine::new(pool, None)
+        .await
+        .expect("memory engine should start")
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn parse_rss_items() {
+    let feed = parse_feed(RSS).expect("valid rss");
+    assert_eq!(feed.title.as_deref(), Some("Example & Co"));
+    assert_eq!(feed.entries.len(), 2);
+
+    let first = feed.entries.first().expect("first item");
+    assert_eq!(first.guid, "post-1");
+    assert_eq!(first.title, "First & best");
+    assert_eq!(first.link.as_deref(), Some("https://example.com/1"));
+    assert_eq!(first.summary.as_deref(), Some("Hello world"));
+    assert_eq!(first.published.as_deref(), Some("2026-10-15T07:12:00Z"));
+
+    // Without a guid the link identifies the item.
+    let second = feed.entries.get(1).expect("second item");
+    assert_eq!(second.guid, "https://example.com/2");
+    assert!(second.published.is_none());
+}
+
+#[test]
+fn parse_atom_entries() {
+    let feed = parse_feed(ATOM).expect("valid atom");
+    assert_eq!(feed.title.as_deref(), Some("Atom Blog"));
+
+    let entry = feed.entries.first().expect("entry");
+    assert_eq!(entry.guid, "tag:blog.example.com,2026:1");
+    assert_eq!(
+        entry.link.as_deref(),
+        Some("https://blog.example.com/release")
+    );
+    assert_eq!(entry.summary.as_deref(), Some("Shipped."));
+    assert_eq!(entry.published.as_deref(), Some("2026-10-15T08:00:00Z"));
+}
+
+#[test]
+fn parse_rejects_non_feed() {
+    assert!(parse_feed("<html><body>hi</body></html>").is_err());
+}
+
+#[test]
+fn priority_round_trips() {
+    assert_eq!(FeedPriority::parse("HIGH"), Some(FeedPriority::High));
+    assert_eq!(FeedPriority::parse("normal"), Some(FeedPriority::Normal));
+    assert_eq!(FeedPriority::parse("urgent"), None);
+    assert_eq!(FeedPriority::High.as_str(), "high");
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn record_entries_dedupes_by_guid() {
+    let memory = setup_memory().await;
+    let feed = parse_feed(RSS).expect("valid rss");
+    let id = subscribe(
+        &memory,
+        "https://example.com/feed",
+        None,
+        FeedPriority::Normal,
+    )
+    .await
+    .expect("subscribe");
+
+    let first = record_entries(&memory, id, &feed.entries, false)
+        .await
+        .expect("record");
+    let again = record_entries(&memory, id, &feed.entries, false)
+        .await
+        .expect("record again");
+    assert_eq!(first, 2);
+    assert_eq!(again, 0);
+
+    let pending = pending_entries(memory.pool(), FeedPriority::Normal, 10)
+        .await
+        .expect("pending");
+    assert_eq!(pending.len(), 2);
+    assert!(pending_entries(memory.pool(), FeedPriority::High, 10)
+        .await
+        .expect("pending high")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn baseline_entries_are_not_pending() {
+    let memory = setup_memory().await;
+    let feed = parse_feed(ATOM).expect("valid atom");
+    let id = subscribe(
+        &memory,
+        "https://blog.example.com/feed.xml",
+        None,
+        FeedPriority::High,
+    )
+    .await
+    .expect("subscribe");
+
+    record_entries(&memory, id, &feed.entries, true)
+        .await
+        .expect("baseline");
+    assert!(pending_entries(memory.pool(), FeedPriority::High, 10)
+        .await
+        .expect("pending")
+        .is_empty());
+    assert_eq!(
+        recent_entries(memory.pool(), Some(id), 10)
+            .await
+            .expect("recent")
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn resubscribe_updates_priority_and_unsubscribe_removes() {
+    let memory = setup_memory().await;
+    let url = "https://example.com/feed";
+    let id = subscribe(&memory, url, Some("Example"), FeedPriority::Normal)
+        .await
+        .expect("subscribe");
+    let same = subscribe(&memory, url, None, FeedPriority::High)
+        .await
+        .expect("resubscribe");
+    assert_eq!(id, same);
+
+    let feeds = list_feeds(memory.pool()).await.expect("list");
+    let feed = feeds.first().expect("feed");
+    assert_eq!(feed.priority, FeedPriority::High);
+    assert_eq!(feed.title.as_deref(), Some("Example"));
+    assert!(format_feed_list(&feeds, &[]).contains("[high] Example — https://example.com/feed"));
+
+    assert!(unsubscribe(&memory, url).await.expect("unsubscribe"));
+    assert!(!unsubscribe(&memory, url).await.expect("second unsubscribe"));
+    assert!(list_feeds(memory.pool()).await.expect("list").is_empty());
+}
@@ -0,0 +1,238 @@
+//! Tests for `src/tools/self_test.rs` — example parsing, output checks, and
+//! the self-test gate in `create_tool`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use wintermute::executor::{
+    ExecOptions, ExecResult, Executor, ExecutorError, ExecutorKind, HealthStatus,
+};
+use wintermute::tools::create_tool::create_tool;
+use wintermute::tools::registry::{DynamicToolRegistry, ToolManifest};
+use wintermute::tools::self_test::{check_output, parse_examples, run_self_test, ToolExample};
+
+// ---------------------------------------------------------------------------
+// Mock executor
+// ---------------------------------------------------------------------------
+
+/// Returns `tool_stdout` for script runs and succeeds silently otherwise,
+/// recording every command.
+struct ScriptExecutor {
+    tool_stdout: String,
+    commands: Mutex<Vec<String>>,
+    scripts_dir: PathBuf,
+}
+
+impl ScriptExecutor {
+    fn new(tool_stdout: &str) -> Self {
+        Self {
+            tool_stdout: tool_stdout.to_owned(),
+            commands: Mutex::new(Vec::new()),
+            scripts_dir: PathBuf::from("/tmp/scripts"),
+        }
+    }
+
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().expect("lock").clone()
+    }
+}
+
+#[async_trait]
+impl Executor for ScriptExecutor {
+    async fn execute(
+        &self,
+        command: &str,
+        _opts: ExecOptions,
+    ) -> Result<ExecResult, ExecutorError> {
+        self.commands.lock().expect("lock").push(command.to_owned());
+        let stdout = if command.contains("python3") {
+            self.tool_stdout.clone()
+        } else if command.starts_with("test -f") {
+            "create".to_owned()
+        } else {
+            String::new()
+        };
+        Ok(ExecResult {
+            exit_code: Some(0),
+            stdout,
+            stderr: String::new(),
+            timed_out: false,
+            duration: Duration::from_millis(5),
+        })
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, ExecutorError> {
+        Ok(HealthStatus::Healthy {
+            kind: ExecutorKind::Direct,
+            details: "mock".to_owned(),
+        })
+    }
+
+    fn scripts_dir(&self) -> &Path {
+        &self.scripts_dir
+    }
+
+    fn workspace_dir(&self) -> &Path {
+        &self.scripts_dir
+    }
+
+    fn kind(&self) -> ExecutorKind {
+        ExecutorKind::Direct
+    }
+}
+
+fn params_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": { "n": { "type": "integer" } },
+        "required": ["n"]
+    })
+}
+
+fn example(input: serde_json::Value, expect: Option<serde_json::Value>) -> ToolExample {
+    ToolExample { input, expect }
+}
+
+// ---------------------------------------------------------------------------
+// Examples and validation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn parse_examples_requires_at_least_one() {
+    assert!(parse_examples(None, &params_schema()).is_err());
+    assert!(parse_examples(Some(&json!([])), &params_schema()).is_err());
+    assert!(parse_examples(Some(&json!({"input": {}})), &params_schema()).is_err());
+}
+
+#[test]
+fn parse_examples_checks_inputs_against_parameters() {
+    let err = parse_examples(Some(&json!([{"input": {"n": "two"}}])), &params_schema())
+        .expect_err("string is not an integer");
+    let err = err.to_string();
+    assert!(err.contains("example 1"), "got: {err}");
+    assert!(err.contains("/n:"), "got: {err}");
+
+    let examples = parse_examples(
+        Some(&json!([{"input": {"n": 2}, "expect": {"double": 4}}])),
+        &params_schema(),
+    )
+    .expect("valid examples");
+    assert_eq!(examples.len(), 1);
+}
+
+#[test]
+fn check_output_requires_json_and_expected_fields() {
+    let ex = example(json!({"n": 2}), Some(json!({"double": 4})));
+    assert!(check_output(&ex, r#"{"double": 4, "extra": true}"#, None).is_ok());
+    assert!(check_output(&ex, "four", None)
+        .expect_err("not json")
+        .contains("not valid JSON"));
+    assert!(check_output(&ex, r#"{"double": 5}"#, None)
+        .expect_err("wrong value")
+        .contains("$.double expected 4, got 5"));
+
+    let output_schema = json!({"type": "object", "required": ["double"]});
+    let plain = example(json!({"n": 2}), None);
+    assert!(check_output(&plain, "{}", Some(&output_schema))
+        .expect_err("missing field")
+        .contains("output does not match output_schema"));
+}
+
+// ---------------------------------------------------------------------------
+// Harness
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn run_self_test_reports_each_failure() {
+    let executor = ScriptExecutor::new(r#"{"double": 4}"#);
+    let examples = vec![
+        example(json!({"n": 2}), Some(json!({"double": 4}))),
+        example(json!({"n": 3}), Some(json!({"double": 6}))),
+    ];
+
+    let report = run_self_test(
+        &executor,
+        "/tmp/scripts/.t.candidate.py",
+        &examples,
+        None,
+        10,
+        None,
+    )
+    .await;
+    assert!(!report.is_success());
+    assert_eq!(report.examples, 2);
+    assert_eq!(report.passed, 1);
+    assert_eq!(report.failures.first().map(|f| f.index), Some(1));
+    assert!(report.failure_summary().contains("example 2"));
+}
+
+#[tokio::test]
+async fn create_tool_does_not_activate_failing_tool() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let registry =
+        DynamicToolRegistry::new_without_watcher(dir.path().to_path_buf()).expect("registry");
+    let executor = ScriptExecutor::new("not json");
+
+    let err = create_tool(
+        &executor,
+        &registry,
+        "doubler",
+        "Double a number",
+        &params_schema(),
+        "print('not json')",
+        30,
+        &[example(json!({"n": 2}), None)],
+        None,
+        &ToolManifest::default(),
+    )
+    .await
+    .expect_err("self-test fails");
+    assert!(err.to_string().contains("self-test failed for 'doubler'"));
+
+    let commands = executor.commands();
+    assert!(commands.iter().any(|c| c.starts_with("rm -f")));
+    assert!(!commands.iter().any(|c| c.contains("doubler.json")));
+    assert!(!commands.iter().any(|c| c.starts_with("mv -f")));
+}
+
+#[tokio::test]
+async fn create_tool_activates_passing_tool_with_report() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let registry =
+        DynamicToolRegistry::new_without_watcher(dir.path().to_path_buf()).expect("registry");
+    let executor = ScriptExecutor::new(r#"{"double": 4}"#);
+
+    let out = create_tool(
+        &executor,
+        &registry,
+        "doubler",
+        "Double a number",
+        &params_schema(),
+        "import json,sys; print(json.dumps({'double': json.load(sys.stdin)['n'] * 2}))",
+        30,
+        &[example(json!({"n": 2}), Some(json!({"double": 4})))],
+        None,
+        &ToolManifest {
+            needs_network: vec!["api.example.com".to_owned()],
+            max_runtime_secs: Some(5),
+            needs_workspace_write: false,
+        },
+    )
+    .await
+    .expect("self-test passes");
+    assert!(out.contains("self-test passed 1/1"));
+
+    let commands = executor.commands();
+    assert!(commands.iter().any(|c| c.starts_with("mv -f")));
+    let schema_write = commands
+        .iter()
+        .find(|c| c.contains("doubler.json"))
+        .expect("schema written");
+    assert!(schema_write.contains("self_test"));
+    assert!(schema_write.contains("api.example.com"));
+    assert!(schema_write.contains("\"max_runtime_secs\": 5"));
+}
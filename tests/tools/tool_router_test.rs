@@ -126,6 +126,12 @@ async fn create_dummy_memory_engine() -> Arc<wintermute::memory::MemoryEngine> {
         .await
         .expect("002 should apply");
 
+    let grants_sql = include_str!("../../migrations/015_tool_network_grants.sql");
+    sqlx::raw_sql(grants_sql)
+        .execute(&pool)
+        .await
+        .expect("015 should apply");
+
     Arc::new(
         wintermute::memory::MemoryEngine::new(pool, None)
             .await
@@ -189,6 +195,55 @@ async fn core_tool_dispatch_works() {
     );
 }
 
+#[tokio::test]
+async fn missing_required_argument_is_rejected_before_dispatch() {
+    let executor = Arc::new(RouterMockExecutor::new());
+    let redactor = Redactor::new(Vec::new());
+    let router = build_router(executor, redactor).await;
+
+    let result = router
+        .execute("execute_command", &json!({"timeout_secs": 5}))
+        .await;
+
+    assert!(result.is_error, "schema violation should be an error");
+    assert!(
+        result.content.contains("invalid input")
+            && result.content.contains("execute_command")
+            && result
+                .content
+                .contains("\"command\" is a required property"),
+        "error should name the tool and the missing field, got: {}",
+        result.content
+    );
+    assert!(
+        !result.content.contains("mock output"),
+        "executor must not run on invalid input"
+    );
+}
+
+#[tokio::test]
+async fn wrong_argument_type_reports_json_pointer() {
+    let executor = Arc::new(RouterMockExecutor::new());
+    let redactor = Redactor::new(Vec::new());
+    let router = build_router(executor, redactor).await;
+
+    let input = json!({"command": "echo test", "timeout_secs": "soon"});
+    let result = router.execute("execute_command", &input).await;
+
+    assert!(result.is_error);
+    assert!(
+        result.content.contains("/timeout_secs:"),
+        "error should point at the bad field, got: {}",
+        result.content
+    );
+}
+
+#[test]
+fn uncompilable_schema_skips_validation() {
+    let schema = json!({"type": "no-such-type"});
+    assert!(wintermute::tools::validate_input("broken", &schema, &json!({"x": 1})).is_ok());
+}
+
 #[tokio::test]
 async fn output_is_redacted() {
     // Create an executor that returns output containing a known secret.
@@ -364,8 +419,8 @@ async fn tool_definitions_returns_core_plus_dynamic() {
 
     let defs = router.tool_definitions(10, None);
 
-    // Browser is hidden without a configured bridge: 9 visible core + 1 dynamic.
-    assert_eq!(defs.len(), 10, "should have 9 core + 1 dynamic tool");
+    // Browser is hidden without a configured bridge: 10 visible core + 1 dynamic.
+    assert_eq!(defs.len(), 13, "should have 12 core + 1 dynamic tool");
 
     let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
     assert!(
@@ -429,12 +484,12 @@ async fn tool_definitions_respects_max_dynamic_limit() {
         None,
     );
 
-    // max_dynamic = 1, so total should be 9 visible core + 1 dynamic = 10.
+    // max_dynamic = 1, so total should be 12 visible core + 1 dynamic = 13.
     let defs = router.tool_definitions(1, None);
     assert_eq!(
         defs.len(),
-        10,
-        "should have 9 core + at most 1 dynamic, got {}",
+        13,
+        "should have 12 core + at most 1 dynamic, got {}",
         defs.len()
     );
 }
@@ -492,7 +547,7 @@ async fn tool_definitions_with_query_prefers_relevant_dynamic_tool() {
     );
 
     let defs = router.tool_definitions(1, Some("weather forecast"));
-    assert_eq!(defs.len(), 10, "should have 9 core + 1 dynamic");
+    assert_eq!(defs.len(), 13, "should have 12 core + 1 dynamic");
     let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
     assert!(names.contains(&"weather_tool"));
     assert!(!names.contains(&"db_tool"));
@@ -534,3 +589,211 @@ async fn tool_definitions_include_browser_when_bridge_is_configured() {
         "browser should be visible when bridge is configured"
     );
 }
+
+#[tokio::test]
+async fn dynamic_tool_network_requires_grant() {
+    use wintermute::memory::TrustSource;
+
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let schema = json!({
+        "name": "weather",
+        "description": "Fetch the forecast",
+        "parameters": { "type": "object" },
+        "manifest": { "needs_network": ["api.weather.example"] }
+    });
+    std::fs::write(
+        dir.path().join("weather.json"),
+        serde_json::to_string_pretty(&schema).expect("serialize"),
+    )
+    .expect("write");
+    let registry = DynamicToolRegistry::new_without_watcher(dir.path().to_path_buf())
+        .expect("registry should initialise");
+
+    let memory = create_dummy_memory_engine().await;
+    let router = ToolRouter::new(
+        Arc::new(RouterMockExecutor::new()),
+        Redactor::new(Vec::new()),
+        Arc::clone(&memory),
+        registry,
+        None,
+        Arc::new(RateLimiter::new(60, 30)),
+        Arc::new(RateLimiter::new(60, 10)),
+        Arc::new(RateLimiter::new(60, 60)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let denied = router.execute("weather", &json!({})).await;
+    assert!(denied.is_error);
+    assert!(
+        denied.content.contains("not approved"),
+        "got: {}",
+        denied.content
+    );
+
+    memory
+        .grant_tool_network(
+            "weather",
+            &["api.weather.example".to_owned()],
+            TrustSource::User,
+        )
+        .await
+        .expect("grant should queue");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let allowed = router.execute("weather", &json!({})).await;
+    assert!(!allowed.is_error, "got: {}", allowed.content);
+    assert_eq!(allowed.content, "mock output");
+}
+
+#[tokio::test]
+async fn disabled_dynamic_tool_is_refused() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let schema = json!({
+        "name": "flaky",
+        "description": "Fails a lot",
+        "parameters": { "type": "object" },
+        "_meta": {
+            "created_at": "2026-03-01T00:00:00Z",
+            "last_used": null,
+            "invocations": 10,
+            "success_rate": 0.2,
+            "avg_duration_ms": 5,
+            "last_error": "boom",
+            "version": 1,
+            "disabled": { "at": "2026-03-02T00:00:00Z", "success_rate": 0.2, "window": 10 }
+        }
+    });
+    std::fs::write(
+        dir.path().join("flaky.json"),
+        serde_json::to_string_pretty(&schema).expect("serialize"),
+    )
+    .expect("write");
+    let registry = DynamicToolRegistry::new_without_watcher(dir.path().to_path_buf())
+        .expect("registry should initialise");
+
+    let router = ToolRouter::new(
+        Arc::new(RouterMockExecutor::new()),
+        Redactor::new(Vec::new()),
+        create_dummy_memory_engine().await,
+        Arc::clone(&registry),
+        None,
+        Arc::new(RateLimiter::new(60, 30)),
+        Arc::new(RateLimiter::new(60, 10)),
+        Arc::new(RateLimiter::new(60, 60)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let result = router.execute("flaky", &json!({})).await;
+    assert!(result.is_error);
+    assert!(
+        result.content.contains("disabled"),
+        "got: {}",
+        result.content
+    );
+    let meta = registry.get("flaky").and_then(|s| s.meta).expect("meta");
+    assert_eq!(meta.invocations, 10, "refused call must not be recorded");
+}
+
+struct UnavailableExecutor {
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl Executor for UnavailableExecutor {
+    async fn execute(
+        &self,
+        _command: &str,
+        _opts: ExecOptions,
+    ) -> Result<ExecResult, ExecutorError> {
+        Err(ExecutorError::Infrastructure(
+            "container is not running".to_owned(),
+        ))
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, ExecutorError> {
+        Ok(HealthStatus::Unavailable {
+            kind: ExecutorKind::Direct,
+            details: "down".to_owned(),
+        })
+    }
+
+    fn scripts_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn workspace_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn kind(&self) -> ExecutorKind {
+        ExecutorKind::Direct
+    }
+}
+
+#[tokio::test]
+async fn infrastructure_error_is_not_recorded_against_dynamic_tool() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let schema = json!({
+        "name": "steady",
+        "description": "Usually works",
+        "parameters": { "type": "object" }
+    });
+    std::fs::write(
+        dir.path().join("steady.json"),
+        serde_json::to_string_pretty(&schema).expect("serialize"),
+    )
+    .expect("write");
+    let registry = DynamicToolRegistry::new_without_watcher(dir.path().to_path_buf())
+        .expect("registry should initialise");
+
+    let router = ToolRouter::new(
+        Arc::new(UnavailableExecutor {
+            dir: dir.path().to_path_buf(),
+        }),
+        Redactor::new(Vec::new()),
+        create_dummy_memory_engine().await,
+        Arc::clone(&registry),
+        None,
+        Arc::new(RateLimiter::new(60, 30)),
+        Arc::new(RateLimiter::new(60, 10)),
+        Arc::new(RateLimiter::new(60, 60)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let result = router.execute("steady", &json!({})).await;
+    assert!(result.is_error);
+    assert!(
+        result.content.contains("container is not running"),
+        "got: {}",
+        result.content
+    );
+    let invocations = registry
+        .get("steady")
+        .and_then(|s| s.meta)
+        .map_or(0, |m| m.invocations);
+    assert_eq!(
+        invocations, 0,
+        "executor outage must not count as a failure"
+    );
+}
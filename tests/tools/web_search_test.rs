@@ -0,0 +1,142 @@
+//! Tests for `src/tools/web_search.rs` — backend response parsing and config.
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use wintermute::config::{SearchBackendKind, SearchConfig};
+use wintermute::credentials::Credentials;
+use wintermute::tools::web_search::{
+    format_results, parse_brave_json, parse_duckduckgo_html, parse_searxng_json, result_domain,
+    SearchResult, WebSearch, BRAVE_API_KEY,
+};
+
+const DDG_HTML: &str = r#"
+<div class="result results_links results_links_deep web-result">
+  <h2 class="result__title">
+    <a rel="nofollow" class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F&amp;rut=abc">Rust <b>Programming</b> Language</a>
+  </h2>
+  <a class="result__snippet" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F">A language empowering everyone to build reliable &amp; efficient software.</a>
+</div>
+<div class="result results_links results_links_deep web-result">
+  <h2 class="result__title">
+    <a rel="nofollow" class="result__a" href="https://docs.rs/">Docs.rs</a>
+  </h2>
+  <a class="result__snippet" href="https://docs.rs/">Documentation for crates.</a>
+</div>
+"#;
+
+#[test]
+fn parses_duckduckgo_html_results() {
+    let results = parse_duckduckgo_html(DDG_HTML);
+    assert_eq!(results.len(), 2);
+
+    assert_eq!(results[0].title, "Rust Programming Language");
+    assert_eq!(results[0].url, "https://www.rust-lang.org/");
+    assert_eq!(
+        results[0].snippet,
+        "A language empowering everyone to build reliable & efficient software."
+    );
+    assert!(!results[0].trusted);
+
+    assert_eq!(results[1].url, "https://docs.rs/");
+    assert_eq!(results[1].snippet, "Documentation for crates.");
+}
+
+#[test]
+fn duckduckgo_ad_links_are_skipped() {
+    let html = r#"<a class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fduckduckgo.com%2Fy.js%3Fad%3D1">Ad</a>"#;
+    assert!(parse_duckduckgo_html(html).is_empty());
+}
+
+#[test]
+fn parses_searxng_json_results() {
+    let body = json!({
+        "results": [
+            {"title": "Example", "url": "https://example.com/a", "content": "Snippet  text"},
+            {"title": "No URL"}
+        ]
+    });
+    let results = parse_searxng_json(&body);
+    assert_eq!(results.len(), 1, "results without a URL are dropped");
+    assert_eq!(results[0].url, "https://example.com/a");
+    assert_eq!(results[0].snippet, "Snippet text");
+}
+
+#[test]
+fn parses_brave_json_results_and_strips_markup() {
+    let body = json!({
+        "web": {"results": [
+            {"title": "Brave", "url": "https://brave.com/", "description": "The <strong>private</strong> browser"}
+        ]}
+    });
+    let results = parse_brave_json(&body);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].snippet, "The private browser");
+}
+
+#[test]
+fn unexpected_json_yields_no_results() {
+    assert!(parse_searxng_json(&json!({"error": "nope"})).is_empty());
+    assert!(parse_brave_json(&json!({"web": {}})).is_empty());
+}
+
+#[test]
+fn result_domain_extracts_host() {
+    assert_eq!(
+        result_domain("https://docs.rs/regex/latest").as_deref(),
+        Some("docs.rs")
+    );
+    assert_eq!(result_domain("not a url"), None);
+}
+
+#[test]
+fn format_results_includes_trust_flag() {
+    let results = vec![SearchResult {
+        title: "Docs".to_owned(),
+        url: "https://docs.rs/".to_owned(),
+        snippet: String::new(),
+        trusted: true,
+    }];
+    let parsed: serde_json::Value =
+        serde_json::from_str(&format_results("duckduckgo", "rust docs", &results))
+            .expect("output should be JSON");
+    assert_eq!(parsed["backend"], "duckduckgo");
+    assert_eq!(parsed["results"][0]["trusted"], true);
+}
+
+#[test]
+fn from_config_requires_backend_settings() {
+    let empty = Credentials::from_map(BTreeMap::new());
+
+    let searxng = SearchConfig {
+        backend: SearchBackendKind::Searxng,
+        searxng_url: None,
+        max_results: 5,
+    };
+    assert!(WebSearch::from_config(&searxng, &empty).is_err());
+
+    let brave = SearchConfig {
+        backend: SearchBackendKind::Brave,
+        searxng_url: None,
+        max_results: 5,
+    };
+    assert!(WebSearch::from_config(&brave, &empty).is_err());
+
+    let mut vars = BTreeMap::new();
+    vars.insert(BRAVE_API_KEY.to_owned(), "key".to_owned());
+    let creds = Credentials::from_map(vars);
+    let search = WebSearch::from_config(&brave, &creds).expect("brave with key");
+    assert_eq!(search.backend().name(), "brave");
+}
+
+#[test]
+fn default_search_config_uses_duckduckgo() {
+    let config = SearchConfig::default();
+    assert_eq!(config.backend, SearchBackendKind::DuckDuckGo);
+    assert_eq!(config.max_results, 8);
+
+    let parsed: SearchConfig =
+        toml::from_str("backend = \"searxng\"\nsearxng_url = \"http://127.0.0.1:8888\"")
+            .expect("search config should parse");
+    assert_eq!(parsed.backend, SearchBackendKind::Searxng);
+}
@@ -0,0 +1,6 @@
+//! Integration tests for `src/tui/`.
+
+#[path = "tui/app_test.rs"]
+mod app_test;
+#[path = "tui/ui_test.rs"]
+mod ui_test;
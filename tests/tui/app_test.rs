@@ -0,0 +1,124 @@
+//! Tests for `src/tui/app.rs`.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use wintermute::agent::TelegramOutbound;
+use wintermute::tui::app::{strip_html, Action, App, Speaker, MAX_CHAT_LINES};
+
+fn type_line(app: &mut App, line: &str) -> Option<Action> {
+    for c in line.chars() {
+        assert!(app
+            .handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+            .is_none());
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+}
+
+fn outbound(text: &str, approval: Option<(&str, &str)>) -> TelegramOutbound {
+    TelegramOutbound {
+        user_id: 1,
+        text: Some(text.to_owned()),
+        file_path: None,
+        approval_keyboard: approval.map(|(id, tool)| (id.to_owned(), tool.to_owned())),
+        keyboard: None,
+    }
+}
+
+#[test]
+fn enter_sends_trimmed_input_and_clears_line() {
+    let mut app = App::new();
+    let action = type_line(&mut app, "  hello  ");
+    assert_eq!(action, Some(Action::Send("hello".to_owned())));
+    assert!(app.input.is_empty());
+    let last = app.chat.last().expect("chat line");
+    assert_eq!(last.speaker, Speaker::User);
+    assert_eq!(last.text, "hello");
+
+    assert_eq!(type_line(&mut app, "   "), None);
+}
+
+#[test]
+fn backspace_and_quit_keys() {
+    let mut app = App::new();
+    app.handle_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    assert_eq!(app.input, "a");
+
+    assert_eq!(
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Some(Action::Quit)
+    );
+    assert_eq!(
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        Some(Action::Quit)
+    );
+    app.input.clear();
+    assert_eq!(type_line(&mut app, "/quit"), Some(Action::Quit));
+}
+
+#[test]
+fn approvals_resolve_latest_or_by_id() {
+    let mut app = App::new();
+    app.push_outbound(&outbound(
+        "Tool <b>a</b> needs approval",
+        Some(("id1", "a")),
+    ));
+    app.push_outbound(&outbound(
+        "Tool <b>b</b> needs approval",
+        Some(("id2", "b")),
+    ));
+    assert_eq!(app.pending.len(), 2);
+
+    assert_eq!(
+        type_line(&mut app, "/deny id1"),
+        Some(Action::Resolve {
+            approval_id: "id1".to_owned(),
+            approved: false
+        })
+    );
+    assert_eq!(
+        type_line(&mut app, "/approve"),
+        Some(Action::Resolve {
+            approval_id: "id2".to_owned(),
+            approved: true
+        })
+    );
+    assert_eq!(type_line(&mut app, "/approve"), None);
+    let last = app.chat.last().expect("chat line");
+    assert!(last.text.contains("No matching approval"));
+}
+
+#[test]
+fn outbound_html_is_rendered_as_plain_text() {
+    let mut app = App::new();
+    app.push_outbound(&outbound("<b>Hi</b> &lt;there&gt; &amp; you", None));
+    let last = app.chat.last().expect("chat line");
+    assert_eq!(last.speaker, Speaker::Agent);
+    assert_eq!(last.text, "Hi <there> & you");
+    assert_eq!(strip_html("<pre>a &amp;lt; b</pre>"), "a &lt; b");
+}
+
+#[test]
+fn chat_history_is_bounded() {
+    let mut app = App::new();
+    for i in 0..MAX_CHAT_LINES + 10 {
+        app.push(Speaker::System, i.to_string());
+    }
+    assert_eq!(app.chat.len(), MAX_CHAT_LINES);
+    assert_eq!(
+        app.chat.last().map(|l| l.text.clone()),
+        Some((MAX_CHAT_LINES + 9).to_string())
+    );
+}
+
+#[test]
+fn budget_ratio_is_clamped() {
+    let mut app = App::new();
+    assert_eq!(app.budget_ratio(), 0.0);
+    app.budget_limit = 100;
+    app.budget_used = 25;
+    assert!((app.budget_ratio() - 0.25).abs() < f64::EPSILON);
+    app.budget_used = 500;
+    assert_eq!(app.budget_ratio(), 1.0);
+}
@@ -0,0 +1,39 @@
+//! Tests for `src/tui/ui.rs`.
+
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use wintermute::tui::app::{App, Speaker};
+use wintermute::tui::ui::draw;
+
+#[test]
+fn draw_renders_all_panes() {
+    let mut app = App::new();
+    app.sessions = vec!["user_42".to_owned()];
+    app.budget_used = 10;
+    app.budget_limit = 100;
+    app.push(Speaker::Agent, "hello from the agent");
+    app.input = "typing".to_owned();
+    let logs = vec!["INFO agent turn finished".to_owned()];
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).expect("terminal");
+    terminal
+        .draw(|frame| draw(frame, &app, &logs))
+        .expect("draw");
+
+    let buffer = terminal.backend().buffer();
+    let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+    for needle in [
+        "Sessions",
+        "user_42",
+        "Budget",
+        "10/100",
+        "Chat",
+        "hello from the agent",
+        "Logs",
+        "agent turn finished",
+        "> typing",
+    ] {
+        assert!(text.contains(needle), "missing {needle:?}");
+    }
+}
@@ -0,0 +1,6 @@
+//! Integration tests for `src/whatsapp/`.
+
+#[path = "whatsapp/media_test.rs"]
+mod media_test;
+#[path = "whatsapp/reconnect_test.rs"]
+mod reconnect_test;
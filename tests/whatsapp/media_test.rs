@@ -0,0 +1,166 @@
+//! Tests for `src/whatsapp/media.rs` — inbox filenames, descriptions, and
+//! outbound file preparation.
+//!
+//! Note: `save_incoming` requires a running WhatsApp bridge.
+
+use std::path::Path;
+
+use wintermute::whatsapp::events::{MediaKind, WhatsAppEvent, WhatsAppMedia};
+use wintermute::whatsapp::media::{
+    describe, incoming_filename, load_outgoing, mime_for_path, MAX_OUTGOING_MEDIA_BYTES,
+};
+use wintermute::whatsapp::WhatsAppError;
+
+fn media(kind: MediaKind, mime_type: Option<&str>) -> WhatsAppMedia {
+    WhatsAppMedia {
+        kind,
+        mime_type: mime_type.map(str::to_owned),
+        file_name: None,
+        duration_secs: None,
+    }
+}
+
+#[test]
+fn message_event_parses_media_metadata() {
+    let json = r#"{
+        "type": "message",
+        "jid": "4915112345678@s.whatsapp.net",
+        "from_me": false,
+        "message_id": "ABC123",
+        "media": {"kind": "voice", "mime_type": "audio/ogg; codecs=opus", "duration_secs": 7}
+    }"#;
+    let event: WhatsAppEvent = serde_json::from_str(json).expect("event should parse");
+    match event {
+        WhatsAppEvent::Message { text, media, .. } => {
+            assert!(text.is_empty(), "missing text should default to empty");
+            let media = media.expect("media should be present");
+            assert_eq!(media.kind, MediaKind::Voice);
+            assert_eq!(media.duration_secs, Some(7));
+        }
+        other => panic!("expected message event, got {other:?}"),
+    }
+}
+
+#[test]
+fn message_event_without_media_still_parses() {
+    let json = r#"{"type": "message", "jid": "x@s.whatsapp.net", "text": "hi", "from_me": false}"#;
+    let event: WhatsAppEvent = serde_json::from_str(json).expect("event should parse");
+    assert!(matches!(event, WhatsAppEvent::Message { media: None, .. }));
+}
+
+#[test]
+fn unknown_media_kind_maps_to_other() {
+    let parsed: WhatsAppMedia =
+        serde_json::from_str(r#"{"kind": "sticker"}"#).expect("media should parse");
+    assert_eq!(parsed.kind, MediaKind::Other);
+}
+
+#[test]
+fn incoming_filename_uses_kind_and_mime() {
+    let ts = "20260223_101500";
+    assert_eq!(
+        incoming_filename(&media(MediaKind::Voice, Some("audio/ogg; codecs=opus")), ts),
+        "wa_voice_20260223_101500.ogg"
+    );
+    assert_eq!(
+        incoming_filename(&media(MediaKind::Image, Some("image/png")), ts),
+        "wa_photo_20260223_101500.png"
+    );
+    assert_eq!(
+        incoming_filename(&media(MediaKind::Image, None), ts),
+        "wa_photo_20260223_101500.jpg"
+    );
+}
+
+#[test]
+fn incoming_document_keeps_sanitized_name() {
+    let mut doc = media(MediaKind::Document, Some("application/pdf"));
+    doc.file_name = Some("../../quote.pdf".to_owned());
+    assert_eq!(incoming_filename(&doc, "ts"), "_.._quote.pdf");
+
+    doc.file_name = None;
+    assert_eq!(incoming_filename(&doc, "ts"), "wa_doc_ts");
+}
+
+#[test]
+fn describe_matches_telegram_shapes() {
+    let mut voice = media(MediaKind::Voice, None);
+    voice.duration_secs = Some(12);
+    assert_eq!(
+        describe(&voice, Path::new("/inbox/v.ogg"), ""),
+        "[Voice message: /inbox/v.ogg, 12s]"
+    );
+    assert_eq!(
+        describe(
+            &media(MediaKind::Image, None),
+            Path::new("/inbox/p.jpg"),
+            ""
+        ),
+        "[Photo: /inbox/p.jpg]"
+    );
+    assert_eq!(
+        describe(
+            &media(MediaKind::Document, None),
+            Path::new("/inbox/d.pdf"),
+            ""
+        ),
+        "[Document: /inbox/d.pdf]"
+    );
+}
+
+#[test]
+fn describe_appends_caption() {
+    let text = describe(
+        &media(MediaKind::Image, None),
+        Path::new("/inbox/p.jpg"),
+        "  the invoice  ",
+    );
+    assert_eq!(text, "[Photo: /inbox/p.jpg]\nthe invoice");
+}
+
+#[test]
+fn mime_for_path_is_case_insensitive() {
+    assert_eq!(mime_for_path(Path::new("a.JPG")), "image/jpeg");
+    assert_eq!(
+        mime_for_path(Path::new("note.ogg")),
+        "audio/ogg; codecs=opus"
+    );
+    assert_eq!(mime_for_path(Path::new("blob")), "application/octet-stream");
+}
+
+#[tokio::test]
+async fn load_outgoing_reads_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("report.pdf");
+    std::fs::write(&path, b"%PDF-1.4").expect("write file");
+
+    let media = load_outgoing(&path, Some("here you go".to_owned()), false)
+        .await
+        .expect("file should load");
+    assert_eq!(media.file_name, "report.pdf");
+    assert_eq!(media.mime_type, "application/pdf");
+    assert_eq!(media.caption.as_deref(), Some("here you go"));
+    assert!(!media.voice);
+}
+
+#[tokio::test]
+async fn load_outgoing_rejects_non_audio_voice_note() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("photo.jpg");
+    std::fs::write(&path, b"jpeg").expect("write file");
+
+    let result = load_outgoing(&path, None, true).await;
+    assert!(matches!(result, Err(WhatsAppError::InvalidMedia(_))));
+}
+
+#[tokio::test]
+async fn load_outgoing_rejects_oversized_file() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("big.mp4");
+    let file = std::fs::File::create(&path).expect("create file");
+    file.set_len(MAX_OUTGOING_MEDIA_BYTES.saturating_add(1))
+        .expect("extend file");
+
+    let result = load_outgoing(&path, None, false).await;
+    assert!(matches!(result, Err(WhatsAppError::InvalidMedia(_))));
+}
@@ -0,0 +1,80 @@
+//! Tests for `src/whatsapp/reconnect.rs` — backoff, logged-out detection, and
+//! QR decoding.
+//!
+//! Note: the supervisor loop itself requires a running WhatsApp bridge.
+
+use std::time::Duration;
+
+use base64::Engine;
+use wintermute::whatsapp::events::WhatsAppEvent;
+use wintermute::whatsapp::reconnect::{decode_qr, is_logged_out, Backoff};
+
+#[test]
+fn backoff_doubles_up_to_ceiling() {
+    let mut backoff = Backoff::new(1_000, 5_000);
+    let delays: Vec<Duration> = (0..5).map(|_| backoff.next_delay()).collect();
+    assert_eq!(
+        delays,
+        vec![
+            Duration::from_millis(1_000),
+            Duration::from_millis(2_000),
+            Duration::from_millis(4_000),
+            Duration::from_millis(5_000),
+            Duration::from_millis(5_000),
+        ]
+    );
+}
+
+#[test]
+fn backoff_reset_starts_over() {
+    let mut backoff = Backoff::new(1_000, 5_000);
+    backoff.next_delay();
+    backoff.next_delay();
+    backoff.reset();
+    assert_eq!(backoff.next_delay(), Duration::from_millis(1_000));
+}
+
+#[test]
+fn logged_out_reasons_are_detected() {
+    assert!(is_logged_out(Some("Connection Failure: logged out")));
+    assert!(is_logged_out(Some("loggedOut")));
+    assert!(is_logged_out(Some("stream errored (401)")));
+    assert!(!is_logged_out(Some("connection closed")));
+    assert!(!is_logged_out(None));
+}
+
+#[test]
+fn disconnected_event_parses_logged_out_flag() {
+    let json = r#"{"type": "disconnected", "reason": "device removed", "logged_out": true}"#;
+    let event: WhatsAppEvent = serde_json::from_str(json).expect("event should parse");
+    assert!(matches!(
+        event,
+        WhatsAppEvent::Disconnected {
+            logged_out: true,
+            ..
+        }
+    ));
+
+    let legacy: WhatsAppEvent = serde_json::from_str(r#"{"type": "disconnected", "reason": null}"#)
+        .expect("event should parse");
+    assert!(matches!(
+        legacy,
+        WhatsAppEvent::Disconnected {
+            logged_out: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn decode_qr_accepts_plain_and_data_url() {
+    let png = b"\x89PNG\r\n";
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+
+    assert_eq!(decode_qr(&encoded).as_deref(), Some(&png[..]));
+    assert_eq!(
+        decode_qr(&format!("data:image/png;base64,{encoded}")).as_deref(),
+        Some(&png[..])
+    );
+    assert!(decode_qr("not base64!").is_none());
+}